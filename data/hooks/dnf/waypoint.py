@@ -0,0 +1,35 @@
+# Snapshot the system before and after every dnf transaction
+#
+# A snapshot that can't be created never blocks the transaction.
+
+import subprocess
+import sys
+
+import dnf
+
+WAYPOINT_CLI = "/usr/bin/waypoint-cli"
+
+
+class Waypoint(dnf.Plugin):
+    name = "waypoint"
+
+    def _snapshot(self, phase):
+        transaction = self.base.transaction
+        targets = sorted({pkg.name for pkg in transaction.install_set | transaction.remove_set})
+        # The hook runs inside dnf's Python process, so name the command here
+        context = " ".join(["dnf"] + sys.argv[1:])
+        try:
+            subprocess.run(
+                [WAYPOINT_CLI, "create", "--%s-transaction" % phase, "--targets-from-stdin", context],
+                input="\n".join(targets),
+                universal_newlines=True,
+                check=False,
+            )
+        except OSError as e:
+            print("waypoint: skipped %s-transaction snapshot: %s" % (phase, e), file=sys.stderr)
+
+    def pre_transaction(self):
+        self._snapshot("pre")
+
+    def transaction(self):
+        self._snapshot("post")
@@ -0,0 +1,26 @@
+#!/usr/bin/env bash
+# Regenerate po/waypoint.pot from the files listed in po/POTFILES
+# and merge new strings into existing translations.
+set -euo pipefail
+
+cd "$(dirname -- "$0")/.."
+
+# Rust sources need xgettext 0.24 or newer
+xgettext \
+    --language=Rust \
+    --from-code=UTF-8 \
+    --keyword= \
+    --keyword=gettext \
+    --keyword=gettext_f \
+    --keyword=gettext_noop \
+    --keyword=ngettext:1,2 \
+    --keyword=ngettext_f:1,2 \
+    --add-comments=Translators \
+    --package-name=waypoint \
+    --files-from=po/POTFILES \
+    --output=po/waypoint.pot
+
+while read -r lang; do
+    [[ -z "$lang" || "$lang" == \#* ]] && continue
+    msgmerge --update --backup=none "po/${lang}.po" po/waypoint.pot
+done < po/LINGUAS
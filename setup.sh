@@ -21,6 +21,7 @@ install_dependencies() {
         pkg-config
         polkit-devel
         dbus-devel
+        gettext
         rsync
     )
 
@@ -238,6 +239,40 @@ install_icons() {
     fi
 }
 
+install_translations() {
+    local po_dir="po"
+    local languages=()
+
+    if [[ -f "$po_dir/LINGUAS" ]]; then
+        while read -r lang; do
+            [[ -z "$lang" || "$lang" == \#* ]] && continue
+            languages+=("$lang")
+        done < "$po_dir/LINGUAS"
+    fi
+
+    if (( ${#languages[@]} == 0 )); then
+        return
+    fi
+
+    if ! command -v msgfmt >/dev/null 2>&1; then
+        echo "msgfmt not found; skipping translations"
+        return
+    fi
+
+    echo "Installing translations..."
+    for lang in "${languages[@]}"; do
+        local mo_file
+        mo_file=$(mktemp)
+        if msgfmt --check -o "$mo_file" "$po_dir/${lang}.po"; then
+            echo " → Installing translation: $lang"
+            sudo install -D -m644 "$mo_file" "${DATADIR}/locale/${lang}/LC_MESSAGES/waypoint.mo"
+        else
+            echo " ⚠ Invalid translation skipped: $lang"
+        fi
+        rm -f "$mo_file"
+    done
+}
+
 install_scheduler_service() {
     if [[ ! -f "services/waypoint-scheduler/run" ]]; then
         echo "Scheduler service script missing"
@@ -288,6 +323,39 @@ install_scheduler_service() {
     echo " ℹ To disable scheduler: sudo rm /var/service/waypoint-scheduler"
 }
 
+install_package_hooks() {
+    # Pre/post-transaction snapshots for package managers that support hooks.
+    # xbps has no transaction hooks, so Void Linux relies on scheduled snapshots.
+    if [[ -d /usr/share/libalpm/hooks ]]; then
+        echo "Installing pacman hooks..."
+        local hook
+        for hook in data/hooks/pacman/*.hook; do
+            sed "s|/usr/bin/waypoint-cli|${BINDIR}/waypoint-cli|" "$hook" \
+                | sudo install -D -m644 /dev/stdin "/usr/share/libalpm/hooks/$(basename "$hook")"
+        done
+    fi
+
+    if [[ -d /etc/apt/apt.conf.d ]]; then
+        echo "Installing apt hook..."
+        sed "s|/usr/bin/waypoint-cli|${BINDIR}/waypoint-cli|g" data/hooks/apt/80waypoint \
+            | sudo install -D -m644 /dev/stdin /etc/apt/apt.conf.d/80waypoint
+    fi
+
+    local dnf_plugins
+    dnf_plugins=$(dnf_plugin_dir)
+    if [[ -n "$dnf_plugins" ]]; then
+        echo "Installing dnf plugin..."
+        sed "s|/usr/bin/waypoint-cli|${BINDIR}/waypoint-cli|" data/hooks/dnf/waypoint.py \
+            | sudo install -D -m644 /dev/stdin "${dnf_plugins}/waypoint.py"
+        sudo install -D -m644 data/hooks/dnf/waypoint.conf /etc/dnf/plugins/waypoint.conf
+    fi
+}
+
+# Plugin directory of dnf 4, empty if dnf isn't installed
+dnf_plugin_dir() {
+    python3 -c 'import dnf.const; print(dnf.const.PLUGINPATH)' 2>/dev/null || true
+}
+
 reload_dbus() {
     echo "Reloading system services..."
 
@@ -443,6 +511,27 @@ uninstall_icons() {
     fi
 }
 
+uninstall_translations() {
+    echo "Removing translations..."
+    sudo find "${DATADIR}/locale" -path '*/LC_MESSAGES/waypoint.mo' -delete 2>/dev/null || true
+}
+
+uninstall_package_hooks() {
+    local dnf_plugins
+    dnf_plugins=$(dnf_plugin_dir)
+    local hook
+    for hook in /usr/share/libalpm/hooks/05-waypoint-pre.hook \
+        /usr/share/libalpm/hooks/zz-waypoint-post.hook \
+        /etc/apt/apt.conf.d/80waypoint \
+        ${dnf_plugins:+"${dnf_plugins}/waypoint.py"} \
+        /etc/dnf/plugins/waypoint.conf; do
+        if [[ -f "$hook" ]]; then
+            echo " → Removing $hook"
+            sudo rm -f "$hook"
+        fi
+    done
+}
+
 uninstall_scheduler_service() {
     echo "Removing scheduler service..."
 
@@ -486,10 +575,12 @@ case "$ACTION" in
         install_binaries
         install_desktop_entry
         install_icons
+        install_translations
         install_polkit_policy
         install_polkit_rules
         install_dbus_service
         install_scheduler_service
+        install_package_hooks
         create_metadata_dir
         reload_dbus
         clean_build_artifacts
@@ -513,10 +604,12 @@ case "$ACTION" in
         uninstall_binaries
         uninstall_desktop_entry
         uninstall_icons
+        uninstall_translations
         uninstall_polkit_policy
         uninstall_polkit_rules
         uninstall_dbus_service
         uninstall_scheduler_service
+        uninstall_package_hooks
         reload_dbus
         echo
         echo "✓ Uninstallation complete!"
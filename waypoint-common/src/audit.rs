@@ -0,0 +1,77 @@
+//! Structured audit events shared between the helper and the GUI
+//!
+//! The helper writes one JSON-encoded `AuditEvent` per line to the audit log file
+//! (in addition to the `audit` log target). The GUI reads them back for display.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Audit log entry for security-relevant events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEvent {
+    /// RFC 3339 timestamp
+    pub timestamp: String,
+    /// UID of the D-Bus caller
+    pub user_id: String,
+    /// Resolved user name, if available
+    pub user_name: Option<String>,
+    /// PID of the D-Bus caller
+    pub process_id: u32,
+    /// Operation name (e.g. "create_snapshot", "modify_configuration")
+    pub operation: String,
+    /// Affected resource (snapshot name, config type, ...)
+    pub resource: String,
+    /// "success", "failure" or "denied"
+    pub result: String,
+    /// Extra details such as error messages
+    pub details: Option<String>,
+}
+
+impl AuditEvent {
+    /// Whether the audited operation succeeded
+    pub fn is_success(&self) -> bool {
+        self.result == "success"
+    }
+
+    /// User name if known, otherwise the numeric UID
+    pub fn user_display(&self) -> &str {
+        self.user_name.as_deref().unwrap_or(&self.user_id)
+    }
+}
+
+/// Location of the persistent audit log (JSON lines)
+pub fn audit_log_path() -> PathBuf {
+    PathBuf::from("/var/log/waypoint/audit.log")
+}
+
+/// Parse audit log content, skipping lines that aren't valid events
+///
+/// Events are returned in file order (oldest first).
+pub fn parse_audit_log(content: &str) -> Vec<AuditEvent> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_audit_log() {
+        let content = r#"{"timestamp":"2025-01-01T10:00:00+00:00","user_id":"1000","user_name":"alice","process_id":42,"operation":"create_snapshot","resource":"waypoint-1","result":"success","details":null}
+not json
+{"timestamp":"2025-01-01T11:00:00+00:00","user_id":"1001","user_name":null,"process_id":43,"operation":"delete_snapshot","resource":"waypoint-1","result":"failure","details":"error: busy"}
+"#;
+
+        let events = parse_audit_log(content);
+        assert_eq!(events.len(), 2);
+        assert!(events[0].is_success());
+        assert_eq!(events[0].user_display(), "alice");
+        assert!(!events[1].is_success());
+        assert_eq!(events[1].user_display(), "1001");
+        assert_eq!(events[1].details.as_deref(), Some("error: busy"));
+    }
+}
@@ -5,7 +5,7 @@
 use anyhow::Context;
 use chrono::{Duration, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 
 use crate::SnapshotInfo;
@@ -137,6 +137,44 @@ impl BackupFilter {
     }
 }
 
+/// Events that can start an automatic backup to a destination
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackupTrigger {
+    /// A new snapshot was created
+    SnapshotCreation,
+    /// The destination drive was mounted
+    DriveMount,
+    /// The destination's backup interval elapsed
+    Schedule,
+    /// A snapshot was marked as favorite
+    Favorite,
+}
+
+/// How snapshots are written to a backup destination
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupFormat {
+    /// btrfs send/receive on btrfs drives, rsync elsewhere
+    #[default]
+    Native,
+    /// A self-contained btrfs image file per snapshot, for archiving or
+    /// burning to optical media
+    Image,
+}
+
+/// What to do when a backup doesn't fit on its destination
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FullDestinationAction {
+    /// Leave the backups queued and notify the user
+    #[default]
+    Skip,
+    /// Ask the user whether to delete old backups to make room
+    Ask,
+    /// Delete the oldest backups until the new one fits
+    Prune,
+}
+
 /// Configuration for a single backup destination
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackupDestinationConfig {
@@ -174,16 +212,62 @@ pub struct BackupDestinationConfig {
     #[serde(default = "default_true")]
     pub on_drive_mount: bool,
 
+    /// Backup when a snapshot is marked as favorite
+    #[serde(default)]
+    pub on_favorite: bool,
+
+    /// Backup periodically while the drive is mounted
+    #[serde(default)]
+    pub on_schedule: bool,
+
+    /// Hours between scheduled backups
+    #[serde(default = "default_schedule_interval_hours")]
+    pub schedule_interval_hours: u32,
+
     /// Retention days (optional, None means keep all backups)
     #[serde(default)]
     pub retention_days: Option<u32>,
+
+    /// Verify each backup after transfer (None: only on removable drives)
+    #[serde(default)]
+    pub verify_after_backup: Option<bool>,
+
+    /// How backups are written to this destination
+    #[serde(default)]
+    pub format: BackupFormat,
+
+    /// What to do when the destination is too full for a backup
+    #[serde(default)]
+    pub when_full: FullDestinationAction,
 }
 
 fn default_true() -> bool {
     true
 }
 
+fn default_schedule_interval_hours() -> u32 {
+    24
+}
+
 impl BackupDestinationConfig {
+    /// Whether this destination should back up in response to `trigger`
+    pub fn triggers_on(&self, trigger: BackupTrigger) -> bool {
+        match trigger {
+            BackupTrigger::SnapshotCreation => self.on_snapshot_creation,
+            BackupTrigger::DriveMount => self.on_drive_mount,
+            BackupTrigger::Schedule => self.on_schedule,
+            BackupTrigger::Favorite => self.on_favorite,
+        }
+    }
+
+    /// Whether backups to this destination are verified after transfer
+    ///
+    /// Unless set explicitly, removable drives are verified and others aren't,
+    /// since USB drives and cables are the likeliest to corrupt a transfer.
+    pub fn verifies_after_backup(&self, removable: bool) -> bool {
+        self.verify_after_backup.unwrap_or(removable)
+    }
+
     /// Get the display name for this destination
     /// Priority: nickname → label → last_mount_point
     pub fn display_name(&self) -> &str {
@@ -203,6 +287,47 @@ impl BackupDestinationConfig {
     }
 }
 
+/// Attempts after which a failing backup stops being retried automatically
+pub const MAX_BACKUP_ATTEMPTS: u32 = 5;
+
+/// Delay before the first automatic retry (seconds); doubles with each attempt
+const RETRY_BASE_DELAY_SECS: i64 = 60;
+
+/// Upper bound on the delay between automatic retries (seconds)
+const RETRY_MAX_DELAY_SECS: i64 = 6 * 3600;
+
+/// Rules deciding whether a snapshot may be backed up automatically at all
+///
+/// Applied before any destination filter. An explicit exclusion always wins; otherwise
+/// favorites are always eligible and other snapshots follow their schedule's
+/// auto-backup setting (manual snapshots are eligible).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackupEligibility {
+    /// Whether the snapshot is marked as a favorite
+    pub is_favorite: bool,
+    /// Whether the user excluded this snapshot from backups
+    pub excluded: bool,
+    /// Whether the schedule that created the snapshot allows automatic backups
+    pub schedule_allows_backup: bool,
+}
+
+impl Default for BackupEligibility {
+    fn default() -> Self {
+        Self {
+            is_favorite: false,
+            excluded: false,
+            schedule_allows_backup: true,
+        }
+    }
+}
+
+impl BackupEligibility {
+    /// Whether the snapshot may be queued for automatic backup
+    pub fn is_eligible(&self) -> bool {
+        !self.excluded && (self.is_favorite || self.schedule_allows_backup)
+    }
+}
+
 /// Status of a pending backup
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -213,7 +338,7 @@ pub enum BackupStatus {
     InProgress,
     /// Successfully backed up
     Completed,
-    /// Failed with error
+    /// Failed permanently after `MAX_BACKUP_ATTEMPTS` attempts; needs a manual retry
     Failed,
 }
 
@@ -243,9 +368,35 @@ pub struct PendingBackup {
     /// Timestamp of last attempt (Unix timestamp)
     #[serde(default)]
     pub last_attempt: Option<i64>,
+
+    /// Earliest time the next automatic attempt may run (Unix timestamp)
+    #[serde(default)]
+    pub next_retry_at: Option<i64>,
+}
+
+impl PendingBackup {
+    /// Whether this backup may be attempted at `now` (Unix timestamp)
+    pub fn is_due(&self, now: i64) -> bool {
+        self.status == BackupStatus::Pending && self.next_retry_at.is_none_or(|at| now >= at)
+    }
+
+    /// Whether a previous attempt failed and the backup is waiting to be retried
+    pub fn is_retrying(&self) -> bool {
+        self.status == BackupStatus::Pending && self.retry_count > 0
+    }
+}
+
+/// Backoff before retry number `attempt` (1-based): 1 min, 2 min, 4 min, ... capped at 6 hours
+fn retry_delay_secs(attempt: u32) -> i64 {
+    let exponent = attempt.saturating_sub(1).min(16);
+    (RETRY_BASE_DELAY_SECS << exponent).min(RETRY_MAX_DELAY_SECS)
 }
 
 /// Record of a completed backup
+///
+/// History holds at most one record per snapshot, destination and backup path,
+/// so a snapshot maps to the set of backups that exist for it. Deleting one of
+/// them removes only that record.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackupRecord {
     /// Snapshot ID that was backed up
@@ -271,6 +422,10 @@ pub struct BackupRecord {
     /// Parent snapshot ID if incremental
     #[serde(default)]
     pub parent_snapshot_id: Option<String>,
+
+    /// Result of the verification after transfer, None if it wasn't verified
+    #[serde(default)]
+    pub verified: Option<bool>,
 }
 
 /// Main backup configuration and state
@@ -331,7 +486,8 @@ impl BackupConfig {
         }
 
         let contents = std::fs::read_to_string(path)?;
-        let config: Self = toml::from_str(&contents)?;
+        let mut config: Self = toml::from_str(&contents)?;
+        config.dedup_history();
         Ok(config)
     }
 
@@ -398,6 +554,7 @@ impl BackupConfig {
             retry_count: 0,
             last_error: None,
             last_attempt: None,
+            next_retry_at: None,
         };
 
         self.pending_backups.push(pending);
@@ -411,6 +568,16 @@ impl BackupConfig {
             .collect()
     }
 
+    /// Get pending backups for a destination that may be attempted at `now`
+    ///
+    /// Backups waiting out their retry backoff are left out.
+    pub fn due_for_destination(&self, uuid: &str, now: i64) -> Vec<&PendingBackup> {
+        self.pending_backups
+            .iter()
+            .filter(|pb| pb.destination_uuid == uuid && pb.is_due(now))
+            .collect()
+    }
+
     /// Mark a backup as completed
     pub fn mark_completed(
         &mut self,
@@ -426,6 +593,13 @@ impl BackupConfig {
             !(pb.snapshot_id == snapshot_id && pb.destination_uuid == destination_uuid)
         });
 
+        // Replace any earlier record of the same backup
+        self.backup_history.retain(|record| {
+            !(record.snapshot_id == snapshot_id
+                && record.destination_uuid == destination_uuid
+                && record.backup_path == backup_path)
+        });
+
         // Add to history
         let record = BackupRecord {
             snapshot_id: snapshot_id.to_string(),
@@ -438,31 +612,72 @@ impl BackupConfig {
             size_bytes,
             is_incremental,
             parent_snapshot_id,
+            verified: None,
         };
 
         self.backup_history.push(record);
     }
 
-    /// Mark a backup as failed
+    /// Record the result of verifying a completed backup
+    pub fn set_verified(&mut self, snapshot_id: &str, destination_uuid: &str, verified: bool) {
+        if let Some(record) = self
+            .backup_history
+            .iter_mut()
+            .filter(|r| r.snapshot_id == snapshot_id && r.destination_uuid == destination_uuid)
+            .max_by_key(|r| r.completed_at)
+        {
+            record.verified = Some(verified);
+        }
+    }
+
+    /// Snapshots whose backup to a destination failed verification since `since` (Unix timestamp)
+    pub fn failed_verifications(&self, destination_uuid: &str, since: i64) -> Vec<String> {
+        self.backup_history
+            .iter()
+            .filter(|r| {
+                r.destination_uuid == destination_uuid
+                    && r.completed_at >= since
+                    && r.verified == Some(false)
+            })
+            .map(|r| r.snapshot_id.clone())
+            .collect()
+    }
+
+    /// Record a failed backup attempt
+    ///
+    /// The backup stays queued and is retried with exponential backoff until
+    /// `MAX_BACKUP_ATTEMPTS` is reached, after which it is marked as failed.
     pub fn mark_failed(&mut self, snapshot_id: &str, destination_uuid: &str, error: String) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        self.record_failure(snapshot_id, destination_uuid, error, now);
+    }
+
+    fn record_failure(&mut self, snapshot_id: &str, destination_uuid: &str, error: String, now: i64) {
         if let Some(pending) = self
             .pending_backups
             .iter_mut()
             .find(|pb| pb.snapshot_id == snapshot_id && pb.destination_uuid == destination_uuid)
         {
-            pending.status = BackupStatus::Failed;
             pending.last_error = Some(error);
             pending.retry_count += 1;
-            pending.last_attempt = Some(
-                std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs() as i64,
-            );
+            pending.last_attempt = Some(now);
+
+            if pending.retry_count >= MAX_BACKUP_ATTEMPTS {
+                pending.status = BackupStatus::Failed;
+                pending.next_retry_at = None;
+            } else {
+                pending.status = BackupStatus::Pending;
+                pending.next_retry_at = Some(now + retry_delay_secs(pending.retry_count));
+            }
         }
     }
 
     /// Reset a failed backup to pending (for retry)
+    ///
+    /// A manual retry starts over with a fresh attempt budget.
     pub fn retry_backup(&mut self, snapshot_id: &str, destination_uuid: &str) {
         if let Some(pending) = self
             .pending_backups
@@ -470,6 +685,8 @@ impl BackupConfig {
             .find(|pb| pb.snapshot_id == snapshot_id && pb.destination_uuid == destination_uuid)
         {
             pending.status = BackupStatus::Pending;
+            pending.retry_count = 0;
+            pending.next_retry_at = None;
         }
     }
 
@@ -488,6 +705,42 @@ impl BackupConfig {
             .max_by_key(|r| r.completed_at)
     }
 
+    /// Oldest backup on a destination that may be deleted to make room at `now` (Unix timestamp)
+    ///
+    /// Backups within the destination's retention period are kept, and so is its
+    /// latest backup, the parent of the next incremental one.
+    pub fn prune_candidate(&self, destination_uuid: &str, now: i64) -> Option<&BackupRecord> {
+        let retention_secs = self
+            .get_destination(destination_uuid)?
+            .retention_days
+            .map_or(0, |days| i64::from(days) * 86400);
+        let latest = self.get_latest_backup(destination_uuid)?;
+
+        self.backup_history
+            .iter()
+            .filter(|r| r.destination_uuid == destination_uuid)
+            .filter(|r| r.backup_path != latest.backup_path)
+            .filter(|r| now - r.completed_at >= retention_secs)
+            .min_by_key(|r| r.completed_at)
+    }
+
+    /// Check whether a scheduled backup to a destination is due at `now` (Unix timestamp)
+    ///
+    /// Due when scheduled backups are enabled and no backup completed within the
+    /// destination's interval.
+    pub fn is_scheduled_backup_due(&self, destination_uuid: &str, now: i64) -> bool {
+        let Some(dest) = self.get_destination(destination_uuid) else {
+            return false;
+        };
+        if !dest.enabled || !dest.on_schedule {
+            return false;
+        }
+
+        let interval_secs = i64::from(dest.schedule_interval_hours.max(1)) * 3600;
+        self.get_latest_backup(destination_uuid)
+            .is_none_or(|record| now - record.completed_at >= interval_secs)
+    }
+
     /// Get backup history for a snapshot
     pub fn get_snapshot_backups(&self, snapshot_id: &str) -> Vec<&BackupRecord> {
         self.backup_history
@@ -495,6 +748,65 @@ impl BackupConfig {
             .filter(|r| r.snapshot_id == snapshot_id)
             .collect()
     }
+
+    /// Backups of a snapshot on destinations that are still configured, by destination UUID
+    ///
+    /// A destination can hold more than one backup of the same snapshot, e.g.
+    /// after it was moved to another directory on the drive.
+    pub fn snapshot_backups_by_destination(
+        &self,
+        snapshot_id: &str,
+    ) -> BTreeMap<&str, Vec<&BackupRecord>> {
+        let mut backups: BTreeMap<&str, Vec<&BackupRecord>> = BTreeMap::new();
+        for record in self.get_snapshot_backups(snapshot_id) {
+            if self.get_destination(&record.destination_uuid).is_some() {
+                backups
+                    .entry(record.destination_uuid.as_str())
+                    .or_default()
+                    .push(record);
+            }
+        }
+        backups
+    }
+
+    /// Whether at least one backup of a snapshot exists on a configured destination
+    ///
+    /// Backups that failed verification don't count.
+    pub fn is_snapshot_backed_up(&self, snapshot_id: &str) -> bool {
+        self.snapshot_backups_by_destination(snapshot_id)
+            .values()
+            .flatten()
+            .any(|record| record.verified != Some(false))
+    }
+
+    /// Forget a deleted backup
+    ///
+    /// Other backups of the same snapshot are kept, so it stays backed up as long
+    /// as one of them exists. Returns the number of records removed.
+    pub fn remove_backup_record(&mut self, backup_path: &str) -> usize {
+        let before = self.backup_history.len();
+        self.backup_history.retain(|record| record.backup_path != backup_path);
+        before - self.backup_history.len()
+    }
+
+    /// Drop duplicate records of the same backup, keeping the latest
+    ///
+    /// Older versions appended a new record every time a snapshot was backed up
+    /// again, which made it look like it had more backups than it does.
+    fn dedup_history(&mut self) {
+        let mut seen = HashSet::new();
+        let mut history = std::mem::take(&mut self.backup_history);
+        history.sort_by_key(|record| std::cmp::Reverse(record.completed_at));
+        history.retain(|record| {
+            seen.insert((
+                record.snapshot_id.clone(),
+                record.destination_uuid.clone(),
+                record.backup_path.clone(),
+            ))
+        });
+        history.sort_by_key(|record| record.completed_at);
+        self.backup_history = history;
+    }
 }
 
 #[cfg(test)]
@@ -532,6 +844,90 @@ mod tests {
         assert_eq!(config.pending_backups.len(), 0);
         assert_eq!(config.backup_history.len(), 1);
         assert_eq!(config.backup_history[0].snapshot_id, "snap1");
+        assert_eq!(config.backup_history[0].verified, None);
+
+        config.set_verified("snap1", "uuid1", false);
+        assert_eq!(config.backup_history[0].verified, Some(false));
+        assert_eq!(config.failed_verifications("uuid1", 0), vec!["snap1"]);
+        assert!(config.failed_verifications("uuid2", 0).is_empty());
+
+        config.set_verified("snap1", "uuid1", true);
+        assert!(config.failed_verifications("uuid1", 0).is_empty());
+    }
+
+    #[test]
+    fn test_scheduled_backup_due() {
+        let mut config = BackupConfig::default();
+        let dest: BackupDestinationConfig = toml::from_str(
+            r#"
+            uuid = "uuid1"
+            label = "Backup"
+            on_schedule = true
+            schedule_interval_hours = 6
+            "#,
+        )
+        .unwrap();
+        assert!(dest.triggers_on(BackupTrigger::Schedule));
+        assert!(!dest.triggers_on(BackupTrigger::Favorite));
+        // Verification defaults to the drive type until it's set
+        assert!(dest.verifies_after_backup(true));
+        assert!(!dest.verifies_after_backup(false));
+        config.add_destination("uuid1".to_string(), dest);
+
+        // Never backed up
+        assert!(config.is_scheduled_backup_due("uuid1", 0));
+
+        config.mark_completed("snap1", "uuid1", "/backup/snap1".to_string(), None, false, None);
+        let completed_at = config.backup_history[0].completed_at;
+        assert!(!config.is_scheduled_backup_due("uuid1", completed_at + 3600));
+        assert!(config.is_scheduled_backup_due("uuid1", completed_at + 6 * 3600));
+        assert!(!config.is_scheduled_backup_due("unknown", completed_at + 6 * 3600));
+    }
+
+    #[test]
+    fn test_failed_backup_retry_backoff() {
+        let mut config = BackupConfig::default();
+        config.add_pending_backup("snap1".to_string(), "uuid1".to_string());
+
+        config.record_failure("snap1", "uuid1", "drive removed".to_string(), 1000);
+        let pending = &config.pending_backups[0];
+        assert_eq!(pending.status, BackupStatus::Pending);
+        assert!(pending.is_retrying());
+        assert_eq!(pending.next_retry_at, Some(1060));
+        assert!(config.due_for_destination("uuid1", 1059).is_empty());
+        assert_eq!(config.due_for_destination("uuid1", 1060).len(), 1);
+
+        config.record_failure("snap1", "uuid1", "drive removed".to_string(), 2000);
+        assert_eq!(config.pending_backups[0].next_retry_at, Some(2120));
+
+        for _ in 2..MAX_BACKUP_ATTEMPTS {
+            config.record_failure("snap1", "uuid1", "drive removed".to_string(), 3000);
+        }
+        assert_eq!(config.pending_backups[0].status, BackupStatus::Failed);
+        assert!(config.due_for_destination("uuid1", i64::MAX).is_empty());
+
+        config.retry_backup("snap1", "uuid1");
+        assert_eq!(config.pending_backups[0].retry_count, 0);
+        assert_eq!(config.due_for_destination("uuid1", 3000).len(), 1);
+    }
+
+    #[test]
+    fn test_backup_eligibility() {
+        assert!(BackupEligibility::default().is_eligible());
+
+        let hourly = BackupEligibility {
+            schedule_allows_backup: false,
+            ..Default::default()
+        };
+        assert!(!hourly.is_eligible());
+        assert!(BackupEligibility { is_favorite: true, ..hourly }.is_eligible());
+
+        let excluded = BackupEligibility {
+            is_favorite: true,
+            excluded: true,
+            schedule_allows_backup: true,
+        };
+        assert!(!excluded.is_eligible());
     }
 
     #[test]
@@ -550,4 +946,99 @@ mod tests {
         assert!(!config.is_backed_up("snap1", "uuid2"));
         assert!(!config.is_backed_up("snap2", "uuid1"));
     }
+
+    #[test]
+    fn test_snapshot_backups_across_destinations() {
+        let mut config = BackupConfig::default();
+        for uuid in ["uuid1", "uuid2"] {
+            let dest: BackupDestinationConfig =
+                toml::from_str(&format!("uuid = \"{uuid}\"\nlabel = \"{uuid}\"")).unwrap();
+            config.add_destination(uuid.to_string(), dest);
+        }
+
+        config.mark_completed("snap1", "uuid1", "/a/snap1".to_string(), None, false, None);
+        config.mark_completed("snap1", "uuid2", "/b/snap1".to_string(), None, false, None);
+        // Backing up again replaces the record instead of adding another
+        config.mark_completed("snap1", "uuid1", "/a/snap1".to_string(), None, false, None);
+        // Records for removed destinations don't count
+        config.mark_completed("snap1", "gone", "/c/snap1".to_string(), None, false, None);
+
+        let backups = config.snapshot_backups_by_destination("snap1");
+        assert_eq!(backups.keys().copied().collect::<Vec<_>>(), vec!["uuid1", "uuid2"]);
+        assert_eq!(backups["uuid1"].len(), 1);
+
+        // Deleting one backup leaves the snapshot backed up
+        assert_eq!(config.remove_backup_record("/a/snap1"), 1);
+        assert!(config.is_snapshot_backed_up("snap1"));
+
+        // A backup that failed verification doesn't count
+        config.set_verified("snap1", "uuid2", false);
+        assert!(!config.is_snapshot_backed_up("snap1"));
+        config.set_verified("snap1", "uuid2", true);
+        assert!(config.is_snapshot_backed_up("snap1"));
+
+        assert_eq!(config.remove_backup_record("/b/snap1"), 1);
+        assert!(!config.is_snapshot_backed_up("snap1"));
+    }
+
+    #[test]
+    fn test_prune_candidate() {
+        let mut config = BackupConfig::default();
+        let dest: BackupDestinationConfig =
+            toml::from_str("uuid = \"uuid1\"\nlabel = \"Backup\"\nretention_days = 7").unwrap();
+        assert_eq!(dest.when_full, FullDestinationAction::Skip);
+        config.add_destination("uuid1".to_string(), dest);
+
+        let day = 86400;
+        for (name, days) in [("snap2", 20), ("snap1", 10), ("snap3", 30)] {
+            config.backup_history.push(BackupRecord {
+                snapshot_id: name.to_string(),
+                destination_uuid: "uuid1".to_string(),
+                backup_path: format!("/a/{name}"),
+                completed_at: days * day,
+                size_bytes: None,
+                is_incremental: false,
+                parent_snapshot_id: None,
+                verified: None,
+            });
+        }
+
+        let candidate = |config: &BackupConfig, now| {
+            config
+                .prune_candidate("uuid1", now)
+                .map(|r| r.snapshot_id.clone())
+        };
+
+        // Oldest first, as long as it's past the retention period
+        assert_eq!(candidate(&config, 30 * day).as_deref(), Some("snap1"));
+        assert_eq!(candidate(&config, 16 * day), None);
+        config.remove_backup_record("/a/snap1");
+        assert_eq!(candidate(&config, 30 * day).as_deref(), Some("snap2"));
+
+        // The latest backup is never pruned
+        config.remove_backup_record("/a/snap2");
+        assert_eq!(candidate(&config, 100 * day), None);
+        assert!(config.prune_candidate("unknown", 100 * day).is_none());
+    }
+
+    #[test]
+    fn test_dedup_history() {
+        let mut config = BackupConfig::default();
+        for completed_at in [1, 3, 2] {
+            config.backup_history.push(BackupRecord {
+                snapshot_id: "snap1".to_string(),
+                destination_uuid: "uuid1".to_string(),
+                backup_path: "/a/snap1".to_string(),
+                completed_at,
+                size_bytes: None,
+                is_incremental: false,
+                parent_snapshot_id: None,
+                verified: None,
+            });
+        }
+
+        config.dedup_history();
+        assert_eq!(config.backup_history.len(), 1);
+        assert_eq!(config.backup_history[0].completed_at, 3);
+    }
 }
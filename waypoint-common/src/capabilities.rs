@@ -0,0 +1,160 @@
+//! Helper interface version and feature negotiation
+//!
+//! The GUI and the privileged helper can be upgraded separately. The helper
+//! advertises its interface version and optional features through
+//! `GetCapabilities`, so a newer GUI can hide operations an older helper
+//! doesn't implement instead of failing with D-Bus "unknown method" errors.
+
+use serde::{Deserialize, Serialize};
+
+/// Version of the helper D-Bus interface
+///
+/// Bumped whenever methods are added or signatures change. Helpers that predate
+/// `GetCapabilities` are treated as version 0.
+pub const HELPER_INTERFACE_VERSION: u32 = 26;
+
+/// `CloneSnapshot`
+pub const FEATURE_CLONE_SNAPSHOT: &str = "clone-snapshot";
+/// `ExportConfig` / `ImportConfig`
+pub const FEATURE_CONFIG_TRANSFER: &str = "config-transfer";
+/// `GetAuditLog`
+pub const FEATURE_AUDIT_LOG: &str = "audit-log";
+/// `MountSnapshotOverlay`, `UnmountSnapshotOverlay`, `ListSnapshotOverlays`
+pub const FEATURE_SNAPSHOT_OVERLAYS: &str = "snapshot-overlays";
+/// `CompareProgress` signal and `CancelCompare`
+pub const FEATURE_COMPARE_PROGRESS: &str = "compare-progress";
+/// `verify` argument of `RestoreFiles`
+pub const FEATURE_VERIFIED_FILE_RESTORE: &str = "verified-file-restore";
+/// `EstimateDedupSavings`, `DeduplicateSnapshots` and the `DedupProgress` signal
+pub const FEATURE_DEDUP: &str = "dedup";
+/// `RestoreToSubvolume`
+pub const FEATURE_RESTORE_TO_SUBVOLUME: &str = "restore-to-subvolume";
+/// `StartBalance`, `GetBalanceStatus`, `CancelBalance` and the `BalanceProgress` signal
+pub const FEATURE_BALANCE: &str = "balance";
+/// `CancelCreate`
+pub const FEATURE_CANCEL_CREATE: &str = "cancel-create";
+/// `PreviewQuotaCleanup` and the `QuotaCleanup` signal
+pub const FEATURE_QUOTA_CLEANUP_PREVIEW: &str = "quota-cleanup-preview";
+/// `GetSnapshotReadOnly` and `SetSnapshotReadOnly`
+pub const FEATURE_READ_ONLY_TOGGLE: &str = "read-only-toggle";
+/// `GetFilesystemStats`
+pub const FEATURE_FILESYSTEM_STATS: &str = "filesystem-stats";
+/// `SetRetentionGroup`
+pub const FEATURE_RETENTION_GROUP: &str = "retention-group";
+/// `CompareBackup`
+pub const FEATURE_COMPARE_BACKUP: &str = "compare-backup";
+/// `SetSnapshotExpiry`
+pub const FEATURE_SNAPSHOT_EXPIRY: &str = "snapshot-expiry";
+/// `LinkTransaction`
+pub const FEATURE_TRANSACTION_LINK: &str = "transaction-link";
+/// `nested_subvolumes` argument of `RestoreFiles`
+pub const FEATURE_NESTED_SUBVOLUME_RESTORE: &str = "nested-subvolume-restore";
+/// `GetCommandTranscript`
+pub const FEATURE_COMMAND_TRANSCRIPT: &str = "command-transcript";
+/// `PreviewRestoreFromBackup` and the `new_name` argument of `RestoreFromBackup`
+pub const FEATURE_BACKUP_RESTORE_PREVIEW: &str = "backup-restore-preview";
+/// `GetSnapshotDirPermissions` and `SecureSnapshotDirs`
+pub const FEATURE_SNAPSHOT_DIR_PERMISSIONS: &str = "snapshot-dir-permissions";
+/// `GetSnapshotGenerations`
+pub const FEATURE_SNAPSHOT_GENERATIONS: &str = "snapshot-generations";
+/// `SetSnapshotCategory`
+pub const FEATURE_SNAPSHOT_CATEGORY: &str = "snapshot-category";
+/// `GetSnapshotCompression` and `SetSnapshotCompression`
+pub const FEATURE_SNAPSHOT_COMPRESSION: &str = "snapshot-compression";
+/// `GetSnapshotSharing`
+pub const FEATURE_SNAPSHOT_SHARING: &str = "snapshot-sharing";
+/// `CancelAuthorization`
+pub const FEATURE_CANCEL_AUTHORIZATION: &str = "cancel-authorization";
+/// `BackupSnapshotImage` and restoring image backups
+pub const FEATURE_IMAGE_BACKUP: &str = "image-backup";
+/// `ListSnapshotSummaries` and `GetSnapshotPackages`
+pub const FEATURE_SNAPSHOT_SUMMARIES: &str = "snapshot-summaries";
+/// `SetSnapshotPinned`
+pub const FEATURE_SNAPSHOT_PINS: &str = "snapshot-pins";
+/// `CheckSnapshotMetadata` and `ResolveMetadataConflict`
+pub const FEATURE_METADATA_CHECK: &str = "metadata-check";
+/// `GetSnapshotExclusiveSizes`
+pub const FEATURE_EXCLUSIVE_SIZES: &str = "exclusive-sizes";
+
+/// Every optional feature implemented by this version of the helper
+pub const HELPER_FEATURES: &[&str] = &[
+    FEATURE_CLONE_SNAPSHOT,
+    FEATURE_CONFIG_TRANSFER,
+    FEATURE_AUDIT_LOG,
+    FEATURE_SNAPSHOT_OVERLAYS,
+    FEATURE_COMPARE_PROGRESS,
+    FEATURE_VERIFIED_FILE_RESTORE,
+    FEATURE_DEDUP,
+    FEATURE_RESTORE_TO_SUBVOLUME,
+    FEATURE_BALANCE,
+    FEATURE_CANCEL_CREATE,
+    FEATURE_QUOTA_CLEANUP_PREVIEW,
+    FEATURE_READ_ONLY_TOGGLE,
+    FEATURE_FILESYSTEM_STATS,
+    FEATURE_RETENTION_GROUP,
+    FEATURE_COMPARE_BACKUP,
+    FEATURE_SNAPSHOT_EXPIRY,
+    FEATURE_TRANSACTION_LINK,
+    FEATURE_NESTED_SUBVOLUME_RESTORE,
+    FEATURE_COMMAND_TRANSCRIPT,
+    FEATURE_BACKUP_RESTORE_PREVIEW,
+    FEATURE_SNAPSHOT_DIR_PERMISSIONS,
+    FEATURE_SNAPSHOT_GENERATIONS,
+    FEATURE_SNAPSHOT_CATEGORY,
+    FEATURE_SNAPSHOT_COMPRESSION,
+    FEATURE_SNAPSHOT_SHARING,
+    FEATURE_CANCEL_AUTHORIZATION,
+    FEATURE_IMAGE_BACKUP,
+    FEATURE_SNAPSHOT_SUMMARIES,
+    FEATURE_SNAPSHOT_PINS,
+    FEATURE_METADATA_CHECK,
+    FEATURE_EXCLUSIVE_SIZES,
+];
+
+/// Interface version and optional features offered by a helper
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HelperCapabilities {
+    /// Interface version (see `HELPER_INTERFACE_VERSION`)
+    pub interface_version: u32,
+    /// Names of supported optional features
+    pub features: Vec<String>,
+}
+
+impl HelperCapabilities {
+    /// Capabilities of this build of the helper
+    pub fn current() -> Self {
+        Self {
+            interface_version: HELPER_INTERFACE_VERSION,
+            features: HELPER_FEATURES.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+
+    /// Capabilities assumed for a helper without `GetCapabilities`
+    pub fn legacy() -> Self {
+        Self {
+            interface_version: 0,
+            features: Vec::new(),
+        }
+    }
+
+    /// Whether the helper supports an optional feature
+    pub fn supports(&self, feature: &str) -> bool {
+        self.features.iter().any(|f| f == feature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_support() {
+        let current = HelperCapabilities::current();
+        assert!(current.supports(FEATURE_SNAPSHOT_OVERLAYS));
+        assert!(!current.supports("time-travel"));
+        assert!(!HelperCapabilities::legacy().supports(FEATURE_AUDIT_LOG));
+
+        let json = serde_json::to_string(&current).unwrap();
+        assert_eq!(serde_json::from_str::<HelperCapabilities>(&json).unwrap(), current);
+    }
+}
@@ -0,0 +1,112 @@
+//! Btrfs compression of the snapshot directory
+//!
+//! The `compression` property of a directory makes btrfs compress data written
+//! into it from then on. Data that is already there keeps the compression it
+//! was written with, so savings grow as new data arrives.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Algorithms accepted by `btrfs property set <path> compression`
+pub const COMPRESSION_ALGORITHMS: &[&str] = &["zstd", "lzo", "zlib"];
+
+/// Check an algorithm for the snapshot directory (empty resets the property)
+pub fn validate_compression(algorithm: &str) -> Result<(), String> {
+    if algorithm.is_empty() || COMPRESSION_ALGORITHMS.contains(&algorithm) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unknown compression '{algorithm}', expected one of: {}",
+            COMPRESSION_ALGORITHMS.join(", ")
+        ))
+    }
+}
+
+/// Disk space used by compressed and uncompressed data, from `compsize`
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CompressionUsage {
+    /// Space the data takes on disk
+    pub disk_bytes: u64,
+    /// Size of the same data uncompressed
+    pub uncompressed_bytes: u64,
+}
+
+impl CompressionUsage {
+    /// Space saved by compression
+    pub fn saved_bytes(&self) -> u64 {
+        self.uncompressed_bytes.saturating_sub(self.disk_bytes)
+    }
+
+    /// Space saved as a percentage of the uncompressed size
+    pub fn saved_percent(&self) -> f64 {
+        if self.uncompressed_bytes == 0 {
+            return 0.0;
+        }
+        self.saved_bytes() as f64 / self.uncompressed_bytes as f64 * 100.0
+    }
+}
+
+/// Compression settings and savings of the snapshot directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SnapshotCompression {
+    pub path: PathBuf,
+    /// `compression` property of the directory, None when unset
+    pub property: Option<String>,
+    /// `compress=` or `compress-force=` mount option of its filesystem
+    pub mount_option: Option<String>,
+    /// Savings so far, None when `compsize` isn't installed or failed
+    pub usage: Option<CompressionUsage>,
+}
+
+impl SnapshotCompression {
+    /// Algorithm used for new data, None when it's stored uncompressed
+    ///
+    /// The property wins over the mount option; `none` and `no` turn
+    /// compression off.
+    pub fn effective(&self) -> Option<&str> {
+        match self.property.as_deref() {
+            Some("none" | "no") => None,
+            Some(algorithm) => Some(algorithm),
+            None => self.mount_option.as_deref(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_compression() {
+        assert!(validate_compression("zstd").is_ok());
+        assert!(validate_compression("").is_ok());
+        assert!(validate_compression("zstd:3").is_err());
+        assert!(validate_compression("gzip").is_err());
+    }
+
+    #[test]
+    fn test_effective_compression() {
+        let mut compression = SnapshotCompression {
+            mount_option: Some("zstd:3".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(compression.effective(), Some("zstd:3"));
+
+        compression.property = Some("lzo".to_string());
+        assert_eq!(compression.effective(), Some("lzo"));
+
+        compression.property = Some("none".to_string());
+        assert_eq!(compression.effective(), None);
+    }
+
+    #[test]
+    fn test_compression_savings() {
+        let usage = CompressionUsage {
+            disk_bytes: 300,
+            uncompressed_bytes: 1000,
+        };
+        assert_eq!(usage.saved_bytes(), 700);
+        assert_eq!(usage.saved_percent(), 70.0);
+        assert_eq!(CompressionUsage::default().saved_percent(), 0.0);
+    }
+}
@@ -2,12 +2,26 @@
 
 use std::path::PathBuf;
 
+/// Parse a colon-separated list of directories, ignoring empty entries
+fn parse_snapshot_dirs(value: &str) -> Vec<PathBuf> {
+    value
+        .split(':')
+        .map(str::trim)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
 /// Waypoint configuration with support for environment variable overrides
 #[derive(Debug, Clone)]
 pub struct WaypointConfig {
     /// Directory where snapshots are stored (default: /.snapshots)
     pub snapshot_dir: PathBuf,
 
+    /// Further snapshot directories, usually on other Btrfs filesystems
+    /// such as a separate /home (default: none)
+    pub extra_snapshot_dirs: Vec<PathBuf>,
+
     /// Path to metadata file (default: /var/lib/waypoint/snapshots.json)
     pub metadata_file: PathBuf,
 
@@ -44,6 +58,47 @@ pub struct WaypointConfig {
 
     /// Minimum number of snapshots to always keep
     pub retention_min_snapshots: usize,
+
+    /// Where the top-level Btrfs subvolume is mounted when the snapshot
+    /// directory isn't otherwise accessible (default: /mnt/btrfs-root)
+    pub btrfs_root_mount: PathBuf,
+
+    /// Mount options for the top-level subvolume (default: subvolid=5)
+    pub btrfs_root_mount_options: String,
+
+    /// Subvolume holding the snapshots, relative to the top level (default: @snapshots)
+    pub snapshots_subvolume: String,
+
+    /// Time limit for each command run while creating a snapshot, in seconds (default: 120)
+    pub snapshot_timeout_secs: u64,
+
+    /// Make new snapshots read-only, which backups need (default: true)
+    pub read_only_snapshots: bool,
+
+    /// Run size calculations and other disk-heavy housekeeping at idle I/O
+    /// and lowest CPU priority (default: true)
+    pub low_priority_background: bool,
+
+    /// Scheduled snapshots the scheduler creates at the same time (default: 1)
+    pub scheduler_max_concurrent: usize,
+
+    /// Executable the helper runs after a rollback is scheduled, if it exists
+    /// (default: /etc/waypoint/hooks/post-restore)
+    pub post_restore_hook: PathBuf,
+
+    /// Make snapshot directories root-only (mode 700) when the helper starts,
+    /// instead of only warning about looser permissions (default: false)
+    pub enforce_snapshot_dir_permissions: bool,
+
+    /// Also accept JSON requests on a Unix socket, for scripts without D-Bus
+    /// access (default: false)
+    pub socket_api: bool,
+
+    /// Path of that socket (default: /run/waypoint/helper.sock)
+    pub socket_api_path: PathBuf,
+
+    /// Group whose members may connect to that socket (default: wheel)
+    pub socket_api_group: String,
 }
 
 impl Default for WaypointConfig {
@@ -62,6 +117,7 @@ impl Default for WaypointConfig {
 
         Self {
             snapshot_dir: PathBuf::from("/.snapshots"),
+            extra_snapshot_dirs: Vec::new(),
             metadata_file: PathBuf::from("/var/lib/waypoint/snapshots.json"),
             scheduler_config: PathBuf::from("/etc/waypoint/scheduler.conf"),
             schedules_config: PathBuf::from("/etc/waypoint/schedules.toml"),
@@ -74,6 +130,18 @@ impl Default for WaypointConfig {
             retention_max_snapshots: 10,
             retention_max_age_days: 30,
             retention_min_snapshots: 3,
+            btrfs_root_mount: PathBuf::from("/mnt/btrfs-root"),
+            btrfs_root_mount_options: "subvolid=5".to_string(),
+            snapshots_subvolume: "@snapshots".to_string(),
+            snapshot_timeout_secs: 120,
+            read_only_snapshots: true,
+            low_priority_background: true,
+            scheduler_max_concurrent: 1,
+            post_restore_hook: PathBuf::from("/etc/waypoint/hooks/post-restore"),
+            enforce_snapshot_dir_permissions: false,
+            socket_api: false,
+            socket_api_path: PathBuf::from("/run/waypoint/helper.sock"),
+            socket_api_group: "wheel".to_string(),
         }
     }
 }
@@ -83,12 +151,29 @@ impl WaypointConfig {
     ///
     /// Supported environment variables:
     /// - WAYPOINT_SNAPSHOT_DIR: Override snapshot directory
-    /// - WAYPOINT_METADATA_FILE: Override metadata file path
+    /// - WAYPOINT_EXTRA_SNAPSHOT_DIRS: Colon-separated further snapshot directories
+    /// - WAYPOINT_METADATA_FILE: Override metadata file path (a `.toml` path stores it as TOML)
     /// - WAYPOINT_SCHEDULER_CONFIG: Override scheduler config path (deprecated)
     /// - WAYPOINT_SCHEDULES_CONFIG: Override schedules TOML config path
     /// - WAYPOINT_BACKUP_CONFIG: Override backup config path
     /// - WAYPOINT_SERVICE_DIR: Override service directory (for init system integration)
     /// - WAYPOINT_MIN_FREE_SPACE_GB: Override minimum free space (in GB)
+    /// - WAYPOINT_BTRFS_ROOT_MOUNT: Override where the top-level subvolume is mounted
+    /// - WAYPOINT_BTRFS_ROOT_MOUNT_OPTIONS: Override its mount options
+    /// - WAYPOINT_SNAPSHOTS_SUBVOLUME: Override the subvolume holding snapshots
+    /// - WAYPOINT_SNAPSHOT_TIMEOUT_SECS: Override the snapshot command time limit
+    /// - WAYPOINT_READ_ONLY_SNAPSHOTS: Set to "false" to leave new snapshots writable
+    /// - WAYPOINT_LOW_PRIORITY_BACKGROUND: Set to "false" to run housekeeping at normal priority
+    /// - WAYPOINT_SCHEDULER_MAX_CONCURRENT: Override how many scheduled snapshots run at once
+    /// - WAYPOINT_POST_RESTORE_HOOK: Override the post-restore hook path
+    /// - WAYPOINT_ENFORCE_SNAPSHOT_DIR_PERMISSIONS: Set to "true" to make snapshot directories root-only at startup
+    /// - WAYPOINT_SOCKET_API: Set to "true" to let the helper listen on a Unix socket as well
+    /// - WAYPOINT_SOCKET_API_PATH: Override the path of that socket
+    /// - WAYPOINT_SOCKET_API_GROUP: Override the group allowed to use that socket
+    ///
+    /// Without WAYPOINT_SNAPSHOT_DIR, a snapshot directory that doesn't exist
+    /// falls back to the snapshots subvolume under the top-level mount, if
+    /// that is already mounted.
     pub fn new() -> Self {
         let mut config = Self::default();
 
@@ -97,6 +182,10 @@ impl WaypointConfig {
             config.snapshot_dir = PathBuf::from(dir);
         }
 
+        if let Ok(dirs) = std::env::var("WAYPOINT_EXTRA_SNAPSHOT_DIRS") {
+            config.extra_snapshot_dirs = parse_snapshot_dirs(&dirs);
+        }
+
         if let Ok(file) = std::env::var("WAYPOINT_METADATA_FILE") {
             config.metadata_file = PathBuf::from(file);
         }
@@ -123,9 +212,127 @@ impl WaypointConfig {
             }
         }
 
+        if let Ok(dir) = std::env::var("WAYPOINT_BTRFS_ROOT_MOUNT") {
+            config.btrfs_root_mount = PathBuf::from(dir);
+        }
+
+        if let Ok(options) = std::env::var("WAYPOINT_BTRFS_ROOT_MOUNT_OPTIONS") {
+            config.btrfs_root_mount_options = options;
+        }
+
+        if let Ok(subvolume) = std::env::var("WAYPOINT_SNAPSHOTS_SUBVOLUME") {
+            config.snapshots_subvolume = subvolume;
+        }
+
+        if let Ok(secs) = std::env::var("WAYPOINT_SNAPSHOT_TIMEOUT_SECS")
+            && let Ok(secs) = secs.parse::<u64>()
+        {
+            config.snapshot_timeout_secs = secs;
+        }
+
+        if let Ok(value) = std::env::var("WAYPOINT_READ_ONLY_SNAPSHOTS")
+            && let Ok(read_only) = value.parse::<bool>()
+        {
+            config.read_only_snapshots = read_only;
+        }
+
+        if let Ok(value) = std::env::var("WAYPOINT_LOW_PRIORITY_BACKGROUND")
+            && let Ok(low_priority) = value.parse::<bool>()
+        {
+            config.low_priority_background = low_priority;
+        }
+
+        if let Ok(value) = std::env::var("WAYPOINT_SCHEDULER_MAX_CONCURRENT")
+            && let Ok(max) = value.parse::<usize>()
+        {
+            config.scheduler_max_concurrent = max.max(1);
+        }
+
+        if let Ok(path) = std::env::var("WAYPOINT_POST_RESTORE_HOOK") {
+            config.post_restore_hook = PathBuf::from(path);
+        }
+
+        if let Ok(value) = std::env::var("WAYPOINT_ENFORCE_SNAPSHOT_DIR_PERMISSIONS")
+            && let Ok(enforce) = value.parse::<bool>()
+        {
+            config.enforce_snapshot_dir_permissions = enforce;
+        }
+
+        if let Ok(value) = std::env::var("WAYPOINT_SOCKET_API")
+            && let Ok(enabled) = value.parse::<bool>()
+        {
+            config.socket_api = enabled;
+        }
+
+        if let Ok(path) = std::env::var("WAYPOINT_SOCKET_API_PATH") {
+            config.socket_api_path = PathBuf::from(path);
+        }
+
+        if let Ok(group) = std::env::var("WAYPOINT_SOCKET_API_GROUP") {
+            config.socket_api_group = group;
+        }
+
+        if std::env::var("WAYPOINT_SNAPSHOT_DIR").is_err() {
+            config.snapshot_dir = config.resolve_snapshot_dir();
+        }
+
         config
     }
 
+    /// Snapshot directory as seen through the top-level subvolume mount
+    pub fn fallback_snapshot_dir(&self) -> PathBuf {
+        self.btrfs_root_mount
+            .join(self.snapshots_subvolume.trim_start_matches('/'))
+    }
+
+    /// The snapshot directory if it exists, otherwise the fallback below the
+    /// top-level mount if that exists, otherwise the snapshot directory
+    pub fn resolve_snapshot_dir(&self) -> PathBuf {
+        if self.snapshot_dir.exists() {
+            return self.snapshot_dir.clone();
+        }
+        let fallback = self.fallback_snapshot_dir();
+        if fallback.exists() {
+            fallback
+        } else {
+            self.snapshot_dir.clone()
+        }
+    }
+
+    /// All snapshot directories, the primary one first and without duplicates
+    pub fn snapshot_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = vec![self.snapshot_dir.clone()];
+        for dir in &self.extra_snapshot_dirs {
+            if !dirs.contains(dir) {
+                dirs.push(dir.clone());
+            }
+        }
+        dirs
+    }
+
+    /// Directory holding the snapshot `name`
+    ///
+    /// Each snapshot lives in exactly one snapshot directory. Returns the
+    /// first directory that contains it, or the primary one if none does.
+    pub fn locate_snapshot_dir(&self, name: &str) -> PathBuf {
+        let dirs = self.snapshot_dirs();
+        // A root-only directory hides its snapshots, so it can only be guessed
+        // to hold the snapshot when no other directory does
+        dirs.iter()
+            .find(|dir| dir.join(name).exists())
+            .or_else(|| {
+                dirs.iter()
+                    .find(|dir| crate::permissions::exists_or_hidden(&dir.join(name)))
+            })
+            .cloned()
+            .unwrap_or_else(|| self.snapshot_dir.clone())
+    }
+
+    /// Time limit for each command run while creating a snapshot
+    pub fn snapshot_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.snapshot_timeout_secs)
+    }
+
     /// Get the full path to the scheduler service
     pub fn scheduler_service_path(&self) -> PathBuf {
         self.service_dir.join("waypoint-scheduler")
@@ -147,6 +354,11 @@ mod tests {
         assert_eq!(config.min_free_space_bytes, 1024 * 1024 * 1024);
         assert_eq!(config.ui_window_width, 800);
         assert_eq!(config.ui_window_height, 600);
+        assert_eq!(
+            config.snapshot_timeout(),
+            std::time::Duration::from_secs(120)
+        );
+        assert!(config.read_only_snapshots);
     }
 
     #[test]
@@ -157,4 +369,75 @@ mod tests {
             PathBuf::from("/var/service/waypoint-scheduler")
         );
     }
+
+    #[test]
+    fn test_fallback_snapshot_dir() {
+        let config = WaypointConfig::default();
+        assert_eq!(
+            config.fallback_snapshot_dir(),
+            PathBuf::from("/mnt/btrfs-root/@snapshots")
+        );
+
+        let config = WaypointConfig {
+            btrfs_root_mount: PathBuf::from("/run/waypoint/root"),
+            snapshots_subvolume: "/@/.snapshots".to_string(),
+            ..WaypointConfig::default()
+        };
+        assert_eq!(
+            config.fallback_snapshot_dir(),
+            PathBuf::from("/run/waypoint/root/@/.snapshots")
+        );
+    }
+
+    #[test]
+    fn test_resolve_snapshot_dir() {
+        let dir = std::env::temp_dir().join(format!("waypoint-config-test-{}", std::process::id()));
+        let fallback = dir.join("@snapshots");
+
+        let mut config = WaypointConfig {
+            snapshot_dir: dir.join("missing"),
+            btrfs_root_mount: dir.clone(),
+            ..WaypointConfig::default()
+        };
+
+        // Neither exists: keep the configured directory
+        assert_eq!(config.resolve_snapshot_dir(), dir.join("missing"));
+
+        std::fs::create_dir_all(&fallback).unwrap();
+        assert_eq!(config.resolve_snapshot_dir(), fallback);
+
+        config.snapshot_dir = dir.clone();
+        assert_eq!(config.resolve_snapshot_dir(), dir);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_snapshot_dirs() {
+        assert_eq!(
+            parse_snapshot_dirs("/home/.snapshots: :/data/.snapshots:"),
+            vec![
+                PathBuf::from("/home/.snapshots"),
+                PathBuf::from("/data/.snapshots")
+            ]
+        );
+
+        let dir = std::env::temp_dir().join(format!("waypoint-dirs-test-{}", std::process::id()));
+        let config = WaypointConfig {
+            snapshot_dir: dir.join("root"),
+            extra_snapshot_dirs: vec![dir.join("home"), dir.join("root")],
+            ..WaypointConfig::default()
+        };
+        assert_eq!(
+            config.snapshot_dirs(),
+            vec![dir.join("root"), dir.join("home")]
+        );
+
+        // Snapshots that exist nowhere belong to the primary directory
+        assert_eq!(config.locate_snapshot_dir("snap"), dir.join("root"));
+        std::fs::create_dir_all(dir.join("home").join("snap")).unwrap();
+        assert_eq!(config.locate_snapshot_dir("snap"), dir.join("home"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
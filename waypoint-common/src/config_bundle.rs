@@ -0,0 +1,167 @@
+//! Portable bundle of all Waypoint settings
+//!
+//! Bundles the system-wide configuration files (schedules, quota, exclude) and the
+//! per-user settings (backup destinations, manual snapshot subvolumes) into a single
+//! JSON document so settings can be moved to another machine in one step.
+
+use crate::{BackupConfig, ExcludeConfig, QuotaConfig, SchedulesConfig};
+use anyhow::{Context, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Current bundle format version
+pub const CONFIG_BUNDLE_VERSION: u32 = 1;
+
+/// All Waypoint settings in a single portable document
+///
+/// Config files are stored as their raw TOML text so that comments and formatting
+/// survive the round trip. A `None` section is left untouched on import.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    /// Bundle format version
+    #[serde(default)]
+    pub version: u32,
+
+    /// When the bundle was exported
+    #[serde(default)]
+    pub exported_at: Option<DateTime<Utc>>,
+
+    /// Contents of /etc/waypoint/schedules.toml
+    #[serde(default)]
+    pub schedules: Option<String>,
+
+    /// Contents of /etc/waypoint/quota.toml
+    #[serde(default)]
+    pub quota: Option<String>,
+
+    /// Contents of /etc/waypoint/exclude.toml
+    #[serde(default)]
+    pub exclude: Option<String>,
+
+    /// Contents of the user's backup-config.toml
+    #[serde(default)]
+    pub backup: Option<String>,
+
+    /// Subvolumes selected for manual snapshots
+    #[serde(default)]
+    pub subvolumes: Option<Vec<PathBuf>>,
+}
+
+impl ConfigBundle {
+    /// Create an empty bundle stamped with the current version and time
+    pub fn new() -> Self {
+        Self {
+            version: CONFIG_BUNDLE_VERSION,
+            exported_at: Some(Utc::now()),
+            ..Default::default()
+        }
+    }
+
+    /// Parse and validate a bundle from JSON
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        let bundle: Self = serde_json::from_str(json).context("Invalid configuration bundle")?;
+        bundle.validate()?;
+        Ok(bundle)
+    }
+
+    /// Serialize the bundle to pretty-printed JSON
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        serde_json::to_string_pretty(self).context("Failed to serialize configuration bundle")
+    }
+
+    /// Validate every section using the same parsers that load the live config
+    ///
+    /// Nothing should be written unless this succeeds, so a bad section can never
+    /// leave the system with a partially imported configuration.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.version == 0 || self.version > CONFIG_BUNDLE_VERSION {
+            bail!(
+                "Unsupported configuration bundle version {} (expected 1-{CONFIG_BUNDLE_VERSION})",
+                self.version
+            );
+        }
+
+        if let Some(schedules) = &self.schedules {
+            let config: SchedulesConfig =
+                toml::from_str(schedules).context("Invalid schedules configuration")?;
+            for schedule in &config.schedules {
+                schedule
+                    .validate()
+                    .map_err(|e| anyhow::anyhow!("Invalid schedules configuration: {e}"))?;
+            }
+        }
+
+        if let Some(quota) = &self.quota {
+            toml::from_str::<QuotaConfig>(quota).context("Invalid quota configuration")?;
+        }
+
+        if let Some(exclude) = &self.exclude {
+            toml::from_str::<ExcludeConfig>(exclude).context("Invalid exclude configuration")?;
+        }
+
+        if let Some(backup) = &self.backup {
+            toml::from_str::<BackupConfig>(backup).context("Invalid backup configuration")?;
+        }
+
+        if let Some(path) = self.subvolumes.iter().flatten().find(|p| !p.is_absolute()) {
+            bail!(
+                "Invalid subvolume configuration: {} is not an absolute path",
+                path.display()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Whether the bundle contains any system-wide configuration
+    pub fn has_system_config(&self) -> bool {
+        self.schedules.is_some() || self.quota.is_some() || self.exclude.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut bundle = ConfigBundle::new();
+        bundle.quota = Some(toml::to_string_pretty(&QuotaConfig::default()).unwrap());
+        bundle.subvolumes = Some(vec![PathBuf::from("/"), PathBuf::from("/home")]);
+
+        let json = bundle.to_json().unwrap();
+        let parsed = ConfigBundle::from_json(&json).unwrap();
+
+        assert_eq!(parsed.version, CONFIG_BUNDLE_VERSION);
+        assert_eq!(parsed.quota, bundle.quota);
+        assert_eq!(parsed.subvolumes, bundle.subvolumes);
+        assert!(parsed.has_system_config());
+    }
+
+    #[test]
+    fn test_rejects_invalid_section() {
+        let mut bundle = ConfigBundle::new();
+        bundle.quota = Some("enabled = \"not a bool\"".to_string());
+
+        assert!(bundle.validate().is_err());
+    }
+
+    #[test]
+    fn test_rejects_unknown_version() {
+        let mut bundle = ConfigBundle::new();
+        bundle.version = CONFIG_BUNDLE_VERSION + 1;
+        assert!(bundle.validate().is_err());
+
+        bundle.version = 0;
+        assert!(bundle.validate().is_err());
+    }
+
+    #[test]
+    fn test_rejects_relative_subvolume() {
+        let mut bundle = ConfigBundle::new();
+        bundle.subvolumes = Some(vec![PathBuf::from("home")]);
+
+        assert!(bundle.validate().is_err());
+    }
+}
@@ -0,0 +1,218 @@
+//! Structured errors returned by the helper
+//!
+//! Helper D-Bus methods report failures as `(false, message)`. The message
+//! starts with a machine-readable code in brackets, e.g.
+//! `[not-found] Failed to delete snapshot: Snapshot not found: foo`, so
+//! clients can react to the kind of failure without matching on prose. The
+//! rest of the message stays human readable, which keeps old clients and the
+//! CLI working unchanged.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Kind of failure reported by the helper
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorCode {
+    /// Polkit denied the operation
+    AuthFailed,
+    /// The caller exceeded the operation rate limit
+    RateLimited,
+    /// The snapshot, backup or path doesn't exist
+    NotFound,
+    /// A snapshot or path with that name already exists
+    AlreadyExists,
+    /// Another operation is using the snapshot
+    Busy,
+    /// Not enough free space on the filesystem
+    InsufficientSpace,
+    /// The filesystem is mounted read-only, e.g. after btrfs detected errors
+    ReadOnlyFilesystem,
+    /// A Btrfs quota limit was hit
+    QuotaExceeded,
+    /// Btrfs metadata space is exhausted and needs a balance
+    MetadataFull,
+    /// The request was malformed or failed validation
+    InvalidInput,
+    /// A command did not finish within its time limit
+    TimedOut,
+    /// The operation was cancelled by the caller
+    Cancelled,
+    /// A btrfs command failed
+    BtrfsError,
+    /// Any other failure
+    Internal,
+}
+
+impl ErrorCode {
+    /// All codes, for parsing
+    const ALL: [ErrorCode; 14] = [
+        ErrorCode::AuthFailed,
+        ErrorCode::RateLimited,
+        ErrorCode::NotFound,
+        ErrorCode::AlreadyExists,
+        ErrorCode::Busy,
+        ErrorCode::InsufficientSpace,
+        ErrorCode::ReadOnlyFilesystem,
+        ErrorCode::QuotaExceeded,
+        ErrorCode::MetadataFull,
+        ErrorCode::InvalidInput,
+        ErrorCode::TimedOut,
+        ErrorCode::Cancelled,
+        ErrorCode::BtrfsError,
+        ErrorCode::Internal,
+    ];
+
+    /// Wire name of the code
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::AuthFailed => "auth-failed",
+            ErrorCode::RateLimited => "rate-limited",
+            ErrorCode::NotFound => "not-found",
+            ErrorCode::AlreadyExists => "already-exists",
+            ErrorCode::Busy => "busy",
+            ErrorCode::InsufficientSpace => "insufficient-space",
+            ErrorCode::ReadOnlyFilesystem => "read-only-filesystem",
+            ErrorCode::QuotaExceeded => "quota-exceeded",
+            ErrorCode::MetadataFull => "metadata-full",
+            ErrorCode::InvalidInput => "invalid-input",
+            ErrorCode::TimedOut => "timed-out",
+            ErrorCode::Cancelled => "cancelled",
+            ErrorCode::BtrfsError => "btrfs-error",
+            ErrorCode::Internal => "internal",
+        }
+    }
+
+    /// Parse a wire name
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|code| code.as_str() == s)
+    }
+
+    /// Guess the code of an untyped error from its message
+    ///
+    /// Used for errors raised by commands and library code that doesn't
+    /// construct a `HelperError`, e.g. "No space left on device" from btrfs.
+    pub fn classify(message: &str) -> Self {
+        let lower = message.to_lowercase();
+        if lower.contains("authorization failed") || lower.contains("not authorized") {
+            ErrorCode::AuthFailed
+        } else if lower.contains("rate limit exceeded") {
+            ErrorCode::RateLimited
+        } else if lower.contains("no space left") || lower.contains("not enough space") {
+            ErrorCode::InsufficientSpace
+        } else if lower.contains("read-only file system") {
+            ErrorCode::ReadOnlyFilesystem
+        } else if lower.contains("quota exceeded") {
+            ErrorCode::QuotaExceeded
+        } else if lower.contains("already exists") {
+            ErrorCode::AlreadyExists
+        } else if lower.contains("not found") || lower.contains("no such file") {
+            ErrorCode::NotFound
+        } else if lower.contains("device or resource busy") {
+            ErrorCode::Busy
+        } else if lower.contains("timed out") {
+            ErrorCode::TimedOut
+        } else if lower.contains("cancelled") {
+            ErrorCode::Cancelled
+        } else if lower.contains("btrfs") {
+            ErrorCode::BtrfsError
+        } else {
+            ErrorCode::Internal
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error with a code and a human-readable message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HelperError {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl HelperError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// Encode for the message part of a `(false, message)` D-Bus response
+    pub fn encode(&self) -> String {
+        format!("[{}] {}", self.code, self.message)
+    }
+
+    /// Decode a failure message from the helper
+    ///
+    /// Messages without a code (from older helpers) are classified by content.
+    pub fn decode(response: &str) -> Self {
+        let coded = response
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once("] "))
+            .and_then(|(code, message)| Some((ErrorCode::parse(code)?, message)));
+
+        match coded {
+            Some((code, message)) => Self::new(code, message),
+            None => Self::new(ErrorCode::classify(response), response),
+        }
+    }
+
+    /// Find the code of an error chain, falling back to classifying its message
+    pub fn code_of(error: &anyhow::Error) -> ErrorCode {
+        error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<HelperError>())
+            .map(|e| e.code)
+            .unwrap_or_else(|| ErrorCode::classify(&format!("{error:#}")))
+    }
+}
+
+impl fmt::Display for HelperError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for HelperError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let error = HelperError::new(ErrorCode::NotFound, "Snapshot not found: foo");
+        assert_eq!(error.encode(), "[not-found] Snapshot not found: foo");
+        assert_eq!(HelperError::decode(&error.encode()), error);
+    }
+
+    #[test]
+    fn test_decode_uncoded_message() {
+        let error = HelperError::decode("Failed to create snapshot: No space left on device");
+        assert_eq!(error.code, ErrorCode::InsufficientSpace);
+        assert_eq!(error.message, "Failed to create snapshot: No space left on device");
+
+        let error = HelperError::decode("[bogus] something");
+        assert_eq!(error.code, ErrorCode::Internal);
+        assert_eq!(error.message, "[bogus] something");
+    }
+
+    #[test]
+    fn test_code_of_error_chain() {
+        let error = anyhow::Error::new(HelperError::new(ErrorCode::Busy, "Snapshot is busy"))
+            .context("Failed to delete snapshot");
+        assert_eq!(HelperError::code_of(&error), ErrorCode::Busy);
+
+        let error = anyhow::anyhow!("Disk quota exceeded");
+        assert_eq!(HelperError::code_of(&error), ErrorCode::QuotaExceeded);
+
+        let error = anyhow::anyhow!("ERROR: cannot snapshot '/': Read-only file system");
+        assert_eq!(HelperError::code_of(&error), ErrorCode::ReadOnlyFilesystem);
+    }
+}
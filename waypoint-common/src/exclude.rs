@@ -77,6 +77,59 @@ impl ExcludePattern {
         }
     }
 
+    /// Check that the pattern can match the paths it looks like it matches
+    ///
+    /// Matching is plain string comparison, so mistakes like a trailing
+    /// slash or glob syntax other than `*` don't fail, they just never match.
+    pub fn validate(&self) -> Result<(), String> {
+        let pattern = self.pattern.as_str();
+        if pattern.trim().is_empty() {
+            return Err("Pattern cannot be empty".to_string());
+        }
+        if !pattern.starts_with('/') {
+            return Err(format!(
+                "'{pattern}' must be an absolute path starting with /"
+            ));
+        }
+        if pattern.split('/').any(|component| component == "..") {
+            return Err(format!("'{pattern}' must not contain '..'"));
+        }
+        if pattern.len() > 1 && pattern.ends_with('/') {
+            return Err(format!(
+                "'{pattern}' ends with '/', which never matches; remove the trailing slash"
+            ));
+        }
+        if pattern.contains("//") {
+            return Err(format!("'{pattern}' contains '//', which never matches"));
+        }
+
+        match self.pattern_type {
+            PatternType::Exact | PatternType::Prefix => {
+                if pattern.contains('*') {
+                    return Err(format!(
+                        "'{pattern}' contains '*', which only works in glob patterns"
+                    ));
+                }
+            }
+            PatternType::Glob => {
+                if !pattern.contains('*') {
+                    return Err(format!(
+                        "'{pattern}' has no '*'; use an exact or prefix match instead"
+                    ));
+                }
+                if let Some(c) = pattern
+                    .chars()
+                    .find(|c| matches!(c, '?' | '[' | ']' | '{' | '}'))
+                {
+                    return Err(format!(
+                        "'{pattern}' uses '{c}', but glob patterns only support '*'"
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Simple glob matching (supports * wildcard)
     fn simple_glob_match(&self, path: &str, pattern: &str) -> bool {
         // Split pattern by * and check if all parts are present in order
@@ -268,6 +321,43 @@ impl ExcludeConfig {
         self.patterns.iter().filter(|p| p.enabled).collect()
     }
 
+    /// Enabled patterns that would exclude `path`
+    ///
+    /// A matching directory is removed with everything in it, so patterns
+    /// matching one of the parent directories count as well.
+    pub fn matching_patterns(&self, path: &Path) -> Vec<&ExcludePattern> {
+        self.patterns
+            .iter()
+            .filter(|p| path.ancestors().any(|ancestor| p.matches(ancestor)))
+            .collect()
+    }
+
+    /// Check every pattern, naming the first invalid one
+    pub fn validate(&self) -> Result<(), String> {
+        self.patterns.iter().try_for_each(ExcludePattern::validate)
+    }
+
+    /// Check the patterns that aren't already in `previous`
+    ///
+    /// Patterns saved before validation existed may be invalid. Those are
+    /// returned as warnings instead, so they don't block saving other changes.
+    pub fn validate_changes(&self, previous: &ExcludeConfig) -> Result<Vec<String>, String> {
+        let mut warnings = Vec::new();
+        for pattern in &self.patterns {
+            let Err(e) = pattern.validate() else {
+                continue;
+            };
+            let existed = previous.patterns.iter().any(|old| {
+                old.pattern == pattern.pattern && old.pattern_type == pattern.pattern_type
+            });
+            if !existed {
+                return Err(e);
+            }
+            warnings.push(e);
+        }
+        Ok(warnings)
+    }
+
     /// Add a custom pattern
     pub fn add_pattern(&mut self, pattern: ExcludePattern) {
         self.patterns.push(pattern);
@@ -329,6 +419,79 @@ mod tests {
         assert!(!pattern.matches(Path::new("/root/.cache")));
     }
 
+    #[test]
+    fn test_validate_pattern() {
+        let pattern = |text: &str, pattern_type| {
+            ExcludePattern::new(text.to_string(), pattern_type, "test".to_string())
+        };
+
+        let valid = [
+            ("/var/cache", PatternType::Prefix),
+            ("/home/*/.cache", PatternType::Glob),
+            ("/", PatternType::Exact),
+        ];
+        for (text, pattern_type) in valid {
+            assert!(pattern(text, pattern_type).validate().is_ok(), "{text}");
+        }
+
+        let invalid = [
+            ("", PatternType::Prefix),
+            ("var/cache", PatternType::Prefix),
+            ("/var/cache/", PatternType::Prefix),
+            ("/var/../etc", PatternType::Prefix),
+            ("/home/*/.cache", PatternType::Exact),
+            ("/var/cache", PatternType::Glob),
+            ("/home/*/.cache/[ab]", PatternType::Glob),
+        ];
+        for (text, pattern_type) in invalid {
+            assert!(pattern(text, pattern_type).validate().is_err(), "{text}");
+        }
+
+        assert!(ExcludeConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_changes_allows_existing_invalid_patterns() {
+        let legacy = ExcludePattern::new(
+            "/var/cache/".to_string(),
+            PatternType::Prefix,
+            "legacy".to_string(),
+        );
+        let mut previous = ExcludeConfig::default();
+        previous.add_pattern(legacy.clone());
+
+        // Toggling the legacy pattern only warns about it
+        let mut config = previous.clone();
+        config.patterns.last_mut().unwrap().enabled = false;
+        assert_eq!(config.validate_changes(&previous).unwrap().len(), 1);
+
+        // A newly added invalid pattern is still refused
+        config.add_pattern(ExcludePattern::new(
+            "relative/path".to_string(),
+            PatternType::Prefix,
+            "new".to_string(),
+        ));
+        assert!(config.validate_changes(&previous).is_err());
+
+        // Without the legacy pattern there is nothing to warn about
+        assert!(
+            ExcludeConfig::default()
+                .validate_changes(&previous)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_matching_patterns() {
+        let config = ExcludeConfig::default();
+
+        let matches = config.matching_patterns(Path::new("/home/alice/.cache/thumbnails"));
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].pattern, "/home/*/.cache");
+        assert!(config.matching_patterns(Path::new("/etc/fstab")).is_empty());
+    }
+
     #[test]
     fn test_disabled_pattern() {
         let mut pattern =
@@ -22,6 +22,19 @@ pub fn format_bytes(bytes: u64) -> String {
     format!("{size:.2} {}", UNITS[unit_idx])
 }
 
+/// Format a change in size with its sign, using the same units as `format_bytes`
+///
+/// # Examples
+/// ```
+/// use waypoint_common::format::format_size_delta;
+/// assert_eq!(format_size_delta(1536), "+1.50 KiB");
+/// assert_eq!(format_size_delta(-1048576), "-1.00 MiB");
+/// ```
+pub fn format_size_delta(delta: i64) -> String {
+    let sign = if delta < 0 { '-' } else { '+' };
+    format!("{sign}{}", format_bytes(delta.unsigned_abs()))
+}
+
 /// Format elapsed time into human-readable string
 ///
 /// # Examples
@@ -76,6 +89,13 @@ mod tests {
         assert_eq!(format_bytes(1073741824), "1.00 GiB");
     }
 
+    #[test]
+    fn test_format_size_delta() {
+        assert_eq!(format_size_delta(0), "+0.00 B");
+        assert_eq!(format_size_delta(1024), "+1.00 KiB");
+        assert_eq!(format_size_delta(-1536), "-1.50 KiB");
+    }
+
     #[test]
     fn test_format_elapsed_time() {
         assert_eq!(format_elapsed_time(0), "0s");
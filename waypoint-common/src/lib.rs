@@ -1,9 +1,18 @@
 // Shared types and utilities for Waypoint
 
+pub mod audit;
 pub mod backup_config;
+pub mod capabilities;
+pub mod compression;
 pub mod config;
+pub mod config_bundle;
+pub mod error;
 pub mod exclude;
 pub mod format;
+pub mod metadata_check;
+pub mod metadata_format;
+pub mod path_encoding;
+pub mod permissions;
 pub mod quota;
 pub mod retention;
 pub mod schedules;
@@ -14,13 +23,22 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 pub use backup_config::{
-    BackupConfig, BackupDestinationConfig, BackupFilter, BackupRecord, BackupStatus, PendingBackup,
+    BackupConfig, BackupDestinationConfig, BackupEligibility, BackupFilter, BackupFormat,
+    BackupRecord, BackupStatus, BackupTrigger, FullDestinationAction, PendingBackup,
 };
+pub use capabilities::HelperCapabilities;
+pub use compression::{CompressionUsage, SnapshotCompression};
 pub use config::WaypointConfig;
+pub use config_bundle::ConfigBundle;
+pub use error::{ErrorCode, HelperError};
 pub use exclude::{ExcludeConfig, ExcludePattern, PatternType};
 pub use format::{format_bytes, format_elapsed_time};
-pub use quota::{QuotaConfig, QuotaType, QuotaUsage};
-pub use retention::{SnapshotForRetention, TimelineRetention};
+pub use metadata_check::{ConflictKind, ConflictResolution, MetadataConflict};
+pub use metadata_format::MetadataFormat;
+pub use path_encoding::{decode_path, encode_path};
+pub use permissions::SnapshotDirPermissions;
+pub use quota::{QuotaCleanupReport, QuotaConfig, QuotaType, QuotaUsage, SnapshotSharing};
+pub use retention::{SnapshotForRetention, TimelineRetention, parse_retention_duration};
 pub use schedules::{Schedule, ScheduleType, SchedulesConfig};
 
 /// A package installed on the system
@@ -70,6 +88,325 @@ pub struct SnapshotInfo {
     /// List of subvolumes included in this snapshot (mount points)
     #[serde(default)]
     pub subvolumes: Vec<PathBuf>,
+    /// Prefix of the schedule whose retention applies, overriding the name prefix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention_group: Option<String>,
+    /// When the snapshot is deleted by cleanup, regardless of schedule retention
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Package manager transaction the snapshot was taken before or after
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction: Option<TransactionLink>,
+    /// Why the snapshot was taken
+    #[serde(default, skip_serializing_if = "SnapshotCategory::is_manual")]
+    pub category: SnapshotCategory,
+}
+
+impl SnapshotInfo {
+    /// Drop the package list, keeping only how many packages it had
+    ///
+    /// Package lists make up most of a listing on systems with thousands of
+    /// packages, and the snapshot list only shows the count.
+    ///
+    /// # Examples
+    /// ```
+    /// # use waypoint_common::{Package, SnapshotInfo, SnapshotCategory};
+    /// # let package = Package { name: "linux".into(), version: "6.6.1_1".into() };
+    /// # let snapshot = SnapshotInfo {
+    /// #     name: "daily".into(), timestamp: chrono::Utc::now(), description: None,
+    /// #     package_count: None, packages: vec![package], subvolumes: Vec::new(),
+    /// #     retention_group: None, expires_at: None, transaction: None,
+    /// #     category: SnapshotCategory::Manual,
+    /// # };
+    /// let summary = snapshot.without_packages();
+    /// assert!(summary.packages.is_empty());
+    /// assert_eq!(summary.package_count, Some(1));
+    /// ```
+    pub fn without_packages(mut self) -> Self {
+        if !self.packages.is_empty() {
+            self.package_count = Some(self.packages.len());
+        }
+        self.packages = Vec::new();
+        self
+    }
+}
+
+/// Side of a package manager transaction a snapshot was taken on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransactionPhase {
+    Pre,
+    Post,
+}
+
+/// Links a snapshot to the package manager transaction it was taken around
+///
+/// The pre- and post-transaction snapshots share the transaction ID and name
+/// each other, so the pair can be shown together and the transaction undone
+/// by restoring the pre snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionLink {
+    /// ID from the package manager, or a sequence number assigned by Waypoint
+    pub id: String,
+    pub phase: TransactionPhase,
+    /// Snapshot taken on the other side of the transaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paired_with: Option<String>,
+}
+
+impl TransactionLink {
+    /// Name of the snapshot taken before the transaction
+    ///
+    /// Restoring it undoes the transaction. `name` is the snapshot this link
+    /// belongs to.
+    pub fn pre_snapshot<'a>(&'a self, name: &'a str) -> Option<&'a str> {
+        match self.phase {
+            TransactionPhase::Pre => Some(name),
+            TransactionPhase::Post => self.paired_with.as_deref(),
+        }
+    }
+}
+
+/// Why a snapshot was taken, chosen when it's created
+///
+/// Categories filter and color the snapshot list. Milestones are kept like
+/// favorites: neither retention nor quota cleanup ever deletes them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SnapshotCategory {
+    #[default]
+    Manual,
+    /// Taken before installing updates
+    PreUpdate,
+    /// Taken before changing system configuration
+    PreConfigChange,
+    /// A known-good state worth keeping for good
+    Milestone,
+}
+
+impl SnapshotCategory {
+    pub const ALL: [SnapshotCategory; 4] = [
+        SnapshotCategory::Manual,
+        SnapshotCategory::PreUpdate,
+        SnapshotCategory::PreConfigChange,
+        SnapshotCategory::Milestone,
+    ];
+
+    /// Wire name of the category
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SnapshotCategory::Manual => "manual",
+            SnapshotCategory::PreUpdate => "pre-update",
+            SnapshotCategory::PreConfigChange => "pre-config-change",
+            SnapshotCategory::Milestone => "milestone",
+        }
+    }
+
+    /// Parse a wire name
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.as_str() == s)
+    }
+
+    /// Name shown to users
+    pub fn label(self) -> &'static str {
+        match self {
+            SnapshotCategory::Manual => "Manual",
+            SnapshotCategory::PreUpdate => "Before Update",
+            SnapshotCategory::PreConfigChange => "Before Config Change",
+            SnapshotCategory::Milestone => "Milestone",
+        }
+    }
+
+    pub fn is_manual(&self) -> bool {
+        *self == SnapshotCategory::Manual
+    }
+
+    /// Whether cleanup must never delete snapshots of this category
+    pub fn is_protected(self) -> bool {
+        self == SnapshotCategory::Milestone
+    }
+}
+
+/// What a file restore does with btrfs subvolumes nested inside a restored directory
+///
+/// Nested subvolumes such as `/var/lib/docker` aren't part of the snapshot of
+/// their parent: they show up as empty placeholder directories, or as separate
+/// subvolumes when the snapshot holds one of its own.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NestedSubvolumeMode {
+    /// Leave nested subvolumes alone
+    #[default]
+    Skip,
+    /// Copy them like plain directories
+    Recurse,
+    /// Recreate them as subvolumes and copy their contents
+    Subvolume,
+}
+
+impl NestedSubvolumeMode {
+    pub const ALL: [NestedSubvolumeMode; 3] = [
+        NestedSubvolumeMode::Skip,
+        NestedSubvolumeMode::Recurse,
+        NestedSubvolumeMode::Subvolume,
+    ];
+
+    /// Wire name of the mode
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NestedSubvolumeMode::Skip => "skip",
+            NestedSubvolumeMode::Recurse => "recurse",
+            NestedSubvolumeMode::Subvolume => "subvolume",
+        }
+    }
+
+    /// Parse a wire name
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.as_str() == s)
+    }
+}
+
+/// A command run by the helper, as shown in the command transcript
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CommandRecord {
+    /// Increases by one per command since the helper started
+    pub sequence: u64,
+    /// When the command was started
+    pub timestamp: DateTime<Utc>,
+    /// Command line, quoted so it can be pasted into a shell
+    pub command: String,
+}
+
+/// An ephemeral read-write overlay of a snapshot subvolume
+///
+/// The snapshot is the read-only lower layer and all writes go to a tmpfs upper
+/// layer, so every change is discarded when the overlay is unmounted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OverlayMount {
+    /// Snapshot the overlay is based on
+    pub snapshot_name: String,
+    /// Subvolume mount point within the snapshot (e.g., "/", "/home")
+    pub subvolume: PathBuf,
+    /// Where the writable merged view is mounted
+    pub mount_point: PathBuf,
+    /// When the overlay was created
+    pub created_at: DateTime<Utc>,
+    /// PID of the process that requested the overlay; stale once it exits
+    pub owner_pid: u32,
+}
+
+/// Estimate of how much space deduplicating the snapshot directory could reclaim
+///
+/// Only data that is not already shared between snapshots (exclusive extents) can
+/// be reclaimed, so `exclusive_bytes` is an upper bound. Real savings depend on how
+/// much of that data is identical content, e.g. files rewritten by reinstalls.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DedupEstimate {
+    /// Number of snapshots examined
+    pub snapshot_count: usize,
+    /// Sum of the referenced size of every snapshot
+    pub total_bytes: u64,
+    /// Data referenced by exactly one snapshot
+    pub exclusive_bytes: u64,
+    /// Data already shared between snapshots
+    pub shared_bytes: u64,
+}
+
+/// Space on the filesystem containing a path
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FilesystemStats {
+    /// Size of the filesystem
+    pub total_bytes: u64,
+    /// Free space, including blocks reserved for root
+    pub free_bytes: u64,
+    /// Free space usable by unprivileged users (what `df` shows as available)
+    pub available_bytes: u64,
+    /// Chunk allocation, for btrfs filesystems whose usage could be read
+    pub btrfs: Option<BtrfsAllocation>,
+}
+
+/// Chunk allocation of a btrfs filesystem, from `btrfs filesystem usage`
+///
+/// Btrfs can run out of space for new chunks while `available_bytes` still
+/// looks fine, so a low `unallocated_bytes` is the earlier warning sign.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BtrfsAllocation {
+    /// Size of all devices
+    pub device_size_bytes: u64,
+    /// Space allocated to chunks
+    pub allocated_bytes: u64,
+    /// Space not yet allocated to any chunk
+    pub unallocated_bytes: u64,
+    /// Btrfs' own estimate of free space, taking RAID profiles into account
+    pub free_estimated_bytes: u64,
+    /// Size of data chunks
+    pub data_total_bytes: u64,
+    /// Data stored in data chunks
+    pub data_used_bytes: u64,
+    /// Size of metadata chunks
+    pub metadata_total_bytes: u64,
+    /// Metadata stored in metadata chunks
+    pub metadata_used_bytes: u64,
+}
+
+/// State of a `btrfs balance` on the snapshot filesystem
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BalanceStatus {
+    /// A balance is running (or paused) on the filesystem
+    pub running: bool,
+    /// The running balance is paused
+    pub paused: bool,
+    /// Chunks relocated so far
+    pub chunks_balanced: u64,
+    /// Estimated number of chunks to relocate
+    pub chunks_total: u64,
+    /// Outcome of the last balance started through the helper:
+    /// "complete", "cancelled" or "failed: <reason>"
+    pub last_result: Option<String>,
+}
+
+/// Result of comparing a backup with the snapshot it was made from
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BackupComparison {
+    pub subvolumes: Vec<SubvolumeComparison>,
+}
+
+impl BackupComparison {
+    /// Whether every subvolume of the backup matches its source
+    pub fn matches(&self) -> bool {
+        self.subvolumes.iter().all(|s| s.difference_count == 0)
+    }
+}
+
+/// Comparison of one backed-up subvolume with its source
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SubvolumeComparison {
+    /// Subvolume directory in the snapshot, e.g. `root` or `home`
+    pub subvolume: String,
+    /// How it was compared: "received-uuid" when the backup is an unmodified
+    /// btrfs receive of the source, otherwise "contents"
+    pub method: String,
+    /// Differences found, at most a few hundred
+    pub differences: Vec<String>,
+    /// Number of differences, which may be more than are listed
+    pub difference_count: usize,
+}
+
+/// What restoring a backup would do, from `PreviewRestoreFromBackup`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BackupRestorePreview {
+    /// Name the backup is restored under unless the caller picks another
+    pub snapshot_name: String,
+    /// Description saved with the backup
+    pub description: Option<String>,
+    /// When the backed-up snapshot was taken, if the backup has its metadata
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Mount points of the backed-up subvolumes, if the backup has its metadata
+    pub subvolumes: Vec<PathBuf>,
+    /// Local snapshot of the same name, which the backup can't be restored over
+    pub existing_snapshot: Option<PathBuf>,
+    /// How the local snapshot of the same name differs from the backup
+    pub comparison: Option<BackupComparison>,
 }
 
 /// Result of a snapshot operation
@@ -106,6 +443,24 @@ pub const POLKIT_ACTION_DELETE: &str = "tech.geektoshi.waypoint.delete-snapshot"
 pub const POLKIT_ACTION_RESTORE: &str = "tech.geektoshi.waypoint.restore-snapshot";
 pub const POLKIT_ACTION_CONFIGURE: &str = "tech.geektoshi.waypoint.configure-system";
 
+/// Get the running kernel release from `/proc/sys/kernel/osrelease`
+///
+/// The GUI and the helper both record and compare this value, so they must
+/// read it the same way.
+pub fn kernel_version() -> Option<String> {
+    std::fs::read_to_string("/proc/sys/kernel/osrelease")
+        .ok()
+        .and_then(|release| parse_kernel_release(&release))
+}
+
+/// Parse a kernel release as printed by `uname -r` or procfs
+///
+/// Returns `None` if the output is blank.
+pub fn parse_kernel_release(output: &str) -> Option<String> {
+    let release = output.trim();
+    (!release.is_empty()).then(|| release.to_string())
+}
+
 /// Validate snapshot name for security and filesystem compatibility
 ///
 /// # Arguments
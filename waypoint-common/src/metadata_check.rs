@@ -0,0 +1,103 @@
+//! Consistency check of the snapshot metadata
+//!
+//! Crashes during creation, renaming or deletion can leave metadata entries
+//! that contradict each other or their snapshot directory. The helper finds
+//! them and resolves them as the user chooses.
+
+use serde::{Deserialize, Serialize};
+
+/// Kind of inconsistency between snapshot metadata entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictKind {
+    /// Several entries for the same snapshot name
+    DuplicateName,
+    /// Entries with different names for the same snapshot directory
+    SharedPath,
+    /// An entry named differently than its directory, e.g. after an interrupted rename
+    NameMismatch,
+}
+
+/// Metadata entries that contradict each other or their snapshot directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetadataConflict {
+    pub kind: ConflictKind,
+    /// Name of the snapshot directory, which resolving the conflict gives the entry
+    pub name: String,
+    /// IDs of the entries involved, in metadata order
+    pub ids: Vec<String>,
+}
+
+impl MetadataConflict {
+    /// What is wrong, for the maintenance view
+    pub fn description(&self) -> String {
+        match self.kind {
+            ConflictKind::DuplicateName => {
+                format!("{} entries describe the same snapshot", self.ids.len())
+            }
+            ConflictKind::SharedPath => format!(
+                "{} entries with different names point at this snapshot's directory",
+                self.ids.len()
+            ),
+            ConflictKind::NameMismatch => {
+                "The entry has another name than the snapshot's directory".to_string()
+            }
+        }
+    }
+}
+
+/// How to resolve a `MetadataConflict`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictResolution {
+    /// Combine the entries into one named after the directory, keeping details
+    /// that only some of them have
+    Merge,
+    /// Keep the entry that matches the directory best and drop the others
+    RemoveExtra,
+}
+
+impl ConflictResolution {
+    /// Wire name of the resolution
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ConflictResolution::Merge => "merge",
+            ConflictResolution::RemoveExtra => "remove-extra",
+        }
+    }
+
+    /// Parse a wire name
+    pub fn parse(s: &str) -> Option<Self> {
+        [ConflictResolution::Merge, ConflictResolution::RemoveExtra]
+            .into_iter()
+            .find(|r| r.as_str() == s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conflict_wire_format() {
+        let conflict = MetadataConflict {
+            kind: ConflictKind::SharedPath,
+            name: "weekly".to_string(),
+            ids: vec!["3".to_string(), "4".to_string()],
+        };
+        let json = serde_json::to_string(&conflict).unwrap();
+        assert!(json.contains("\"shared-path\""));
+        assert_eq!(
+            serde_json::from_str::<MetadataConflict>(&json).unwrap(),
+            conflict
+        );
+
+        for resolution in [ConflictResolution::Merge, ConflictResolution::RemoveExtra] {
+            assert_eq!(
+                ConflictResolution::parse(resolution.as_str()),
+                Some(resolution)
+            );
+        }
+        assert_eq!(ConflictResolution::parse("delete"), None);
+    }
+}
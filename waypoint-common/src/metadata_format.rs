@@ -0,0 +1,139 @@
+//! Serialization of the snapshot metadata file
+//!
+//! The metadata file is JSON by default. Pointing `WAYPOINT_METADATA_FILE` at
+//! a file ending in `.toml` stores it as TOML instead, which is easier to edit
+//! and diff by hand. The format follows the extension, so the GUI and the
+//! helper must both read and write the file through here.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Format of the snapshot metadata file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataFormat {
+    Json,
+    Toml,
+}
+
+/// A TOML document is a table, so the entries go under `[[snapshots]]`
+#[derive(Serialize, Deserialize)]
+struct TomlDocument<T> {
+    #[serde(default)]
+    snapshots: T,
+}
+
+impl MetadataFormat {
+    /// Format for a metadata file, by its extension
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use waypoint_common::MetadataFormat;
+    /// assert_eq!(MetadataFormat::from_path(Path::new("snapshots.toml")), MetadataFormat::Toml);
+    /// assert_eq!(MetadataFormat::from_path(Path::new("snapshots.json")), MetadataFormat::Json);
+    /// ```
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => Self::Toml,
+            _ => Self::Json,
+        }
+    }
+
+    /// Parse the entries of a metadata file
+    ///
+    /// An empty TOML file has no entries.
+    pub fn parse<T: DeserializeOwned>(self, content: &str) -> Result<Vec<T>> {
+        match self {
+            Self::Json => serde_json::from_str(content).context("Invalid JSON metadata"),
+            Self::Toml => toml::from_str::<TomlDocument<Vec<T>>>(content)
+                .map(|document| document.snapshots)
+                .context("Invalid TOML metadata"),
+        }
+    }
+
+    /// Serialize entries for a metadata file
+    pub fn serialize<T: Serialize>(self, entries: &[T]) -> Result<String> {
+        match self {
+            Self::Json => serde_json::to_string_pretty(entries).context("Failed to serialize JSON"),
+            Self::Toml => toml::to_string_pretty(&TomlDocument { snapshots: entries })
+                .context("Failed to serialize TOML"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Package, SnapshotCategory, SnapshotInfo, TransactionLink, TransactionPhase};
+    use chrono::{TimeZone, Utc};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_toml_round_trip() {
+        let snapshots = vec![
+            SnapshotInfo {
+                name: "daily-20251110-0300".to_string(),
+                timestamp: Utc.with_ymd_and_hms(2025, 11, 10, 3, 0, 0).unwrap(),
+                description: Some("Before \"upgrade\"".to_string()),
+                package_count: Some(1),
+                packages: vec![Package {
+                    name: "linux".to_string(),
+                    version: "6.6.1_1".to_string(),
+                }],
+                subvolumes: vec![PathBuf::from("/"), PathBuf::from("/home")],
+                retention_group: Some("weekly".to_string()),
+                expires_at: Some(Utc.with_ymd_and_hms(2025, 12, 1, 0, 0, 0).unwrap()),
+                transaction: Some(TransactionLink {
+                    id: "4521".to_string(),
+                    phase: TransactionPhase::Pre,
+                    paired_with: Some("post-transaction-20251110-0301".to_string()),
+                }),
+                category: SnapshotCategory::PreUpdate,
+            },
+            SnapshotInfo {
+                name: "manual".to_string(),
+                timestamp: Utc.with_ymd_and_hms(2025, 11, 11, 12, 0, 0).unwrap(),
+                description: None,
+                package_count: None,
+                packages: Vec::new(),
+                subvolumes: Vec::new(),
+                retention_group: None,
+                expires_at: None,
+                transaction: None,
+                category: SnapshotCategory::Manual,
+            },
+        ];
+
+        let toml = MetadataFormat::Toml.serialize(&snapshots).unwrap();
+        assert!(toml.contains("[[snapshots]]"));
+        assert!(toml.contains("category = \"pre-update\""));
+        let parsed: Vec<SnapshotInfo> = MetadataFormat::Toml.parse(&toml).unwrap();
+
+        // Compare through JSON, which covers every field
+        assert_eq!(
+            MetadataFormat::Json.serialize(&parsed).unwrap(),
+            MetadataFormat::Json.serialize(&snapshots).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_toml_defaults() {
+        // Hand-written entries may leave out fields with defaults
+        let toml = r#"
+            [[snapshots]]
+            name = "manual"
+            timestamp = "2025-11-11T12:00:00Z"
+            packages = []
+        "#;
+        let parsed: Vec<SnapshotInfo> = MetadataFormat::Toml.parse(toml).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert!(parsed[0].subvolumes.is_empty());
+        assert!(parsed[0].description.is_none());
+        assert_eq!(parsed[0].category, SnapshotCategory::Manual);
+
+        let empty: Vec<SnapshotInfo> = MetadataFormat::Toml.parse("").unwrap();
+        assert!(empty.is_empty());
+    }
+}
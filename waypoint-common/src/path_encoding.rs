@@ -0,0 +1,105 @@
+//! Lossless text form of file paths
+//!
+//! Linux file names are arbitrary bytes, but D-Bus strings and JSON must be
+//! UTF-8. Paths crossing the helper boundary (comparison results, files to
+//! restore) use this encoding so names that aren't valid UTF-8 survive the
+//! round-trip instead of being replaced with U+FFFD: bytes that aren't valid
+//! UTF-8 become `\xNN` and backslashes become `\\`. Everything else is passed
+//! through, so ordinary paths look the same as before.
+
+use std::ffi::OsString;
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::path::{Path, PathBuf};
+
+/// Encode a path as UTF-8 text
+pub fn encode_path(path: &Path) -> String {
+    let mut encoded = String::new();
+    for chunk in path.as_os_str().as_bytes().utf8_chunks() {
+        for c in chunk.valid().chars() {
+            if c == '\\' {
+                encoded.push_str("\\\\");
+            } else {
+                encoded.push(c);
+            }
+        }
+        for byte in chunk.invalid() {
+            encoded.push_str(&format!("\\x{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+/// Decode a path encoded with `encode_path`
+///
+/// A backslash that doesn't start an escape is kept as is, so plain paths
+/// from older clients decode to themselves.
+pub fn decode_path(encoded: &str) -> PathBuf {
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'\\' {
+            if bytes.get(i + 1) == Some(&b'\\') {
+                decoded.push(b'\\');
+                i += 2;
+                continue;
+            }
+            if bytes.get(i + 1) == Some(&b'x') {
+                let byte = encoded
+                    .get(i + 2..i + 4)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                if let Some(byte) = byte {
+                    decoded.push(byte);
+                    i += 4;
+                    continue;
+                }
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    PathBuf::from(OsString::from_vec(decoded))
+}
+
+/// Whether an encoded path stands for a name that isn't valid UTF-8
+///
+/// Such paths can only be shown with their escapes, so the GUI flags them.
+pub fn is_non_utf8(encoded: &str) -> bool {
+    decode_path(encoded).to_str().is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsStr;
+
+    #[test]
+    fn test_roundtrip_non_utf8() {
+        let path = Path::new(OsStr::from_bytes(b"/home/user/caf\xe9.txt"));
+        let encoded = encode_path(path);
+        assert_eq!(encoded, "/home/user/caf\\xE9.txt");
+        assert_eq!(decode_path(&encoded), path);
+        assert!(is_non_utf8(&encoded));
+    }
+
+    #[test]
+    fn test_roundtrip_backslashes_and_utf8() {
+        let path = Path::new("/tmp/a\\xE9 \\ b/ñ");
+        let encoded = encode_path(path);
+        assert_eq!(encoded, "/tmp/a\\\\xE9 \\\\ b/ñ");
+        assert_eq!(decode_path(&encoded), path);
+        assert!(!is_non_utf8(&encoded));
+
+        // Plain paths are unchanged
+        assert_eq!(encode_path(Path::new("/etc/fstab")), "/etc/fstab");
+    }
+
+    #[test]
+    fn test_decode_unescaped_backslash() {
+        assert_eq!(decode_path("/tmp/a\\b"), Path::new("/tmp/a\\b"));
+        assert_eq!(decode_path("/tmp/a\\xZZ"), Path::new("/tmp/a\\xZZ"));
+        assert_eq!(decode_path("/tmp/end\\"), Path::new("/tmp/end\\"));
+    }
+}
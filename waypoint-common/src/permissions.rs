@@ -0,0 +1,121 @@
+//! Permissions of the snapshot directories
+//!
+//! Files in a snapshot keep the permissions they had when it was taken, so a
+//! key or password file that was readable then stays readable in every older
+//! snapshot of `/`, even after it was locked down. Keeping the snapshot
+//! directory itself root-only (mode 700) closes that for all snapshots at once.
+
+use serde::{Deserialize, Serialize};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+
+use crate::WaypointConfig;
+
+/// Mode of a hardened snapshot directory
+pub const SECURE_SNAPSHOT_DIR_MODE: u32 = 0o700;
+
+/// Owner and permissions of a snapshot directory
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SnapshotDirPermissions {
+    pub path: PathBuf,
+    /// Permission bits, e.g. `0o755`
+    pub mode: u32,
+    pub owner_uid: u32,
+}
+
+impl SnapshotDirPermissions {
+    /// Read the owner and permissions of a directory
+    pub fn inspect(path: &Path) -> std::io::Result<Self> {
+        let metadata = std::fs::metadata(path)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            mode: metadata.mode() & 0o7777,
+            owner_uid: metadata.uid(),
+        })
+    }
+
+    /// Whether only root can get into the directory
+    pub fn is_secure(&self) -> bool {
+        self.owner_uid == 0 && self.mode & 0o077 == 0
+    }
+
+    /// Whether every user on the system can get into the directory
+    pub fn is_world_accessible(&self) -> bool {
+        self.mode & 0o005 != 0
+    }
+
+    /// Permissions in `ls -l` style, e.g. `rwxr-xr-x`
+    pub fn mode_string(&self) -> String {
+        let flags = ['r', 'w', 'x'];
+        (0..9)
+            .map(|bit| {
+                if self.mode & (0o400 >> bit) != 0 {
+                    flags[bit % 3]
+                } else {
+                    '-'
+                }
+            })
+            .collect()
+    }
+}
+
+/// Whether `path` exists as far as a process without root can tell
+///
+/// Inside a root-only snapshot directory every lookup fails with "permission
+/// denied", which `Path::exists` reports as missing. Such paths count as
+/// existing here, since only the helper can check them.
+pub fn exists_or_hidden(path: &Path) -> bool {
+    path.try_exists()
+        .unwrap_or_else(|e| e.kind() == std::io::ErrorKind::PermissionDenied)
+}
+
+/// Owner and permissions of every snapshot directory that exists
+pub fn inspect_snapshot_dirs(config: &WaypointConfig) -> Vec<SnapshotDirPermissions> {
+    config
+        .snapshot_dirs()
+        .iter()
+        .filter_map(|dir| SnapshotDirPermissions::inspect(dir).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_snapshot_dir_permissions() {
+        let permissions = |mode, owner_uid| SnapshotDirPermissions {
+            path: PathBuf::from("/.snapshots"),
+            mode,
+            owner_uid,
+        };
+
+        assert!(permissions(0o700, 0).is_secure());
+        assert!(permissions(0o500, 0).is_secure());
+        assert!(!permissions(0o750, 0).is_secure());
+        assert!(!permissions(0o700, 1000).is_secure());
+
+        assert!(permissions(0o755, 0).is_world_accessible());
+        assert!(!permissions(0o750, 0).is_world_accessible());
+
+        assert_eq!(permissions(0o755, 0).mode_string(), "rwxr-xr-x");
+        assert_eq!(permissions(0o700, 0).mode_string(), "rwx------");
+    }
+
+    #[test]
+    fn test_inspect() {
+        let dir =
+            std::env::temp_dir().join(format!("waypoint-permissions-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o751)).unwrap();
+
+        let permissions = SnapshotDirPermissions::inspect(&dir).unwrap();
+        assert_eq!(permissions.mode, 0o751);
+        assert!(permissions.is_world_accessible());
+        assert!(exists_or_hidden(&dir));
+        assert!(!exists_or_hidden(&dir.join("missing")));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -45,6 +45,11 @@ pub struct QuotaConfig {
     /// Whether to automatically clean up old snapshots when quota is exceeded
     #[serde(default = "default_auto_cleanup")]
     pub auto_cleanup: bool,
+
+    /// Warn when a snapshot's exclusive size exceeds this many bytes
+    /// None means no warning
+    #[serde(default)]
+    pub size_warning_bytes: Option<u64>,
 }
 
 fn default_cleanup_threshold() -> f64 {
@@ -64,6 +69,7 @@ impl Default for QuotaConfig {
             per_snapshot_limit_bytes: None,
             cleanup_threshold: default_cleanup_threshold(),
             auto_cleanup: default_auto_cleanup(),
+            size_warning_bytes: None,
         }
     }
 }
@@ -102,6 +108,16 @@ impl QuotaConfig {
         Ok(())
     }
 
+    /// Check whether a snapshot's exclusive size exceeds the warning threshold
+    ///
+    /// Only applies when quotas are enabled, since exclusive sizes come from qgroups.
+    pub fn exceeds_size_warning(&self, exclusive_bytes: u64) -> bool {
+        self.enabled
+            && self
+                .size_warning_bytes
+                .is_some_and(|limit| limit > 0 && exclusive_bytes > limit)
+    }
+
     /// Parse a human-readable size string (e.g., "50G", "1T", "500M")
     pub fn parse_size(size_str: &str) -> anyhow::Result<u64> {
         let size_str = size_str.trim().to_uppercase();
@@ -184,6 +200,62 @@ impl QuotaUsage {
             .map(|pct| pct >= threshold)
             .unwrap_or(false)
     }
+
+    /// Pick the snapshots to delete to bring usage down to `target` (0.0-1.0)
+    ///
+    /// `candidates` are `(name, bytes)` pairs, oldest first, with protected
+    /// snapshots already left out. Each deletion is assumed to free its bytes,
+    /// so this is an estimate of what auto-cleanup will remove.
+    pub fn plan_cleanup(&self, candidates: &[(String, u64)], target: f64) -> Vec<String> {
+        let Some(limit) = self.limit.filter(|limit| *limit > 0) else {
+            return Vec::new();
+        };
+
+        let mut remaining = self.referenced;
+        let mut planned = Vec::new();
+        for (name, bytes) in candidates {
+            if remaining as f64 / limit as f64 <= target {
+                break;
+            }
+            remaining = remaining.saturating_sub(*bytes);
+            planned.push(name.clone());
+        }
+        planned
+    }
+}
+
+/// How much snapshot data is shared through btrfs extents
+///
+/// Snapshots reference the same extents as each other and as the live
+/// subvolumes, so adding up their sizes overstates what they cost on disk.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotSharing {
+    /// Sum of the referenced bytes of every snapshot subvolume
+    pub referenced_sum: u64,
+    /// Bytes that only snapshots use, i.e. what deleting all of them would free
+    pub exclusive: u64,
+    /// Number of snapshot subvolumes counted
+    pub subvolumes: usize,
+}
+
+impl SnapshotSharing {
+    /// Bytes the snapshots reference but don't take up on their own
+    pub fn shared_bytes(&self) -> u64 {
+        self.referenced_sum.saturating_sub(self.exclusive)
+    }
+}
+
+/// Snapshots removed by quota auto-cleanup, or that a preview expects it to remove
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuotaCleanupReport {
+    /// Whether this is a preview and nothing was deleted
+    pub dry_run: bool,
+
+    /// Snapshot names, oldest first
+    pub snapshots: Vec<String>,
+
+    /// Favorites and milestones that were kept even though they are among the oldest
+    pub skipped_favorites: Vec<String>,
 }
 
 #[cfg(test)]
@@ -229,6 +301,27 @@ mod tests {
         assert!(!usage.exceeds_threshold(0.6));
     }
 
+    #[test]
+    fn test_plan_cleanup() {
+        let usage = QuotaUsage {
+            referenced: 95,
+            exclusive: 0,
+            limit: Some(100),
+        };
+        let candidates = vec![
+            ("old".to_string(), 10),
+            ("older".to_string(), 10),
+            ("newest".to_string(), 10),
+        ];
+
+        // 95% -> 85% -> 75%, which is at or below the 80% target
+        assert_eq!(usage.plan_cleanup(&candidates, 0.8), vec!["old", "older"]);
+        assert!(usage.plan_cleanup(&candidates, 0.95).is_empty());
+
+        let unlimited = QuotaUsage { limit: None, ..usage };
+        assert!(unlimited.plan_cleanup(&candidates, 0.8).is_empty());
+    }
+
     #[test]
     fn test_default_config() {
         let config = QuotaConfig::default();
@@ -237,6 +330,29 @@ mod tests {
         assert_eq!(config.total_limit_bytes, None);
         assert_eq!(config.cleanup_threshold, 0.9);
         assert!(config.auto_cleanup);
+        assert_eq!(config.size_warning_bytes, None);
+    }
+
+    #[test]
+    fn test_exceeds_size_warning() {
+        let mut config = QuotaConfig {
+            enabled: true,
+            size_warning_bytes: Some(10 * 1024 * 1024 * 1024), // 10 GB
+            ..Default::default()
+        };
+
+        assert!(config.exceeds_size_warning(11 * 1024 * 1024 * 1024));
+        assert!(!config.exceeds_size_warning(10 * 1024 * 1024 * 1024));
+        assert!(!config.exceeds_size_warning(1024));
+
+        // No warning when quotas are disabled (sizes aren't exclusive)
+        config.enabled = false;
+        assert!(!config.exceeds_size_warning(11 * 1024 * 1024 * 1024));
+
+        // Zero means disabled
+        config.enabled = true;
+        config.size_warning_bytes = Some(0);
+        assert!(!config.exceeds_size_warning(1));
     }
 
     #[test]
@@ -29,6 +29,26 @@ pub struct TimelineRetention {
 
     /// Number of yearly snapshots to keep (0 = disabled)
     pub yearly_limit: u32,
+
+    /// How long to keep hourly snapshots, e.g. "2w"; overrides `hourly_limit`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hourly_for: Option<String>,
+
+    /// How long to keep daily snapshots, e.g. "3mo"; overrides `daily_limit`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_for: Option<String>,
+
+    /// How long to keep weekly snapshots; overrides `weekly_limit`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weekly_for: Option<String>,
+
+    /// How long to keep monthly snapshots; overrides `monthly_limit`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly_for: Option<String>,
+
+    /// How long to keep yearly snapshots; overrides `yearly_limit`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yearly_for: Option<String>,
 }
 
 impl Default for TimelineRetention {
@@ -39,11 +59,28 @@ impl Default for TimelineRetention {
             weekly_limit: 4,
             monthly_limit: 3,
             yearly_limit: 0,
+            ..Self::no_durations()
         }
     }
 }
 
 impl TimelineRetention {
+    /// Limits only, without any durations set
+    fn no_durations() -> Self {
+        Self {
+            hourly_limit: 0,
+            daily_limit: 0,
+            weekly_limit: 0,
+            monthly_limit: 0,
+            yearly_limit: 0,
+            hourly_for: None,
+            daily_for: None,
+            weekly_for: None,
+            monthly_for: None,
+            yearly_for: None,
+        }
+    }
+
     /// Create retention for hourly schedule
     pub fn for_hourly() -> Self {
         Self {
@@ -52,6 +89,7 @@ impl TimelineRetention {
             weekly_limit: 0,
             monthly_limit: 0,
             yearly_limit: 0,
+            ..Self::no_durations()
         }
     }
 
@@ -63,6 +101,7 @@ impl TimelineRetention {
             weekly_limit: 0,
             monthly_limit: 0,
             yearly_limit: 0,
+            ..Self::no_durations()
         }
     }
 
@@ -74,6 +113,7 @@ impl TimelineRetention {
             weekly_limit: 4,
             monthly_limit: 0,
             yearly_limit: 0,
+            ..Self::no_durations()
         }
     }
 
@@ -85,8 +125,91 @@ impl TimelineRetention {
             weekly_limit: 0,
             monthly_limit: 12,
             yearly_limit: 0,
+            ..Self::no_durations()
         }
     }
+
+    /// Check that every duration parses
+    pub fn validate(&self) -> Result<(), String> {
+        for (tier, duration) in self.durations() {
+            if let Some(duration) = duration {
+                parse_retention_duration(duration)
+                    .map_err(|e| format!("Invalid {tier} retention: {e}"))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Copy with each duration converted into its bucket limit
+    ///
+    /// A duration covers as many buckets as it spans, rounded up, so "2w" of
+    /// hourlies keeps 336 hourly snapshots. Durations that don't parse leave
+    /// the limit unchanged.
+    pub fn resolved(&self) -> Self {
+        let limit = |duration: &Option<String>, bucket_hours: i64, fallback: u32| {
+            duration
+                .as_deref()
+                .and_then(|d| parse_retention_duration(d).ok())
+                .map(|d| {
+                    let buckets = (d.num_hours() + bucket_hours - 1) / bucket_hours;
+                    u32::try_from(buckets).unwrap_or(u32::MAX)
+                })
+                .unwrap_or(fallback)
+        };
+
+        Self {
+            hourly_limit: limit(&self.hourly_for, 1, self.hourly_limit),
+            daily_limit: limit(&self.daily_for, 24, self.daily_limit),
+            weekly_limit: limit(&self.weekly_for, 24 * 7, self.weekly_limit),
+            monthly_limit: limit(&self.monthly_for, 24 * 30, self.monthly_limit),
+            yearly_limit: limit(&self.yearly_for, 24 * 365, self.yearly_limit),
+            ..self.clone()
+        }
+    }
+
+    fn durations(&self) -> [(&'static str, &Option<String>); 5] {
+        [
+            ("hourly", &self.hourly_for),
+            ("daily", &self.daily_for),
+            ("weekly", &self.weekly_for),
+            ("monthly", &self.monthly_for),
+            ("yearly", &self.yearly_for),
+        ]
+    }
+}
+
+/// Parse a human retention duration like "36h", "2w", "3mo" or "1y"
+///
+/// Units are h, d, w, mo and y (or their full names). Months count as 30
+/// days and years as 365, matching the timeline buckets.
+pub fn parse_retention_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim().to_lowercase();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+
+    let count: i64 = number
+        .parse()
+        .map_err(|_| format!("'{input}' must start with a number, e.g. 2w"))?;
+    if count == 0 {
+        return Err(format!("'{input}' must be longer than zero"));
+    }
+    // Keep well clear of chrono's limits
+    if count > 100_000 {
+        return Err(format!("'{input}' is too long"));
+    }
+
+    let days = match unit.trim() {
+        "h" | "hour" | "hours" => return Ok(Duration::hours(count)),
+        "d" | "day" | "days" => 1,
+        "w" | "week" | "weeks" => 7,
+        "mo" | "month" | "months" => 30,
+        "y" | "year" | "years" => 365,
+        "" => return Err(format!("'{input}' needs a unit: h, d, w, mo or y")),
+        other => return Err(format!("Unknown unit '{other}'; use h, d, w, mo or y")),
+    };
+    Ok(Duration::days(count * days))
 }
 
 /// Time bucket for grouping snapshots
@@ -153,6 +276,7 @@ pub fn apply_timeline_retention(
     now: DateTime<Utc>,
 ) -> Vec<String> {
     let mut to_keep = HashSet::new();
+    let retention = &retention.resolved();
 
     // Sort by timestamp (newest first) for easier processing
     let mut sorted = snapshots.to_vec();
@@ -414,4 +538,57 @@ mod tests {
         // Snapshot from 10 days ago: outside daily range but within weekly range
         assert_eq!(to_delete.len(), 0);
     }
+
+    #[test]
+    fn test_parse_retention_duration() {
+        assert_eq!(parse_retention_duration("36h"), Ok(Duration::hours(36)));
+        assert_eq!(parse_retention_duration("2w"), Ok(Duration::days(14)));
+        assert_eq!(parse_retention_duration(" 3 months "), Ok(Duration::days(90)));
+        assert_eq!(parse_retention_duration("1Y"), Ok(Duration::days(365)));
+
+        assert!(parse_retention_duration("").is_err());
+        assert!(parse_retention_duration("2").is_err());
+        assert!(parse_retention_duration("0d").is_err());
+        assert!(parse_retention_duration("w").is_err());
+        assert!(parse_retention_duration("2fortnights").is_err());
+    }
+
+    #[test]
+    fn test_duration_retention() {
+        let retention = TimelineRetention {
+            hourly_for: Some("2w".to_string()),
+            daily_for: Some("3mo".to_string()),
+            ..TimelineRetention::for_hourly()
+        };
+        assert!(retention.validate().is_ok());
+
+        let resolved = retention.resolved();
+        assert_eq!(resolved.hourly_limit, 336);
+        assert_eq!(resolved.daily_limit, 90);
+        assert_eq!(resolved.weekly_limit, 0);
+
+        let now = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        let snapshots = vec![
+            SnapshotForRetention {
+                name: "hourly-10days".to_string(),
+                timestamp: now - Duration::days(10),
+            },
+            SnapshotForRetention {
+                name: "hourly-60days".to_string(),
+                timestamp: now - Duration::days(60),
+            },
+            SnapshotForRetention {
+                name: "hourly-100days".to_string(),
+                timestamp: now - Duration::days(100),
+            },
+        ];
+        let to_delete = apply_timeline_retention(&snapshots, &retention, now);
+        assert_eq!(to_delete, vec!["hourly-100days".to_string()]);
+
+        let invalid = TimelineRetention {
+            weekly_for: Some("soon".to_string()),
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
 }
@@ -1,8 +1,10 @@
 // Snapshot schedule configuration with TOML support
 
+use chrono::{DateTime, Local, NaiveTime, TimeDelta, Timelike};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use crate::SnapshotCategory;
 use crate::retention::TimelineRetention;
 
 /// Type of snapshot schedule
@@ -76,6 +78,34 @@ pub struct Schedule {
     /// If empty, defaults to ["/"]
     #[serde(default)]
     pub subvolumes: Vec<PathBuf>,
+
+    /// Subvolumes temporarily left out of this schedule's snapshots
+    /// They stay in `subvolumes`, so including them again needs no schedule edit
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excluded_subvolumes: Vec<PathBuf>,
+
+    /// Whether snapshots from this schedule are backed up automatically
+    #[serde(default = "default_auto_backup")]
+    pub auto_backup: bool,
+
+    /// Category of the snapshots this schedule creates
+    /// Milestone schedules keep their snapshots out of retention cleanup
+    #[serde(default, skip_serializing_if = "SnapshotCategory::is_manual")]
+    pub category: SnapshotCategory,
+
+    /// Start of the time of day the schedule may fire in (HH:MM format)
+    /// Set together with `active_end`; runs outside the window are skipped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_start: Option<String>,
+
+    /// End of the time of day the schedule may fire in (HH:MM format, inclusive)
+    /// Earlier than `active_start` for windows that cross midnight
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_end: Option<String>,
+}
+
+fn default_auto_backup() -> bool {
+    true
 }
 
 impl Schedule {
@@ -93,6 +123,11 @@ impl Schedule {
             keep_days: 1,
             timeline_retention: Some(TimelineRetention::for_hourly()),
             subvolumes: vec![PathBuf::from("/")],
+            excluded_subvolumes: Vec::new(),
+            auto_backup: true,
+            category: SnapshotCategory::Manual,
+            active_start: None,
+            active_end: None,
         }
     }
 
@@ -110,6 +145,11 @@ impl Schedule {
             keep_days: 7,
             timeline_retention: Some(TimelineRetention::for_daily()),
             subvolumes: vec![PathBuf::from("/")],
+            excluded_subvolumes: Vec::new(),
+            auto_backup: true,
+            category: SnapshotCategory::Manual,
+            active_start: None,
+            active_end: None,
         }
     }
 
@@ -127,6 +167,11 @@ impl Schedule {
             keep_days: 28,
             timeline_retention: Some(TimelineRetention::for_weekly()),
             subvolumes: vec![PathBuf::from("/")],
+            excluded_subvolumes: Vec::new(),
+            auto_backup: true,
+            category: SnapshotCategory::Manual,
+            active_start: None,
+            active_end: None,
         }
     }
 
@@ -144,9 +189,90 @@ impl Schedule {
             keep_days: 90,
             timeline_retention: Some(TimelineRetention::for_monthly()),
             subvolumes: vec![PathBuf::from("/")],
+            excluded_subvolumes: Vec::new(),
+            auto_backup: true,
+            category: SnapshotCategory::Manual,
+            active_start: None,
+            active_end: None,
+        }
+    }
+
+    /// Name of the snapshot this schedule creates at `time`
+    ///
+    /// The prefix followed by the local time to the minute, e.g.
+    /// "daily-20250101-0200". Fails if the prefix isn't a valid snapshot name.
+    pub fn snapshot_name(&self, time: DateTime<Local>) -> Result<String, String> {
+        crate::validate_snapshot_name(&self.prefix)
+            .map_err(|e| format!("Invalid schedule prefix '{}': {e}", self.prefix))?;
+        Ok(format!("{}-{}", self.prefix, time.format("%Y%m%d-%H%M")))
+    }
+
+    /// Mount points to snapshot, the root filesystem if none are configured
+    pub fn snapshot_subvolumes(&self) -> Vec<String> {
+        let subvolumes: Vec<String> = self
+            .subvolumes
+            .iter()
+            .filter_map(|p| p.to_str().map(|s| s.to_string()))
+            .collect();
+        if subvolumes.is_empty() {
+            vec!["/".to_string()]
+        } else {
+            subvolumes
+        }
+    }
+
+    /// Mount points to snapshot now, leaving out temporarily excluded ones
+    ///
+    /// Empty when every subvolume is excluded.
+    pub fn active_subvolumes(&self) -> Vec<String> {
+        self.snapshot_subvolumes()
+            .into_iter()
+            .filter(|subvolume| !self.is_subvolume_excluded(Path::new(subvolume)))
+            .collect()
+    }
+
+    /// Whether a subvolume is temporarily left out of this schedule's snapshots
+    pub fn is_subvolume_excluded(&self, subvolume: &Path) -> bool {
+        self.excluded_subvolumes.iter().any(|p| p == subvolume)
+    }
+
+    /// Temporarily leave a subvolume out of this schedule's snapshots, or include it again
+    pub fn set_subvolume_excluded(&mut self, subvolume: &Path, excluded: bool) {
+        self.excluded_subvolumes.retain(|p| p != subvolume);
+        if excluded {
+            self.excluded_subvolumes.push(subvolume.to_path_buf());
+        }
+    }
+
+    /// Active hours as start and end times, None when the schedule may fire at any time
+    pub fn active_window(&self) -> Option<(NaiveTime, NaiveTime)> {
+        let start = NaiveTime::parse_from_str(self.active_start.as_deref()?, "%H:%M").ok()?;
+        let end = NaiveTime::parse_from_str(self.active_end.as_deref()?, "%H:%M").ok()?;
+        Some((start, end))
+    }
+
+    /// Whether the schedule may fire at `time` of day
+    ///
+    /// Both ends of the window are included. A window whose end is earlier
+    /// than its start crosses midnight, e.g. 22:00-06:00.
+    pub fn is_active_at(&self, time: NaiveTime) -> bool {
+        match self.active_window() {
+            None => true,
+            Some((start, end)) if start <= end => start <= time && time <= end,
+            Some((start, end)) => time >= start || time <= end,
         }
     }
 
+    /// Next full hour after `now` that falls within the active hours
+    ///
+    /// None only when no full hour of the day is inside the window.
+    pub fn next_hourly_run(&self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        let top_of_hour = now.with_minute(0)?.with_second(0)?.with_nanosecond(0)?;
+        (1..=24)
+            .map(|hours| top_of_hour + TimeDelta::hours(hours))
+            .find(|next| self.is_active_at(next.time()))
+    }
+
     /// Validate this schedule configuration
     pub fn validate(&self) -> Result<(), String> {
         // Validate time format if present
@@ -174,6 +300,44 @@ impl Schedule {
             }
         }
 
+        if let Some(ref timeline) = self.timeline_retention {
+            timeline.validate()?;
+        }
+
+        // Validate active hours if present
+        for time in [&self.active_start, &self.active_end].into_iter().flatten() {
+            if !is_valid_time_format(time) {
+                return Err(format!(
+                    "Invalid active hours time '{time}'. Expected HH:MM (24-hour)"
+                ));
+            }
+        }
+        if self.active_start.is_some() != self.active_end.is_some() {
+            return Err("Active hours need both 'active_start' and 'active_end'".to_string());
+        }
+        if let Some((start, end)) = self.active_window() {
+            if start == end {
+                return Err("Active hours must not start and end at the same time".to_string());
+            }
+            let fires_outside = match self.schedule_type {
+                ScheduleType::Hourly => !(0..24)
+                    .filter_map(|hour| NaiveTime::from_hms_opt(hour, 0, 0))
+                    .any(|time| self.is_active_at(time)),
+                _ => self
+                    .time
+                    .as_deref()
+                    .and_then(|time| NaiveTime::parse_from_str(time, "%H:%M").ok())
+                    .is_some_and(|time| !self.is_active_at(time)),
+            };
+            if fires_outside {
+                return Err(format!(
+                    "Schedule never runs within its active hours {} - {}",
+                    start.format("%H:%M"),
+                    end.format("%H:%M")
+                ));
+            }
+        }
+
         // Type-specific validations
         match self.schedule_type {
             ScheduleType::Hourly => {
@@ -240,6 +404,15 @@ impl SchedulesConfig {
         Ok(config)
     }
 
+    /// Load schedules from a TOML file, falling back to the defaults if it is missing or invalid
+    pub fn load_or_default(path: &PathBuf) -> Self {
+        if path.exists() {
+            Self::load_from_file(path).unwrap_or_default()
+        } else {
+            Self::default()
+        }
+    }
+
     /// Save schedules to a TOML file
     pub fn save_to_file(&self, path: &PathBuf) -> anyhow::Result<()> {
         // Validate all schedules before saving
@@ -270,6 +443,58 @@ impl SchedulesConfig {
             .find(|s| s.schedule_type == schedule_type)
     }
 
+    /// Find the schedule that created a snapshot, based on its "<prefix>-" name
+    ///
+    /// The longest matching prefix wins so "daily" never claims "daily-extra-..." snapshots
+    /// when a "daily-extra" schedule exists.
+    pub fn schedule_for_snapshot(&self, snapshot_name: &str) -> Option<&Schedule> {
+        self.schedules
+            .iter()
+            .filter(|s| {
+                !s.prefix.is_empty()
+                    && snapshot_name
+                        .strip_prefix(s.prefix.as_str())
+                        .is_some_and(|rest| rest.starts_with('-'))
+            })
+            .max_by_key(|s| s.prefix.len())
+    }
+
+    /// Check that a manually chosen snapshot name doesn't use a schedule prefix
+    ///
+    /// Schedule prefixes are reserved: a manual snapshot named "daily-..." would
+    /// be taken for one of the daily schedule's snapshots and deleted by its
+    /// retention policy. Disabled schedules count too, since retention still
+    /// applies to the snapshots they created.
+    pub fn check_manual_name(&self, snapshot_name: &str) -> Result<(), String> {
+        match self.schedule_for_snapshot(snapshot_name) {
+            Some(schedule) => Err(format!(
+                "Names starting with '{}-' are reserved for the {} schedule, \
+                 whose retention would delete this snapshot",
+                schedule.prefix,
+                schedule.schedule_type.as_str()
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Find the schedule whose retention policy applies to a snapshot
+    ///
+    /// A retention group assigned in the snapshot's metadata takes precedence
+    /// over the name, so manual snapshots can be handed over to a schedule.
+    /// Snapshots with neither a group nor a schedule prefix are never removed
+    /// by retention. A group naming a schedule that no longer exists keeps the
+    /// snapshot out of retention rather than falling back to its name.
+    pub fn retention_schedule(
+        &self,
+        snapshot_name: &str,
+        retention_group: Option<&str>,
+    ) -> Option<&Schedule> {
+        match retention_group {
+            Some(group) => self.schedules.iter().find(|s| s.prefix == group),
+            None => self.schedule_for_snapshot(snapshot_name),
+        }
+    }
+
     /// Get mutable schedule by type
     pub fn get_schedule_mut(&mut self, schedule_type: ScheduleType) -> Option<&mut Schedule> {
         self.schedules
@@ -348,6 +573,150 @@ mod tests {
         assert!(toml.contains("enabled = true"));
     }
 
+    #[test]
+    fn test_schedule_for_snapshot() {
+        let mut config = SchedulesConfig::default();
+        let mut extra = Schedule::default_daily();
+        extra.prefix = "daily-extra".to_string();
+        config.schedules.push(extra);
+
+        let daily = config.schedule_for_snapshot("daily-20250101-0300").unwrap();
+        assert_eq!(daily.prefix, "daily");
+        let extra = config.schedule_for_snapshot("daily-extra-20250101-0300").unwrap();
+        assert_eq!(extra.prefix, "daily-extra");
+        assert!(config.schedule_for_snapshot("dailyish-20250101").is_none());
+        assert!(config.schedule_for_snapshot("waypoint-20250101-030000").is_none());
+    }
+
+    #[test]
+    fn test_check_manual_name() {
+        let mut config = SchedulesConfig::default();
+        config.schedules.iter_mut().for_each(|s| s.enabled = false);
+
+        assert!(config.check_manual_name("waypoint-20250101-030000").is_ok());
+        assert!(config.check_manual_name("dailyish").is_ok());
+        let error = config.check_manual_name("daily-before-upgrade").unwrap_err();
+        assert!(error.contains("'daily-'"));
+    }
+
+    #[test]
+    fn test_retention_schedule() {
+        let config = SchedulesConfig::default();
+
+        let daily = config
+            .retention_schedule("daily-20250101-0300", None)
+            .unwrap();
+        assert_eq!(daily.prefix, "daily");
+        assert!(
+            config
+                .retention_schedule("waypoint-20250101-030000", None)
+                .is_none()
+        );
+
+        let weekly = config
+            .retention_schedule("waypoint-20250101-030000", Some("weekly"))
+            .unwrap();
+        assert_eq!(weekly.prefix, "weekly");
+        let weekly = config
+            .retention_schedule("daily-20250101-0300", Some("weekly"))
+            .unwrap();
+        assert_eq!(weekly.prefix, "weekly");
+        assert!(
+            config
+                .retention_schedule("daily-20250101-0300", Some("gone"))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_active_hours() {
+        let at = |hour, minute| NaiveTime::from_hms_opt(hour, minute, 0).unwrap();
+        let mut schedule = Schedule::default_hourly();
+        assert!(schedule.is_active_at(at(3, 0)));
+
+        schedule.active_start = Some("08:00".to_string());
+        schedule.active_end = Some("22:00".to_string());
+        assert!(schedule.validate().is_ok());
+        assert!(schedule.is_active_at(at(8, 0)));
+        assert!(schedule.is_active_at(at(22, 0)));
+        assert!(!schedule.is_active_at(at(3, 0)));
+
+        let evening = chrono::NaiveDate::from_ymd_opt(2025, 1, 2)
+            .unwrap()
+            .and_hms_opt(22, 30, 0)
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap();
+        let next = schedule.next_hourly_run(evening).unwrap();
+        assert_eq!(next.time(), at(8, 0));
+        assert!(next > evening);
+
+        // Crossing midnight
+        schedule.active_start = Some("22:00".to_string());
+        schedule.active_end = Some("06:00".to_string());
+        assert!(schedule.is_active_at(at(23, 0)));
+        assert!(schedule.is_active_at(at(2, 0)));
+        assert!(!schedule.is_active_at(at(12, 0)));
+        assert_eq!(schedule.next_hourly_run(evening).unwrap().time(), at(23, 0));
+
+        // Incomplete, empty or never-firing windows
+        schedule.active_end = None;
+        assert!(schedule.validate().is_err());
+        schedule.active_end = Some("22:00".to_string());
+        assert!(schedule.validate().is_err());
+        schedule.active_start = Some("08:10".to_string());
+        schedule.active_end = Some("08:50".to_string());
+        assert!(schedule.validate().is_err());
+
+        let mut daily = Schedule::default_daily();
+        daily.active_start = Some("08:00".to_string());
+        daily.active_end = Some("22:00".to_string());
+        assert!(daily.validate().is_err());
+        daily.time = Some("12:00".to_string());
+        assert!(daily.validate().is_ok());
+    }
+
+    #[test]
+    fn test_snapshot_name_and_subvolumes() {
+        let mut schedule = Schedule::default_daily();
+        let time = chrono::NaiveDate::from_ymd_opt(2025, 1, 2)
+            .unwrap()
+            .and_hms_opt(3, 4, 5)
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap();
+        assert_eq!(schedule.snapshot_name(time).unwrap(), "daily-20250102-0304");
+
+        schedule.subvolumes.clear();
+        assert_eq!(schedule.snapshot_subvolumes(), vec!["/"]);
+
+        schedule.subvolumes = vec![PathBuf::from("/"), PathBuf::from("/home")];
+        schedule.set_subvolume_excluded(Path::new("/home"), true);
+        assert!(schedule.is_subvolume_excluded(Path::new("/home")));
+        assert_eq!(schedule.snapshot_subvolumes(), vec!["/", "/home"]);
+        assert_eq!(schedule.active_subvolumes(), vec!["/"]);
+        schedule.set_subvolume_excluded(Path::new("/"), true);
+        assert!(schedule.active_subvolumes().is_empty());
+        schedule.set_subvolume_excluded(Path::new("/home"), false);
+        assert_eq!(schedule.active_subvolumes(), vec!["/home"]);
+
+        schedule.prefix = "../daily".to_string();
+        assert!(schedule.snapshot_name(time).is_err());
+    }
+
+    #[test]
+    fn test_auto_backup_defaults_to_enabled() {
+        let toml = r#"
+            [[schedule]]
+            enabled = true
+            type = "hourly"
+            prefix = "hourly"
+            description = "Hourly snapshot"
+        "#;
+        let config: SchedulesConfig = toml::from_str(toml).unwrap();
+        assert!(config.schedules[0].auto_backup);
+    }
+
     #[test]
     fn test_enabled_schedules() {
         let config = SchedulesConfig::default();
@@ -1,66 +1,79 @@
 //! Structured audit logging for security events
 
 use chrono::Utc;
+use std::io::Write;
+use waypoint_common::audit::{AuditEvent, audit_log_path};
 
-/// Audit log entry for security-relevant events
-#[derive(Debug, serde::Serialize)]
-struct AuditEvent {
-    timestamp: String,
-    user_id: String,
-    user_name: Option<String>,
-    process_id: u32,
-    operation: String,
-    resource: String,
-    result: String,
-    details: Option<String>,
-}
-
-impl AuditEvent {
-    fn new(
-        user_id: String,
-        process_id: u32,
-        operation: &str,
-        resource: &str,
-        result: &str,
-    ) -> Self {
-        // Try to get username from UID
-        let user_name = get_username_from_uid(&user_id);
-
-        Self {
-            timestamp: Utc::now().to_rfc3339(),
-            user_id,
-            user_name,
-            process_id,
-            operation: operation.to_string(),
-            resource: resource.to_string(),
-            result: result.to_string(),
-            details: None,
+use crate::transcript::RecordedCommand;
+
+/// Rotate the audit log once it grows beyond this size
+const MAX_AUDIT_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+fn new_event(
+    user_id: String,
+    process_id: u32,
+    operation: &str,
+    resource: &str,
+    result: &str,
+) -> AuditEvent {
+    // Try to get username from UID
+    let user_name = get_username_from_uid(&user_id);
+
+    AuditEvent {
+        timestamp: Utc::now().to_rfc3339(),
+        user_id,
+        user_name,
+        process_id,
+        operation: operation.to_string(),
+        resource: resource.to_string(),
+        result: result.to_string(),
+        details: None,
+    }
+}
+
+/// Log the audit event as structured JSON
+fn log_event(event: &AuditEvent) {
+    // Log as JSON for easy parsing by audit tools
+    if let Ok(json) = serde_json::to_string(event) {
+        log::info!(target: "audit", "{json}");
+
+        if let Err(e) = append_to_audit_log(&json) {
+            log::warn!("Failed to write audit log file: {e}");
         }
+    } else {
+        // Fallback to unstructured if serialization fails
+        log::info!(
+            target: "audit",
+            "user={} pid={} operation={} resource={} result={}",
+            event.user_id,
+            event.process_id,
+            event.operation,
+            event.resource,
+            event.result
+        );
     }
+}
 
-    fn with_details(mut self, details: String) -> Self {
-        self.details = Some(details);
-        self
+/// Append a JSON line to the persistent audit log so the GUI can display it
+fn append_to_audit_log(json: &str) -> std::io::Result<()> {
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let path = audit_log_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
     }
 
-    /// Log the audit event as structured JSON
-    fn log(&self) {
-        // Log as JSON for easy parsing by audit tools
-        if let Ok(json) = serde_json::to_string(self) {
-            log::info!(target: "audit", "{json}");
-        } else {
-            // Fallback to unstructured if serialization fails
-            log::info!(
-                target: "audit",
-                "user={} pid={} operation={} resource={} result={}",
-                self.user_id,
-                self.process_id,
-                self.operation,
-                self.resource,
-                self.result
-            );
-        }
+    // Keep a single rotated copy rather than growing forever
+    if std::fs::metadata(&path).is_ok_and(|m| m.len() > MAX_AUDIT_LOG_BYTES) {
+        std::fs::rename(&path, path.with_extension("log.1"))?;
     }
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .mode(0o600)
+        .open(&path)?;
+    writeln!(file, "{json}")
 }
 
 /// Get username from UID (best effort)
@@ -70,7 +83,7 @@ fn get_username_from_uid(uid_str: &str) -> Option<String> {
     let output = Command::new("id")
         .arg("-un")
         .arg(uid_str)
-        .output()
+        .recorded_output()
         .ok()?;
 
     if output.status.success() {
@@ -91,7 +104,7 @@ pub fn log_snapshot_create(
     error: Option<&str>,
 ) {
     let result = if success { "success" } else { "failure" };
-    let mut event = AuditEvent::new(
+    let mut event = new_event(
         user_id,
         process_id,
         "create_snapshot",
@@ -100,10 +113,10 @@ pub fn log_snapshot_create(
     );
 
     if let Some(err) = error {
-        event = event.with_details(format!("error: {err}"));
+        event.details = Some(format!("error: {err}"));
     }
 
-    event.log();
+    log_event(&event);
 }
 
 /// Log a snapshot deletion event
@@ -115,7 +128,7 @@ pub fn log_snapshot_delete(
     error: Option<&str>,
 ) {
     let result = if success { "success" } else { "failure" };
-    let mut event = AuditEvent::new(
+    let mut event = new_event(
         user_id,
         process_id,
         "delete_snapshot",
@@ -124,10 +137,10 @@ pub fn log_snapshot_delete(
     );
 
     if let Some(err) = error {
-        event = event.with_details(format!("error: {err}"));
+        event.details = Some(format!("error: {err}"));
     }
 
-    event.log();
+    log_event(&event);
 }
 
 /// Log a snapshot restore/rollback event
@@ -139,7 +152,7 @@ pub fn log_snapshot_restore(
     error: Option<&str>,
 ) {
     let result = if success { "success" } else { "failure" };
-    let mut event = AuditEvent::new(
+    let mut event = new_event(
         user_id,
         process_id,
         "restore_snapshot",
@@ -148,10 +161,251 @@ pub fn log_snapshot_restore(
     );
 
     if let Some(err) = error {
-        event = event.with_details(format!("error: {err}"));
+        event.details = Some(format!("error: {err}"));
     }
 
-    event.log();
+    log_event(&event);
+}
+
+/// Log a restore of a snapshot subvolume to a new subvolume
+pub fn log_subvolume_restore(
+    user_id: String,
+    process_id: u32,
+    snapshot_name: &str,
+    target_path: &str,
+    success: bool,
+    error: Option<&str>,
+) {
+    let result = if success { "success" } else { "failure" };
+    let mut event = new_event(
+        user_id,
+        process_id,
+        "restore_to_subvolume",
+        snapshot_name,
+        result,
+    );
+
+    event.details = Some(match error {
+        Some(err) => format!("target: {target_path}, error: {err}"),
+        None => format!("target: {target_path}"),
+    });
+
+    log_event(&event);
+}
+
+/// Log mounting of an ephemeral writable snapshot overlay
+pub fn log_overlay_mount(
+    user_id: String,
+    process_id: u32,
+    snapshot_name: &str,
+    success: bool,
+    error: Option<&str>,
+) {
+    let result = if success { "success" } else { "failure" };
+    let mut event = new_event(
+        user_id,
+        process_id,
+        "mount_overlay",
+        snapshot_name,
+        result,
+    );
+
+    if let Some(err) = error {
+        event.details = Some(format!("error: {err}"));
+    }
+
+    log_event(&event);
+}
+
+/// Log a manual deduplication of the snapshot directory
+pub fn log_deduplication(
+    user_id: String,
+    process_id: u32,
+    success: bool,
+    details: Option<&str>,
+) {
+    let result = if success { "success" } else { "failure" };
+    let mut event = new_event(
+        user_id,
+        process_id,
+        "deduplicate_snapshots",
+        "snapshots",
+        result,
+    );
+    event.details = details.map(str::to_string);
+
+    log_event(&event);
+}
+
+/// Log a balance of the snapshot filesystem
+pub fn log_balance(
+    user_id: String,
+    process_id: u32,
+    success: bool,
+    details: Option<&str>,
+) {
+    let result = if success { "success" } else { "failure" };
+    let mut event = new_event(
+        user_id,
+        process_id,
+        "balance_filesystem",
+        "snapshots",
+        result,
+    );
+    event.details = details.map(str::to_string);
+
+    log_event(&event);
+}
+
+/// Log a change of a snapshot's read-only property
+pub fn log_read_only_change(
+    user_id: String,
+    process_id: u32,
+    snapshot_name: &str,
+    read_only: bool,
+    success: bool,
+    error: Option<&str>,
+) {
+    let result = if success { "success" } else { "failure" };
+    let mut event = new_event(
+        user_id,
+        process_id,
+        "set_read_only",
+        snapshot_name,
+        result,
+    );
+    event.details = Some(match error {
+        Some(err) => format!("read_only={read_only}, error: {err}"),
+        None => format!("read_only={read_only}"),
+    });
+
+    log_event(&event);
+}
+
+/// Log a change of the schedule whose retention applies to a snapshot
+pub fn log_retention_group_change(
+    user_id: String,
+    process_id: u32,
+    snapshot_name: &str,
+    group: &str,
+    success: bool,
+    error: Option<&str>,
+) {
+    let result = if success { "success" } else { "failure" };
+    let mut event = new_event(
+        user_id,
+        process_id,
+        "set_retention_group",
+        snapshot_name,
+        result,
+    );
+    event.details = Some(match error {
+        Some(err) => format!("group={group:?}, error: {err}"),
+        None => format!("group={group:?}"),
+    });
+
+    log_event(&event);
+}
+
+/// Log a change of the date after which a snapshot is deleted
+pub fn log_expiry_change(
+    user_id: String,
+    process_id: u32,
+    snapshot_name: &str,
+    expires_at: &str,
+    success: bool,
+    error: Option<&str>,
+) {
+    let result = if success { "success" } else { "failure" };
+    let mut event = new_event(user_id, process_id, "set_expiry", snapshot_name, result);
+    event.details = Some(match error {
+        Some(err) => format!("expires_at={expires_at:?}, error: {err}"),
+        None => format!("expires_at={expires_at:?}"),
+    });
+
+    log_event(&event);
+}
+
+/// Log a change of why a snapshot was taken
+pub fn log_category_change(
+    user_id: String,
+    process_id: u32,
+    snapshot_name: &str,
+    category: &str,
+    success: bool,
+    error: Option<&str>,
+) {
+    let result = if success { "success" } else { "failure" };
+    let mut event = new_event(user_id, process_id, "set_category", snapshot_name, result);
+    event.details = Some(match error {
+        Some(err) => format!("category={category:?}, error: {err}"),
+        None => format!("category={category:?}"),
+    });
+
+    log_event(&event);
+}
+
+/// Log resolving conflicting metadata entries of a snapshot
+pub fn log_metadata_repair(
+    user_id: String,
+    process_id: u32,
+    snapshot_name: &str,
+    resolution: &str,
+    success: bool,
+    error: Option<&str>,
+) {
+    let result = if success { "success" } else { "failure" };
+    let mut event = new_event(user_id, process_id, "fix_metadata", snapshot_name, result);
+    event.details = Some(match error {
+        Some(err) => format!("resolution={resolution:?}, error: {err}"),
+        None => format!("resolution={resolution:?}"),
+    });
+
+    log_event(&event);
+}
+
+/// Log pinning or unpinning a snapshot
+pub fn log_pin_change(
+    user_id: String,
+    process_id: u32,
+    snapshot_name: &str,
+    pinned: bool,
+    success: bool,
+    error: Option<&str>,
+) {
+    let result = if success { "success" } else { "failure" };
+    let mut event = new_event(user_id, process_id, "set_pinned", snapshot_name, result);
+    event.details = Some(match error {
+        Some(err) => format!("pinned={pinned}, error: {err}"),
+        None => format!("pinned={pinned}"),
+    });
+
+    log_event(&event);
+}
+
+/// Log linking a snapshot to a package manager transaction
+pub fn log_transaction_link(
+    user_id: String,
+    process_id: u32,
+    snapshot_name: &str,
+    details: &str,
+    success: bool,
+    error: Option<&str>,
+) {
+    let result = if success { "success" } else { "failure" };
+    let mut event = new_event(
+        user_id,
+        process_id,
+        "link_transaction",
+        snapshot_name,
+        result,
+    );
+    event.details = Some(match error {
+        Some(err) => format!("{details}, error: {err}"),
+        None => details.to_string(),
+    });
+
+    log_event(&event);
 }
 
 /// Log a configuration change event
@@ -163,7 +417,7 @@ pub fn log_config_change(
     error: Option<&str>,
 ) {
     let result = if success { "success" } else { "failure" };
-    let mut event = AuditEvent::new(
+    let mut event = new_event(
         user_id,
         process_id,
         "modify_configuration",
@@ -172,10 +426,10 @@ pub fn log_config_change(
     );
 
     if let Some(err) = error {
-        event = event.with_details(format!("error: {err}"));
+        event.details = Some(format!("error: {err}"));
     }
 
-    event.log();
+    log_event(&event);
 }
 
 /// Log an authorization failure
@@ -185,13 +439,14 @@ pub fn log_auth_failure(
     operation: &str,
     reason: &str,
 ) {
-    let event = AuditEvent::new(
+    let mut event = new_event(
         user_id,
         process_id,
         operation,
         "authorization",
         "denied",
-    ).with_details(format!("reason: {reason}"));
+    );
+    event.details = Some(format!("reason: {reason}"));
 
-    event.log();
+    log_event(&event);
 }
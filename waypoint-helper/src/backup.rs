@@ -3,12 +3,21 @@
 
 use anyhow::{anyhow, bail, Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::SyncSender;
-use waypoint_common::WaypointConfig;
+use waypoint_common::{
+    BackupComparison, BackupFormat, BackupRestorePreview, ErrorCode, HelperError, MetadataFormat,
+    SubvolumeComparison, WaypointConfig,
+};
+
+use crate::backup_image::{self, ImageMount};
+use crate::btrfs::METADATA_SIDECAR;
+use crate::transcript::RecordedCommand;
 
 /// Progress update message for backup operations
 #[derive(Debug, Clone)]
@@ -55,7 +64,7 @@ pub fn scan_backup_destinations() -> Result<Vec<BackupDestination>> {
         .arg("-o")
         .arg("TARGET,LABEL,SOURCE,FSTYPE,UUID")
         .arg("-J") // JSON output - properly handles spaces and special chars
-        .output()
+        .recorded_output()
         .context("Failed to list mounted filesystems")?;
 
     let output_str = String::from_utf8_lossy(&output.stdout);
@@ -222,6 +231,25 @@ fn validate_backup_path(backup_path: &Path) -> Result<PathBuf> {
     )
 }
 
+/// Canonical paths of the snapshot directories
+///
+/// The primary directory must resolve; further ones that don't exist are skipped.
+fn canonical_snapshot_dirs(config: &WaypointConfig) -> Result<Vec<PathBuf>> {
+    let mut dirs = vec![
+        config
+            .snapshot_dir
+            .canonicalize()
+            .context("Failed to resolve configured snapshot directory")?,
+    ];
+    dirs.extend(
+        config
+            .extra_snapshot_dirs
+            .iter()
+            .filter_map(|dir| dir.canonicalize().ok()),
+    );
+    Ok(dirs)
+}
+
 /// Detect the type of drive based on mount point, source device, and filesystem type
 fn detect_drive_type(_mount_point: &str, source: &str, fstype: &str) -> DriveType {
     // Check for network filesystems
@@ -279,11 +307,11 @@ fn is_removable_device(device: &str) -> bool {
 }
 
 /// Calculate the disk usage of a directory using du command
-fn calculate_directory_size(path: &Path) -> Result<u64> {
-    let output = Command::new("du")
+pub fn calculate_directory_size(path: &Path) -> Result<u64> {
+    let output = crate::low_priority::command("du")
         .arg("-sb") // -s for summary, -b for bytes
         .arg(path)
-        .output()
+        .recorded_output()
         .context("Failed to run du command")?;
 
     if !output.status.success() {
@@ -302,25 +330,29 @@ fn calculate_directory_size(path: &Path) -> Result<u64> {
 }
 
 /// Load snapshot metadata from the waypoint metadata file
-/// This reads the snapshots.json file to get information about which subvolumes are included
+/// This reads the snapshots.json file to get information about which subvolumes are included,
+/// falling back to the metadata sidecar inside the snapshot directory
 fn load_snapshot_metadata(snapshot_name: &str) -> Result<waypoint_common::SnapshotInfo> {
     let config = WaypointConfig::new();
     let metadata_path = &config.metadata_file;
 
-    if !metadata_path.exists() {
-        bail!("Snapshot metadata file not found: {}", metadata_path.display());
-    }
+    if metadata_path.exists() {
+        let contents = fs::read_to_string(metadata_path)
+            .context("Failed to read snapshot metadata")?;
 
-    let contents = fs::read_to_string(metadata_path)
-        .context("Failed to read snapshot metadata")?;
+        let snapshots: Vec<waypoint_common::SnapshotInfo> =
+            MetadataFormat::from_path(metadata_path)
+                .parse(&contents)
+                .context("Failed to parse snapshot metadata")?;
 
-    let snapshots: Vec<waypoint_common::SnapshotInfo> = serde_json::from_str(&contents)
-        .context("Failed to parse snapshot metadata")?;
+        if let Some(info) = snapshots.into_iter().find(|s| s.name == snapshot_name) {
+            return Ok(info);
+        }
+    }
 
-    snapshots
-        .into_iter()
-        .find(|s| s.name == snapshot_name)
-        .ok_or_else(|| anyhow!("Snapshot '{snapshot_name}' not found in metadata"))
+    crate::btrfs::read_metadata_sidecar(snapshot_name)
+        .map(Into::into)
+        .map_err(|_| anyhow!("Snapshot '{snapshot_name}' not found in metadata"))
 }
 
 /// Convert a mount point path to a subdirectory name for backups
@@ -342,20 +374,31 @@ fn mount_point_to_subdir_name(mount_point: &Path) -> String {
 /// - btrfs: Uses btrfs send/receive (supports incremental)
 /// - ntfs/exfat/vfat/cifs/nfs: Uses rsync (full copy)
 ///
+/// With `BackupFormat::Image` the snapshot is written to a self-contained
+/// image file instead, whatever the destination filesystem.
+///
 /// Returns a tuple of (backup_path, size_bytes)
 pub fn backup_snapshot(
     snapshot_path: &str,
     destination_mount: &str,
     parent_snapshot: Option<&str>,
+    format: BackupFormat,
     progress_tx: Option<SyncSender<BackupProgress>>,
+    cancel: &AtomicBool,
 ) -> Result<(String, u64)> {
     let snapshot = Path::new(snapshot_path);
 
     // Validate inputs
     if !snapshot.exists() {
-        return Err(anyhow::anyhow!(
-            "Snapshot does not exist: {snapshot_path}"
-        ));
+        // Nothing will ever finish an interrupted backup of a deleted snapshot
+        if let Some(name) = snapshot.file_name().and_then(|n| n.to_str()) {
+            remove_abandoned_backup(destination_mount, name);
+        }
+        return Err(HelperError::new(
+            ErrorCode::NotFound,
+            format!("Snapshot does not exist: {snapshot_path}"),
+        )
+        .into());
     }
 
     // SECURITY: Validate destination_mount is a legitimate backup destination
@@ -367,12 +410,120 @@ pub fn backup_snapshot(
     // Detect destination filesystem type
     let fstype = detect_filesystem_type(destination_mount_str)?;
 
+    // Fail before transferring anything if the backup can't fit
+    if format == BackupFormat::Native {
+        match estimate_backup_size(snapshot, &validated_dest, &fstype, parent_snapshot) {
+            Ok(needed) => check_destination_space(&validated_dest, needed)?,
+            Err(e) => log::warn!("Couldn't estimate the backup size, not checking free space: {e}"),
+        }
+    }
+
     // Route to appropriate backup method (use validated path)
-    if fstype == "btrfs" {
-        backup_snapshot_btrfs(snapshot_path, destination_mount_str, parent_snapshot, progress_tx)
+    if format == BackupFormat::Image {
+        backup_image::backup_snapshot_image(
+            snapshot_path,
+            destination_mount_str,
+            &fstype,
+            progress_tx,
+            cancel,
+        )
+    } else if fstype == "btrfs" {
+        backup_snapshot_btrfs(
+            snapshot_path,
+            destination_mount_str,
+            parent_snapshot,
+            progress_tx,
+            cancel,
+        )
     } else {
-        backup_snapshot_rsync(snapshot_path, destination_mount_str, progress_tx)
+        backup_snapshot_rsync(snapshot_path, destination_mount_str, progress_tx, cancel)
+    }
+}
+
+/// Transfers take as long as the data needs; only cancellation stops them
+const TRANSFER_TIMEOUT: std::time::Duration = std::time::Duration::MAX;
+
+/// Fail with a "cancelled" error once `cancel` is set
+fn check_cancelled(cancel: &AtomicBool) -> Result<()> {
+    if cancel.load(Ordering::Relaxed) {
+        bail!(HelperError::new(
+            ErrorCode::Cancelled,
+            "Transfer was cancelled"
+        ));
+    }
+    Ok(())
+}
+
+/// Extra room required on top of a backup's estimated size, in percent
+///
+/// Covers the metadata the destination writes for the received data.
+const SPACE_MARGIN_PERCENT: u64 = 5;
+
+/// Estimate how many bytes a backup of `snapshot` writes to the destination
+///
+/// Rsync backups count the part of the snapshot not copied yet. Btrfs backups
+/// count each subvolume still to be sent: incremental ones by the extents a
+/// send without file data would write, full ones at their size.
+fn estimate_backup_size(
+    snapshot: &Path,
+    destination_mount: &Path,
+    fstype: &str,
+    parent_snapshot: Option<&str>,
+) -> Result<u64> {
+    let snapshot_name = snapshot
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| anyhow!("Invalid snapshot path"))?;
+    let backup_dir = destination_mount.join("waypoint-backups");
+
+    if fstype != "btrfs" {
+        // An interrupted copy is continued, so only what is missing counts
+        let copied = calculate_directory_size(&backup_dir.join(snapshot_name)).unwrap_or(0);
+        return Ok(calculate_directory_size(snapshot)?.saturating_sub(copied));
+    }
+
+    let metadata = load_snapshot_metadata(snapshot_name)?;
+
+    let mut total = 0;
+    for mount_point in &metadata.subvolumes {
+        let subvol_name = mount_point_to_subdir_name(mount_point);
+        let subvol_path = snapshot.join(&subvol_name);
+        let subvol_backup = backup_dir.join(snapshot_name).join(&subvol_name);
+        if !subvol_path.exists() || (subvol_backup.exists() && is_complete_receive(&subvol_backup))
+        {
+            continue;
+        }
+
+        let parent = parent_snapshot
+            .and_then(|parent| incremental_parent(Path::new(parent), &backup_dir, &subvol_name));
+        total += match parent {
+            Some((parent_subvol, _)) => {
+                dump_data_bytes(&incremental_dump(&subvol_path, &parent_subvol)?)
+            }
+            None => calculate_directory_size(&subvol_path)?,
+        };
     }
+    Ok(total)
+}
+
+/// Fail with `InsufficientSpace` unless `destination` has room for `needed` bytes
+pub fn check_destination_space(destination: &Path, needed: u64) -> Result<()> {
+    let stats =
+        nix::sys::statvfs::statvfs(destination).context("Failed to get filesystem statistics")?;
+    let available = stats.blocks_available() * stats.block_size();
+    let required = needed.saturating_add(needed / 100 * SPACE_MARGIN_PERCENT);
+
+    if required > available {
+        bail!(HelperError::new(
+            ErrorCode::InsufficientSpace,
+            format!(
+                "Not enough space at the backup destination: the backup needs about {}, {} is free",
+                waypoint_common::format_bytes(required),
+                waypoint_common::format_bytes(available)
+            ),
+        ));
+    }
+    Ok(())
 }
 
 /// Detect the filesystem type of a mount point
@@ -382,7 +533,7 @@ fn detect_filesystem_type(mount_point: &str) -> Result<String> {
         .arg("-o")
         .arg("FSTYPE")
         .arg(mount_point)
-        .output()
+        .recorded_output()
         .context("Failed to detect filesystem type")?;
 
     if !output.status.success() {
@@ -406,13 +557,14 @@ fn backup_single_subvolume_btrfs(
     subvol_path: &Path,
     parent_subvol: Option<&Path>,
     receive_dir: &Path,
+    cancel: &AtomicBool,
 ) -> Result<()> {
     // Verify it's actually a btrfs subvolume
     let is_subvolume = Command::new("btrfs")
         .arg("subvolume")
         .arg("show")
         .arg(subvol_path)
-        .output()
+        .recorded_output()
         .map(|output| output.status.success())
         .unwrap_or(false);
 
@@ -441,7 +593,9 @@ fn backup_single_subvolume_btrfs(
     receive_cmd.arg("receive").arg(receive_dir);
 
     // Execute send | receive pipeline
-    let mut send_child = send_cmd.spawn().context("Failed to start btrfs send")?;
+    let mut send_child = send_cmd
+        .recorded_spawn()
+        .context("Failed to start btrfs send")?;
 
     let send_stdout = send_child
         .stdout
@@ -458,9 +612,15 @@ fn backup_single_subvolume_btrfs(
 
     receive_cmd.stdin(send_stdout);
 
-    let receive_output = receive_cmd
-        .output()
-        .context("Failed to run btrfs receive")?;
+    let receive_output =
+        match crate::output_with_timeout(&mut receive_cmd, TRANSFER_TIMEOUT, Some(cancel)) {
+            Ok(output) => output,
+            Err(e) => {
+                let _ = send_child.kill();
+                let _ = send_child.wait();
+                return Err(e);
+            }
+        };
 
     let send_status = send_child.wait().context("Failed to wait for btrfs send")?;
 
@@ -489,15 +649,259 @@ fn backup_single_subvolume_btrfs(
     Ok(())
 }
 
+/// Subvolumes an incremental backup reused instead of sending, stored in the
+/// backup directory as a map of subvolume name to the snapshot whose backup
+/// received the data
+const UNCHANGED_SUBVOLUMES: &str = ".waypoint-unchanged.json";
+
+/// Progress of a backup, kept in the backup directory while the
+/// transfer runs and removed once it completes
+///
+/// A backup directory that still has this file was interrupted, e.g. by
+/// unplugging the drive, and is resumed by the next attempt.
+const TRANSFER_STATE: &str = ".waypoint-transfer.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TransferState {
+    /// Subvolumes received completely
+    #[serde(default)]
+    completed: Vec<String>,
+    /// Completed subvolumes that reused an earlier backup (see `UNCHANGED_SUBVOLUMES`)
+    #[serde(default)]
+    unchanged: BTreeMap<String, String>,
+}
+
+impl TransferState {
+    fn load(backup_dir: &Path) -> Option<Self> {
+        let content = fs::read_to_string(backup_dir.join(TRANSFER_STATE)).ok()?;
+        match serde_json::from_str(&content) {
+            Ok(state) => Some(state),
+            Err(e) => {
+                log::warn!(
+                    "Ignoring unreadable transfer state in {}: {e}",
+                    backup_dir.display()
+                );
+                Some(Self::default())
+            }
+        }
+    }
+
+    fn save(&self, backup_dir: &Path) -> Result<()> {
+        let content =
+            serde_json::to_string_pretty(self).context("Failed to serialize transfer state")?;
+        fs::write(backup_dir.join(TRANSFER_STATE), content)
+            .context("Failed to record transfer state")
+    }
+}
+
+/// Whether a backup directory holds an interrupted transfer
+fn is_interrupted_backup(backup_dir: &Path) -> bool {
+    backup_dir.join(TRANSFER_STATE).exists()
+}
+
+/// Delete the interrupted backup of `snapshot_name` at a destination, if there is one
+///
+/// Only called once the snapshot is gone, when the transfer can't be resumed.
+/// Completed backups are never touched.
+fn remove_abandoned_backup(destination_mount: &str, snapshot_name: &str) {
+    if waypoint_common::validate_snapshot_name(snapshot_name).is_err() {
+        return;
+    }
+    let Ok(destination) = validate_backup_destination(destination_mount) else {
+        return;
+    };
+    let backup_dir = destination.join("waypoint-backups").join(snapshot_name);
+    if !is_interrupted_backup(&backup_dir) {
+        return;
+    }
+
+    log::info!(
+        "Snapshot {snapshot_name} is gone, removing its interrupted backup {}",
+        backup_dir.display()
+    );
+    if let Err(e) = delete_backup(&backup_dir.to_string_lossy()) {
+        log::warn!(
+            "Failed to remove interrupted backup {}: {e}",
+            backup_dir.display()
+        );
+    }
+}
+
+/// Parse the received UUID from `btrfs subvolume show` output
+///
+/// btrfs receive only sets it after the whole stream was applied, so a
+/// subvolume without one was cut off mid-transfer.
+fn parse_received_uuid(show_output: &str) -> Option<String> {
+    show_output.lines().find_map(|line| {
+        let uuid = line.trim().strip_prefix("Received UUID:")?.trim();
+        (!uuid.is_empty() && uuid != "-").then(|| uuid.to_string())
+    })
+}
+
+/// Whether a subvolume on the destination was received completely
+fn is_complete_receive(subvol: &Path) -> bool {
+    Command::new("btrfs")
+        .args(["subvolume", "show"])
+        .arg(subvol)
+        .recorded_output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| parse_received_uuid(&String::from_utf8_lossy(&output.stdout)))
+        .is_some()
+}
+
+/// Delete what an interrupted transfer left of a subvolume backup
+fn remove_partial_subvolume(subvol: &Path) -> Result<()> {
+    let output = Command::new("btrfs")
+        .args(["subvolume", "delete"])
+        .arg(subvol)
+        .recorded_output()
+        .context("Failed to run btrfs subvolume delete")?;
+
+    if !output.status.success() {
+        bail!(
+            "Failed to delete partial backup {}: {}",
+            subvol.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+fn read_unchanged_subvolumes(backup_dir: &Path) -> BTreeMap<String, String> {
+    fs::read_to_string(backup_dir.join(UNCHANGED_SUBVOLUMES))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Find the subvolume to send `subvol_name` incrementally against
+///
+/// Returns the parent subvolume and the name of the snapshot whose backup
+/// received it. That is usually the parent snapshot itself, but if the
+/// parent's backup reused an unchanged subvolume, the destination only knows
+/// the earlier snapshot that was actually sent.
+fn incremental_parent(
+    parent_snapshot: &Path,
+    backup_dir: &Path,
+    subvol_name: &str,
+) -> Option<(PathBuf, String)> {
+    let parent_name = parent_snapshot.file_name()?.to_str()?;
+    let origin = read_unchanged_subvolumes(&backup_dir.join(parent_name))
+        .remove(subvol_name)
+        .unwrap_or_else(|| parent_name.to_string());
+
+    let parent_subvol = parent_snapshot.with_file_name(&origin).join(subvol_name);
+    if is_interrupted_backup(&backup_dir.join(&origin)) {
+        log::warn!("Backup of parent '{origin}' is incomplete, doing full backup");
+        return None;
+    }
+    if !parent_subvol.exists() || !backup_dir.join(&origin).join(subvol_name).exists() {
+        log::warn!("Parent subvolume '{subvol_name}' not found, doing full backup");
+        return None;
+    }
+    Some((parent_subvol, origin))
+}
+
+/// Whether a `btrfs receive --dump` of an incremental stream has no changes
+///
+/// An unchanged subvolume only produces the `snapshot` command that creates
+/// it from the parent.
+fn is_empty_incremental_dump(dump: &str) -> bool {
+    let mut commands = dump.lines().filter(|line| !line.trim().is_empty());
+    commands
+        .next()
+        .is_some_and(|line| line.starts_with("snapshot"))
+        && commands.next().is_none()
+}
+
+/// Bytes of file data an incremental stream dumped by `btrfs receive --dump` writes
+///
+/// Streams sent with `--no-data` describe writes as `update_extent` commands;
+/// clones share data the destination already has and don't count.
+fn dump_data_bytes(dump: &str) -> u64 {
+    dump.lines()
+        .filter(|line| line.starts_with("update_extent") || line.starts_with("write"))
+        .filter_map(|line| {
+            line.split_whitespace()
+                .find_map(|field| field.strip_prefix("len="))
+                .and_then(|len| len.parse::<u64>().ok())
+        })
+        .sum()
+}
+
+/// Check whether a subvolume is identical to its parent
+fn subvolume_unchanged(subvol_path: &Path, parent_subvol: &Path) -> Result<bool> {
+    let dump = incremental_dump(subvol_path, parent_subvol)?;
+    Ok(is_empty_incremental_dump(&dump))
+}
+
+/// Dump of the difference between a subvolume and its parent
+///
+/// Sends the difference without file data, which only walks the metadata
+/// trees and is quick even for large subvolumes.
+fn incremental_dump(subvol_path: &Path, parent_subvol: &Path) -> Result<String> {
+    let mut send_child = Command::new("btrfs")
+        .args(["send", "--no-data", "-q", "-p"])
+        .arg(parent_subvol)
+        .arg(subvol_path)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .recorded_spawn()
+        .context("Failed to start btrfs send")?;
+
+    let send_stdout = send_child
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow!("Failed to capture send output"))?;
+
+    let dump_output = Command::new("btrfs")
+        .args(["receive", "--dump"])
+        .stdin(send_stdout)
+        .recorded_output()
+        .context("Failed to run btrfs receive --dump")?;
+
+    let send_status = send_child.wait().context("Failed to wait for btrfs send")?;
+    if !send_status.success() || !dump_output.status.success() {
+        bail!(
+            "Failed to compare {} with its parent",
+            subvol_path.display()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&dump_output.stdout).into_owned())
+}
+
+/// Reuse the backup of an unchanged subvolume with a read-only snapshot of it
+/// on the destination, which takes no time or space
+fn reuse_subvolume_backup(previous_backup: &Path, target: &Path) -> Result<()> {
+    let output = Command::new("btrfs")
+        .args(["subvolume", "snapshot", "-r"])
+        .arg(previous_backup)
+        .arg(target)
+        .recorded_output()
+        .context("Failed to run btrfs subvolume snapshot")?;
+
+    if !output.status.success() {
+        bail!(
+            "Failed to snapshot {}: {}",
+            previous_backup.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
 /// Backup a snapshot to a btrfs destination using btrfs send/receive
 /// Handles multi-subvolume snapshots by backing up each subvolume separately
 ///
 /// Returns a tuple of (backup_path, size_bytes)
-fn backup_snapshot_btrfs(
+pub fn backup_snapshot_btrfs(
     snapshot_path: &str,
     destination_mount: &str,
     parent_snapshot: Option<&str>,
     progress_tx: Option<SyncSender<BackupProgress>>,
+    cancel: &AtomicBool,
 ) -> Result<(String, u64)> {
     let snapshot = Path::new(snapshot_path);
     let dest_mount = Path::new(destination_mount);
@@ -538,9 +942,31 @@ fn backup_snapshot_btrfs(
 
     // Create snapshot-specific directory
     let snapshot_backup_dir = backup_dir.join(snapshot_name);
+    let existing_backup = snapshot_backup_dir.exists();
     fs::create_dir_all(&snapshot_backup_dir)
         .context("Failed to create snapshot backup directory")?;
 
+    // Pick up where an interrupted transfer stopped
+    let mut transfer = match TransferState::load(&snapshot_backup_dir) {
+        Some(state) => {
+            log::info!(
+                "Resuming interrupted backup of '{snapshot_name}' ({} subvolume(s) already done)",
+                state.completed.len()
+            );
+            state
+        }
+        None => {
+            if existing_backup {
+                log::info!("Backup of '{snapshot_name}' already exists, checking its subvolumes");
+            }
+            TransferState {
+                completed: Vec::new(),
+                unchanged: read_unchanged_subvolumes(&snapshot_backup_dir),
+            }
+        }
+    };
+    transfer.save(&snapshot_backup_dir)?;
+
     log::info!(
         "Backing up {} subvolumes for snapshot '{}'",
         metadata.subvolumes.len(),
@@ -569,6 +995,7 @@ fn backup_snapshot_btrfs(
 
     // Backup each subvolume
     for mount_point in &metadata.subvolumes {
+        check_cancelled(cancel)?;
         let subvol_name = mount_point_to_subdir_name(mount_point);
         let subvol_path = snapshot.join(&subvol_name);
 
@@ -579,23 +1006,24 @@ fn backup_snapshot_btrfs(
             continue;
         }
 
+        // btrfs send only accepts read-only subvolumes and says little else
+        if !crate::btrfs::is_read_only(&subvol_path).unwrap_or(true) {
+            return Err(HelperError::new(
+                ErrorCode::InvalidInput,
+                format!(
+                    "Snapshot '{snapshot_name}' is writable; make it read-only before backing it up"
+                ),
+            )
+            .into());
+        }
+
         log::info!("Backing up subvolume: {} ({})", subvol_name, mount_point.display());
 
         // Determine parent subvolume for incremental backup
-        let parent_subvol = if let Some(parent_snap) = parent_snapshot {
-            let parent_path = Path::new(parent_snap);
-            let parent_subvol_path = parent_path.join(&subvol_name);
-            if parent_subvol_path.exists() {
-                Some(parent_subvol_path)
-            } else {
-                log::warn!(
-                    "Parent subvolume '{subvol_name}' not found, doing full backup"
-                );
-                None
-            }
-        } else {
-            None
-        };
+        let parent = parent_snapshot.and_then(|parent_snap| {
+            incremental_parent(Path::new(parent_snap), &backup_dir, &subvol_name)
+        });
+        let parent_subvol = parent.as_ref().map(|(path, _)| path.clone());
 
         // Create subdirectory for this subvolume in the backup
         let subvol_backup_dir = snapshot_backup_dir.join(&subvol_name);
@@ -606,17 +1034,102 @@ fn backup_snapshot_btrfs(
             anyhow::bail!("Backup directory has no parent: {}", subvol_backup_dir.display());
         }
 
+        // btrfs receive can't continue a cut-off stream, so keep subvolumes
+        // that arrived completely and send the rest again
+        if subvol_backup_dir.exists() {
+            if transfer.completed.contains(&subvol_name)
+                || transfer.unchanged.contains_key(&subvol_name)
+                || is_complete_receive(&subvol_backup_dir)
+            {
+                log::info!("Subvolume '{subvol_name}' was already backed up, skipping");
+                if !transfer.completed.contains(&subvol_name) {
+                    transfer.completed.push(subvol_name);
+                    transfer.save(&snapshot_backup_dir)?;
+                }
+                continue;
+            }
+            log::warn!(
+                "Removing partial backup of subvolume '{subvol_name}' from an interrupted transfer"
+            );
+            remove_partial_subvolume(&subvol_backup_dir)?;
+        }
+
+        // Nothing changed since the parent: reuse its backup instead of sending
+        if let Some((parent_subvol, origin)) = &parent {
+            match subvolume_unchanged(&subvol_path, parent_subvol) {
+                Ok(true) => {
+                    match reuse_subvolume_backup(
+                        &backup_dir.join(origin).join(&subvol_name),
+                        &subvol_backup_dir,
+                    ) {
+                        Ok(()) => {
+                            log::info!(
+                                "Subvolume '{subvol_name}' is unchanged since '{origin}', reused its backup"
+                            );
+                            transfer
+                                .unchanged
+                                .insert(subvol_name.clone(), origin.clone());
+                            transfer.completed.push(subvol_name);
+                            transfer.save(&snapshot_backup_dir)?;
+                            continue;
+                        }
+                        Err(e) => log::warn!(
+                            "Failed to reuse backup of unchanged subvolume '{subvol_name}', sending it: {e}"
+                        ),
+                    }
+                }
+                Ok(false) => {}
+                Err(e) => log::debug!("Couldn't check subvolume '{subvol_name}' for changes: {e}"),
+            }
+        }
+
         // Backup this subvolume
-        backup_single_subvolume_btrfs(
+        if let Err(e) = backup_single_subvolume_btrfs(
             &subvol_path,
             parent_subvol.as_deref(),
             &snapshot_backup_dir,
-        )
-        .with_context(|| format!("Failed to backup subvolume '{subvol_name}'"))?;
+            cancel,
+        ) {
+            // The transfer state stays behind, so the next attempt resumes from here
+            if cancel.load(Ordering::Relaxed) && subvol_backup_dir.exists() {
+                log::info!(
+                    "Removing partial backup of subvolume '{subvol_name}' after cancellation"
+                );
+                if let Err(cleanup_err) = remove_partial_subvolume(&subvol_backup_dir) {
+                    log::error!("{cleanup_err}");
+                }
+            }
+            return Err(e).with_context(|| format!("Failed to backup subvolume '{subvol_name}'"));
+        }
 
         log::info!("Successfully backed up subvolume: {subvol_name}");
+        transfer.completed.push(subvol_name);
+        transfer.save(&snapshot_backup_dir)?;
+    }
+
+    // Later incremental backups send reused subvolumes against the snapshot
+    // that was actually received
+    let unchanged = transfer.unchanged;
+    if !unchanged.is_empty() {
+        let content = serde_json::to_string_pretty(&unchanged)
+            .context("Failed to serialize unchanged subvolumes")?;
+        fs::write(snapshot_backup_dir.join(UNCHANGED_SUBVOLUMES), content)
+            .context("Failed to record unchanged subvolumes")?;
+        log::info!(
+            "Skipped {} unchanged subvolume(s) for snapshot '{snapshot_name}'",
+            unchanged.len()
+        );
+    }
+
+    // Keep the metadata with the backup so a restore can bring it back
+    if let Err(e) = crate::btrfs::export_metadata_to_backup(snapshot_name, &snapshot_backup_dir) {
+        log::warn!("Failed to store metadata with backup of {snapshot_name}: {e}");
     }
 
+    // Everything arrived, so this is no longer an interrupted transfer
+    fs::remove_file(snapshot_backup_dir.join(TRANSFER_STATE))
+        .context("Failed to clear transfer state")?;
+
     // Calculate total backup size
     let size_bytes = calculate_directory_size(&snapshot_backup_dir)?;
 
@@ -657,6 +1170,7 @@ fn backup_snapshot_rsync(
     snapshot_path: &str,
     destination_mount: &str,
     progress_tx: Option<SyncSender<BackupProgress>>,
+    cancel: &AtomicBool,
 ) -> Result<(String, u64)> {
     let snapshot = Path::new(snapshot_path);
     let dest_mount = Path::new(destination_mount);
@@ -692,6 +1206,10 @@ fn backup_snapshot_rsync(
     fs::create_dir_all(&snapshot_backup_dir)
         .context("Failed to create snapshot backup directory")?;
 
+    // rsync picks up where it stopped by itself, so the state only marks the
+    // backup as unfinished until every subvolume is copied
+    TransferState::default().save(&snapshot_backup_dir)?;
+
     log::info!(
         "Backing up {} subvolumes for snapshot '{}' using rsync",
         metadata.subvolumes.len(),
@@ -755,7 +1273,8 @@ fn backup_snapshot_rsync(
         // - --inplace/--partial: write in-place so only touched blocks are updated and allow resume
         // - --no-inc-recursive: avoid the incremental recursion bookkeeping (less metadata churn)
         // - --human-readable/--info=progress2/--outbuf=L: friendlier logging + steady progress output
-        let output = Command::new("rsync")
+        let mut rsync = Command::new("rsync");
+        rsync
             .arg("-aHAX")
             .arg("--delete-after")
             .arg("--inplace")
@@ -765,9 +1284,9 @@ fn backup_snapshot_rsync(
             .arg("--info=progress2")
             .arg("--outbuf=L")
             .arg(format!("{}/", source_dir.display())) // Trailing slash = copy contents
-            .arg(&dest_subvol_dir)
-            .output()
-            .context("Failed to run rsync")?;
+            .arg(&dest_subvol_dir);
+        // A cancelled rsync leaves files that the next run brings up to date
+        let output = crate::output_with_timeout(&mut rsync, TRANSFER_TIMEOUT, Some(cancel))?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -777,6 +1296,15 @@ fn backup_snapshot_rsync(
         log::info!("Successfully backed up subvolume: {subvol_name}");
     }
 
+    // Keep the metadata with the backup so a restore can bring it back
+    if let Err(e) = crate::btrfs::export_metadata_to_backup(snapshot_name, &snapshot_backup_dir) {
+        log::warn!("Failed to store metadata with backup of {snapshot_name}: {e}");
+    }
+
+    // Everything was copied, so this is no longer an interrupted transfer
+    fs::remove_file(snapshot_backup_dir.join(TRANSFER_STATE))
+        .context("Failed to clear transfer state")?;
+
     // Calculate total backup size
     let size_bytes = calculate_directory_size(&snapshot_backup_dir)?;
 
@@ -824,17 +1352,29 @@ pub fn list_backups(destination_mount: &str) -> Result<Vec<String>> {
         let entry = entry?;
         let path = entry.path();
 
+        // Image backups are finished files; unfinished ones end in .partial
+        if backup_image::is_image(&path) {
+            backups.push(path.to_string_lossy().to_string());
+            continue;
+        }
+
         // Skip if not a directory
         if !path.is_dir() {
             continue;
         }
 
+        // Finished by the next backup of the same snapshot
+        if is_interrupted_backup(&path) {
+            log::info!("Skipping interrupted backup: {}", path.display());
+            continue;
+        }
+
         // Check if it's a btrfs subvolume or a regular directory
         let is_btrfs_subvolume = Command::new("btrfs")
             .arg("subvolume")
             .arg("show")
             .arg(&path)
-            .output()
+            .recorded_output()
             .map(|output| output.status.success())
             .unwrap_or(false);
 
@@ -875,11 +1415,28 @@ pub fn list_backups(destination_mount: &str) -> Result<Vec<String>> {
 pub fn delete_backup(backup_path: &str) -> Result<()> {
     let path = Path::new(backup_path);
 
+    // Reported separately so callers can drop records of backups removed by hand
+    if let Err(e) = fs::symlink_metadata(path)
+        && e.kind() == std::io::ErrorKind::NotFound
+    {
+        bail!(HelperError::new(
+            ErrorCode::NotFound,
+            format!("Backup not found: {}", path.display())
+        ));
+    }
+
     // Security check: validate path is within waypoint-backups on a trusted destination
     // This uses canonicalization and checks against scanned backup destinations
     let validated_path = validate_backup_path(path)
         .context("Failed to validate backup path for deletion")?;
 
+    if backup_image::is_image(&validated_path) {
+        log::info!("Deleting image backup: {}", validated_path.display());
+        fs::remove_file(&validated_path)
+            .with_context(|| format!("Failed to remove {}", validated_path.display()))?;
+        return Ok(());
+    }
+
     // Check if it's a directory
     if !validated_path.is_dir() {
         bail!("Backup path is not a directory: {}", validated_path.display());
@@ -897,7 +1454,7 @@ pub fn delete_backup(backup_path: &str) -> Result<()> {
                     .arg("subvolume")
                     .arg("show")
                     .arg(entry.path())
-                    .output()
+                    .recorded_output()
                     .map(|output| output.status.success())
                     .unwrap_or(false)
             })
@@ -922,7 +1479,7 @@ pub fn delete_backup(backup_path: &str) -> Result<()> {
                 .arg("subvolume")
                 .arg("show")
                 .arg(&subvol_path)
-                .output()
+                .recorded_output()
                 .map(|output| output.status.success())
                 .unwrap_or(false);
 
@@ -932,7 +1489,7 @@ pub fn delete_backup(backup_path: &str) -> Result<()> {
                     .arg("subvolume")
                     .arg("delete")
                     .arg(&subvol_path)
-                    .output()
+                    .recorded_output()
                     .with_context(|| format!("Failed to delete subvolume: {}", subvol_path.display()))?;
 
                 if !output.status.success() {
@@ -1007,14 +1564,11 @@ pub fn apply_backup_retention(
         let entry = entry?;
         let path = entry.path();
 
-        if !path.is_dir() {
+        if !path.is_dir() && !backup_image::is_image(&path) {
             continue;
         }
 
-        let name = path.file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("")
-            .to_string();
+        let name = backup_snapshot_name(&path).unwrap_or("").to_string();
 
         // Get backup age from directory modification time
         let age_days = if let Ok(metadata) = entry.metadata() {
@@ -1152,7 +1706,16 @@ pub fn apply_backup_retention(
 /// 1. Detect if the backup directory contains multiple subvolumes
 /// 2. Restore each subvolume to the correct location
 /// 3. Recreate the snapshot directory structure
-pub fn restore_from_backup(backup_path: &str, snapshots_dir: &str) -> Result<String> {
+///
+/// The snapshot is restored under the backup's name, or `new_name` if given.
+/// Restoring over an existing local snapshot of that name is refused. Image
+/// backups are loop-mounted read-only for the duration of the restore.
+pub fn restore_from_backup(
+    backup_path: &str,
+    snapshots_dir: &str,
+    new_name: Option<&str>,
+    cancel: &AtomicBool,
+) -> Result<String> {
     use std::os::unix::fs::MetadataExt;
 
     let backup = Path::new(backup_path);
@@ -1180,6 +1743,34 @@ pub fn restore_from_backup(backup_path: &str, snapshots_dir: &str) -> Result<Str
         .canonicalize()
         .context("Failed to resolve snapshots directory - does not exist or is inaccessible")?;
 
+    if is_interrupted_backup(&backup) {
+        return Err(HelperError::new(
+            ErrorCode::InvalidInput,
+            "This backup was interrupted and is incomplete; run the backup again to finish it",
+        )
+        .into());
+    }
+
+    let backup_name =
+        backup_snapshot_name(&backup).ok_or_else(|| anyhow::anyhow!("Invalid backup path"))?;
+    let name = new_name.unwrap_or(backup_name);
+    waypoint_common::validate_snapshot_name(name).map_err(|e| {
+        HelperError::new(
+            ErrorCode::InvalidInput,
+            format!("Invalid snapshot name '{name}': {e}"),
+        )
+    })?;
+    if let Some(existing) = existing_snapshot(name) {
+        return Err(HelperError::new(
+            ErrorCode::AlreadyExists,
+            format!(
+                "A snapshot named '{name}' already exists at {}; restore the backup under a different name",
+                existing.display()
+            ),
+        )
+        .into());
+    }
+
     // Capture the inode of the backup path for TOCTOU protection
     let backup_metadata = fs::metadata(&backup)
         .context("Failed to get backup metadata for inode verification")?;
@@ -1190,15 +1781,13 @@ pub fn restore_from_backup(backup_path: &str, snapshots_dir: &str) -> Result<Str
     // This prevents attackers from restoring to arbitrary directories like / or /etc
     use waypoint_common::WaypointConfig;
     let config = WaypointConfig::new();
-    let expected_snapshot_dir = config.snapshot_dir.canonicalize()
-        .context("Failed to canonicalize configured snapshot directory")?;
+    let expected_snapshot_dirs = canonical_snapshot_dirs(&config)?;
 
-    if dest != expected_snapshot_dir {
+    if !expected_snapshot_dirs.contains(&dest) {
         return Err(anyhow::anyhow!(
-            "Security: Destination directory '{}' does not match the configured snapshot directory '{}'. \
+            "Security: Destination directory '{}' is not a configured snapshot directory. \
              This prevents restoring backups to arbitrary filesystem locations.",
-            dest.display(),
-            expected_snapshot_dir.display()
+            dest.display()
         ));
     }
 
@@ -1217,13 +1806,16 @@ pub fn restore_from_backup(backup_path: &str, snapshots_dir: &str) -> Result<Str
         ));
     }
 
+    // Images are restored from the backup inside them
+    let (_image_mount, source) = open_backup(&backup, backup_name)?;
+
     // Detect if backup is a btrfs subvolume or rsync directory
     // Use the canonicalized path immediately to minimize race window
     let is_btrfs_subvolume = Command::new("btrfs")
         .arg("subvolume")
         .arg("show")
-        .arg(&backup)
-        .output()
+        .arg(&source)
+        .recorded_output()
         .map(|output| output.status.success())
         .unwrap_or(false);
 
@@ -1238,15 +1830,102 @@ pub fn restore_from_backup(backup_path: &str, snapshots_dir: &str) -> Result<Str
         ));
     }
 
-    if is_btrfs_subvolume {
-        restore_from_backup_btrfs(&backup, &dest)
+    let restored_path = if is_btrfs_subvolume {
+        restore_from_backup_btrfs(&source, &dest, name, cancel)?
+    } else {
+        restore_from_backup_rsync(&source, &dest, name, cancel)?
+    };
+
+    // Bring back the description, packages and subvolume list saved with the backup
+    if let Err(e) = crate::btrfs::import_metadata_from_backup(&source, name) {
+        log::warn!("Restored {name} without its original metadata: {e}");
+    }
+
+    Ok(restored_path)
+}
+
+/// Name of the snapshot a backup directory or image holds
+fn backup_snapshot_name(backup: &Path) -> Option<&str> {
+    if backup_image::is_image(backup) {
+        backup.file_stem()?.to_str()
+    } else {
+        backup.file_name()?.to_str()
+    }
+}
+
+/// Backup directory to read a backup from, loop-mounting it first if it is an image
+///
+/// Keep the returned mount alive for as long as the directory is used.
+fn open_backup(backup: &Path, snapshot_name: &str) -> Result<(Option<ImageMount>, PathBuf)> {
+    if !backup_image::is_image(backup) {
+        return Ok((None, backup.to_path_buf()));
+    }
+    let mount = ImageMount::mount(backup, false)?;
+    let dir = mount.backup_dir(snapshot_name);
+    if !dir.is_dir() {
+        bail!(HelperError::new(
+            ErrorCode::InvalidInput,
+            format!("{} holds no backup of '{snapshot_name}'", backup.display()),
+        ));
+    }
+    Ok((Some(mount), dir))
+}
+
+/// Local snapshot of that name, in any snapshot directory
+fn existing_snapshot(name: &str) -> Option<PathBuf> {
+    let path = crate::btrfs::locate_snapshot(name);
+    fs::symlink_metadata(&path).is_ok().then_some(path)
+}
+
+/// Restore a btrfs backup using btrfs send/receive, under the name `name`
+fn restore_from_backup_btrfs(
+    backup: &Path,
+    dest: &Path,
+    name: &str,
+    cancel: &AtomicBool,
+) -> Result<String> {
+    let restored_path = dest.join(name);
+
+    if backup.file_name() == Some(name.as_ref()) {
+        receive_backup(backup, dest, cancel)?;
     } else {
-        restore_from_backup_rsync(&backup, &dest)
+        // btrfs receive keeps the name of the sent subvolume, so receive into
+        // a staging directory and move the snapshot into place from there
+        let staging = dest.join(format!(".restore-{name}"));
+        fs::create_dir(&staging)
+            .with_context(|| format!("Failed to create {}", staging.display()))?;
+        let result = receive_backup(backup, &staging, cancel).and_then(|received| {
+            let moved = fs::rename(&received, &restored_path).with_context(|| {
+                format!(
+                    "Failed to move restored snapshot to {}",
+                    restored_path.display()
+                )
+            });
+            if moved.is_err()
+                && let Err(cleanup_err) = remove_partial_subvolume(&received)
+            {
+                log::error!("{cleanup_err}");
+            }
+            moved
+        });
+        if let Err(e) = fs::remove_dir(&staging) {
+            log::warn!("Failed to remove {}: {e}", staging.display());
+        }
+        result?;
     }
+
+    // INTEGRITY VERIFICATION: Verify the restored snapshot
+    verify_restored_snapshot(backup, &restored_path, true)?;
+
+    Ok(restored_path.to_string_lossy().to_string())
 }
 
-/// Restore a btrfs backup using btrfs send/receive
-fn restore_from_backup_btrfs(backup: &Path, dest: &Path) -> Result<String> {
+/// Receive a btrfs backup into `dest`, returning the received subvolume
+fn receive_backup(backup: &Path, dest: &Path, cancel: &AtomicBool) -> Result<PathBuf> {
+    let snapshot_name = backup
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("Invalid backup path"))?;
+    let restored_path = dest.join(snapshot_name);
 
     // Build send command
     let mut send_cmd = Command::new("btrfs");
@@ -1261,7 +1940,9 @@ fn restore_from_backup_btrfs(backup: &Path, dest: &Path) -> Result<String> {
     receive_cmd.arg("receive").arg(dest);
 
     // Execute pipeline
-    let mut send_child = send_cmd.spawn().context("Failed to start btrfs send")?;
+    let mut send_child = send_cmd
+        .recorded_spawn()
+        .context("Failed to start btrfs send")?;
 
     let send_stdout = send_child
         .stdout
@@ -1278,9 +1959,25 @@ fn restore_from_backup_btrfs(backup: &Path, dest: &Path) -> Result<String> {
 
     receive_cmd.stdin(send_stdout);
 
-    let receive_output = receive_cmd
-        .output()
-        .context("Failed to run btrfs receive")?;
+    let receive_output =
+        match crate::output_with_timeout(&mut receive_cmd, TRANSFER_TIMEOUT, Some(cancel)) {
+            Ok(output) => output,
+            Err(e) => {
+                let _ = send_child.kill();
+                let _ = send_child.wait();
+                // A half-received snapshot would look like a real one
+                if restored_path.exists() {
+                    log::info!(
+                        "Removing partially restored snapshot {}",
+                        restored_path.display()
+                    );
+                    if let Err(cleanup_err) = remove_partial_subvolume(&restored_path) {
+                        log::error!("{cleanup_err}");
+                    }
+                }
+                return Err(e);
+            }
+        };
 
     let send_status = send_child.wait().context("Failed to wait for btrfs send")?;
 
@@ -1306,36 +2003,24 @@ fn restore_from_backup_btrfs(backup: &Path, dest: &Path) -> Result<String> {
         return Err(anyhow::anyhow!("btrfs receive failed: {stderr}"));
     }
 
-    // Return restored snapshot path
-    let snapshot_name = backup
-        .file_name()
-        .and_then(|n| n.to_str())
-        .ok_or_else(|| anyhow::anyhow!("Invalid backup path"))?;
-
-    let restored_path = dest.join(snapshot_name);
-
-    // INTEGRITY VERIFICATION: Verify the restored snapshot
-    verify_restored_snapshot(backup, &restored_path, true)?;
-
-    Ok(restored_path.to_string_lossy().to_string())
+    Ok(restored_path)
 }
 
-/// Restore an rsync backup by creating a new btrfs snapshot and copying files
-fn restore_from_backup_rsync(backup: &Path, dest: &Path) -> Result<String> {
-    // Get backup name
-    let snapshot_name = backup
-        .file_name()
-        .and_then(|n| n.to_str())
-        .ok_or_else(|| anyhow::anyhow!("Invalid backup path"))?;
-
-    let restored_path = dest.join(snapshot_name);
+/// Restore an rsync backup by creating a new btrfs snapshot named `name` and copying files
+fn restore_from_backup_rsync(
+    backup: &Path,
+    dest: &Path,
+    name: &str,
+    cancel: &AtomicBool,
+) -> Result<String> {
+    let restored_path = dest.join(name);
 
     // Create a new btrfs subvolume for the restored snapshot
     let output = Command::new("btrfs")
         .arg("subvolume")
         .arg("create")
         .arg(&restored_path)
-        .output()
+        .recorded_output()
         .context("Failed to create restore subvolume")?;
 
     if !output.status.success() {
@@ -1351,31 +2036,40 @@ fn restore_from_backup_rsync(backup: &Path, dest: &Path) -> Result<String> {
             .arg("subvolume")
             .arg("delete")
             .arg(&restored_path)
-            .output();
+            .recorded_output();
         return Err(e).context("Failed to create root directory");
     }
 
     // Use rsync to copy backup contents into the root directory
-    let output = Command::new("rsync")
+    let mut rsync = Command::new("rsync");
+    rsync
         .arg("-aHAX")
+        .arg(format!("--exclude=/{METADATA_SIDECAR}"))
         .arg(format!("{}/", backup.display())) // Trailing slash = copy contents
-        .arg(&root_dir)
-        .output()
-        .context("Failed to run rsync for restore")?;
+        .arg(&root_dir);
+    let result = crate::output_with_timeout(&mut rsync, TRANSFER_TIMEOUT, Some(cancel))
+        .context("Failed to run rsync for restore")
+        .and_then(|output| {
+            if output.status.success() {
+                Ok(())
+            } else {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                Err(anyhow::anyhow!("rsync restore failed: {stderr}"))
+            }
+        });
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        // RESOURCE CLEANUP: Clean up failed restore subvolume
+    if let Err(e) = result {
+        // RESOURCE CLEANUP: Clean up failed (or cancelled) restore subvolume
         log::warn!("rsync restore failed, cleaning up subvolume: {}", restored_path.display());
         if let Err(cleanup_err) = Command::new("btrfs")
             .arg("subvolume")
             .arg("delete")
             .arg(&restored_path)
-            .output()
+            .recorded_output()
         {
             log::error!("Failed to cleanup restore subvolume: {}", cleanup_err);
         }
-        return Err(anyhow::anyhow!("rsync restore failed: {stderr}"));
+        return Err(e);
     }
 
     // INTEGRITY VERIFICATION: Verify the restored snapshot
@@ -1443,7 +2137,7 @@ pub fn get_drive_stats(destination_mount: &str) -> Result<DriveStats> {
                         .arg("subvolume")
                         .arg("show")
                         .arg(&path)
-                        .output()
+                        .recorded_output()
                         .map(|output| output.status.success())
                         .unwrap_or(false)
                 }
@@ -1515,7 +2209,7 @@ fn verify_restored_snapshot(backup_source: &Path, restored_path: &Path, is_btrfs
             .arg("subvolume")
             .arg("show")
             .arg(restored_path)
-            .output()
+            .recorded_output()
             .map(|output| output.status.success())
             .unwrap_or(false);
 
@@ -1526,9 +2220,19 @@ fn verify_restored_snapshot(backup_source: &Path, restored_path: &Path, is_btrfs
     }
 
     // 4. Compare file counts and sizes with backup source
-    let backup_stats = get_directory_stats(backup_source)
+    let mut backup_stats = get_directory_stats(backup_source)
         .context("Failed to get backup source statistics for verification")?;
 
+    // The stored metadata isn't part of the restored data
+    for sidecar_name in [METADATA_SIDECAR, UNCHANGED_SUBVOLUMES] {
+        if let Ok(sidecar) = fs::metadata(backup_source.join(sidecar_name))
+            && sidecar.is_file()
+        {
+            backup_stats.0 = backup_stats.0.saturating_sub(1);
+            backup_stats.1 = backup_stats.1.saturating_sub(sidecar.len());
+        }
+    }
+
     // For rsync restores, we need to check the "root" subdirectory since that's where the actual data is
     let verify_path = if !is_btrfs && restored_path.join("root").exists() {
         restored_path.join("root")
@@ -1622,15 +2326,11 @@ pub fn verify_backup(
         let canonical = snapshot_path
             .canonicalize()
             .context("Failed to resolve snapshot path")?;
-        let canonical_snapshot_root = config
-            .snapshot_dir
-            .canonicalize()
-            .context("Failed to resolve configured snapshot directory")?;
-        if !canonical.starts_with(&canonical_snapshot_root) {
+        let snapshot_roots = canonical_snapshot_dirs(&config)?;
+        if !snapshot_roots.iter().any(|root| canonical.starts_with(root)) {
             anyhow::bail!(
-                "Security: Snapshot path {} is outside configured snapshot directory {}",
-                canonical.display(),
-                canonical_snapshot_root.display()
+                "Security: Snapshot path {} is outside the configured snapshot directories",
+                canonical.display()
             );
         }
         Some(canonical)
@@ -1648,7 +2348,24 @@ pub fn verify_backup(
         });
     }
 
-    let backup_path = waypoint_backups.join(snapshot_id);
+    // Image backups are checked through the backup inside them
+    let image = waypoint_backups.join(backup_image::image_file_name(snapshot_id));
+    let (_image_mount, backup_path) = if backup_image::is_image(&image)
+        && waypoint_common::validate_snapshot_name(snapshot_id).is_ok()
+    {
+        match open_backup(&validate_backup_path(&image)?, snapshot_id) {
+            Ok(opened) => opened,
+            Err(e) => {
+                return Ok(VerificationResult {
+                    success: false,
+                    message: "Image backup could not be read".to_string(),
+                    details: vec![e.to_string()],
+                });
+            }
+        }
+    } else {
+        (None, waypoint_backups.join(snapshot_id))
+    };
     if !backup_path.exists() {
         return Ok(VerificationResult {
             success: false,
@@ -1669,7 +2386,7 @@ pub fn verify_backup(
                     .arg("subvolume")
                     .arg("show")
                     .arg(entry.path())
-                    .output()
+                    .recorded_output()
                     .map(|output| output.status.success())
                     .unwrap_or(false)
             })
@@ -1716,7 +2433,7 @@ pub fn verify_backup(
                 .arg("subvolume")
                 .arg("show")
                 .arg(&backup_subvol)
-                .output()
+                .recorded_output()
                 .map(|output| output.status.success())
                 .unwrap_or(false);
 
@@ -1863,10 +2580,10 @@ pub fn verify_backup(
 
 /// Get directory statistics (file count and total size)
 fn get_directory_stats(path: &Path) -> Result<(usize, u64)> {
-    let output = Command::new("du")
+    let output = crate::low_priority::command("du")
         .args(["-s", "--apparent-size", "--block-size=1"])
         .arg(path)
-        .output()?;
+        .recorded_output()?;
 
     if !output.status.success() {
         bail!("Failed to get directory size");
@@ -1880,12 +2597,385 @@ fn get_directory_stats(path: &Path) -> Result<(usize, u64)> {
         .unwrap_or(0);
 
     // Count files
-    let output = Command::new("find")
+    let output = crate::low_priority::command("find")
         .arg(path)
         .args(["-type", "f"])
-        .output()?;
+        .recorded_output()?;
 
     let file_count = output.stdout.iter().filter(|&&b| b == b'\n').count();
 
     Ok((file_count, size))
 }
+
+/// Differences listed per subvolume when comparing a backup with its source
+const MAX_LISTED_DIFFERENCES: usize = 200;
+
+/// Parse the subvolume's own UUID from `btrfs subvolume show` output
+fn parse_subvolume_uuid(show_output: &str) -> Option<String> {
+    show_output.lines().find_map(|line| {
+        let uuid = line.trim().strip_prefix("UUID:")?.trim();
+        (!uuid.is_empty() && uuid != "-").then(|| uuid.to_string())
+    })
+}
+
+/// Output of `btrfs subvolume show`, if the path is a subvolume
+fn subvolume_show(path: &Path) -> Option<String> {
+    Command::new("btrfs")
+        .args(["subvolume", "show"])
+        .arg(path)
+        .recorded_output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Describe the entries of `rsync --dry-run --out-format='%i %n'` output
+///
+/// The source is the snapshot and the destination the backup, so entries
+/// rsync would delete only exist in the backup.
+fn parse_itemized_changes(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            if let Some(path) = line.strip_prefix("*deleting") {
+                return Some(format!("Only in backup: {}", path.trim()));
+            }
+
+            let (flags, path) = line.split_once(' ')?;
+            if flags.len() != 11 {
+                return None;
+            }
+            if flags[2..].contains('+') {
+                return Some(format!("Missing from backup: {path}"));
+            }
+
+            let changed: Vec<&str> = flags[2..]
+                .chars()
+                .zip([
+                    "content",
+                    "size",
+                    "modification time",
+                    "permissions",
+                    "owner",
+                    "group",
+                    "",
+                    "ACLs",
+                    "extended attributes",
+                ])
+                .filter(|(flag, what)| !matches!(flag, '.' | ' ') && !what.is_empty())
+                .map(|(_, what)| what)
+                .collect();
+            (!changed.is_empty()).then(|| format!("Differs ({}): {path}", changed.join(", ")))
+        })
+        .collect()
+}
+
+/// Compare two directory trees by size, time and attributes without copying anything
+fn compare_contents(source: &Path, backup: &Path) -> Result<Vec<String>> {
+    let output = crate::low_priority::command("rsync")
+        .args([
+            "-aHAX",
+            "--dry-run",
+            "--delete",
+            "--no-inc-recursive",
+            "--out-format=%i %n",
+        ])
+        .arg(format!("{}/", source.display()))
+        .arg(format!("{}/", backup.display()))
+        .recorded_output()
+        .context("Failed to run rsync")?;
+
+    if !output.status.success() {
+        bail!(
+            "rsync failed comparing {}: {}",
+            source.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_itemized_changes(&stdout))
+}
+
+/// Compare a backup with the snapshot it was made from
+///
+/// A btrfs backup that is a read-only receive of the source subvolume is
+/// identical by construction, so only its received UUID is checked. Other
+/// subvolumes, rsync backups and btrfs backups that reused an earlier
+/// subvolume are compared file by file.
+pub fn compare_backup_with_source(
+    snapshot_path: &str,
+    destination_mount: &str,
+    snapshot_id: &str,
+) -> Result<BackupComparison> {
+    let config = WaypointConfig::new();
+    let canonical_destination = validate_backup_destination(destination_mount)?;
+
+    let snapshot = Path::new(snapshot_path).canonicalize().map_err(|_| {
+        HelperError::new(
+            ErrorCode::NotFound,
+            format!("Snapshot {snapshot_id} no longer exists, there is nothing to compare with"),
+        )
+    })?;
+    let snapshot_roots = canonical_snapshot_dirs(&config)?;
+    if !snapshot_roots.iter().any(|root| snapshot.starts_with(root)) {
+        bail!(
+            "Security: Snapshot path {} is outside the configured snapshot directories",
+            snapshot.display()
+        );
+    }
+
+    let backups_dir = canonical_destination.join("waypoint-backups");
+    let image = backups_dir.join(backup_image::image_file_name(snapshot_id));
+    let backup_path = if backup_image::is_image(&image) {
+        image
+    } else {
+        backups_dir.join(snapshot_id)
+    };
+    let backup_path = validate_backup_path(&backup_path).map_err(|_| {
+        HelperError::new(
+            ErrorCode::NotFound,
+            format!("Backup {snapshot_id} not found"),
+        )
+    })?;
+    if is_interrupted_backup(&backup_path) {
+        return Err(HelperError::new(
+            ErrorCode::InvalidInput,
+            format!("Backup {snapshot_id} is incomplete; run the backup again to finish it"),
+        )
+        .into());
+    }
+
+    let (_image_mount, backup_path) = open_backup(&backup_path, snapshot_id)?;
+    let metadata = load_snapshot_metadata(snapshot_id)?;
+    compare_subvolumes(&snapshot, &backup_path, &metadata.subvolumes)
+}
+
+/// Compare the given subvolumes of a snapshot with their copies in a backup
+///
+/// Subvolumes the backup reused from an earlier one (see `UNCHANGED_SUBVOLUMES`)
+/// are compared with the copy in that earlier backup.
+fn compare_subvolumes(
+    snapshot: &Path,
+    backup_path: &Path,
+    subvolumes: &[PathBuf],
+) -> Result<BackupComparison> {
+    let mut comparison = BackupComparison::default();
+    let unchanged = read_unchanged_subvolumes(backup_path);
+
+    for mount_point in subvolumes {
+        let subvol_name = mount_point_to_subdir_name(mount_point);
+        let source = snapshot.join(&subvol_name);
+        let reused_from = unchanged
+            .get(&subvol_name)
+            .filter(|origin| waypoint_common::validate_snapshot_name(origin).is_ok());
+        let backup = match reused_from {
+            Some(origin) => backup_path.with_file_name(origin).join(&subvol_name),
+            None => backup_path.join(&subvol_name),
+        };
+
+        if !source.exists() {
+            log::warn!("Subvolume '{subvol_name}' not found in snapshot, not comparing it");
+            continue;
+        }
+        if !backup.exists() {
+            comparison.subvolumes.push(SubvolumeComparison {
+                subvolume: subvol_name,
+                method: "contents".to_string(),
+                differences: vec!["Missing from backup".to_string()],
+                difference_count: 1,
+            });
+            continue;
+        }
+
+        // A reused copy was received from another snapshot, so only its contents can match
+        if let Some(origin) = reused_from {
+            log::info!("Backup of '{subvol_name}' was reused from '{origin}', comparing contents");
+        } else if let Some(backup_show) = subvolume_show(&backup) {
+            let source_uuid = subvolume_show(&source).and_then(|show| parse_subvolume_uuid(&show));
+            let received_uuid = parse_received_uuid(&backup_show);
+            if source_uuid.is_some()
+                && source_uuid == received_uuid
+                && crate::btrfs::is_read_only(&backup).unwrap_or(false)
+            {
+                comparison.subvolumes.push(SubvolumeComparison {
+                    subvolume: subvol_name,
+                    method: "received-uuid".to_string(),
+                    ..Default::default()
+                });
+                continue;
+            }
+            log::info!(
+                "Backup of '{subvol_name}' isn't a plain receive of the source, comparing contents"
+            );
+        }
+
+        // rsync backups copy the subvolume's root directory, not the subvolume itself
+        let rsync_source = source.join("root");
+        let source_dir = if subvolume_show(&backup).is_none() && rsync_source.is_dir() {
+            rsync_source
+        } else {
+            source
+        };
+
+        let mut differences = compare_contents(&source_dir, &backup)?;
+        let difference_count = differences.len();
+        differences.truncate(MAX_LISTED_DIFFERENCES);
+        comparison.subvolumes.push(SubvolumeComparison {
+            subvolume: subvol_name,
+            method: "contents".to_string(),
+            differences,
+            difference_count,
+        });
+    }
+
+    Ok(comparison)
+}
+
+/// Describe what restoring a backup would do
+///
+/// Lists what the backup contains and, if a local snapshot already has its
+/// name, how that snapshot differs from the backup, so the caller can restore
+/// under another name instead.
+pub fn preview_restore_from_backup(backup_path: &str) -> Result<BackupRestorePreview> {
+    let backup = validate_backup_path(Path::new(backup_path))?;
+    if is_interrupted_backup(&backup) {
+        return Err(HelperError::new(
+            ErrorCode::InvalidInput,
+            "This backup was interrupted and is incomplete; run the backup again to finish it",
+        )
+        .into());
+    }
+
+    let snapshot_name = backup_snapshot_name(&backup)
+        .ok_or_else(|| anyhow::anyhow!("Invalid backup path"))?
+        .to_string();
+    let (_image_mount, backup) = open_backup(&backup, &snapshot_name)?;
+    let mut preview = BackupRestorePreview {
+        snapshot_name: snapshot_name.clone(),
+        ..Default::default()
+    };
+
+    match crate::btrfs::read_backup_metadata(&backup) {
+        Ok(metadata) => {
+            preview.description = metadata.description;
+            preview.timestamp = Some(metadata.timestamp);
+            preview.subvolumes = metadata.subvolumes;
+        }
+        Err(e) => log::info!("No metadata in backup {}: {e}", backup.display()),
+    }
+
+    if waypoint_common::validate_snapshot_name(&snapshot_name).is_ok()
+        && let Some(existing) = existing_snapshot(&snapshot_name)
+    {
+        // Compare the subvolumes of the local snapshot, or else those in the backup
+        let subvolumes = load_snapshot_metadata(&snapshot_name)
+            .map(|local| local.subvolumes)
+            .unwrap_or_else(|_| preview.subvolumes.clone());
+        preview.comparison = Some(compare_subvolumes(&existing, &backup, &subvolumes)?);
+        preview.existing_snapshot = Some(existing);
+    }
+
+    Ok(preview)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_snapshot_name() {
+        let dir = std::env::temp_dir().join(format!("waypoint-backup-name-{}", std::process::id()));
+        fs::create_dir_all(dir.join("pre-upgrade")).unwrap();
+        fs::write(dir.join("weekly.img"), b"").unwrap();
+
+        assert_eq!(
+            backup_snapshot_name(&dir.join("pre-upgrade")),
+            Some("pre-upgrade")
+        );
+        assert_eq!(
+            backup_snapshot_name(&dir.join("weekly.img")),
+            Some("weekly")
+        );
+        // Only image files lose their extension
+        assert_eq!(
+            backup_snapshot_name(&dir.join("missing.img")),
+            Some("missing.img")
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_received_uuid() {
+        let complete = "\
+@snapshots/daily-20250101-0300/root
+\tName: \t\t\troot
+\tUUID: \t\t\t2d1e0a44-6c6a-e54b-b2f2-6dd2c0d7cf26
+\tParent UUID: \t\t-
+\tReceived UUID: \t\tf3e1c6b1-1e0f-4c44-9d6b-2a0f2b9b8b0d
+\tFlags: \t\t\treadonly
+";
+        assert_eq!(
+            parse_received_uuid(complete).as_deref(),
+            Some("f3e1c6b1-1e0f-4c44-9d6b-2a0f2b9b8b0d")
+        );
+
+        let partial = complete.replace("f3e1c6b1-1e0f-4c44-9d6b-2a0f2b9b8b0d", "-");
+        assert!(parse_received_uuid(&partial).is_none());
+        assert!(parse_received_uuid("ERROR: not a subvolume").is_none());
+    }
+
+    #[test]
+    fn test_parse_itemized_changes() {
+        let output = "\
+.d..t...... ./
+>f.st...... etc/fstab
+cd+++++++++ etc/new/
+>f+++++++++ etc/new/file
+*deleting   var/tmp/stale
+.f...p..... usr/bin/tool
+";
+        assert_eq!(
+            parse_itemized_changes(output),
+            vec![
+                "Differs (modification time): ./",
+                "Differs (size, modification time): etc/fstab",
+                "Missing from backup: etc/new/",
+                "Missing from backup: etc/new/file",
+                "Only in backup: var/tmp/stale",
+                "Differs (permissions): usr/bin/tool",
+            ]
+        );
+        assert!(parse_itemized_changes("sending incremental file list\n").is_empty());
+
+        let show = "root\n\tUUID: \t\t\t2d1e0a44\n\tParent UUID: \t\t-\n";
+        assert_eq!(parse_subvolume_uuid(show).as_deref(), Some("2d1e0a44"));
+    }
+
+    #[test]
+    fn test_is_empty_incremental_dump() {
+        let unchanged = "snapshot        ./root                          uuid=0f3b transid=120 parent_uuid=9a1c parent_transid=110\n";
+        assert!(is_empty_incremental_dump(unchanged));
+
+        let changed = format!(
+            "{unchanged}utimes          ./root/                         atime=2026-10-01T10:00:00+0000\n"
+        );
+        assert!(!is_empty_incremental_dump(&changed));
+
+        assert!(!is_empty_incremental_dump(""));
+    }
+
+    #[test]
+    fn test_dump_data_bytes() {
+        let dump = "\
+snapshot        ./root                          uuid=0f3b transid=120 parent_uuid=9a1c parent_transid=110
+update_extent   ./root/etc/hostname             offset=0 len=4096
+update_extent   ./root/var/log/messages         offset=8192 len=131072
+clone           ./root/usr/lib/libfoo.so        offset=0 len=65536 from=./root/usr/lib/libbar.so clone_offset=0
+truncate        ./root/etc/hostname             size=12
+";
+        assert_eq!(dump_data_bytes(dump), 4096 + 131072);
+        assert_eq!(dump_data_bytes(""), 0);
+    }
+}
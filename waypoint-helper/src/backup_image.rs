@@ -0,0 +1,360 @@
+//! Self-contained image backups
+//!
+//! An image backup is a btrfs filesystem in a regular file,
+//! `waypoint-backups/<snapshot>.img` on the destination. Inside it the snapshot
+//! is laid out exactly as on a btrfs destination, so the image can be archived
+//! or burned to optical media and restored later by loop-mounting it. Finished
+//! images are shrunk to what the filesystem needs and turned into btrfs seed
+//! devices, which the kernel only mounts read-only.
+
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::SyncSender;
+use waypoint_common::{ErrorCode, HelperError};
+
+use crate::backup::BackupProgress;
+use crate::transcript::RecordedCommand;
+
+/// File extension of image backups
+pub const IMAGE_EXTENSION: &str = "img";
+
+/// Where images are loop-mounted while the helper works with them
+const IMAGE_MOUNT_ROOT: &str = "/run/waypoint/images";
+
+/// Smallest image created, leaving mkfs.btrfs room for its metadata
+const MIN_IMAGE_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Metadata needed per file or directory: inode, directory entries and extent
+/// items, or the whole file if it is small enough to be stored inline
+const METADATA_PER_INODE: u64 = 8 * 1024;
+
+/// Copies btrfs keeps of metadata on a single device (DUP profile)
+const METADATA_COPIES: u64 = 2;
+
+/// Largest file a FAT filesystem can hold
+const FAT_MAX_FILE_SIZE: u64 = 4 * 1024 * 1024 * 1024 - 1;
+
+/// Tells concurrent mounts of images with the same name apart
+static NEXT_MOUNT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// File name of the image backup of `snapshot_name`
+pub fn image_file_name(snapshot_name: &str) -> String {
+    format!("{snapshot_name}.{IMAGE_EXTENSION}")
+}
+
+/// Whether `path` is an image backup rather than a backup directory
+pub fn is_image(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == IMAGE_EXTENSION) && path.is_file()
+}
+
+/// Size of an image able to hold `data_bytes` of snapshot data in `inodes` files
+///
+/// Metadata is estimated from the number of files, since snapshots of many small
+/// files need far more of it than their data size suggests, and counted twice for
+/// the DUP profile. A quarter of the data is added for checksums and partly filled
+/// chunks. The image is shrunk once the snapshot is in it, so erring on the large
+/// side only costs space while the backup runs.
+fn image_size(data_bytes: u64, inodes: u64) -> u64 {
+    let metadata = inodes
+        .saturating_mul(METADATA_PER_INODE)
+        .saturating_mul(METADATA_COPIES);
+    data_bytes
+        .saturating_add(data_bytes / 4)
+        .saturating_add(metadata)
+        .saturating_add(MIN_IMAGE_SIZE)
+}
+
+/// Number of files and directories below `path`
+fn count_inodes(path: &Path) -> Result<u64> {
+    let output = crate::low_priority::command("du")
+        .args(["-s", "--inodes"])
+        .arg(path)
+        .recorded_output()
+        .context("Failed to run du command")?;
+    if !output.status.success() {
+        bail!(
+            "du failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .next()
+        .and_then(|count| count.parse().ok())
+        .ok_or_else(|| anyhow::anyhow!("Invalid du output"))
+}
+
+/// Parse the byte count from `btrfs inspect-internal min-dev-size`
+///
+/// The output looks like `1234567168 bytes (1.15GiB)`.
+fn parse_min_dev_size(output: &str) -> Option<u64> {
+    let mut fields = output.split_whitespace();
+    let bytes = fields.next()?.parse().ok()?;
+    (fields.next()? == "bytes").then_some(bytes)
+}
+
+/// Image loop-mounted by the helper, unmounted again on drop
+pub struct ImageMount {
+    mount_point: PathBuf,
+    mounted: bool,
+}
+
+impl ImageMount {
+    /// Loop-mount `image`, read-only unless `writable` is set
+    pub fn mount(image: &Path, writable: bool) -> Result<Self> {
+        let name = image
+            .file_stem()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| anyhow::anyhow!("Invalid image path: {}", image.display()))?;
+        let id = NEXT_MOUNT_ID.fetch_add(1, Ordering::Relaxed);
+        let mount_point = Path::new(IMAGE_MOUNT_ROOT).join(format!("{name}-{id}"));
+        fs::create_dir_all(&mount_point)
+            .with_context(|| format!("Failed to create {}", mount_point.display()))?;
+
+        let options = if writable { "loop" } else { "loop,ro" };
+        let image_str = image.to_string_lossy();
+        let mount_point_str = mount_point.to_string_lossy();
+        let args = ["-t", "btrfs", "-o", options, &image_str, &mount_point_str];
+        if let Err(e) = crate::run_command("mount", &args) {
+            let _ = fs::remove_dir(&mount_point);
+            return Err(e.context(format!("Failed to mount image {}", image.display())));
+        }
+
+        log::info!(
+            "Mounted image {} at {}",
+            image.display(),
+            mount_point.display()
+        );
+        Ok(Self {
+            mount_point,
+            mounted: true,
+        })
+    }
+
+    /// Directory the image is mounted on
+    pub fn path(&self) -> &Path {
+        &self.mount_point
+    }
+
+    /// Backup directory of `snapshot_name` inside the image
+    pub fn backup_dir(&self, snapshot_name: &str) -> PathBuf {
+        self.mount_point
+            .join("waypoint-backups")
+            .join(snapshot_name)
+    }
+
+    /// Unmount the image, reporting failures instead of only logging them
+    fn unmount(&mut self) -> Result<()> {
+        if !self.mounted {
+            return Ok(());
+        }
+        let mount_point = self.mount_point.to_string_lossy().to_string();
+        crate::run_command("umount", &[&mount_point])?;
+        self.mounted = false;
+        let _ = fs::remove_dir(&self.mount_point);
+        log::info!("Unmounted image at {mount_point}");
+        Ok(())
+    }
+}
+
+impl Drop for ImageMount {
+    fn drop(&mut self) {
+        if let Err(e) = self.unmount() {
+            log::warn!(
+                "Failed to unmount image at {}: {e}",
+                self.mount_point.display()
+            );
+        }
+    }
+}
+
+/// Back up a snapshot into an image file on the destination
+///
+/// The image is written as `<snapshot>.img.partial` and renamed once it is
+/// complete, so an interrupted backup never looks like a finished one. An
+/// existing image of the snapshot is kept as it is. Returns the path of the
+/// image and its size.
+pub fn backup_snapshot_image(
+    snapshot_path: &str,
+    destination_mount: &str,
+    fstype: &str,
+    progress_tx: Option<SyncSender<BackupProgress>>,
+    cancel: &AtomicBool,
+) -> Result<(String, u64)> {
+    let snapshot = Path::new(snapshot_path);
+    let snapshot_name = snapshot
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| anyhow::anyhow!("Invalid snapshot path"))?;
+
+    let backup_dir = Path::new(destination_mount).join("waypoint-backups");
+    fs::create_dir_all(&backup_dir).context("Failed to create backup directory")?;
+
+    let image = backup_dir.join(image_file_name(snapshot_name));
+    if image.exists() {
+        log::info!("Image backup of '{snapshot_name}' already exists, keeping it");
+        let size = fs::metadata(&image)?.len();
+        return Ok((image.to_string_lossy().to_string(), size));
+    }
+
+    let size = image_size(
+        crate::backup::calculate_directory_size(snapshot)?,
+        count_inodes(snapshot)?,
+    );
+    if fstype == "vfat" && size > FAT_MAX_FILE_SIZE {
+        bail!(HelperError::new(
+            ErrorCode::InvalidInput,
+            format!(
+                "An image of '{snapshot_name}' needs {}, more than a FAT drive can hold in one file",
+                waypoint_common::format_bytes(size)
+            ),
+        ));
+    }
+
+    crate::backup::check_destination_space(&backup_dir, size)?;
+
+    let partial = backup_dir.join(format!("{}.partial", image_file_name(snapshot_name)));
+    log::info!(
+        "Writing image backup of '{snapshot_name}' ({}) to {}",
+        waypoint_common::format_bytes(size),
+        image.display()
+    );
+    if let Err(e) = write_image(snapshot_path, &partial, size, progress_tx, cancel) {
+        if let Err(cleanup_err) = fs::remove_file(&partial) {
+            log::warn!("Failed to remove {}: {cleanup_err}", partial.display());
+        }
+        return Err(e);
+    }
+
+    fs::rename(&partial, &image)
+        .with_context(|| format!("Failed to move image into place at {}", image.display()))?;
+    let size = fs::metadata(&image)?.len();
+    Ok((image.to_string_lossy().to_string(), size))
+}
+
+/// Shrink the filesystem of a mounted image to the smallest size it fits in
+///
+/// Returns the new size, which the image file can then be truncated to.
+fn shrink_filesystem(mount: &ImageMount) -> Result<u64> {
+    let mount_point = mount.path().to_string_lossy().to_string();
+    let (stdout, _) = crate::run_command_with_output(
+        "btrfs",
+        &["inspect-internal", "min-dev-size", &mount_point],
+    )?;
+    let min_size = parse_min_dev_size(&stdout)
+        .ok_or_else(|| anyhow::anyhow!("Unexpected min-dev-size output: {}", stdout.trim()))?;
+    crate::run_command(
+        "btrfs",
+        &["filesystem", "resize", &min_size.to_string(), &mount_point],
+    )?;
+    Ok(min_size)
+}
+
+/// Create a btrfs filesystem of `size` bytes in `image` and receive the snapshot into it
+///
+/// Afterwards the image is shrunk to what the received snapshot needs.
+fn write_image(
+    snapshot_path: &str,
+    image: &Path,
+    size: u64,
+    progress_tx: Option<SyncSender<BackupProgress>>,
+    cancel: &AtomicBool,
+) -> Result<()> {
+    let file =
+        fs::File::create(image).with_context(|| format!("Failed to create {}", image.display()))?;
+    file.set_len(size)
+        .with_context(|| format!("Failed to allocate {}", image.display()))?;
+    drop(file);
+
+    let image_str = image.to_string_lossy();
+    crate::run_command("mkfs.btrfs", &["-q", "-L", "waypoint-backup", &image_str])
+        .context("Failed to create the image filesystem")?;
+
+    let mut mount = ImageMount::mount(image, true)?;
+    let mount_point = mount.path().to_string_lossy().to_string();
+    crate::backup::backup_snapshot_btrfs(snapshot_path, &mount_point, None, progress_tx, cancel)?;
+
+    // A shrink that fails only leaves the image larger than it needs to be
+    let shrunk = shrink_filesystem(&mount)
+        .inspect_err(|e| log::warn!("Could not shrink {}: {e}", image.display()))
+        .ok();
+    mount.unmount()?;
+    if let Some(new_size) = shrunk.filter(|&new_size| new_size < size) {
+        fs::OpenOptions::new()
+            .write(true)
+            .open(image)
+            .and_then(|file| file.set_len(new_size))
+            .with_context(|| format!("Failed to truncate {}", image.display()))?;
+        log::info!(
+            "Shrunk {} to {}",
+            image.display(),
+            waypoint_common::format_bytes(new_size)
+        );
+    }
+
+    // A seed device can only be mounted read-only, which keeps the archive intact
+    match Command::new("btrfstune")
+        .args(["-S", "1"])
+        .arg(image)
+        .recorded_output()
+    {
+        Ok(output) if output.status.success() => {}
+        Ok(output) => log::warn!(
+            "Could not mark {} as a seed device: {}",
+            image.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+        Err(e) => log::warn!(
+            "Could not run btrfstune, {} stays writable: {e}",
+            image.display()
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_size() {
+        assert_eq!(image_size(0, 0), MIN_IMAGE_SIZE);
+        assert_eq!(image_size(4 << 30, 0), (5 << 30) + MIN_IMAGE_SIZE);
+        assert_eq!(image_size(u64::MAX, u64::MAX), u64::MAX);
+        assert_eq!(image_file_name("pre-upgrade"), "pre-upgrade.img");
+
+        // A million small files need far more than a quarter of their data in metadata
+        let small_files = image_size(100 << 20, 1_000_000);
+        assert!(small_files > 1_000_000 * METADATA_PER_INODE * METADATA_COPIES);
+    }
+
+    #[test]
+    fn test_parse_min_dev_size() {
+        assert_eq!(
+            parse_min_dev_size("1234567168 bytes (1.15GiB)\n"),
+            Some(1_234_567_168)
+        );
+        assert_eq!(parse_min_dev_size(""), None);
+        assert_eq!(parse_min_dev_size("ERROR: not a btrfs filesystem"), None);
+    }
+
+    #[test]
+    fn test_is_image() {
+        let dir = std::env::temp_dir().join(format!("waypoint-image-test-{}", std::process::id()));
+        fs::create_dir_all(dir.join("snapshot.img")).unwrap();
+        fs::write(dir.join("pre-upgrade.img"), b"").unwrap();
+        fs::write(dir.join("notes.txt"), b"").unwrap();
+
+        assert!(is_image(&dir.join("pre-upgrade.img")));
+        // Backup directories and other files aren't images, nor is a missing file
+        assert!(!is_image(&dir.join("snapshot.img")));
+        assert!(!is_image(&dir.join("notes.txt")));
+        assert!(!is_image(&dir.join("missing.img")));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,195 @@
+// Btrfs balance of the snapshot filesystem for waypoint-helper
+//
+// Filesystems full of snapshots can run out of unallocated space while data
+// and metadata chunks are only partly used, so writes fail with ENOSPC even
+// though df reports free space. A filtered balance rewrites the emptiest
+// chunks and returns their space to the unallocated pool. Balances can run
+// for hours, so they are started in the background and monitored through
+// `btrfs balance status`. This is a manual maintenance action.
+
+use anyhow::{Context, Result, bail};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use waypoint_common::{BalanceStatus, ErrorCode, HelperError, WaypointConfig};
+
+use crate::transcript::RecordedCommand;
+
+/// Set while a balance started by the helper runs
+static BALANCE_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Outcome of the last balance started by the helper
+static LAST_RESULT: Mutex<Option<String>> = Mutex::new(None);
+
+fn filesystem_path() -> PathBuf {
+    WaypointConfig::new().snapshot_dir
+}
+
+/// Parse `btrfs balance status` output
+///
+/// Example of a running balance:
+/// ```text
+/// Balance on '/' is running
+/// 12 out of about 40 chunks balanced (13 considered),  70% left
+/// ```
+fn parse_balance_status(output: &str) -> BalanceStatus {
+    let mut status = BalanceStatus::default();
+
+    for line in output.lines() {
+        let line = line.trim();
+        if line.starts_with("Balance on") {
+            status.running = line.contains("is running") || line.contains("is paused");
+            status.paused = line.contains("is paused");
+        } else if let Some((balanced, rest)) = line.split_once(" out of about ") {
+            status.chunks_balanced = balanced.trim().parse().unwrap_or(0);
+            status.chunks_total = rest
+                .split_whitespace()
+                .next()
+                .and_then(|total| total.parse().ok())
+                .unwrap_or(0);
+        }
+    }
+
+    status
+}
+
+/// Current balance state of the snapshot filesystem
+pub fn status() -> Result<BalanceStatus> {
+    // Exits with 1 while a balance is running or paused, so check the output instead
+    let output = Command::new("btrfs")
+        .args(["balance", "status"])
+        .arg(filesystem_path())
+        .recorded_output()
+        .context("Failed to run btrfs balance status")?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if output.status.code() == Some(2) {
+        bail!(
+            "btrfs balance status failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let mut status = parse_balance_status(&stdout);
+    status.last_result = LAST_RESULT.lock().ok().and_then(|result| result.clone());
+    Ok(status)
+}
+
+/// Start a filtered balance of chunks at most `usage` percent full
+///
+/// Returns the running `btrfs balance start` process; pass it to `wait` to
+/// collect the outcome.
+pub fn start(usage: u32) -> Result<Child> {
+    if usage == 0 || usage > 100 {
+        return Err(HelperError::new(
+            ErrorCode::InvalidInput,
+            format!("Usage filter must be between 1 and 100, got {usage}"),
+        )
+        .into());
+    }
+
+    if BALANCE_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err(HelperError::new(ErrorCode::Busy, "A balance is already running").into());
+    }
+
+    let result = (|| {
+        if status()?.running {
+            return Err(HelperError::new(
+                ErrorCode::Busy,
+                "A balance is already running on this filesystem",
+            )
+            .into());
+        }
+
+        let path = filesystem_path();
+        log::info!("Starting balance of {} with usage filter {usage}%", path.display());
+        Command::new("btrfs")
+            .args(["balance", "start"])
+            .arg(format!("-dusage={usage}"))
+            .arg(format!("-musage={usage}"))
+            .arg(&path)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .recorded_spawn()
+            .context("Failed to run btrfs balance start")
+    })();
+
+    if result.is_err() {
+        BALANCE_RUNNING.store(false, Ordering::SeqCst);
+    }
+    result
+}
+
+/// Wait for a balance started with `start` and record its outcome
+///
+/// Returns `Ok(true)` if it completed and `Ok(false)` if it was cancelled.
+pub fn wait(mut child: Child) -> Result<bool> {
+    let stderr = child.stderr.take();
+    let status = child.wait().context("Failed to wait for btrfs balance");
+    let stderr = stderr
+        .map(|mut stderr| {
+            let mut text = String::new();
+            let _ = std::io::Read::read_to_string(&mut stderr, &mut text);
+            text
+        })
+        .unwrap_or_default();
+    BALANCE_RUNNING.store(false, Ordering::SeqCst);
+
+    let result = match status {
+        Ok(status) if status.success() => Ok(true),
+        // A cancelled balance exits with an error mentioning the cancellation
+        Ok(_) if stderr.contains("canceled") || stderr.contains("cancelled") => Ok(false),
+        Ok(_) => Err(anyhow::anyhow!("btrfs balance failed: {}", stderr.trim())),
+        Err(e) => Err(e),
+    };
+
+    let summary = match &result {
+        Ok(true) => "complete".to_string(),
+        Ok(false) => "cancelled".to_string(),
+        Err(e) => format!("failed: {e}"),
+    };
+    log::info!("Balance finished: {summary}");
+    if let Ok(mut last) = LAST_RESULT.lock() {
+        *last = Some(summary);
+    }
+
+    result
+}
+
+/// Ask the running balance to stop after the chunk it is working on
+pub fn cancel() -> Result<()> {
+    if !status()?.running {
+        return Err(HelperError::new(ErrorCode::NotFound, "No balance is running").into());
+    }
+    let path = filesystem_path();
+    crate::run_command("btrfs", &["balance", "cancel", &path.to_string_lossy()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_balance_status_running() {
+        let output = "Balance on '/' is running\n\
+                      12 out of about 40 chunks balanced (13 considered),  70% left\n";
+        let status = parse_balance_status(output);
+        assert!(status.running);
+        assert!(!status.paused);
+        assert_eq!(status.chunks_balanced, 12);
+        assert_eq!(status.chunks_total, 40);
+    }
+
+    #[test]
+    fn test_parse_balance_status_paused_and_idle() {
+        let status = parse_balance_status(
+            "Balance on '/.snapshots' is paused\n3 out of about 9 chunks balanced (4 considered),  67% left\n",
+        );
+        assert!(status.running);
+        assert!(status.paused);
+
+        let status = parse_balance_status("No balance found on '/'\n");
+        assert_eq!(status, BalanceStatus::default());
+    }
+}
@@ -6,15 +6,36 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
 use version_compare::{Cmp, compare};
-use waypoint_common::{Package, SnapshotInfo, WaypointConfig};
+use waypoint_common::{
+    ConflictResolution, ErrorCode, HelperError, MetadataConflict, MetadataFormat, Package,
+    SnapshotCategory, SnapshotInfo, SnapshotSharing, TransactionLink, TransactionPhase,
+    WaypointConfig,
+};
+
+use crate::metadata_check;
+use crate::transcript::RecordedCommand;
+
+/// Metadata sidecar written inside each snapshot directory
+///
+/// Keeps a copy of the snapshot's metadata next to its subvolumes, so the
+/// snapshot stays self-describing if the central metadata file is lost or the
+/// entry goes missing.
+pub const METADATA_SIDECAR: &str = ".waypoint-metadata.json";
 
 /// Global configuration instance
 static CONFIG: OnceLock<WaypointConfig> = OnceLock::new();
 
 /// Initialize the global configuration (called once at startup)
+///
+/// Also reports a read-only snapshot filesystem right away, since every
+/// snapshot operation will fail until it is repaired.
 pub fn init_config() {
-    CONFIG.get_or_init(WaypointConfig::new);
+    let config = CONFIG.get_or_init(WaypointConfig::new);
+    if let Err(e) = crate::filesystem_stats::check_writable(&config.snapshot_dir) {
+        log::error!("{e}");
+    }
 }
 
 /// Get the snapshot directory path
@@ -25,6 +46,61 @@ fn snapshot_dir() -> &'static Path {
         .as_path()
 }
 
+/// Fail early if the snapshot directory holding `snapshot_path` is read-only
+fn check_writable_parent(snapshot_path: &Path) -> Result<()> {
+    crate::filesystem_stats::check_writable(snapshot_path.parent().unwrap_or(snapshot_dir()))
+}
+
+/// Get all snapshot directories, the primary one first
+fn snapshot_dirs() -> Vec<PathBuf> {
+    CONFIG.get_or_init(WaypointConfig::new).snapshot_dirs()
+}
+
+/// Get the path of a snapshot in whichever snapshot directory holds it
+///
+/// Snapshots that don't exist yet resolve to the primary snapshot directory.
+pub fn locate_snapshot(name: &str) -> PathBuf {
+    CONFIG
+        .get_or_init(WaypointConfig::new)
+        .locate_snapshot_dir(name)
+        .join(name)
+}
+
+/// Snapshot directory new snapshots of `subvolumes` go to
+///
+/// Btrfs can only snapshot a subvolume into its own filesystem, so this is
+/// the first snapshot directory on the same filesystem as the subvolumes, or
+/// the primary one if none is. With a single snapshot directory, nothing needs
+/// to be looked up.
+fn snapshot_dir_for(subvolumes: &[PathBuf]) -> Result<PathBuf> {
+    let dirs = snapshot_dirs();
+    if dirs.len() == 1 {
+        return Ok(dirs[0].clone());
+    }
+
+    let mut uuids = subvolumes
+        .iter()
+        .map(|subvolume| get_filesystem_uuid(subvolume))
+        .collect::<Result<Vec<_>>>()?;
+    uuids.sort();
+    uuids.dedup();
+    if uuids.len() > 1 {
+        bail!(HelperError::new(
+            ErrorCode::InvalidInput,
+            "The subvolumes are on different filesystems; snapshot each filesystem separately",
+        ));
+    }
+
+    let dir = uuids
+        .first()
+        .and_then(|uuid| {
+            dirs.iter()
+                .find(|dir| get_filesystem_uuid(dir).is_ok_and(|dir_uuid| &dir_uuid == uuid))
+        })
+        .unwrap_or(&dirs[0]);
+    Ok(dir.clone())
+}
+
 /// Get the metadata file path
 fn metadata_file() -> &'static Path {
     CONFIG
@@ -48,6 +124,28 @@ pub struct Snapshot {
     /// List of subvolumes included in this snapshot (mount points)
     #[serde(default)]
     pub subvolumes: Vec<PathBuf>,
+    /// Prefix of the schedule whose retention applies, overriding the name prefix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention_group: Option<String>,
+    /// When the snapshot is deleted by cleanup, regardless of schedule retention
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Package manager transaction the snapshot was taken before or after
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction: Option<TransactionLink>,
+    /// Why the snapshot was taken
+    #[serde(default, skip_serializing_if = "SnapshotCategory::is_manual")]
+    pub category: SnapshotCategory,
+    /// Pinned by the user, so cleanup never deletes it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_favorite: bool,
+}
+
+impl Snapshot {
+    /// Whether the snapshot's expiry date has passed
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
 }
 
 impl From<Snapshot> for SnapshotInfo {
@@ -59,119 +157,148 @@ impl From<Snapshot> for SnapshotInfo {
             package_count: s.package_count,
             packages: s.packages,
             subvolumes: s.subvolumes,
+            retention_group: s.retention_group,
+            expires_at: s.expires_at,
+            transaction: s.transaction,
+            category: s.category,
         }
     }
 }
 
 /// Create a new snapshot of multiple subvolumes
+///
+/// Each `btrfs subvolume snapshot` call is limited to the configured snapshot
+/// timeout, and `cancel` is checked between subvolumes. On any failure the
+/// subvolumes created so far are deleted again. With a `qgroup`, each snapshot
+/// subvolume is inherited into it (see `snapshot_qgroup`).
 pub fn create_snapshot(
     name: &str,
     description: Option<&str>,
     packages: Vec<Package>,
+    kernel_version: Option<String>,
     subvolumes: Vec<PathBuf>,
+    qgroup: Option<&str>,
+    cancel: Option<&AtomicBool>,
 ) -> Result<()> {
     ensure_snapshot_name(name)?;
 
+    // Metadata and backups are keyed by name, so it must be unique across directories
+    if snapshot_dirs().iter().any(|dir| dir.join(name).exists()) {
+        bail!(HelperError::new(
+            ErrorCode::AlreadyExists,
+            format!("A snapshot named '{name}' already exists"),
+        ));
+    }
+
     // Default to root if no subvolumes specified
     let subvolumes_to_snapshot = if subvolumes.is_empty() {
         vec![PathBuf::from("/")]
     } else {
         subvolumes
     };
+    let snap_dir = snapshot_dir_for(&subvolumes_to_snapshot)?;
+    crate::filesystem_stats::check_writable(&snap_dir)?;
+
+    // The snapshot qgroup only exists on the filesystem of the primary directory
+    let qgroup = qgroup.filter(|_| snap_dir == snapshot_dir());
 
     // Load exclude patterns
     let exclude_config = waypoint_common::ExcludeConfig::load().unwrap_or_default();
     let enabled_patterns = exclude_config.enabled_patterns();
 
     // Ensure snapshot directory exists
-    let snap_dir = snapshot_dir();
-    fs::create_dir_all(snap_dir).context("Failed to create snapshot directory")?;
+    fs::create_dir_all(&snap_dir).context("Failed to create snapshot directory")?;
 
     // Create a directory for this snapshot group
     let snapshot_base_path = snap_dir.join(name);
     fs::create_dir_all(&snapshot_base_path).context("Failed to create snapshot base directory")?;
 
-    // Create snapshots for each subvolume
-    for subvol_mount in &subvolumes_to_snapshot {
-        let subvol_name = if subvol_mount == &PathBuf::from("/") {
-            "root".to_string()
-        } else {
-            // Convert /home to "home", /var to "var", etc.
-            subvol_mount
-                .to_string_lossy()
-                .trim_start_matches('/')
-                .replace('/', "_")
-        };
-
-        let snapshot_path = snapshot_base_path.join(&subvol_name);
+    let config = WaypointConfig::new();
+    let timeout = config.snapshot_timeout();
+    let result = (|| -> Result<()> {
+        // Create snapshots for each subvolume
+        for subvol_mount in &subvolumes_to_snapshot {
+            if cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+                return Err(HelperError::new(
+                    ErrorCode::Cancelled,
+                    "Snapshot creation was cancelled",
+                )
+                .into());
+            }
 
-        // Use the mount point directly as the source
-        let source_path = subvol_mount;
+            let subvol_name = if subvol_mount == &PathBuf::from("/") {
+                "root".to_string()
+            } else {
+                // Convert /home to "home", /var to "var", etc.
+                subvol_mount
+                    .to_string_lossy()
+                    .trim_start_matches('/')
+                    .replace('/', "_")
+            };
 
-        log::info!(
-            "Creating snapshot: {} -> {}",
-            source_path.display(),
-            snapshot_path.display()
-        );
+            let snapshot_path = snapshot_base_path.join(&subvol_name);
 
-        // Create the btrfs snapshot as WRITABLE (no -r flag) so we can apply exclusions
-        let output = Command::new("btrfs")
-            .arg("subvolume")
-            .arg("snapshot")
-            .arg(source_path)
-            .arg(&snapshot_path)
-            .output()
-            .context(format!(
-                "Failed to create snapshot of {}",
-                source_path.display()
-            ))?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            // Clean up partial snapshots
-            let _ = cleanup_failed_snapshot(&snapshot_base_path);
-            bail!(
-                "Failed to create snapshot of {}: {}\n{}",
-                source_path.display(),
-                stderr,
-                stdout
-            );
-        }
+            // Use the mount point directly as the source
+            let source_path = subvol_mount;
 
-        // Apply exclude patterns by deleting matching files
-        if !enabled_patterns.is_empty() {
             log::info!(
-                "Applying {} exclude patterns to {}",
-                enabled_patterns.len(),
+                "Creating snapshot: {} -> {}",
+                source_path.display(),
                 snapshot_path.display()
             );
-            if let Err(e) = apply_exclusions(&snapshot_path, &enabled_patterns) {
-                log::error!(
-                    "Failed to apply exclusions to {}: {}",
-                    snapshot_path.display(),
-                    e
+
+            // Create the btrfs snapshot as WRITABLE (no -r flag) so we can apply exclusions
+            let mut command = Command::new("btrfs");
+            command.arg("subvolume").arg("snapshot");
+            if let Some(qgroup) = qgroup {
+                command.arg("-i").arg(qgroup);
+            }
+            command.arg(source_path).arg(&snapshot_path);
+            let output = crate::output_with_timeout(&mut command, timeout, cancel).context(
+                format!("Failed to create snapshot of {}", source_path.display()),
+            )?;
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                bail!(
+                    "Failed to create snapshot of {}: {}\n{}",
+                    source_path.display(),
+                    stderr,
+                    stdout
                 );
-                // Don't fail the whole snapshot, just log the error
             }
-        }
 
-        // Now make the snapshot read-only
-        let output = Command::new("btrfs")
-            .arg("property")
-            .arg("set")
-            .arg("-ts")
-            .arg(&snapshot_path)
-            .arg("ro")
-            .arg("true")
-            .output()
-            .context("Failed to make snapshot read-only")?;
+            // Apply exclude patterns by deleting matching files
+            if !enabled_patterns.is_empty() {
+                log::info!(
+                    "Applying {} exclude patterns to {}",
+                    enabled_patterns.len(),
+                    snapshot_path.display()
+                );
+                if let Err(e) = apply_exclusions(&snapshot_path, &enabled_patterns) {
+                    log::error!(
+                        "Failed to apply exclusions to {}: {}",
+                        snapshot_path.display(),
+                        e
+                    );
+                    // Don't fail the whole snapshot, just log the error
+                }
+            }
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            log::warn!("Failed to make snapshot read-only: {stderr}");
-            // Continue anyway - writable snapshots still work
+            // Now make the snapshot read-only; btrfs send refuses writable snapshots
+            if config.read_only_snapshots {
+                set_read_only(&snapshot_path, true)
+                    .context("Failed to make snapshot read-only")?;
+            }
         }
+        Ok(())
+    })();
+
+    // Clean up partial snapshots, including ones left by a killed command
+    if let Err(e) = result {
+        let _ = cleanup_failed_snapshot(&snapshot_base_path);
+        return Err(e);
     }
 
     // Save metadata
@@ -181,12 +308,22 @@ pub fn create_snapshot(
         timestamp: Utc::now(),
         path: snapshot_base_path.clone(),
         description: description.map(String::from),
-        kernel_version: get_kernel_version(),
+        kernel_version,
         package_count: Some(packages.len()),
         packages,
         subvolumes: subvolumes_to_snapshot,
+        retention_group: None,
+        expires_at: None,
+        transaction: None,
+        category: SnapshotCategory::Manual,
+        is_favorite: false,
     };
 
+    // The sidecar is a fallback copy, so failing to write it isn't fatal
+    if let Err(e) = write_metadata_sidecar(&snapshot) {
+        log::warn!("Failed to write metadata sidecar for {name}: {e}");
+    }
+
     // RESOURCE CLEANUP: If metadata save fails, clean up the snapshots we just created
     // This prevents orphaned snapshots that exist on disk but aren't tracked
     if let Err(e) = add_snapshot_metadata(snapshot) {
@@ -328,7 +465,7 @@ fn cleanup_failed_snapshot(snapshot_path: &Path) -> Result<()> {
                         .arg("subvolume")
                         .arg("delete")
                         .arg(&path)
-                        .output()
+                        .recorded_output()
                     {
                         Ok(output) if output.status.success() => {
                             log::debug!("Cleaned up subvolume: {}", path.display());
@@ -349,6 +486,7 @@ fn cleanup_failed_snapshot(snapshot_path: &Path) -> Result<()> {
         }
 
         // Remove the directory
+        let _ = fs::remove_file(snapshot_path.join(METADATA_SIDECAR));
         match fs::remove_dir(snapshot_path) {
             Ok(_) => log::info!("Cleanup complete: {} subvolumes deleted, {} failures", cleaned_count, failed_count),
             Err(e) => log::warn!("Failed to remove snapshot directory {}: {}", snapshot_path.display(), e),
@@ -360,11 +498,12 @@ fn cleanup_failed_snapshot(snapshot_path: &Path) -> Result<()> {
 /// Delete a snapshot (and all its subvolumes)
 pub fn delete_snapshot(name: &str) -> Result<()> {
     ensure_snapshot_name(name)?;
-    let snapshot_path = snapshot_dir().join(name);
+    let snapshot_path = locate_snapshot(name);
     ensure_within_snapshot_dir(&snapshot_path)?;
+    check_writable_parent(&snapshot_path)?;
 
     if !snapshot_path.exists() {
-        bail!("Snapshot not found: {name}");
+        bail!(HelperError::new(ErrorCode::NotFound, format!("Snapshot not found: {name}")));
     }
 
     // Check if it's a directory (new multi-subvolume format) or a single subvolume (old format)
@@ -382,7 +521,7 @@ pub fn delete_snapshot(name: &str) -> Result<()> {
                     .arg("subvolume")
                     .arg("delete")
                     .arg(&subvol_path)
-                    .output()
+                    .recorded_output()
                     .context("Failed to execute btrfs subvolume delete")?;
 
                 if !output.status.success() {
@@ -393,6 +532,10 @@ pub fn delete_snapshot(name: &str) -> Result<()> {
         }
 
         // Remove the parent directory
+        let sidecar = snapshot_path.join(METADATA_SIDECAR);
+        if sidecar.exists() {
+            fs::remove_file(&sidecar).context("Failed to remove snapshot metadata sidecar")?;
+        }
         fs::remove_dir(&snapshot_path).context("Failed to remove snapshot directory")?;
     } else {
         // Old format: single subvolume snapshot
@@ -400,7 +543,7 @@ pub fn delete_snapshot(name: &str) -> Result<()> {
             .arg("subvolume")
             .arg("delete")
             .arg(&snapshot_path)
-            .output()
+            .recorded_output()
             .context("Failed to execute btrfs subvolume delete")?;
 
         if !output.status.success() {
@@ -415,13 +558,128 @@ pub fn delete_snapshot(name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Clone an existing snapshot under a new name
+///
+/// Each subvolume of the source is snapshotted again into a new snapshot
+/// directory. This is cheap on btrfs since the copies share extents with the
+/// source until modified. Metadata (packages, kernel, subvolumes) is copied
+/// from the source so the clone behaves like any other restore point.
+pub fn clone_snapshot(source: &str, new_name: &str, writable: bool) -> Result<()> {
+    ensure_snapshot_name(source)?;
+    ensure_snapshot_name(new_name)?;
+
+    let source_path = locate_snapshot(source);
+    ensure_within_snapshot_dir(&source_path)?;
+    check_writable_parent(&source_path)?;
+
+    if !source_path.is_dir() {
+        bail!(HelperError::new(ErrorCode::NotFound, format!("Snapshot not found: {source}")));
+    }
+
+    // Clones share extents with their source, so they stay on its filesystem
+    let target_path = source_path.with_file_name(new_name);
+    ensure_within_snapshot_dir(&target_path)?;
+
+    // Metadata and backups are keyed by name, so it must be unique across directories
+    if snapshot_dirs().iter().any(|dir| dir.join(new_name).exists()) {
+        bail!(HelperError::new(
+            ErrorCode::AlreadyExists,
+            format!("A snapshot named '{new_name}' already exists"),
+        ));
+    }
+
+    let source_meta = get_snapshot_metadata(source)?;
+
+    fs::create_dir_all(&target_path).context("Failed to create snapshot base directory")?;
+
+    let qgroup = snapshot_qgroup();
+    let entries = fs::read_dir(&source_path).context("Failed to read snapshot directory")?;
+    for entry in entries {
+        let entry = entry.context("Failed to read directory entry")?;
+        let subvol_path = entry.path();
+
+        if !subvol_path.is_dir() {
+            continue;
+        }
+
+        let clone_path = target_path.join(entry.file_name());
+
+        log::info!(
+            "Cloning snapshot subvolume: {} -> {}",
+            subvol_path.display(),
+            clone_path.display()
+        );
+
+        let mut cmd = Command::new("btrfs");
+        cmd.arg("subvolume").arg("snapshot");
+        if !writable {
+            cmd.arg("-r");
+        }
+        if let Some(qgroup) = qgroup {
+            cmd.arg("-i").arg(qgroup);
+        }
+
+        let output = cmd
+            .arg(&subvol_path)
+            .arg(&clone_path)
+            .recorded_output()
+            .context("Failed to execute btrfs subvolume snapshot")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let _ = cleanup_failed_snapshot(&target_path);
+            bail!(
+                "Failed to clone {}: {}",
+                subvol_path.display(),
+                stderr.trim()
+            );
+        }
+    }
+
+    let description = match &source_meta.description {
+        Some(desc) if !desc.is_empty() => format!("Copy of {source}: {desc}"),
+        _ => format!("Copy of {source}"),
+    };
+
+    let snapshot = Snapshot {
+        id: format!("snapshot-{}", Utc::now().format("%Y%m%d-%H%M%S")),
+        name: new_name.to_string(),
+        timestamp: Utc::now(),
+        path: target_path.clone(),
+        description: Some(description),
+        kernel_version: source_meta.kernel_version,
+        package_count: source_meta.package_count,
+        packages: source_meta.packages,
+        subvolumes: source_meta.subvolumes,
+        retention_group: None,
+        expires_at: None,
+        transaction: None,
+        category: SnapshotCategory::Manual,
+        is_favorite: false,
+    };
+
+    if let Err(e) = write_metadata_sidecar(&snapshot) {
+        log::warn!("Failed to write metadata sidecar for {new_name}: {e}");
+    }
+
+    if let Err(e) = add_snapshot_metadata(snapshot) {
+        log::error!("Failed to save cloned snapshot metadata, cleaning up: {}", e);
+        let _ = cleanup_failed_snapshot(&target_path);
+        return Err(e);
+    }
+
+    Ok(())
+}
+
 /// Restore a snapshot (set as default boot subvolume)
 pub fn restore_snapshot(name: &str) -> Result<()> {
-    let snapshot_base_path = snapshot_dir().join(name);
+    let snapshot_base_path = locate_snapshot(name);
     ensure_within_snapshot_dir(&snapshot_base_path)?;
+    check_writable_parent(&snapshot_base_path)?;
+    crate::filesystem_stats::check_writable(Path::new("/"))?;
 
     if !snapshot_base_path.exists() {
-        bail!("Snapshot not found: {name}");
+        bail!(HelperError::new(ErrorCode::NotFound, format!("Snapshot not found: {name}")));
     }
 
     // Load snapshot metadata to check which subvolumes were included
@@ -454,7 +712,7 @@ pub fn restore_snapshot(name: &str) -> Result<()> {
                 .arg("subvolume")
                 .arg("delete")
                 .arg(&writable_root)
-                .output();
+                .recorded_output();
         }
 
         create_writable_snapshot(&root_snapshot_path, &writable_root)
@@ -500,7 +758,7 @@ pub fn restore_snapshot(name: &str) -> Result<()> {
         .arg("set-default")
         .arg(subvol_id.to_string())
         .arg("/")
-        .output()
+        .recorded_output()
         .context("Failed to execute btrfs subvolume set-default")?;
 
     if !output.status.success() {
@@ -511,6 +769,86 @@ pub fn restore_snapshot(name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Restore one subvolume of a snapshot to a new writable subvolume
+///
+/// Unlike a rollback this leaves the running system and the default subvolume
+/// untouched: the copy can be mounted and inspected, then deleted with
+/// `btrfs subvolume delete` once it's no longer needed. Returns the path of the
+/// new subvolume.
+pub fn restore_to_new_subvolume(name: &str, subvolume: &Path, target: &Path) -> Result<PathBuf> {
+    ensure_snapshot_name(name)?;
+    crate::filesystem_stats::check_writable(target)?;
+
+    let snapshot_meta = get_snapshot_metadata(name)?;
+    if !snapshot_meta.subvolumes.is_empty() && !snapshot_meta.subvolumes.iter().any(|s| s == subvolume) {
+        bail!(
+            "Snapshot '{name}' does not include subvolume {}",
+            subvolume.display()
+        );
+    }
+
+    let subvol_name = if subvolume == Path::new("/") {
+        "root".to_string()
+    } else {
+        subvolume
+            .to_string_lossy()
+            .trim_start_matches('/')
+            .replace('/', "_")
+    };
+    let source = locate_snapshot(name).join(&subvol_name);
+    ensure_within_snapshot_dir(&source)?;
+    if !source.is_dir() {
+        bail!("Snapshot subvolume not found: {}", source.display());
+    }
+
+    validate_restore_target(target)?;
+
+    log::info!(
+        "Restoring {} of snapshot '{}' to new subvolume {}",
+        subvolume.display(),
+        name,
+        target.display()
+    );
+    create_writable_snapshot(&source, target)?;
+
+    Ok(target.to_path_buf())
+}
+
+/// Check that a user-chosen path can receive a new subvolume
+fn validate_restore_target(target: &Path) -> Result<()> {
+    use std::path::Component;
+
+    if !target.is_absolute() {
+        bail!("Target path must be absolute: {}", target.display());
+    }
+
+    if target
+        .components()
+        .any(|c| matches!(c, Component::ParentDir | Component::CurDir))
+    {
+        bail!("Target path must not contain '.' or '..': {}", target.display());
+    }
+
+    if snapshot_dirs().iter().any(|dir| target.starts_with(dir)) {
+        bail!("Target path must be outside the snapshot directory");
+    }
+
+    if fs::symlink_metadata(target).is_ok() {
+        bail!("Target path already exists: {}", target.display());
+    }
+
+    let parent = target
+        .parent()
+        .ok_or_else(|| anyhow!("Target path has no parent directory"))?;
+    let parent_meta = fs::symlink_metadata(parent)
+        .with_context(|| format!("Parent directory does not exist: {}", parent.display()))?;
+    if !parent_meta.is_dir() {
+        bail!("Parent is not a directory: {}", parent.display());
+    }
+
+    Ok(())
+}
+
 /// Clean up orphaned writable snapshot copies
 ///
 /// Scans for root-writable subvolumes created during multi-subvolume restores
@@ -582,7 +920,7 @@ pub fn cleanup_writable_snapshots() -> Result<Vec<String>> {
             .arg("subvolume")
             .arg("delete")
             .arg(&writable_path)
-            .output()
+            .recorded_output()
             .context("Failed to execute btrfs subvolume delete")?;
 
         if output.status.success() {
@@ -606,6 +944,25 @@ pub fn list_snapshots() -> Result<Vec<Snapshot>> {
     load_snapshot_metadata()
 }
 
+/// Package lists of several snapshots; unknown names are left out
+pub fn get_snapshot_packages(
+    names: &[String],
+) -> Result<std::collections::HashMap<String, Vec<Package>>> {
+    Ok(packages_of(load_snapshot_metadata()?, names))
+}
+
+/// Package lists of the snapshots named in `names`, keyed by name
+fn packages_of(
+    snapshots: Vec<Snapshot>,
+    names: &[String],
+) -> std::collections::HashMap<String, Vec<Package>> {
+    snapshots
+        .into_iter()
+        .filter(|s| names.contains(&s.name))
+        .map(|s| (s.name, s.packages))
+        .collect()
+}
+
 /// Get sizes for multiple snapshots efficiently
 /// Returns a HashMap mapping snapshot names to sizes in bytes
 ///
@@ -635,12 +992,275 @@ pub fn get_snapshot_sizes(snapshot_names: Vec<String>) -> Result<std::collection
     Ok(results)
 }
 
+/// Exclusive qgroup sizes of several snapshots, i.e. what deleting each would free
+///
+/// Unlike `get_snapshot_sizes` there is no `du` fallback, whose sizes include
+/// data shared with other snapshots. Snapshots without a known exclusive size
+/// (quotas disabled, missing qgroups, unknown names) are left out.
+pub fn get_snapshot_exclusive_sizes(
+    snapshot_names: Vec<String>,
+) -> Result<std::collections::HashMap<String, u64>> {
+    let snapshots = load_snapshot_metadata()?;
+    Ok(snapshot_names
+        .into_iter()
+        .filter_map(|name| {
+            let snapshot = snapshots.iter().find(|s| s.name == name)?;
+            match get_snapshot_exclusive_size(&snapshot.path) {
+                Ok(size) => Some((name, size)),
+                Err(e) => {
+                    log::debug!("Exclusive size of {name} unavailable: {e}");
+                    None
+                }
+            }
+        })
+        .collect())
+}
+
 /// Internal implementation to get a single snapshot's size
+///
+/// Prefers the exclusive qgroup size when quotas are enabled, which is
+/// near-instant compared to walking every file. Falls back to `du` otherwise.
 fn get_snapshot_size_impl(path: &Path) -> Result<u64> {
-    let output = Command::new("du")
+    match get_snapshot_exclusive_size(path) {
+        Ok(size) => return Ok(size),
+        Err(e) => log::debug!("Qgroup size unavailable for {}, using du: {e}", path.display()),
+    }
+
+    get_du_size(path)
+}
+
+/// Sum the exclusive qgroup sizes of every subvolume inside a snapshot directory
+///
+/// Fails if quotas are disabled or any subvolume has no level-0 qgroup, so callers
+/// can fall back to a slower recursive walk.
+pub fn get_snapshot_exclusive_size(path: &Path) -> Result<u64> {
+    let exclusive_sizes = get_qgroup_exclusive_sizes(path)?;
+
+    let mut total = 0u64;
+    let mut found_any = false;
+
+    for entry in fs::read_dir(path)
+        .with_context(|| format!("Failed to read snapshot directory {}", path.display()))?
+    {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+
+        let subvol_id = get_subvolume_id(&entry.path())?;
+        let size = exclusive_sizes.get(&subvol_id).ok_or_else(|| {
+            anyhow!("No qgroup found for subvolume {}", entry.path().display())
+        })?;
+        total = total.saturating_add(*size);
+        found_any = true;
+    }
+
+    if !found_any {
+        bail!("No subvolumes found in {}", path.display());
+    }
+
+    Ok(total)
+}
+
+/// Read exclusive sizes of all level-0 qgroups on the filesystem containing `path`
+///
+/// Returns a map of subvolume ID to exclusive bytes.
+pub fn get_qgroup_exclusive_sizes(path: &Path) -> Result<std::collections::HashMap<u64, u64>> {
+    let output = Command::new("btrfs")
+        .arg("qgroup")
+        .arg("show")
+        .arg("--raw")
+        .arg(path)
+        .recorded_output()
+        .context("Failed to execute btrfs qgroup show")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("Quotas not available: {}", stderr.trim());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_level0_qgroups(&stdout)
+        .into_iter()
+        .map(|(id, (_, excl))| (id, excl))
+        .collect())
+}
+
+/// Referenced and exclusive bytes of every level-0 qgroup, keyed by subvolume ID
+fn parse_level0_qgroups(show_output: &str) -> std::collections::HashMap<u64, (u64, u64)> {
+    let mut sizes = std::collections::HashMap::new();
+
+    // Format: qgroupid rfer excl ...
+    for line in show_output.lines() {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 3 {
+            continue;
+        }
+
+        let Some(id_str) = parts[0].strip_prefix("0/") else {
+            continue;
+        };
+
+        if let (Ok(id), Ok(rfer), Ok(excl)) = (
+            id_str.parse::<u64>(),
+            parts[1].parse::<u64>(),
+            parts[2].parse::<u64>(),
+        ) {
+            sizes.insert(id, (rfer, excl));
+        }
+    }
+
+    sizes
+}
+
+/// Compare what snapshots reference with what they take up on their own
+///
+/// Requires quotas. The exclusive figure comes from `SNAPSHOT_QGROUP`, which
+/// counts extents shared between snapshots once; without that group it falls
+/// back to the level-0 sum, which leaves those extents out. Snapshot
+/// directories on the same filesystem share that group, so it is only counted
+/// once per filesystem.
+pub fn get_snapshot_sharing() -> Result<SnapshotSharing> {
+    let mut sharing = SnapshotSharing::default();
+    let mut grouped_filesystems = std::collections::HashSet::new();
+
+    for snap_dir in snapshot_dirs().iter().filter(|dir| dir.exists()) {
+        let output = Command::new("btrfs")
+            .args(["qgroup", "show", "--raw"])
+            .arg(snap_dir)
+            .recorded_output()
+            .context("Failed to execute btrfs qgroup show")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("Quotas not available: {}", stderr.trim());
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let level0 = parse_level0_qgroups(&stdout);
+        let mut level0_exclusive = 0u64;
+        for snapshot in fs::read_dir(snap_dir)?.flatten() {
+            let Ok(subvolumes) = fs::read_dir(snapshot.path()) else {
+                continue;
+            };
+            for subvol in subvolumes.flatten().filter(|e| e.path().is_dir()) {
+                let Some((rfer, excl)) = get_subvolume_id(&subvol.path())
+                    .ok()
+                    .and_then(|id| level0.get(&id))
+                else {
+                    continue;
+                };
+                sharing.referenced_sum = sharing.referenced_sum.saturating_add(*rfer);
+                level0_exclusive = level0_exclusive.saturating_add(*excl);
+                sharing.subvolumes += 1;
+            }
+        }
+
+        let exclusive = match parse_qgroup_usage(&stdout, SNAPSHOT_QGROUP) {
+            Some((_, excl)) => {
+                let fsid = get_filesystem_uuid(snap_dir)
+                    .unwrap_or_else(|_| snap_dir.to_string_lossy().into_owned());
+                if grouped_filesystems.insert(fsid) {
+                    excl
+                } else {
+                    0
+                }
+            }
+            None => level0_exclusive,
+        };
+        sharing.exclusive = sharing.exclusive.saturating_add(exclusive);
+    }
+
+    Ok(sharing)
+}
+
+/// Level-1 qgroup that snapshot subvolumes are inherited into
+///
+/// Summing level-0 qgroups counts extents shared between snapshots once per
+/// snapshot and includes subvolumes that aren't snapshots at all. The parent
+/// qgroup counts shared extents once and only covers snapshots.
+pub const SNAPSHOT_QGROUP: &str = "1/100";
+
+/// Referenced and exclusive bytes of one qgroup in `btrfs qgroup show --raw` output
+pub fn parse_qgroup_usage(show_output: &str, qgroup: &str) -> Option<(u64, u64)> {
+    show_output.lines().find_map(|line| {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 3 || parts[0] != qgroup {
+            return None;
+        }
+        Some((parts[1].parse().ok()?, parts[2].parse().ok()?))
+    })
+}
+
+/// Qgroup new snapshots should inherit into, or `None` when quotas are disabled
+///
+/// Creates `SNAPSHOT_QGROUP` the first time and assigns the existing snapshots
+/// to it, so the group covers snapshots taken before it existed too.
+pub fn snapshot_qgroup() -> Option<&'static str> {
+    let snap_dir = snapshot_dir();
+    let output = Command::new("btrfs")
+        .args(["qgroup", "show", "--raw"])
+        .arg(snap_dir)
+        .recorded_output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if parse_qgroup_usage(&stdout, SNAPSHOT_QGROUP).is_none()
+        && let Err(e) = create_snapshot_qgroup(snap_dir)
+    {
+        log::warn!("Snapshots won't be grouped for quota accounting: {e}");
+        return None;
+    }
+    Some(SNAPSHOT_QGROUP)
+}
+
+fn create_snapshot_qgroup(snap_dir: &Path) -> Result<()> {
+    let output = Command::new("btrfs")
+        .args(["qgroup", "create", SNAPSHOT_QGROUP])
+        .arg(snap_dir)
+        .recorded_output()
+        .context("Failed to execute btrfs qgroup create")?;
+    if !output.status.success() {
+        bail!(
+            "Failed to create qgroup {SNAPSHOT_QGROUP}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    log::info!("Created qgroup {SNAPSHOT_QGROUP} for snapshots");
+
+    // Assigning is best-effort; an ungrouped snapshot only skews the total
+    for snapshot in fs::read_dir(snap_dir)?.flatten() {
+        let Ok(subvolumes) = fs::read_dir(snapshot.path()) else {
+            continue;
+        };
+        for subvol in subvolumes.flatten().filter(|e| e.path().is_dir()) {
+            let Ok(id) = get_subvolume_id(&subvol.path()) else {
+                continue;
+            };
+            let assigned = Command::new("btrfs")
+                .args(["qgroup", "assign", &format!("0/{id}"), SNAPSHOT_QGROUP])
+                .arg(snap_dir)
+                .recorded_output()
+                .is_ok_and(|output| output.status.success());
+            if !assigned {
+                log::warn!(
+                    "Failed to assign {} to qgroup {SNAPSHOT_QGROUP}",
+                    subvol.path().display()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Get the apparent size of a directory tree using `du`
+fn get_du_size(path: &Path) -> Result<u64> {
+    let output = crate::low_priority::command("du")
         .arg("-sb")
         .arg(path)
-        .output()
+        .recorded_output()
         .context("Failed to execute du command")?;
 
     // Check command success first
@@ -704,7 +1324,7 @@ pub fn verify_snapshot(name: &str) -> Result<VerificationResult> {
     let mut warnings = Vec::new();
 
     // Check snapshot base directory exists first
-    let snapshot_base_path = snapshot_dir().join(name);
+    let snapshot_base_path = locate_snapshot(name);
     if !snapshot_base_path.exists() {
         errors.push(format!(
             "Snapshot directory does not exist: {}",
@@ -762,7 +1382,7 @@ pub fn verify_snapshot(name: &str) -> Result<VerificationResult> {
                     .arg("subvolume")
                     .arg("show")
                     .arg(&path)
-                    .output()
+                    .recorded_output()
                 {
                     Ok(output) if output.status.success() => {
                         // Subvolume is valid
@@ -792,7 +1412,7 @@ pub fn verify_snapshot(name: &str) -> Result<VerificationResult> {
                         .arg("subvolume")
                         .arg("show")
                         .arg(&path)
-                        .output()
+                        .recorded_output()
                     {
                         if output.status.success() {
                             found_valid_subvol = true;
@@ -816,7 +1436,7 @@ pub fn verify_snapshot(name: &str) -> Result<VerificationResult> {
             .arg("subvolume")
             .arg("show")
             .arg(&snapshot_base_path)
-            .output()
+            .recorded_output()
         {
             Ok(output) if output.status.success() => {
                 // Subvolume is valid
@@ -886,7 +1506,7 @@ pub fn preview_restore(name: &str) -> Result<RestorePreview> {
 
     // Get current packages
     let current_packages =
-        get_installed_packages().context("Failed to get current installed packages")?;
+        get_installed_packages(None).context("Failed to get current installed packages")?;
 
     // Build maps for easy lookup
     let current_pkg_map: HashMap<String, String> = current_packages
@@ -967,8 +1587,8 @@ pub fn preview_restore(name: &str) -> Result<RestorePreview> {
         }
     }
 
-    // Get current kernel version
-    let current_kernel = get_current_kernel_version().ok();
+    // Get current kernel version (same source as snapshot creation so they compare equal)
+    let current_kernel = get_kernel_version();
 
     // Format affected subvolumes
     let affected_subvolumes: Vec<String> = snapshot_meta
@@ -1000,47 +1620,302 @@ pub fn preview_restore(name: &str) -> Result<RestorePreview> {
     })
 }
 
-/// Get current kernel version
-fn get_current_kernel_version() -> Result<String> {
-    let output = Command::new("uname")
-        .arg("-r")
-        .output()
-        .context("Failed to execute uname")?;
+/// Get subvolume ID for a path
+fn get_subvolume_id(path: &Path) -> Result<u64> {
+    let output = Command::new("btrfs")
+        .arg("subvolume")
+        .arg("show")
+        .arg(path)
+        .recorded_output()
+        .context("Failed to execute btrfs subvolume show")?;
 
     if !output.status.success() {
-        bail!("Failed to get kernel version");
+        bail!("Failed to get subvolume info for {path:?}");
     }
 
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    for line in stdout.lines() {
+        let line = line.trim();
+        if line.starts_with("Subvolume ID:") {
+            if let Some(id_str) = line.split_whitespace().nth(2) {
+                if let Ok(id) = id_str.parse() {
+                    return Ok(id);
+                }
+            }
+        }
+    }
+
+    bail!("Could not parse subvolume ID from output");
 }
 
-/// Get subvolume ID for a path
-fn get_subvolume_id(path: &Path) -> Result<u64> {
+/// Whether `path` is the top of a btrfs subvolume
+pub fn is_subvolume(path: &Path) -> bool {
+    path.is_dir()
+        && Command::new("btrfs")
+            .arg("subvolume")
+            .arg("show")
+            .arg(path)
+            .recorded_output()
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+}
+
+/// Inode number of the top directory of every btrfs subvolume
+const SUBVOLUME_ROOT_INODE: u64 = 256;
+
+/// Inode number btrfs gives the empty directory left in a snapshot where a
+/// nested subvolume was
+const SUBVOLUME_PLACEHOLDER_INODE: u64 = 2;
+
+/// A btrfs subvolume boundary found while walking a snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubvolumeBoundary {
+    /// Empty directory standing in for a nested subvolume that wasn't snapshotted
+    Placeholder,
+    /// A separate subvolume with its own contents
+    Subvolume,
+}
+
+/// Classify a directory by its inode and device against its parent directory's device
+///
+/// Each subvolume is its own device, and its top directory is always inode 256.
+pub fn subvolume_boundary(ino: u64, dev: u64, parent_dev: u64) -> Option<SubvolumeBoundary> {
+    if ino == SUBVOLUME_PLACEHOLDER_INODE {
+        Some(SubvolumeBoundary::Placeholder)
+    } else if dev != parent_dev || ino == SUBVOLUME_ROOT_INODE {
+        Some(SubvolumeBoundary::Subvolume)
+    } else {
+        None
+    }
+}
+
+/// Create an empty subvolume at `path`
+pub fn create_subvolume(path: &Path) -> Result<()> {
     let output = Command::new("btrfs")
-        .arg("subvolume")
-        .arg("show")
+        .args(["subvolume", "create"])
+        .arg(path)
+        .recorded_output()
+        .context("Failed to execute btrfs subvolume create")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "Failed to create subvolume {}: {}",
+            path.display(),
+            stderr.trim()
+        );
+    }
+    Ok(())
+}
+
+/// Parse the output of `btrfs property get <path> ro`
+fn parse_ro_property(output: &str) -> Option<bool> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("ro="))
+        .and_then(|value| value.parse().ok())
+}
+
+/// Whether a subvolume is read-only
+pub fn is_read_only(path: &Path) -> Result<bool> {
+    let output = Command::new("btrfs")
+        .args(["property", "get", "-ts"])
+        .arg(path)
+        .arg("ro")
+        .recorded_output()
+        .context("Failed to execute btrfs property get")?;
+
+    if !output.status.success() {
+        bail!(
+            "Failed to read the read-only property of {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    parse_ro_property(&String::from_utf8_lossy(&output.stdout))
+        .context(format!("Unexpected btrfs property output for {}", path.display()))
+}
+
+/// Set or clear the read-only property of a subvolume
+pub fn set_read_only(path: &Path, read_only: bool) -> Result<()> {
+    let value = if read_only { "true" } else { "false" };
+    let output = Command::new("btrfs")
+        .args(["property", "set", "-ts"])
+        .arg(path)
+        .args(["ro", value])
+        .recorded_output()
+        .context("Failed to execute btrfs property set")?;
+
+    if !output.status.success() {
+        bail!(
+            "Failed to set ro={value} on {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Subvolumes making up a snapshot
+///
+/// Older snapshots are a single subvolume, newer ones a directory holding
+/// one subvolume per snapshotted mount point.
+fn snapshot_subvolumes(name: &str) -> Result<Vec<PathBuf>> {
+    ensure_snapshot_name(name)?;
+    let base = locate_snapshot(name);
+    if !base.is_dir() {
+        return Err(HelperError::new(
+            ErrorCode::NotFound,
+            format!("Snapshot not found: {name}"),
+        )
+        .into());
+    }
+    if is_subvolume(&base) {
+        return Ok(vec![base]);
+    }
+
+    let mut subvolumes: Vec<PathBuf> = fs::read_dir(&base)
+        .context("Failed to read snapshot directory")?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| is_subvolume(path))
+        .collect();
+    subvolumes.sort();
+    Ok(subvolumes)
+}
+
+/// Whether every subvolume of a snapshot is read-only
+pub fn is_snapshot_read_only(name: &str) -> Result<bool> {
+    for subvolume in snapshot_subvolumes(name)? {
+        if !is_read_only(&subvolume)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Read-only state of several snapshots; snapshots that can't be checked are left out
+pub fn get_read_only_states(names: &[String]) -> std::collections::HashMap<String, bool> {
+    names
+        .iter()
+        .filter_map(|name| match is_snapshot_read_only(name) {
+            Ok(read_only) => Some((name.clone(), read_only)),
+            Err(e) => {
+                log::warn!("Could not check whether {name} is read-only: {e}");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Parse the generation from the output of `btrfs subvolume show`
+fn parse_subvolume_generation(output: &str) -> Option<u64> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Generation:"))
+        .and_then(|value| value.trim().parse().ok())
+}
+
+/// Generation of the last transaction that changed a subvolume
+fn get_subvolume_generation(path: &Path) -> Result<u64> {
+    let output = Command::new("btrfs")
+        .args(["subvolume", "show"])
         .arg(path)
-        .output()
+        .recorded_output()
         .context("Failed to execute btrfs subvolume show")?;
 
     if !output.status.success() {
-        bail!("Failed to get subvolume info for {path:?}");
+        bail!(
+            "Failed to get subvolume info for {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    parse_subvolume_generation(&String::from_utf8_lossy(&output.stdout)).context(format!(
+        "Could not parse the generation of {}",
+        path.display()
+    ))
+}
 
-    for line in stdout.lines() {
-        let line = line.trim();
-        if line.starts_with("Subvolume ID:") {
-            if let Some(id_str) = line.split_whitespace().nth(2) {
-                if let Ok(id) = id_str.parse() {
-                    return Ok(id);
-                }
+/// Generation of a snapshot, the newest of its subvolumes
+///
+/// It stays the same as long as the snapshot is read-only.
+pub fn get_snapshot_generation(name: &str) -> Result<u64> {
+    let mut generation = 0;
+    for subvolume in snapshot_subvolumes(name)? {
+        generation = generation.max(get_subvolume_generation(&subvolume)?);
+    }
+    Ok(generation)
+}
+
+/// Generations of several snapshots; snapshots that can't be checked are left out
+pub fn get_snapshot_generations(names: &[String]) -> std::collections::HashMap<String, u64> {
+    names
+        .iter()
+        .filter_map(|name| match get_snapshot_generation(name) {
+            Ok(generation) => Some((name.clone(), generation)),
+            Err(e) => {
+                log::warn!("Could not get the generation of {name}: {e}");
+                None
             }
-        }
+        })
+        .collect()
+}
+
+/// Make all subvolumes of a snapshot read-only or writable
+pub fn set_snapshot_read_only(name: &str, read_only: bool) -> Result<()> {
+    for subvolume in snapshot_subvolumes(name)? {
+        set_read_only(&subvolume, read_only)?;
     }
+    log::info!(
+        "Made snapshot {name} {}",
+        if read_only { "read-only" } else { "writable" }
+    );
+    Ok(())
+}
 
-    bail!("Could not parse subvolume ID from output");
+/// Check that every enabled schedule only includes existing subvolumes
+///
+/// Returns one problem per missing subvolume, naming the schedule by prefix.
+fn find_missing_schedule_subvolumes<F>(
+    config: &waypoint_common::SchedulesConfig,
+    is_subvolume: F,
+) -> Vec<String>
+where
+    F: Fn(&Path) -> bool,
+{
+    config
+        .schedules
+        .iter()
+        .filter(|schedule| schedule.enabled)
+        .flat_map(|schedule| {
+            schedule
+                .subvolumes
+                .iter()
+                .filter(|path| !is_subvolume(path))
+                .map(|path| format!("'{}' includes {}", schedule.prefix, path.display()))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Reject schedule configurations that would snapshot subvolumes that no longer exist
+pub fn validate_schedule_subvolumes(config: &waypoint_common::SchedulesConfig) -> Result<()> {
+    let missing = find_missing_schedule_subvolumes(config, is_subvolume);
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    Err(HelperError::new(
+        ErrorCode::InvalidInput,
+        format!(
+            "Schedules include paths that are not Btrfs subvolumes: {}. Edit the schedules to remove them.",
+            missing.join("; ")
+        ),
+    )
+    .into())
 }
 
 /// Get the default boot subvolume ID
@@ -1049,7 +1924,7 @@ fn get_default_subvolume_id() -> Result<u64> {
         .arg("subvolume")
         .arg("get-default")
         .arg("/")
-        .output()
+        .recorded_output()
         .context("Failed to execute btrfs subvolume get-default")?;
 
     if !output.status.success() {
@@ -1078,20 +1953,42 @@ fn get_current_subvolume_id() -> Result<u64> {
     get_subvolume_id(Path::new("/"))
 }
 
-/// Get current kernel version
-fn get_kernel_version() -> Option<String> {
-    fs::read_to_string("/proc/version")
-        .ok()
-        .and_then(|v| v.split_whitespace().nth(2).map(String::from))
+/// Get the running kernel release (equivalent to `uname -r`)
+///
+/// Reads procfs through `waypoint_common::kernel_version`, like the GUI, and
+/// falls back to `uname -r` if procfs is unavailable. Used both when recording
+/// snapshot metadata and when previewing a restore, so the two values are
+/// directly comparable.
+pub fn get_kernel_version() -> Option<String> {
+    if let Some(release) = waypoint_common::kernel_version() {
+        return Some(release);
+    }
+
+    let output = Command::new("uname").arg("-r").recorded_output().ok()?;
+    if !output.status.success() {
+        log::warn!("Failed to determine kernel version");
+        return None;
+    }
+
+    waypoint_common::parse_kernel_release(&String::from_utf8_lossy(&output.stdout))
 }
 
 pub fn ensure_snapshot_name(name: &str) -> Result<()> {
-    waypoint_common::validate_snapshot_name(name)
-        .map_err(|e| anyhow!("Invalid snapshot name '{name}': {e}"))
+    waypoint_common::validate_snapshot_name(name).map_err(|e| {
+        HelperError::new(
+            ErrorCode::InvalidInput,
+            format!("Invalid snapshot name '{name}': {e}"),
+        )
+        .into()
+    })
 }
 
 fn ensure_within_snapshot_dir(path: &Path) -> Result<()> {
-    let base = snapshot_dir();
+    let dirs = snapshot_dirs();
+    let base = dirs
+        .iter()
+        .find(|dir| path.starts_with(dir))
+        .unwrap_or(&dirs[0]);
 
     // Try to canonicalize the path
     match path.canonicalize() {
@@ -1152,18 +2049,9 @@ fn ensure_within_snapshot_dir(path: &Path) -> Result<()> {
 
 /// Load snapshot metadata from file
 fn load_snapshot_metadata() -> Result<Vec<Snapshot>> {
-    let path = metadata_file();
-
-    if !path.exists() {
-        return Ok(Vec::new());
-    }
-
-    let content = fs::read_to_string(path).context("Failed to read snapshots metadata")?;
-
-    let parsed: Vec<Snapshot> =
-        serde_json::from_str(&content).context("Failed to parse snapshots metadata")?;
+    let parsed = load_stored_snapshot_metadata()?;
 
-    let base_dir = snapshot_dir();
+    let base_dirs = snapshot_dirs();
     let mut sanitized = Vec::with_capacity(parsed.len());
 
     for mut snapshot in parsed {
@@ -1176,8 +2064,8 @@ fn load_snapshot_metadata() -> Result<Vec<Snapshot>> {
             continue;
         }
 
-        let resolved_path = base_dir.join(&snapshot.name);
-        if !resolved_path.starts_with(base_dir) {
+        let resolved_path = locate_snapshot(&snapshot.name);
+        if !base_dirs.iter().any(|dir| resolved_path.starts_with(dir)) {
             log::warn!(
                 "Ignoring snapshot metadata entry '{}' with unexpected path {}",
                 snapshot.name,
@@ -1193,6 +2081,66 @@ fn load_snapshot_metadata() -> Result<Vec<Snapshot>> {
     Ok(sanitized)
 }
 
+/// Metadata entries as stored, without checking their names and paths
+fn load_stored_snapshot_metadata() -> Result<Vec<Snapshot>> {
+    let path = metadata_file();
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path).context("Failed to read snapshots metadata")?;
+
+    MetadataFormat::from_path(path)
+        .parse(&content)
+        .context("Failed to parse snapshots metadata")
+}
+
+/// Find metadata entries that contradict each other or their snapshot directory
+///
+/// Entries whose directory is gone are left out; cleanup removes those.
+pub fn check_metadata_conflicts() -> Result<Vec<MetadataConflict>> {
+    let snapshots: Vec<Snapshot> = load_stored_snapshot_metadata()?
+        .into_iter()
+        .filter(|snapshot| fs::symlink_metadata(&snapshot.path).is_ok())
+        .collect();
+    Ok(metadata_check::find_conflicts(&snapshots))
+}
+
+/// Resolve a conflict returned by `check_metadata_conflicts`
+///
+/// Fails with `not-found` if the metadata changed since and the conflict no
+/// longer exists as described.
+pub fn resolve_metadata_conflict(
+    conflict: &MetadataConflict,
+    resolution: ConflictResolution,
+) -> Result<()> {
+    let name = &conflict.name;
+    ensure_snapshot_name(name)?;
+    if !check_metadata_conflicts()?.contains(conflict) {
+        bail!(HelperError::new(
+            ErrorCode::NotFound,
+            format!("The metadata of {name} changed since it was checked"),
+        ));
+    }
+
+    let mut snapshots = load_stored_snapshot_metadata()?;
+    let kept = metadata_check::apply_resolution(&mut snapshots, conflict, resolution);
+    save_snapshot_metadata(&snapshots)?;
+
+    if let Some(mut kept) = kept {
+        kept.path = locate_snapshot(&kept.name);
+        if let Err(e) = write_metadata_sidecar(&kept) {
+            log::warn!("Failed to update metadata sidecar for {}: {e}", kept.name);
+        }
+    }
+    log::info!(
+        "Resolved metadata conflict for {name} ({})",
+        resolution.as_str()
+    );
+    Ok(())
+}
+
 /// Save snapshot metadata to file
 fn save_snapshot_metadata(snapshots: &[Snapshot]) -> Result<()> {
     let path = metadata_file();
@@ -1202,8 +2150,9 @@ fn save_snapshot_metadata(snapshots: &[Snapshot]) -> Result<()> {
         fs::create_dir_all(parent).context("Failed to create metadata directory")?;
     }
 
-    let content =
-        serde_json::to_string_pretty(snapshots).context("Failed to serialize snapshots")?;
+    let content = MetadataFormat::from_path(path)
+        .serialize(snapshots)
+        .context("Failed to serialize snapshots")?;
 
     fs::write(path, content).context("Failed to write snapshots metadata")?;
 
@@ -1224,34 +2173,305 @@ fn remove_snapshot_metadata(name: &str) -> Result<()> {
     save_snapshot_metadata(&snapshots)
 }
 
+/// Assign a snapshot to a schedule's retention, or clear the assignment
+///
+/// Without a group, retention goes by the snapshot's name prefix.
+pub fn set_retention_group(name: &str, group: Option<&str>) -> Result<()> {
+    ensure_snapshot_name(name)?;
+    let mut snapshots = load_snapshot_metadata()?;
+    let snapshot = snapshots
+        .iter_mut()
+        .find(|s| s.name == name)
+        .ok_or_else(|| anyhow::anyhow!("Snapshot metadata not found: {name}"))?;
+    snapshot.retention_group = group.map(String::from);
+    let updated = snapshot.clone();
+    save_snapshot_metadata(&snapshots)?;
+
+    if let Err(e) = write_metadata_sidecar(&updated) {
+        log::warn!("Failed to update metadata sidecar for {name}: {e}");
+    }
+    log::info!("Set retention group of {name} to {group:?}");
+    Ok(())
+}
+
+/// Set or clear the date after which cleanup deletes a snapshot
+pub fn set_snapshot_expiry(name: &str, expires_at: Option<DateTime<Utc>>) -> Result<()> {
+    ensure_snapshot_name(name)?;
+    let mut snapshots = load_snapshot_metadata()?;
+    let snapshot = snapshots
+        .iter_mut()
+        .find(|s| s.name == name)
+        .ok_or_else(|| anyhow::anyhow!("Snapshot metadata not found: {name}"))?;
+    snapshot.expires_at = expires_at;
+    let updated = snapshot.clone();
+    save_snapshot_metadata(&snapshots)?;
+
+    if let Err(e) = write_metadata_sidecar(&updated) {
+        log::warn!("Failed to update metadata sidecar for {name}: {e}");
+    }
+    log::info!("Set expiry of {name} to {expires_at:?}");
+    Ok(())
+}
+
+/// Set why a snapshot was taken
+pub fn set_snapshot_category(name: &str, category: SnapshotCategory) -> Result<()> {
+    ensure_snapshot_name(name)?;
+    let mut snapshots = load_snapshot_metadata()?;
+    let snapshot = snapshots
+        .iter_mut()
+        .find(|s| s.name == name)
+        .ok_or_else(|| anyhow::anyhow!("Snapshot metadata not found: {name}"))?;
+    snapshot.category = category;
+    let updated = snapshot.clone();
+    save_snapshot_metadata(&snapshots)?;
+
+    if let Err(e) = write_metadata_sidecar(&updated) {
+        log::warn!("Failed to update metadata sidecar for {name}: {e}");
+    }
+    log::info!("Set category of {name} to {}", category.as_str());
+    Ok(())
+}
+
+/// Pin or unpin a snapshot, keeping it out of every cleanup
+pub fn set_snapshot_pinned(name: &str, pinned: bool) -> Result<()> {
+    ensure_snapshot_name(name)?;
+    let mut snapshots = load_snapshot_metadata()?;
+    let snapshot = snapshots
+        .iter_mut()
+        .find(|s| s.name == name)
+        .ok_or_else(|| anyhow::anyhow!("Snapshot metadata not found: {name}"))?;
+    snapshot.is_favorite = pinned;
+    let updated = snapshot.clone();
+    save_snapshot_metadata(&snapshots)?;
+
+    if let Err(e) = write_metadata_sidecar(&updated) {
+        log::warn!("Failed to update metadata sidecar for {name}: {e}");
+    }
+    log::info!("Set pinned state of {name} to {pinned}");
+    Ok(())
+}
+
+/// Next Waypoint-assigned transaction number, for package managers without IDs
+fn next_transaction_id(snapshots: &[Snapshot]) -> String {
+    let last = snapshots
+        .iter()
+        .filter_map(|s| s.transaction.as_ref()?.id.parse::<u64>().ok())
+        .max()
+        .unwrap_or(0);
+    (last + 1).to_string()
+}
+
+/// Link `name` to a transaction in the loaded metadata
+///
+/// Without a `transaction_id`, a post snapshot shares its pre snapshot's ID
+/// and a pre snapshot gets the next sequence number.
+fn apply_transaction_link(
+    snapshots: &mut [Snapshot],
+    name: &str,
+    phase: TransactionPhase,
+    transaction_id: Option<&str>,
+    pre_snapshot: Option<&str>,
+) -> Result<TransactionLink> {
+    if !snapshots.iter().any(|s| s.name == name) {
+        bail!(HelperError::new(
+            ErrorCode::NotFound,
+            format!("Snapshot metadata not found: {name}")
+        ));
+    }
+    if phase == TransactionPhase::Pre && pre_snapshot.is_some() {
+        bail!(HelperError::new(
+            ErrorCode::InvalidInput,
+            "Only a post-transaction snapshot can name a pre-transaction snapshot"
+        ));
+    }
+
+    let pre = match pre_snapshot {
+        Some(pre) => Some(
+            snapshots
+                .iter()
+                .position(|s| s.name == pre)
+                .ok_or_else(|| {
+                    HelperError::new(ErrorCode::NotFound, format!("Snapshot not found: {pre}"))
+                })?,
+        ),
+        None => None,
+    };
+
+    let id = transaction_id
+        .map(String::from)
+        .or_else(|| {
+            pre.and_then(|index| snapshots[index].transaction.as_ref())
+                .map(|link| link.id.clone())
+        })
+        .unwrap_or_else(|| next_transaction_id(snapshots));
+
+    if let Some(index) = pre {
+        snapshots[index].transaction = Some(TransactionLink {
+            id: id.clone(),
+            phase: TransactionPhase::Pre,
+            paired_with: Some(name.to_string()),
+        });
+    }
+
+    let link = TransactionLink {
+        id,
+        phase,
+        paired_with: pre_snapshot.map(String::from),
+    };
+    if let Some(snapshot) = snapshots.iter_mut().find(|s| s.name == name) {
+        snapshot.transaction = Some(link.clone());
+    }
+    Ok(link)
+}
+
+/// Record the package manager transaction a snapshot was taken around
+///
+/// A post snapshot names its pre snapshot, which is linked back to it, so
+/// the pair can be shown together and the transaction undone.
+pub fn link_transaction(
+    name: &str,
+    phase: TransactionPhase,
+    transaction_id: Option<&str>,
+    pre_snapshot: Option<&str>,
+) -> Result<TransactionLink> {
+    ensure_snapshot_name(name)?;
+    if let Some(pre) = pre_snapshot {
+        ensure_snapshot_name(pre)?;
+    }
+
+    let mut snapshots = load_snapshot_metadata()?;
+    let link = apply_transaction_link(&mut snapshots, name, phase, transaction_id, pre_snapshot)?;
+    save_snapshot_metadata(&snapshots)?;
+
+    for snapshot in snapshots
+        .iter()
+        .filter(|s| s.name == name || Some(s.name.as_str()) == pre_snapshot)
+    {
+        if let Err(e) = write_metadata_sidecar(snapshot) {
+            log::warn!(
+                "Failed to update metadata sidecar for {}: {e}",
+                snapshot.name
+            );
+        }
+    }
+    log::info!(
+        "Linked {name} to transaction {} ({:?})",
+        link.id,
+        link.phase
+    );
+    Ok(link)
+}
+
 /// Get snapshot metadata by name
+///
+/// Falls back to the sidecar inside the snapshot directory when the central
+/// metadata file has no entry for it.
 pub fn get_snapshot_metadata(name: &str) -> Result<Snapshot> {
     ensure_snapshot_name(name)?;
     let snapshots = load_snapshot_metadata()?;
-    snapshots
-        .into_iter()
-        .find(|s| s.name == name)
-        .context(format!("Snapshot metadata not found: {name}"))
+    if let Some(snapshot) = snapshots.into_iter().find(|s| s.name == name) {
+        return Ok(snapshot);
+    }
+
+    read_metadata_sidecar(name).context(format!("Snapshot metadata not found: {name}"))
+}
+
+/// Write the metadata sidecar into a snapshot directory
+fn write_metadata_sidecar(snapshot: &Snapshot) -> Result<()> {
+    let content =
+        serde_json::to_string_pretty(snapshot).context("Failed to serialize snapshot metadata")?;
+    fs::write(snapshot.path.join(METADATA_SIDECAR), content)
+        .context("Failed to write snapshot metadata sidecar")
+}
+
+/// Read the metadata sidecar of a snapshot
+///
+/// The name and path are taken from the snapshot's location rather than the
+/// file, since the directory may have been renamed or moved since creation.
+pub fn read_metadata_sidecar(name: &str) -> Result<Snapshot> {
+    ensure_snapshot_name(name)?;
+    let snapshot_path = locate_snapshot(name);
+    ensure_within_snapshot_dir(&snapshot_path)?;
+
+    let content = fs::read_to_string(snapshot_path.join(METADATA_SIDECAR))
+        .context("Failed to read snapshot metadata sidecar")?;
+    let mut snapshot = parse_metadata_sidecar(&content)?;
+    snapshot.name = name.to_string();
+    snapshot.path = snapshot_path;
+    Ok(snapshot)
+}
+
+fn parse_metadata_sidecar(content: &str) -> Result<Snapshot> {
+    serde_json::from_str(content).context("Failed to parse snapshot metadata sidecar")
+}
+
+/// Copy a snapshot's metadata into its backup directory
+///
+/// Backups only carry the subvolumes, so without this a restored snapshot
+/// would lose its description, package list and kernel version.
+pub fn export_metadata_to_backup(name: &str, backup_dir: &Path) -> Result<()> {
+    let snapshot = get_snapshot_metadata(name)?;
+    let content =
+        serde_json::to_string_pretty(&snapshot).context("Failed to serialize snapshot metadata")?;
+    fs::write(backup_dir.join(METADATA_SIDECAR), content)
+        .context("Failed to write snapshot metadata to backup")
+}
+
+/// Read the snapshot metadata saved with a backup
+///
+/// Fails if the backup has no metadata, as with backups made by older versions.
+pub fn read_backup_metadata(backup_dir: &Path) -> Result<Snapshot> {
+    let content = fs::read_to_string(backup_dir.join(METADATA_SIDECAR))
+        .context("Backup has no snapshot metadata")?;
+    parse_metadata_sidecar(&content)
+}
+
+/// Register a snapshot restored from a backup using the backup's metadata
+///
+/// Replaces any existing entry of the same name. Fails if the backup has no
+/// metadata, as with backups made by older versions.
+pub fn import_metadata_from_backup(backup_dir: &Path, name: &str) -> Result<()> {
+    ensure_snapshot_name(name)?;
+    let snapshot_path = locate_snapshot(name);
+    ensure_within_snapshot_dir(&snapshot_path)?;
+
+    let mut snapshot = read_backup_metadata(backup_dir)?;
+    // Restored under another name, it's a separate snapshot from the original
+    if snapshot.name != name {
+        snapshot.id = format!("snapshot-{}", Utc::now().format("%Y%m%d-%H%M%S"));
+    }
+    snapshot.name = name.to_string();
+    snapshot.path = snapshot_path;
+
+    // Read-only received subvolumes can't take a sidecar; the central entry is enough
+    if let Err(e) = write_metadata_sidecar(&snapshot) {
+        log::debug!("Could not write metadata sidecar for restored snapshot {name}: {e}");
+    }
+
+    let mut snapshots = load_snapshot_metadata()?;
+    snapshots.retain(|s| s.name != name);
+    snapshots.push(snapshot);
+    save_snapshot_metadata(&snapshots)
 }
 
-/// Get filesystem UUID for a mount point
-#[allow(dead_code)]
-fn get_filesystem_uuid(mount_point: &Path) -> Result<String> {
+/// Get the UUID of the filesystem holding `path`
+fn get_filesystem_uuid(path: &Path) -> Result<String> {
     let output = Command::new("findmnt")
         .arg("-n")
         .arg("-o")
         .arg("UUID")
-        .arg(mount_point)
-        .output()
+        .arg("--target")
+        .arg(path)
+        .recorded_output()
         .context("Failed to execute findmnt")?;
 
     if !output.status.success() {
-        bail!("Failed to get UUID for {mount_point:?}");
+        bail!("Failed to get UUID for {path:?}");
     }
 
     let uuid = String::from_utf8_lossy(&output.stdout).trim().to_string();
     if uuid.is_empty() {
-        bail!("No UUID found for {mount_point:?}");
+        bail!("No UUID found for {path:?}");
     }
 
     Ok(uuid)
@@ -1306,6 +2526,7 @@ fn update_fstab_for_snapshot(
     // Read current fstab
     let fstab_content = fs::read_to_string(fstab_path).context("Failed to read fstab")?;
 
+    let snapshots_subvolume = crate::btrfs_root::snapshots_subvolume();
     let mut updated_lines = Vec::new();
     let mut updated = false;
 
@@ -1343,7 +2564,8 @@ fn update_fstab_for_snapshot(
         }
 
         // Update the subvol option to point to the snapshot
-        let new_options = update_subvol_option(options, snapshot_name, &mount_path)?;
+        let new_options =
+            update_subvol_option(options, &snapshots_subvolume, snapshot_name, &mount_path)?;
 
         // Reconstruct the fstab line with updated options
         let mut new_parts = parts.clone();
@@ -1388,7 +2610,11 @@ fn validate_fstab(
     let fstab_content = fs::read_to_string(fstab_path)
         .context("Failed to read fstab for validation")?;
 
-    let snapshot_base = snapshot_dir().join(snapshot_name);
+    let snapshot_base = locate_snapshot(snapshot_name);
+    let snapshot_subvol = format!(
+        "{}/{snapshot_name}",
+        crate::btrfs_root::snapshots_subvolume()
+    );
     let mut validated_entries = 0;
     let mut errors = Vec::new();
 
@@ -1466,7 +2692,7 @@ fn validate_fstab(
         }
 
         // Verify the subvol option points to the snapshot
-        if !options.contains(&format!("@snapshots/{}", snapshot_name)) {
+        if !options.contains(&snapshot_subvol) {
             errors.push(format!(
                 "Line {}: Mount options don't reference snapshot '{}': {}",
                 line_num + 1,
@@ -1560,7 +2786,7 @@ fn verify_snapshot_subvolume_exists(snapshot_base: &Path, mount_point: &Path) ->
         .arg("subvolume")
         .arg("show")
         .arg(&subvol_path)
-        .output()
+        .recorded_output()
         .context("Failed to execute btrfs subvolume show")?;
 
     if !output.status.success() {
@@ -1574,7 +2800,15 @@ fn verify_snapshot_subvolume_exists(snapshot_base: &Path, mount_point: &Path) ->
 }
 
 /// Update the subvol option in mount options string
-fn update_subvol_option(options: &str, snapshot_name: &str, mount_point: &Path) -> Result<String> {
+///
+/// `snapshots_subvolume` is the subvolume holding the snapshots, relative to
+/// the top level, e.g. `@snapshots`.
+fn update_subvol_option(
+    options: &str,
+    snapshots_subvolume: &str,
+    snapshot_name: &str,
+    mount_point: &Path,
+) -> Result<String> {
     let opts: Vec<&str> = options.split(',').collect();
     let mut new_opts = Vec::new();
     let mut found_subvol = false;
@@ -1590,7 +2824,7 @@ fn update_subvol_option(options: &str, snapshot_name: &str, mount_point: &Path)
     };
 
     // The new subvol path in the snapshot
-    let new_subvol = format!("@snapshots/{snapshot_name}/{subvol_name}");
+    let new_subvol = format!("{snapshots_subvolume}/{snapshot_name}/{subvol_name}");
 
     for opt in opts {
         if opt.starts_with("subvol=") || opt.starts_with("subvolid=") {
@@ -1618,7 +2852,7 @@ fn create_writable_snapshot(source: &Path, dest: &Path) -> Result<()> {
         // Note: no -r flag, so it's writable
         .arg(source)
         .arg(dest)
-        .output()
+        .recorded_output()
         .context("Failed to create writable snapshot")?;
 
     if !output.status.success() {
@@ -1633,6 +2867,79 @@ fn create_writable_snapshot(source: &Path, dest: &Path) -> Result<()> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_qgroup_usage() {
+        let output = "\
+Qgroupid    Referenced    Exclusive   Path
+--------    ----------    ---------   ----
+0/5           16384         16384     <toplevel>
+0/257       8470528       1105920     @snapshots/daily/root
+1/100      10485760       3145728     <0 member qgroups>
+";
+        assert_eq!(
+            parse_qgroup_usage(output, SNAPSHOT_QGROUP),
+            Some((10485760, 3145728))
+        );
+        assert_eq!(
+            parse_qgroup_usage(output, "0/257"),
+            Some((8470528, 1105920))
+        );
+        assert_eq!(parse_qgroup_usage(output, "1/0"), None);
+    }
+
+    #[test]
+    fn test_parse_level0_qgroups() {
+        let output = "\
+Qgroupid    Referenced    Exclusive   Path
+--------    ----------    ---------   ----
+0/5           16384         16384     <toplevel>
+0/257       8470528       1105920     @snapshots/daily/root
+1/100      10485760       3145728     <0 member qgroups>
+";
+        let sizes = parse_level0_qgroups(output);
+        assert_eq!(sizes.len(), 2);
+        assert_eq!(sizes.get(&257), Some(&(8470528, 1105920)));
+        assert_eq!(sizes.get(&100), None);
+    }
+
+    #[test]
+    fn test_parse_ro_property() {
+        assert_eq!(parse_ro_property("ro=true\n"), Some(true));
+        assert_eq!(parse_ro_property("ro=false\n"), Some(false));
+        assert_eq!(parse_ro_property(""), None);
+    }
+
+    #[test]
+    fn test_parse_subvolume_generation() {
+        let output = "@snapshots/root\n\
+                      \tName: \t\t\troot\n\
+                      \tSubvolume ID: \t\t271\n\
+                      \tGeneration: \t\t4711\n\
+                      \tGen at creation: \t4702\n";
+        assert_eq!(parse_subvolume_generation(output), Some(4711));
+        assert_eq!(parse_subvolume_generation(""), None);
+    }
+
+    #[test]
+    fn test_subvolume_boundary() {
+        // Plain directory on the same subvolume
+        assert_eq!(subvolume_boundary(4711, 40, 40), None);
+        // Nested subvolume left out of the snapshot
+        assert_eq!(
+            subvolume_boundary(SUBVOLUME_PLACEHOLDER_INODE, 40, 40),
+            Some(SubvolumeBoundary::Placeholder)
+        );
+        // Subvolume inside the snapshot, found by device or root inode
+        assert_eq!(
+            subvolume_boundary(300, 41, 40),
+            Some(SubvolumeBoundary::Subvolume)
+        );
+        assert_eq!(
+            subvolume_boundary(SUBVOLUME_ROOT_INODE, 40, 40),
+            Some(SubvolumeBoundary::Subvolume)
+        );
+    }
+
     #[test]
     fn test_update_subvol_option_root_filesystem() {
         // Test updating subvol option for root filesystem
@@ -1640,7 +2947,7 @@ mod tests {
         let snapshot_name = "snapshot-20251111-120000";
         let mount_point = PathBuf::from("/");
 
-        let result = update_subvol_option(options, snapshot_name, &mount_point).unwrap();
+        let result = update_subvol_option(options, "@snapshots", snapshot_name, &mount_point).unwrap();
 
         // Should replace subvol=@ with subvol=@snapshots/snapshot-20251111-120000/root
         assert!(result.contains("subvol=@snapshots/snapshot-20251111-120000/root"));
@@ -1655,7 +2962,7 @@ mod tests {
         let snapshot_name = "backup-2025";
         let mount_point = PathBuf::from("/home");
 
-        let result = update_subvol_option(options, snapshot_name, &mount_point).unwrap();
+        let result = update_subvol_option(options, "@snapshots", snapshot_name, &mount_point).unwrap();
 
         // Should use "home" as subvolume name
         assert!(result.contains("subvol=@snapshots/backup-2025/home"));
@@ -1669,7 +2976,7 @@ mod tests {
         let snapshot_name = "test-snapshot";
         let mount_point = PathBuf::from("/");
 
-        let result = update_subvol_option(options, snapshot_name, &mount_point).unwrap();
+        let result = update_subvol_option(options, "@snapshots", snapshot_name, &mount_point).unwrap();
 
         // subvolid should be replaced with subvol
         assert!(result.contains("subvol=@snapshots/test-snapshot/root"));
@@ -1683,7 +2990,7 @@ mod tests {
         let snapshot_name = "new-snapshot";
         let mount_point = PathBuf::from("/");
 
-        let result = update_subvol_option(options, snapshot_name, &mount_point).unwrap();
+        let result = update_subvol_option(options, "@snapshots", snapshot_name, &mount_point).unwrap();
 
         // Should add subvol option
         assert!(result.contains("subvol=@snapshots/new-snapshot/root"));
@@ -1697,7 +3004,7 @@ mod tests {
         let snapshot_name = "snapshot-1";
         let mount_point = PathBuf::from("/var/lib");
 
-        let result = update_subvol_option(options, snapshot_name, &mount_point).unwrap();
+        let result = update_subvol_option(options, "@snapshots", snapshot_name, &mount_point).unwrap();
 
         // Should convert /var/lib to var_lib
         assert!(result.contains("subvol=@snapshots/snapshot-1/var_lib"));
@@ -1710,7 +3017,7 @@ mod tests {
         let snapshot_name = "test";
         let mount_point = PathBuf::from("/");
 
-        let result = update_subvol_option(options, snapshot_name, &mount_point).unwrap();
+        let result = update_subvol_option(options, "@snapshots", snapshot_name, &mount_point).unwrap();
 
         // All options except subvol should be preserved
         assert!(result.contains("rw"));
@@ -1728,7 +3035,7 @@ mod tests {
         let snapshot_name = "pre-upgrade_2025-01-11";
         let mount_point = PathBuf::from("/");
 
-        let result = update_subvol_option(options, snapshot_name, &mount_point).unwrap();
+        let result = update_subvol_option(options, "@snapshots", snapshot_name, &mount_point).unwrap();
 
         assert!(result.contains("subvol=@snapshots/pre-upgrade_2025-01-11/root"));
     }
@@ -1740,9 +3047,221 @@ mod tests {
         let snapshot_name = "test";
         let mount_point = PathBuf::from("/");
 
-        let result = update_subvol_option(options, snapshot_name, &mount_point).unwrap();
+        let result = update_subvol_option(options, "@snapshots", snapshot_name, &mount_point).unwrap();
 
         // Should still add subvol option
         assert!(result.contains("subvol=@snapshots/test/root"));
     }
+
+    #[test]
+    fn test_parse_metadata_sidecar_roundtrip() {
+        let snapshot = Snapshot {
+            id: "snapshot-20251111-120000".to_string(),
+            name: "before-update".to_string(),
+            timestamp: Utc::now(),
+            path: PathBuf::from("/.snapshots/before-update"),
+            description: Some("Before system update".to_string()),
+            kernel_version: Some("6.6.1_1".to_string()),
+            package_count: Some(1),
+            packages: vec![Package {
+                name: "linux".to_string(),
+                version: "6.6_1".to_string(),
+            }],
+            subvolumes: vec![PathBuf::from("/"), PathBuf::from("/home")],
+            retention_group: None,
+            expires_at: None,
+            transaction: None,
+            category: SnapshotCategory::PreUpdate,
+            is_favorite: false,
+        };
+
+        let content = serde_json::to_string_pretty(&snapshot).unwrap();
+        let parsed = parse_metadata_sidecar(&content).unwrap();
+        assert_eq!(parsed.name, "before-update");
+        assert_eq!(parsed.packages.len(), 1);
+        assert_eq!(parsed.subvolumes, snapshot.subvolumes);
+        assert_eq!(parsed.category, SnapshotCategory::PreUpdate);
+        assert!(!parsed.is_expired(Utc::now()));
+
+        assert!(parse_metadata_sidecar("not json").is_err());
+    }
+
+    #[test]
+    fn test_snapshot_expiry() {
+        let now = Utc::now();
+        let mut snapshot = Snapshot {
+            id: "temp".to_string(),
+            name: "temp".to_string(),
+            timestamp: now,
+            path: PathBuf::from("/.snapshots/temp"),
+            description: None,
+            kernel_version: None,
+            package_count: None,
+            packages: Vec::new(),
+            subvolumes: vec![PathBuf::from("/")],
+            retention_group: None,
+            expires_at: Some(now + chrono::Duration::days(7)),
+            transaction: None,
+            category: SnapshotCategory::Manual,
+            is_favorite: false,
+        };
+        assert!(!snapshot.is_expired(now));
+        assert!(snapshot.is_expired(now + chrono::Duration::days(7)));
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert_eq!(
+            parse_metadata_sidecar(&json).unwrap().expires_at,
+            snapshot.expires_at
+        );
+
+        // Metadata without an expiry never expires
+        snapshot.expires_at = None;
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert!(!json.contains("expires_at"));
+        assert!(!parse_metadata_sidecar(&json).unwrap().is_expired(now));
+    }
+
+    #[test]
+    fn test_packages_of() {
+        let snapshot = |name: &str, package: &str| Snapshot {
+            id: name.to_string(),
+            name: name.to_string(),
+            timestamp: Utc::now(),
+            path: PathBuf::from("/.snapshots").join(name),
+            description: None,
+            kernel_version: None,
+            package_count: Some(1),
+            packages: vec![Package {
+                name: package.to_string(),
+                version: "1.0_1".to_string(),
+            }],
+            subvolumes: vec![PathBuf::from("/")],
+            retention_group: None,
+            expires_at: None,
+            transaction: None,
+            category: SnapshotCategory::Manual,
+            is_favorite: false,
+        };
+        let snapshots = vec![snapshot("before", "bash"), snapshot("after", "zsh")];
+
+        // Only requested snapshots are returned and unknown names are left out
+        let names = vec!["after".to_string(), "missing".to_string()];
+        let packages = packages_of(snapshots.clone(), &names);
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages["after"][0].name, "zsh");
+
+        assert!(packages_of(snapshots, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_apply_transaction_link() {
+        let snapshot = |name: &str| Snapshot {
+            id: name.to_string(),
+            name: name.to_string(),
+            timestamp: Utc::now(),
+            path: PathBuf::from("/.snapshots").join(name),
+            description: None,
+            kernel_version: None,
+            package_count: None,
+            packages: Vec::new(),
+            subvolumes: vec![PathBuf::from("/")],
+            retention_group: None,
+            expires_at: None,
+            transaction: None,
+            category: SnapshotCategory::Manual,
+            is_favorite: false,
+        };
+        let mut snapshots = vec![snapshot("pre-1"), snapshot("post-1"), snapshot("pre-2")];
+
+        // Waypoint numbers transactions when the package manager has no ID
+        let link =
+            apply_transaction_link(&mut snapshots, "pre-1", TransactionPhase::Pre, None, None)
+                .unwrap();
+        assert_eq!(link.id, "1");
+        assert_eq!(link.paired_with, None);
+
+        // The post snapshot shares the ID and both name each other
+        let link = apply_transaction_link(
+            &mut snapshots,
+            "post-1",
+            TransactionPhase::Post,
+            None,
+            Some("pre-1"),
+        )
+        .unwrap();
+        assert_eq!(link.id, "1");
+        assert_eq!(link.pre_snapshot("post-1"), Some("pre-1"));
+        let pre = snapshots[0].transaction.as_ref().unwrap();
+        assert_eq!(pre.paired_with.as_deref(), Some("post-1"));
+        assert_eq!(pre.pre_snapshot("pre-1"), Some("pre-1"));
+
+        let link =
+            apply_transaction_link(&mut snapshots, "pre-2", TransactionPhase::Pre, None, None)
+                .unwrap();
+        assert_eq!(link.id, "2");
+        let link = apply_transaction_link(
+            &mut snapshots,
+            "pre-2",
+            TransactionPhase::Pre,
+            Some("4521"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(link.id, "4521");
+
+        assert!(
+            apply_transaction_link(
+                &mut snapshots,
+                "pre-2",
+                TransactionPhase::Pre,
+                None,
+                Some("pre-1")
+            )
+            .is_err()
+        );
+        assert!(
+            apply_transaction_link(
+                &mut snapshots,
+                "post-1",
+                TransactionPhase::Post,
+                None,
+                Some("gone")
+            )
+            .is_err()
+        );
+        assert!(
+            apply_transaction_link(&mut snapshots, "missing", TransactionPhase::Pre, None, None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_validate_restore_target() {
+        assert!(validate_restore_target(Path::new("relative/path")).is_err());
+        assert!(validate_restore_target(Path::new("/tmp/../etc/restored")).is_err());
+        assert!(validate_restore_target(Path::new("/nonexistent-waypoint-dir/restored")).is_err());
+        assert!(validate_restore_target(Path::new("/tmp")).is_err());
+        assert!(validate_restore_target(&snapshot_dir().join("restored")).is_err());
+    }
+
+    #[test]
+    fn test_find_missing_schedule_subvolumes() {
+        let daily = waypoint_common::Schedule {
+            subvolumes: vec![PathBuf::from("/"), PathBuf::from("/data")],
+            ..waypoint_common::Schedule::default_daily()
+        };
+        // Disabled schedules aren't run, so stale paths there are left alone
+        let weekly = waypoint_common::Schedule {
+            subvolumes: vec![PathBuf::from("/gone")],
+            ..waypoint_common::Schedule::default_weekly()
+        };
+        let config = waypoint_common::SchedulesConfig {
+            schedules: vec![daily, weekly],
+        };
+
+        let missing = find_missing_schedule_subvolumes(&config, |path| path == Path::new("/"));
+        assert_eq!(missing, vec!["'daily' includes /data".to_string()]);
+
+        assert!(find_missing_schedule_subvolumes(&config, |_| true).is_empty());
+    }
 }
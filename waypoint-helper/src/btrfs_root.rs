@@ -0,0 +1,281 @@
+// Access to the top-level Btrfs subvolume for waypoint-helper
+//
+// Most systems mount the snapshots subvolume at the snapshot directory, but
+// some only have it reachable through the top-level subvolume. In that case
+// the helper mounts the top level itself, with the mount point and options
+// from the configuration, and unmounts it again when it shuts down. Nothing is
+// mounted when the snapshot directory is already accessible.
+
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use waypoint_common::WaypointConfig;
+
+use crate::transcript::RecordedCommand;
+
+/// One line of /proc/self/mountinfo
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MountInfo {
+    /// Path of the mount's root within its filesystem (the subvolume for btrfs)
+    root: String,
+    mount_point: PathBuf,
+    fs_type: String,
+    /// Filesystem-specific options, e.g. `rw,compress=zstd:3,subvol=/@`
+    super_options: String,
+}
+
+/// Parse /proc/self/mountinfo
+///
+/// Format: `id parent major:minor root mount_point options [optional...] - fstype source super_options`
+fn parse_mountinfo(content: &str) -> Vec<MountInfo> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (left, right) = line.split_once(" - ")?;
+            let fields: Vec<&str> = left.split_whitespace().collect();
+            let mut right = right.split_whitespace();
+            Some(MountInfo {
+                root: unescape(fields.get(3)?),
+                mount_point: PathBuf::from(unescape(fields.get(4)?)),
+                fs_type: right.next()?.to_string(),
+                super_options: right.nth(1).unwrap_or_default().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Undo the octal escaping of spaces and friends in mountinfo paths
+fn unescape(field: &str) -> String {
+    field
+        .replace("\\040", " ")
+        .replace("\\011", "\t")
+        .replace("\\012", "\n")
+        .replace("\\134", "\\")
+}
+
+fn read_mountinfo() -> Vec<MountInfo> {
+    fs::read_to_string("/proc/self/mountinfo")
+        .map(|content| parse_mountinfo(&content))
+        .unwrap_or_default()
+}
+
+/// Mount containing `path`: the one with the longest matching mount point
+///
+/// Later entries win on ties, since they are mounted on top of earlier ones.
+fn containing_mount<'a>(mounts: &'a [MountInfo], path: &Path) -> Option<&'a MountInfo> {
+    mounts
+        .iter()
+        .filter(|m| path.starts_with(&m.mount_point))
+        .max_by_key(|m| m.mount_point.components().count())
+}
+
+/// Path of `path` relative to the top-level subvolume, if it is on btrfs
+fn subvolume_path_from_mounts(mounts: &[MountInfo], path: &Path) -> Option<String> {
+    let mount = containing_mount(mounts, path)?;
+    if mount.fs_type != "btrfs" {
+        return None;
+    }
+
+    let relative = path.strip_prefix(&mount.mount_point).ok()?;
+    let full = Path::new(&mount.root).join(relative);
+    let full = full.to_string_lossy().trim_matches('/').to_string();
+    (!full.is_empty()).then_some(full)
+}
+
+/// Algorithm of the `compress=` or `compress-force=` option of the btrfs mount containing `path`
+fn compression_from_mounts(mounts: &[MountInfo], path: &Path) -> Option<String> {
+    let mount = containing_mount(mounts, path).filter(|m| m.fs_type == "btrfs")?;
+    mount.super_options.split(',').find_map(|option| {
+        option
+            .strip_prefix("compress=")
+            .or_else(|| option.strip_prefix("compress-force="))
+            .map(String::from)
+    })
+}
+
+/// Compression the filesystem holding `path` is mounted with, if any
+pub fn mount_compression(path: &Path) -> Option<String> {
+    compression_from_mounts(&read_mountinfo(), path)
+}
+
+/// Subvolume holding the snapshots, relative to the top level
+///
+/// Detected from how the snapshot directory is mounted, so fstab entries
+/// written during a restore point at the right place even when the layout
+/// differs from the default `@snapshots`. Falls back to the configured
+/// `snapshots_subvolume`.
+pub fn snapshots_subvolume() -> String {
+    let config = WaypointConfig::new();
+    subvolume_path_from_mounts(&read_mountinfo(), &config.snapshot_dir)
+        .unwrap_or(config.snapshots_subvolume)
+}
+
+/// Device holding the root filesystem, without the `[/subvol]` suffix findmnt adds
+fn root_device() -> Result<String> {
+    let output = Command::new("findmnt")
+        .args(["-n", "-o", "SOURCE,FSTYPE", "/"])
+        .recorded_output()
+        .context("Failed to run findmnt")?;
+    if !output.status.success() {
+        bail!("findmnt failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut fields = stdout.split_whitespace();
+    let source = fields.next().unwrap_or_default();
+    if fields.next() != Some("btrfs") {
+        bail!("The root filesystem is not Btrfs");
+    }
+    Ok(source.split('[').next().unwrap_or(source).to_string())
+}
+
+/// Top-level subvolume mounted by the helper, unmounted again on drop
+pub struct RootMount {
+    mount_point: PathBuf,
+    created_dir: bool,
+}
+
+impl Drop for RootMount {
+    fn drop(&mut self) {
+        let result = Command::new("umount")
+            .arg(&self.mount_point)
+            .recorded_output();
+        match result {
+            Ok(output) if output.status.success() => {
+                log::info!("Unmounted Btrfs root at {}", self.mount_point.display());
+                if self.created_dir {
+                    let _ = fs::remove_dir(&self.mount_point);
+                }
+            }
+            Ok(output) => log::warn!(
+                "Failed to unmount Btrfs root at {}: {}",
+                self.mount_point.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            Err(e) => log::warn!("Failed to run umount: {e}"),
+        }
+    }
+}
+
+/// Make the snapshot directory accessible, mounting the top level if needed
+///
+/// Returns `None` when nothing had to be mounted: the snapshot directory
+/// exists, or the top-level mount point is already in use. Keep the returned
+/// guard alive for as long as the snapshots are needed.
+pub fn ensure_snapshot_dir() -> Result<Option<RootMount>> {
+    let config = WaypointConfig::new();
+    if config.snapshot_dir.exists() {
+        return Ok(None);
+    }
+
+    let mount_point = config.btrfs_root_mount.clone();
+    if read_mountinfo().iter().any(|m| m.mount_point == mount_point) {
+        log::info!(
+            "{} is already mounted; not mounting the Btrfs root again",
+            mount_point.display()
+        );
+        return Ok(None);
+    }
+
+    let device = root_device()?;
+    let created_dir = !mount_point.exists();
+    if created_dir {
+        fs::create_dir_all(&mount_point)
+            .context(format!("Failed to create {}", mount_point.display()))?;
+    }
+
+    let mount_point_str = mount_point.to_string_lossy().to_string();
+    let mut args = vec!["-t", "btrfs"];
+    if !config.btrfs_root_mount_options.is_empty() {
+        args.extend(["-o", config.btrfs_root_mount_options.as_str()]);
+    }
+    args.extend([device.as_str(), mount_point_str.as_str()]);
+
+    if let Err(e) = crate::run_command("mount", &args) {
+        if created_dir {
+            let _ = fs::remove_dir(&mount_point);
+        }
+        return Err(e.context("Failed to mount the Btrfs root"));
+    }
+
+    log::info!(
+        "Mounted Btrfs root {device} at {} with options '{}'",
+        mount_point.display(),
+        config.btrfs_root_mount_options
+    );
+    let guard = RootMount {
+        mount_point,
+        created_dir,
+    };
+
+    // Nothing to gain from the mount if the snapshots aren't there either
+    let fallback = config.fallback_snapshot_dir();
+    if !fallback.is_dir() {
+        bail!(
+            "Snapshot subvolume '{}' not found under {}",
+            config.snapshots_subvolume,
+            config.btrfs_root_mount.display()
+        );
+    }
+
+    Ok(Some(guard))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOUNTINFO: &str = "\
+22 1 0:21 /@ / rw,relatime shared:1 - btrfs /dev/nvme0n1p2 rw,ssd,subvol=/@
+23 22 0:21 /@home /home rw,relatime shared:2 - btrfs /dev/nvme0n1p2 rw,ssd,subvol=/@home
+24 22 0:21 /@snapshots /.snapshots rw,relatime shared:3 - btrfs /dev/nvme0n1p2 rw,ssd,compress=zstd:3,subvol=/@snapshots
+25 22 0:22 / /tmp rw,nosuid shared:4 - tmpfs tmpfs rw
+26 22 0:21 / /mnt/my\\040root rw,relatime shared:5 - btrfs /dev/nvme0n1p2 rw,ssd,subvolid=5
+";
+
+    #[test]
+    fn test_parse_mountinfo() {
+        let mounts = parse_mountinfo(MOUNTINFO);
+        assert_eq!(mounts.len(), 5);
+        assert_eq!(mounts[2].root, "/@snapshots");
+        assert_eq!(mounts[2].mount_point, PathBuf::from("/.snapshots"));
+        assert_eq!(mounts[2].fs_type, "btrfs");
+        assert_eq!(mounts[4].mount_point, PathBuf::from("/mnt/my root"));
+        assert_eq!(mounts[3].super_options, "rw");
+    }
+
+    #[test]
+    fn test_compression_from_mounts() {
+        let mounts = parse_mountinfo(MOUNTINFO);
+        assert_eq!(
+            compression_from_mounts(&mounts, Path::new("/.snapshots/daily")),
+            Some("zstd:3".to_string())
+        );
+        assert_eq!(compression_from_mounts(&mounts, Path::new("/home")), None);
+        assert_eq!(compression_from_mounts(&mounts, Path::new("/tmp")), None);
+    }
+
+    #[test]
+    fn test_subvolume_path_from_mounts() {
+        let mounts = parse_mountinfo(MOUNTINFO);
+
+        // Dedicated snapshots subvolume
+        assert_eq!(
+            subvolume_path_from_mounts(&mounts, Path::new("/.snapshots")),
+            Some("@snapshots".to_string())
+        );
+        // Plain directory inside the root subvolume
+        assert_eq!(
+            subvolume_path_from_mounts(&mounts, Path::new("/var/snapshots")),
+            Some("@/var/snapshots".to_string())
+        );
+        // Through a mount of the top level
+        assert_eq!(
+            subvolume_path_from_mounts(&mounts, Path::new("/mnt/my root/@snapshots")),
+            Some("@snapshots".to_string())
+        );
+        // Not on btrfs
+        assert_eq!(subvolume_path_from_mounts(&mounts, Path::new("/tmp/snapshots")), None);
+    }
+}
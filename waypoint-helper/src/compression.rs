@@ -0,0 +1,195 @@
+// Btrfs compression of the snapshot directory for waypoint-helper
+//
+// The property is set on the snapshot directory itself, so it only affects
+// data written into that directory afterwards, such as snapshots received
+// when restoring from a backup. Snapshots of the system share their extents
+// with the source subvolumes and keep whatever compression those were written
+// with. `compsize` is optional; without it the savings are simply not reported.
+
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use waypoint_common::compression::validate_compression;
+use waypoint_common::{
+    CompressionUsage, ErrorCode, HelperError, SnapshotCompression, WaypointConfig,
+};
+
+use crate::transcript::RecordedCommand;
+
+/// Upper bound for `compsize`, which walks every extent below the directory
+const COMPSIZE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long a `compsize` measurement is reused before measuring again
+const USAGE_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Last `compsize` measurement: the directory, when it was taken and the result
+static USAGE_CACHE: Mutex<Option<(PathBuf, Instant, Option<CompressionUsage>)>> = Mutex::new(None);
+
+/// Parse `btrfs property get <path> compression` output
+///
+/// Prints `compression=zstd` when set and nothing otherwise.
+fn parse_property_output(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("compression="))
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(String::from)
+}
+
+/// Parse the TOTAL line of `compsize -b` output
+///
+/// Example:
+/// ```text
+/// Processed 1200 files, 900 regular extents (950 refs), 300 inline.
+/// Type       Perc     Disk Usage   Uncompressed Referenced
+/// TOTAL       42%      420000000   1000000000   1800000000
+/// zstd        30%      180000000    600000000    700000000
+/// ```
+fn parse_compsize_total(output: &str) -> Option<CompressionUsage> {
+    let line = output.lines().find(|line| line.starts_with("TOTAL"))?;
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    Some(CompressionUsage {
+        disk_bytes: fields.get(2)?.parse().ok()?,
+        uncompressed_bytes: fields.get(3)?.parse().ok()?,
+    })
+}
+
+/// `compression` property of `path`, None when unset
+fn read_property(path: &Path) -> Result<Option<String>> {
+    let output = Command::new("btrfs")
+        .args(["property", "get"])
+        .arg(path)
+        .arg("compression")
+        .recorded_output()
+        .context("Failed to run btrfs property get")?;
+
+    if !output.status.success() {
+        bail!(
+            "Failed to read compression of {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_property_output(&stdout))
+}
+
+/// Space used by the data below `path`, None when `compsize` is unavailable
+fn compression_usage(path: &Path) -> Option<CompressionUsage> {
+    let output = crate::output_with_timeout(
+        Command::new("compsize").arg("-b").arg(path),
+        COMPSIZE_TIMEOUT,
+        None,
+    )
+    .inspect_err(|e| log::debug!("compsize not available: {e}"))
+    .ok()?;
+
+    if !output.status.success() {
+        log::debug!(
+            "compsize failed for {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+
+    parse_compsize_total(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// `compression_usage` of `path`, reusing a measurement younger than `USAGE_CACHE_TTL`
+///
+/// Holding the lock while measuring keeps concurrent callers from starting
+/// several `compsize` walks of the same directory.
+fn cached_compression_usage(path: &Path) -> Option<CompressionUsage> {
+    let mut cache = USAGE_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((cached_path, measured_at, usage)) = cache.as_ref()
+        && cached_path == path
+        && measured_at.elapsed() < USAGE_CACHE_TTL
+    {
+        return *usage;
+    }
+
+    let usage = compression_usage(path);
+    *cache = Some((path.to_path_buf(), Instant::now(), usage));
+    usage
+}
+
+/// Current compression settings and savings of the snapshot directory
+pub fn get_snapshot_compression() -> Result<SnapshotCompression> {
+    let path = WaypointConfig::new().snapshot_dir;
+    if !path.exists() {
+        bail!("Snapshot directory {} does not exist", path.display());
+    }
+
+    Ok(SnapshotCompression {
+        property: read_property(&path)?,
+        mount_option: crate::btrfs_root::mount_compression(&path),
+        usage: cached_compression_usage(&path),
+        path,
+    })
+}
+
+/// Set the `compression` property of the snapshot directory
+///
+/// An empty algorithm resets the property so the mount option applies again.
+/// Existing snapshots and new snapshots of the system are not recompressed;
+/// see the module comment.
+pub fn set_snapshot_compression(algorithm: &str) -> Result<SnapshotCompression> {
+    validate_compression(algorithm).map_err(|e| HelperError::new(ErrorCode::InvalidInput, e))?;
+
+    let path = WaypointConfig::new().snapshot_dir;
+    crate::filesystem_stats::check_writable(&path)?;
+
+    let output = Command::new("btrfs")
+        .args(["property", "set"])
+        .arg(&path)
+        .args(["compression", algorithm])
+        .recorded_output()
+        .context("Failed to run btrfs property set")?;
+
+    if !output.status.success() {
+        bail!(
+            "Failed to set compression of {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    log::info!("Compression of {} set to '{algorithm}'", path.display());
+    get_snapshot_compression()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_property_output() {
+        assert_eq!(
+            parse_property_output("compression=zstd\n"),
+            Some("zstd".to_string())
+        );
+        assert_eq!(parse_property_output(""), None);
+        assert_eq!(parse_property_output("compression=\n"), None);
+    }
+
+    #[test]
+    fn test_parse_compsize_total() {
+        let output = "Processed 1200 files, 900 regular extents (950 refs), 300 inline.\n\
+                      Type       Perc     Disk Usage   Uncompressed Referenced\n\
+                      TOTAL       42%      420000000   1000000000   1800000000\n\
+                      zstd        30%      180000000    600000000    700000000\n";
+        assert_eq!(
+            parse_compsize_total(output),
+            Some(CompressionUsage {
+                disk_bytes: 420_000_000,
+                uncompressed_bytes: 1_000_000_000,
+            })
+        );
+        assert_eq!(parse_compsize_total("ERROR: no such file"), None);
+    }
+}
@@ -0,0 +1,209 @@
+// Offline deduplication of snapshots for waypoint-helper
+//
+// Snapshots of mostly identical data can still hold duplicate extents, for
+// example when packages were reinstalled or files rewritten with the same
+// content. duperemove finds those and asks the kernel to share them. This is
+// a manual maintenance action and is never run automatically.
+
+use anyhow::{Context, Result, bail};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::SyncSender;
+use waypoint_common::{DedupEstimate, WaypointConfig};
+
+use crate::transcript::RecordedCommand;
+
+/// Hash database used by duperemove so large runs don't have to fit in memory
+const HASHFILE_PATH: &str = "/var/tmp/waypoint-dedup.hash";
+
+/// Set while a deduplication runs; a second run would only compete for I/O
+static DEDUP_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Progress update from a running deduplication
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DedupProgress {
+    /// "scanning" or "deduplicating"
+    pub stage: &'static str,
+    /// Items processed in the current stage
+    pub done: u64,
+    /// Total items in the current stage
+    pub total: u64,
+}
+
+/// Snapshot directories below the configured snapshot directory
+fn snapshot_paths() -> Result<Vec<PathBuf>> {
+    let snapshot_dir = WaypointConfig::new().snapshot_dir;
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(&snapshot_dir)
+        .context(format!("Failed to read {}", snapshot_dir.display()))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Estimate how much space deduplication could reclaim
+///
+/// Uses `btrfs filesystem du`, which walks every snapshot but only reads
+/// metadata, so it is much cheaper than a real duperemove scan.
+pub fn estimate_savings() -> Result<DedupEstimate> {
+    let paths = snapshot_paths()?;
+    if paths.is_empty() {
+        return Ok(DedupEstimate::default());
+    }
+
+    let path_strings: Vec<String> = paths.iter().map(|p| p.to_string_lossy().to_string()).collect();
+    let mut args = vec!["filesystem", "du", "-s", "--raw"];
+    args.extend(path_strings.iter().map(String::as_str));
+
+    let (stdout, _stderr) = crate::run_command_with_output("btrfs", &args)?;
+    let (total_bytes, exclusive_bytes) = parse_filesystem_du(&stdout);
+
+    Ok(DedupEstimate {
+        snapshot_count: paths.len(),
+        total_bytes,
+        exclusive_bytes,
+        shared_bytes: total_bytes.saturating_sub(exclusive_bytes),
+    })
+}
+
+/// Sum the Total and Exclusive columns of `btrfs filesystem du -s --raw` output
+fn parse_filesystem_du(output: &str) -> (u64, u64) {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut columns = line.split_whitespace();
+            let total = columns.next()?.parse::<u64>().ok()?;
+            let exclusive = columns.next()?.parse::<u64>().ok()?;
+            Some((total, exclusive))
+        })
+        .fold((0, 0), |(total, exclusive), (t, e)| (total + t, exclusive + e))
+}
+
+/// Parse a duperemove progress line such as "[12/3400] (0.35%) csum: /path"
+/// or "[0x1234] (00012/00200) Try to dedupe extents with id 4f2a"
+fn parse_progress_line(line: &str) -> Option<DedupProgress> {
+    let stage = if line.contains("csum:") {
+        "scanning"
+    } else if line.contains("Try to dedupe") {
+        "deduplicating"
+    } else {
+        return None;
+    };
+
+    line.split(['[', ']', '(', ')']).find_map(|part| {
+        let (done, total) = part.trim().split_once('/')?;
+        Some(DedupProgress {
+            stage,
+            done: done.parse().ok()?,
+            total: total.parse().ok()?,
+        })
+    })
+}
+
+/// Available bytes on the filesystem holding `path`
+fn available_bytes(path: &Path) -> Result<u64> {
+    let stats = nix::sys::statvfs::statvfs(path).context("Failed to get filesystem statistics")?;
+    Ok(stats.blocks_available() * stats.fragment_size())
+}
+
+/// Deduplicate all snapshots and return the number of bytes reclaimed
+///
+/// The reclaimed amount is measured as the change in free space, after asking
+/// btrfs to commit, so it includes space freed by any concurrent activity.
+pub fn deduplicate(progress_tx: Option<&SyncSender<DedupProgress>>) -> Result<u64> {
+    if DEDUP_RUNNING.swap(true, Ordering::SeqCst) {
+        bail!("Deduplication is already running");
+    }
+    let result = deduplicate_locked(progress_tx);
+    DEDUP_RUNNING.store(false, Ordering::SeqCst);
+    result
+}
+
+fn deduplicate_locked(progress_tx: Option<&SyncSender<DedupProgress>>) -> Result<u64> {
+    let snapshot_dir = WaypointConfig::new().snapshot_dir;
+    if snapshot_paths()?.is_empty() {
+        bail!("No snapshots to deduplicate");
+    }
+
+    if Command::new("duperemove").arg("--version").recorded_output().is_err() {
+        bail!("duperemove is not installed. Install the duperemove package to deduplicate snapshots");
+    }
+
+    let free_before = available_bytes(&snapshot_dir)?;
+    let snapshot_dir_str = snapshot_dir.to_string_lossy().to_string();
+    let hashfile_arg = format!("--hashfile={HASHFILE_PATH}");
+
+    log::info!("Starting deduplication of {snapshot_dir_str}");
+    let mut child = crate::low_priority::command("duperemove")
+        .args(["-d", "-r", "-h", &hashfile_arg, &snapshot_dir_str])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .recorded_spawn()
+        .context("Failed to run duperemove")?;
+
+    // Drain stderr separately so a chatty duperemove can never block on a full pipe
+    let stderr_reader = child.stderr.take().map(|stderr| {
+        std::thread::spawn(move || {
+            let mut text = String::new();
+            let _ = std::io::Read::read_to_string(&mut BufReader::new(stderr), &mut text);
+            text
+        })
+    });
+
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if let (Some(tx), Some(progress)) = (progress_tx, parse_progress_line(&line)) {
+                // Drop updates rather than block when the consumer is slow
+                let _ = tx.try_send(progress);
+            }
+        }
+    }
+
+    let status = child.wait().context("Failed to wait for duperemove")?;
+    let stderr = stderr_reader
+        .and_then(|handle| handle.join().ok())
+        .unwrap_or_default();
+    let _ = std::fs::remove_file(HASHFILE_PATH);
+
+    if !status.success() {
+        bail!("duperemove failed: {}", stderr.trim());
+    }
+
+    // Freed extents only show up as free space once the transaction commits
+    crate::run_command("btrfs", &["filesystem", "sync", &snapshot_dir_str])?;
+    let free_after = available_bytes(&snapshot_dir)?;
+    let reclaimed = free_after.saturating_sub(free_before);
+
+    log::info!("Deduplication of {snapshot_dir_str} reclaimed {reclaimed} bytes");
+    Ok(reclaimed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_progress_line() {
+        assert_eq!(
+            parse_progress_line("[12/3400] (0.35%) csum: /.snapshots/a/root/usr/bin/ls"),
+            Some(DedupProgress { stage: "scanning", done: 12, total: 3400 })
+        );
+        assert_eq!(
+            parse_progress_line("[0x55d0c4a0] (00012/00200) Try to dedupe extents with id 4f2a"),
+            Some(DedupProgress { stage: "deduplicating", done: 12, total: 200 })
+        );
+        assert_eq!(parse_progress_line("Using 4 threads for file hashing phase"), None);
+    }
+
+    #[test]
+    fn test_parse_filesystem_du() {
+        let output = "     Total   Exclusive  Set shared  Filename\n\
+                      1000        200       800  /.snapshots/a\n\
+                      1500        300       900  /.snapshots/b\n";
+        assert_eq!(parse_filesystem_du(output), (2500, 500));
+    }
+}
@@ -0,0 +1,359 @@
+// Free space and btrfs allocation for waypoint-helper
+//
+// The GUI used to run `df`, which can't see btrfs chunk allocation and gives
+// misleading numbers on filesystems with RAID profiles or nearly full chunks.
+// `btrfs filesystem usage` needs root, so the helper reports both.
+
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use waypoint_common::{
+    BtrfsAllocation, ErrorCode, FilesystemStats, HelperError, SchedulesConfig, WaypointConfig,
+    format_bytes,
+};
+
+use crate::transcript::RecordedCommand;
+
+/// Free metadata below which a snapshot is likely to fail
+///
+/// Btrfs keeps part of the metadata space as a global reserve, so
+/// operations start failing before the chunks are completely used.
+const METADATA_MIN_FREE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Unallocated space needed to add a metadata chunk (DUP writes two 256 MiB copies)
+const METADATA_CHUNK_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Parse the byte value of a `btrfs filesystem usage -b` field
+///
+/// Values may be followed by extra details, e.g. `123456\t(min: 1000)`.
+fn parse_bytes(value: &str) -> Option<u64> {
+    value.split_whitespace().next()?.parse().ok()
+}
+
+/// Parse a chunk summary line such as `Data,single: Size:400000, Used:250000 (62.50%)`
+fn parse_chunk_line(line: &str) -> Option<(u64, u64)> {
+    let (_, rest) = line.split_once(": ")?;
+    let mut size = None;
+    let mut used = None;
+    for part in rest.split(", ") {
+        if let Some(value) = part.strip_prefix("Size:") {
+            size = parse_bytes(value);
+        } else if let Some(value) = part.strip_prefix("Used:") {
+            used = parse_bytes(value);
+        }
+    }
+    Some((size?, used?))
+}
+
+/// Parse `btrfs filesystem usage -b` output
+///
+/// Example (abridged):
+/// ```text
+/// Overall:
+///     Device size:                 1000000000
+///     Device allocated:             500000000
+///     Device unallocated:           500000000
+///     Free (estimated):             650000000      (min: 400000000)
+///
+/// Data,single: Size:400000000, Used:250000000 (62.50%)
+/// Metadata,DUP: Size:50000000, Used:25000000 (50.00%)
+/// ```
+fn parse_filesystem_usage(output: &str) -> Option<BtrfsAllocation> {
+    let mut allocation = BtrfsAllocation::default();
+    let mut found = false;
+
+    for line in output.lines() {
+        let line = line.trim();
+        if line.starts_with("Data,") {
+            if let Some((size, used)) = parse_chunk_line(line) {
+                allocation.data_total_bytes += size;
+                allocation.data_used_bytes += used;
+            }
+            continue;
+        }
+        if line.starts_with("Metadata,") {
+            if let Some((size, used)) = parse_chunk_line(line) {
+                allocation.metadata_total_bytes += size;
+                allocation.metadata_used_bytes += used;
+            }
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let field = match key.trim() {
+            "Device size" => &mut allocation.device_size_bytes,
+            "Device allocated" => &mut allocation.allocated_bytes,
+            "Device unallocated" => &mut allocation.unallocated_bytes,
+            "Free (estimated)" => &mut allocation.free_estimated_bytes,
+            _ => continue,
+        };
+        if let Some(bytes) = parse_bytes(value) {
+            *field = bytes;
+            found = true;
+        }
+    }
+
+    found.then_some(allocation)
+}
+
+/// Chunk allocation of the btrfs filesystem containing `path`
+fn btrfs_allocation(path: &Path) -> Option<BtrfsAllocation> {
+    let output = Command::new("btrfs")
+        .args(["filesystem", "usage", "-b"])
+        .arg(path)
+        .recorded_output()
+        .ok()?;
+
+    if !output.status.success() {
+        log::debug!(
+            "btrfs filesystem usage failed for {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+
+    parse_filesystem_usage(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Whether the metadata chunks are full and no new ones can be allocated
+fn metadata_exhausted(allocation: &BtrfsAllocation) -> bool {
+    allocation.metadata_total_bytes > 0
+        && allocation
+            .metadata_total_bytes
+            .saturating_sub(allocation.metadata_used_bytes)
+            < METADATA_MIN_FREE_BYTES
+        && allocation.unallocated_bytes < METADATA_CHUNK_BYTES
+}
+
+/// Refuse to snapshot when btrfs metadata space is exhausted
+///
+/// `btrfs subvolume snapshot` then fails with a bare "No space left on
+/// device" while `df` still shows free space. The fix is a balance, so the
+/// error says so. Filesystems whose usage can't be read are not checked.
+pub fn check_metadata_space(path: &Path) -> Result<()> {
+    let Some(allocation) = btrfs_allocation(path) else {
+        return Ok(());
+    };
+    if !metadata_exhausted(&allocation) {
+        return Ok(());
+    }
+
+    Err(HelperError::new(
+        ErrorCode::MetadataFull,
+        format!(
+            "Btrfs metadata space on {} is full ({} of {} used) and no unallocated space \
+             is left for more. Run a balance to free metadata space, e.g. \
+             `btrfs balance start -dusage=25 {}`, then try again.",
+            path.display(),
+            format_bytes(allocation.metadata_used_bytes),
+            format_bytes(allocation.metadata_total_bytes),
+            path.display()
+        ),
+    )
+    .into())
+}
+
+/// Whether the filesystem containing `path` is mounted read-only
+///
+/// Paths that don't exist yet are checked on their closest existing parent.
+pub fn is_mounted_read_only(path: &Path) -> bool {
+    let Some(existing) = path.ancestors().find(|p| p.exists()) else {
+        return false;
+    };
+    nix::sys::statvfs::statvfs(existing).is_ok_and(|stats| {
+        stats
+            .flags()
+            .contains(nix::sys::statvfs::FsFlags::ST_RDONLY)
+    })
+}
+
+/// Refuse to write to a filesystem that is mounted read-only
+///
+/// Btrfs remounts itself read-only when it detects errors, after which every
+/// write fails with a bare "Read-only file system". The error says what
+/// happened and that the filesystem has to be repaired first.
+pub fn check_writable(path: &Path) -> Result<()> {
+    if !is_mounted_read_only(path) {
+        return Ok(());
+    }
+
+    Err(HelperError::new(
+        ErrorCode::ReadOnlyFilesystem,
+        format!(
+            "The filesystem holding {} is mounted read-only. Btrfs does this when it \
+             detects errors; check `dmesg` for the cause. Snapshot operations are \
+             disabled until the filesystem is repaired and remounted read-write.",
+            path.display()
+        ),
+    )
+    .into())
+}
+
+/// Whether stats may be reported for `path`
+///
+/// Only a snapshot directory or one of its parents (the GUI asks for the
+/// closest existing one) and the subvolumes schedules snapshot are allowed.
+fn is_stats_path_allowed(path: &Path, snapshot_dirs: &[PathBuf], subvolumes: &[PathBuf]) -> bool {
+    snapshot_dirs.iter().any(|dir| dir.starts_with(path))
+        || subvolumes.iter().any(|subvolume| subvolume == path)
+}
+
+/// Refuse stats for paths outside the configured snapshot and subvolume mounts
+///
+/// The D-Bus method needs no authorization, so it must not run
+/// `btrfs filesystem usage` on arbitrary caller-supplied paths.
+fn ensure_stats_path_allowed(path: &Path) -> Result<()> {
+    let config = WaypointConfig::new();
+    let resolve = |path: &PathBuf| path.canonicalize().unwrap_or_else(|_| path.clone());
+    let snapshot_dirs: Vec<PathBuf> = config.snapshot_dirs().iter().map(resolve).collect();
+    let subvolumes: Vec<PathBuf> = SchedulesConfig::load_or_default(&config.schedules_config)
+        .schedules
+        .iter()
+        .flat_map(|schedule| schedule.subvolumes.iter().map(resolve))
+        .collect();
+
+    if is_stats_path_allowed(path, &snapshot_dirs, &subvolumes) {
+        return Ok(());
+    }
+
+    Err(HelperError::new(
+        ErrorCode::InvalidInput,
+        format!(
+            "{} is not a snapshot directory or a snapshotted subvolume",
+            path.display()
+        ),
+    )
+    .into())
+}
+
+/// Space on the filesystem containing `path`
+///
+/// `path` must be a snapshot directory, one of its parents or a subvolume that
+/// schedules snapshot. The btrfs allocation is left out for other filesystems.
+pub fn get_filesystem_stats(path: &str) -> Result<FilesystemStats> {
+    let path = Path::new(path);
+    if !path.is_absolute() {
+        bail!("Path must be absolute: {}", path.display());
+    }
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", path.display()))?;
+    ensure_stats_path_allowed(&path)?;
+
+    let stats = nix::sys::statvfs::statvfs(&path).context("Failed to get filesystem statistics")?;
+    let fragment_size = stats.fragment_size();
+
+    Ok(FilesystemStats {
+        total_bytes: stats.blocks() * fragment_size,
+        free_bytes: stats.blocks_free() * fragment_size,
+        available_bytes: stats.blocks_available() * fragment_size,
+        btrfs: btrfs_allocation(&path),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USAGE: &str = "\
+Overall:
+    Device size:\t\t\t  1000000000
+    Device allocated:\t\t   500000000
+    Device unallocated:\t\t   500000000
+    Device missing:\t\t\t           0
+    Used:\t\t\t\t   300000000
+    Free (estimated):\t\t   650000000\t(min: 400000000)
+    Data ratio:\t\t\t        1.00
+    Global reserve:\t\t     5000000\t(used: 0)
+
+Data,single: Size:400000000, Used:250000000 (62.50%)
+   /dev/sda2\t 400000000
+
+Metadata,DUP: Size:50000000, Used:25000000 (50.00%)
+   /dev/sda2\t 100000000
+
+System,DUP: Size:8388608, Used:16384 (0.20%)
+   /dev/sda2\t  16777216
+
+Unallocated:
+   /dev/sda2\t 500000000
+";
+
+    #[test]
+    fn test_parse_filesystem_usage() {
+        let allocation = parse_filesystem_usage(USAGE).unwrap();
+        assert_eq!(
+            allocation,
+            BtrfsAllocation {
+                device_size_bytes: 1_000_000_000,
+                allocated_bytes: 500_000_000,
+                unallocated_bytes: 500_000_000,
+                free_estimated_bytes: 650_000_000,
+                data_total_bytes: 400_000_000,
+                data_used_bytes: 250_000_000,
+                metadata_total_bytes: 50_000_000,
+                metadata_used_bytes: 25_000_000,
+            }
+        );
+
+        assert!(parse_filesystem_usage("ERROR: not a btrfs filesystem").is_none());
+    }
+
+    #[test]
+    fn test_metadata_exhausted() {
+        const GIB: u64 = 1024 * 1024 * 1024;
+        let mut allocation = BtrfsAllocation {
+            unallocated_bytes: 10 * GIB,
+            metadata_total_bytes: 2 * GIB,
+            metadata_used_bytes: GIB,
+            ..Default::default()
+        };
+        assert!(!metadata_exhausted(&allocation));
+
+        // Metadata chunks nearly full, but a new chunk can still be allocated
+        allocation.metadata_used_bytes = allocation.metadata_total_bytes - 1024;
+        assert!(!metadata_exhausted(&allocation));
+
+        allocation.unallocated_bytes = 0;
+        assert!(metadata_exhausted(&allocation));
+
+        assert!(!metadata_exhausted(&BtrfsAllocation::default()));
+    }
+
+    #[test]
+    fn test_check_writable_missing_path() {
+        // A snapshot directory that doesn't exist yet is checked on its parent
+        let path = std::env::temp_dir()
+            .join("waypoint-missing")
+            .join("snapshots");
+        assert!(!is_mounted_read_only(&path));
+        assert!(check_writable(&path).is_ok());
+    }
+
+    #[test]
+    fn test_is_stats_path_allowed() {
+        let snapshot_dirs = [
+            PathBuf::from("/.snapshots"),
+            PathBuf::from("/home/.snapshots"),
+        ];
+        let subvolumes = [PathBuf::from("/"), PathBuf::from("/var")];
+
+        for allowed in ["/.snapshots", "/home/.snapshots", "/home", "/", "/var"] {
+            assert!(is_stats_path_allowed(
+                Path::new(allowed),
+                &snapshot_dirs,
+                &subvolumes
+            ));
+        }
+        for refused in ["/etc", "/.snapshots/foo", "/var/lib", "/home/user"] {
+            assert!(!is_stats_path_allowed(
+                Path::new(refused),
+                &snapshot_dirs,
+                &subvolumes
+            ));
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// Post-restore hook for waypoint-helper
+//
+// After a rollback is scheduled, administrators may want to update the
+// bootloader, log the event or notify a monitoring system. If the hook
+// executable exists it is run with the restore details in its environment.
+// The restore has already happened by then, so a failing hook only warns.
+
+use anyhow::{Context, Result, bail};
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+use waypoint_common::WaypointConfig;
+
+/// How long the hook may run before it is stopped
+const HOOK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Refuse hooks that someone other than root could have changed
+///
+/// The helper runs as root, so a hook writable by other users would let
+/// them run anything as root by triggering a restore.
+fn check_hook_permissions(path: &Path) -> Result<()> {
+    let metadata =
+        std::fs::metadata(path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+    if !metadata.is_file() {
+        bail!("{} is not a regular file", path.display());
+    }
+    if metadata.uid() != 0 {
+        bail!("{} is not owned by root", path.display());
+    }
+    let mode = metadata.permissions().mode();
+    if mode & 0o022 != 0 {
+        bail!("{} is writable by group or others", path.display());
+    }
+    if mode & 0o100 == 0 {
+        bail!("{} is not executable", path.display());
+    }
+    Ok(())
+}
+
+/// Run the post-restore hook, if one is installed
+///
+/// The hook gets `WAYPOINT_HOOK=post-restore`, the restored snapshot in
+/// `WAYPOINT_SNAPSHOT` and the pre-rollback backup in
+/// `WAYPOINT_PRE_ROLLBACK_BACKUP`.
+pub fn run_post_restore_hook(snapshot_name: &str, backup_name: &str) {
+    let hook = WaypointConfig::new().post_restore_hook;
+    if !hook.exists() {
+        return;
+    }
+
+    if let Err(e) = check_hook_permissions(&hook) {
+        log::warn!("Not running post-restore hook: {e}");
+        return;
+    }
+
+    log::info!("Running post-restore hook {}", hook.display());
+    let mut command = Command::new(&hook);
+    command
+        .env("WAYPOINT_HOOK", "post-restore")
+        .env("WAYPOINT_SNAPSHOT", snapshot_name)
+        .env("WAYPOINT_PRE_ROLLBACK_BACKUP", backup_name);
+
+    match crate::output_with_timeout(&mut command, HOOK_TIMEOUT, None) {
+        Ok(output) if output.status.success() => {
+            log::info!("Post-restore hook finished");
+        }
+        Ok(output) => {
+            log::warn!(
+                "Post-restore hook failed ({}): {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Err(e) => log::warn!("Post-restore hook failed: {e}"),
+    }
+}
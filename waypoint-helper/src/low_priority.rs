@@ -0,0 +1,61 @@
+//! Low-priority commands for background housekeeping
+//!
+//! Size calculations, backup verification and deduplication read every file
+//! in a snapshot, which can saturate the disk and make the desktop stutter,
+//! especially on spinning disks. Commands created with `command` run in the
+//! idle I/O scheduling class (like `ionice -c3`) and at the lowest CPU
+//! priority (like `nice -n 19`), so they only use the disk when nothing else
+//! needs it. Set `WAYPOINT_LOW_PRIORITY_BACKGROUND=false` to run them at
+//! normal priority.
+
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+use waypoint_common::WaypointConfig;
+
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+const IOPRIO_CLASS_IDLE: libc::c_int = 3;
+const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+
+/// Niceness of background commands (the lowest priority)
+const BACKGROUND_NICENESS: libc::c_int = 19;
+
+/// Create a command that runs at low I/O and CPU priority when enabled
+pub fn command(program: &str) -> Command {
+    let mut cmd = Command::new(program);
+    if WaypointConfig::new().low_priority_background {
+        // SAFETY: the closure only makes async-signal-safe system calls
+        unsafe {
+            cmd.pre_exec(lower_priority);
+        }
+    }
+    cmd
+}
+
+/// Lower the priority of the current (forked) process
+///
+/// Failures are ignored: a command at normal priority is better than none.
+fn lower_priority() -> std::io::Result<()> {
+    // SAFETY: plain system calls on the calling process (pid 0)
+    unsafe {
+        libc::syscall(
+            libc::SYS_ioprio_set,
+            IOPRIO_WHO_PROCESS,
+            0,
+            IOPRIO_CLASS_IDLE << IOPRIO_CLASS_SHIFT,
+        );
+        libc::setpriority(libc::PRIO_PROCESS, 0, BACKGROUND_NICENESS);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_runs_at_low_priority() {
+        let output = command("sh").args(["-c", "nice"]).output().unwrap();
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "19");
+    }
+}
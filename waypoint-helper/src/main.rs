@@ -6,19 +6,42 @@ use serde::Deserialize;
 #[cfg(unix)]
 use std::os::unix::fs::symlink;
 use std::process::Command;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use tokio::signal::unix::{SignalKind, signal};
+use transcript::RecordedCommand;
 use waypoint_common::*;
 use zbus::{Connection, ConnectionBuilder, interface};
 
 mod audit;
 mod backup;
+mod backup_image;
+mod balance;
 mod btrfs;
+mod btrfs_root;
+mod compression;
+mod dedup;
+mod filesystem_stats;
+mod hooks;
+mod low_priority;
+mod metadata_check;
+mod overlay;
 mod packages;
+mod permissions;
+mod shutdown;
+mod snapshot_lock;
+mod socket_api;
+mod transcript;
 
 /// Global counter for mutex poisoning events (for monitoring)
 static MUTEX_POISON_COUNT: AtomicUsize = AtomicUsize::new(0);
 
+/// Polkit checks waiting on the user, as cancellation ID and the PID they are for
+static PENDING_AUTHORIZATIONS: std::sync::Mutex<Vec<(String, u32)>> =
+    std::sync::Mutex::new(Vec::new());
+
+/// Source of unique Polkit cancellation IDs
+static NEXT_AUTHORIZATION_ID: AtomicU64 = AtomicU64::new(1);
+
 /// Simple rate limiter to prevent DoS via expensive operations
 /// Implements a per-user, per-operation cooldown period
 #[derive(Debug, Clone)]
@@ -82,16 +105,44 @@ fn scheduler_service_path() -> String {
 /// Main D-Bus service interface for Waypoint operations
 struct WaypointHelper {
     rate_limiter: RateLimiter,
+    /// Cancellation flags of running comparisons, keyed by "uid:old:new"
+    compare_cancellations: std::sync::Arc<std::sync::Mutex<CancellationMap>>,
+    /// Cancellation flags of running snapshot creations, keyed by "uid:name"
+    create_cancellations: std::sync::Arc<std::sync::Mutex<CancellationMap>>,
 }
 
+/// Cancellation flags per key; the same comparison may be running more than once
+type CancellationMap =
+    std::collections::HashMap<String, Vec<std::sync::Arc<std::sync::atomic::AtomicBool>>>;
+
 impl WaypointHelper {
     fn new() -> Self {
         Self {
             // Rate limit: 1 operation per 5 seconds per user
             rate_limiter: RateLimiter::new(5),
+            compare_cancellations: Default::default(),
+            create_cancellations: Default::default(),
         }
     }
 
+    /// Lock the comparison cancellation map, recovering from poisoning
+    fn compare_cancellations(&self) -> std::sync::MutexGuard<'_, CancellationMap> {
+        self.compare_cancellations.lock().unwrap_or_else(|poisoned| {
+            MUTEX_POISON_COUNT.fetch_add(1, Ordering::Relaxed);
+            log::error!("Comparison cancellation mutex poisoned, recovering");
+            poisoned.into_inner()
+        })
+    }
+
+    /// Lock the snapshot creation cancellation map, recovering from poisoning
+    fn create_cancellations(&self) -> std::sync::MutexGuard<'_, CancellationMap> {
+        self.create_cancellations.lock().unwrap_or_else(|poisoned| {
+            MUTEX_POISON_COUNT.fetch_add(1, Ordering::Relaxed);
+            log::error!("Snapshot creation cancellation mutex poisoned, recovering");
+            poisoned.into_inner()
+        })
+    }
+
     /// Get caller's user ID from D-Bus header
     async fn get_caller_uid(hdr: &zbus::message::Header<'_>, connection: &Connection) -> Result<String> {
         let caller = hdr
@@ -158,6 +209,13 @@ impl WaypointHelper {
         created_by: &str,
     ) -> zbus::Result<()>;
 
+    /// Signal emitted when quota auto-cleanup deleted snapshots
+    #[zbus(signal)]
+    async fn quota_cleanup(
+        ctxt: &zbus::SignalContext<'_>,
+        deleted_snapshots: &[String],
+    ) -> zbus::Result<()>;
+
     /// Signal emitted during backup operations to report progress
     #[zbus(signal)]
     async fn backup_progress(
@@ -167,9 +225,45 @@ impl WaypointHelper {
         bytes_transferred: u64,
         total_bytes: u64,
         speed_bytes_per_sec: u64,
-        stage: &str, // "preparing", "transferring", "verifying", "complete"
+        stage: &str, // "preparing", "transferring", "verifying", "complete", "failed"
+    ) -> zbus::Result<()>;
+
+    /// Signal emitted while comparing snapshots so clients can show activity
+    #[zbus(signal)]
+    async fn compare_progress(
+        ctxt: &zbus::SignalContext<'_>,
+        old_snapshot: &str,
+        new_snapshot: &str,
+        entries_scanned: u64,
+        changes_found: u64,
+        stage: &str, // "scanning", "comparing", "complete", "cancelled", "failed"
+    ) -> zbus::Result<()>;
+
+    /// Signal emitted while deduplicating snapshots
+    #[zbus(signal)]
+    async fn dedup_progress(
+        ctxt: &zbus::SignalContext<'_>,
+        done: u64,
+        total: u64,
+        stage: &str, // "scanning", "deduplicating", "complete", "failed"
+    ) -> zbus::Result<()>;
+
+    /// Signal emitted while a balance started with `StartBalance` runs
+    #[zbus(signal)]
+    async fn balance_progress(
+        ctxt: &zbus::SignalContext<'_>,
+        balanced: u64,
+        total: u64,
+        stage: &str, // "running", "complete", "cancelled", "failed"
     ) -> zbus::Result<()>;
 
+    /// Report the interface version and optional features as JSON
+    ///
+    /// Read-only and unauthenticated, so clients can negotiate before calling anything else.
+    async fn get_capabilities(&self) -> String {
+        serde_json::to_string(&HelperCapabilities::current()).unwrap_or_else(|_| "{}".to_string())
+    }
+
     /// Create a new snapshot
     async fn create_snapshot(
         &self,
@@ -186,53 +280,84 @@ impl WaypointHelper {
         // Check authorization
         if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CREATE).await {
             audit::log_auth_failure(uid, pid, "create_snapshot", &e.to_string());
-            return (false, format!("Authorization failed: {e}"));
+            return (false, auth_failure_message(&e));
         }
 
-        // Rate limiting check
-        if let Err(wait_time) = self.rate_limiter.check_rate_limit(&uid, "create_snapshot") {
-            log::warn!("Rate limit exceeded for user {uid} creating snapshot");
-            audit::log_snapshot_create(uid, pid, &name, false, Some("rate limit exceeded"));
-            return (
+        // Try to determine who created the snapshot
+        let created_by = if hdr
+            .sender()
+            .map(|s| s.as_str())
+            .unwrap_or("")
+            .contains("waypoint-scheduler")
+        {
+            "scheduler"
+        } else {
+            "gui"
+        };
+
+        self.create_snapshot_as(&ctxt, uid, pid, created_by, name, description, subvolumes)
+            .await
+    }
+
+    /// Cancel a snapshot creation started by the same user
+    ///
+    /// The running command is killed and any subvolumes created so far are removed.
+    async fn cancel_create(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        name: String,
+    ) -> (bool, String) {
+        let (uid, _pid) = Self::get_caller_info(&hdr, connection).await;
+        let key = format!("{uid}:{name}");
+
+        match self.create_cancellations().get(&key) {
+            Some(flags) => {
+                for flag in flags {
+                    flag.store(true, Ordering::Relaxed);
+                }
+                (true, "Snapshot creation cancelled".to_string())
+            }
+            None => (
                 false,
-                format!(
-                    "Rate limit exceeded. Please wait {} seconds before creating another snapshot",
-                    wait_time.as_secs()
-                ),
-            );
+                HelperError::new(
+                    ErrorCode::NotFound,
+                    format!("No snapshot named '{name}' is being created"),
+                )
+                .encode(),
+            ),
         }
+    }
 
-        // Create the snapshot
-        match Self::create_snapshot_impl(&name, &description, subvolumes) {
-            Ok(msg) => {
-                // Audit log successful creation
-                audit::log_snapshot_create(uid.clone(), pid, &name, true, None);
-                // Emit signal for successful snapshot creation
-                // Try to determine who created the snapshot
-                let created_by = if hdr
-                    .sender()
-                    .map(|s| s.as_str())
-                    .unwrap_or("")
-                    .contains("waypoint-scheduler")
-                {
-                    "scheduler"
-                } else {
-                    "gui"
-                };
+    /// Cancel the caller's authorization checks that are waiting on a password
+    ///
+    /// Dismisses the Polkit prompts, and the calls that triggered them fail
+    /// with `cancelled`. Only checks for the calling process are affected, so
+    /// this does not require authorization.
+    async fn cancel_authorization(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+    ) -> (bool, String) {
+        let (_uid, pid) = Self::get_caller_info(&hdr, connection).await;
+        let pending: Vec<String> = lock_pending_authorizations()
+            .iter()
+            .filter(|(_, caller_pid)| pid != 0 && *caller_pid == pid)
+            .map(|(id, _)| id.clone())
+            .collect();
 
-                if let Err(e) = Self::snapshot_created(&ctxt, &name, created_by).await {
-                    log::error!("Failed to emit snapshot_created signal: {e}");
-                }
+        if pending.is_empty() {
+            return (
+                false,
+                HelperError::new(ErrorCode::NotFound, "No authorization is pending").encode(),
+            );
+        }
 
-                (true, msg)
-            }
-            Err(e) => {
-                // Audit log failed creation
-                let error_msg = e.to_string();
-                audit::log_snapshot_create(uid, pid, &name, false, Some(&error_msg));
-                (false, format!("Failed to create snapshot: {}", sanitize_error_for_client(&e)))
-            }
+        for id in &pending {
+            cancel_polkit_check(connection, id).await;
         }
+        let message = format!("Cancelled {} authorization request(s)", pending.len());
+        (true, message)
     }
 
     /// Delete a snapshot
@@ -248,540 +373,2103 @@ impl WaypointHelper {
         // Check authorization
         if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_DELETE).await {
             audit::log_auth_failure(uid, pid, POLKIT_ACTION_DELETE, &e.to_string());
-            return (false, format!("Authorization failed: {e}"));
+            return (false, auth_failure_message(&e));
         }
 
-        // Delete the snapshot
-        match btrfs::delete_snapshot(&name) {
-            Ok(_) => {
-                audit::log_snapshot_delete(uid, pid, &name, true, None);
-                (true, format!("Snapshot '{name}' deleted successfully"))
-            }
-            Err(e) => {
-                let error_msg = e.to_string();
-                audit::log_snapshot_delete(uid, pid, &name, false, Some(&error_msg));
-                (false, format!("Failed to delete snapshot: {e}"))
-            }
-        }
+        self.delete_snapshot_as(uid, pid, name).await
     }
 
-    /// Restore a snapshot (rollback system)
-    async fn restore_snapshot(
+    /// Clone a snapshot under a new name
+    ///
+    /// Creates read-only (or writable) copies of every subvolume in the source
+    /// snapshot and copies its metadata, leaving the original untouched.
+    async fn clone_snapshot(
         &self,
         #[zbus(header)] hdr: zbus::message::Header<'_>,
         #[zbus(connection)] connection: &Connection,
-        name: String,
+        source: String,
+        new_name: String,
+        writable: bool,
     ) -> (bool, String) {
         // Get caller info for audit logging
         let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
 
         // Check authorization
-        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_RESTORE).await {
-            audit::log_auth_failure(uid, pid, POLKIT_ACTION_RESTORE, &e.to_string());
-            return (false, format!("Authorization failed: {e}"));
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CREATE).await {
+            audit::log_auth_failure(uid, pid, "clone_snapshot", &e.to_string());
+            return (false, auth_failure_message(&e));
         }
 
-        // Perform rollback
-        match Self::restore_snapshot_impl(&name) {
-            Ok(_) => {
-                audit::log_snapshot_restore(uid, pid, &name, true, None);
-                (true, format!("Snapshot '{name}' restored successfully. Reboot to apply changes."))
-            }
-            Err(e) => {
-                let error_msg = e.to_string();
-                audit::log_snapshot_restore(uid, pid, &name, false, Some(&error_msg));
-                (false, format!("Failed to restore snapshot: {e}"))
-            }
+        // Rate limiting check (shares the create budget since it produces a new snapshot)
+        if let Err(wait_time) = self.rate_limiter.check_rate_limit(&uid, "create_snapshot") {
+            log::warn!("Rate limit exceeded for user {uid} cloning snapshot");
+            audit::log_snapshot_create(uid, pid, &new_name, false, Some("rate limit exceeded"));
+            return (
+                false,
+                HelperError::new(
+                    ErrorCode::RateLimited,
+                    format!(
+                        "Rate limit exceeded. Please wait {} seconds before creating another snapshot",
+                        wait_time.as_secs()
+                    ),
+                )
+                .encode(),
+            );
         }
-    }
 
-    /// List all snapshots
-    async fn list_snapshots(&self) -> String {
-        // Listing doesn't require authorization (read-only)
-        match btrfs::list_snapshots() {
-            Ok(snapshots) => {
-                let snapshot_infos: Vec<SnapshotInfo> =
-                    snapshots.into_iter().map(|s| s.into()).collect();
+        let _operation = match shutdown::begin("snapshot clone", None) {
+            Ok(operation) => operation,
+            Err(e) => {
+                audit::log_snapshot_create(uid, pid, &new_name, false, Some(&e.to_string()));
+                return (false, error_message(&e, ""));
+            }
+        };
 
-                serde_json::to_string(&snapshot_infos).unwrap_or_else(|_| "[]".to_string())
+        match btrfs::clone_snapshot(&source, &new_name, writable) {
+            Ok(_) => {
+                audit::log_snapshot_create(uid, pid, &new_name, true, None);
+                (true, format!("Snapshot '{source}' cloned to '{new_name}'"))
             }
             Err(e) => {
-                log::error!("Failed to list snapshots: {e}");
-                "[]".to_string()
+                let error_msg = e.to_string();
+                audit::log_snapshot_create(uid, pid, &new_name, false, Some(&error_msg));
+                (false, error_message(&e, "Failed to clone snapshot"))
             }
         }
     }
 
-    /// Get sizes for multiple snapshots
-    /// Returns JSON object mapping snapshot names to sizes in bytes
-    /// This method runs with privileges, so it can access snapshot directories
-    async fn get_snapshot_sizes(&self, snapshot_names: Vec<String>) -> String {
-        // Getting sizes is read-only, no authorization needed
-        match btrfs::get_snapshot_sizes(snapshot_names) {
-            Ok(sizes) => serde_json::to_string(&sizes).unwrap_or_else(|_| "{}".to_string()),
-            Err(e) => {
-                log::error!("Failed to get snapshot sizes: {e}");
-                "{}".to_string()
-            }
-        }
+    /// Read-only state of snapshots
+    ///
+    /// Returns a JSON object mapping snapshot names to `true` when every
+    /// subvolume is read-only. Snapshots that can't be checked are left out.
+    /// This is a read-only operation and does not require authorization.
+    async fn get_snapshot_read_only(&self, snapshot_names: Vec<String>) -> String {
+        let states = tokio::task::spawn_blocking(move || btrfs::get_read_only_states(&snapshot_names))
+            .await
+            .unwrap_or_default();
+        serde_json::to_string(&states).unwrap_or_else(|_| "{}".to_string())
     }
 
-    /// Verify snapshot integrity
-    async fn verify_snapshot(&self, name: String) -> String {
-        // Verification is read-only, no authorization needed
-        match btrfs::verify_snapshot(&name) {
-            Ok(result) => serde_json::to_string(&result).unwrap_or_else(|_| {
-                r#"{"is_valid":false,"errors":["Failed to serialize result"],"warnings":[]}"#
-                    .to_string()
-            }),
-            Err(e) => {
-                log::error!("Failed to verify snapshot: {e}");
-                serde_json::to_string(&btrfs::VerificationResult {
-                    is_valid: false,
-                    errors: vec![format!("Verification failed: {}", e)],
-                    warnings: vec![],
-                })
-                .unwrap_or_else(|_| {
-                    r#"{"is_valid":false,"errors":["Failed to verify"],"warnings":[]}"#.to_string()
-                })
-            }
-        }
+    /// Get the Btrfs generation of several snapshots
+    ///
+    /// Returns a JSON object mapping snapshot names to generations. The
+    /// generation of a read-only snapshot never changes, so clients can cache
+    /// comparisons by it. This is a read-only operation and does not require
+    /// authorization.
+    async fn get_snapshot_generations(&self, snapshot_names: Vec<String>) -> String {
+        let generations =
+            tokio::task::spawn_blocking(move || btrfs::get_snapshot_generations(&snapshot_names))
+                .await
+                .unwrap_or_default();
+        serde_json::to_string(&generations).unwrap_or_else(|_| "{}".to_string())
     }
 
-    /// Preview what will happen if a snapshot is restored
-    async fn preview_restore(
+    /// Make a snapshot read-only or writable
+    ///
+    /// Backups need read-only snapshots, so writable ones are only meant as
+    /// temporary scratch copies.
+    async fn set_snapshot_read_only(
         &self,
         #[zbus(header)] hdr: zbus::message::Header<'_>,
         #[zbus(connection)] connection: &Connection,
         name: String,
+        read_only: bool,
     ) -> (bool, String) {
-        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_RESTORE).await {
-            return (false, format!("Authorization failed: {e}"));
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
+            return (false, auth_failure_message(&e));
         }
 
-        match btrfs::preview_restore(&name) {
-            Ok(result) => match serde_json::to_string(&result) {
-                Ok(json) => (true, json),
-                Err(e) => (false, format!("Failed to serialize preview: {e}")),
-            },
+        let name_clone = name.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let _lock = snapshot_lock::try_lock(&name_clone, "change read-only state")?;
+            btrfs::set_snapshot_read_only(&name_clone, read_only)
+        })
+        .await
+        .unwrap_or_else(|e| Err(anyhow::anyhow!("Read-only task failed: {e}")));
+
+        match result {
+            Ok(()) => {
+                audit::log_read_only_change(uid, pid, &name, read_only, true, None);
+                let state = if read_only { "read-only" } else { "writable" };
+                (true, format!("Snapshot '{name}' is now {state}"))
+            }
             Err(e) => {
-                log::error!("Failed to preview restore: {e}");
-                (false, format!("Failed to preview restore: {e}"))
+                audit::log_read_only_change(uid, pid, &name, read_only, false, Some(&e.to_string()));
+                (false, error_message(&e, "Failed to change read-only state"))
             }
         }
     }
 
-    /// Save schedules TOML configuration file
-    async fn save_schedules_config(
+    /// Put a snapshot under a schedule's retention policy
+    ///
+    /// `group` must be the prefix of a configured schedule. An empty group
+    /// clears the assignment so retention goes by the name prefix again.
+    async fn set_retention_group(
         &self,
         #[zbus(header)] hdr: zbus::message::Header<'_>,
         #[zbus(connection)] connection: &Connection,
-        toml_content: String,
+        name: String,
+        group: String,
     ) -> (bool, String) {
-        // Get caller info for audit logging
         let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
 
-        // Check authorization
         if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
-            audit::log_auth_failure(uid.clone(), pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
-            return (false, format!("Authorization failed: {e}"));
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
+            return (false, auth_failure_message(&e));
         }
 
-        // Validate TOML by parsing it first
-        use waypoint_common::schedules::SchedulesConfig;
-        match toml::from_str::<SchedulesConfig>(&toml_content) {
-            Ok(_) => {
-                // TOML is valid, proceed to save
+        let name_clone = name.clone();
+        let group_clone = group.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let group = (!group_clone.is_empty()).then_some(group_clone.as_str());
+            if let Some(group) = group {
+                let schedules = SchedulesConfig::load_from_file(&WaypointConfig::new().schedules_config)
+                    .context("Failed to load schedules configuration")?;
+                if !schedules.schedules.iter().any(|s| s.prefix == group) {
+                    return Err(HelperError::new(
+                        ErrorCode::InvalidInput,
+                        format!("No schedule uses the prefix '{group}'"),
+                    )
+                    .into());
+                }
+            }
+            let _lock = snapshot_lock::try_lock(&name_clone, "change retention group")?;
+            btrfs::set_retention_group(&name_clone, group)
+        })
+        .await
+        .unwrap_or_else(|e| Err(anyhow::anyhow!("Retention group task failed: {e}")));
+
+        match result {
+            Ok(()) => {
+                audit::log_retention_group_change(uid, pid, &name, &group, true, None);
+                let policy = if group.is_empty() {
+                    "its name prefix".to_string()
+                } else {
+                    format!("the '{group}' schedule")
+                };
+                (true, format!("Retention of '{name}' now follows {policy}"))
             }
             Err(e) => {
-                let error_msg = e.to_string();
-                audit::log_config_change(uid, pid, "schedules", false, Some(&error_msg));
-                return (false, format!("Invalid TOML configuration: {e}"));
+                let error = e.to_string();
+                audit::log_retention_group_change(uid, pid, &name, &group, false, Some(&error));
+                (false, error_message(&e, "Failed to change retention group"))
             }
         }
+    }
 
-        let config = WaypointConfig::new();
-        let schedules_path = config.schedules_config;
+    /// Set the date after which cleanup deletes a snapshot
+    ///
+    /// `expires_at` is an RFC 3339 timestamp; an empty string clears the
+    /// expiry. Expired snapshots are deleted by the next cleanup whatever their
+    /// schedule's retention says, so this needs the delete permission.
+    async fn set_snapshot_expiry(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        name: String,
+        expires_at: String,
+    ) -> (bool, String) {
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
 
-        // Create parent directory if it doesn't exist
-        if let Some(parent) = schedules_path.parent() {
-            if let Err(e) = std::fs::create_dir_all(parent) {
-                let error_msg = e.to_string();
-                audit::log_config_change(uid, pid, "schedules", false, Some(&error_msg));
-                return (false, format!("Failed to create config directory: {e}"));
-            }
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_DELETE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_DELETE, &e.to_string());
+            return (false, auth_failure_message(&e));
         }
 
-        // Write configuration file
-        match std::fs::write(&schedules_path, toml_content) {
-            Ok(_) => {
-                audit::log_config_change(uid, pid, "schedules", true, None);
-                (true, "Schedules configuration saved".to_string())
+        let name_clone = name.clone();
+        let expires_clone = expires_at.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let expiry = if expires_clone.is_empty() {
+                None
+            } else {
+                let at = chrono::DateTime::parse_from_rfc3339(&expires_clone)
+                    .map_err(|e| {
+                        HelperError::new(
+                            ErrorCode::InvalidInput,
+                            format!("Invalid expiry date '{expires_clone}': {e}"),
+                        )
+                    })?
+                    .with_timezone(&chrono::Utc);
+                if at <= chrono::Utc::now() {
+                    return Err(HelperError::new(
+                        ErrorCode::InvalidInput,
+                        "The expiry date is in the past",
+                    )
+                    .into());
+                }
+                Some(at)
+            };
+            let _lock = snapshot_lock::try_lock(&name_clone, "change expiry")?;
+            btrfs::set_snapshot_expiry(&name_clone, expiry)
+        })
+        .await
+        .unwrap_or_else(|e| Err(anyhow::anyhow!("Expiry task failed: {e}")));
+
+        match result {
+            Ok(()) => {
+                audit::log_expiry_change(uid, pid, &name, &expires_at, true, None);
+                if expires_at.is_empty() {
+                    (true, format!("Snapshot '{name}' no longer expires"))
+                } else {
+                    (true, format!("Snapshot '{name}' expires at {expires_at}"))
+                }
             }
             Err(e) => {
-                let error_msg = e.to_string();
-                audit::log_config_change(uid, pid, "schedules", false, Some(&error_msg));
-                (false, format!("Failed to save configuration: {e}"))
+                let error = e.to_string();
+                audit::log_expiry_change(uid, pid, &name, &expires_at, false, Some(&error));
+                (false, error_message(&e, "Failed to change expiry"))
             }
         }
     }
 
-    /// Restart scheduler service
-    async fn restart_scheduler(
+    /// Link a snapshot to the package manager transaction it was taken around
+    ///
+    /// `phase` is `pre` or `post`. An empty `transaction_id` lets Waypoint
+    /// number the transaction; a post snapshot then shares the ID of
+    /// `pre_snapshot`, which is linked back to it. Called by the package
+    /// manager hooks right after creating the snapshot, so it needs the same
+    /// permission.
+    async fn link_transaction(
         &self,
         #[zbus(header)] hdr: zbus::message::Header<'_>,
         #[zbus(connection)] connection: &Connection,
+        name: String,
+        phase: String,
+        transaction_id: String,
+        pre_snapshot: String,
     ) -> (bool, String) {
-        // Check authorization
-        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
-            return (false, format!("Authorization failed: {e}"));
-        }
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
 
-        run_command("sv", &["restart", "waypoint-scheduler"])
-            .map(|_| (true, "Scheduler service restarted".to_string()))
-            .unwrap_or_else(|e| (false, format!("Failed to restart scheduler service: {e}")))
-    }
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CREATE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CREATE, &e.to_string());
+            return (false, auth_failure_message(&e));
+        }
 
-    /// Get scheduler service status
+        let details = format!(
+            "phase={phase:?}, transaction_id={transaction_id:?}, pre_snapshot={pre_snapshot:?}"
+        );
+        let name_clone = name.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let phase = match phase.as_str() {
+                "pre" => TransactionPhase::Pre,
+                "post" => TransactionPhase::Post,
+                other => {
+                    return Err(HelperError::new(
+                        ErrorCode::InvalidInput,
+                        format!("Invalid transaction phase '{other}', expected 'pre' or 'post'"),
+                    )
+                    .into());
+                }
+            };
+            let _lock = snapshot_lock::try_lock(&name_clone, "link transaction")?;
+            btrfs::link_transaction(
+                &name_clone,
+                phase,
+                (!transaction_id.is_empty()).then_some(transaction_id.as_str()),
+                (!pre_snapshot.is_empty()).then_some(pre_snapshot.as_str()),
+            )
+        })
+        .await
+        .unwrap_or_else(|e| Err(anyhow::anyhow!("Transaction link task failed: {e}")));
+
+        match result {
+            Ok(link) => {
+                audit::log_transaction_link(uid, pid, &name, &details, true, None);
+                (true, link.id)
+            }
+            Err(e) => {
+                let error = e.to_string();
+                audit::log_transaction_link(uid, pid, &name, &details, false, Some(&error));
+                (false, error_message(&e, "Failed to link transaction"))
+            }
+        }
+    }
+
+    /// Set why a snapshot was taken
+    ///
+    /// `category` is `manual`, `pre-update`, `pre-config-change` or
+    /// `milestone`; cleanup never deletes milestones. Set right after creating
+    /// the snapshot, so it needs the same permission.
+    async fn set_snapshot_category(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        name: String,
+        category: String,
+    ) -> (bool, String) {
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CREATE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CREATE, &e.to_string());
+            return (false, auth_failure_message(&e));
+        }
+
+        let name_clone = name.clone();
+        let category_clone = category.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let category = SnapshotCategory::parse(&category_clone).ok_or_else(|| {
+                HelperError::new(
+                    ErrorCode::InvalidInput,
+                    format!("Unknown snapshot category '{category_clone}'"),
+                )
+            })?;
+            let _lock = snapshot_lock::try_lock(&name_clone, "change category")?;
+            btrfs::set_snapshot_category(&name_clone, category)
+        })
+        .await
+        .unwrap_or_else(|e| Err(anyhow::anyhow!("Category task failed: {e}")));
+
+        match result {
+            Ok(()) => {
+                audit::log_category_change(uid, pid, &name, &category, true, None);
+                (true, format!("Category of '{name}' set to {category}"))
+            }
+            Err(e) => {
+                let error = e.to_string();
+                audit::log_category_change(uid, pid, &name, &category, false, Some(&error));
+                (false, error_message(&e, "Failed to change category"))
+            }
+        }
+    }
+
+    /// Pin or unpin a snapshot
+    ///
+    /// Neither `CleanupSnapshots` nor quota cleanup deletes pinned snapshots.
+    async fn set_snapshot_pinned(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        name: String,
+        pinned: bool,
+    ) -> (bool, String) {
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CREATE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CREATE, &e.to_string());
+            return (false, auth_failure_message(&e));
+        }
+
+        let name_clone = name.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let _lock = snapshot_lock::try_lock(&name_clone, "change pin")?;
+            btrfs::set_snapshot_pinned(&name_clone, pinned)
+        })
+        .await
+        .unwrap_or_else(|e| Err(anyhow::anyhow!("Pin task failed: {e}")));
+
+        match result {
+            Ok(()) => {
+                audit::log_pin_change(uid, pid, &name, pinned, true, None);
+                let state = if pinned { "pinned" } else { "unpinned" };
+                (true, format!("Snapshot '{name}' {state}"))
+            }
+            Err(e) => {
+                let error = e.to_string();
+                audit::log_pin_change(uid, pid, &name, pinned, false, Some(&error));
+                (false, error_message(&e, "Failed to change pin"))
+            }
+        }
+    }
+
+    /// Restore a snapshot (rollback system)
+    async fn restore_snapshot(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        name: String,
+    ) -> (bool, String) {
+        // Get caller info for audit logging
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        // Check authorization
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_RESTORE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_RESTORE, &e.to_string());
+            return (false, auth_failure_message(&e));
+        }
+
+        self.restore_snapshot_as(uid, pid, name).await
+    }
+
+    /// Restore one subvolume of a snapshot to a new writable subvolume
+    ///
+    /// Non-destructive alternative to a rollback: the running system and the
+    /// default subvolume are left untouched. Returns the path of the new subvolume.
+    async fn restore_to_subvolume(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        name: String,
+        subvolume: String,
+        target_path: String,
+    ) -> (bool, String) {
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_RESTORE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_RESTORE, &e.to_string());
+            return (false, auth_failure_message(&e));
+        }
+
+        let _operation = match shutdown::begin("subvolume restore", None) {
+            Ok(operation) => operation,
+            Err(e) => {
+                audit::log_subvolume_restore(
+                    uid,
+                    pid,
+                    &name,
+                    &target_path,
+                    false,
+                    Some(&e.to_string()),
+                );
+                return (false, error_message(&e, ""));
+            }
+        };
+
+        let _lock = match snapshot_lock::try_lock(&name, "restore") {
+            Ok(lock) => lock,
+            Err(e) => {
+                audit::log_subvolume_restore(uid, pid, &name, &target_path, false, Some(&e.to_string()));
+                return (false, error_message(&e, ""));
+            }
+        };
+
+        let subvolume = if subvolume.is_empty() { "/".to_string() } else { subvolume };
+
+        match btrfs::restore_to_new_subvolume(
+            &name,
+            std::path::Path::new(&subvolume),
+            std::path::Path::new(&target_path),
+        ) {
+            Ok(path) => {
+                audit::log_subvolume_restore(uid, pid, &name, &target_path, true, None);
+                (true, path.to_string_lossy().to_string())
+            }
+            Err(e) => {
+                let error_msg = e.to_string();
+                audit::log_subvolume_restore(uid, pid, &name, &target_path, false, Some(&error_msg));
+                (false, error_message(&e, "Failed to restore to new subvolume"))
+            }
+        }
+    }
+
+    /// List all snapshots
+    async fn list_snapshots(&self) -> String {
+        // Listing doesn't require authorization (read-only)
+        match btrfs::list_snapshots() {
+            Ok(snapshots) => {
+                let snapshot_infos: Vec<SnapshotInfo> =
+                    snapshots.into_iter().map(|s| s.into()).collect();
+
+                serde_json::to_string(&snapshot_infos).unwrap_or_else(|_| "[]".to_string())
+            }
+            Err(e) => {
+                log::error!("Failed to list snapshots: {e}");
+                "[]".to_string()
+            }
+        }
+    }
+
+    /// List all snapshots without their package lists
+    ///
+    /// `package_count` still tells how many packages each snapshot has; the
+    /// lists themselves come from `get_snapshot_packages` when needed.
+    async fn list_snapshot_summaries(&self) -> String {
+        // Listing doesn't require authorization (read-only)
+        match btrfs::list_snapshots() {
+            Ok(snapshots) => {
+                let summaries: Vec<SnapshotInfo> = snapshots
+                    .into_iter()
+                    .map(|s| SnapshotInfo::from(s).without_packages())
+                    .collect();
+
+                serde_json::to_string(&summaries).unwrap_or_else(|_| "[]".to_string())
+            }
+            Err(e) => {
+                log::error!("Failed to list snapshots: {e}");
+                "[]".to_string()
+            }
+        }
+    }
+
+    /// Get the package lists of several snapshots
+    ///
+    /// Returns a JSON object mapping snapshot names to their packages. This is
+    /// a read-only operation and does not require authorization.
+    async fn get_snapshot_packages(&self, snapshot_names: Vec<String>) -> String {
+        match btrfs::get_snapshot_packages(&snapshot_names) {
+            Ok(packages) => serde_json::to_string(&packages).unwrap_or_else(|_| "{}".to_string()),
+            Err(e) => {
+                log::error!("Failed to get snapshot packages: {e}");
+                "{}".to_string()
+            }
+        }
+    }
+
+    /// Get sizes for multiple snapshots
+    /// Returns JSON object mapping snapshot names to sizes in bytes
+    /// This method runs with privileges, so it can access snapshot directories
+    async fn get_snapshot_sizes(&self, snapshot_names: Vec<String>) -> String {
+        // Getting sizes is read-only, no authorization needed
+        match btrfs::get_snapshot_sizes(snapshot_names) {
+            Ok(sizes) => serde_json::to_string(&sizes).unwrap_or_else(|_| "{}".to_string()),
+            Err(e) => {
+                log::error!("Failed to get snapshot sizes: {e}");
+                "{}".to_string()
+            }
+        }
+    }
+
+    /// Get the exclusive qgroup sizes of several snapshots
+    ///
+    /// Returns a JSON object mapping snapshot names to the bytes deleting them
+    /// would free. Snapshots whose exclusive size is unknown are left out.
+    async fn get_snapshot_exclusive_sizes(&self, snapshot_names: Vec<String>) -> String {
+        // Getting sizes is read-only, no authorization needed
+        let result = tokio::task::spawn_blocking(move || {
+            btrfs::get_snapshot_exclusive_sizes(snapshot_names)
+        })
+        .await
+        .unwrap_or_else(|e| Err(anyhow::anyhow!("Size task failed: {e}")));
+        match result {
+            Ok(sizes) => serde_json::to_string(&sizes).unwrap_or_else(|_| "{}".to_string()),
+            Err(e) => {
+                log::error!("Failed to get exclusive snapshot sizes: {e}");
+                "{}".to_string()
+            }
+        }
+    }
+
+    /// Verify snapshot integrity
+    async fn verify_snapshot(&self, name: String) -> String {
+        // Verification is read-only, no authorization needed
+        match btrfs::verify_snapshot(&name) {
+            Ok(result) => serde_json::to_string(&result).unwrap_or_else(|_| {
+                r#"{"is_valid":false,"errors":["Failed to serialize result"],"warnings":[]}"#
+                    .to_string()
+            }),
+            Err(e) => {
+                log::error!("Failed to verify snapshot: {e}");
+                serde_json::to_string(&btrfs::VerificationResult {
+                    is_valid: false,
+                    errors: vec![format!("Verification failed: {}", e)],
+                    warnings: vec![],
+                })
+                .unwrap_or_else(|_| {
+                    r#"{"is_valid":false,"errors":["Failed to verify"],"warnings":[]}"#.to_string()
+                })
+            }
+        }
+    }
+
+    /// Preview what will happen if a snapshot is restored
+    async fn preview_restore(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        name: String,
+    ) -> (bool, String) {
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_RESTORE).await {
+            return (false, auth_failure_message(&e));
+        }
+
+        match btrfs::preview_restore(&name) {
+            Ok(result) => match serde_json::to_string(&result) {
+                Ok(json) => (true, json),
+                Err(e) => (false, format!("Failed to serialize preview: {e}")),
+            },
+            Err(e) => {
+                log::error!("Failed to preview restore: {e}");
+                (false, error_message(&e, "Failed to preview restore"))
+            }
+        }
+    }
+
+    /// Save schedules TOML configuration file
+    async fn save_schedules_config(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        toml_content: String,
+    ) -> (bool, String) {
+        // Get caller info for audit logging
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        // Check authorization
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
+            audit::log_auth_failure(uid.clone(), pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
+            return (false, auth_failure_message(&e));
+        }
+
+        // Validate TOML by parsing it first
+        use waypoint_common::schedules::SchedulesConfig;
+        let schedules = match toml::from_str::<SchedulesConfig>(&toml_content) {
+            Ok(schedules) => schedules,
+            Err(e) => {
+                let error_msg = e.to_string();
+                audit::log_config_change(uid, pid, "schedules", false, Some(&error_msg));
+                return (false, format!("Invalid TOML configuration: {e}"));
+            }
+        };
+
+        // Reject what the scheduler would refuse to load
+        if let Err(e) = schedules.schedules.iter().try_for_each(|schedule| schedule.validate()) {
+            audit::log_config_change(uid, pid, "schedules", false, Some(&e));
+            let message = format!("Invalid schedules: {e}");
+            return (false, HelperError::new(ErrorCode::InvalidInput, message).encode());
+        }
+
+        // Catch schedules pointing at removed subvolumes now rather than at snapshot time
+        let validation = tokio::task::spawn_blocking(move || {
+            btrfs::validate_schedule_subvolumes(&schedules)
+        })
+        .await
+        .unwrap_or_else(|e| Err(anyhow::anyhow!("Validation task failed: {e}")));
+        if let Err(e) = validation {
+            let error_msg = e.to_string();
+            audit::log_config_change(uid, pid, "schedules", false, Some(&error_msg));
+            return (false, error_message(&e, "Invalid schedules"));
+        }
+
+        let config = WaypointConfig::new();
+        let schedules_path = config.schedules_config;
+
+        // Create parent directory if it doesn't exist
+        if let Some(parent) = schedules_path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                let error_msg = e.to_string();
+                audit::log_config_change(uid, pid, "schedules", false, Some(&error_msg));
+                return (false, format!("Failed to create config directory: {e}"));
+            }
+        }
+
+        // Write configuration file
+        match std::fs::write(&schedules_path, toml_content) {
+            Ok(_) => {
+                audit::log_config_change(uid, pid, "schedules", true, None);
+                (true, "Schedules configuration saved".to_string())
+            }
+            Err(e) => {
+                let error_msg = e.to_string();
+                audit::log_config_change(uid, pid, "schedules", false, Some(&error_msg));
+                (false, format!("Failed to save configuration: {e}"))
+            }
+        }
+    }
+
+    /// Restart scheduler service
+    async fn restart_scheduler(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+    ) -> (bool, String) {
+        // Check authorization
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
+            return (false, auth_failure_message(&e));
+        }
+
+        run_command("sv", &["restart", "waypoint-scheduler"])
+            .map(|_| (true, "Scheduler service restarted".to_string()))
+            .unwrap_or_else(|e| (false, format!("Failed to restart scheduler service: {e}")))
+    }
+
+    /// Get scheduler service status
     async fn get_scheduler_status(&self) -> String {
         let service_enabled = std::path::Path::new(&scheduler_service_path()).exists();
 
-        if !service_enabled {
-            return "disabled".to_string();
+        if !service_enabled {
+            return "disabled".to_string();
+        }
+
+        run_command_with_output("sv", &["status", "waypoint-scheduler"])
+            .map(|(stdout, stderr)| {
+                if stdout.contains("run:") {
+                    "running".to_string()
+                } else if stdout.contains("down:") || stderr.contains("unable to") {
+                    "stopped".to_string()
+                } else {
+                    "unknown".to_string()
+                }
+            })
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to query scheduler status: {e}");
+                "unknown".to_string()
+            })
+    }
+
+    /// Apply retention cleanup based on schedule-based or global retention rules
+    async fn cleanup_snapshots(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        schedule_based: bool,
+    ) -> (bool, String) {
+        // Check authorization
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_DELETE).await {
+            return (false, auth_failure_message(&e));
+        }
+
+        self.cleanup_snapshots_as(schedule_based).await
+    }
+
+    /// Clean up orphaned writable snapshot copies
+    ///
+    /// Scans for root-writable subvolumes created during multi-subvolume restores
+    /// and deletes those that are no longer needed (not currently booted, not default).
+    /// This is safe to run at any time and will never delete active subvolumes.
+    ///
+    /// Returns a success message listing the deleted subvolumes.
+    async fn cleanup_writable_snapshots(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+    ) -> (bool, String) {
+        // Get caller info for audit logging
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        // Check authorization - use delete permission since we're deleting subvolumes
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_DELETE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_DELETE, &e.to_string());
+            return (false, auth_failure_message(&e));
+        }
+
+        // Perform cleanup
+        match btrfs::cleanup_writable_snapshots() {
+            Ok(deleted) => {
+                let count = deleted.len();
+
+                // Log each deleted writable snapshot
+                for path in &deleted {
+                    audit::log_snapshot_delete(uid.clone(), pid, path, true, None);
+                }
+
+                if count == 0 {
+                    (true, "No orphaned writable snapshots found".to_string())
+                } else {
+                    (
+                        true,
+                        format!(
+                            "Successfully cleaned up {} orphaned writable snapshot(s):\n{}",
+                            count,
+                            deleted.join("\n")
+                        ),
+                    )
+                }
+            }
+            Err(e) => {
+                let error_msg = e.to_string();
+                audit::log_auth_failure(uid, pid, "cleanup_writable_snapshots", &error_msg);
+                (false, format!("Failed to cleanup writable snapshots: {e}"))
+            }
+        }
+    }
+
+    /// Restore files from a snapshot to the filesystem
+    ///
+    /// Restores individual files or directories from a snapshot back to the live system.
+    /// Can restore to original locations or to a custom directory.
+    ///
+    /// # Arguments
+    /// * `snapshot_name` - Name of the snapshot to restore from
+    /// * `file_paths` - Paths within the snapshot to restore (e.g., "/etc/fstab", "/home/user/doc.txt")
+    /// * `target_directory` - Where to restore files. Empty string = original locations, otherwise custom path
+    /// * `overwrite` - Whether to overwrite existing files
+    /// * `verify` - Hash each restored file and its source and report mismatches as failures
+    /// * `nested_subvolumes` - What to do with nested subvolumes: "skip", "recurse" or "subvolume"
+    #[allow(clippy::too_many_arguments)]
+    async fn restore_files(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        snapshot_name: String,
+        file_paths: Vec<String>,
+        target_directory: String,
+        overwrite: bool,
+        verify: bool,
+        nested_subvolumes: String,
+    ) -> (bool, String) {
+        // Check authorization - file restoration requires restore permissions
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_RESTORE).await {
+            return (false, auth_failure_message(&e));
+        }
+
+        let Some(nested_subvolumes) = NestedSubvolumeMode::parse(&nested_subvolumes) else {
+            let message = format!("Invalid nested subvolume mode: {nested_subvolumes}");
+            return (
+                false,
+                HelperError::new(ErrorCode::InvalidInput, message).encode(),
+            );
+        };
+
+        let _operation = match shutdown::begin("file restore", None) {
+            Ok(operation) => operation,
+            Err(e) => return (false, error_message(&e, "")),
+        };
+
+        // Perform file restoration
+        result_to_dbus_response(
+            Self::restore_files_impl(
+                &snapshot_name,
+                file_paths,
+                &target_directory,
+                overwrite,
+                verify,
+                nested_subvolumes,
+            ),
+            "File restoration failed"
+        )
+    }
+
+    /// Mount an ephemeral writable overlay of a snapshot subvolume
+    ///
+    /// The snapshot stays untouched: writes land in a tmpfs and are discarded on
+    /// unmount. Returns the `OverlayMount` as JSON on success.
+    async fn mount_snapshot_overlay(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        snapshot_name: String,
+        subvolume: String,
+    ) -> (bool, String) {
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_RESTORE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_RESTORE, &e.to_string());
+            return (false, auth_failure_message(&e));
+        }
+
+        let subvolume = if subvolume.is_empty() { "/".to_string() } else { subvolume };
+
+        match overlay::mount_overlay(&snapshot_name, std::path::Path::new(&subvolume), pid) {
+            Ok(mount) => {
+                audit::log_overlay_mount(uid, pid, &snapshot_name, true, None);
+                match serde_json::to_string(&mount) {
+                    Ok(json) => (true, json),
+                    Err(e) => (false, format!("Failed to serialize overlay: {e}")),
+                }
+            }
+            Err(e) => {
+                let error_msg = e.to_string();
+                audit::log_overlay_mount(uid, pid, &snapshot_name, false, Some(&error_msg));
+                (false, error_message(&e, "Failed to mount overlay"))
+            }
+        }
+    }
+
+    /// Unmount a snapshot overlay, discarding all changes made in it
+    ///
+    /// The process that mounted the overlay may unmount it without authorization.
+    async fn unmount_snapshot_overlay(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        mount_point: String,
+    ) -> (bool, String) {
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+        let mount_path = std::path::Path::new(&mount_point);
+
+        let authorized = if overlay::overlay_owner(mount_path) == Some(pid) {
+            Ok(())
+        } else {
+            check_authorization(&hdr, connection, POLKIT_ACTION_RESTORE).await
+        };
+        if let Err(e) = authorized {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_RESTORE, &e.to_string());
+            return (false, auth_failure_message(&e));
+        }
+
+        match overlay::unmount_overlay(mount_path) {
+            Ok(()) => (true, format!("Overlay at {mount_point} unmounted, changes discarded")),
+            Err(e) => (
+                false,
+                error_message(&e, "Failed to unmount overlay"),
+            ),
+        }
+    }
+
+    /// List active snapshot overlays as JSON
+    ///
+    /// Read-only; overlays whose owning process has exited are reaped first.
+    async fn list_snapshot_overlays(&self) -> String {
+        serde_json::to_string(&overlay::list_overlays()).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    /// Compare two snapshots and return list of changed files
+    ///
+    /// This is a read-only operation and does not require authorization.
+    /// Progress is reported through `CompareProgress` signals; the final "complete"
+    /// signal carries the total number of changes, which may exceed the number
+    /// returned when the result was capped.
+    async fn compare_snapshots(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        #[zbus(signal_context)] ctxt: zbus::SignalContext<'_>,
+        old_snapshot_name: String,
+        new_snapshot_name: String,
+    ) -> (bool, String) {
+        let (uid, _pid) = Self::get_caller_info(&hdr, connection).await;
+        let key = format!("{uid}:{old_snapshot_name}:{new_snapshot_name}");
+
+        let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let _operation = match shutdown::begin("comparison", Some(cancel.clone())) {
+            Ok(operation) => operation,
+            Err(e) => return (false, error_message(&e, "")),
+        };
+        self.compare_cancellations()
+            .entry(key.clone())
+            .or_default()
+            .push(cancel.clone());
+
+        // Bounded so a slow consumer never makes progress reporting grow memory
+        let (progress_tx, progress_rx) = std::sync::mpsc::sync_channel::<CompareProgress>(16);
+
+        let old_clone = old_snapshot_name.clone();
+        let new_clone = new_snapshot_name.clone();
+        let cancel_clone = cancel.clone();
+        let mut compare_handle = tokio::task::spawn_blocking(move || {
+            Self::compare_snapshots_impl(
+                &old_clone,
+                &new_clone,
+                Some(&progress_tx),
+                &cancel_clone,
+            )
+        });
+
+        let result = loop {
+            tokio::select! {
+                _ = tokio::time::sleep(tokio::time::Duration::from_millis(250)) => {
+                    let updates: Vec<CompareProgress> = progress_rx.try_iter().collect();
+                    if let Some(progress) = updates.last() {
+                        let _ = Self::compare_progress(
+                            &ctxt,
+                            &old_snapshot_name,
+                            &new_snapshot_name,
+                            progress.entries_scanned,
+                            progress.changes_found,
+                            progress.stage,
+                        ).await;
+                    }
+                }
+                result = &mut compare_handle => {
+                    break result.unwrap_or_else(|e| {
+                        Err(anyhow::anyhow!("Comparison task failed: {e}"))
+                    });
+                }
+            }
+        };
+
+        {
+            let mut cancellations = self.compare_cancellations();
+            if let Some(flags) = cancellations.get_mut(&key) {
+                flags.retain(|flag| !std::sync::Arc::ptr_eq(flag, &cancel));
+                if flags.is_empty() {
+                    cancellations.remove(&key);
+                }
+            }
+        }
+
+        // Always finish with a terminal stage so listeners can stop waiting
+        let (stage, total) = match &result {
+            Ok((_, total)) => ("complete", *total),
+            Err(_) if cancel.load(Ordering::Relaxed) => ("cancelled", 0),
+            Err(_) => ("failed", 0),
+        };
+        let _ = Self::compare_progress(
+            &ctxt,
+            &old_snapshot_name,
+            &new_snapshot_name,
+            0,
+            total,
+            stage,
+        )
+        .await;
+
+        result_to_dbus_response(result.map(|(json, _)| json), "Comparison failed")
+    }
+
+    /// Cancel a running comparison started by the same user
+    async fn cancel_compare(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        old_snapshot_name: String,
+        new_snapshot_name: String,
+    ) -> (bool, String) {
+        let (uid, _pid) = Self::get_caller_info(&hdr, connection).await;
+        let key = format!("{uid}:{old_snapshot_name}:{new_snapshot_name}");
+
+        match self.compare_cancellations().get(&key) {
+            Some(flags) => {
+                for flag in flags {
+                    flag.store(true, Ordering::Relaxed);
+                }
+                (true, "Comparison cancelled".to_string())
+            }
+            None => (false, "No running comparison for these snapshots".to_string()),
+        }
+    }
+
+    /// Estimate how much space deduplicating the snapshots could reclaim
+    ///
+    /// Read-only and does not require authorization. Returns a JSON `DedupEstimate`.
+    async fn estimate_dedup_savings(&self) -> (bool, String) {
+        let result = tokio::task::spawn_blocking(dedup::estimate_savings)
+            .await
+            .unwrap_or_else(|e| Err(anyhow::anyhow!("Estimate task failed: {e}")))
+            .and_then(|estimate| serde_json::to_string(&estimate).map_err(Into::into));
+        result_to_dbus_response(result, "Failed to estimate deduplication savings")
+    }
+
+    /// Deduplicate identical extents across all snapshots
+    ///
+    /// Long-running manual maintenance action. Progress is reported through
+    /// `DedupProgress` signals, ending with "complete" or "failed".
+    ///
+    /// # Returns
+    /// * `(success, message, reclaimed_bytes)`
+    async fn deduplicate_snapshots(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        #[zbus(signal_context)] ctxt: zbus::SignalContext<'_>,
+    ) -> (bool, String, u64) {
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
+            return (false, auth_failure_message(&e), 0);
+        }
+
+        let _operation = match shutdown::begin("deduplication", None) {
+            Ok(operation) => operation,
+            Err(e) => return (false, error_message(&e, ""), 0),
+        };
+
+        let (progress_tx, progress_rx) = std::sync::mpsc::sync_channel::<dedup::DedupProgress>(16);
+        let mut dedup_handle =
+            tokio::task::spawn_blocking(move || dedup::deduplicate(Some(&progress_tx)));
+
+        let result = loop {
+            tokio::select! {
+                _ = tokio::time::sleep(tokio::time::Duration::from_millis(500)) => {
+                    let updates: Vec<dedup::DedupProgress> = progress_rx.try_iter().collect();
+                    if let Some(progress) = updates.last() {
+                        let _ = Self::dedup_progress(&ctxt, progress.done, progress.total, progress.stage).await;
+                    }
+                }
+                result = &mut dedup_handle => {
+                    break result.unwrap_or_else(|e| {
+                        Err(anyhow::anyhow!("Deduplication task failed: {e}"))
+                    });
+                }
+            }
+        };
+
+        match result {
+            Ok(reclaimed) => {
+                let _ = Self::dedup_progress(&ctxt, 0, 0, "complete").await;
+                audit::log_deduplication(uid, pid, true, Some(&format!("reclaimed {reclaimed} bytes")));
+                (
+                    true,
+                    format!("Deduplication complete, reclaimed {}", format_bytes(reclaimed)),
+                    reclaimed,
+                )
+            }
+            Err(e) => {
+                let _ = Self::dedup_progress(&ctxt, 0, 0, "failed").await;
+                audit::log_deduplication(uid, pid, false, Some(&format!("error: {e}")));
+                (false, error_message(&e, ""), 0)
+            }
+        }
+    }
+
+    /// Start a filtered balance of the snapshot filesystem
+    ///
+    /// Relocates data and metadata chunks that are at most `usage` percent
+    /// full, returning their space to the unallocated pool. Returns as soon as
+    /// the balance has started; progress is reported through `BalanceProgress`
+    /// signals every few seconds, ending with "complete", "cancelled" or "failed".
+    async fn start_balance(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        #[zbus(signal_context)] ctxt: zbus::SignalContext<'_>,
+        usage: u32,
+    ) -> (bool, String) {
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
+            return (false, auth_failure_message(&e));
+        }
+
+        let child = match balance::start(usage) {
+            Ok(child) => child,
+            Err(e) => {
+                audit::log_balance(uid, pid, false, Some(&format!("error: {e}")));
+                return (false, error_message(&e, "Failed to start balance"));
+            }
+        };
+
+        let ctxt = ctxt.to_owned();
+        tokio::spawn(async move {
+            let mut balance_handle = tokio::task::spawn_blocking(move || balance::wait(child));
+
+            let result = loop {
+                tokio::select! {
+                    _ = tokio::time::sleep(tokio::time::Duration::from_secs(2)) => {
+                        if let Ok(Ok(status)) = tokio::task::spawn_blocking(balance::status).await {
+                            let _ = Self::balance_progress(
+                                &ctxt,
+                                status.chunks_balanced,
+                                status.chunks_total,
+                                "running",
+                            ).await;
+                        }
+                    }
+                    result = &mut balance_handle => {
+                        break result.unwrap_or_else(|e| {
+                            Err(anyhow::anyhow!("Balance task failed: {e}"))
+                        });
+                    }
+                }
+            };
+
+            let stage = match &result {
+                Ok(true) => "complete",
+                Ok(false) => "cancelled",
+                Err(_) => "failed",
+            };
+            let _ = Self::balance_progress(&ctxt, 0, 0, stage).await;
+            match result {
+                Ok(_) => audit::log_balance(uid, pid, true, Some(&format!("usage {usage}%, {stage}"))),
+                Err(e) => audit::log_balance(uid, pid, false, Some(&format!("error: {e}"))),
+            }
+        });
+
+        (true, format!("Balance started with usage filter {usage}%"))
+    }
+
+    /// Get the balance state of the snapshot filesystem
+    ///
+    /// Read-only and does not require authorization. Returns a JSON `BalanceStatus`.
+    async fn get_balance_status(&self) -> (bool, String) {
+        let result = tokio::task::spawn_blocking(balance::status)
+            .await
+            .unwrap_or_else(|e| Err(anyhow::anyhow!("Status task failed: {e}")))
+            .and_then(|status| serde_json::to_string(&status).map_err(Into::into));
+        result_to_dbus_response(result, "Failed to get balance status")
+    }
+
+    /// Cancel the running balance
+    ///
+    /// Waits until the chunk being relocated is finished, which can take a while.
+    async fn cancel_balance(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+    ) -> (bool, String) {
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
+            return (false, auth_failure_message(&e));
+        }
+
+        let result = tokio::task::spawn_blocking(balance::cancel)
+            .await
+            .unwrap_or_else(|e| Err(anyhow::anyhow!("Cancel task failed: {e}")))
+            .map(|()| "Balance cancelled".to_string());
+        result_to_dbus_response(result, "Failed to cancel balance")
+    }
+
+    /// Enable btrfs quotas on the snapshot filesystem
+    ///
+    /// # Arguments
+    /// * `use_simple` - Whether to use simple quotas (true) or traditional qgroups (false)
+    async fn enable_quotas(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        use_simple: bool,
+    ) -> (bool, String) {
+        // Check authorization
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
+            return (false, auth_failure_message(&e));
+        }
+
+        result_to_dbus_response(
+            Self::enable_quotas_impl(use_simple),
+            "Failed to enable quotas"
+        )
+    }
+
+    /// Disable btrfs quotas on the snapshot filesystem
+    async fn disable_quotas(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+    ) -> (bool, String) {
+        // Check authorization
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
+            return (false, auth_failure_message(&e));
+        }
+
+        result_to_dbus_response(
+            Self::disable_quotas_impl(),
+            "Failed to disable quotas"
+        )
+    }
+
+    /// Get quota usage for the snapshot filesystem
+    ///
+    /// Returns JSON string with quota usage information
+    /// This is a read-only operation and does not require authorization
+    async fn get_quota_usage(&self) -> (bool, String) {
+        result_to_dbus_response(
+            Self::get_quota_usage_impl(),
+            "Failed to get quota usage"
+        )
+    }
+
+    /// Get free space and btrfs allocation of the filesystem containing `path`
+    ///
+    /// Returns a JSON `FilesystemStats`. The allocation details need root, which
+    /// is why the GUI asks the helper instead of running `df`. Only snapshot
+    /// directories, their parents and scheduled subvolumes are accepted.
+    /// This is a read-only operation and does not require authorization
+    async fn get_filesystem_stats(
+&self, path: String) -> (bool, String) {
+        let result =
+            tokio::task::spawn_blocking(move || filesystem_stats::get_filesystem_stats(&path))
+                .await
+                .unwrap_or_else(|e| Err(anyhow::anyhow!("Stats task failed: {e}")))
+                .and_then(|stats| serde_json::to_string(&stats).map_err(Into::into));
+        result_to_dbus_response(result, "Failed to get filesystem stats")
+    }
+
+    /// Preview what quota auto-cleanup would delete right now
+    ///
+    /// Returns a JSON `QuotaCleanupReport` with `dry_run` set. Nothing is deleted.
+    /// This is a read-only operation and does not require authorization
+    async fn preview_quota_cleanup(&self) -> (bool, String) {
+        let result = tokio::task::spawn_blocking(|| Self::check_quota_and_cleanup(true))
+            .await
+            .unwrap_or_else(|e| Err(anyhow::anyhow!("Preview task failed: {e}")))
+            .and_then(|report| serde_json::to_string(&report).map_err(Into::into));
+        result_to_dbus_response(result, "Failed to preview quota cleanup")
+    }
+
+    /// Set quota limit for the snapshot filesystem
+    ///
+    /// # Arguments
+    /// * `limit_bytes` - Size limit in bytes
+    async fn set_quota_limit(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        limit_bytes: u64,
+    ) -> (bool, String) {
+        // Check authorization
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
+            return (false, auth_failure_message(&e));
+        }
+
+        result_to_dbus_response(
+            Self::set_quota_limit_impl(limit_bytes),
+            "Failed to set quota limit"
+        )
+    }
+
+    /// Get btrfs compression of the snapshot directory
+    ///
+    /// Returns a JSON `SnapshotCompression` with the directory's property, the
+    /// mount option and, when `compsize` is installed, the space saved so far.
+    /// This is a read-only operation and does not require authorization
+    async fn get_snapshot_compression(&self) -> (bool, String) {
+        let result = tokio::task::spawn_blocking(compression::get_snapshot_compression)
+            .await
+            .unwrap_or_else(|e| Err(anyhow::anyhow!("Compression task failed: {e}")))
+            .and_then(|compression| serde_json::to_string(&compression).map_err(Into::into));
+        result_to_dbus_response(result, "Failed to get snapshot compression")
+    }
+
+    /// Get how much snapshot data is shared through extents
+    ///
+    /// Returns a JSON `SnapshotSharing` comparing the summed referenced size of
+    /// all snapshots with the space only they use. Requires quotas.
+    /// This is a read-only operation and does not require authorization
+    async fn get_snapshot_sharing(&self) -> (bool, String) {
+        let result = tokio::task::spawn_blocking(btrfs::get_snapshot_sharing)
+            .await
+            .unwrap_or_else(|e| Err(anyhow::anyhow!("Sharing task failed: {e}")))
+            .and_then(|sharing| serde_json::to_string(&sharing).map_err(Into::into));
+        result_to_dbus_response(result, "Failed to get snapshot sharing")
+    }
+
+    /// Set btrfs compression of the snapshot directory
+    ///
+    /// Only data written afterwards is compressed. Returns the new state as a
+    /// JSON `SnapshotCompression`.
+    ///
+    /// # Arguments
+    /// * `algorithm` - `zstd`, `lzo` or `zlib`, or empty to follow the mount option
+    async fn set_snapshot_compression(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        algorithm: String,
+    ) -> (bool, String) {
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
+            return (false, auth_failure_message(&e));
         }
 
-        run_command_with_output("sv", &["status", "waypoint-scheduler"])
-            .map(|(stdout, stderr)| {
-                if stdout.contains("run:") {
-                    "running".to_string()
-                } else if stdout.contains("down:") || stderr.contains("unable to") {
-                    "stopped".to_string()
-                } else {
-                    "unknown".to_string()
-                }
-            })
-            .unwrap_or_else(|e| {
-                log::warn!("Failed to query scheduler status: {e}");
-                "unknown".to_string()
-            })
+        let result =
+            tokio::task::spawn_blocking(move || compression::set_snapshot_compression(&algorithm))
+                .await
+                .unwrap_or_else(|e| Err(anyhow::anyhow!("Compression task failed: {e}")))
+                .and_then(|compression| serde_json::to_string(&compression).map_err(Into::into));
+
+        let error = result.as_ref().err().map(|e| e.to_string());
+        audit::log_config_change(
+            uid,
+            pid,
+            "snapshot-compression",
+            result.is_ok(),
+            error.as_deref(),
+        );
+        result_to_dbus_response(result, "Failed to set snapshot compression")
     }
 
-    /// Apply retention cleanup based on schedule-based or global retention rules
-    async fn cleanup_snapshots(
+    /// Save quota configuration to /etc/waypoint/quota.toml
+    ///
+    /// # Arguments
+    /// * `config_toml` - TOML string of quota configuration
+    async fn save_quota_config(
         &self,
         #[zbus(header)] hdr: zbus::message::Header<'_>,
         #[zbus(connection)] connection: &Connection,
-        schedule_based: bool,
+        config_toml: String,
     ) -> (bool, String) {
+        // Get caller info for audit logging
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
         // Check authorization
-        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_DELETE).await {
-            return (false, format!("Authorization failed: {e}"));
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
+            return (false, auth_failure_message(&e));
         }
 
-        // Perform cleanup
+        match Self::save_quota_config_impl(&config_toml) {
+            Ok(msg) => {
+                audit::log_config_change(uid, pid, "quota", true, None);
+                (true, msg)
+            }
+            Err(e) => {
+                let error_msg = e.to_string();
+                audit::log_config_change(uid, pid, "quota", false, Some(&error_msg));
+                (false, format!("Failed to save quota configuration: {e}"))
+            }
+        }
+    }
+
+    /// Save exclude configuration to /etc/waypoint/exclude.toml
+    ///
+    /// # Arguments
+    /// * `config_toml` - TOML string of exclude configuration
+    async fn save_exclude_config(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        config_toml: String,
+    ) -> (bool, String) {
+        // Get caller info for audit logging
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        // Check authorization
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
+            return (false, auth_failure_message(&e));
+        }
+
+        match Self::save_exclude_config_impl(&config_toml) {
+            Ok(msg) => {
+                audit::log_config_change(uid, pid, "exclude", true, None);
+                (true, msg)
+            }
+            Err(e) => {
+                let error_msg = e.to_string();
+                audit::log_config_change(uid, pid, "exclude", false, Some(&error_msg));
+                (false, format!("Failed to save exclude configuration: {e}"))
+            }
+        }
+    }
+
+    /// Export system configuration as a portable JSON bundle
+    ///
+    /// Includes schedules, quota and exclude configuration. This is a read-only
+    /// operation and does not require authorization.
+    async fn export_config(&self) -> (bool, String) {
+        match Self::export_config_impl() {
+            Ok(json) => (true, json),
+            Err(e) => {
+                log::error!("Failed to export configuration: {e}");
+                (false, error_message(&e, ""))
+            }
+        }
+    }
+
+    /// Import system configuration from a JSON bundle
+    ///
+    /// Every section is validated before anything is written, and files are
+    /// replaced atomically so a failed import leaves the existing config intact.
+    /// Quotas and the scheduler are then updated to match.
+    ///
+    /// # Arguments
+    /// * `bundle_json` - JSON produced by `export_config`
+    async fn import_config(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        bundle_json: String,
+    ) -> (bool, String) {
+        // Get caller info for audit logging
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        // Check authorization
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
+            return (false, auth_failure_message(&e));
+        }
+
+        match Self::import_config_impl(&bundle_json) {
+            Ok(msg) => {
+                audit::log_config_change(uid, pid, "import", true, None);
+                (true, msg)
+            }
+            Err(e) => {
+                let error_msg = e.to_string();
+                audit::log_config_change(uid, pid, "import", false, Some(&error_msg));
+                (false, format!("Failed to import configuration: {e}"))
+            }
+        }
+    }
+
+    /// Get recent audit log events as a JSON array (newest last)
+    ///
+    /// Requires configuration privileges because events reveal who did what.
+    ///
+    /// # Arguments
+    /// * `max_events` - Maximum number of events to return (0 = all)
+    async fn get_audit_log(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        max_events: u32,
+    ) -> (bool, String) {
+        // Get caller info for audit logging
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        // Check authorization
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
+            return (false, auth_failure_message(&e));
+        }
+
+        match Self::get_audit_log_impl(max_events as usize) {
+            Ok(json) => (true, json),
+            Err(e) => {
+                log::error!("Failed to read audit log: {e}");
+                (false, error_message(&e, ""))
+            }
+        }
+    }
+
+    /// Get the most recent commands the helper ran as a JSON array (newest last)
+    ///
+    /// Requires configuration privileges because command lines reveal paths
+    /// and snapshot names of other users' operations.
+    ///
+    /// # Arguments
+    /// * `max_records` - Maximum number of commands to return (0 = all that are kept)
+    async fn get_command_transcript(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        max_records: u32,
+    ) -> (bool, String) {
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
+            return (false, auth_failure_message(&e));
+        }
+
+        match serde_json::to_string(&transcript::recent(max_records as usize)) {
+            Ok(json) => (true, json),
+            Err(e) => (
+                false,
+                format!("Failed to serialize command transcript: {e}"),
+            ),
+        }
+    }
+
+    /// Get the owner and permissions of each snapshot directory
+    ///
+    /// Returns a JSON array of `SnapshotDirPermissions`. Directories that
+    /// aren't root-only let other users read files from snapshots.
+    /// This is a read-only operation and does not require authorization
+    async fn get_snapshot_dir_permissions(&self) -> (bool, String) {
+        let permissions =
+            waypoint_common::permissions::inspect_snapshot_dirs(&WaypointConfig::new());
         result_to_dbus_response(
-            Self::cleanup_snapshots_impl(schedule_based),
-            "Cleanup failed"
+            serde_json::to_string(&permissions).map_err(Into::into),
+            "Failed to get snapshot directory permissions",
         )
     }
 
-    /// Clean up orphaned writable snapshot copies
+    /// Make every snapshot directory owned by root and accessible to root only
     ///
-    /// Scans for root-writable subvolumes created during multi-subvolume restores
-    /// and deletes those that are no longer needed (not currently booted, not default).
-    /// This is safe to run at any time and will never delete active subvolumes.
+    /// Returns the permissions afterwards as JSON, like `GetSnapshotDirPermissions`.
+    async fn secure_snapshot_dirs(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+    ) -> (bool, String) {
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
+            return (false, auth_failure_message(&e));
+        }
+
+        match permissions::secure_snapshot_dirs() {
+            Ok(permissions) => {
+                audit::log_config_change(uid, pid, "snapshot-dir-permissions", true, None);
+                result_to_dbus_response(
+                    serde_json::to_string(&permissions).map_err(Into::into),
+                    "Failed to serialize snapshot directory permissions",
+                )
+            }
+            Err(e) => {
+                let error_msg = format!("{e:#}");
+                audit::log_config_change(
+                    uid,
+                    pid,
+                    "snapshot-dir-permissions",
+                    false,
+                    Some(&error_msg),
+                );
+                (
+                    false,
+                    error_message(&e, "Failed to secure snapshot directories"),
+                )
+            }
+        }
+    }
+
+    /// Update snapshot metadata (specifically size_bytes)
     ///
-    /// Returns a success message listing the deleted subvolumes.
-    async fn cleanup_writable_snapshots(
+    /// # Arguments
+    /// * `snapshot_json` - JSON string of the snapshot with updated metadata
+    async fn update_snapshot_metadata(
         &self,
         #[zbus(header)] hdr: zbus::message::Header<'_>,
         #[zbus(connection)] connection: &Connection,
+        snapshot_json: String,
     ) -> (bool, String) {
         // Get caller info for audit logging
         let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
 
-        // Check authorization - use delete permission since we're deleting subvolumes
-        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_DELETE).await {
-            audit::log_auth_failure(uid, pid, POLKIT_ACTION_DELETE, &e.to_string());
-            return (false, format!("Authorization failed: {e}"));
+        // Check authorization - using configure action since we're modifying metadata
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
+            return (false, auth_failure_message(&e));
         }
 
-        // Perform cleanup
-        match btrfs::cleanup_writable_snapshots() {
-            Ok(deleted) => {
-                let count = deleted.len();
+        match Self::update_snapshot_metadata_impl(&snapshot_json) {
+            Ok(msg) => {
+                log::info!("Snapshot metadata updated by uid={uid} pid={pid}");
+                (true, msg)
+            }
+            Err(e) => {
+                log::error!("Failed to update snapshot metadata: {e}");
+                (false, format!("Failed to update snapshot metadata: {e}"))
+            }
+        }
+    }
 
-                // Log each deleted writable snapshot
-                for path in &deleted {
-                    audit::log_snapshot_delete(uid.clone(), pid, path, true, None);
-                }
+    /// Find metadata entries that contradict each other or their snapshot directory
+    ///
+    /// Returns a JSON array of `MetadataConflict`.
+    /// This is a read-only operation and does not require authorization
+    async fn check_snapshot_metadata(&self) -> (bool, String) {
+        let result = tokio::task::spawn_blocking(btrfs::check_metadata_conflicts)
+            .await
+            .unwrap_or_else(|e| Err(anyhow::anyhow!("Metadata check task failed: {e}")))
+            .and_then(|conflicts| serde_json::to_string(&conflicts).map_err(Into::into));
+        result_to_dbus_response(result, "Failed to check snapshot metadata")
+    }
+
+    /// Resolve a conflict returned by `CheckSnapshotMetadata`
+    ///
+    /// # Arguments
+    /// * `conflict_json` - The `MetadataConflict` as returned
+    /// * `resolution` - `merge` or `remove-extra`
+    async fn resolve_metadata_conflict(
+        &self,
+        #[zbus(header)] hdr: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+        conflict_json: String,
+        resolution: String,
+    ) -> (bool, String) {
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
+            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
+            return (false, auth_failure_message(&e));
+        }
+
+        let invalid = |message: String| {
+            (
+                false,
+                HelperError::new(ErrorCode::InvalidInput, message).encode(),
+            )
+        };
+        let conflict: MetadataConflict = match serde_json::from_str(&conflict_json) {
+            Ok(conflict) => conflict,
+            Err(e) => return invalid(format!("Invalid metadata conflict: {e}")),
+        };
+        let Some(parsed) = ConflictResolution::parse(&resolution) else {
+            return invalid(format!("Unknown resolution '{resolution}'"));
+        };
+
+        let name = conflict.name.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            btrfs::resolve_metadata_conflict(&conflict, parsed)
+        })
+        .await
+        .unwrap_or_else(|e| Err(anyhow::anyhow!("Metadata repair task failed: {e}")));
 
-                if count == 0 {
-                    (true, "No orphaned writable snapshots found".to_string())
-                } else {
-                    (
-                        true,
-                        format!(
-                            "Successfully cleaned up {} orphaned writable snapshot(s):\n{}",
-                            count,
-                            deleted.join("\n")
-                        ),
-                    )
-                }
+        match result {
+            Ok(()) => {
+                audit::log_metadata_repair(uid, pid, &name, &resolution, true, None);
+                (true, format!("Metadata of '{name}' repaired"))
             }
             Err(e) => {
-                let error_msg = e.to_string();
-                audit::log_auth_failure(uid, pid, "cleanup_writable_snapshots", &error_msg);
-                (false, format!("Failed to cleanup writable snapshots: {e}"))
+                let error = e.to_string();
+                audit::log_metadata_repair(uid, pid, &name, &resolution, false, Some(&error));
+                (false, error_message(&e, "Failed to repair metadata"))
             }
         }
     }
 
-    /// Restore files from a snapshot to the filesystem
+    /// Scan for available backup destinations
     ///
-    /// Restores individual files or directories from a snapshot back to the live system.
-    /// Can restore to original locations or to a custom directory.
+    /// This is a read-only operation and does not require authorization
+    async fn scan_backup_destinations(&self) -> (bool, String) {
+        match backup::scan_backup_destinations() {
+            Ok(destinations) => match serde_json::to_string(&destinations) {
+                Ok(json) => (true, json),
+                Err(e) => (false, format!("Failed to serialize destinations: {e}")),
+            },
+            Err(e) => (false, format!("Failed to scan destinations: {e}")),
+        }
+    }
+
+    /// Backup a snapshot to an external drive
     ///
     /// # Arguments
-    /// * `snapshot_name` - Name of the snapshot to restore from
-    /// * `file_paths` - Paths within the snapshot to restore (e.g., "/etc/fstab", "/home/user/doc.txt")
-    /// * `target_directory` - Where to restore files. Empty string = original locations, otherwise custom path
-    /// * `overwrite` - Whether to overwrite existing files
-    async fn restore_files(
+    /// * `snapshot_path` - Full path to the snapshot (e.g., /.snapshots/my-snapshot)
+    /// * `destination_mount` - Mount point of backup destination
+    /// * `parent_snapshot` - Optional parent snapshot path for incremental backup
+    ///
+    /// # Returns
+    /// * `(success, message_or_path, size_bytes)` - On success: (true, backup_path, size). On failure: (false, error, 0)
+    async fn backup_snapshot(
         &self,
         #[zbus(header)] hdr: zbus::message::Header<'_>,
         #[zbus(connection)] connection: &Connection,
-        snapshot_name: String,
-        file_paths: Vec<String>,
-        target_directory: String,
-        overwrite: bool,
-    ) -> (bool, String) {
-        // Check authorization - file restoration requires restore permissions
-        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_RESTORE).await {
-            return (false, format!("Authorization failed: {e}"));
+        #[zbus(signal_context)] ctxt: zbus::SignalContext<'_>,
+        snapshot_path: String,
+        destination_mount: String,
+        parent_snapshot: String,
+    ) -> (bool, String, u64) {
+        // Check authorization
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CREATE).await {
+            return (false, auth_failure_message(&e), 0);
         }
 
-        // Perform file restoration
-        result_to_dbus_response(
-            Self::restore_files_impl(&snapshot_name, file_paths, &target_directory, overwrite),
-            "File restoration failed"
+        Self::run_backup(
+            &ctxt,
+            snapshot_path,
+            destination_mount,
+            parent_snapshot,
+            BackupFormat::Native,
         )
+        .await
     }
 
-    /// Compare two snapshots and return list of changed files
+    /// Backup a snapshot to a self-contained btrfs image file on an external drive
     ///
-    /// This is a read-only operation and does not require authorization
-    async fn compare_snapshots(
-        &self,
-        old_snapshot_name: String,
-        new_snapshot_name: String,
-    ) -> (bool, String) {
-        result_to_dbus_response(
-            Self::compare_snapshots_impl(&old_snapshot_name, &new_snapshot_name),
-            "Comparison failed"
-        )
-    }
-
-    /// Enable btrfs quotas on the snapshot filesystem
+    /// The image is written to `waypoint-backups/<snapshot>.img` on the
+    /// destination, which may use any supported filesystem. Images are always
+    /// full backups. Progress is reported like for `backup_snapshot`.
     ///
-    /// # Arguments
-    /// * `use_simple` - Whether to use simple quotas (true) or traditional qgroups (false)
-    async fn enable_quotas(
+    /// # Returns
+    /// * `(success, message_or_path, size_bytes)` - On success: (true, image_path, size). On failure: (false, error, 0)
+    async fn backup_snapshot_image(
         &self,
         #[zbus(header)] hdr: zbus::message::Header<'_>,
         #[zbus(connection)] connection: &Connection,
-        use_simple: bool,
-    ) -> (bool, String) {
-        // Check authorization
-        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
-            return (false, format!("Authorization failed: {e}"));
+        #[zbus(signal_context)] ctxt: zbus::SignalContext<'_>,
+        snapshot_path: String,
+        destination_mount: String,
+    ) -> (bool, String, u64) {
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CREATE).await {
+            return (false, auth_failure_message(&e), 0);
         }
 
-        result_to_dbus_response(
-            Self::enable_quotas_impl(use_simple),
-            "Failed to enable quotas"
+        Self::run_backup(
+            &ctxt,
+            snapshot_path,
+            destination_mount,
+            String::new(),
+            BackupFormat::Image,
         )
+        .await
     }
 
-    /// Disable btrfs quotas on the snapshot filesystem
-    async fn disable_quotas(
+    /// List backups at a destination
+    async fn list_backups(
         &self,
         #[zbus(header)] hdr: zbus::message::Header<'_>,
         #[zbus(connection)] connection: &Connection,
+        destination_mount: String,
     ) -> (bool, String) {
         // Check authorization
-        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
-            return (false, format!("Authorization failed: {e}"));
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CREATE).await {
+            return (false, auth_failure_message(&e));
         }
 
-        result_to_dbus_response(
-            Self::disable_quotas_impl(),
-            "Failed to disable quotas"
-        )
-    }
-
-    /// Get quota usage for the snapshot filesystem
-    ///
-    /// Returns JSON string with quota usage information
-    /// This is a read-only operation and does not require authorization
-    async fn get_quota_usage(&self) -> (bool, String) {
-        result_to_dbus_response(
-            Self::get_quota_usage_impl(),
-            "Failed to get quota usage"
-        )
+        match backup::list_backups(&destination_mount) {
+            Ok(backups) => match serde_json::to_string(&backups) {
+                Ok(json) => (true, json),
+                Err(e) => (false, format!("Failed to serialize backups: {e}")),
+            },
+            Err(e) => (false, format!("Failed to list backups: {e}")),
+        }
     }
 
-    /// Set quota limit for the snapshot filesystem
+    /// Delete a backup from destination
     ///
     /// # Arguments
-    /// * `limit_bytes` - Size limit in bytes
-    async fn set_quota_limit(
+    /// * `backup_path` - Full path to the backup to delete
+    ///
+    /// # Returns
+    /// * `(success, message)` - Success status and message/error
+    async fn delete_backup(
         &self,
         #[zbus(header)] hdr: zbus::message::Header<'_>,
         #[zbus(connection)] connection: &Connection,
-        limit_bytes: u64,
+        backup_path: String,
     ) -> (bool, String) {
-        // Check authorization
-        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
-            return (false, format!("Authorization failed: {e}"));
+        // Get caller info for audit logging
+        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+
+        // Check authorization - require CREATE permission (same as creating snapshots/backups)
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CREATE).await {
+            log::warn!("Unauthorized backup deletion attempt by {} (PID {}): {}", uid, pid, backup_path);
+            return (false, auth_failure_message(&e));
         }
 
-        result_to_dbus_response(
-            Self::set_quota_limit_impl(limit_bytes),
-            "Failed to set quota limit"
-        )
+        let _operation = match shutdown::begin("backup deletion", None) {
+            Ok(operation) => operation,
+            Err(e) => return (false, error_message(&e, "")),
+        };
+
+        log::info!("User {} (PID {}) deleting backup: {}", uid, pid, backup_path);
+
+        match backup::delete_backup(&backup_path) {
+            Ok(()) => {
+                log::info!("Successfully deleted backup: {}", backup_path);
+                (true, format!("Backup deleted successfully: {}", backup_path))
+            }
+            Err(e) => {
+                log::error!("Failed to delete backup {}: {}", backup_path, e);
+                (false, error_message(&e, "Failed to delete backup"))
+            }
+        }
     }
 
-    /// Save quota configuration to /etc/waypoint/quota.toml
+    /// Apply retention policy to backups at a destination
     ///
     /// # Arguments
-    /// * `config_toml` - TOML string of quota configuration
-    async fn save_quota_config(
+    /// * `destination_mount` - Mount point of backup destination
+    /// * `retention_days` - Maximum age of backups to keep (in days)
+    /// * `filter_json` - JSON-serialized BackupFilter
+    /// * `snapshots_json` - JSON-serialized array of SnapshotInfo
+    ///
+    /// # Returns
+    /// * `(success, json_result)` - JSON array of deleted backup paths or error message
+    async fn apply_backup_retention(
         &self,
         #[zbus(header)] hdr: zbus::message::Header<'_>,
         #[zbus(connection)] connection: &Connection,
-        config_toml: String,
+        destination_mount: String,
+        retention_days: u32,
+        filter_json: String,
+        snapshots_json: String,
     ) -> (bool, String) {
         // Get caller info for audit logging
         let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
 
         // Check authorization
-        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
-            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
-            return (false, format!("Authorization failed: {e}"));
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CREATE).await {
+            log::warn!(
+                "Unauthorized retention cleanup attempt by {} (PID {}): {}",
+                uid, pid, destination_mount
+            );
+            return (false, auth_failure_message(&e));
         }
 
-        match Self::save_quota_config_impl(&config_toml) {
-            Ok(msg) => {
-                audit::log_config_change(uid, pid, "quota", true, None);
-                (true, msg)
+        let _operation = match shutdown::begin("backup retention", None) {
+            Ok(operation) => operation,
+            Err(e) => return (false, error_message(&e, "")),
+        };
+
+        log::info!(
+            "User {} (PID {}) applying retention to {}: {} days",
+            uid, pid, destination_mount, retention_days
+        );
+
+        // Deserialize filter and snapshots
+        let filter: waypoint_common::BackupFilter = match serde_json::from_str(&filter_json) {
+            Ok(f) => f,
+            Err(e) => {
+                return (false, format!("Failed to parse filter: {e}"));
+            }
+        };
+
+        let all_snapshots: Vec<waypoint_common::SnapshotInfo> =
+            match serde_json::from_str(&snapshots_json) {
+                Ok(s) => s,
+                Err(e) => {
+                    return (false, format!("Failed to parse snapshots: {e}"));
+                }
+            };
+
+        match backup::apply_backup_retention(
+            &destination_mount,
+            retention_days,
+            &filter,
+            &all_snapshots,
+        ) {
+            Ok(deleted_paths) => {
+                log::info!("Retention policy deleted {} backups", deleted_paths.len());
+                match serde_json::to_string(&deleted_paths) {
+                    Ok(json) => (true, json),
+                    Err(e) => (false, format!("Failed to serialize result: {e}")),
+                }
             }
             Err(e) => {
-                let error_msg = e.to_string();
-                audit::log_config_change(uid, pid, "quota", false, Some(&error_msg));
-                (false, format!("Failed to save quota configuration: {e}"))
+                log::error!("Failed to apply retention policy: {}", e);
+                (false, format!("Failed to apply retention: {e}"))
             }
         }
     }
 
-    /// Save exclude configuration to /etc/waypoint/exclude.toml
+    /// Get drive health statistics
+    async fn get_drive_stats(
+        &self,
+        destination_mount: String,
+    ) -> (bool, String) {
+        match backup::get_drive_stats(&destination_mount) {
+            Ok(stats) => match serde_json::to_string(&stats) {
+                Ok(json) => (true, json),
+                Err(e) => (false, format!("Failed to serialize stats: {e}")),
+            },
+            Err(e) => (false, format!("Failed to get drive stats: {e}")),
+        }
+    }
+
+    /// Verify a backup's integrity
+    ///
+    /// # Arguments
+    /// * `snapshot_path` - Full path to the original snapshot (e.g., /.snapshots/my-snapshot)
+    /// * `destination_mount` - Mount point of backup destination
+    /// * `snapshot_id` - ID/name of the snapshot to verify
+    ///
+    /// # Returns
+    /// * `(success, json_result)` - JSON containing verification details
+    async fn verify_backup(
+        &self,
+        snapshot_path: String,
+        destination_mount: String,
+        snapshot_id: String,
+    ) -> (bool, String) {
+        // Verification is read-only but still needs input validation to avoid probing arbitrary paths
+        match backup::verify_backup(&snapshot_path, &destination_mount, &snapshot_id) {
+            Ok(result) => match serde_json::to_string(&result) {
+                Ok(json) => (true, json),
+                Err(e) => (false, format!("Failed to serialize verification result: {e}")),
+            },
+            Err(e) => (false, format!("Verification failed: {e}")),
+        }
+    }
+
+    /// Compare a backup with the snapshot it was made from
     ///
     /// # Arguments
-    /// * `config_toml` - TOML string of exclude configuration
-    async fn save_exclude_config(
+    /// * `snapshot_path` - Full path to the original snapshot (e.g., /.snapshots/my-snapshot)
+    /// * `destination_mount` - Mount point of backup destination
+    /// * `snapshot_id` - ID/name of the backed up snapshot
+    ///
+    /// # Returns
+    /// * `(success, json_result)` - JSON `BackupComparison` listing differences per subvolume
+    async fn compare_backup(
         &self,
         #[zbus(header)] hdr: zbus::message::Header<'_>,
         #[zbus(connection)] connection: &Connection,
-        config_toml: String,
+        snapshot_path: String,
+        destination_mount: String,
+        snapshot_id: String,
     ) -> (bool, String) {
-        // Get caller info for audit logging
-        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
-
-        // Check authorization
-        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
-            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
-            return (false, format!("Authorization failed: {e}"));
+        // The result lists file names from the snapshot, so it needs the same rights as backing up
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CREATE).await {
+            return (false, auth_failure_message(&e));
         }
 
-        match Self::save_exclude_config_impl(&config_toml) {
-            Ok(msg) => {
-                audit::log_config_change(uid, pid, "exclude", true, None);
-                (true, msg)
-            }
-            Err(e) => {
-                let error_msg = e.to_string();
-                audit::log_config_change(uid, pid, "exclude", false, Some(&error_msg));
-                (false, format!("Failed to save exclude configuration: {e}"))
-            }
+        let result = tokio::task::spawn_blocking(move || {
+            backup::compare_backup_with_source(&snapshot_path, &destination_mount, &snapshot_id)
+        })
+        .await
+        .unwrap_or_else(|e| Err(anyhow::anyhow!("Comparison task failed: {e}")));
+
+        match result {
+            Ok(comparison) => match serde_json::to_string(&comparison) {
+                Ok(json) => (true, json),
+                Err(e) => (false, format!("Failed to serialize comparison: {e}")),
+            },
+            Err(e) => (false, error_message(&e, "Failed to compare backup")),
         }
     }
 
-    /// Update snapshot metadata (specifically size_bytes)
+    /// Describe what restoring a backup would do
     ///
     /// # Arguments
-    /// * `snapshot_json` - JSON string of the snapshot with updated metadata
-    async fn update_snapshot_metadata(
+    /// * `backup_path` - Full path to the backup on its destination
+    ///
+    /// # Returns
+    /// * `(success, json_result)` - JSON `BackupRestorePreview` with the backup's contents and
+    ///   any conflicting local snapshot
+    async fn preview_restore_from_backup(
         &self,
         #[zbus(header)] hdr: zbus::message::Header<'_>,
         #[zbus(connection)] connection: &Connection,
-        snapshot_json: String,
+        backup_path: String,
     ) -> (bool, String) {
-        // Get caller info for audit logging
-        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
-
-        // Check authorization - using configure action since we're modifying metadata
-        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CONFIGURE).await {
-            audit::log_auth_failure(uid, pid, POLKIT_ACTION_CONFIGURE, &e.to_string());
-            return (false, format!("Authorization failed: {e}"));
+        // The comparison lists file names from the snapshot, so it needs the same rights as restoring
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_RESTORE).await {
+            return (false, auth_failure_message(&e));
         }
 
-        match Self::update_snapshot_metadata_impl(&snapshot_json) {
-            Ok(msg) => {
-                log::info!("Snapshot metadata updated by uid={uid} pid={pid}");
-                (true, msg)
-            }
-            Err(e) => {
-                log::error!("Failed to update snapshot metadata: {e}");
-                (false, format!("Failed to update snapshot metadata: {e}"))
-            }
-        }
-    }
+        let result =
+            tokio::task::spawn_blocking(move || backup::preview_restore_from_backup(&backup_path))
+                .await
+                .unwrap_or_else(|e| Err(anyhow::anyhow!("Preview task failed: {e}")));
 
-    /// Scan for available backup destinations
-    ///
-    /// This is a read-only operation and does not require authorization
-    async fn scan_backup_destinations(&self) -> (bool, String) {
-        match backup::scan_backup_destinations() {
-            Ok(destinations) => match serde_json::to_string(&destinations) {
+        match result {
+            Ok(preview) => match serde_json::to_string(&preview) {
                 Ok(json) => (true, json),
-                Err(e) => (false, format!("Failed to serialize destinations: {e}")),
+                Err(e) => (false, format!("Failed to serialize restore preview: {e}")),
             },
-            Err(e) => (false, format!("Failed to scan destinations: {e}")),
+            Err(e) => (
+                false,
+                error_message(&e, "Failed to preview restore from backup"),
+            ),
         }
     }
 
-    /// Backup a snapshot to an external drive
-    ///
-    /// # Arguments
-    /// * `snapshot_path` - Full path to the snapshot (e.g., /.snapshots/my-snapshot)
-    /// * `destination_mount` - Mount point of backup destination
-    /// * `parent_snapshot` - Optional parent snapshot path for incremental backup
+    /// Restore a snapshot from backup
     ///
-    /// # Returns
-    /// * `(success, message_or_path, size_bytes)` - On success: (true, backup_path, size). On failure: (false, error, 0)
-    async fn backup_snapshot(
+    /// An empty `new_name` restores the snapshot under the backup's name.
+    async fn restore_from_backup(
         &self,
         #[zbus(header)] hdr: zbus::message::Header<'_>,
         #[zbus(connection)] connection: &Connection,
-        #[zbus(signal_context)] ctxt: zbus::SignalContext<'_>,
+        backup_path: String,
+        snapshots_dir: String,
+        new_name: String,
+    ) -> (bool, String) {
+        // Check authorization - use restore action since we're restoring a snapshot
+        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_RESTORE).await {
+            return (false, auth_failure_message(&e));
+        }
+
+        // The restored snapshot keeps the backup's name unless renamed
+        let new_name = (!new_name.is_empty()).then_some(new_name);
+        let snapshot_name = new_name.clone().unwrap_or_else(|| {
+            std::path::Path::new(&backup_path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| backup_path.clone())
+        });
+        let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let _operation = match shutdown::begin("restore from backup", Some(cancel.clone())) {
+            Ok(operation) => operation,
+            Err(e) => return (false, error_message(&e, "")),
+        };
+
+        let _lock = match snapshot_lock::try_lock(&snapshot_name, "restore from backup") {
+            Ok(lock) => lock,
+            Err(e) => return (false, error_message(&e, "")),
+        };
+
+        // Receive off the async runtime so the helper can still shut down meanwhile
+        let result = tokio::task::spawn_blocking(move || {
+            backup::restore_from_backup(&backup_path, &snapshots_dir, new_name.as_deref(), &cancel)
+        })
+        .await
+        .unwrap_or_else(|e| Err(anyhow::anyhow!("Restore task failed: {e}")));
+
+        match result {
+            Ok(restored_path) => (true, restored_path),
+            Err(e) => (false, error_message(&e, "Failed to restore from backup")),
+        }
+    }
+}
+
+impl WaypointHelper {
+    /// Run an authorized backup, emitting `BackupProgress` signals until it finishes
+    async fn run_backup(
+        ctxt: &zbus::SignalContext<'_>,
         snapshot_path: String,
         destination_mount: String,
         parent_snapshot: String,
+        format: BackupFormat,
     ) -> (bool, String, u64) {
-        // Check authorization
-        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CREATE).await {
-            return (false, format!("Authorization failed: {e}"), 0);
-        }
+        // Keep the snapshot from being deleted while it is sent
+        let snapshot_name = std::path::Path::new(&snapshot_path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| snapshot_path.clone());
+        // Cancelled if the helper has to stop before the transfer finishes
+        let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let _operation = match shutdown::begin("backup", Some(cancel.clone())) {
+            Ok(operation) => operation,
+            Err(e) => return (false, error_message(&e, ""), 0),
+        };
+
+        let _lock = match snapshot_lock::try_lock(&snapshot_name, "backup") {
+            Ok(lock) => lock,
+            Err(e) => return (false, error_message(&e, ""), 0),
+        };
 
         let parent = if parent_snapshot.is_empty() {
             None
@@ -823,7 +2511,9 @@ impl WaypointHelper {
                 &snapshot_path_clone,
                 &destination_mount_clone,
                 parent_clone.as_deref(),
+                format,
                 Some(progress_tx),
+                &cancel,
             )
         });
 
@@ -842,7 +2532,7 @@ impl WaypointHelper {
                         }).try_recv()
                     }).await {
                         if let Err(e) = Self::backup_progress(
-                            &ctxt,
+                            ctxt,
                             &progress.snapshot_id,
                             &dest_uuid_ref, // Use looked-up UUID
                             progress.bytes_transferred,
@@ -869,7 +2559,7 @@ impl WaypointHelper {
                         }).await {
                             Ok(Ok(progress)) => {
                                 let _ = Self::backup_progress(
-                                    &ctxt,
+                                    ctxt,
                                     &progress.snapshot_id,
                                     &dest_uuid_ref, // Use looked-up UUID
                                     progress.bytes_transferred,
@@ -882,10 +2572,23 @@ impl WaypointHelper {
                         }
                     }
 
+                    // Progress only ends with "complete" on success, so tell listeners about failures
+                    if !matches!(result, Ok(Ok(_))) {
+                        let _ = Self::backup_progress(
+                            ctxt,
+                            &snapshot_name,
+                            &destination_uuid,
+                            0,
+                            0,
+                            0,
+                            "failed",
+                        ).await;
+                    }
+
                     // Return backup result
                     return match result {
                         Ok(Ok((backup_path, size_bytes))) => (true, backup_path, size_bytes),
-                        Ok(Err(e)) => (false, format!("Failed to backup snapshot: {e}"), 0),
+                        Ok(Err(e)) => (false, error_message(&e, "Failed to backup snapshot"), 0),
                         Err(e) => (false, format!("Backup task failed: {e}"), 0),
                     };
                 }
@@ -893,209 +2596,204 @@ impl WaypointHelper {
         }
     }
 
-    /// List backups at a destination
-    async fn list_backups(
+    /// Create a snapshot for an authorized caller
+    ///
+    /// Shared by the D-Bus and socket transports; `created_by` is reported in
+    /// the `SnapshotCreated` signal.
+    #[allow(clippy::too_many_arguments)]
+    async fn create_snapshot_as(
         &self,
-        #[zbus(header)] hdr: zbus::message::Header<'_>,
-        #[zbus(connection)] connection: &Connection,
-        destination_mount: String,
+        ctxt: &zbus::SignalContext<'_>,
+        uid: String,
+        pid: u32,
+        created_by: &str,
+        name: String,
+        description: String,
+        subvolumes: Vec<String>,
     ) -> (bool, String) {
-        // Check authorization
-        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CREATE).await {
-            return (false, format!("Authorization failed: {e}"));
-        }
-
-        match backup::list_backups(&destination_mount) {
-            Ok(backups) => match serde_json::to_string(&backups) {
-                Ok(json) => (true, json),
-                Err(e) => (false, format!("Failed to serialize backups: {e}")),
-            },
-            Err(e) => (false, format!("Failed to list backups: {e}")),
+        // Rate limiting check
+        if let Err(wait_time) = self.rate_limiter.check_rate_limit(&uid, "create_snapshot") {
+            log::warn!("Rate limit exceeded for user {uid} creating snapshot");
+            audit::log_snapshot_create(uid, pid, &name, false, Some("rate limit exceeded"));
+            return (
+                false,
+                HelperError::new(
+                    ErrorCode::RateLimited,
+                    format!(
+                        "Rate limit exceeded. Please wait {} seconds before creating another snapshot",
+                        wait_time.as_secs()
+                    ),
+                )
+                .encode(),
+            );
         }
-    }
 
-    /// Delete a backup from destination
-    ///
-    /// # Arguments
-    /// * `backup_path` - Full path to the backup to delete
-    ///
-    /// # Returns
-    /// * `(success, message)` - Success status and message/error
-    async fn delete_backup(
-        &self,
-        #[zbus(header)] hdr: zbus::message::Header<'_>,
-        #[zbus(connection)] connection: &Connection,
-        backup_path: String,
-    ) -> (bool, String) {
-        // Get caller info for audit logging
-        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
+        let key = format!("{uid}:{name}");
+        let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let _operation = match shutdown::begin("snapshot creation", Some(cancel.clone())) {
+            Ok(operation) => operation,
+            Err(e) => {
+                audit::log_snapshot_create(uid, pid, &name, false, Some(&e.to_string()));
+                return (false, error_message(&e, ""));
+            }
+        };
+        self.create_cancellations()
+            .entry(key.clone())
+            .or_default()
+            .push(cancel.clone());
+
+        // Create the snapshot off the async runtime so a slow command doesn't stall other calls
+        let name_clone = name.clone();
+        let cancel_clone = cancel.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            Self::create_snapshot_impl(&name_clone, &description, subvolumes, &cancel_clone)
+        })
+        .await
+        .unwrap_or_else(|e| Err(anyhow::anyhow!("Snapshot task failed: {e}")));
 
-        // Check authorization - require CREATE permission (same as creating snapshots/backups)
-        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CREATE).await {
-            log::warn!("Unauthorized backup deletion attempt by {} (PID {}): {}", uid, pid, backup_path);
-            return (false, format!("Authorization failed: {e}"));
+        {
+            let mut cancellations = self.create_cancellations();
+            if let Some(flags) = cancellations.get_mut(&key) {
+                flags.retain(|flag| !std::sync::Arc::ptr_eq(flag, &cancel));
+                if flags.is_empty() {
+                    cancellations.remove(&key);
+                }
+            }
         }
 
-        log::info!("User {} (PID {}) deleting backup: {}", uid, pid, backup_path);
+        match result {
+            Ok((msg, cleanup)) => {
+                if !cleanup.snapshots.is_empty()
+                    && let Err(e) = Self::quota_cleanup(ctxt, &cleanup.snapshots).await
+                {
+                    log::error!("Failed to emit quota_cleanup signal: {e}");
+                }
 
-        match backup::delete_backup(&backup_path) {
-            Ok(()) => {
-                log::info!("Successfully deleted backup: {}", backup_path);
-                (true, format!("Backup deleted successfully: {}", backup_path))
+                // Audit log successful creation
+                audit::log_snapshot_create(uid.clone(), pid, &name, true, None);
+                // Emit signal for successful snapshot creation
+                if let Err(e) = Self::snapshot_created(ctxt, &name, created_by).await {
+                    log::error!("Failed to emit snapshot_created signal: {e}");
+                }
+
+                (true, msg)
             }
             Err(e) => {
-                log::error!("Failed to delete backup {}: {}", backup_path, e);
-                (false, format!("Failed to delete backup: {e}"))
+                // Audit log failed creation
+                let error_msg = e.to_string();
+                audit::log_snapshot_create(uid, pid, &name, false, Some(&error_msg));
+                (false, error_message(&e, "Failed to create snapshot"))
             }
         }
     }
 
-    /// Apply retention policy to backups at a destination
-    ///
-    /// # Arguments
-    /// * `destination_mount` - Mount point of backup destination
-    /// * `retention_days` - Maximum age of backups to keep (in days)
-    /// * `filter_json` - JSON-serialized BackupFilter
-    /// * `snapshots_json` - JSON-serialized array of SnapshotInfo
-    ///
-    /// # Returns
-    /// * `(success, json_result)` - JSON array of deleted backup paths or error message
-    async fn apply_backup_retention(
-        &self,
-        #[zbus(header)] hdr: zbus::message::Header<'_>,
-        #[zbus(connection)] connection: &Connection,
-        destination_mount: String,
-        retention_days: u32,
-        filter_json: String,
-        snapshots_json: String,
-    ) -> (bool, String) {
-        // Get caller info for audit logging
-        let (uid, pid) = Self::get_caller_info(&hdr, connection).await;
-
-        // Check authorization
-        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_CREATE).await {
-            log::warn!(
-                "Unauthorized retention cleanup attempt by {} (PID {}): {}",
-                uid, pid, destination_mount
-            );
-            return (false, format!("Authorization failed: {e}"));
-        }
-
-        log::info!(
-            "User {} (PID {}) applying retention to {}: {} days",
-            uid, pid, destination_mount, retention_days
-        );
-
-        // Deserialize filter and snapshots
-        let filter: waypoint_common::BackupFilter = match serde_json::from_str(&filter_json) {
-            Ok(f) => f,
+    /// Delete a snapshot for an authorized caller
+    async fn delete_snapshot_as(&self, uid: String, pid: u32, name: String) -> (bool, String) {
+        let _operation = match shutdown::begin("snapshot deletion", None) {
+            Ok(operation) => operation,
             Err(e) => {
-                return (false, format!("Failed to parse filter: {e}"));
+                audit::log_snapshot_delete(uid, pid, &name, false, Some(&e.to_string()));
+                return (false, error_message(&e, ""));
             }
         };
 
-        let all_snapshots: Vec<waypoint_common::SnapshotInfo> =
-            match serde_json::from_str(&snapshots_json) {
-                Ok(s) => s,
-                Err(e) => {
-                    return (false, format!("Failed to parse snapshots: {e}"));
-                }
-            };
+        // Refuse while a backup or restore of this snapshot is running
+        let _lock = match snapshot_lock::try_lock(&name, "delete") {
+            Ok(lock) => lock,
+            Err(e) => {
+                audit::log_snapshot_delete(uid, pid, &name, false, Some(&e.to_string()));
+                return (false, error_message(&e, ""));
+            }
+        };
 
-        match backup::apply_backup_retention(
-            &destination_mount,
-            retention_days,
-            &filter,
-            &all_snapshots,
-        ) {
-            Ok(deleted_paths) => {
-                log::info!("Retention policy deleted {} backups", deleted_paths.len());
-                match serde_json::to_string(&deleted_paths) {
-                    Ok(json) => (true, json),
-                    Err(e) => (false, format!("Failed to serialize result: {e}")),
-                }
+        // Delete the snapshot
+        match btrfs::delete_snapshot(&name) {
+            Ok(_) => {
+                audit::log_snapshot_delete(uid, pid, &name, true, None);
+                (true, format!("Snapshot '{name}' deleted successfully"))
             }
             Err(e) => {
-                log::error!("Failed to apply retention policy: {}", e);
-                (false, format!("Failed to apply retention: {e}"))
+                let error_msg = e.to_string();
+                audit::log_snapshot_delete(uid, pid, &name, false, Some(&error_msg));
+                (false, error_message(&e, "Failed to delete snapshot"))
             }
         }
     }
 
-    /// Get drive health statistics
-    async fn get_drive_stats(
-        &self,
-        destination_mount: String,
-    ) -> (bool, String) {
-        match backup::get_drive_stats(&destination_mount) {
-            Ok(stats) => match serde_json::to_string(&stats) {
-                Ok(json) => (true, json),
-                Err(e) => (false, format!("Failed to serialize stats: {e}")),
-            },
-            Err(e) => (false, format!("Failed to get drive stats: {e}")),
-        }
-    }
+    /// Roll back to a snapshot for an authorized caller
+    async fn restore_snapshot_as(&self, uid: String, pid: u32, name: String) -> (bool, String) {
+        let _operation = match shutdown::begin("rollback", None) {
+            Ok(operation) => operation,
+            Err(e) => {
+                audit::log_snapshot_restore(uid, pid, &name, false, Some(&e.to_string()));
+                return (false, error_message(&e, ""));
+            }
+        };
 
-    /// Verify a backup's integrity
-    ///
-    /// # Arguments
-    /// * `snapshot_path` - Full path to the original snapshot (e.g., /.snapshots/my-snapshot)
-    /// * `destination_mount` - Mount point of backup destination
-    /// * `snapshot_id` - ID/name of the snapshot to verify
-    ///
-    /// # Returns
-    /// * `(success, json_result)` - JSON containing verification details
-    async fn verify_backup(
-        &self,
-        snapshot_path: String,
-        destination_mount: String,
-        snapshot_id: String,
-    ) -> (bool, String) {
-        // Verification is read-only but still needs input validation to avoid probing arbitrary paths
-        match backup::verify_backup(&snapshot_path, &destination_mount, &snapshot_id) {
-            Ok(result) => match serde_json::to_string(&result) {
-                Ok(json) => (true, json),
-                Err(e) => (false, format!("Failed to serialize verification result: {e}")),
-            },
-            Err(e) => (false, format!("Verification failed: {e}")),
+        let _lock = match snapshot_lock::try_lock(&name, "restore") {
+            Ok(lock) => lock,
+            Err(e) => {
+                audit::log_snapshot_restore(uid, pid, &name, false, Some(&e.to_string()));
+                return (false, error_message(&e, ""));
+            }
+        };
+
+        // Perform rollback
+        match Self::restore_snapshot_impl(&name) {
+            Ok(_) => {
+                audit::log_snapshot_restore(uid, pid, &name, true, None);
+                (true, format!("Snapshot '{name}' restored successfully. Reboot to apply changes."))
+            }
+            Err(e) => {
+                let error_msg = e.to_string();
+                audit::log_snapshot_restore(uid, pid, &name, false, Some(&error_msg));
+                (false, error_message(&e, "Failed to restore snapshot"))
+            }
         }
     }
 
-    /// Restore a snapshot from backup
-    async fn restore_from_backup(
-        &self,
-        #[zbus(header)] hdr: zbus::message::Header<'_>,
-        #[zbus(connection)] connection: &Connection,
-        backup_path: String,
-        snapshots_dir: String,
-    ) -> (bool, String) {
-        // Check authorization - use restore action since we're restoring a snapshot
-        if let Err(e) = check_authorization(&hdr, connection, POLKIT_ACTION_RESTORE).await {
-            return (false, format!("Authorization failed: {e}"));
-        }
+    /// Apply retention cleanup for an authorized caller
+    async fn cleanup_snapshots_as(&self, schedule_based: bool) -> (bool, String) {
+        let _operation = match shutdown::begin("snapshot cleanup", None) {
+            Ok(operation) => operation,
+            Err(e) => return (false, error_message(&e, "")),
+        };
 
-        match backup::restore_from_backup(&backup_path, &snapshots_dir) {
-            Ok(restored_path) => (true, restored_path),
-            Err(e) => (false, format!("Failed to restore from backup: {e}")),
-        }
+        // Perform cleanup
+        result_to_dbus_response(
+            Self::cleanup_snapshots_impl(schedule_based),
+            "Cleanup failed"
+        )
     }
-}
 
-impl WaypointHelper {
     fn create_snapshot_impl(
         name: &str,
         description: &str,
         subvolumes: Vec<String>,
-    ) -> Result<String> {
+        cancel: &std::sync::atomic::AtomicBool,
+    ) -> Result<(String, QuotaCleanupReport)> {
+        // Quota cleanup would fail the same way on a read-only filesystem
+        filesystem_stats::check_writable(&WaypointConfig::new().snapshot_dir)?;
+
         // Check quota and cleanup if needed
-        if let Err(e) = Self::check_quota_and_cleanup() {
+        let cleanup = Self::check_quota_and_cleanup(false).unwrap_or_else(|e| {
             log::warn!("Failed to check quota before snapshot: {e}");
             // Continue anyway - quota check is not critical
-        }
+            QuotaCleanupReport::default()
+        });
+
+        // Fail with an actionable error instead of btrfs' bare ENOSPC
+        filesystem_stats::check_metadata_space(&WaypointConfig::new().snapshot_dir)?;
 
         // Get installed packages
-        let packages =
-            packages::get_installed_packages().context("Failed to get installed packages")?;
+        let packages = packages::get_installed_packages(Some(cancel))
+            .context("Failed to get installed packages")?;
+
+        // Capture the running kernel so the restore preview can show kernel changes
+        let kernel_version = btrfs::get_kernel_version();
+        if kernel_version.is_none() {
+            log::warn!("Could not detect kernel version for snapshot {name}");
+        }
 
         // Convert String paths to PathBuf
         let subvol_paths: Vec<std::path::PathBuf> = subvolumes
@@ -1103,11 +2801,22 @@ impl WaypointHelper {
             .map(std::path::PathBuf::from)
             .collect();
 
+        // With quotas enabled, group snapshots so the quota total only counts them
+        let qgroup = btrfs::snapshot_qgroup();
+
         // Create btrfs snapshot
-        btrfs::create_snapshot(name, Some(description), packages, subvol_paths)
-            .context("Failed to create btrfs snapshot")?;
+        btrfs::create_snapshot(
+            name,
+            Some(description),
+            packages,
+            kernel_version,
+            subvol_paths,
+            qgroup,
+            Some(cancel),
+        )
+        .context("Failed to create btrfs snapshot")?;
 
-        Ok(format!("Snapshot '{name}' created successfully"))
+        Ok((format!("Snapshot '{name}' created successfully"), cleanup))
     }
 
     fn restore_snapshot_impl(name: &str) -> Result<String> {
@@ -1129,7 +2838,7 @@ impl WaypointHelper {
             }
         }
 
-        let packages = packages::get_installed_packages()
+        let packages = packages::get_installed_packages(None)
             .context("Failed to get installed packages for backup")?;
 
         // Backup only root filesystem
@@ -1138,7 +2847,10 @@ impl WaypointHelper {
             &backup_name,
             Some("Pre-rollback backup"),
             packages,
+            btrfs::get_kernel_version(),
             root_only,
+            btrfs::snapshot_qgroup(),
+            None,
         )
         .context("Failed to create pre-rollback backup")?;
 
@@ -1153,13 +2865,14 @@ impl WaypointHelper {
             log::info!("Successfully cleaned up orphaned writable snapshots after restore");
         }
 
+        hooks::run_post_restore_hook(name, &backup_name);
+
         Ok(format!(
             "Snapshot '{name}' will be active after reboot. Backup created: '{backup_name}'"
         ))
     }
 
     fn cleanup_snapshots_impl(schedule_based: bool) -> Result<String> {
-        use std::collections::HashSet;
         use waypoint_common::WaypointConfig;
         use waypoint_common::schedules::SchedulesConfig;
         use waypoint_common::retention::{apply_timeline_retention, SnapshotForRetention};
@@ -1167,31 +2880,14 @@ impl WaypointHelper {
         let config = WaypointConfig::new();
         let snapshots = btrfs::list_snapshots().context("Failed to list snapshots")?;
 
-        // Load snapshot metadata to check for pinned/favorited snapshots
-        #[derive(Deserialize)]
-        struct SnapshotMetadataEntry {
-            id: String,
-            #[serde(default)]
-            is_favorite: bool,
-        }
-
-        let favorited_ids: HashSet<String> = {
-            if let Ok(content) = std::fs::read_to_string(&config.metadata_file) {
-                if let Ok(metadata) = serde_json::from_str::<Vec<SnapshotMetadataEntry>>(&content) {
-                    metadata
-                        .iter()
-                        .filter(|m| m.is_favorite)
-                        .map(|m| m.id.clone())
-                        .collect()
-                } else {
-                    HashSet::new()
-                }
-            } else {
-                HashSet::new()
-            }
-        };
+        // Pinned snapshots and protected categories such as milestones are never deleted
+        let protected: std::collections::HashSet<&str> = snapshots
+            .iter()
+            .filter(|s| s.is_favorite || s.category.is_protected())
+            .map(|s| s.name.as_str())
+            .collect();
 
-        let to_delete = if schedule_based {
+        let mut to_delete = if schedule_based {
             // Use per-schedule retention from schedules.toml
             let schedules = SchedulesConfig::load_from_file(&config.schedules_config)
                 .context("Failed to load schedules configuration")?;
@@ -1206,7 +2902,11 @@ impl WaypointHelper {
 
                 let matching: Vec<_> = snapshots
                     .iter()
-                    .filter(|s| s.name.starts_with(&schedule.prefix))
+                    .filter(|s| {
+                        schedules
+                            .retention_schedule(&s.name, s.retention_group.as_deref())
+                            .is_some_and(|owner| owner.prefix == schedule.prefix)
+                    })
                     .collect();
 
                 let now = chrono::Utc::now();
@@ -1257,23 +2957,35 @@ impl WaypointHelper {
                     legacy_delete
                 };
 
-                // Filter out favorited snapshots
+                // Filter out favorites and milestones
                 for name in delete_list {
-                    if !favorited_ids.contains(&name) {
+                    if !protected.contains(name.as_str()) {
                         all_to_delete.push(name);
                     }
                 }
             }
             all_to_delete
         } else {
-            // Legacy global retention policy is not implemented
-            anyhow::bail!(
-                "Legacy global retention policy is not implemented. \
-                 Please use --schedule-based flag with waypoint-cli cleanup. \
-                 Schedule-based retention allows fine-grained control per backup schedule."
+            // Legacy global retention policy is not implemented, so only expiry applies
+            log::warn!(
+                "Legacy global retention policy is not implemented, only deleting expired \
+                 snapshots. Use --schedule-based with waypoint-cli cleanup for retention."
             );
+            Vec::new()
         };
 
+        // Expired snapshots go whatever their schedule keeps, but favorites and milestones stay
+        let now = chrono::Utc::now();
+        for snapshot in &snapshots {
+            if snapshot.is_expired(now)
+                && !protected.contains(snapshot.name.as_str())
+                && !to_delete.contains(&snapshot.name)
+            {
+                log::info!("Snapshot {} expired, deleting it", snapshot.name);
+                to_delete.push(snapshot.name.clone());
+            }
+        }
+
         if to_delete.is_empty() {
             return Ok("No snapshots to clean up".to_string());
         }
@@ -1290,6 +3002,15 @@ impl WaypointHelper {
                 failed.push(snapshot_name.clone());
                 continue;
             }
+            // Busy snapshots are left for the next cleanup run
+            let _lock = match snapshot_lock::try_lock(snapshot_name, "delete") {
+                Ok(lock) => lock,
+                Err(e) => {
+                    log::warn!("Skipping snapshot '{snapshot_name}': {e}");
+                    failed.push(snapshot_name.clone());
+                    continue;
+                }
+            };
             match btrfs::delete_snapshot(snapshot_name) {
                 Ok(_) => {
                     log::info!("Deleted old snapshot: {snapshot_name}");
@@ -1316,15 +3037,18 @@ impl WaypointHelper {
         file_paths: Vec<String>,
         target_directory: &str,
         overwrite: bool,
+        verify: bool,
+        nested_subvolumes: NestedSubvolumeMode,
     ) -> Result<String> {
         use std::fs;
+        use std::os::unix::fs::MetadataExt;
         use std::path::{Component, Path, PathBuf};
 
         waypoint_common::validate_snapshot_name(snapshot_name)
             .map_err(|e| anyhow::anyhow!("Invalid snapshot name '{snapshot_name}': {e}"))?;
 
         let config = WaypointConfig::new();
-        let snapshot_base_dir = config.snapshot_dir.join(snapshot_name);
+        let snapshot_base_dir = btrfs::locate_snapshot(snapshot_name);
 
         // Load snapshot metadata (from global metadata file) to get list of subvolumes
         let metadata_snapshot = crate::btrfs::get_snapshot_metadata(snapshot_name)
@@ -1398,33 +3122,50 @@ impl WaypointHelper {
             Ok(())
         }
 
-        fn sanitize_absolute_path(path: &str) -> Result<PathBuf> {
-            let candidate = Path::new(path);
-            ensure_safe_absolute(candidate)?;
-            Ok(candidate.to_path_buf())
-        }
+        // Exclude patterns are honored while recursing into restored directories
+        let exclude_config = ExcludeConfig::load().unwrap_or_else(|e| {
+            log::warn!("Failed to load exclude configuration, using defaults: {e}");
+            ExcludeConfig::default()
+        });
+        let excludes: Vec<ExcludePattern> =
+            exclude_config.enabled_patterns().into_iter().cloned().collect();
 
         let mut restored_count = 0;
+        let mut excluded_count = 0;
+        let mut verified_count = 0;
+        let mut nested_skipped_count = 0;
         let mut failed_files = Vec::new();
         let use_custom_target = !target_directory.is_empty();
         let custom_target_base = if use_custom_target {
-            let base_path = Path::new(target_directory);
-            ensure_safe_absolute(base_path)?;
-            Some(base_path.to_path_buf())
+            let base_path = waypoint_common::decode_path(target_directory);
+            ensure_safe_absolute(&base_path)?;
+            Some(base_path)
         } else {
             None
         };
 
+        // Subvolumes can only be recreated on btrfs; check before restoring anything
+        if matches!(nested_subvolumes, NestedSubvolumeMode::Subvolume) {
+            match &custom_target_base {
+                Some(base_dir) => ensure_btrfs_target(base_dir)?,
+                None => {
+                    for file_path in &file_paths {
+                        let decoded = waypoint_common::decode_path(file_path);
+                        ensure_btrfs_target(&Path::new("/").join(decoded))?;
+                    }
+                }
+            }
+        }
+
         for file_path in &file_paths {
-            // Ensure path starts with /
-            let normalized_path = if file_path.starts_with('/') {
-                file_path.to_string()
-            } else {
-                format!("/{file_path}")
-            };
+            // Paths are encoded so names that aren't valid UTF-8 arrive intact
+            let decoded = waypoint_common::decode_path(file_path);
+            let path_buf = Path::new("/").join(&decoded);
+            // Encoded form for messages and the failed-files list
+            let normalized_path = waypoint_common::encode_path(&path_buf);
 
             // Validate path structure to prevent traversal outside the snapshot
-            let path_buf = sanitize_absolute_path(&normalized_path).map_err(|e| {
+            ensure_safe_absolute(&path_buf).map_err(|e| {
                 anyhow::anyhow!("Invalid restore path '{normalized_path}': {e}")
             })?;
 
@@ -1444,11 +3185,12 @@ impl WaypointHelper {
             })?;
 
             // Verify the canonicalized path is still within the expected snapshot directory
-            if !snapshot_root.starts_with(&config.snapshot_dir) {
+            let snapshot_dir = snapshot_base_dir.parent().unwrap_or(&config.snapshot_dir);
+            if !snapshot_root.starts_with(snapshot_dir) {
                 anyhow::bail!(
                     "Security: Subvolume path resolves outside snapshot directory. \
                      Expected under {}, got {}",
-                    config.snapshot_dir.display(),
+                    snapshot_dir.display(),
                     snapshot_root.display()
                 );
             }
@@ -1479,7 +3221,7 @@ impl WaypointHelper {
                 base_dir.join(filename)
             } else {
                 // Restore to original location
-                sanitize_absolute_path(&normalized_path)?
+                path_buf.clone()
             };
 
             // Check if target exists
@@ -1564,16 +3306,63 @@ impl WaypointHelper {
                             failed_files.push(normalized_path.clone());
                         }
                     } else if metadata.is_dir() {
-                        if let Err(e) = copy_dir_recursive(&snapshot_root, &source, &target) {
-                            log::error!("Failed to restore directory {normalized_path}: {e}");
-                            failed_files.push(normalized_path.clone());
-                        } else {
-                            restored_count += 1;
+                        // The selected directory may itself be a nested subvolume
+                        if source != snapshot_root {
+                            let parent = source.parent().unwrap_or(&snapshot_root);
+                            let nested = fs::metadata(parent)
+                                .map_err(anyhow::Error::from)
+                                .and_then(|parent| {
+                                    handle_nested_subvolume(
+                                        &metadata,
+                                        parent.dev(),
+                                        &source,
+                                        &target,
+                                        nested_subvolumes,
+                                    )
+                                });
+                            match nested {
+                                Ok(true) => {}
+                                Ok(false) => {
+                                    nested_skipped_count += 1;
+                                    continue;
+                                }
+                                Err(e) => {
+                                    log::error!(
+                                        "Failed to restore directory {normalized_path}: {e}"
+                                    );
+                                    failed_files.push(normalized_path.clone());
+                                    continue;
+                                }
+                            }
+                        }
+
+                        match copy_dir_recursive(
+                            &snapshot_root,
+                            &source,
+                            &target,
+                            &path_buf,
+                            &excludes,
+                            verify,
+                            nested_subvolumes,
+                        ) {
+                            Ok(stats) => {
+                                restored_count += 1;
+                                excluded_count += stats.skipped;
+                                verified_count += stats.verified;
+                                nested_skipped_count += stats.nested_skipped;
+                            }
+                            Err(e) => {
+                                log::error!("Failed to restore directory {normalized_path}: {e}");
+                                failed_files.push(normalized_path.clone());
+                            }
                         }
                     } else if metadata.is_file() {
                         if let Err(e) = fs::copy(&source, &target) {
                             log::error!("Failed to restore file {normalized_path}: {e}");
                             failed_files.push(normalized_path.clone());
+                        } else if let Err(e) = verify.then(|| verify_copy(&source, &target)).transpose() {
+                            log::error!("Verification failed for {normalized_path}: {e}");
+                            failed_files.push(normalized_path.clone());
                         } else {
                             if let Err(e) = preserve_metadata(&source, &target) {
                                 log::warn!(
@@ -1583,6 +3372,7 @@ impl WaypointHelper {
                                 );
                             }
                             restored_count += 1;
+                            verified_count += usize::from(verify);
                         }
                     } else {
                         log::warn!("Unsupported file type in snapshot: {normalized_path}");
@@ -1596,14 +3386,29 @@ impl WaypointHelper {
             }
         }
 
+        let mut excluded_note = if excluded_count > 0 {
+            format!(", skipped {excluded_count} excluded item(s)")
+        } else {
+            String::new()
+        };
+        if nested_skipped_count > 0 {
+            excluded_note.push_str(&format!(
+                ", skipped {nested_skipped_count} nested subvolume(s)"
+            ));
+        }
+        if verify {
+            excluded_note.push_str(&format!(", verified {verified_count} file(s)"));
+        }
+
         if failed_files.is_empty() {
             Ok(format!(
-                "Successfully restored {restored_count} file(s) from snapshot '{snapshot_name}'"
+                "Successfully restored {restored_count} file(s) from snapshot '{snapshot_name}'{excluded_note}"
             ))
         } else {
             Ok(format!(
-                "Restored {} file(s), failed to restore {}: {:?}",
+                "Restored {} file(s){}, failed to restore {}: {:?}",
                 restored_count,
+                excluded_note,
                 failed_files.len(),
                 failed_files
             ))
@@ -1611,17 +3416,19 @@ impl WaypointHelper {
     }
 
     /// Compare two snapshots using find + diff
-    fn compare_snapshots_impl(old_snapshot_name: &str, new_snapshot_name: &str) -> Result<String> {
-        use std::process::{Command, Stdio};
-
+    fn compare_snapshots_impl(
+        old_snapshot_name: &str,
+        new_snapshot_name: &str,
+        progress: Option<&std::sync::mpsc::SyncSender<CompareProgress>>,
+        cancel: &std::sync::atomic::AtomicBool,
+    ) -> Result<(String, u64)> {
         waypoint_common::validate_snapshot_name(old_snapshot_name)
             .map_err(|e| anyhow::anyhow!("Invalid snapshot name '{old_snapshot_name}': {e}"))?;
         waypoint_common::validate_snapshot_name(new_snapshot_name)
             .map_err(|e| anyhow::anyhow!("Invalid snapshot name '{new_snapshot_name}': {e}"))?;
 
-        let config = WaypointConfig::new();
-        let old_path = config.snapshot_dir.join(old_snapshot_name).join("root");
-        let new_path = config.snapshot_dir.join(new_snapshot_name).join("root");
+        let old_path = btrfs::locate_snapshot(old_snapshot_name).join("root");
+        let new_path = btrfs::locate_snapshot(new_snapshot_name).join("root");
 
         // Verify both snapshots exist
         if !old_path.exists() {
@@ -1631,49 +3438,31 @@ impl WaypointHelper {
             anyhow::bail!("New snapshot not found: {}", new_path.display());
         }
 
-        // Generate file listing for old snapshot
-        // Format: type path size mtime
-        // type: f=file, d=directory, l=symlink
-        let old_output = Command::new("find")
-            .arg(&old_path)
-            .arg("-xdev") // Don't cross filesystem boundaries
-            .arg("-printf")
-            .arg("%y %P %s %T@\n") // type, path (relative), size, mtime
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .context("Failed to run find on old snapshot")?;
-
-        if !old_output.status.success() {
-            let stderr = String::from_utf8_lossy(&old_output.stderr);
-            anyhow::bail!("find failed on old snapshot: {}", stderr);
-        }
-
-        // Generate file listing for new snapshot
-        let new_output = Command::new("find")
-            .arg(&new_path)
-            .arg("-xdev")
-            .arg("-printf")
-            .arg("%y %P %s %T@\n")
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .context("Failed to run find on new snapshot")?;
-
-        if !new_output.status.success() {
-            let stderr = String::from_utf8_lossy(&new_output.stderr);
-            anyhow::bail!("find failed on new snapshot: {}", stderr);
-        }
-
-        // Parse old snapshot file list into HashMap
-        let old_files = parse_find_output(&String::from_utf8_lossy(&old_output.stdout))?;
-        let new_files = parse_find_output(&String::from_utf8_lossy(&new_output.stdout))?;
+        // Stream both file listings, reporting progress as entries are parsed
+        let mut scanned = 0;
+        let old_files = scan_snapshot_files(&old_path, &mut scanned, progress, cancel)?;
+        let new_files = scan_snapshot_files(&new_path, &mut scanned, progress, cancel)?;
 
         // Compare and detect changes
-        let changes = compare_file_lists(&old_files, &new_files);
+        let (changes, total) = compare_file_lists(&old_files, &new_files);
+        if let Some(tx) = progress {
+            let _ = tx.try_send(CompareProgress {
+                entries_scanned: scanned,
+                changes_found: total,
+                stage: "comparing",
+            });
+        }
+
+        if total > changes.len() as u64 {
+            log::warn!(
+                "Comparison of {old_snapshot_name} and {new_snapshot_name} found {total} changes, returning the first {}",
+                changes.len()
+            );
+        }
 
         // Serialize to JSON
-        serde_json::to_string(&changes).context("Failed to serialize changes to JSON")
+        let json = serde_json::to_string(&changes).context("Failed to serialize changes to JSON")?;
+        Ok((json, total))
     }
 
     /// Enable quotas on the btrfs filesystem
@@ -1744,6 +3533,23 @@ impl WaypointHelper {
             ],
         )?;
 
+        // Quota limit is stored in our config file, not in btrfs
+        // (btrfs quotas are per-subvolume, we want total limit)
+        let limit = QuotaConfig::load().unwrap_or_default().total_limit_bytes;
+
+        // The snapshot qgroup counts extents shared between snapshots once
+        if let Some((referenced, exclusive)) =
+            btrfs::parse_qgroup_usage(&stdout, btrfs::SNAPSHOT_QGROUP)
+        {
+            let usage = QuotaUsage {
+                referenced,
+                exclusive,
+                limit,
+            };
+            return serde_json::to_string(&usage)
+                .context("Failed to serialize quota usage to JSON");
+        }
+
         // Parse qgroup output
         // Format: qgroupid rfer excl max_rfer max_excl
         // Sum up all level-0 qgroups (snapshots)
@@ -1824,11 +3630,6 @@ impl WaypointHelper {
             );
         }
 
-        // Get limit from our config file, not from btrfs
-        // (btrfs quotas are per-subvolume, we want total limit)
-        let quota_config = QuotaConfig::load().unwrap_or_default();
-        let limit = quota_config.total_limit_bytes;
-
         let usage = QuotaUsage {
             referenced: total_referenced,
             exclusive: total_exclusive,
@@ -1850,8 +3651,12 @@ impl WaypointHelper {
         Ok("Quota limit updated in configuration".to_string())
     }
 
-    /// Check quota usage and cleanup old snapshots if needed
-    fn check_quota_and_cleanup() -> Result<()> {
+    /// Delete the oldest snapshots while quota usage is above the cleanup threshold
+    ///
+    /// Favorites and milestones are kept, like in schedule-based cleanup. With
+    /// `dry_run` nothing is deleted and the report lists what would be,
+    /// estimated from each snapshot's exclusive size.
+    fn check_quota_and_cleanup(dry_run: bool) -> Result<QuotaCleanupReport> {
         use waypoint_common::QuotaConfig;
 
         // Load quota configuration
@@ -1859,38 +3664,79 @@ impl WaypointHelper {
 
         // Only proceed if quotas are enabled and auto-cleanup is on
         if !quota_config.enabled || !quota_config.auto_cleanup {
-            return Ok(());
+            return Ok(QuotaCleanupReport {
+                dry_run,
+                ..Default::default()
+            });
         }
 
         // Get current usage
         let usage_json = Self::get_quota_usage_impl()?;
         let usage: waypoint_common::QuotaUsage = serde_json::from_str(&usage_json)?;
 
+        Self::cleanup_for_quota(dry_run, &quota_config, &usage, &WaypointConfig::new())
+    }
+
+    /// Quota cleanup once the configuration and current usage are known
+    fn cleanup_for_quota(
+        dry_run: bool,
+        quota_config: &waypoint_common::QuotaConfig,
+        usage: &waypoint_common::QuotaUsage,
+        config: &WaypointConfig,
+    ) -> Result<QuotaCleanupReport> {
+        let mut report = QuotaCleanupReport {
+            dry_run,
+            ..Default::default()
+        };
+
         // Check if we exceed the threshold
-        if usage.exceeds_threshold(quota_config.cleanup_threshold) {
+        if !usage.exceeds_threshold(quota_config.cleanup_threshold) {
+            return Ok(report);
+        }
+
+        if !dry_run {
             log::info!(
                 "Quota usage exceeds threshold ({}%), triggering cleanup",
                 quota_config.cleanup_threshold * 100.0
             );
+        }
 
-            // Load snapshots and find oldest ones
-            let metadata_path = WaypointConfig::new().metadata_file;
-            if !metadata_path.exists() {
-                return Ok(());
-            }
+        // Load snapshots and find oldest ones
+        if !config.metadata_file.exists() {
+            return Ok(report);
+        }
 
-            let contents = std::fs::read_to_string(&metadata_path)?;
-            let mut snapshots: Vec<waypoint_common::SnapshotInfo> =
-                serde_json::from_str(&contents)?;
+        let contents = std::fs::read_to_string(&config.metadata_file)?;
+        let mut snapshots: Vec<waypoint_common::SnapshotInfo> =
+            MetadataFormat::from_path(&config.metadata_file).parse(&contents)?;
 
-            // Sort by timestamp (oldest first)
-            snapshots.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        // Quotas cover the primary snapshot directory's filesystem only
+        snapshots
+            .retain(|snapshot| config.locate_snapshot_dir(&snapshot.name) == config.snapshot_dir);
 
-            // Delete oldest snapshots until we're below threshold
-            let target_usage = quota_config.cleanup_threshold * 0.8; // Target 80% of threshold
-            let mut deleted_count = 0;
+        // Sort by timestamp (oldest first)
+        snapshots.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
 
-            for snapshot in snapshots.iter() {
+        let favorites = Self::load_protected_snapshots(config);
+        let (protected, candidates): (Vec<_>, Vec<_>) = snapshots
+            .into_iter()
+            .partition(|snapshot| favorites.contains(&snapshot.name));
+
+        // Delete oldest snapshots until we're below threshold
+        let target_usage = quota_config.cleanup_threshold * 0.8; // Target 80% of threshold
+
+        if dry_run {
+            let sized: Vec<(String, u64)> = candidates
+                .iter()
+                .map(|snapshot| {
+                    let path = btrfs::locate_snapshot(&snapshot.name);
+                    let size = btrfs::get_snapshot_exclusive_size(&path).unwrap_or(0);
+                    (snapshot.name.clone(), size)
+                })
+                .collect();
+            report.snapshots = usage.plan_cleanup(&sized, target_usage);
+        } else {
+            for snapshot in candidates.iter() {
                 // Re-check usage after each deletion
                 let current_usage_json = Self::get_quota_usage_impl()?;
                 let current_usage: waypoint_common::QuotaUsage =
@@ -1902,68 +3748,303 @@ impl WaypointHelper {
                     }
                 }
 
-                // Delete this snapshot
+                // Delete this snapshot (unless another operation is using it)
+                let _lock = match snapshot_lock::try_lock(&snapshot.name, "delete") {
+                    Ok(lock) => lock,
+                    Err(e) => {
+                        log::warn!("Auto-cleanup: Skipping snapshot '{}': {e}", snapshot.name);
+                        continue;
+                    }
+                };
                 log::info!("Auto-cleanup: Deleting snapshot '{}'", snapshot.name);
                 if let Err(e) = btrfs::delete_snapshot(&snapshot.name) {
                     log::error!("Failed to delete snapshot '{}': {}", snapshot.name, e);
                     continue;
                 }
 
-                deleted_count += 1;
+                report.snapshots.push(snapshot.name.clone());
             }
 
-            if deleted_count > 0 {
+            if !report.snapshots.is_empty() {
                 log::info!(
-                    "Auto-cleanup: Deleted {deleted_count} snapshot(s) to free quota space"
+                    "Auto-cleanup: Deleted {} snapshot(s) to free quota space",
+                    report.snapshots.len()
                 );
             }
         }
 
-        Ok(())
-    }
+        // Favorites and milestones that cleanup reached, or all of them if nothing else could go
+        let cutoff = report
+            .snapshots
+            .last()
+            .and_then(|name| candidates.iter().find(|s| &s.name == name))
+            .map(|snapshot| snapshot.timestamp);
+        report.skipped_favorites = protected
+            .iter()
+            .filter(|snapshot| cutoff.is_none_or(|cutoff| snapshot.timestamp <= cutoff))
+            .map(|snapshot| snapshot.name.clone())
+            .collect();
+        if !report.skipped_favorites.is_empty() && !dry_run {
+            log::info!(
+                "Auto-cleanup: Kept {} favorite or milestone snapshot(s)",
+                report.skipped_favorites.len()
+            );
+        }
+
+        Ok(report)
+    }
+
+    /// Names and IDs of favorites and milestones in the metadata file
+    ///
+    /// Cleanup never deletes these. Both names and IDs are collected because
+    /// callers refer to snapshots by either.
+    fn load_protected_snapshots(config: &WaypointConfig) -> std::collections::HashSet<String> {
+        #[derive(Deserialize)]
+        struct SnapshotMetadataEntry {
+            id: String,
+            #[serde(default)]
+            name: Option<String>,
+            #[serde(default)]
+            is_favorite: bool,
+            #[serde(default)]
+            category: SnapshotCategory,
+        }
+
+        std::fs::read_to_string(&config.metadata_file)
+            .ok()
+            .and_then(|content| {
+                MetadataFormat::from_path(&config.metadata_file)
+                    .parse::<SnapshotMetadataEntry>(&content)
+                    .ok()
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|entry| entry.is_favorite || entry.category.is_protected())
+            .flat_map(|entry| std::iter::once(entry.id).chain(entry.name))
+            .collect()
+    }
+
+    /// Save quota configuration to file
+    fn save_quota_config_impl(config_toml: &str) -> Result<String> {
+        use waypoint_common::QuotaConfig;
+
+        // Validate TOML by parsing it
+        let _config: QuotaConfig =
+            toml::from_str(config_toml).context("Invalid quota configuration")?;
+
+        let config_path = QuotaConfig::default_path();
+
+        // Create parent directory if it doesn't exist
+        if let Some(parent) = config_path.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create config directory")?;
+        }
+
+        // Write configuration
+        std::fs::write(&config_path, config_toml)
+            .context("Failed to write quota configuration file")?;
+
+        Ok("Quota configuration saved successfully".to_string())
+    }
+
+    /// Save exclude configuration to file
+    fn save_exclude_config_impl(config_toml: &str) -> Result<String> {
+        use waypoint_common::ExcludeConfig;
+
+        // Validate TOML by parsing it
+        let config: ExcludeConfig =
+            toml::from_str(config_toml).context("Invalid exclude configuration")?;
+
+        // Only new patterns must be valid; older ones that never match are kept
+        let previous = ExcludeConfig::load().unwrap_or_default();
+        let warnings = config
+            .validate_changes(&previous)
+            .map_err(|e| anyhow::anyhow!("Invalid exclude pattern: {e}"))?;
+        for warning in &warnings {
+            log::warn!("Keeping invalid exclude pattern: {warning}");
+        }
+
+        let config_path = std::path::PathBuf::from("/etc/waypoint/exclude.toml");
+
+        // Create parent directory if it doesn't exist
+        if let Some(parent) = config_path.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create config directory")?;
+        }
+
+        // Write configuration
+        std::fs::write(&config_path, config_toml)
+            .context("Failed to write exclude configuration file")?;
+
+        if !warnings.is_empty() {
+            return Ok(format!(
+                "Exclude configuration saved; {} pattern(s) can never match: {}",
+                warnings.len(),
+                warnings.join("; ")
+            ));
+        }
+        Ok("Exclude configuration saved successfully".to_string())
+    }
+
+    /// System configuration files included in config bundles
+    fn system_config_paths() -> [(&'static str, std::path::PathBuf); 3] {
+        [
+            ("schedules", WaypointConfig::new().schedules_config),
+            ("quota", QuotaConfig::default_path()),
+            ("exclude", std::path::PathBuf::from("/etc/waypoint/exclude.toml")),
+        ]
+    }
+
+    /// Export configuration implementation
+    fn export_config_impl() -> Result<String> {
+        let mut bundle = ConfigBundle::new();
+
+        for (section, path) in Self::system_config_paths() {
+            if !path.exists() {
+                continue;
+            }
+
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+
+            match section {
+                "schedules" => bundle.schedules = Some(content),
+                "quota" => bundle.quota = Some(content),
+                _ => bundle.exclude = Some(content),
+            }
+        }
+
+        bundle.to_json()
+    }
+
+    /// Import configuration implementation
+    fn import_config_impl(bundle_json: &str) -> Result<String> {
+        // Validates every section (including user sections) before writing anything
+        let bundle = ConfigBundle::from_json(bundle_json)?;
+
+        let sections = Self::system_config_paths()
+            .into_iter()
+            .filter_map(|(section, path)| {
+                let content = match section {
+                    "schedules" => bundle.schedules.as_ref(),
+                    "quota" => bundle.quota.as_ref(),
+                    _ => bundle.exclude.as_ref(),
+                }?;
+                Some((section, path, content))
+            })
+            .collect::<Vec<_>>();
+
+        if sections.is_empty() {
+            return Ok("Configuration bundle contains no system settings".to_string());
+        }
 
-    /// Save quota configuration to file
-    fn save_quota_config_impl(config_toml: &str) -> Result<String> {
-        use waypoint_common::QuotaConfig;
+        // Stage every file first so a write failure can't leave a partial import
+        let mut staged = Vec::new();
+        for (section, path, content) in &sections {
+            let tmp_path = path.with_extension("toml.import");
+
+            let result = path
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|_| {
+                    use std::io::Write;
+                    let mut file = std::fs::File::create(&tmp_path)?;
+                    file.write_all(content.as_bytes())?;
+                    // Flush to disk so a crash can't rename an empty file into place
+                    file.sync_all()
+                });
 
-        // Validate TOML by parsing it
-        let _config: QuotaConfig =
-            toml::from_str(config_toml).context("Invalid quota configuration")?;
+            if let Err(e) = result {
+                for (tmp, _) in &staged {
+                    let _ = std::fs::remove_file(tmp);
+                }
+                let _ = std::fs::remove_file(&tmp_path);
+                return Err(e).with_context(|| format!("Failed to stage {section} configuration"));
+            }
 
-        let config_path = QuotaConfig::default_path();
+            staged.push((tmp_path, path.clone()));
+        }
 
-        // Create parent directory if it doesn't exist
-        if let Some(parent) = config_path.parent() {
-            std::fs::create_dir_all(parent).context("Failed to create config directory")?;
+        // Renames within the same directory are atomic
+        for (tmp_path, path) in &staged {
+            std::fs::rename(tmp_path, path)
+                .with_context(|| format!("Failed to replace {}", path.display()))?;
         }
 
-        // Write configuration
-        std::fs::write(&config_path, config_toml)
-            .context("Failed to write quota configuration file")?;
+        let names: Vec<&str> = sections.iter().map(|(section, _, _)| *section).collect();
+        log::info!("Imported configuration sections: {}", names.join(", "));
 
-        Ok("Quota configuration saved successfully".to_string())
+        // The files are in place now, so failures below are reported but don't fail the import
+        let warnings = Self::apply_imported_config(&bundle);
+        if warnings.is_empty() {
+            Ok(format!("Imported {} configuration", names.join(", ")))
+        } else {
+            Ok(format!(
+                "Imported {} configuration, but applying it failed: {}",
+                names.join(", "),
+                warnings.join("; ")
+            ))
+        }
     }
 
-    /// Save exclude configuration to file
-    fn save_exclude_config_impl(config_toml: &str) -> Result<String> {
-        use waypoint_common::ExcludeConfig;
+    /// Apply imported settings the way saving them from the GUI does
+    ///
+    /// Enables or disables quotas to match the imported quota config and restarts
+    /// the scheduler so it picks up imported schedules. Returns what failed.
+    fn apply_imported_config(bundle: &ConfigBundle) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        if let Some(quota) = &bundle.quota {
+            // Already validated by ConfigBundle::from_json
+            let result = toml::from_str::<QuotaConfig>(quota)
+                .context("Invalid quota configuration")
+                .and_then(|config| {
+                    if config.enabled {
+                        Self::enable_quotas_impl(matches!(config.quota_type, QuotaType::Simple))
+                    } else {
+                        Self::disable_quotas_impl()
+                    }
+                });
+            match result {
+                Ok(msg) => log::info!("Import: {msg}"),
+                Err(e) => {
+                    log::warn!("Import: failed to apply quota settings: {e}");
+                    warnings.push(format!("quotas: {e}"));
+                }
+            }
+        }
 
-        // Validate TOML by parsing it
-        let _config: ExcludeConfig =
-            toml::from_str(config_toml).context("Invalid exclude configuration")?;
+        if bundle.schedules.is_some() && std::path::Path::new(&scheduler_service_path()).exists() {
+            match run_command("sv", &["restart", "waypoint-scheduler"]) {
+                Ok(_) => log::info!("Import: restarted scheduler service"),
+                Err(e) => {
+                    log::warn!("Import: failed to restart scheduler service: {e}");
+                    warnings.push(format!("scheduler restart: {e}"));
+                }
+            }
+        }
 
-        let config_path = std::path::PathBuf::from("/etc/waypoint/exclude.toml");
+        warnings
+    }
 
-        // Create parent directory if it doesn't exist
-        if let Some(parent) = config_path.parent() {
-            std::fs::create_dir_all(parent).context("Failed to create config directory")?;
+    /// Read audit events from the current and rotated audit log files
+    fn get_audit_log_impl(max_events: usize) -> Result<String> {
+        let path = waypoint_common::audit::audit_log_path();
+        let rotated = path.with_extension("log.1");
+
+        let mut events = Vec::new();
+        for file in [rotated, path] {
+            if !file.exists() {
+                continue;
+            }
+            let content = std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            events.extend(waypoint_common::audit::parse_audit_log(&content));
         }
 
-        // Write configuration
-        std::fs::write(&config_path, config_toml)
-            .context("Failed to write exclude configuration file")?;
+        if max_events > 0 && events.len() > max_events {
+            events.drain(..events.len() - max_events);
+        }
 
-        Ok("Exclude configuration saved successfully".to_string())
+        serde_json::to_string(&events).context("Failed to serialize audit events")
     }
 
     /// Update snapshot metadata implementation
@@ -1986,11 +4067,13 @@ impl WaypointHelper {
         let config = WaypointConfig::new();
         let metadata_path = &config.metadata_file;
 
-        // Load existing snapshots as JSON array
+        // Load existing snapshots as generic values, keeping fields this code doesn't know
+        let format = MetadataFormat::from_path(metadata_path);
         let content = fs::read_to_string(metadata_path)
             .context("Failed to read metadata file")?;
-        let mut snapshots: Vec<serde_json::Value> =
-            serde_json::from_str(&content).context("Failed to parse metadata file")?;
+        let mut snapshots: Vec<serde_json::Value> = format
+            .parse(&content)
+            .context("Failed to parse metadata file")?;
 
         // Find and update the snapshot
         let mut found = false;
@@ -2010,7 +4093,8 @@ impl WaypointHelper {
         }
 
         // Save back to file
-        let updated_content = serde_json::to_string_pretty(&snapshots)
+        let updated_content = format
+            .serialize(&snapshots)
             .context("Failed to serialize snapshots")?;
         fs::write(metadata_path, updated_content)
             .context("Failed to write metadata file")?;
@@ -2098,13 +4182,117 @@ fn validate_symlink_target(
     Ok(())
 }
 
+/// Counts reported by `copy_dir_recursive`
+#[derive(Debug, Default)]
+struct CopyStats {
+    /// Entries skipped because they matched an exclude pattern
+    skipped: usize,
+    /// Files whose copy was verified against the source
+    verified: usize,
+    /// Nested subvolumes left out
+    nested_skipped: usize,
+}
+
+/// Fail with `invalid-input` unless `path`, or its closest existing parent, is on btrfs
+fn ensure_btrfs_target(path: &std::path::Path) -> Result<()> {
+    let existing = path
+        .ancestors()
+        .find(|p| p.exists())
+        .unwrap_or(std::path::Path::new("/"));
+    let output = Command::new("findmnt")
+        .args(["-no", "FSTYPE", "--target"])
+        .arg(existing)
+        .recorded_output()
+        .context("Failed to execute findmnt")?;
+
+    let fstype = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || fstype.is_empty() {
+        anyhow::bail!(
+            "Failed to get the filesystem type of {}",
+            existing.display()
+        );
+    }
+    if fstype != "btrfs" {
+        return Err(HelperError::new(
+            ErrorCode::InvalidInput,
+            format!(
+                "Nested subvolumes can only be restored as subvolumes onto btrfs, but {} is on {fstype}",
+                path.display()
+            ),
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Apply the nested subvolume mode to a directory about to be restored
+///
+/// `parent_dev` is the device of the directory containing `source`. Returns
+/// whether the directory should still be copied; in `Subvolume` mode a missing
+/// target is created as a subvolume first.
+fn handle_nested_subvolume(
+    metadata: &std::fs::Metadata,
+    parent_dev: u64,
+    source: &std::path::Path,
+    target: &std::path::Path,
+    mode: NestedSubvolumeMode,
+) -> Result<bool> {
+    use std::os::unix::fs::MetadataExt;
+
+    let Some(boundary) = btrfs::subvolume_boundary(metadata.ino(), metadata.dev(), parent_dev)
+    else {
+        return Ok(true);
+    };
+    let kind = match boundary {
+        btrfs::SubvolumeBoundary::Placeholder => "nested subvolume (not part of the snapshot)",
+        btrfs::SubvolumeBoundary::Subvolume => "nested subvolume",
+    };
+
+    match mode {
+        NestedSubvolumeMode::Skip => {
+            log::warn!("Skipping {kind} during restore: {}", source.display());
+            Ok(false)
+        }
+        NestedSubvolumeMode::Recurse => {
+            log::warn!(
+                "Restoring {kind} as a plain directory: {}",
+                source.display()
+            );
+            Ok(true)
+        }
+        NestedSubvolumeMode::Subvolume => {
+            if !target.exists() {
+                btrfs::create_subvolume(target)?;
+                log::info!("Recreated {kind} as a subvolume: {}", target.display());
+            } else if !btrfs::is_subvolume(target) {
+                log::warn!(
+                    "{} is not a subvolume, restoring {kind} {} into it as a plain directory",
+                    target.display(),
+                    source.display()
+                );
+            }
+            Ok(true)
+        }
+    }
+}
+
 /// Recursively copy a directory and its contents without escaping the snapshot root
+///
+/// `original` is the path `source` corresponds to on the live system. Entries whose
+/// original path matches one of `excludes` are skipped. With `verify`, every copied
+/// file is hashed against its source and a mismatch fails the copy. Nested
+/// subvolumes are handled according to `nested`.
 fn copy_dir_recursive(
     snapshot_root: &std::path::Path,
     source: &std::path::Path,
     target: &std::path::Path,
-) -> Result<()> {
+    original: &std::path::Path,
+    excludes: &[ExcludePattern],
+    verify: bool,
+    nested: NestedSubvolumeMode,
+) -> Result<CopyStats> {
     use std::fs;
+    use std::os::unix::fs::MetadataExt;
 
     if !source.starts_with(snapshot_root) {
         anyhow::bail!("Source {} is outside of snapshot root", source.display());
@@ -2117,6 +4305,11 @@ fn copy_dir_recursive(
     // Copy metadata
     preserve_metadata(source, target)?;
 
+    let mut stats = CopyStats::default();
+    let source_dev = fs::metadata(source)
+        .context(format!("Failed to stat {}", source.display()))?
+        .dev();
+
     // Iterate through directory entries
     for entry in
         fs::read_dir(source).context(format!("Failed to read directory: {}", source.display()))?
@@ -2133,6 +4326,17 @@ fn copy_dir_recursive(
             );
         }
 
+        // Skip anything matched by the exclude configuration
+        let original_path = original.join(entry.file_name());
+        if excludes.iter().any(|pattern| pattern.matches(&original_path)) {
+            log::debug!(
+                "Skipping excluded path during restore: {}",
+                original_path.display()
+            );
+            stats.skipped += 1;
+            continue;
+        }
+
         let target_path = target.join(entry.file_name());
         let metadata = fs::symlink_metadata(&source_path)
             .context(format!("Failed to stat {}", source_path.display()))?;
@@ -2168,12 +4372,33 @@ fn copy_dir_recursive(
                 );
             }
         } else if metadata.is_dir() {
+            if !handle_nested_subvolume(&metadata, source_dev, &source_path, &target_path, nested)?
+            {
+                stats.nested_skipped += 1;
+                continue;
+            }
+
             // Recursively copy subdirectory
-            copy_dir_recursive(snapshot_root, &source_path, &target_path)?;
+            let sub_stats = copy_dir_recursive(
+                snapshot_root,
+                &source_path,
+                &target_path,
+                &original_path,
+                excludes,
+                verify,
+                nested,
+            )?;
+            stats.skipped += sub_stats.skipped;
+            stats.verified += sub_stats.verified;
+            stats.nested_skipped += sub_stats.nested_skipped;
         } else if metadata.is_file() {
             // Copy file
             fs::copy(&source_path, &target_path)
                 .context(format!("Failed to copy file: {}", source_path.display()))?;
+            if verify {
+                verify_copy(&source_path, &target_path)?;
+                stats.verified += 1;
+            }
             preserve_metadata(&source_path, &target_path)?;
         } else {
             log::warn!(
@@ -2183,6 +4408,39 @@ fn copy_dir_recursive(
         }
     }
 
+    Ok(stats)
+}
+
+/// SHA-256 digest of a file's contents
+fn sha256_file(path: &std::path::Path) -> Result<[u8; 32]> {
+    use sha2::{Digest, Sha256};
+    use std::io::Read;
+
+    let mut file = std::fs::File::open(path)
+        .context(format!("Failed to open {} for verification", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .context(format!("Failed to read {} for verification", path.display()))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher.finalize().into())
+}
+
+/// Check that a restored file is byte-identical to its snapshot source
+fn verify_copy(source: &std::path::Path, target: &std::path::Path) -> Result<()> {
+    if sha256_file(source)? != sha256_file(target)? {
+        anyhow::bail!(
+            "Checksum mismatch: {} does not match {}",
+            target.display(),
+            source.display()
+        );
+    }
     Ok(())
 }
 
@@ -2255,119 +4513,253 @@ fn sanitize_error_for_client(error: &anyhow::Error) -> String {
     }
 }
 
+/// Build the coded failure message of a D-Bus response from an error
+///
+/// The message is sanitized, prefixed with `error_prefix` if it isn't empty,
+/// and tagged with the error's `ErrorCode` so clients don't have to match on
+/// the prose.
+fn error_message(error: &anyhow::Error, error_prefix: &str) -> String {
+    let code = HelperError::code_of(error);
+    let sanitized = sanitize_error_for_client(error);
+    let message = if error_prefix.is_empty() {
+        sanitized
+    } else {
+        format!("{error_prefix}: {sanitized}")
+    };
+    HelperError::new(code, message).encode()
+}
+
+/// Failure message for a denied authorization check
+///
+/// Keeps the code of checks the caller cancelled or that timed out, so
+/// clients don't report them as a denial.
+fn auth_failure_message(error: &anyhow::Error) -> String {
+    let code = match HelperError::code_of(error) {
+        code @ (ErrorCode::Cancelled | ErrorCode::TimedOut) => code,
+        _ => ErrorCode::AuthFailed,
+    };
+    HelperError::new(code, format!("Authorization failed: {error}")).encode()
+}
+
 /// Convert a Result<String> to (bool, String) for D-Bus responses
 /// Applies consistent error sanitization and formatting
 fn result_to_dbus_response(result: Result<String>, error_prefix: &str) -> (bool, String) {
     match result {
         Ok(msg) => (true, msg),
-        Err(e) => {
-            let sanitized = sanitize_error_for_client(&e);
-            (false, format!("{error_prefix}: {sanitized}"))
-        }
+        Err(e) => (false, error_message(&e, error_prefix)),
     }
 }
 
-/// Parse btrfs receive --dump output into structured changes
+/// A file that differs between two snapshots
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 struct FileChange {
-    change_type: String, // "Added", "Modified", "Deleted"
+    change_type: String, // "Added", "Modified", "Deleted", "Renamed"
     path: String,
+    /// Path in the old snapshot of a renamed file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    old_path: Option<String>,
 }
 
 /// File metadata for comparison
 #[derive(Debug, Clone)]
 struct FileMetadata {
+    /// find's file type letter (f, d, l, ...)
+    file_type: u8,
+    /// Inode number; snapshots of a subvolume share them, so a file keeps its
+    /// inode when it is renamed
+    inode: u64,
     size: u64,
     mtime: String,
 }
 
-/// Parse find output into a HashMap of path -> metadata
-fn parse_find_output(output: &str) -> Result<std::collections::HashMap<String, FileMetadata>> {
-    let mut files = std::collections::HashMap::new();
+/// Maximum number of changes returned by a single comparison
+///
+/// Diffs with millions of changed files would otherwise exhaust memory in both
+/// the helper and the GUI. The total count is still reported.
+const MAX_COMPARE_CHANGES: usize = 100_000;
 
-    for line in output.lines() {
-        if line.trim().is_empty() {
-            continue;
-        }
+/// Report progress (and check for cancellation) every this many listing entries
+const COMPARE_PROGRESS_INTERVAL: u64 = 5_000;
 
-        let parts: Vec<&str> = line.splitn(4, ' ').collect();
-        if parts.len() < 4 {
-            continue;
+/// Progress of a running snapshot comparison
+#[derive(Debug, Clone)]
+struct CompareProgress {
+    entries_scanned: u64,
+    changes_found: u64,
+    stage: &'static str,
+}
+
+/// List all files below `root` with `find`, streaming its output
+///
+/// `scanned` is a running count across calls so progress covers both snapshots.
+fn scan_snapshot_files(
+    root: &std::path::Path,
+    scanned: &mut u64,
+    progress: Option<&std::sync::mpsc::SyncSender<CompareProgress>>,
+    cancel: &std::sync::atomic::AtomicBool,
+) -> Result<std::collections::HashMap<String, FileMetadata>> {
+    use std::io::BufRead;
+    use std::process::Stdio;
+
+    // Format: type inode size mtime path, NUL-terminated so any file name survives
+    // type: f=file, d=directory, l=symlink
+    let mut child = Command::new("find")
+        .arg(root)
+        .arg("-xdev") // Don't cross filesystem boundaries
+        .arg("-printf")
+        .arg("%y %i %s %T@ %P\\0") // type, inode, size, mtime, path (relative)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .recorded_spawn()
+        .context(format!("Failed to run find on {}", root.display()))?;
+
+    let stdout = child.stdout.take().context("Failed to capture find output")?;
+    let mut reader = std::io::BufReader::new(stdout);
+    let mut files = std::collections::HashMap::new();
+    let mut line = Vec::new();
+
+    loop {
+        line.clear();
+        let read = reader
+            .read_until(b'\0', &mut line)
+            .context("Failed to read find output")?;
+        if read == 0 {
+            break;
         }
 
-        // parts[0] = file type (f, d, l, etc) - not used for comparison
-        let path = parts[1].to_string();
-        let size = parts[2].parse::<u64>().unwrap_or(0);
-        let mtime = parts[3].to_string();
+        if let Some((path, metadata)) = parse_find_entry(&line) {
+            files.insert(path, metadata);
+        }
 
-        // Skip empty path (root directory itself)
-        if path.is_empty() {
-            continue;
+        *scanned += 1;
+        if scanned.is_multiple_of(COMPARE_PROGRESS_INTERVAL) {
+            if cancel.load(Ordering::Relaxed) {
+                let _ = child.kill();
+                let _ = child.wait();
+                anyhow::bail!("Comparison cancelled");
+            }
+            if let Some(tx) = progress {
+                let _ = tx.try_send(CompareProgress {
+                    entries_scanned: *scanned,
+                    changes_found: 0,
+                    stage: "scanning",
+                });
+            }
         }
+    }
 
-        files.insert(
-            path,
-            FileMetadata {
-                size,
-                mtime,
-            },
-        );
+    let status = child.wait().context("Failed to wait for find")?;
+    if !status.success() {
+        anyhow::bail!("find failed on {}: {status}", root.display());
     }
 
     Ok(files)
 }
 
+/// Parse one NUL-terminated find entry into (encoded path, metadata)
+///
+/// The path is kept as bytes until it is encoded with `encode_path`, so file
+/// names that aren't valid UTF-8 can still be restored from the results.
+fn parse_find_entry(entry: &[u8]) -> Option<(String, FileMetadata)> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let entry = entry.strip_suffix(b"\0").unwrap_or(entry);
+    let mut parts = entry.splitn(5, |&b| b == b' ');
+
+    // File type (f, d, l, etc), so a rename is only matched to the same kind of file
+    let file_type = *parts.next()?.first()?;
+    let inode = std::str::from_utf8(parts.next()?)
+        .ok()?
+        .parse::<u64>()
+        .ok()?;
+    let size = std::str::from_utf8(parts.next()?).ok()?.parse::<u64>().unwrap_or(0);
+    let mtime = String::from_utf8_lossy(parts.next()?).into_owned();
+    let path = parts.next()?;
+
+    // Skip empty path (root directory itself)
+    if path.is_empty() {
+        return None;
+    }
+
+    let path = waypoint_common::encode_path(std::path::Path::new(std::ffi::OsStr::from_bytes(path)));
+    Some((
+        path,
+        FileMetadata {
+            file_type,
+            inode,
+            size,
+            mtime,
+        },
+    ))
+}
+
 /// Compare two file lists and detect changes
+///
+/// A file that is gone from its old path while the same inode shows up under
+/// a new one is reported once as renamed, with both paths. Returns at most
+/// `MAX_COMPARE_CHANGES` changes (sorted by path) together with the total
+/// number of changes found.
 fn compare_file_lists(
     old_files: &std::collections::HashMap<String, FileMetadata>,
     new_files: &std::collections::HashMap<String, FileMetadata>,
-) -> Vec<FileChange> {
-    let mut changes = Vec::new();
-    let mut seen_paths = std::collections::HashSet::new();
+) -> (Vec<FileChange>, u64) {
+    // Borrowed from the file lists until the kept changes are known
+    let mut found: Vec<(&str, &str, Option<&str>)> = Vec::new();
+    let mut record = |change_type, path, old_path| found.push((change_type, path, old_path));
+
+    // Files only in the old snapshot by inode and type; hard links share an inode
+    let mut removed: std::collections::HashMap<(u64, u8), Vec<&str>> =
+        std::collections::HashMap::new();
+    for (path, old_meta) in old_files {
+        if !new_files.contains_key(path) {
+            removed
+                .entry((old_meta.inode, old_meta.file_type))
+                .or_default()
+                .push(path);
+        }
+    }
 
-    // Find added and modified files
+    // Find added, renamed and modified files
     for (path, new_meta) in new_files {
         if let Some(old_meta) = old_files.get(path) {
             // File exists in both - check if modified
             // Compare size and mtime to detect modifications
             if old_meta.size != new_meta.size || old_meta.mtime != new_meta.mtime {
-                let full_path = format!("/{}", path);
-                if seen_paths.insert(full_path.clone()) {
-                    changes.push(FileChange {
-                        change_type: "Modified".to_string(),
-                        path: full_path,
-                    });
-                }
+                record("Modified", path, None);
             }
+        } else if let Some(old_path) = removed
+            .get_mut(&(new_meta.inode, new_meta.file_type))
+            .and_then(|paths| paths.pop())
+        {
+            record("Renamed", path, Some(old_path));
         } else {
             // File only in new snapshot - added
-            let full_path = format!("/{}", path);
-            if seen_paths.insert(full_path.clone()) {
-                changes.push(FileChange {
-                    change_type: "Added".to_string(),
-                    path: full_path,
-                });
-            }
+            record("Added", path, None);
         }
     }
 
     // Find deleted files
-    for path in old_files.keys() {
-        if !new_files.contains_key(path) {
-            let full_path = format!("/{}", path);
-            if seen_paths.insert(full_path.clone()) {
-                changes.push(FileChange {
-                    change_type: "Deleted".to_string(),
-                    path: full_path,
-                });
-            }
-        }
+    for path in removed.into_values().flatten() {
+        record("Deleted", path, None);
     }
 
-    // Sort by path for consistent output
-    changes.sort_by(|a, b| a.path.cmp(&b.path));
-
-    changes
+    // Keep the first changes by path, sorted for consistent output
+    let total = found.len() as u64;
+    if found.len() > MAX_COMPARE_CHANGES {
+        found.select_nth_unstable_by(MAX_COMPARE_CHANGES, |a, b| a.1.cmp(b.1));
+        found.truncate(MAX_COMPARE_CHANGES);
+    }
+    found.sort_unstable_by(|a, b| a.1.cmp(b.1));
+
+    let changes = found
+        .into_iter()
+        .map(|(change_type, path, old_path)| FileChange {
+            change_type: change_type.to_string(),
+            path: format!("/{path}"),
+            old_path: old_path.map(|old_path| format!("/{old_path}")),
+        })
+        .collect();
+    (changes, total)
 }
 
 /// Check Polkit authorization for an action
@@ -2379,9 +4771,6 @@ async fn check_authorization(
     connection: &Connection,
     action_id: &str,
 ) -> Result<()> {
-    use std::collections::HashMap;
-    use zbus::zvariant::{ObjectPath, Value};
-
     log::debug!("Authorization requested for action: {action_id}");
 
     // Get the caller's bus name from the message header
@@ -2411,16 +4800,69 @@ async fn check_authorization(
 
     log::debug!("Caller PID: {caller_pid}");
 
-    // Get process start time from /proc
-    let start_time = get_process_start_time(caller_pid)?;
+    let subject = ProcessSubject {
+        pid: caller_pid,
+        start_time: get_process_start_time(caller_pid)?,
+        uid: None,
+    };
+    check_process_authorization(connection, &subject, action_id).await
+}
+
+/// Process a request came from, as identified when the request arrived
+struct ProcessSubject {
+    pid: u32,
+    /// Start time from `/proc/<pid>/stat`, which tells a reused PID apart
+    start_time: u64,
+    /// Effective user ID, if the transport reported it
+    uid: Option<u32>,
+}
+
+impl ProcessSubject {
+    /// Fail if `pid` now belongs to a different process than the caller
+    fn ensure_unchanged(&self) -> Result<()> {
+        let start_time = get_process_start_time(self.pid)?;
+        let uid_changed = match self.uid {
+            Some(uid) => get_process_uid(self.pid)? != uid,
+            None => false,
+        };
+        if start_time != self.start_time || uid_changed {
+            return Err(HelperError::new(
+                ErrorCode::AuthFailed,
+                format!("Process {} changed since it sent the request", self.pid),
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Check Polkit authorization for an action on behalf of a process
+///
+/// Used directly by transports that identify the caller by PID rather than
+/// bus name. The process is checked against `subject` before and after
+/// asking Polkit, so a PID reused meanwhile is never authorized.
+async fn check_process_authorization(
+    connection: &Connection,
+    subject: &ProcessSubject,
+    action_id: &str,
+) -> Result<()> {
+    use std::collections::HashMap;
+    use zbus::zvariant::{ObjectPath, Value};
+
+    subject.ensure_unchanged()?;
 
     // Build the subject structure for Polkit
     // Subject is (subject_kind, subject_details)
+    let caller_pid = subject.pid;
     let mut subject_details: HashMap<String, Value> = HashMap::new();
     subject_details.insert("pid".to_string(), Value::U32(caller_pid));
-    subject_details.insert("start-time".to_string(), Value::U64(start_time));
+    subject_details.insert("start-time".to_string(), Value::U64(subject.start_time));
+    if let Some(uid) = subject.uid {
+        // Polkit takes the UID as a signed 32-bit integer
+        subject_details.insert("uid".to_string(), Value::I32(uid as i32));
+    }
 
-    let subject = ("unix-process", subject_details);
+    let polkit_subject = ("unix-process", subject_details);
 
     // Details dict (empty for now)
     let details: HashMap<String, String> = HashMap::new();
@@ -2431,9 +4873,9 @@ async fn check_authorization(
     // and configuring passwordless Polkit rules in /etc/polkit-1/rules.d/
     let flags: u32 = 1;
 
-    // Cancellation ID (empty string = no cancellation)
-    // Could be used to cancel long-running auth requests, but not needed here
-    let cancellation_id = "";
+    // Cancellation ID, so CancelAuthorization or a timeout can dismiss the prompt
+    let pending = PendingAuthorization::register(caller_pid);
+    let cancellation_id = pending.id.as_str();
 
     // Call Polkit CheckAuthorization
     // Note: Polkit handles timeouts internally based on system configuration.
@@ -2444,11 +4886,11 @@ async fn check_authorization(
 
     // Add explicit timeout to D-Bus call
     // This prevents indefinite hangs if Polkit service is unresponsive
-    // Configurable via WAYPOINT_POLKIT_TIMEOUT environment variable (default: 120 seconds)
+    // Configurable via WAYPOINT_POLKIT_TIMEOUT environment variable (default: 60 seconds)
     let timeout_secs = std::env::var("WAYPOINT_POLKIT_TIMEOUT")
         .ok()
         .and_then(|s| s.parse::<u64>().ok())
-        .unwrap_or(120);
+        .unwrap_or(60);
 
     let result = tokio::time::timeout(
         std::time::Duration::from_secs(timeout_secs),
@@ -2457,13 +4899,30 @@ async fn check_authorization(
             polkit_path,
             Some("org.freedesktop.PolicyKit1.Authority"),
             "CheckAuthorization",
-            &(subject, action_id, details, flags, cancellation_id),
+            &(polkit_subject, action_id, details, flags, cancellation_id),
         ),
     )
-    .await
-    .with_context(|| format!("Polkit authorization timed out after {timeout_secs} seconds"))?;
+    .await;
 
-    let msg = result.context("Failed to call Polkit CheckAuthorization")?;
+    let msg = match result {
+        Ok(Ok(msg)) => msg,
+        Ok(Err(zbus::Error::MethodError(name, _, _)))
+            if name.as_str() == "org.freedesktop.PolicyKit1.Error.Cancelled" =>
+        {
+            let error = HelperError::new(ErrorCode::Cancelled, "Authorization was cancelled");
+            return Err(error.into());
+        }
+        Ok(Err(e)) => return Err(e).context("Failed to call Polkit CheckAuthorization"),
+        Err(_) => {
+            // Dismiss the prompt rather than leave it open for a request that already failed
+            cancel_polkit_check(connection, cancellation_id).await;
+            return Err(HelperError::new(
+                ErrorCode::TimedOut,
+                format!("Polkit authorization timed out after {timeout_secs} seconds"),
+            )
+            .into());
+        }
+    };
 
     // Result is (is_authorized, is_challenge, details)
     let (is_authorized, is_challenge, auth_details): (bool, bool, HashMap<String, String>) = msg
@@ -2475,11 +4934,62 @@ async fn check_authorization(
         "Authorization result: authorized={is_authorized}, challenge={is_challenge}, details={auth_details:?}"
     );
 
-    if is_authorized {
-        Ok(())
-    } else {
+    if !is_authorized {
         anyhow::bail!("Action '{action_id}' not authorized");
     }
+
+    // The process may have exited during a password prompt and its PID been reused
+    subject.ensure_unchanged()
+}
+
+/// A Polkit check in progress, removed from `PENDING_AUTHORIZATIONS` when dropped
+struct PendingAuthorization {
+    id: String,
+}
+
+impl PendingAuthorization {
+    fn register(caller_pid: u32) -> Self {
+        let id = format!(
+            "waypoint-{caller_pid}-{}",
+            NEXT_AUTHORIZATION_ID.fetch_add(1, Ordering::Relaxed)
+        );
+        lock_pending_authorizations().push((id.clone(), caller_pid));
+        Self { id }
+    }
+}
+
+impl Drop for PendingAuthorization {
+    fn drop(&mut self) {
+        lock_pending_authorizations().retain(|(id, _)| *id != self.id);
+    }
+}
+
+/// Lock the pending authorization list, recovering from poisoning
+fn lock_pending_authorizations() -> std::sync::MutexGuard<'static, Vec<(String, u32)>> {
+    PENDING_AUTHORIZATIONS.lock().unwrap_or_else(|poisoned| {
+        MUTEX_POISON_COUNT.fetch_add(1, Ordering::Relaxed);
+        log::error!("Pending authorization mutex poisoned, recovering");
+        poisoned.into_inner()
+    })
+}
+
+/// Ask Polkit to dismiss the prompt of a pending check
+///
+/// The check then fails with `Cancelled`. Only the connection that started a
+/// check may cancel it, which is why clients go through the helper.
+async fn cancel_polkit_check(connection: &Connection, cancellation_id: &str) {
+    let result = connection
+        .call_method(
+            Some("org.freedesktop.PolicyKit1"),
+            "/org/freedesktop/PolicyKit1/Authority",
+            Some("org.freedesktop.PolicyKit1.Authority"),
+            "CancelCheckAuthorization",
+            &cancellation_id,
+        )
+        .await;
+    if let Err(e) = result {
+        log::warn!("Failed to cancel Polkit check {cancellation_id}: {e}");
+    }
 }
 
 /// Get process start time from `/proc/[pid]/stat`
@@ -2529,6 +5039,21 @@ fn get_process_start_time(pid: u32) -> Result<u64> {
     Ok(start_time)
 }
 
+/// Get the effective user ID of a process from /proc/[pid]/status
+fn get_process_uid(pid: u32) -> Result<u32> {
+    let status_path = format!("/proc/{pid}/status");
+    let status =
+        std::fs::read_to_string(&status_path).context(format!("Failed to read {status_path}"))?;
+
+    // "Uid:" is followed by the real, effective, saved and filesystem UIDs
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("Uid:"))
+        .and_then(|uids| uids.split_whitespace().nth(1))
+        .and_then(|uid| uid.parse().ok())
+        .with_context(|| format!("No effective UID in {status_path}"))
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize logging
@@ -2540,9 +5065,25 @@ async fn main() -> Result<()> {
         std::process::exit(1);
     }
 
+    // Mount the Btrfs root if the snapshot directory is only reachable through
+    // it; the guard unmounts it again when the helper exits
+    let _root_mount = match btrfs_root::ensure_snapshot_dir() {
+        Ok(guard) => guard,
+        Err(e) => {
+            log::warn!("Snapshot directory is not accessible: {e:#}");
+            None
+        }
+    };
+
     // Initialize configuration
     btrfs::init_config();
 
+    // Snapshots of / are only as private as the directory holding them
+    permissions::check_at_startup();
+
+    // Overlays owned by clients that died while the helper was down
+    overlay::reap_stale_overlays();
+
     log::info!(
         "Starting Waypoint Helper service v{}",
         env!("CARGO_PKG_VERSION")
@@ -2550,7 +5091,7 @@ async fn main() -> Result<()> {
 
     // Build the D-Bus connection
     let helper = WaypointHelper::new();
-    let _connection = ConnectionBuilder::system()?
+    let connection = ConnectionBuilder::system()?
         .name(DBUS_SERVICE_NAME)?
         .serve_at(DBUS_OBJECT_PATH, helper)?
         .build()
@@ -2558,6 +5099,15 @@ async fn main() -> Result<()> {
 
     log::info!("Waypoint Helper is ready at {DBUS_OBJECT_PATH}");
 
+    // Optional transport for scripts that can't use D-Bus
+    let config = WaypointConfig::new();
+    let socket_path = config.socket_api.then_some(config.socket_api_path);
+    if let Some(path) = &socket_path
+        && let Err(e) = socket_api::serve(&connection, path, &config.socket_api_group).await
+    {
+        log::error!("Failed to start the socket API: {e:#}");
+    }
+
     // Wait for termination signal
     let mut sigterm = signal(SignalKind::terminate())?;
     let mut sigint = signal(SignalKind::interrupt())?;
@@ -2567,12 +5117,19 @@ async fn main() -> Result<()> {
         _ = sigint.recv() => log::info!("Received SIGINT, shutting down..."),
     }
 
+    // Don't abandon a backup or restore halfway
+    shutdown::shutdown(shutdown::SHUTDOWN_GRACE_PERIOD).await;
+
+    if let Some(path) = socket_path {
+        let _ = std::fs::remove_file(path);
+    }
+
     Ok(())
 }
 fn run_command(cmd: &str, args: &[&str]) -> Result<()> {
     let output = Command::new(cmd)
         .args(args)
-        .output()
+        .recorded_output()
         .context(format!("Failed to run {cmd}"))?;
     if output.status.success() {
         Ok(())
@@ -2582,10 +5139,71 @@ fn run_command(cmd: &str, args: &[&str]) -> Result<()> {
     }
 }
 
+/// Run a command to completion, giving up after `timeout` or once `cancel` is set
+///
+/// The command is killed when it is abandoned. A process stuck in the kernel may
+/// not exit right away, so it is reaped in the background instead of waited for.
+fn output_with_timeout(
+    command: &mut Command,
+    timeout: std::time::Duration,
+    cancel: Option<&std::sync::atomic::AtomicBool>,
+) -> Result<std::process::Output> {
+    use std::io::Read;
+    use std::process::Stdio;
+
+    // Read the pipes on their own threads so a full pipe can't block the command
+    fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> std::thread::JoinHandle<Vec<u8>> {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buf);
+            }
+            buf
+        })
+    }
+
+    let program = command.get_program().to_string_lossy().to_string();
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .recorded_spawn()
+        .context(format!("Failed to run {program}"))?;
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let started = std::time::Instant::now();
+    let abandoned = loop {
+        if let Some(status) = child.try_wait().context(format!("Failed to wait for {program}"))? {
+            return Ok(std::process::Output {
+                status,
+                stdout: stdout.join().unwrap_or_default(),
+                stderr: stderr.join().unwrap_or_default(),
+            });
+        }
+        if cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+            break HelperError::new(ErrorCode::Cancelled, format!("{program} was cancelled"));
+        }
+        if started.elapsed() >= timeout {
+            break HelperError::new(
+                ErrorCode::TimedOut,
+                format!("{program} timed out after {} seconds", timeout.as_secs()),
+            );
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    };
+
+    log::warn!("Stopping {program}: {abandoned}");
+    let _ = child.kill();
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Err(abandoned.into())
+}
+
 fn run_command_with_output(cmd: &str, args: &[&str]) -> Result<(String, String)> {
     let output = Command::new(cmd)
         .args(args)
-        .output()
+        .recorded_output()
         .context(format!("Failed to run {cmd}"))?;
     let stdout = String::from_utf8_lossy(&output.stdout).to_string();
     let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -2595,3 +5213,190 @@ fn run_command_with_output(cmd: &str, args: &[&str]) -> Result<(String, String)>
         Err(anyhow::anyhow!("{} failed: {}", cmd, stderr.trim()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_subject_detects_reuse() {
+        let pid = std::process::id();
+        let subject = ProcessSubject {
+            pid,
+            start_time: get_process_start_time(pid).unwrap(),
+            uid: Some(nix::unistd::geteuid().as_raw()),
+        };
+        assert!(subject.ensure_unchanged().is_ok());
+
+        let restarted = ProcessSubject {
+            pid,
+            start_time: subject.start_time + 1,
+            uid: None,
+        };
+        assert!(restarted.ensure_unchanged().is_err());
+
+        let other_user = ProcessSubject {
+            pid,
+            start_time: subject.start_time,
+            uid: Some(get_process_uid(pid).unwrap() + 1),
+        };
+        assert!(other_user.ensure_unchanged().is_err());
+    }
+
+    #[test]
+    fn test_compare_file_lists_keeps_first_changes_by_path() {
+        let file = |inode| FileMetadata {
+            file_type: b'f',
+            inode,
+            size: 0,
+            mtime: "0".to_string(),
+        };
+        let old_files = std::collections::HashMap::new();
+        let count = MAX_COMPARE_CHANGES as u64 + 10;
+        let new_files: std::collections::HashMap<String, FileMetadata> = (0..count)
+            .rev()
+            .map(|i| (format!("file-{i:07}"), file(i)))
+            .collect();
+
+        let (changes, total) = compare_file_lists(&old_files, &new_files);
+        assert_eq!(total, count);
+        assert_eq!(changes.len(), MAX_COMPARE_CHANGES);
+        assert_eq!(changes[0].path, "/file-0000000");
+        assert_eq!(
+            changes[MAX_COMPARE_CHANGES - 1].path,
+            format!("/file-{:07}", MAX_COMPARE_CHANGES - 1)
+        );
+        assert!(changes.windows(2).all(|pair| pair[0].path < pair[1].path));
+    }
+
+    #[test]
+    fn test_compare_file_lists_matches_renames_by_inode() {
+        let entry = |file_type, inode| FileMetadata {
+            file_type,
+            inode,
+            size: 0,
+            mtime: "0".to_string(),
+        };
+        let old_files: std::collections::HashMap<String, FileMetadata> = [
+            ("old-name", entry(b'f', 1)),
+            ("old-dir", entry(b'd', 2)),
+            ("was-file", entry(b'f', 3)),
+            ("gone", entry(b'f', 4)),
+        ]
+        .into_iter()
+        .map(|(path, meta)| (path.to_string(), meta))
+        .collect();
+        let new_files: std::collections::HashMap<String, FileMetadata> = [
+            ("new-name", entry(b'f', 1)),
+            ("new-dir", entry(b'd', 2)),
+            // Same inode but another kind of file, so not a rename
+            ("now-dir", entry(b'd', 3)),
+        ]
+        .into_iter()
+        .map(|(path, meta)| (path.to_string(), meta))
+        .collect();
+
+        let (changes, total) = compare_file_lists(&old_files, &new_files);
+        let summary: Vec<(&str, &str, Option<&str>)> = changes
+            .iter()
+            .map(|change| {
+                (
+                    change.change_type.as_str(),
+                    change.path.as_str(),
+                    change.old_path.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(total, 5);
+        assert_eq!(
+            summary,
+            vec![
+                ("Deleted", "/gone", None),
+                ("Renamed", "/new-dir", Some("/old-dir")),
+                ("Renamed", "/new-name", Some("/old-name")),
+                ("Added", "/now-dir", None),
+                ("Deleted", "/was-file", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_find_entry() {
+        let (path, meta) = parse_find_entry(b"f 42 1024 1700000000.5 dir/file name\0").unwrap();
+        assert_eq!(path, "dir/file name");
+        assert_eq!(meta.file_type, b'f');
+        assert_eq!(meta.inode, 42);
+        assert_eq!(meta.size, 1024);
+        assert_eq!(meta.mtime, "1700000000.5");
+
+        // Non-UTF-8 names are encoded rather than dropped
+        let (path, _) = parse_find_entry(b"f 1 0 0 bad\xff\0").unwrap();
+        assert_eq!(path, "bad\\xFF");
+
+        // The root directory itself has an empty path
+        assert!(parse_find_entry(b"d 256 0 0 \0").is_none());
+        // Missing fields
+        assert!(parse_find_entry(b"f 42 1024 1700000000.5\0").is_none());
+        assert!(parse_find_entry(b"\0").is_none());
+        // Inode must be a number
+        assert!(parse_find_entry(b"f x 1024 1700000000.5 file\0").is_none());
+    }
+
+    #[test]
+    fn test_quota_cleanup_keeps_pinned_snapshots() {
+        let dir = std::env::temp_dir().join(format!("waypoint-quota-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // The pinned snapshot is the oldest, so cleanup reaches it first
+        let pinned = btrfs::Snapshot {
+            id: "pinned".to_string(),
+            name: "pinned".to_string(),
+            timestamp: chrono::Utc::now() - chrono::Duration::days(2),
+            path: dir.join("pinned"),
+            description: None,
+            kernel_version: None,
+            package_count: None,
+            packages: Vec::new(),
+            subvolumes: vec![std::path::PathBuf::from("/")],
+            retention_group: None,
+            expires_at: None,
+            transaction: None,
+            category: SnapshotCategory::Manual,
+            is_favorite: true,
+        };
+        let mut unpinned = pinned.clone();
+        unpinned.id = "unpinned".to_string();
+        unpinned.name = "unpinned".to_string();
+        unpinned.path = dir.join("unpinned");
+        unpinned.timestamp = chrono::Utc::now() - chrono::Duration::days(1);
+        unpinned.is_favorite = false;
+
+        let mut config = WaypointConfig::new();
+        config.snapshot_dir = dir.clone();
+        config.extra_snapshot_dirs = Vec::new();
+        config.metadata_file = dir.join("snapshots.json");
+        std::fs::write(
+            &config.metadata_file,
+            serde_json::to_string(&vec![pinned, unpinned]).unwrap(),
+        )
+        .unwrap();
+
+        let quota_config = waypoint_common::QuotaConfig {
+            enabled: true,
+            auto_cleanup: true,
+            ..Default::default()
+        };
+        let usage = waypoint_common::QuotaUsage {
+            referenced: 95,
+            exclusive: 95,
+            limit: Some(100),
+        };
+        let report =
+            WaypointHelper::cleanup_for_quota(true, &quota_config, &usage, &config).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(report.dry_run);
+        assert_eq!(report.snapshots, vec!["unpinned".to_string()]);
+        assert_eq!(report.skipped_favorites, vec!["pinned".to_string()]);
+    }
+}
@@ -0,0 +1,221 @@
+// Snapshot metadata consistency check for waypoint-helper
+//
+// Finds metadata entries that share a name or a directory, or are named
+// differently than their directory, and merges or removes them as the user
+// chooses. Works on the entries as stored, before loading replaces each path
+// with the one the name implies.
+
+use crate::btrfs::Snapshot;
+use std::collections::HashMap;
+use waypoint_common::{ConflictKind, ConflictResolution, MetadataConflict};
+
+/// Name of the directory a snapshot entry points at
+fn directory_name(snapshot: &Snapshot) -> Option<&str> {
+    snapshot.path.file_name().and_then(|n| n.to_str())
+}
+
+/// Find entries that share a name or a directory, or don't match their directory
+///
+/// Expects only entries whose directory exists.
+pub fn find_conflicts(snapshots: &[Snapshot]) -> Vec<MetadataConflict> {
+    fn root(parent: &[usize], mut index: usize) -> usize {
+        while parent[index] != index {
+            index = parent[index];
+        }
+        index
+    }
+
+    // Entries sharing a name or a directory end up in the same group
+    let mut parent: Vec<usize> = (0..snapshots.len()).collect();
+    let mut by_name = HashMap::new();
+    let mut by_path = HashMap::new();
+    for (index, snapshot) in snapshots.iter().enumerate() {
+        let first_with_name = *by_name.entry(snapshot.name.as_str()).or_insert(index);
+        let first_with_path = *by_path.entry(snapshot.path.as_path()).or_insert(index);
+        for other in [first_with_name, first_with_path] {
+            let (a, b) = (root(&parent, index), root(&parent, other));
+            parent[a] = b;
+        }
+    }
+
+    let mut groups: Vec<Vec<&Snapshot>> = Vec::new();
+    let mut group_of_root = HashMap::new();
+    for (index, snapshot) in snapshots.iter().enumerate() {
+        let group = *group_of_root
+            .entry(root(&parent, index))
+            .or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+        groups[group].push(snapshot);
+    }
+
+    let mut conflicts = Vec::new();
+    for entries in groups {
+        let ids = entries.iter().map(|s| s.id.clone()).collect();
+        if let [snapshot] = entries[..] {
+            if let Some(dir) = directory_name(snapshot)
+                && dir != snapshot.name
+            {
+                conflicts.push(MetadataConflict {
+                    kind: ConflictKind::NameMismatch,
+                    name: dir.to_string(),
+                    ids,
+                });
+            }
+            continue;
+        }
+
+        let kind = if entries.iter().all(|s| s.name == entries[0].name) {
+            ConflictKind::DuplicateName
+        } else {
+            ConflictKind::SharedPath
+        };
+        let name = entries
+            .iter()
+            .find(|s| directory_name(s) == Some(s.name.as_str()))
+            .map(|s| s.name.as_str())
+            .or_else(|| directory_name(entries[0]))
+            .unwrap_or(&entries[0].name)
+            .to_string();
+        conflicts.push(MetadataConflict { kind, name, ids });
+    }
+    conflicts
+}
+
+/// Fill in details `snapshot` lacks from `others`, the first one having them winning
+fn merge_details(snapshot: &mut Snapshot, others: &[Snapshot]) {
+    fn fill<T: Clone>(value: &mut Option<T>, other: &Option<T>) {
+        if value.is_none() {
+            value.clone_from(other);
+        }
+    }
+
+    for other in others {
+        fill(&mut snapshot.description, &other.description);
+        fill(&mut snapshot.kernel_version, &other.kernel_version);
+        fill(&mut snapshot.package_count, &other.package_count);
+        fill(&mut snapshot.retention_group, &other.retention_group);
+        fill(&mut snapshot.expires_at, &other.expires_at);
+        fill(&mut snapshot.transaction, &other.transaction);
+        if snapshot.packages.is_empty() {
+            snapshot.packages.clone_from(&other.packages);
+        }
+        if snapshot.subvolumes.is_empty() {
+            snapshot.subvolumes.clone_from(&other.subvolumes);
+        }
+        if snapshot.category.is_manual() {
+            snapshot.category = other.category;
+        }
+        snapshot.is_favorite |= other.is_favorite;
+    }
+}
+
+/// Resolve a conflict found by `find_conflicts` in `snapshots`
+///
+/// Returns the entry that was kept, if any of the conflict's entries remain.
+pub fn apply_resolution(
+    snapshots: &mut Vec<Snapshot>,
+    conflict: &MetadataConflict,
+    resolution: ConflictResolution,
+) -> Option<Snapshot> {
+    let involved: Vec<usize> = (0..snapshots.len())
+        .filter(|&i| conflict.ids.contains(&snapshots[i].id))
+        .collect();
+
+    // The entry named like its directory, otherwise the newest
+    let name = conflict.name.as_str();
+    let matches_directory = |s: &Snapshot| s.name == name && directory_name(s) == Some(name);
+    let newest = || {
+        involved
+            .iter()
+            .copied()
+            .max_by_key(|&i| snapshots[i].timestamp)
+    };
+    let keep = involved
+        .iter()
+        .copied()
+        .find(|&i| matches_directory(&snapshots[i]))
+        .or_else(newest)?;
+
+    let mut others: Vec<Snapshot> = involved
+        .iter()
+        .filter(|&&i| i != keep)
+        .map(|&i| snapshots[i].clone())
+        .collect();
+    others.sort_by_key(|s| std::cmp::Reverse(s.timestamp));
+
+    if resolution == ConflictResolution::Merge {
+        merge_details(&mut snapshots[keep], &others);
+        snapshots[keep].name = conflict.name.clone();
+    }
+
+    let kept = snapshots[keep].clone();
+    snapshots.retain(|s| s.id == kept.id || !conflict.ids.contains(&s.id));
+    Some(kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::path::PathBuf;
+    use waypoint_common::SnapshotCategory;
+
+    #[test]
+    fn test_metadata_conflicts() {
+        let snapshot = |id: &str, name: &str, dir: &str, description: Option<&str>| Snapshot {
+            id: id.to_string(),
+            name: name.to_string(),
+            timestamp: Utc::now(),
+            path: PathBuf::from("/.snapshots").join(dir),
+            description: description.map(String::from),
+            kernel_version: None,
+            package_count: None,
+            packages: Vec::new(),
+            subvolumes: Vec::new(),
+            retention_group: None,
+            expires_at: None,
+            transaction: None,
+            category: SnapshotCategory::Manual,
+            is_favorite: false,
+        };
+        let mut snapshots = vec![
+            snapshot("1", "daily", "daily", None),
+            snapshot("2", "daily", "daily", Some("Before upgrade")),
+            snapshot("3", "weekly", "weekly", None),
+            snapshot("4", "weekly-old", "weekly", None),
+            snapshot("5", "renamed", "new-name", None),
+            snapshot("6", "fine", "fine", None),
+        ];
+
+        let conflicts = find_conflicts(&snapshots);
+        let found: Vec<(ConflictKind, &str)> = conflicts
+            .iter()
+            .map(|c| (c.kind, c.name.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (ConflictKind::DuplicateName, "daily"),
+                (ConflictKind::SharedPath, "weekly"),
+                (ConflictKind::NameMismatch, "new-name"),
+            ]
+        );
+
+        // Merging keeps details only the dropped entry had
+        let resolutions = [
+            ConflictResolution::Merge,
+            ConflictResolution::RemoveExtra,
+            ConflictResolution::Merge,
+        ];
+        for (conflict, resolution) in conflicts.iter().zip(resolutions) {
+            assert!(apply_resolution(&mut snapshots, conflict, resolution).is_some());
+        }
+        let ids: Vec<&str> = snapshots.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["1", "3", "5", "6"]);
+        assert_eq!(snapshots[0].description.as_deref(), Some("Before upgrade"));
+        assert_eq!(snapshots[2].name, "new-name");
+        assert!(find_conflicts(&snapshots).is_empty());
+    }
+}
@@ -0,0 +1,316 @@
+// Ephemeral read-write overlays of snapshots for waypoint-helper
+//
+// Each overlay gets its own tmpfs holding the overlayfs upper and work
+// directories, so unmounting discards every change made through it. Active
+// overlays are tracked in a state file under /run so that overlays left behind
+// by a crashed client can be reaped later.
+
+use anyhow::{Context, Result, bail};
+use chrono::Utc;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use waypoint_common::OverlayMount;
+
+use crate::btrfs;
+use crate::transcript::RecordedCommand;
+
+/// Directory under which overlays are mounted
+const OVERLAY_ROOT: &str = "/run/waypoint/overlays";
+
+/// State file tracking active overlays
+const OVERLAY_STATE_FILE: &str = "/run/waypoint/overlays.json";
+
+/// Size limit of the tmpfs holding an overlay's changes
+const OVERLAY_TMPFS_SIZE: &str = "25%";
+
+/// Serializes overlay operations so reaping never races a mount in progress
+static OVERLAY_LOCK: Mutex<()> = Mutex::new(());
+
+fn lock() -> std::sync::MutexGuard<'static, ()> {
+    OVERLAY_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Map a subvolume mount point to its directory name inside a snapshot
+fn subvolume_dir_name(mount_point: &Path) -> String {
+    if mount_point == Path::new("/") {
+        "root".to_string()
+    } else {
+        mount_point
+            .to_string_lossy()
+            .trim_start_matches('/')
+            .replace('/', "_")
+    }
+}
+
+fn load_state() -> Vec<OverlayMount> {
+    fs::read_to_string(OVERLAY_STATE_FILE)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(overlays: &[OverlayMount]) -> Result<()> {
+    if let Some(parent) = Path::new(OVERLAY_STATE_FILE).parent() {
+        fs::create_dir_all(parent).context("Failed to create overlay state directory")?;
+    }
+
+    let json = serde_json::to_string_pretty(overlays)?;
+    let temp_path = format!("{OVERLAY_STATE_FILE}.tmp");
+    fs::write(&temp_path, json).context("Failed to write overlay state")?;
+    fs::rename(&temp_path, OVERLAY_STATE_FILE).context("Failed to save overlay state")?;
+    Ok(())
+}
+
+fn process_alive(pid: u32) -> bool {
+    pid != 0 && Path::new(&format!("/proc/{pid}")).exists()
+}
+
+fn is_mounted(path: &Path) -> bool {
+    let Ok(mounts) = fs::read_to_string("/proc/self/mounts") else {
+        return false;
+    };
+    let path = path.to_string_lossy();
+    mounts
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .any(|mount_point| mount_point == path)
+}
+
+fn umount(path: &Path) -> Result<()> {
+    if !is_mounted(path) {
+        return Ok(());
+    }
+
+    let output = std::process::Command::new("umount")
+        .arg(path)
+        .recorded_output()
+        .context("Failed to execute umount")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("Failed to unmount {}: {}", path.display(), stderr.trim());
+    }
+    Ok(())
+}
+
+/// Tear down an overlay: merged view first, then its tmpfs, then the directories
+fn teardown(base: &Path) -> Result<()> {
+    umount(&base.join("merged"))?;
+    umount(base)?;
+    fs::remove_dir_all(base).context(format!("Failed to remove {}", base.display()))?;
+    Ok(())
+}
+
+/// Base directory of an overlay given its merged mount point
+fn overlay_base(mount_point: &Path) -> Result<PathBuf> {
+    let base = mount_point
+        .parent()
+        .filter(|base| base.parent() == Some(Path::new(OVERLAY_ROOT)))
+        .context("Not a Waypoint overlay mount")?;
+    Ok(base.to_path_buf())
+}
+
+/// Mount a writable overlay of one subvolume of a snapshot
+pub fn mount_overlay(
+    snapshot_name: &str,
+    subvolume: &Path,
+    owner_pid: u32,
+) -> Result<OverlayMount> {
+    btrfs::ensure_snapshot_name(snapshot_name)?;
+
+    let _guard = lock();
+    reap_stale_locked();
+
+    let metadata = btrfs::get_snapshot_metadata(snapshot_name)?;
+    if !metadata.subvolumes.is_empty() && !metadata.subvolumes.iter().any(|s| s == subvolume) {
+        bail!(
+            "Snapshot '{snapshot_name}' does not include subvolume {}",
+            subvolume.display()
+        );
+    }
+
+    let lower = btrfs::locate_snapshot(snapshot_name).join(subvolume_dir_name(subvolume));
+    if !lower.is_dir() {
+        bail!("Snapshot subvolume not found: {}", lower.display());
+    }
+
+    // overlayfs uses ',' and ':' as option separators
+    if lower.to_string_lossy().contains([',', ':']) {
+        bail!("Snapshot path {} cannot be used as an overlay layer", lower.display());
+    }
+
+    let base = Path::new(OVERLAY_ROOT).join(format!(
+        "{snapshot_name}-{}-{}",
+        subvolume_dir_name(subvolume),
+        Utc::now().format("%Y%m%d-%H%M%S")
+    ));
+    if base.exists() {
+        bail!("An overlay for this snapshot was just created, please try again");
+    }
+    fs::create_dir_all(&base).context("Failed to create overlay directory")?;
+
+    let result = (|| -> Result<PathBuf> {
+        let size_option = format!("size={OVERLAY_TMPFS_SIZE},mode=0755");
+        crate::run_command(
+            "mount",
+            &[
+                "-t",
+                "tmpfs",
+                "-o",
+                &size_option,
+                "waypoint-overlay",
+                &base.to_string_lossy(),
+            ],
+        )?;
+
+        let upper = base.join("upper");
+        let work = base.join("work");
+        let merged = base.join("merged");
+        for dir in [&upper, &work, &merged] {
+            fs::create_dir(dir).context(format!("Failed to create {}", dir.display()))?;
+        }
+
+        let options = format!(
+            "lowerdir={},upperdir={},workdir={}",
+            lower.display(),
+            upper.display(),
+            work.display()
+        );
+        crate::run_command(
+            "mount",
+            &[
+                "-t",
+                "overlay",
+                "-o",
+                &options,
+                "overlay",
+                &merged.to_string_lossy(),
+            ],
+        )?;
+
+        Ok(merged)
+    })();
+
+    let merged = match result {
+        Ok(merged) => merged,
+        Err(e) => {
+            if let Err(cleanup_err) = teardown(&base) {
+                log::warn!(
+                    "Failed to clean up partial overlay {}: {cleanup_err}",
+                    base.display()
+                );
+            }
+            return Err(e);
+        }
+    };
+
+    let overlay = OverlayMount {
+        snapshot_name: snapshot_name.to_string(),
+        subvolume: subvolume.to_path_buf(),
+        mount_point: merged,
+        created_at: Utc::now(),
+        owner_pid,
+    };
+
+    let mut overlays = load_state();
+    overlays.push(overlay.clone());
+    if let Err(e) = save_state(&overlays) {
+        let _ = teardown(&base);
+        return Err(e);
+    }
+
+    log::info!(
+        "Mounted ephemeral overlay of {snapshot_name} ({}) at {}",
+        subvolume.display(),
+        overlay.mount_point.display()
+    );
+    Ok(overlay)
+}
+
+/// Unmount an overlay and discard all changes made through it
+pub fn unmount_overlay(mount_point: &Path) -> Result<()> {
+    let _guard = lock();
+    let mut overlays = load_state();
+    let Some(index) = overlays.iter().position(|o| o.mount_point == mount_point) else {
+        bail!("No active overlay at {}", mount_point.display());
+    };
+
+    teardown(&overlay_base(mount_point)?)?;
+    let overlay = overlays.remove(index);
+    save_state(&overlays)?;
+
+    log::info!(
+        "Unmounted overlay of {} at {}",
+        overlay.snapshot_name,
+        mount_point.display()
+    );
+    Ok(())
+}
+
+/// PID of the process that owns the overlay mounted at `mount_point`
+pub fn overlay_owner(mount_point: &Path) -> Option<u32> {
+    load_state()
+        .into_iter()
+        .find(|o| o.mount_point == mount_point)
+        .map(|o| o.owner_pid)
+}
+
+/// List active overlays, reaping any whose owner has exited
+pub fn list_overlays() -> Vec<OverlayMount> {
+    let _guard = lock();
+    reap_stale_locked();
+    load_state()
+}
+
+/// Remove overlays whose owning process is gone, plus untracked leftovers
+///
+/// Called at helper startup and before overlays are listed or created, so an
+/// overlay left behind by a crashed client never outlives it for long.
+pub fn reap_stale_overlays() {
+    let _guard = lock();
+    reap_stale_locked();
+}
+
+fn reap_stale_locked() {
+    let overlays = load_state();
+    let mut kept = Vec::new();
+
+    for overlay in overlays {
+        if process_alive(overlay.owner_pid) && is_mounted(&overlay.mount_point) {
+            kept.push(overlay);
+            continue;
+        }
+
+        log::info!(
+            "Reaping stale overlay of {} at {}",
+            overlay.snapshot_name,
+            overlay.mount_point.display()
+        );
+        if let Err(e) = overlay_base(&overlay.mount_point).and_then(|base| teardown(&base)) {
+            log::warn!(
+                "Failed to reap overlay {}: {e}",
+                overlay.mount_point.display()
+            );
+            kept.push(overlay);
+        }
+    }
+
+    // Directories that aren't tracked at all (e.g. state lost mid-mount)
+    if let Ok(entries) = fs::read_dir(OVERLAY_ROOT) {
+        for entry in entries.flatten() {
+            let base = entry.path();
+            if kept.iter().any(|o| o.mount_point.parent() == Some(base.as_path())) {
+                continue;
+            }
+            log::info!("Removing untracked overlay directory {}", base.display());
+            if let Err(e) = teardown(&base) {
+                log::warn!("Failed to remove {}: {e}", base.display());
+            }
+        }
+    }
+
+    if let Err(e) = save_state(&kept) {
+        log::warn!("Failed to update overlay state: {e}");
+    }
+}
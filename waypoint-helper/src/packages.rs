@@ -2,14 +2,19 @@
 
 use anyhow::{Context, Result};
 use std::process::Command;
-use waypoint_common::Package;
+use std::sync::atomic::AtomicBool;
+use waypoint_common::{Package, WaypointConfig};
 
 /// Get list of all installed packages using xbps-query
-pub fn get_installed_packages() -> Result<Vec<Package>> {
-    let output = Command::new("xbps-query")
-        .arg("-l")
-        .output()
-        .context("Failed to execute xbps-query. Is XBPS installed?")?;
+///
+/// Limited to the configured snapshot timeout; `cancel` stops it early.
+pub fn get_installed_packages(cancel: Option<&AtomicBool>) -> Result<Vec<Package>> {
+    let output = crate::output_with_timeout(
+        Command::new("xbps-query").arg("-l"),
+        WaypointConfig::new().snapshot_timeout(),
+        cancel,
+    )
+    .context("Failed to execute xbps-query. Is XBPS installed?")?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
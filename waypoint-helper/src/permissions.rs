@@ -0,0 +1,72 @@
+// Hardening of the snapshot directory permissions
+//
+// Snapshots of `/` hold copies of root's files with the permissions they had
+// when the snapshot was taken. A snapshot directory other users can enter lets
+// them read anything that was readable then, so the helper warns about such
+// directories at startup and can make them root-only (mode 700), either on
+// request or, with WAYPOINT_ENFORCE_SNAPSHOT_DIR_PERMISSIONS, at every start.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use waypoint_common::permissions::{SECURE_SNAPSHOT_DIR_MODE, inspect_snapshot_dirs};
+use waypoint_common::{SnapshotDirPermissions, WaypointConfig};
+
+/// Make a directory owned by root and accessible to root only
+fn secure_dir(path: &Path) -> Result<()> {
+    std::os::unix::fs::chown(path, Some(0), Some(0))
+        .with_context(|| format!("Failed to change the owner of {}", path.display()))?;
+    fs::set_permissions(path, fs::Permissions::from_mode(SECURE_SNAPSHOT_DIR_MODE))
+        .with_context(|| format!("Failed to change the permissions of {}", path.display()))
+}
+
+/// Make every snapshot directory that isn't root-only root-only
+///
+/// Returns the permissions of all snapshot directories afterwards.
+pub fn secure_snapshot_dirs() -> Result<Vec<SnapshotDirPermissions>> {
+    let config = WaypointConfig::new();
+    for permissions in inspect_snapshot_dirs(&config) {
+        if permissions.is_secure() {
+            continue;
+        }
+        secure_dir(&permissions.path)?;
+        log::info!(
+            "Restricted {} to root (was {}, owner {})",
+            permissions.path.display(),
+            permissions.mode_string(),
+            permissions.owner_uid
+        );
+    }
+    Ok(inspect_snapshot_dirs(&config))
+}
+
+/// Warn about snapshot directories other users can read, or fix them if configured
+pub fn check_at_startup() {
+    let config = WaypointConfig::new();
+    let insecure: Vec<_> = inspect_snapshot_dirs(&config)
+        .into_iter()
+        .filter(|permissions| !permissions.is_secure())
+        .collect();
+    if insecure.is_empty() {
+        return;
+    }
+
+    if config.enforce_snapshot_dir_permissions {
+        if let Err(e) = secure_snapshot_dirs() {
+            log::error!("Failed to restrict snapshot directories to root: {e:#}");
+        }
+        return;
+    }
+
+    for permissions in insecure {
+        log::warn!(
+            "Snapshot directory {} is not root-only ({}, owner {}); other users may read \
+             files from snapshots. Fix it from Waypoint or with: chmod 700 {}",
+            permissions.path.display(),
+            permissions.mode_string(),
+            permissions.owner_uid,
+            permissions.path.display()
+        );
+    }
+}
@@ -0,0 +1,170 @@
+// Graceful shutdown for waypoint-helper
+//
+// Exiting on SIGTERM while a backup or restore is running would abandon it
+// halfway and leave partial subvolumes behind. Operations register here while
+// they run; on shutdown the helper refuses new ones, waits for the running
+// ones to finish and cancels whatever is still running after the grace period,
+// which kills their commands and removes partial subvolumes.
+
+use anyhow::{Result, bail};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use waypoint_common::{ErrorCode, HelperError};
+
+/// How long running operations get to finish on their own
+pub const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(60);
+
+/// How long cancelled operations get to kill their commands and clean up
+const CANCEL_GRACE_PERIOD: Duration = Duration::from_secs(15);
+
+/// A running operation and its cancellation flag, if it can be cancelled
+struct RunningOperation {
+    operation: &'static str,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+#[derive(Default)]
+struct State {
+    shutting_down: bool,
+    running: HashMap<u64, RunningOperation>,
+}
+
+static STATE: LazyLock<Mutex<State>> = LazyLock::new(Default::default);
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+fn with_state<T>(f: impl FnOnce(&mut State) -> T) -> T {
+    let mut state = STATE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(&mut state)
+}
+
+/// Held while an operation runs; unregisters it when dropped
+#[derive(Debug)]
+pub struct OperationGuard {
+    id: u64,
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        with_state(|state| state.running.remove(&self.id));
+    }
+}
+
+/// Register a running operation, or fail if the helper is shutting down
+///
+/// Operations that pass a cancellation flag are cancelled if they are still
+/// running when the grace period ends.
+pub fn begin(operation: &'static str, cancel: Option<Arc<AtomicBool>>) -> Result<OperationGuard> {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let accepted = with_state(|state| {
+        if state.shutting_down {
+            return false;
+        }
+        state
+            .running
+            .insert(id, RunningOperation { operation, cancel });
+        true
+    });
+
+    if !accepted {
+        log::warn!("Refusing to start {operation}: helper is shutting down");
+        bail!(HelperError::new(
+            ErrorCode::Busy,
+            "Waypoint helper is shutting down, please try again later",
+        ));
+    }
+    Ok(OperationGuard { id })
+}
+
+/// Names of the operations still running
+fn running_operations() -> Vec<&'static str> {
+    with_state(|state| state.running.values().map(|op| op.operation).collect())
+}
+
+/// Wait until no operation is running, giving up after `timeout`
+async fn wait_until_idle(timeout: Duration) -> bool {
+    let started = Instant::now();
+    loop {
+        if running_operations().is_empty() {
+            return true;
+        }
+        if started.elapsed() >= timeout {
+            return false;
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+}
+
+/// Stop accepting operations and let running ones finish or cancel them
+pub async fn shutdown(grace_period: Duration) {
+    let running = with_state(|state| {
+        state.shutting_down = true;
+        state.running.len()
+    });
+    if running == 0 {
+        return;
+    }
+
+    log::info!(
+        "Waiting up to {} seconds for {running} running operation(s) to finish",
+        grace_period.as_secs()
+    );
+    if wait_until_idle(grace_period).await {
+        log::info!("All operations finished");
+        return;
+    }
+
+    let cancelled = with_state(|state| {
+        state
+            .running
+            .values()
+            .filter_map(|op| op.cancel.as_ref().map(|cancel| (op.operation, cancel)))
+            .map(|(operation, cancel)| {
+                cancel.store(true, Ordering::Relaxed);
+                operation
+            })
+            .collect::<Vec<_>>()
+    });
+    if !cancelled.is_empty() {
+        log::warn!(
+            "Cancelling operations still running: {}",
+            cancelled.join(", ")
+        );
+    }
+
+    if !wait_until_idle(CANCEL_GRACE_PERIOD).await {
+        log::error!(
+            "Exiting with operations still running: {}",
+            running_operations().join(", ")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_shutdown_cancels_and_refuses_operations() {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let guard = begin("backup", Some(cancel.clone())).unwrap();
+
+        // The operation stops once it sees the cancellation
+        let worker = tokio::spawn(async move {
+            while !cancel.load(Ordering::Relaxed) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            drop(guard);
+        });
+
+        shutdown(Duration::from_millis(50)).await;
+        worker.await.unwrap();
+        assert!(running_operations().is_empty());
+
+        let err = begin("delete", None).unwrap_err();
+        assert_eq!(HelperError::code_of(&err), ErrorCode::Busy);
+    }
+}
@@ -0,0 +1,73 @@
+// Per-snapshot operation locks for waypoint-helper
+//
+// Deleting a snapshot while it is being backed up, or two clients restoring
+// the same backup at once, can leave snapshots half-removed or half-received.
+// Operations that modify or read a whole snapshot take its lock first and fail
+// fast with a "busy" error instead of waiting.
+
+use anyhow::{Result, bail};
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use waypoint_common::{ErrorCode, HelperError};
+
+/// Locked snapshot names and the operation holding each lock
+static LOCKED_SNAPSHOTS: LazyLock<Mutex<HashMap<String, &'static str>>> =
+    LazyLock::new(Default::default);
+
+fn with_locks<T>(f: impl FnOnce(&mut HashMap<String, &'static str>) -> T) -> T {
+    let mut locks = LOCKED_SNAPSHOTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(&mut locks)
+}
+
+/// Held while an operation runs on a snapshot; releases the lock when dropped
+#[derive(Debug)]
+pub struct SnapshotGuard {
+    name: String,
+}
+
+impl Drop for SnapshotGuard {
+    fn drop(&mut self) {
+        with_locks(|locks| locks.remove(&self.name));
+    }
+}
+
+/// Lock a snapshot for `operation`, or fail if another operation holds it
+pub fn try_lock(name: &str, operation: &'static str) -> Result<SnapshotGuard> {
+    let holder = with_locks(|locks| match locks.get(name) {
+        Some(holder) => Some(*holder),
+        None => {
+            locks.insert(name.to_string(), operation);
+            None
+        }
+    });
+
+    if let Some(holder) = holder {
+        log::warn!("Refusing to {operation} snapshot '{name}': {holder} in progress");
+        bail!(HelperError::new(
+            ErrorCode::Busy,
+            format!("Snapshot '{name}' is busy ({holder} in progress), please try again later"),
+        ));
+    }
+
+    Ok(SnapshotGuard {
+        name: name.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_lock_is_exclusive_until_dropped() {
+        let guard = try_lock("lock-test-snapshot", "backup").unwrap();
+        let err = try_lock("lock-test-snapshot", "delete").unwrap_err();
+        assert!(err.to_string().contains("busy (backup in progress)"));
+        assert!(try_lock("lock-test-other", "delete").is_ok());
+
+        drop(guard);
+        assert!(try_lock("lock-test-snapshot", "delete").is_ok());
+    }
+}
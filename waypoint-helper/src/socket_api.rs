@@ -0,0 +1,314 @@
+// Unix socket JSON API for waypoint-helper
+//
+// Containers and minimal systems don't always have a convenient way to reach
+// the system bus. With WAYPOINT_SOCKET_API=true the helper also accepts
+// newline-delimited JSON requests on a Unix socket. Callers are identified by
+// the socket's peer credentials and authorized through the same polkit actions
+// as over D-Bus, and requests run the same code as the D-Bus methods.
+//
+// Request:  {"id": 1, "method": "DeleteSnapshot", "params": ["before-upgrade"]}
+// Response: {"id": 1, "success": true, "result": "Snapshot 'before-upgrade' deleted successfully"}
+
+use anyhow::{Context, Result, bail};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::path::Path;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use waypoint_common::*;
+use zbus::Connection;
+use zbus::object_server::InterfaceRef;
+
+use crate::{WaypointHelper, audit};
+
+/// Longest request line accepted
+const MAX_REQUEST_BYTES: u64 = 1024 * 1024;
+
+/// Methods available over the socket, with the polkit action each requires
+///
+/// Names and positional parameters match the D-Bus methods. Operations that
+/// report progress through signals stay D-Bus only.
+const METHODS: &[(&str, Option<&str>)] = &[
+    ("GetCapabilities", None),
+    ("ListSnapshots", None),
+    ("ListSnapshotSummaries", None),
+    ("GetSnapshotPackages", None),
+    ("GetSchedulerStatus", None),
+    ("GetQuotaUsage", None),
+    ("GetFilesystemStats", None),
+    ("GetSnapshotCompression", None),
+    ("GetSnapshotSharing", None),
+    ("CreateSnapshot", Some(POLKIT_ACTION_CREATE)),
+    ("DeleteSnapshot", Some(POLKIT_ACTION_DELETE)),
+    ("RestoreSnapshot", Some(POLKIT_ACTION_RESTORE)),
+    ("CleanupSnapshots", Some(POLKIT_ACTION_DELETE)),
+];
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    /// Echoed back so clients can match responses to requests
+    #[serde(default)]
+    id: serde_json::Value,
+    method: String,
+    #[serde(default)]
+    params: Vec<serde_json::Value>,
+}
+
+/// The `(success, message)` pair a D-Bus method returns
+#[derive(Debug, Serialize)]
+struct Response {
+    id: serde_json::Value,
+    success: bool,
+    result: String,
+}
+
+/// Process on the other end of a connection
+struct Caller {
+    uid: u32,
+    /// 0 when the kernel didn't report it, which polkit never authorizes
+    pid: u32,
+    /// Start time of `pid` when the connection was accepted, if readable
+    start_time: Option<u64>,
+}
+
+/// Positional parameter `index` of a request
+fn param<T: DeserializeOwned>(params: &[serde_json::Value], index: usize) -> Result<T> {
+    let value = params.get(index).cloned().ok_or_else(|| {
+        HelperError::new(
+            ErrorCode::InvalidInput,
+            format!("Missing parameter {}", index + 1),
+        )
+    })?;
+    serde_json::from_value(value).map_err(|e| {
+        HelperError::new(
+            ErrorCode::InvalidInput,
+            format!("Invalid parameter {}: {e}", index + 1),
+        )
+        .into()
+    })
+}
+
+/// Authorize and run one request
+async fn dispatch(
+    helper: &InterfaceRef<WaypointHelper>,
+    connection: &Connection,
+    caller: &Caller,
+    request: &Request,
+) -> Result<(bool, String)> {
+    let Some((method, action)) = METHODS.iter().find(|(name, _)| *name == request.method) else {
+        bail!(HelperError::new(
+            ErrorCode::InvalidInput,
+            format!("Unknown method '{}'", request.method),
+        ));
+    };
+
+    let uid = caller.uid.to_string();
+    let pid = caller.pid;
+    if let Some(action) = action {
+        // Without the start time, a later process reusing the PID could pass for the caller
+        let result = match caller.start_time {
+            Some(start_time) => {
+                let subject = crate::ProcessSubject {
+                    pid,
+                    start_time,
+                    uid: Some(caller.uid),
+                };
+                crate::check_process_authorization(connection, &subject, action).await
+            }
+            None => Err(anyhow::anyhow!("Could not identify process {pid}")),
+        };
+        if let Err(e) = result {
+            audit::log_auth_failure(uid, pid, action, &e.to_string());
+            return Ok((false, crate::auth_failure_message(&e)));
+        }
+    }
+
+    let params = &request.params;
+    let helper_ref = helper.get().await;
+    Ok(match *method {
+        "GetCapabilities" => (true, helper_ref.get_capabilities().await),
+        "ListSnapshots" => (true, helper_ref.list_snapshots().await),
+        "ListSnapshotSummaries" => (true, helper_ref.list_snapshot_summaries().await),
+        "GetSnapshotPackages" => (
+            true,
+            helper_ref.get_snapshot_packages(param(params, 0)?).await,
+        ),
+        "GetSchedulerStatus" => (true, helper_ref.get_scheduler_status().await),
+        "GetQuotaUsage" => helper_ref.get_quota_usage().await,
+        "GetFilesystemStats" => helper_ref.get_filesystem_stats(param(params, 0)?).await,
+        "GetSnapshotCompression" => helper_ref.get_snapshot_compression().await,
+        "GetSnapshotSharing" => helper_ref.get_snapshot_sharing().await,
+        "CreateSnapshot" => {
+            helper_ref
+                .create_snapshot_as(
+                    helper.signal_context(),
+                    uid,
+                    pid,
+                    "socket",
+                    param(params, 0)?,
+                    param(params, 1)?,
+                    param(params, 2)?,
+                )
+                .await
+        }
+        "DeleteSnapshot" => {
+            helper_ref
+                .delete_snapshot_as(uid, pid, param(params, 0)?)
+                .await
+        }
+        "RestoreSnapshot" => {
+            helper_ref
+                .restore_snapshot_as(uid, pid, param(params, 0)?)
+                .await
+        }
+        "CleanupSnapshots" => helper_ref.cleanup_snapshots_as(param(params, 0)?).await,
+        _ => unreachable!("every entry of METHODS is handled"),
+    })
+}
+
+/// Answer requests on one connection until the client closes it
+async fn handle_client(
+    stream: UnixStream,
+    helper: InterfaceRef<WaypointHelper>,
+    connection: Connection,
+) -> Result<()> {
+    let credentials = stream
+        .peer_cred()
+        .context("Failed to read peer credentials")?;
+    let pid = credentials
+        .pid()
+        .and_then(|pid| u32::try_from(pid).ok())
+        .unwrap_or(0);
+    let caller = Caller {
+        uid: credentials.uid(),
+        pid,
+        start_time: (pid != 0)
+            .then(|| crate::get_process_start_time(pid).ok())
+            .flatten(),
+    };
+
+    let (read_half, mut write_half) = stream.into_split();
+    let mut reader = BufReader::new(read_half);
+    loop {
+        let mut line = String::new();
+        let read = (&mut reader)
+            .take(MAX_REQUEST_BYTES)
+            .read_line(&mut line)
+            .await?;
+        if read == 0 {
+            return Ok(());
+        }
+        if !line.ends_with('\n') && read as u64 >= MAX_REQUEST_BYTES {
+            bail!("Request from uid {} is too large", caller.uid);
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => {
+                let (success, result) = dispatch(&helper, &connection, &caller, &request)
+                    .await
+                    .unwrap_or_else(|e| (false, crate::error_message(&e, "Invalid request")));
+                Response {
+                    id: request.id,
+                    success,
+                    result,
+                }
+            }
+            Err(e) => Response {
+                id: serde_json::Value::Null,
+                success: false,
+                result: HelperError::new(ErrorCode::InvalidInput, format!("Invalid request: {e}"))
+                    .encode(),
+            },
+        };
+
+        let mut reply = serde_json::to_string(&response)?;
+        reply.push('\n');
+        write_half.write_all(reply.as_bytes()).await?;
+    }
+}
+
+/// Listen on `path` and serve requests in the background
+///
+/// Only root and members of `group` may connect. Without that group the
+/// socket is root-only.
+pub async fn serve(connection: &Connection, path: &Path, group: &str) -> Result<()> {
+    let helper = connection
+        .object_server()
+        .interface::<_, WaypointHelper>(DBUS_OBJECT_PATH)
+        .await
+        .context("Helper interface is not registered")?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    // A socket left behind by a helper that didn't exit cleanly
+    if std::fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_socket()) {
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove stale {}", path.display()))?;
+    }
+
+    let listener = UnixListener::bind(path)
+        .with_context(|| format!("Failed to listen on {}", path.display()))?;
+    // Privileged methods are still authorized per request, but even the
+    // read-only ones scan every snapshot, so not everyone may connect
+    let mode = match nix::unistd::Group::from_name(group) {
+        Ok(Some(group)) => {
+            nix::unistd::chown(path, None, Some(group.gid))
+                .with_context(|| format!("Failed to change group of {}", path.display()))?;
+            0o660
+        }
+        _ => {
+            log::warn!("Group '{group}' not found, only root can use the socket API");
+            0o600
+        }
+    };
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+        .with_context(|| format!("Failed to set permissions of {}", path.display()))?;
+    log::info!("Socket API listening on {}", path.display());
+
+    let connection = connection.clone();
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    let helper = helper.clone();
+                    let connection = connection.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_client(stream, helper, connection).await {
+                            log::warn!("Socket API client failed: {e:#}");
+                        }
+                    });
+                }
+                Err(e) => log::warn!("Socket API failed to accept a connection: {e}"),
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_params() {
+        let request: Request = serde_json::from_str(
+            r#"{"id": 7, "method": "CreateSnapshot", "params": ["daily", "", ["/"]]}"#,
+        )
+        .unwrap();
+        assert_eq!(request.id, serde_json::json!(7));
+        assert_eq!(param::<String>(&request.params, 0).unwrap(), "daily");
+        assert_eq!(
+            param::<Vec<String>>(&request.params, 2).unwrap(),
+            vec!["/".to_string()]
+        );
+        assert!(param::<bool>(&request.params, 0).is_err());
+        assert!(param::<String>(&request.params, 3).is_err());
+    }
+}
@@ -0,0 +1,142 @@
+// Transcript of the commands run by waypoint-helper
+//
+// Every command the helper runs as root is recorded here before it starts,
+// as a command line that can be pasted into a shell. Users who used to manage
+// btrfs by hand can check exactly what Waypoint did through
+// `GetCommandTranscript`. Only the most recent commands are kept, in memory.
+
+use std::collections::VecDeque;
+use std::io;
+use std::process::{Child, Command, Output};
+use std::sync::{LazyLock, Mutex};
+use waypoint_common::CommandRecord;
+
+/// Number of commands kept in the transcript
+const MAX_RECORDS: usize = 500;
+
+#[derive(Default)]
+struct Transcript {
+    next_sequence: u64,
+    records: VecDeque<CommandRecord>,
+}
+
+static TRANSCRIPT: LazyLock<Mutex<Transcript>> = LazyLock::new(Default::default);
+
+/// Quote an argument for a POSIX shell, leaving plain words alone
+fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-+=@%:,./".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+/// Command line of a command, with the environment variables it sets
+fn command_line(command: &Command) -> String {
+    let envs = command.get_envs().filter_map(|(key, value)| {
+        value.map(|value| {
+            format!(
+                "{}={}",
+                key.to_string_lossy(),
+                shell_quote(&value.to_string_lossy())
+            )
+        })
+    });
+    let program = std::iter::once(shell_quote(&command.get_program().to_string_lossy()));
+    let args = command
+        .get_args()
+        .map(|arg| shell_quote(&arg.to_string_lossy()));
+
+    envs.chain(program)
+        .chain(args)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Record a command that is about to run
+pub fn record(command: &Command) {
+    let line = command_line(command);
+    log::debug!("Running: {line}");
+
+    let mut transcript = TRANSCRIPT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let sequence = transcript.next_sequence;
+    transcript.next_sequence += 1;
+    if transcript.records.len() == MAX_RECORDS {
+        transcript.records.pop_front();
+    }
+    transcript.records.push_back(CommandRecord {
+        sequence,
+        timestamp: chrono::Utc::now(),
+        command: line,
+    });
+}
+
+/// The most recent commands, oldest first (`max_records` 0 = all that are kept)
+pub fn recent(max_records: usize) -> Vec<CommandRecord> {
+    let transcript = TRANSCRIPT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let skip = match max_records {
+        0 => 0,
+        max => transcript.records.len().saturating_sub(max),
+    };
+    transcript.records.iter().skip(skip).cloned().collect()
+}
+
+/// Run commands with a record in the transcript
+///
+/// Use these instead of `output` and `spawn` for every command the helper runs.
+pub trait RecordedCommand {
+    /// Record the command, then run it to completion like `Command::output`
+    fn recorded_output(&mut self) -> io::Result<Output>;
+
+    /// Record the command, then start it like `Command::spawn`
+    fn recorded_spawn(&mut self) -> io::Result<Child>;
+}
+
+impl RecordedCommand for Command {
+    fn recorded_output(&mut self) -> io::Result<Output> {
+        record(self);
+        self.output()
+    }
+
+    fn recorded_spawn(&mut self) -> io::Result<Child> {
+        record(self);
+        self.spawn()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_line_quoting() {
+        let mut command = Command::new("btrfs");
+        command
+            .args(["subvolume", "snapshot", "-r", "/"])
+            .arg("/.snapshots/it's here/root")
+            .env("LC_ALL", "C");
+        assert_eq!(
+            command_line(&command),
+            r"LC_ALL=C btrfs subvolume snapshot -r / '/.snapshots/it'\''s here/root'"
+        );
+        assert_eq!(shell_quote(""), "''");
+    }
+
+    #[test]
+    fn test_transcript_keeps_recent_commands() {
+        let output = Command::new("true").recorded_output().unwrap();
+        assert!(output.status.success());
+
+        // Other tests may run commands at the same time
+        assert!(recent(0).iter().any(|record| record.command == "true"));
+        assert_eq!(recent(1).len(), 1);
+    }
+}
@@ -0,0 +1,164 @@
+// Coordination between schedule threads
+//
+// Schedules that fire at the same time (e.g. hourly and daily at midnight)
+// would otherwise each run retention cleanup right after their snapshot. The
+// coordinator caps how many snapshots are created at once and runs cleanup
+// once per batch: after a snapshot succeeds, cleanup waits for the debounce
+// period and only runs if no other snapshot finished or is pending by then.
+
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+
+/// How long cleanup waits for other snapshots of the same batch
+pub const RETENTION_DEBOUNCE: Duration = Duration::from_secs(30);
+
+#[derive(Default)]
+struct State {
+    /// Snapshot attempts waiting for a slot or running
+    attempts: usize,
+    /// Snapshot attempts holding a slot
+    running: usize,
+    /// A successful snapshot hasn't been followed by cleanup yet
+    retention_pending: bool,
+    /// Bumped on every cleanup request, so older waiters know to stand down
+    retention_generation: u64,
+}
+
+pub struct Coordinator {
+    state: Mutex<State>,
+    slot_freed: Condvar,
+    max_concurrent: usize,
+    debounce: Duration,
+}
+
+/// A slot for creating a snapshot, released when dropped
+pub struct Permit<'a> {
+    coordinator: &'a Coordinator,
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        let mut state = self.coordinator.lock();
+        state.running -= 1;
+        state.attempts -= 1;
+        drop(state);
+        self.coordinator.slot_freed.notify_one();
+    }
+}
+
+impl Coordinator {
+    pub fn new(max_concurrent: usize, debounce: Duration) -> Self {
+        Self {
+            state: Mutex::new(State::default()),
+            slot_freed: Condvar::new(),
+            max_concurrent: max_concurrent.max(1),
+            debounce,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Wait for a free slot to create a snapshot
+    pub fn acquire(&self) -> Permit<'_> {
+        let mut state = self.lock();
+        state.attempts += 1;
+        while state.running >= self.max_concurrent {
+            state = self
+                .slot_freed
+                .wait(state)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+        state.running += 1;
+        Permit { coordinator: self }
+    }
+
+    /// Note that a snapshot was created and retention cleanup is due
+    pub fn request_retention(&self) {
+        let mut state = self.lock();
+        state.retention_pending = true;
+        state.retention_generation += 1;
+    }
+
+    /// Run `cleanup` if it is due and the batch has settled
+    ///
+    /// Called by every schedule thread after its attempt. Returns without
+    /// running cleanup when another thread will take care of it: one that
+    /// requested cleanup later, or one whose attempt is still in progress.
+    pub fn run_retention_when_settled(&self, cleanup: impl FnOnce()) -> bool {
+        let generation = {
+            let state = self.lock();
+            if !state.retention_pending {
+                return false;
+            }
+            state.retention_generation
+        };
+
+        thread::sleep(self.debounce);
+
+        {
+            let mut state = self.lock();
+            if !state.retention_pending
+                || state.retention_generation != generation
+                || state.attempts > 0
+            {
+                return false;
+            }
+            state.retention_pending = false;
+        }
+
+        // Hold a slot so cleanup doesn't run alongside snapshot creation
+        let _permit = self.acquire();
+        cleanup();
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_batch_runs_cleanup_once() {
+        let coordinator = Arc::new(Coordinator::new(1, Duration::from_millis(100)));
+        let cleanups = Arc::new(AtomicUsize::new(0));
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..5)
+            .map(|_| {
+                let coordinator = Arc::clone(&coordinator);
+                let cleanups = Arc::clone(&cleanups);
+                let running = Arc::clone(&running);
+                let max_running = Arc::clone(&max_running);
+                thread::spawn(move || {
+                    {
+                        let _permit = coordinator.acquire();
+                        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        max_running.fetch_max(now, Ordering::SeqCst);
+                        thread::sleep(Duration::from_millis(10));
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        coordinator.request_retention();
+                    }
+                    coordinator.run_retention_when_settled(|| {
+                        cleanups.fetch_add(1, Ordering::SeqCst);
+                    });
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(cleanups.load(Ordering::SeqCst), 1);
+        assert_eq!(max_running.load(Ordering::SeqCst), 1);
+
+        // Nothing pending: no further cleanup
+        assert!(!coordinator.run_retention_when_settled(|| panic!("unexpected cleanup")));
+    }
+}
@@ -1,10 +1,14 @@
 // Waypoint Snapshot Scheduler - Rust Implementation
 // Manages multiple concurrent snapshot schedules using a thread-per-schedule model
 
+mod coordinator;
+mod validate;
+
 use anyhow::{Context, Result};
 use chrono::{Datelike, Local, Timelike};
+use coordinator::{Coordinator, RETENTION_DEBOUNCE};
 use std::process::Command;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 use waypoint_common::{Schedule, ScheduleType, SchedulesConfig, WaypointConfig};
@@ -13,6 +17,12 @@ fn main() {
     // Initialize logging
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
 
+    // Check schedules.toml and print when each schedule runs next
+    if std::env::args().skip(1).any(|arg| arg == "--validate") {
+        let valid = validate::run(&WaypointConfig::new());
+        std::process::exit(if valid { 0 } else { 1 });
+    }
+
     log::info!("================================================");
     log::info!("Waypoint Scheduler Service Starting (Rust)");
     log::info!("================================================");
@@ -21,12 +31,17 @@ fn main() {
     let config = WaypointConfig::new();
     log::info!("Schedules config: {}", config.schedules_config.display());
 
-    // Shared mutex to ensure only one snapshot creation happens at a time
-    let snapshot_lock = Arc::new(Mutex::new(()));
+    // Shared between schedule threads to limit concurrent snapshot creation
+    // and to run retention cleanup once per batch of snapshots
+    log::info!("Concurrent snapshots: {}", config.scheduler_max_concurrent);
+    let coordinator = Arc::new(Coordinator::new(
+        config.scheduler_max_concurrent,
+        RETENTION_DEBOUNCE,
+    ));
 
     // Main service loop - monitors config and spawns schedule threads
     loop {
-        match run_scheduler(&config, Arc::clone(&snapshot_lock)) {
+        match run_scheduler(&config, Arc::clone(&coordinator)) {
             Ok(_) => {
                 // Should never return normally, but if it does, restart
                 log::warn!("Scheduler thread manager exited unexpectedly, restarting...");
@@ -41,7 +56,7 @@ fn main() {
 }
 
 /// Main scheduler - spawns one thread per enabled schedule
-fn run_scheduler(config: &WaypointConfig, snapshot_lock: Arc<Mutex<()>>) -> Result<()> {
+fn run_scheduler(config: &WaypointConfig, coordinator: Arc<Coordinator>) -> Result<()> {
     // Load schedules
     let schedules = load_schedules(config)?;
 
@@ -69,10 +84,10 @@ fn run_scheduler(config: &WaypointConfig, snapshot_lock: Arc<Mutex<()>>) -> Resu
 
     for schedule in enabled {
         let schedule_clone = schedule.clone();
-        let lock_clone = Arc::clone(&snapshot_lock);
+        let coordinator_clone = Arc::clone(&coordinator);
 
         let handle = thread::spawn(move || {
-            run_schedule_thread(schedule_clone, lock_clone);
+            run_schedule_thread(schedule_clone, coordinator_clone);
         });
 
         handles.push(handle);
@@ -88,7 +103,7 @@ fn run_scheduler(config: &WaypointConfig, snapshot_lock: Arc<Mutex<()>>) -> Resu
 }
 
 /// Run a single schedule thread - calculates next run, sleeps, creates snapshot, repeat
-fn run_schedule_thread(schedule: Schedule, snapshot_lock: Arc<Mutex<()>>) {
+fn run_schedule_thread(schedule: Schedule, coordinator: Arc<Coordinator>) {
     log::info!("[{}] Schedule thread started", schedule.prefix);
 
     loop {
@@ -105,21 +120,29 @@ fn run_schedule_thread(schedule: Schedule, snapshot_lock: Arc<Mutex<()>>) {
                 // Sleep until it's time
                 thread::sleep(sleep_duration);
 
-                // Acquire lock to ensure only one snapshot creation at a time
-                let _lock = snapshot_lock.lock().unwrap();
+                {
+                    // Wait for a free slot; limits how many snapshots are created at once
+                    let _permit = coordinator.acquire();
+
+                    // Create the snapshot; a skipped run leaves nothing new to clean up
+                    match create_snapshot(&schedule) {
+                        Ok(RunOutcome::Created) => coordinator.request_retention(),
+                        Ok(RunOutcome::Skipped) => {}
+                        Err(e) => {
+                            log::error!("[{}] Failed to create snapshot: {}", schedule.prefix, e);
+                        }
+                    }
+
+                    // Release the slot (happens automatically when _permit goes out of scope)
+                }
 
-                // Create the snapshot
-                if let Err(e) = create_snapshot(&schedule) {
-                    log::error!("[{}] Failed to create snapshot: {}", schedule.prefix, e);
-                } else {
-                    // Apply retention cleanup after successful snapshot creation
+                // Apply retention cleanup once the batch of snapshots firing together is done
+                coordinator.run_retention_when_settled(|| {
                     if let Err(e) = apply_retention_cleanup() {
                         log::warn!("[{}] Failed to apply retention cleanup: {}", schedule.prefix, e);
                         // Don't fail the schedule thread if cleanup fails
                     }
-                }
-
-                // Release lock (happens automatically when _lock goes out of scope)
+                });
             }
             Err(e) => {
                 log::error!("[{}] Failed to calculate next run time: {}", schedule.prefix, e);
@@ -148,12 +171,22 @@ fn load_schedules(config: &WaypointConfig) -> Result<SchedulesConfig> {
 fn calculate_next_run(schedule: &Schedule) -> Result<Duration> {
     let now = Local::now();
 
+    // Fixed-time schedules fire at their time or not at all
+    if schedule.schedule_type != ScheduleType::Hourly
+        && let Some(time) = schedule.time.as_deref()
+        && let Ok(time_of_day) = chrono::NaiveTime::parse_from_str(time, "%H:%M")
+        && !schedule.is_active_at(time_of_day)
+    {
+        anyhow::bail!("Schedule time {time} is outside its active hours");
+    }
+
     match schedule.schedule_type {
         ScheduleType::Hourly => {
-            // Next hour
-            let seconds_into_hour = now.minute() * 60 + now.second();
-            let seconds_until_next_hour = 3600 - seconds_into_hour;
-            Ok(Duration::from_secs(seconds_until_next_hour as u64))
+            // Next full hour within the active hours
+            let next = schedule
+                .next_hourly_run(now)
+                .ok_or_else(|| anyhow::anyhow!("No full hour falls within the active hours"))?;
+            Ok((next - now).to_std().unwrap_or_default())
         }
 
         ScheduleType::Daily => {
@@ -293,29 +326,68 @@ fn calculate_next_monthly(
 }
 
 
-/// Create a snapshot for the given schedule
-fn create_snapshot(schedule: &Schedule) -> Result<()> {
-    waypoint_common::validate_snapshot_name(&schedule.prefix)
-        .map_err(|e| anyhow::anyhow!("Invalid schedule prefix '{}': {}", schedule.prefix, e))?;
-    let snapshot_name = format!("{}-{}", schedule.prefix, Local::now().format("%Y%m%d-%H%M"));
+/// Subvolumes currently excluded from a schedule
+///
+/// Read from the configuration file at every run, so temporarily excluding a
+/// subvolume takes effect without restarting the scheduler.
+fn current_exclusions(schedule: &Schedule) -> Vec<std::path::PathBuf> {
+    let config = WaypointConfig::new();
+    match SchedulesConfig::load_from_file(&config.schedules_config) {
+        Ok(schedules) => schedules
+            .schedules
+            .into_iter()
+            .find(|s| s.prefix == schedule.prefix)
+            .map(|s| s.excluded_subvolumes)
+            .unwrap_or_else(|| schedule.excluded_subvolumes.clone()),
+        Err(e) => {
+            log::warn!("[{}] Could not reload excluded subvolumes: {}", schedule.prefix, e);
+            schedule.excluded_subvolumes.clone()
+        }
+    }
+}
 
-    log::info!("[{}] Creating scheduled snapshot: {}", schedule.prefix, snapshot_name);
+/// What a scheduled run did
+enum RunOutcome {
+    /// A snapshot was created
+    Created,
+    /// Every subvolume was temporarily excluded, so nothing was created
+    Skipped,
+}
+
+/// Create a snapshot for the given schedule
+fn create_snapshot(schedule: &Schedule) -> Result<RunOutcome> {
+    let snapshot_name = schedule.snapshot_name(Local::now()).map_err(|e| anyhow::anyhow!(e))?;
 
     // Use schedule-specific subvolumes
     // If empty, default to root filesystem only
-    let subvolumes: Vec<String> = if !schedule.subvolumes.is_empty() {
-        schedule.subvolumes.iter()
-            .filter_map(|p| p.to_str().map(|s| s.to_string()))
-            .collect()
-    } else {
+    if schedule.subvolumes.is_empty() {
         log::warn!("[{}] Schedule has no subvolumes configured, defaulting to [/]", schedule.prefix);
-        vec!["/".to_string()]
-    };
+    }
+    let mut schedule = schedule.clone();
+    schedule.excluded_subvolumes = current_exclusions(&schedule);
+    let subvolumes = schedule.active_subvolumes();
+    if subvolumes.is_empty() {
+        log::info!(
+            "[{}] All subvolumes are temporarily excluded, skipping {}",
+            schedule.prefix,
+            snapshot_name
+        );
+        return Ok(RunOutcome::Skipped);
+    }
+    for excluded in &schedule.excluded_subvolumes {
+        log::info!("[{}] Temporarily excluded: {}", schedule.prefix, excluded.display());
+    }
+
+    log::info!("[{}] Creating scheduled snapshot: {}", schedule.prefix, snapshot_name);
     let subvolumes_arg = subvolumes.join(",");
 
     // Call waypoint-cli to create snapshot with subvolumes
-    let output = Command::new("waypoint-cli")
-        .arg("create")
+    let mut command = Command::new("waypoint-cli");
+    command.arg("create");
+    if !schedule.category.is_manual() {
+        command.arg("--category").arg(schedule.category.as_str());
+    }
+    let output = command
         .arg(&snapshot_name)
         .arg(&schedule.description)
         .arg(&subvolumes_arg)
@@ -331,7 +403,7 @@ fn create_snapshot(schedule: &Schedule) -> Result<()> {
         return Err(anyhow::anyhow!("Snapshot creation failed: {stderr}"));
     }
 
-    Ok(())
+    Ok(RunOutcome::Created)
 }
 
 /// Apply retention cleanup after creating a snapshot
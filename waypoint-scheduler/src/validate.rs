@@ -0,0 +1,243 @@
+// Validation of schedules.toml (`waypoint-scheduler --validate`)
+//
+// Mistakes in the schedules only show up as snapshots that never appear. The
+// report checks everything the schedule threads rely on and shows when each
+// schedule fires next, calculated the same way the threads do.
+
+use chrono::Local;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use waypoint_common::{SchedulesConfig, WaypointConfig};
+
+/// Findings for one schedule
+#[derive(Debug, Default)]
+pub struct ScheduleReport {
+    pub prefix: String,
+    pub schedule_type: String,
+    pub enabled: bool,
+    /// Time until the next run, if the schedule is valid
+    pub next_run: Option<Duration>,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Mount points of btrfs filesystems in `/proc/self/mounts` format
+fn btrfs_mount_points(mounts: &str) -> HashSet<PathBuf> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let mount_point = fields.nth(1)?;
+            (fields.next()? == "btrfs").then(|| PathBuf::from(mount_point.replace("\\040", " ")))
+        })
+        .collect()
+}
+
+/// Check every schedule, including disabled ones
+pub fn check_schedules(
+    schedules: &SchedulesConfig,
+    btrfs_mounts: &HashSet<PathBuf>,
+) -> Vec<ScheduleReport> {
+    let mut prefix_counts: HashMap<&str, usize> = HashMap::new();
+    for schedule in &schedules.schedules {
+        *prefix_counts.entry(schedule.prefix.as_str()).or_default() += 1;
+    }
+
+    schedules
+        .schedules
+        .iter()
+        .map(|schedule| {
+            let mut report = ScheduleReport {
+                prefix: schedule.prefix.clone(),
+                schedule_type: schedule.schedule_type.as_str().to_string(),
+                enabled: schedule.enabled,
+                ..Default::default()
+            };
+
+            if let Err(e) = schedule.snapshot_name(Local::now()) {
+                report.errors.push(e);
+            }
+            if prefix_counts[schedule.prefix.as_str()] > 1 {
+                report.errors.push(format!(
+                    "Prefix '{}' is used by more than one schedule",
+                    schedule.prefix
+                ));
+            }
+
+            let fields_valid = match schedule.validate() {
+                Ok(()) => true,
+                Err(e) => {
+                    report.errors.push(e);
+                    false
+                }
+            };
+
+            for subvolume in schedule.snapshot_subvolumes() {
+                let path = Path::new(&subvolume);
+                if !path.exists() {
+                    report
+                        .errors
+                        .push(format!("Subvolume {subvolume} does not exist"));
+                } else if !btrfs_mounts.contains(path) {
+                    report
+                        .errors
+                        .push(format!("{subvolume} is not a mounted btrfs subvolume"));
+                }
+            }
+            if schedule.subvolumes.is_empty() {
+                report
+                    .warnings
+                    .push("No subvolumes configured, snapshots include / only".to_string());
+            }
+            if !schedule.excluded_subvolumes.is_empty() {
+                let excluded: Vec<String> = schedule
+                    .excluded_subvolumes
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect();
+                let warning = if schedule.active_subvolumes().is_empty() {
+                    "All subvolumes are temporarily excluded, no snapshots are created".to_string()
+                } else {
+                    format!("Temporarily excluded: {}", excluded.join(", "))
+                };
+                report.warnings.push(warning);
+            }
+
+            if let Some(day) = schedule.day_of_month
+                && day > 28
+            {
+                report.warnings.push(format!(
+                    "Not every month has a day {day}; some months may be skipped or run late"
+                ));
+            }
+
+            if fields_valid {
+                match crate::calculate_next_run(schedule) {
+                    Ok(next_run) => report.next_run = Some(next_run),
+                    Err(e) => report
+                        .errors
+                        .push(format!("Failed to calculate the next run: {e}")),
+                }
+            }
+
+            report
+        })
+        .collect()
+}
+
+/// Print a validation report for the configured schedules
+///
+/// Returns whether the schedules are free of errors.
+pub fn run(config: &WaypointConfig) -> bool {
+    let path = &config.schedules_config;
+    println!("Schedules: {}", path.display());
+
+    let schedules = if path.exists() {
+        match SchedulesConfig::load_from_file(path) {
+            Ok(schedules) => schedules,
+            Err(e) => {
+                println!("✗ {e:#}");
+                return false;
+            }
+        }
+    } else {
+        println!("! File not found, the scheduler uses the default schedules");
+        SchedulesConfig::default()
+    };
+
+    let mounts = std::fs::read_to_string("/proc/self/mounts").unwrap_or_default();
+    let reports = check_schedules(&schedules, &btrfs_mount_points(&mounts));
+
+    let mut errors = 0;
+    let mut warnings = 0;
+    for report in &reports {
+        println!();
+        println!(
+            "{} ({}, {})",
+            report.prefix,
+            report.schedule_type,
+            if report.enabled {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+
+        if let Some(next_run) = report.next_run {
+            let at = chrono::Duration::from_std(next_run)
+                .map(|delta| (Local::now() + delta).format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default();
+            if report.enabled {
+                println!("  Next run: {at} (in {})", crate::format_duration(next_run));
+            } else {
+                println!("  Next run if enabled: {at}");
+            }
+        }
+        for error in &report.errors {
+            println!("  ✗ {error}");
+        }
+        for warning in &report.warnings {
+            println!("  ! {warning}");
+        }
+
+        // Problems with disabled schedules don't stop anything from running
+        if report.enabled {
+            errors += report.errors.len();
+        }
+        warnings += report.warnings.len();
+    }
+
+    if !reports.iter().any(|report| report.enabled) {
+        println!();
+        println!("! No schedules are enabled");
+        warnings += 1;
+    }
+
+    println!();
+    println!("{errors} error(s), {warnings} warning(s)");
+    errors == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use waypoint_common::Schedule;
+
+    #[test]
+    fn test_check_schedules() {
+        let mounts = "/dev/sda2 / btrfs rw,relatime,subvol=/@ 0 0\n\
+                      proc /proc proc rw 0 0\n\
+                      /dev/sda2 /home btrfs rw,relatime,subvol=/@home 0 0\n";
+        let btrfs_mounts = btrfs_mount_points(mounts);
+        assert_eq!(
+            btrfs_mounts,
+            HashSet::from([PathBuf::from("/"), PathBuf::from("/home")])
+        );
+
+        let mut daily = Schedule::default_daily();
+        daily.subvolumes = vec![PathBuf::from("/")];
+        let mut duplicate = Schedule::default_weekly();
+        duplicate.prefix = daily.prefix.clone();
+        duplicate.subvolumes.clear();
+        let mut invalid = Schedule::default_monthly();
+        invalid.time = Some("25:00".to_string());
+        invalid.subvolumes = vec![PathBuf::from("/proc")];
+
+        let schedules = SchedulesConfig {
+            schedules: vec![daily, duplicate, invalid],
+        };
+        let reports = check_schedules(&schedules, &btrfs_mounts);
+
+        assert!(reports[0].errors[0].contains("more than one schedule"));
+        assert!(reports[0].next_run.is_some());
+        assert!(reports[0].warnings.is_empty());
+
+        assert!(reports[1].errors[0].contains("more than one schedule"));
+        assert!(!reports[1].warnings.is_empty());
+
+        assert_eq!(reports[2].errors.len(), 2);
+        assert!(reports[2].errors[1].contains("not a mounted btrfs subvolume"));
+        assert!(reports[2].next_run.is_none());
+    }
+}
@@ -0,0 +1,180 @@
+//! Friendly history of what Waypoint has done
+//!
+//! Records snapshots created, deleted and restored, backups and automatic
+//! cleanups, so users can check that e.g. the nightly backup ran without
+//! reading system logs. Unlike the helper's audit log this is per user,
+//! written by the GUI from its own operations and from helper signals, and
+//! only keeps the most recent entries.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::path::PathBuf;
+
+use crate::i18n::gettext;
+
+/// Number of entries kept; older ones are dropped
+const MAX_ENTRIES: usize = 500;
+
+/// Kind of recorded operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    SnapshotCreated,
+    SnapshotDeleted,
+    SnapshotRestored,
+    Backup,
+    Cleanup,
+}
+
+impl ActivityKind {
+    /// Human-readable name
+    pub fn label(self) -> String {
+        match self {
+            ActivityKind::SnapshotCreated => gettext("Create snapshot"),
+            ActivityKind::SnapshotDeleted => gettext("Delete snapshot"),
+            ActivityKind::SnapshotRestored => gettext("Restore snapshot"),
+            ActivityKind::Backup => gettext("Back up snapshot"),
+            ActivityKind::Cleanup => gettext("Automatic cleanup"),
+        }
+    }
+}
+
+/// One entry of the activity log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEntry {
+    pub timestamp: DateTime<Utc>,
+    pub kind: ActivityKind,
+    /// Snapshot (or other object) the operation was about
+    pub subject: String,
+    pub success: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+}
+
+/// Activity log stored in `~/.local/share/waypoint/activity.json`
+pub struct ActivityLog {
+    file: PathBuf,
+}
+
+impl ActivityLog {
+    pub fn new() -> Self {
+        let file = match dirs::data_dir() {
+            Some(data_dir) => data_dir.join("waypoint").join("activity.json"),
+            None => PathBuf::from("/tmp/waypoint-activity.json"),
+        };
+        Self { file }
+    }
+
+    /// Load all entries, oldest first
+    ///
+    /// A missing file is an empty log.
+    pub fn load(&self) -> Result<Vec<ActivityEntry>> {
+        if !self.file.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&self.file)
+            .with_context(|| format!("Failed to read {}", self.file.display()))?;
+        serde_json::from_str(&content).context("Failed to parse activity log")
+    }
+
+    /// Append an entry, dropping the oldest ones beyond the limit
+    pub fn append(&self, entry: ActivityEntry) -> Result<()> {
+        let _lock = self.write_lock()?;
+        // Start over rather than stop logging if the file got corrupted
+        let mut entries = self.load().unwrap_or_default();
+        push_trimmed(&mut entries, entry, MAX_ENTRIES);
+        self.save(&entries)
+    }
+
+    /// Remove all entries
+    pub fn clear(&self) -> Result<()> {
+        let _lock = self.write_lock()?;
+        self.save(&[])
+    }
+
+    /// Serialize read-modify-write cycles between threads and Waypoint processes
+    ///
+    /// The log file itself is replaced on every write, so a separate lock file
+    /// is held instead. The lock is released when the returned file is dropped.
+    fn write_lock(&self) -> Result<File> {
+        let lock_path = self.file.with_extension("lock");
+        if let Some(parent) = lock_path.parent() {
+            fs::create_dir_all(parent).context("Failed to create activity log directory")?;
+        }
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)
+            .with_context(|| format!("Failed to open {}", lock_path.display()))?;
+        fs2::FileExt::lock_exclusive(&file).context("Failed to lock activity log")?;
+        Ok(file)
+    }
+
+    fn save(&self, entries: &[ActivityEntry]) -> Result<()> {
+        if let Some(parent) = self.file.parent() {
+            fs::create_dir_all(parent).context("Failed to create activity log directory")?;
+        }
+        let content =
+            serde_json::to_string_pretty(entries).context("Failed to serialize activity log")?;
+        let tmp_path = self.file.with_extension("tmp");
+        fs::write(&tmp_path, content)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, &self.file)
+            .with_context(|| format!("Failed to replace {}", self.file.display()))
+    }
+}
+
+impl Default for ActivityLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Add an entry and keep at most `max` of the newest
+fn push_trimmed(entries: &mut Vec<ActivityEntry>, entry: ActivityEntry, max: usize) {
+    entries.push(entry);
+    if entries.len() > max {
+        let excess = entries.len() - max;
+        entries.drain(..excess);
+    }
+}
+
+/// Record an operation, logging instead of failing if the log can't be written
+pub fn record(kind: ActivityKind, subject: &str, success: bool, details: Option<String>) {
+    let entry = ActivityEntry {
+        timestamp: Utc::now(),
+        kind,
+        subject: subject.to_string(),
+        success,
+        details,
+    };
+    if let Err(e) = ActivityLog::new().append(entry) {
+        log::warn!("Failed to record activity: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(subject: &str) -> ActivityEntry {
+        ActivityEntry {
+            timestamp: Utc::now(),
+            kind: ActivityKind::SnapshotCreated,
+            subject: subject.to_string(),
+            success: true,
+            details: None,
+        }
+    }
+
+    #[test]
+    fn test_push_trimmed_drops_oldest() {
+        let mut entries = vec![entry("a"), entry("b")];
+        push_trimmed(&mut entries, entry("c"), 2);
+        let subjects: Vec<&str> = entries.iter().map(|e| e.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["b", "c"]);
+    }
+}
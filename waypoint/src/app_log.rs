@@ -0,0 +1,175 @@
+//! Application logging with a runtime level and an in-memory tail
+//!
+//! Records go to stderr through env_logger as before, and are also kept in a
+//! ring buffer so the log viewer can show them without a terminal. The level
+//! starts at `Info`, or at what `RUST_LOG` says when set, and can be changed
+//! while running. Changes last until the app is closed.
+
+use log::{LevelFilter, Log, Metadata, Record};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Number of lines kept for the log viewer
+const MAX_LINES: usize = 2000;
+
+/// Levels offered in the UI, least verbose first
+pub const LEVELS: &[LevelFilter] = &[
+    LevelFilter::Error,
+    LevelFilter::Warn,
+    LevelFilter::Info,
+    LevelFilter::Debug,
+    LevelFilter::Trace,
+];
+
+static BUFFER: Mutex<LogBuffer> = Mutex::new(LogBuffer::new());
+
+/// Whether the `RUST_LOG` filter still applies, i.e. the level wasn't changed in the app
+static ENV_FILTER_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Most recent log lines, each numbered so readers can fetch only new ones
+struct LogBuffer {
+    lines: VecDeque<(u64, String)>,
+    next_seq: u64,
+}
+
+impl LogBuffer {
+    const fn new() -> Self {
+        Self {
+            lines: VecDeque::new(),
+            next_seq: 0,
+        }
+    }
+
+    fn push(&mut self, line: String, max: usize) {
+        self.lines.push_back((self.next_seq, line));
+        self.next_seq += 1;
+        while self.lines.len() > max {
+            self.lines.pop_front();
+        }
+    }
+
+    /// Lines numbered `seq` or later, and the number to ask for next time
+    fn since(&self, seq: u64) -> (Vec<String>, u64) {
+        let lines = self
+            .lines
+            .iter()
+            .filter(|(n, _)| *n >= seq)
+            .map(|(_, line)| line.clone())
+            .collect();
+        (lines, self.next_seq)
+    }
+}
+
+/// Forwards records to env_logger and copies them into the buffer
+struct AppLogger {
+    /// Lets everything through; `log::max_level()` decides
+    inner: env_logger::Logger,
+    /// Per-module `RUST_LOG` filter, dropped once the level is changed in the app
+    env_filter: Option<env_logger::Logger>,
+}
+
+impl Log for AppLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+            && (!ENV_FILTER_ACTIVE.load(Ordering::Relaxed)
+                || self
+                    .env_filter
+                    .as_ref()
+                    .is_none_or(|filter| filter.enabled(metadata)))
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        self.inner.log(record);
+
+        let line = format!(
+            "{} {:<5} {}: {}",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        BUFFER
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(line, MAX_LINES);
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Whether `RUST_LOG` set the starting level
+pub fn overridden_by_env() -> bool {
+    std::env::var_os("RUST_LOG").is_some()
+}
+
+/// Install the logger
+///
+/// Starts at `Info` unless `RUST_LOG` is set. Must be called once, before
+/// anything is logged.
+pub fn init() {
+    let inner = env_logger::Builder::new()
+        .filter_level(LevelFilter::Trace)
+        .build();
+    let (env_filter, level) = if overridden_by_env() {
+        let filter = env_logger::Builder::from_env(env_logger::Env::default()).build();
+        let level = filter.filter();
+        (Some(filter), level)
+    } else {
+        (None, LevelFilter::Info)
+    };
+    ENV_FILTER_ACTIVE.store(env_filter.is_some(), Ordering::Relaxed);
+
+    if log::set_boxed_logger(Box::new(AppLogger { inner, env_filter })).is_ok() {
+        log::set_max_level(level);
+    }
+}
+
+/// Current log level
+pub fn level() -> LevelFilter {
+    log::max_level()
+}
+
+/// Change the log level for the rest of the session
+///
+/// Replaces the `RUST_LOG` filter as well, so a level above what it allowed
+/// takes effect.
+pub fn set_level(level: LevelFilter) {
+    ENV_FILTER_ACTIVE.store(false, Ordering::Relaxed);
+    log::set_max_level(level);
+    log::info!("Log level set to {}", level.as_str().to_lowercase());
+}
+
+/// Log lines recorded since `seq`, and the value to pass next time
+///
+/// Pass 0 to get everything still in the buffer.
+pub fn lines_since(seq: u64) -> (Vec<String>, u64) {
+    BUFFER.lock().unwrap_or_else(|e| e.into_inner()).since(seq)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffer_keeps_newest_and_tails() {
+        let mut buffer = LogBuffer::new();
+        for i in 0..5 {
+            buffer.push(format!("line {i}"), 3);
+        }
+
+        let (lines, next) = buffer.since(0);
+        assert_eq!(lines, vec!["line 2", "line 3", "line 4"]);
+        assert_eq!(next, 5);
+
+        buffer.push("line 5".to_string(), 3);
+        let (lines, next) = buffer.since(next);
+        assert_eq!(lines, vec!["line 5"]);
+        assert_eq!(next, 6);
+    }
+}
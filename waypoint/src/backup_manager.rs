@@ -10,8 +10,12 @@ use anyhow::{Context, Result};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-use waypoint_common::{BackupConfig, BackupDestinationConfig, SnapshotInfo, WaypointConfig};
+use waypoint_common::{
+    BackupConfig, BackupDestinationConfig, BackupEligibility, BackupFormat, BackupTrigger,
+    ErrorCode, FullDestinationAction, HelperError, SnapshotInfo, WaypointConfig,
+};
 
+use crate::activity_log::{self, ActivityKind};
 use crate::dbus_client::WaypointHelperClient;
 use crate::signal_listener::BackupProgressEvent;
 
@@ -37,6 +41,8 @@ pub struct BackupManager {
     config_path: PathBuf,
     /// Live progress tracking: (snapshot_id, destination_uuid) -> progress
     progress: Arc<Mutex<HashMap<(String, String), LiveBackupProgress>>>,
+    /// Destinations whose backups were left queued for lack of space -> reason
+    space_shortages: Arc<Mutex<HashMap<String, String>>>,
 }
 
 impl BackupManager {
@@ -52,6 +58,7 @@ impl BackupManager {
             config: Arc::new(Mutex::new(backup_config)),
             config_path,
             progress: Arc::new(Mutex::new(HashMap::new())),
+            space_shortages: Arc::new(Mutex::new(HashMap::new())),
         })
     }
 
@@ -72,6 +79,15 @@ impl BackupManager {
         Ok(())
     }
 
+    /// Replace the whole configuration (e.g. when importing settings) and save it
+    pub fn replace_config(&self, new_config: BackupConfig) -> Result<()> {
+        let mut config = self.config.lock().unwrap();
+        *config = new_config;
+        drop(config);
+        self.save_config()?;
+        Ok(())
+    }
+
     /// Add or update a destination configuration
     pub fn add_destination(
         &self,
@@ -105,23 +121,32 @@ impl BackupManager {
 
     /// Queue a snapshot for backup to all enabled destinations
     ///
-    /// Called when a new snapshot is created or when manually requested
+    /// Called when a new snapshot is created or marked as favorite. Only destinations
+    /// that have `trigger` enabled are considered.
     ///
     /// # Arguments
     /// * `snapshot` - The snapshot to potentially backup
-    /// * `is_favorite` - Whether this snapshot is marked as a favorite
+    /// * `eligibility` - Favorite, exclusion and schedule rules for this snapshot
     /// * `all_snapshots` - All snapshots (needed for filters like LastN)
+    /// * `trigger` - The event causing the backup
     ///
     /// # Returns
     /// * List of destination UUIDs that were queued
     pub fn queue_snapshot_backup(
         &self,
         snapshot: &SnapshotInfo,
-        is_favorite: bool,
+        eligibility: BackupEligibility,
         all_snapshots: &[SnapshotInfo],
+        trigger: BackupTrigger,
     ) -> Result<Vec<String>> {
         log::info!("queue_snapshot_backup called for snapshot: {}", snapshot.name);
-        log::debug!("  is_favorite: {}, total_snapshots: {}", is_favorite, all_snapshots.len());
+        log::debug!("  eligibility: {:?}, total_snapshots: {}", eligibility, all_snapshots.len());
+
+        if !eligibility.is_eligible() {
+            log::info!("Snapshot {} is not eligible for automatic backup, skipping", snapshot.name);
+            return Ok(Vec::new());
+        }
+        let is_favorite = eligibility.is_favorite;
 
         // Collect destinations to backup to (need to avoid borrowing issues)
         let destinations_to_backup: Vec<String> = {
@@ -133,7 +158,10 @@ impl BackupManager {
                 .enabled_destinations()
                 .filter_map(|(uuid, dest_config)| {
                     log::debug!("  Checking destination: {} ({})", dest_config.label, uuid);
-                    log::debug!("    on_snapshot_creation: {}", dest_config.on_snapshot_creation);
+                    if !dest_config.triggers_on(trigger) {
+                        log::debug!("    {trigger:?} trigger disabled, skipping");
+                        return None;
+                    }
 
                     // Check if this destination wants this snapshot using the new filter matching logic
                     let should_backup = dest_config.filter.matches(snapshot, is_favorite, all_snapshots);
@@ -177,13 +205,15 @@ impl BackupManager {
 
     /// Queue snapshots for a specific destination based on its filter
     ///
-    /// Called when a drive is mounted with "backup on mount" enabled.
-    /// Evaluates the destination's filter against all snapshots and queues those
-    /// that should be backed up but aren't already.
+    /// Called when a drive is mounted with "backup on mount" enabled, or when a
+    /// scheduled backup is due. Evaluates the destination's filter against all
+    /// snapshots and queues those that should be backed up but aren't already.
     ///
     /// # Arguments
     /// * `destination_uuid` - UUID of the destination
     /// * `all_snapshots` - All available snapshots
+    /// * `eligibility` - Backup eligibility rules for a snapshot, by id and name
+    /// * `trigger` - The event causing the backup
     ///
     /// # Returns
     /// * Number of snapshots queued
@@ -191,14 +221,16 @@ impl BackupManager {
         &self,
         destination_uuid: &str,
         all_snapshots: &[crate::snapshot::Snapshot],
+        eligibility: impl Fn(&str, &str) -> BackupEligibility,
+        trigger: BackupTrigger,
     ) -> Result<usize> {
         log::info!("Evaluating snapshots for destination {}", destination_uuid);
 
-        // Get destination config and check if on_drive_mount is enabled
-        let (dest_config, on_drive_mount) = {
+        // Get destination config and check if the trigger is enabled
+        let (dest_config, triggered) = {
             let config = self.config.lock().unwrap();
             match config.get_destination(destination_uuid) {
-                Some(dest) => (dest.clone(), dest.on_drive_mount),
+                Some(dest) => (dest.clone(), dest.triggers_on(trigger)),
                 None => {
                     log::warn!("Destination {} not found", destination_uuid);
                     return Ok(0);
@@ -206,13 +238,13 @@ impl BackupManager {
             }
         };
 
-        if !on_drive_mount {
-            log::debug!("Destination {} does not have on_drive_mount enabled", destination_uuid);
+        if !triggered {
+            log::debug!("Destination {} does not have the {:?} trigger enabled", destination_uuid, trigger);
             return Ok(0);
         }
 
-        log::info!("Destination '{}' has backup_on_mount enabled, evaluating {} snapshots",
-                   dest_config.label, all_snapshots.len());
+        log::info!("Destination '{}' has the {:?} trigger enabled, evaluating {} snapshots",
+                   dest_config.label, trigger, all_snapshots.len());
 
         // Convert snapshots to SnapshotInfo
         let snapshot_infos: Vec<waypoint_common::SnapshotInfo> =
@@ -221,13 +253,19 @@ impl BackupManager {
         let mut queued_count = 0;
 
         // Check each snapshot against the filter
-        for snapshot in &snapshot_infos {
-            // Check if this snapshot matches the destination's filter
-            // Note: favorites are tracked in user_preferences, not in Snapshot struct
-            // For now, we pass false for is_favorite (filters like LastN don't need it)
-            let is_favorite = false;
+        for (source, snapshot) in all_snapshots.iter().zip(&snapshot_infos) {
+            let snapshot_eligibility = eligibility(&source.id, &snapshot.name);
+            if !snapshot_eligibility.is_eligible() {
+                log::debug!("Snapshot {} is not eligible for automatic backup", snapshot.name);
+                continue;
+            }
 
-            let matches_filter = dest_config.filter.matches(snapshot, is_favorite, &snapshot_infos);
+            // Check if this snapshot matches the destination's filter
+            let matches_filter = dest_config.filter.matches(
+                snapshot,
+                snapshot_eligibility.is_favorite,
+                &snapshot_infos,
+            );
 
             if !matches_filter {
                 continue;
@@ -272,13 +310,42 @@ impl BackupManager {
         destination_mount: &str,
         snapshot_dir: &str,
     ) -> Result<(usize, usize, Vec<String>)> {
+        let when_full = {
+            let config = self.config.lock().unwrap();
+            config
+                .get_destination(destination_uuid)
+                .map(|d| d.when_full)
+                .unwrap_or_default()
+        };
+        self.process_pending_backups_with(
+            destination_uuid,
+            destination_mount,
+            snapshot_dir,
+            when_full,
+        )
+    }
+
+    /// Process pending backups, handling a full destination as `when_full` says
+    ///
+    /// Unless old backups are pruned to make room, the first backup that doesn't
+    /// fit leaves it and the rest queued; `take_space_shortage` tells why.
+    pub fn process_pending_backups_with(
+        &self,
+        destination_uuid: &str,
+        destination_mount: &str,
+        snapshot_dir: &str,
+        when_full: FullDestinationAction,
+    ) -> Result<(usize, usize, Vec<String>)> {
+        self.take_space_shortage(destination_uuid);
         let client = WaypointHelperClient::new().context("Failed to connect to waypoint-helper")?;
 
-        // Collect pending snapshot IDs (need to clone to avoid borrowing issues)
+        // Collect pending snapshot IDs (need to clone to avoid borrowing issues).
+        // Backups still waiting out their retry backoff are picked up on a later run.
+        let now = chrono::Utc::now().timestamp();
         let pending_snapshot_ids: Vec<String> = {
             let config = self.config.lock().unwrap();
             config
-                .pending_for_destination(destination_uuid)
+                .due_for_destination(destination_uuid, now)
                 .iter()
                 .map(|pb| pb.snapshot_id.clone())
                 .collect()
@@ -320,6 +387,23 @@ impl BackupManager {
             destination_uuid
         );
 
+        // Unset means verify removable drives only, which takes a scan to find out
+        let verify_setting = {
+            let config = self.config.lock().unwrap();
+            config
+                .get_destination(destination_uuid)
+                .and_then(|d| d.verify_after_backup)
+        };
+        let verify_after_backup = verify_setting
+            .unwrap_or_else(|| Self::is_removable_destination(&client, destination_uuid));
+        let format = {
+            let config = self.config.lock().unwrap();
+            config
+                .get_destination(destination_uuid)
+                .map(|d| d.format)
+                .unwrap_or_default()
+        };
+
         let mut success_count = 0;
         let mut fail_count = 0;
         let mut errors = Vec::new();
@@ -329,8 +413,10 @@ impl BackupManager {
             // Build snapshot path
             let snapshot_path = PathBuf::from(snapshot_dir).join(&snapshot_id);
 
-            // Determine parent for incremental backup
-            let parent_snapshot = {
+            // Determine parent for incremental backup; images are always full backups
+            let parent_snapshot = if format == BackupFormat::Image {
+                None
+            } else {
                 let config = self.config.lock().unwrap();
                 config
                     .get_latest_backup(destination_uuid)
@@ -344,11 +430,46 @@ impl BackupManager {
                 .unwrap_or("")
                 .to_string();
 
-            match client.backup_snapshot(
-                snapshot_path.to_string_lossy().to_string(),
-                destination_mount.to_string(),
-                parent_str,
-            ) {
+            let run_backup = || match format {
+                BackupFormat::Image => client.backup_snapshot_image(
+                    snapshot_path.to_string_lossy().to_string(),
+                    destination_mount.to_string(),
+                ),
+                BackupFormat::Native => client.backup_snapshot(
+                    snapshot_path.to_string_lossy().to_string(),
+                    destination_mount.to_string(),
+                    parent_str.clone(),
+                ),
+            };
+
+            let mut result = run_backup();
+            while when_full == FullDestinationAction::Prune && Self::is_out_of_space(&result) {
+                if !self.prune_oldest_backup(&client, destination_uuid, destination_mount) {
+                    break;
+                }
+                result = run_backup();
+            }
+
+            // Later backups wouldn't fit either, so they all wait for the user
+            if let Ok((false, error_msg, _)) = &result
+                && Self::is_out_of_space(&result)
+            {
+                let reason = HelperError::decode(error_msg).message;
+                log::warn!("Leaving backups to {destination_mount} queued: {reason}");
+                activity_log::record(
+                    ActivityKind::Backup,
+                    &snapshot_id,
+                    false,
+                    Some(format!("Backup to {destination_mount} skipped: {reason}")),
+                );
+                self.space_shortages
+                    .lock()
+                    .unwrap()
+                    .insert(destination_uuid.to_string(), reason);
+                break;
+            }
+
+            match result {
                 Ok((true, backup_path, size_bytes)) => {
                     // Mark as completed
                     let mut config = self.config.lock().unwrap();
@@ -364,10 +485,60 @@ impl BackupManager {
                                 .map(|s| s.to_string())
                         }),
                     );
+                    drop(config);
                     success_count += 1;
+                    activity_log::record(
+                        ActivityKind::Backup,
+                        &snapshot_id,
+                        true,
+                        Some(format!("Backed up to {destination_mount}")),
+                    );
+
+                    if verify_after_backup {
+                        let verification = Self::verify_completed_backup(
+                            &client,
+                            &snapshot_path,
+                            destination_mount,
+                            &snapshot_id,
+                        );
+                        if let Err(reason) = &verification {
+                            log::warn!("Backup of {snapshot_id} to {destination_mount} failed verification: {reason}");
+                            activity_log::record(
+                                ActivityKind::Backup,
+                                &snapshot_id,
+                                false,
+                                Some(format!("Backup to {destination_mount} failed verification: {reason}")),
+                            );
+                            errors.push(format!("{snapshot_id}: verification failed: {reason}"));
+                        }
+                        self.config.lock().unwrap().set_verified(
+                            &snapshot_id,
+                            destination_uuid,
+                            verification.is_ok(),
+                        );
+                    }
+                }
+                Ok((false, error_msg, _))
+                    if HelperError::decode(&error_msg).code == ErrorCode::NotFound
+                        && !timestamp_map.contains_key(&snapshot_id) =>
+                {
+                    // The snapshot was deleted; the helper removed any interrupted
+                    // transfer of it, and retrying can't succeed
+                    log::info!("Dropping queued backup of deleted snapshot {snapshot_id}");
+                    let mut config = self.config.lock().unwrap();
+                    config.pending_backups.retain(|pb| {
+                        !(pb.snapshot_id == snapshot_id && pb.destination_uuid == destination_uuid)
+                    });
                 }
                 Ok((false, error_msg, _)) => {
                     // D-Bus call succeeded but backup failed
+                    let error_msg = waypoint_common::HelperError::decode(&error_msg).message;
+                    activity_log::record(
+                        ActivityKind::Backup,
+                        &snapshot_id,
+                        false,
+                        Some(format!("Backup to {destination_mount} failed: {error_msg}")),
+                    );
                     let mut config = self.config.lock().unwrap();
                     config.mark_failed(&snapshot_id, destination_uuid, error_msg.clone());
                     fail_count += 1;
@@ -376,6 +547,12 @@ impl BackupManager {
                 Err(e) => {
                     // D-Bus call failed
                     let error = e.to_string();
+                    activity_log::record(
+                        ActivityKind::Backup,
+                        &snapshot_id,
+                        false,
+                        Some(format!("Backup to {destination_mount} failed: {error}")),
+                    );
                     let mut config = self.config.lock().unwrap();
                     config.mark_failed(&snapshot_id, destination_uuid, error.clone());
                     fail_count += 1;
@@ -439,6 +616,96 @@ impl BackupManager {
         Ok((success_count, fail_count, errors))
     }
 
+    /// Whether a backup failed because the destination is too full
+    fn is_out_of_space(result: &Result<(bool, String, u64)>) -> bool {
+        matches!(result, Ok((false, error, _))
+            if HelperError::decode(error).code == ErrorCode::InsufficientSpace)
+    }
+
+    /// Delete the oldest backup the destination's retention allows, to make room
+    ///
+    /// Records of backups that were already removed by hand are dropped and the
+    /// next candidate is tried. Returns false if there is none or it couldn't be
+    /// deleted.
+    fn prune_oldest_backup(
+        &self,
+        client: &WaypointHelperClient,
+        destination_uuid: &str,
+        destination_mount: &str,
+    ) -> bool {
+        let now = chrono::Utc::now().timestamp();
+        loop {
+            let Some(record) = self
+                .config
+                .lock()
+                .unwrap()
+                .prune_candidate(destination_uuid, now)
+                .cloned()
+            else {
+                log::info!("No backup on {destination_mount} may be deleted to make room");
+                return false;
+            };
+
+            // The drive may be mounted somewhere else than when the backup was made
+            let Some(file_name) = std::path::Path::new(&record.backup_path).file_name() else {
+                return false;
+            };
+            let backup_path = PathBuf::from(destination_mount)
+                .join("waypoint-backups")
+                .join(file_name);
+
+            match client.delete_backup(backup_path.to_string_lossy().to_string()) {
+                Ok((true, _)) => {}
+                Ok((false, error)) => {
+                    let error = HelperError::decode(&error);
+                    if error.code == ErrorCode::NotFound {
+                        // Removing the stale record lets the next candidate be tried
+                        log::warn!(
+                            "Backup {} is already gone, dropping its record",
+                            backup_path.display()
+                        );
+                        self.config
+                            .lock()
+                            .unwrap()
+                            .remove_backup_record(&record.backup_path);
+                        continue;
+                    }
+                    log::error!(
+                        "Failed to delete {}: {}",
+                        backup_path.display(),
+                        error.message
+                    );
+                    return false;
+                }
+                Err(e) => {
+                    log::error!("Failed to delete {}: {e}", backup_path.display());
+                    return false;
+                }
+            }
+
+            log::info!("Deleted backup {} to make room", backup_path.display());
+            self.config
+                .lock()
+                .unwrap()
+                .remove_backup_record(&record.backup_path);
+            activity_log::record(
+                ActivityKind::Cleanup,
+                &record.snapshot_id,
+                true,
+                Some(format!("Deleted from {destination_mount} to make room")),
+            );
+            return true;
+        }
+    }
+
+    /// Why backups to a destination were left queued by the last run, if they were
+    ///
+    /// Reported once; the next call returns None.
+    pub fn take_space_shortage(&self, destination_uuid: &str) -> Option<String> {
+        let mut shortages = self.space_shortages.lock().unwrap();
+        shortages.remove(destination_uuid)
+    }
+
     /// Retry failed backups for a destination
     pub fn retry_failed_backups(&self, destination_uuid: &str) -> Result<()> {
         let mut config = self.config.lock().unwrap();
@@ -464,37 +731,94 @@ impl BackupManager {
         Ok(())
     }
 
-    /// Get count of pending backups for a destination
+    /// Remove queued (not yet running) backups of a snapshot from every destination
+    pub fn remove_pending_for_snapshot(&self, snapshot_id: &str) -> Result<()> {
+        {
+            let mut config = self.config.lock().unwrap();
+            config.pending_backups.retain(|pb| {
+                pb.snapshot_id != snapshot_id
+                    || pb.status == waypoint_common::BackupStatus::InProgress
+            });
+        }
+        self.save_config()
+    }
+
+    /// Get count of pending backups for a destination that are ready to run
     pub fn get_pending_count(&self, destination_uuid: &str) -> usize {
         let config = self.config.lock().unwrap();
-        config.pending_for_destination(destination_uuid).len()
+        config
+            .due_for_destination(destination_uuid, chrono::Utc::now().timestamp())
+            .len()
     }
 
-    /// Check if a snapshot is backed up to any destination
+    /// Check if a snapshot has a backup on any configured destination
     pub fn is_snapshot_backed_up(&self, snapshot_id: &str) -> bool {
         let config = self.config.lock().unwrap();
-        config
-            .enabled_destinations()
-            .any(|(uuid, _)| config.is_backed_up(snapshot_id, uuid))
+        config.is_snapshot_backed_up(snapshot_id)
     }
 
-    /// Get list of destinations where a snapshot is backed up
+    /// Get list of destinations where a snapshot is backed up, each listed once
+    ///
+    /// Destinations whose backups all failed verification are left out.
     pub fn get_snapshot_backup_destinations(&self, snapshot_id: &str) -> Vec<String> {
         let config = self.config.lock().unwrap();
         config
-            .get_snapshot_backups(snapshot_id)
-            .iter()
-            .map(|record| record.destination_uuid.clone())
+            .snapshot_backups_by_destination(snapshot_id)
+            .into_iter()
+            .filter(|(_, records)| records.iter().any(|r| r.verified != Some(false)))
+            .map(|(uuid, _)| uuid.to_string())
             .collect()
     }
 
+    /// Label of a destination, or its UUID if it isn't configured
+    pub fn destination_label(&self, destination_uuid: &str) -> String {
+        let config = self.config.lock().unwrap();
+        config
+            .get_destination(destination_uuid)
+            .map(|dest| dest.label.clone())
+            .unwrap_or_else(|| destination_uuid.to_string())
+    }
+
+    /// Get labels of the destinations where a snapshot is backed up
+    pub fn get_snapshot_backup_labels(&self, snapshot_id: &str) -> Vec<String> {
+        let config = self.config.lock().unwrap();
+        config
+            .snapshot_backups_by_destination(snapshot_id)
+            .into_keys()
+            .map(|uuid| {
+                config
+                    .get_destination(uuid)
+                    .map(|dest| dest.label.clone())
+                    .unwrap_or_else(|| uuid.to_string())
+            })
+            .collect()
+    }
+
+    /// Snapshots whose backup to a destination failed verification since `since` (Unix timestamp)
+    pub fn failed_verifications(&self, destination_uuid: &str, since: i64) -> Vec<String> {
+        let config = self.config.lock().unwrap();
+        config.failed_verifications(destination_uuid, since)
+    }
+
+    /// Forget a backup that was deleted from its destination
+    pub fn remove_backup_record(&self, backup_path: &str) -> Result<()> {
+        let removed = {
+            let mut config = self.config.lock().unwrap();
+            config.remove_backup_record(backup_path)
+        };
+        if removed > 0 {
+            self.save_config()?;
+        }
+        Ok(())
+    }
+
     /// Update progress for a backup
     pub fn update_progress(&self, event: BackupProgressEvent) {
         let mut progress = self.progress.lock().unwrap();
         let key = (event.snapshot_id.clone(), event.destination_uuid.clone());
 
-        // Remove completed backups from progress tracking
-        if event.stage == "complete" {
+        // Remove finished backups from progress tracking
+        if event.stage == "complete" || event.stage == "failed" {
             progress.remove(&key);
         } else {
             progress.insert(key, LiveBackupProgress {
@@ -544,6 +868,88 @@ impl BackupManager {
         None
     }
 
+    /// Check whether the helper reports a destination as a removable drive
+    fn is_removable_destination(client: &WaypointHelperClient, destination_uuid: &str) -> bool {
+        let Ok((true, result)) = client.scan_backup_destinations() else {
+            return false;
+        };
+        let destinations: Vec<serde_json::Value> =
+            serde_json::from_str(&result).unwrap_or_default();
+
+        destinations.iter().any(|dest| {
+            dest.get("uuid").and_then(|v| v.as_str()) == Some(destination_uuid)
+                && dest.get("drive_type").and_then(|v| v.as_str()) == Some("Removable")
+        })
+    }
+
+    /// Run the content-level verification on a backup that just completed
+    ///
+    /// Returns whether it passed, or the reason it didn't.
+    pub fn verify_completed_backup(
+        client: &WaypointHelperClient,
+        snapshot_path: &std::path::Path,
+        destination_mount: &str,
+        snapshot_id: &str,
+    ) -> std::result::Result<(), String> {
+        match client.verify_backup(
+            snapshot_path.to_string_lossy().to_string(),
+            destination_mount.to_string(),
+            snapshot_id.to_string(),
+        ) {
+            Ok((true, json)) => {
+                match serde_json::from_str::<crate::dbus_client::BackupVerificationResult>(&json) {
+                    Ok(result) if result.success => Ok(()),
+                    Ok(result) => Err(result.message),
+                    Err(e) => Err(format!("Failed to parse verification result: {e}")),
+                }
+            }
+            Ok((false, error)) => Err(waypoint_common::HelperError::decode(&error).message),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Destinations whose scheduled backup is due and whose drive is mounted
+    ///
+    /// # Returns
+    /// * (destination UUID, mount point) pairs
+    pub fn due_scheduled_destinations(&self) -> Vec<(String, String)> {
+        let now = chrono::Utc::now().timestamp();
+        let due: Vec<String> = {
+            let config = self.config.lock().unwrap();
+            config
+                .enabled_destinations()
+                .filter(|(uuid, _)| config.is_scheduled_backup_due(uuid, now))
+                .map(|(uuid, _)| uuid.clone())
+                .collect()
+        };
+
+        // Only look for mounted drives when something is due; scanning calls the helper
+        due.into_iter()
+            .filter_map(|uuid| {
+                let mount_point = self.get_mounted_destination(&uuid)?;
+                Some((uuid, mount_point))
+            })
+            .collect()
+    }
+
+    /// Mounted destinations with failed backups whose retry backoff has elapsed
+    pub fn due_retry_destinations(&self) -> Vec<String> {
+        let now = chrono::Utc::now().timestamp();
+        let mut uuids: Vec<String> = {
+            let config = self.config.lock().unwrap();
+            config
+                .pending_backups
+                .iter()
+                .filter(|pb| pb.is_retrying() && pb.is_due(now))
+                .map(|pb| pb.destination_uuid.clone())
+                .collect()
+        };
+        uuids.sort();
+        uuids.dedup();
+        uuids.retain(|uuid| self.get_mounted_destination(uuid).is_some());
+        uuids
+    }
+
     /// Get backup status summary for display in footer
     pub fn get_backup_status_summary(&self) -> BackupStatusSummary {
         let config = self.config.lock().unwrap();
@@ -604,13 +1010,20 @@ impl BackupManager {
         }
 
         if pending_count > 0 {
+            let retrying_count = config.pending_backups.iter()
+                .filter(|pb| pb.is_retrying())
+                .count();
+            let mut message = if pending_count == 1 {
+                "1 backup pending".to_string()
+            } else {
+                format!("{} backups pending", pending_count)
+            };
+            if retrying_count > 0 {
+                message.push_str(&format!(" • {} retrying", retrying_count));
+            }
             return BackupStatusSummary {
                 status_type: BackupStatusType::Pending,
-                message: if pending_count == 1 {
-                    "1 backup pending".to_string()
-                } else {
-                    format!("{} backups pending", pending_count)
-                },
+                message,
                 clickable: true,
             };
         }
@@ -3,6 +3,8 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::OnceLock;
 use std::time::Duration;
+use waypoint_common::FilesystemStats;
+use waypoint_common::capabilities::FEATURE_FILESYSTEM_STATS;
 
 use crate::cache::TtlCache;
 use crate::performance;
@@ -10,8 +12,8 @@ use crate::performance;
 /// Global cache for snapshot sizes (5-minute TTL)
 static SIZE_CACHE: OnceLock<TtlCache<PathBuf, u64>> = OnceLock::new();
 
-/// Global cache for available disk space (30-second TTL)
-static SPACE_CACHE: OnceLock<TtlCache<PathBuf, u64>> = OnceLock::new();
+/// Global cache for filesystem space (30-second TTL)
+static SPACE_CACHE: OnceLock<TtlCache<PathBuf, FilesystemStats>> = OnceLock::new();
 
 /// Initialize caches (call once at startup)
 pub fn init_cache() {
@@ -25,7 +27,7 @@ fn size_cache() -> &'static TtlCache<PathBuf, u64> {
 }
 
 /// Get the space cache
-fn space_cache() -> &'static TtlCache<PathBuf, u64> {
+fn space_cache() -> &'static TtlCache<PathBuf, FilesystemStats> {
     SPACE_CACHE.get_or_init(|| TtlCache::new(Duration::from_secs(30)))
 }
 
@@ -47,50 +49,158 @@ pub fn is_btrfs(path: &Path) -> Result<bool> {
     Ok(fs_type.trim() == "btrfs")
 }
 
-/// Get available disk space for a path
+/// Check if the filesystem containing a path is mounted read-only
 ///
-/// This function uses a cache with a 30-second TTL to avoid repeatedly
-/// querying the filesystem for the same path.
-pub fn get_available_space(path: &Path) -> Result<u64> {
-    let _timer = performance::tracker().start("get_available_space");
+/// Btrfs remounts itself read-only after detecting errors, which makes every
+/// snapshot operation fail. Can be checked without root.
+pub fn is_mounted_read_only(path: &Path) -> Result<bool> {
+    let output = Command::new("findmnt")
+        .args(["-n", "-o", "OPTIONS", "--target"])
+        .arg(path)
+        .output()
+        .context("Failed to execute findmnt command")?;
+
+    if !output.status.success() {
+        bail!("findmnt command failed");
+    }
+
+    let options = String::from_utf8_lossy(&output.stdout);
+    Ok(options.trim().split(',').any(|option| option == "ro"))
+}
+
+/// Where a snapshot lives on disk
+///
+/// Every snapshot path in the GUI comes from here or `locate_snapshot`, so
+/// metadata, size calculation and browsing agree. The snapshot directory is
+/// the configured one, or the snapshots subvolume below the top-level mount
+/// when only that exists (`WAYPOINT_BTRFS_ROOT_MOUNT` and
+/// `WAYPOINT_SNAPSHOTS_SUBVOLUME` choose the fallback). With further
+/// snapshot directories (`WAYPOINT_EXTRA_SNAPSHOT_DIRS`), it's the one
+/// holding the snapshot.
+pub fn snapshot_path(name: &str) -> PathBuf {
+    waypoint_common::WaypointConfig::new()
+        .locate_snapshot_dir(name)
+        .join(name)
+}
+
+/// Resolve the path recorded in a snapshot's metadata
+///
+/// The helper records the path it saw when creating the snapshot, which
+/// differs from what the GUI sees if one of them could only reach the
+/// snapshots through the fallback. A recorded path that doesn't exist is
+/// replaced with `snapshot_path`; one in a root-only directory is kept.
+pub fn locate_snapshot(recorded: &Path, name: &str) -> PathBuf {
+    if waypoint_common::permissions::exists_or_hidden(recorded) {
+        recorded.to_path_buf()
+    } else {
+        snapshot_path(name)
+    }
+}
+
+/// Directories snapshots may be opened from: the snapshot directories and
+/// the fallback below the top-level mount
+pub fn snapshot_dirs() -> Vec<PathBuf> {
+    let config = waypoint_common::WaypointConfig::new();
+    let mut dirs = config.snapshot_dirs();
+    let fallback = config.fallback_snapshot_dir();
+    if !dirs.contains(&fallback) {
+        dirs.push(fallback);
+    }
+    dirs
+}
+
+/// Snapshot directory holding a snapshot, shown when there is more than one
+///
+/// Returns `None` with a single snapshot directory, where it goes without saying.
+pub fn snapshot_location(path: &Path) -> Option<PathBuf> {
+    let config = waypoint_common::WaypointConfig::new();
+    if config.snapshot_dirs().len() < 2 {
+        return None;
+    }
+    path.parent().map(Path::to_path_buf)
+}
+
+/// Path to query when measuring free space for snapshots
+///
+/// Snapshots may live on a dedicated volume rather than on `/`, so space checks use
+/// the configured snapshot directory (or its closest existing parent if it hasn't
+/// been created yet).
+pub fn snapshot_space_path() -> PathBuf {
+    let snapshot_dir = waypoint_common::WaypointConfig::new().snapshot_dir;
+    snapshot_dir
+        .ancestors()
+        .find(|p| p.exists())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("/"))
+}
+
+/// Get free space and, through the helper, btrfs allocation for a path
+///
+/// Falls back to `df` (without allocation details) when the helper is too old
+/// or can't be reached. Results are cached for 30 seconds per path.
+pub fn get_filesystem_stats(path: &Path) -> Result<FilesystemStats> {
+    let _timer = performance::tracker().start("get_filesystem_stats");
     let path_buf = path.to_path_buf();
 
     // Check cache first
-    if let Some(cached_space) = space_cache().get(&path_buf) {
-        let _cache_timer = performance::tracker().start("get_available_space_cache_hit");
-        return Ok(cached_space);
+    if let Some(cached_stats) = space_cache().get(&path_buf) {
+        let _cache_timer = performance::tracker().start("get_filesystem_stats_cache_hit");
+        return Ok(cached_stats);
     }
 
-    // Cache miss - query filesystem
+    let from_helper = if crate::dbus_client::helper_supports(FEATURE_FILESYSTEM_STATS) {
+        crate::dbus_client::WaypointHelperClient::new()
+            .and_then(|client| client.get_filesystem_stats(path))
+            .inspect_err(|e| log::debug!("Falling back to df for {}: {e}", path.display()))
+            .ok()
+    } else {
+        None
+    };
+
+    let stats = match from_helper {
+        Some(stats) => stats,
+        None => df_filesystem_stats(path)?,
+    };
+
+    // Store in cache
+    space_cache().insert(path_buf, stats.clone());
+
+    Ok(stats)
+}
+
+/// Free space as reported by `df`
+fn df_filesystem_stats(path: &Path) -> Result<FilesystemStats> {
     let _df_timer = performance::tracker().start("df_command");
     let output = Command::new("df")
         .arg("-B1")
-        .arg("--output=avail")
+        .arg("--output=size,avail")
         .arg(path)
         .output()
         .context("Failed to execute df command")?;
-    drop(_df_timer);
 
     if !output.status.success() {
         bail!("Failed to get available space");
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    let lines: Vec<&str> = stdout.lines().collect();
-
-    if lines.len() < 2 {
-        bail!("Unexpected df output");
-    }
+    let line = stdout.lines().nth(1).context("Unexpected df output")?;
+    let mut columns = line.split_whitespace().map(str::parse::<u64>);
 
-    let space: u64 = lines[1]
-        .trim()
-        .parse()
-        .context("Failed to parse available space")?;
+    let (Some(Ok(total)), Some(Ok(available))) = (columns.next(), columns.next()) else {
+        bail!("Failed to parse available space");
+    };
 
-    // Store in cache
-    space_cache().insert(path_buf, space);
+    Ok(FilesystemStats {
+        total_bytes: total,
+        free_bytes: available,
+        available_bytes: available,
+        btrfs: None,
+    })
+}
 
-    Ok(space)
+/// Get available disk space for a path
+pub fn get_available_space(path: &Path) -> Result<u64> {
+    get_filesystem_stats(path).map(|stats| stats.available_bytes)
 }
 
 /// Get all snapshot sizes efficiently via D-Bus helper
@@ -123,6 +233,7 @@ pub fn get_all_snapshot_sizes(paths: &[std::path::PathBuf]) -> std::collections:
                 for path in paths {
                     if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
                         if let Some(&size) = sizes_by_name.get(name) {
+                            size_cache().insert(path.clone(), size);
                             result.insert(path.clone(), size);
                         }
                     }
@@ -148,6 +259,13 @@ pub fn get_all_snapshot_sizes(paths: &[std::path::PathBuf]) -> std::collections:
         .collect()
 }
 
+/// Get a previously calculated snapshot size without running any commands
+///
+/// Returns `None` if the size was never calculated or the cache entry expired.
+pub fn cached_snapshot_size(path: &Path) -> Option<u64> {
+    size_cache().get(&path.to_path_buf())
+}
+
 /// Get the disk usage of a snapshot or subvolume
 /// Returns size in bytes
 ///
@@ -166,8 +284,8 @@ pub fn get_snapshot_size(path: &Path) -> Result<u64> {
     // Cache miss - run du
     // Use du to get actual disk usage
     // -s for summary, -b for bytes
-    // Note: du may return non-zero exit code due to permission denied errors
-    // on some directories in the snapshot, but it still returns a valid size
+    // A non-zero exit means du couldn't read part of the snapshot (e.g. a
+    // root-only snapshot directory), so the size it prints is too small
     let _du_timer = performance::tracker().start("du_command");
     let output = Command::new("du")
         .arg("-sb")
@@ -179,7 +297,7 @@ pub fn get_snapshot_size(path: &Path) -> Result<u64> {
     let stdout = String::from_utf8_lossy(&output.stdout);
     let parts: Vec<&str> = stdout.split_whitespace().collect();
 
-    if parts.is_empty() {
+    if !output.status.success() || parts.is_empty() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         bail!("Failed to get snapshot size: {stderr}");
     }
@@ -1,11 +1,12 @@
 //! Simple TTL-based cache for expensive filesystem operations
 //!
 //! This module provides a thread-safe cache with time-to-live (TTL) support
-//! to reduce redundant filesystem queries for snapshot sizes and disk space.
+//! to reduce redundant filesystem queries for snapshot sizes and disk space,
+//! and keeps the results of snapshot comparisons.
 
 use std::collections::HashMap;
 use std::hash::Hash;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
 /// A cache entry with a value and expiration time
@@ -123,6 +124,29 @@ where
     }
 }
 
+/// Key of a cached comparison: old and new snapshot name, each with its generation
+pub type ComparisonKey = (String, u64, String, u64);
+
+/// Result of a snapshot comparison as returned by the helper
+#[derive(Debug, Clone)]
+pub struct CachedComparison {
+    /// JSON array of changes
+    pub json: String,
+    /// Total number of changes, which may exceed the changes returned when capped
+    pub total: Option<u64>,
+}
+
+static COMPARISON_CACHE: OnceLock<TtlCache<ComparisonKey, CachedComparison>> = OnceLock::new();
+
+/// Cache of snapshot comparisons
+///
+/// A read-only snapshot keeps its generation, so a comparison stays valid until
+/// one of the snapshots changes, which changes the key. The TTL only bounds how
+/// long results are held in memory.
+pub fn comparison_cache() -> &'static TtlCache<ComparisonKey, CachedComparison> {
+    COMPARISON_CACHE.get_or_init(|| TtlCache::new(Duration::from_secs(3600)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
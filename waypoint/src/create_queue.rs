@@ -0,0 +1,105 @@
+//! One snapshot creation at a time
+//!
+//! The helper rate-limits snapshot creation per user, and creations started
+//! while another is running race it for the same subvolumes. Creations
+//! requested in quick succession therefore wait here and start in order, each
+//! once the previous one has finished. The queue belongs to the GTK main
+//! thread.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+use crate::operations::OperationId;
+
+type Job = Box<dyn FnOnce(Turn)>;
+
+#[derive(Default)]
+struct CreateQueue {
+    /// Whether a creation holds the turn
+    busy: bool,
+    waiting: VecDeque<(OperationId, Job)>,
+}
+
+thread_local! {
+    static QUEUE: RefCell<CreateQueue> = RefCell::default();
+}
+
+/// Held by the running creation; the next one starts when it is dropped
+pub struct Turn {
+    _private: (),
+}
+
+impl Drop for Turn {
+    fn drop(&mut self) {
+        let next = QUEUE.with_borrow_mut(|queue| {
+            let next = queue.waiting.pop_front();
+            queue.busy = next.is_some();
+            next
+        });
+        if let Some((_, job)) = next {
+            job(Turn { _private: () });
+        }
+    }
+}
+
+/// Run `job` now if no creation is running, otherwise after the earlier ones
+///
+/// `operation` identifies the job for `remove`. Returns whether it had to wait.
+pub fn run(operation: OperationId, job: impl FnOnce(Turn) + 'static) -> bool {
+    let queued = QUEUE.with_borrow_mut(|queue| std::mem::replace(&mut queue.busy, true));
+    if queued {
+        QUEUE.with_borrow_mut(|queue| queue.waiting.push_back((operation, Box::new(job))));
+    } else {
+        job(Turn { _private: () });
+    }
+    queued
+}
+
+/// Take a job out of the queue before it starts
+///
+/// Returns false if it already started or was never queued.
+pub fn remove(operation: OperationId) -> bool {
+    QUEUE.with_borrow_mut(|queue| {
+        let before = queue.waiting.len();
+        queue.waiting.retain(|(id, _)| *id != operation);
+        queue.waiting.len() != before
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_jobs_run_one_at_a_time() {
+        let started: Rc<RefCell<Vec<OperationId>>> = Rc::default();
+        let turns: Rc<RefCell<Vec<Turn>>> = Rc::default();
+        let job = |id: OperationId| {
+            let started = started.clone();
+            let turns = turns.clone();
+            move |turn: Turn| {
+                started.borrow_mut().push(id);
+                turns.borrow_mut().push(turn);
+            }
+        };
+
+        assert!(!run(1, job(1)));
+        assert!(run(2, job(2)));
+        assert!(run(3, job(3)));
+        assert_eq!(*started.borrow(), vec![1]);
+
+        // A waiting job can be taken out, a running one can't
+        assert!(remove(2));
+        assert!(!remove(1));
+
+        let first = turns.borrow_mut().pop();
+        drop(first);
+        assert_eq!(*started.borrow(), vec![1, 3]);
+
+        let last = turns.borrow_mut().pop();
+        drop(last);
+        assert!(!run(4, job(4)));
+        assert_eq!(*started.borrow(), vec![1, 3, 4]);
+    }
+}
@@ -22,9 +22,13 @@
 //! ```
 
 use anyhow::{Context, Result};
+use std::sync::OnceLock;
+use waypoint_common::capabilities::*;
 use waypoint_common::*;
 use zbus::blocking::Connection as BlockingConnection;
 
+use crate::cache::{CachedComparison, ComparisonKey, comparison_cache};
+
 /// Result of snapshot integrity verification
 ///
 /// Contains validation status and any errors or warnings found during verification.
@@ -66,6 +70,24 @@ pub struct PackageChange {
     pub change_type: String,
 }
 
+/// Progress update from a running snapshot comparison
+#[derive(Debug, Clone)]
+pub struct CompareProgress {
+    /// File listing entries parsed so far (both snapshots)
+    pub entries_scanned: u64,
+    /// Changes found; on "complete" this is the total, even if the result was capped
+    pub changes_found: u64,
+    /// "scanning", "comparing", "complete", "cancelled" or "failed"
+    pub stage: String,
+}
+
+impl CompareProgress {
+    /// Whether this is the last update of the comparison
+    pub fn is_finished(&self) -> bool {
+        matches!(self.stage.as_str(), "complete" | "cancelled" | "failed")
+    }
+}
+
 /// Drive health statistics for backup destinations
 ///
 /// Contains information about drive capacity, backup count, and backup age.
@@ -133,6 +155,30 @@ pub struct WaypointHelperClient {
     connection: BlockingConnection,
 }
 
+/// Capabilities of the running helper, negotiated by the first successful client
+static HELPER_CAPABILITIES: OnceLock<HelperCapabilities> = OnceLock::new();
+
+/// Whether the helper supports an optional feature
+///
+/// Returns false until the capabilities are known, so gated calls never reach
+/// a helper that lacks them. The GUI negotiates with `negotiate_capabilities`
+/// before building its window.
+pub fn helper_supports(feature: &str) -> bool {
+    HELPER_CAPABILITIES
+        .get()
+        .is_some_and(|caps| caps.supports(feature))
+}
+
+/// Query the helper's capabilities unless they are already known
+///
+/// Blocks on D-Bus, so call it from a background thread. If the helper can't
+/// be reached, optional features stay off and the next client tries again.
+pub fn negotiate_capabilities() {
+    if let Err(e) = WaypointHelperClient::new() {
+        log::warn!("Could not connect to waypoint-helper: {e}");
+    }
+}
+
 impl WaypointHelperClient {
     /// Connect to the waypoint-helper D-Bus service
     ///
@@ -151,8 +197,60 @@ impl WaypointHelperClient {
     /// ```
     pub fn new() -> Result<Self> {
         let connection = BlockingConnection::system().context("Failed to connect to system bus")?;
+        let client = Self { connection };
+
+        if HELPER_CAPABILITIES.get().is_none() {
+            match client.query_capabilities() {
+                Ok(caps) => {
+                    if caps.interface_version < HELPER_INTERFACE_VERSION {
+                        log::warn!(
+                            "waypoint-helper interface version {} is older than {}; some features are unavailable",
+                            caps.interface_version,
+                            HELPER_INTERFACE_VERSION
+                        );
+                    }
+                    let _ = HELPER_CAPABILITIES.set(caps);
+                }
+                // Not cached, so the next client tries again (e.g. helper not yet activated)
+                Err(e) => log::debug!("Could not query helper capabilities: {e}"),
+            }
+        }
 
-        Ok(Self { connection })
+        Ok(client)
+    }
+
+    /// Capabilities of the connected helper
+    ///
+    /// Falls back to the legacy set when the helper predates `GetCapabilities`.
+    pub fn capabilities(&self) -> HelperCapabilities {
+        HELPER_CAPABILITIES
+            .get()
+            .cloned()
+            .unwrap_or_else(HelperCapabilities::legacy)
+    }
+
+    /// Whether the connected helper supports an optional feature
+    pub fn supports(&self, feature: &str) -> bool {
+        self.capabilities().supports(feature)
+    }
+
+    fn query_capabilities(&self) -> Result<HelperCapabilities> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        match proxy.call::<_, _, String>("GetCapabilities", &()) {
+            Ok(json) => serde_json::from_str(&json).context("Failed to parse helper capabilities"),
+            Err(zbus::Error::MethodError(name, _, _))
+                if name.as_str() == "org.freedesktop.DBus.Error.UnknownMethod" =>
+            {
+                Ok(HelperCapabilities::legacy())
+            }
+            Err(e) => Err(e).context("Failed to call GetCapabilities"),
+        }
     }
 
     /// Create a new snapshot of specified subvolumes
@@ -216,6 +314,219 @@ impl WaypointHelperClient {
         Ok(result)
     }
 
+    /// Cancel a snapshot creation started by this user
+    ///
+    /// The pending `create_snapshot` call then fails with a `Cancelled` error.
+    pub fn cancel_create(&self, name: &str) -> Result<()> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("CancelCreate", &(name,))
+            .context("Failed to call CancelCreate")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        Ok(())
+    }
+
+    /// Dismiss the password prompts of this process's pending helper calls
+    ///
+    /// The calls waiting on them then fail with a `Cancelled` error.
+    pub fn cancel_authorization(&self) -> Result<()> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("CancelAuthorization", &())
+            .context("Failed to call CancelAuthorization")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        Ok(())
+    }
+
+    /// Read-only state of snapshots, keyed by name
+    ///
+    /// Snapshots the helper couldn't check are missing from the map.
+    pub fn get_snapshot_read_only(
+        &self,
+        snapshot_names: Vec<String>,
+    ) -> Result<std::collections::HashMap<String, bool>> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let json: String = proxy
+            .call("GetSnapshotReadOnly", &(snapshot_names,))
+            .context("Failed to call GetSnapshotReadOnly")?;
+
+        serde_json::from_str(&json).context("Failed to parse read-only states")
+    }
+
+    /// Get the Btrfs generation of several snapshots
+    ///
+    /// Snapshots the helper couldn't read are left out.
+    pub fn get_snapshot_generations(
+        &self,
+        snapshot_names: Vec<String>,
+    ) -> Result<std::collections::HashMap<String, u64>> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let json: String = proxy
+            .call("GetSnapshotGenerations", &(snapshot_names,))
+            .context("Failed to call GetSnapshotGenerations")?;
+
+        serde_json::from_str(&json).context("Failed to parse snapshot generations")
+    }
+
+    /// Key under which the comparison of two snapshots is cached
+    ///
+    /// `None` when the helper can't report the snapshots' generations, in
+    /// which case the comparison isn't cached.
+    fn comparison_key(
+        &self,
+        old_snapshot_name: &str,
+        new_snapshot_name: &str,
+    ) -> Option<ComparisonKey> {
+        if !helper_supports(FEATURE_SNAPSHOT_GENERATIONS) {
+            return None;
+        }
+        let generations = self
+            .get_snapshot_generations(vec![
+                old_snapshot_name.to_string(),
+                new_snapshot_name.to_string(),
+            ])
+            .map_err(|e| log::debug!("Not caching comparison: {e}"))
+            .ok()?;
+        Some((
+            old_snapshot_name.to_string(),
+            *generations.get(old_snapshot_name)?,
+            new_snapshot_name.to_string(),
+            *generations.get(new_snapshot_name)?,
+        ))
+    }
+
+    /// Make a snapshot read-only or writable
+    pub fn set_snapshot_read_only(&self, name: &str, read_only: bool) -> Result<String> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("SetSnapshotReadOnly", &(name, read_only))
+            .context("Failed to call SetSnapshotReadOnly")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        Ok(result.1)
+    }
+
+    /// Put a snapshot under a schedule's retention, or clear it with an empty group
+    pub fn set_retention_group(&self, name: &str, group: &str) -> Result<String> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("SetRetentionGroup", &(name, group))
+            .context("Failed to call SetRetentionGroup")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        Ok(result.1)
+    }
+
+    /// Set when cleanup deletes a snapshot (RFC 3339), or clear it with an empty string
+    pub fn set_snapshot_expiry(&self, name: &str, expires_at: &str) -> Result<String> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("SetSnapshotExpiry", &(name, expires_at))
+            .context("Failed to call SetSnapshotExpiry")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        Ok(result.1)
+    }
+
+    /// Set why a snapshot was taken (a `SnapshotCategory` wire name)
+    pub fn set_snapshot_category(&self, name: &str, category: &str) -> Result<String> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("SetSnapshotCategory", &(name, category))
+            .context("Failed to call SetSnapshotCategory")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        Ok(result.1)
+    }
+
+    /// Pin or unpin a snapshot, keeping it out of every cleanup
+    pub fn set_snapshot_pinned(&self, name: &str, pinned: bool) -> Result<String> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("SetSnapshotPinned", &(name, pinned))
+            .context("Failed to call SetSnapshotPinned")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        Ok(result.1)
+    }
+
     /// Delete a snapshot permanently
     ///
     /// Removes the specified snapshot and all its btrfs subvolumes. This operation
@@ -332,6 +643,63 @@ impl WaypointHelperClient {
         Ok(snapshots)
     }
 
+    /// List all snapshots without their package lists
+    ///
+    /// Only `package_count` is filled in, which keeps the call fast with
+    /// thousands of packages per snapshot. Falls back to a full listing on
+    /// helpers that can't leave the packages out.
+    pub fn list_snapshot_summaries(&self) -> Result<Vec<SnapshotInfo>> {
+        if !helper_supports(FEATURE_SNAPSHOT_SUMMARIES) {
+            let snapshots = self.list_snapshots()?.into_iter();
+            return Ok(snapshots.map(SnapshotInfo::without_packages).collect());
+        }
+
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let json: String = proxy
+            .call("ListSnapshotSummaries", &())
+            .context("Failed to call ListSnapshotSummaries")?;
+
+        serde_json::from_str(&json).context("Failed to parse snapshot list")
+    }
+
+    /// Package lists of the named snapshots, keyed by name
+    ///
+    /// Loads what `list_snapshot_summaries` leaves out, for views that need the
+    /// packages of a few snapshots. Unknown names are left out. Falls back to a
+    /// full listing on helpers without `GetSnapshotPackages`.
+    pub fn get_snapshot_packages(
+        &self,
+        snapshot_names: Vec<String>,
+    ) -> Result<std::collections::HashMap<String, Vec<crate::packages::Package>>> {
+        if !helper_supports(FEATURE_SNAPSHOT_SUMMARIES) {
+            return Ok(self
+                .list_snapshots()?
+                .into_iter()
+                .filter(|s| snapshot_names.contains(&s.name))
+                .map(|s| (s.name, s.packages.iter().map(Into::into).collect()))
+                .collect());
+        }
+
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let json: String = proxy
+            .call("GetSnapshotPackages", &(snapshot_names,))
+            .context("Failed to call GetSnapshotPackages")?;
+
+        serde_json::from_str(&json).context("Failed to parse snapshot packages")
+    }
+
     /// Get sizes for multiple snapshots via privileged helper
     ///
     /// This method uses the D-Bus helper which runs with privileges,
@@ -364,6 +732,28 @@ impl WaypointHelperClient {
         Ok(sizes)
     }
 
+    /// Get the exclusive qgroup sizes of several snapshots via the helper
+    ///
+    /// Maps snapshot names to the bytes deleting them would free. Snapshots
+    /// whose exclusive size is unknown, e.g. with quotas disabled, are missing.
+    pub fn get_snapshot_exclusive_sizes(
+        &self,
+        snapshot_names: Vec<String>,
+    ) -> Result<std::collections::HashMap<String, u64>> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let json: String = proxy
+            .call("GetSnapshotExclusiveSizes", &(snapshot_names,))
+            .context("Failed to call GetSnapshotExclusiveSizes")?;
+
+        serde_json::from_str(&json).context("Failed to parse exclusive snapshot sizes")
+    }
+
     /// Verify snapshot integrity and consistency
     ///
     /// Checks if a snapshot is valid by verifying:
@@ -436,7 +826,7 @@ impl WaypointHelperClient {
             .context("Failed to call PreviewRestore")?;
 
         if !result.0 {
-            anyhow::bail!(result.1);
+            return Err(HelperError::decode(&result.1).into());
         }
 
         let preview: RestorePreview =
@@ -592,6 +982,8 @@ impl WaypointHelperClient {
     /// * `file_paths` - Paths within the snapshot to restore (e.g., vec!["/etc/fstab".to_string()])
     /// * `target_directory` - Where to restore files. Empty string means original locations
     /// * `overwrite` - Whether to overwrite existing files
+    /// * `verify` - Compare SHA-256 hashes of each restored file and its source (reads both twice)
+    /// * `nested_subvolumes` - What to do with btrfs subvolumes nested inside restored folders
     ///
     /// # Returns
     /// * `Ok((true, msg))` - Files restored successfully
@@ -617,7 +1009,9 @@ impl WaypointHelperClient {
     ///     "backup-2025".to_string(),
     ///     vec!["/etc/fstab".to_string()],
     ///     "".to_string(),  // empty = original location
-    ///     true  // overwrite existing file
+    ///     true,  // overwrite existing file
+    ///     true,  // verify the restored copy
+    ///     waypoint_common::NestedSubvolumeMode::Skip,
     /// )?;
     /// # Ok::<(), anyhow::Error>(())
     /// ```
@@ -627,6 +1021,8 @@ impl WaypointHelperClient {
         file_paths: Vec<String>,
         target_directory: String,
         overwrite: bool,
+        verify: bool,
+        nested_subvolumes: NestedSubvolumeMode,
     ) -> Result<(bool, String)> {
         let proxy = zbus::blocking::Proxy::new(
             &self.connection,
@@ -635,19 +1031,39 @@ impl WaypointHelperClient {
             DBUS_INTERFACE_NAME,
         )?;
 
-        let result: (bool, String) = proxy
-            .call(
+        // Older helpers take no verify or nested subvolume argument
+        let result: (bool, String) = if self.supports(FEATURE_NESTED_SUBVOLUME_RESTORE) {
+            proxy.call(
+                "RestoreFiles",
+                &(
+                    snapshot_name,
+                    file_paths,
+                    target_directory,
+                    overwrite,
+                    verify,
+                    nested_subvolumes.as_str(),
+                ),
+            )
+        } else if self.supports(FEATURE_VERIFIED_FILE_RESTORE) {
+            proxy.call(
+                "RestoreFiles",
+                &(snapshot_name, file_paths, target_directory, overwrite, verify),
+            )
+        } else {
+            proxy.call(
                 "RestoreFiles",
                 &(snapshot_name, file_paths, target_directory, overwrite),
             )
-            .context("Failed to call RestoreFiles")?;
+        }
+        .context("Failed to call RestoreFiles")?;
 
         Ok(result)
     }
 
     /// Compare two snapshots and get list of changed files
     ///
-    /// Returns JSON string containing array of changes
+    /// Returns JSON string containing array of changes. Results are cached by
+    /// the generations of both snapshots, so comparing them again is instant.
     ///
     /// **Limitation**: Due to a 25-second D-Bus timeout in zbus 4.0, this operation
     /// will fail for large snapshots that take longer than 25 seconds to compare.
@@ -661,6 +1077,11 @@ impl WaypointHelperClient {
         old_snapshot_name: String,
         new_snapshot_name: String,
     ) -> Result<String> {
+        let key = self.comparison_key(&old_snapshot_name, &new_snapshot_name);
+        if let Some(cached) = key.as_ref().and_then(|key| comparison_cache().get(key)) {
+            return Ok(cached.json);
+        }
+
         let proxy = zbus::blocking::Proxy::new(
             &self.connection,
             DBUS_SERVICE_NAME,
@@ -673,14 +1094,48 @@ impl WaypointHelperClient {
             .context("Failed to call CompareSnapshots")?;
 
         if !result.0 {
-            anyhow::bail!(result.1);
+            return Err(HelperError::decode(&result.1).into());
         }
 
+        if let Some(key) = key {
+            comparison_cache().insert(
+                key,
+                CachedComparison {
+                    json: result.1.clone(),
+                    total: None,
+                },
+            );
+        }
         Ok(result.1)
     }
 
-    /// Enable btrfs quotas on the snapshot filesystem
-    pub fn enable_quotas(&self, use_simple: bool) -> Result<String> {
+    /// Compare two snapshots, reporting progress while the helper scans them
+    ///
+    /// `on_progress` is called from a listener thread for every `CompareProgress`
+    /// signal of this comparison, ending with a finished stage. A cached result
+    /// is returned right away, after a single "complete" update when its total
+    /// is known.
+    pub fn compare_snapshots_with_progress<F>(
+        &self,
+        old_snapshot_name: String,
+        new_snapshot_name: String,
+        on_progress: F,
+    ) -> Result<String>
+    where
+        F: Fn(CompareProgress) + Send + 'static,
+    {
+        let key = self.comparison_key(&old_snapshot_name, &new_snapshot_name);
+        if let Some(cached) = key.as_ref().and_then(|key| comparison_cache().get(key)) {
+            if let Some(total) = cached.total {
+                on_progress(CompareProgress {
+                    entries_scanned: 0,
+                    changes_found: total,
+                    stage: "complete".to_string(),
+                });
+            }
+            return Ok(cached.json);
+        }
+
         let proxy = zbus::blocking::Proxy::new(
             &self.connection,
             DBUS_SERVICE_NAME,
@@ -688,19 +1143,75 @@ impl WaypointHelperClient {
             DBUS_INTERFACE_NAME,
         )?;
 
-        let result: (bool, String) = proxy
-            .call("EnableQuotas", &(use_simple,))
-            .context("Failed to call EnableQuotas")?;
+        // Subscribe before starting so no update is missed. The stream can be
+        // aborted, as no finished stage may arrive if the call itself fails.
+        let signals = zbus::block_on(proxy.inner().receive_signal("CompareProgress"))
+            .context("Failed to subscribe to CompareProgress")?;
+        let (mut signals, stop_listener) = futures_util::stream::abortable(signals);
+
+        let old_name = old_snapshot_name.clone();
+        let new_name = new_snapshot_name.clone();
+        let total = std::sync::Arc::new(std::sync::Mutex::new(None));
+        let total_for_listener = total.clone();
+        std::thread::spawn(move || {
+            while let Some(msg) = zbus::block_on(futures_util::StreamExt::next(&mut signals)) {
+                let Ok((old, new, entries_scanned, changes_found, stage)) =
+                    msg.body().deserialize::<(String, String, u64, u64, String)>()
+                else {
+                    continue;
+                };
+                if old != old_name || new != new_name {
+                    continue;
+                }
+
+                let progress = CompareProgress {
+                    entries_scanned,
+                    changes_found,
+                    stage,
+                };
+                let finished = progress.is_finished();
+                if progress.stage == "complete"
+                    && let Ok(mut total) = total_for_listener.lock()
+                {
+                    *total = Some(progress.changes_found);
+                }
+                on_progress(progress);
+                if finished {
+                    break;
+                }
+            }
+        });
+
+        let result: (bool, String) = match proxy
+            .call("CompareSnapshots", &(old_snapshot_name, new_snapshot_name))
+            .context("Failed to call CompareSnapshots")
+        {
+            Ok(result) => result,
+            Err(e) => {
+                stop_listener.abort();
+                return Err(e);
+            }
+        };
 
         if !result.0 {
-            anyhow::bail!(result.1);
+            stop_listener.abort();
+            return Err(HelperError::decode(&result.1).into());
         }
 
+        if let Some(key) = key {
+            comparison_cache().insert(
+                key,
+                CachedComparison {
+                    json: result.1.clone(),
+                    total: total.lock().ok().and_then(|total| *total),
+                },
+            );
+        }
         Ok(result.1)
     }
 
-    /// Disable btrfs quotas on the snapshot filesystem
-    pub fn disable_quotas(&self) -> Result<String> {
+    /// Cancel a running comparison of the same two snapshots
+    pub fn cancel_compare(&self, old_snapshot_name: &str, new_snapshot_name: &str) -> Result<()> {
         let proxy = zbus::blocking::Proxy::new(
             &self.connection,
             DBUS_SERVICE_NAME,
@@ -709,18 +1220,58 @@ impl WaypointHelperClient {
         )?;
 
         let result: (bool, String) = proxy
-            .call("DisableQuotas", &())
-            .context("Failed to call DisableQuotas")?;
+            .call("CancelCompare", &(old_snapshot_name, new_snapshot_name))
+            .context("Failed to call CancelCompare")?;
 
         if !result.0 {
-            anyhow::bail!(result.1);
+            return Err(HelperError::decode(&result.1).into());
         }
 
-        Ok(result.1)
+        Ok(())
     }
 
-    /// Get quota usage information
-    pub fn get_quota_usage(&self) -> Result<waypoint_common::QuotaUsage> {
+    /// Enable btrfs quotas on the snapshot filesystem
+    pub fn enable_quotas(&self, use_simple: bool) -> Result<String> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("EnableQuotas", &(use_simple,))
+            .context("Failed to call EnableQuotas")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        Ok(result.1)
+    }
+
+    /// Disable btrfs quotas on the snapshot filesystem
+    pub fn disable_quotas(&self) -> Result<String> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("DisableQuotas", &())
+            .context("Failed to call DisableQuotas")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        Ok(result.1)
+    }
+
+    /// Get quota usage information
+    pub fn get_quota_usage(&self) -> Result<waypoint_common::QuotaUsage> {
         let proxy = zbus::blocking::Proxy::new(
             &self.connection,
             DBUS_SERVICE_NAME,
@@ -733,13 +1284,57 @@ impl WaypointHelperClient {
             .context("Failed to call GetQuotaUsage")?;
 
         if !result.0 {
-            anyhow::bail!(result.1);
+            return Err(HelperError::decode(&result.1).into());
         }
 
         let usage: waypoint_common::QuotaUsage = serde_json::from_str(&result.1)?;
         Ok(usage)
     }
 
+    /// Get free space and btrfs allocation of the filesystem containing `path`
+    pub fn get_filesystem_stats(
+        &self,
+        path: &std::path::Path,
+    ) -> Result<waypoint_common::FilesystemStats> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("GetFilesystemStats", &(path.to_string_lossy().to_string(),))
+            .context("Failed to call GetFilesystemStats")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        serde_json::from_str(&result.1).context("Failed to parse filesystem stats")
+    }
+
+    /// Preview which snapshots quota auto-cleanup would delete right now
+    pub fn preview_quota_cleanup(&self) -> Result<waypoint_common::QuotaCleanupReport> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("PreviewQuotaCleanup", &())
+            .context("Failed to call PreviewQuotaCleanup")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        let report: waypoint_common::QuotaCleanupReport = serde_json::from_str(&result.1)?;
+        Ok(report)
+    }
+
     /// Set quota limit in bytes
     pub fn set_quota_limit(&self, limit_bytes: u64) -> Result<String> {
         let proxy = zbus::blocking::Proxy::new(
@@ -754,12 +1349,77 @@ impl WaypointHelperClient {
             .context("Failed to call SetQuotaLimit")?;
 
         if !result.0 {
-            anyhow::bail!(result.1);
+            return Err(HelperError::decode(&result.1).into());
         }
 
         Ok(result.1)
     }
 
+    /// Get btrfs compression of the snapshot directory and the space it saved
+    pub fn get_snapshot_compression(&self) -> Result<waypoint_common::SnapshotCompression> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("GetSnapshotCompression", &())
+            .context("Failed to call GetSnapshotCompression")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        serde_json::from_str(&result.1).context("Failed to parse snapshot compression")
+    }
+
+    /// Get the summed referenced size of all snapshots and the space only they use
+    pub fn get_snapshot_sharing(&self) -> Result<waypoint_common::SnapshotSharing> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("GetSnapshotSharing", &())
+            .context("Failed to call GetSnapshotSharing")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        serde_json::from_str(&result.1).context("Failed to parse snapshot sharing")
+    }
+
+    /// Set btrfs compression of the snapshot directory
+    ///
+    /// An empty algorithm falls back to the filesystem's mount option.
+    pub fn set_snapshot_compression(
+        &self,
+        algorithm: &str,
+    ) -> Result<waypoint_common::SnapshotCompression> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("SetSnapshotCompression", &(algorithm,))
+            .context("Failed to call SetSnapshotCompression")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        serde_json::from_str(&result.1).context("Failed to parse snapshot compression")
+    }
+
     /// Save quota configuration via D-Bus helper
     pub fn save_quota_config(&self, config_toml: String) -> Result<String> {
         let proxy = zbus::blocking::Proxy::new(
@@ -774,7 +1434,7 @@ impl WaypointHelperClient {
             .context("Failed to call SaveQuotaConfig")?;
 
         if !result.0 {
-            anyhow::bail!(result.1);
+            return Err(HelperError::decode(&result.1).into());
         }
 
         Ok(result.1)
@@ -794,7 +1454,398 @@ impl WaypointHelperClient {
             .context("Failed to call SaveExcludeConfig")?;
 
         if !result.0 {
-            anyhow::bail!(result.1);
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        Ok(result.1)
+    }
+
+    /// Export system configuration as a JSON bundle
+    pub fn export_config(&self) -> Result<String> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("ExportConfig", &())
+            .context("Failed to call ExportConfig")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        Ok(result.1)
+    }
+
+    /// Import system configuration from a JSON bundle
+    pub fn import_config(&self, bundle_json: String) -> Result<String> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("ImportConfig", &(bundle_json,))
+            .context("Failed to call ImportConfig")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        Ok(result.1)
+    }
+
+    /// Get recent audit log events (oldest first)
+    pub fn get_audit_log(
+        &self,
+        max_events: u32,
+    ) -> Result<Vec<waypoint_common::audit::AuditEvent>> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("GetAuditLog", &(max_events,))
+            .context("Failed to call GetAuditLog")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        serde_json::from_str(&result.1).context("Failed to parse audit events")
+    }
+
+    /// Get the most recent commands the helper ran (oldest first)
+    pub fn get_command_transcript(&self, max_records: u32) -> Result<Vec<CommandRecord>> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("GetCommandTranscript", &(max_records,))
+            .context("Failed to call GetCommandTranscript")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        serde_json::from_str(&result.1).context("Failed to parse command transcript")
+    }
+
+    /// Get the owner and permissions of each snapshot directory
+    pub fn get_snapshot_dir_permissions(&self) -> Result<Vec<SnapshotDirPermissions>> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("GetSnapshotDirPermissions", &())
+            .context("Failed to call GetSnapshotDirPermissions")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        serde_json::from_str(&result.1).context("Failed to parse snapshot directory permissions")
+    }
+
+    /// Find metadata entries that contradict each other or their snapshot directory
+    pub fn check_snapshot_metadata(&self) -> Result<Vec<MetadataConflict>> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("CheckSnapshotMetadata", &())
+            .context("Failed to call CheckSnapshotMetadata")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        serde_json::from_str(&result.1).context("Failed to parse metadata conflicts")
+    }
+
+    /// Resolve a conflict returned by `check_snapshot_metadata`
+    pub fn resolve_metadata_conflict(
+        &self,
+        conflict: &MetadataConflict,
+        resolution: ConflictResolution,
+    ) -> Result<String> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let conflict_json = serde_json::to_string(conflict)?;
+        let result: (bool, String) = proxy
+            .call(
+                "ResolveMetadataConflict",
+                &(conflict_json, resolution.as_str()),
+            )
+            .context("Failed to call ResolveMetadataConflict")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        Ok(result.1)
+    }
+
+    /// Make every snapshot directory accessible to root only
+    ///
+    /// Returns the permissions afterwards.
+    pub fn secure_snapshot_dirs(&self) -> Result<Vec<SnapshotDirPermissions>> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("SecureSnapshotDirs", &())
+            .context("Failed to call SecureSnapshotDirs")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        serde_json::from_str(&result.1).context("Failed to parse snapshot directory permissions")
+    }
+
+    /// Restore one subvolume of a snapshot to a new writable subvolume
+    ///
+    /// Returns the path of the created subvolume.
+    pub fn restore_to_subvolume(
+        &self,
+        snapshot_name: &str,
+        subvolume: &str,
+        target_path: &str,
+    ) -> Result<String> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("RestoreToSubvolume", &(snapshot_name, subvolume, target_path))
+            .context("Failed to call RestoreToSubvolume")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        Ok(result.1)
+    }
+
+    /// Mount an ephemeral writable overlay of a snapshot subvolume
+    ///
+    /// Changes made in the overlay are discarded when it is unmounted.
+    pub fn mount_snapshot_overlay(
+        &self,
+        snapshot_name: &str,
+        subvolume: &str,
+    ) -> Result<waypoint_common::OverlayMount> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("MountSnapshotOverlay", &(snapshot_name, subvolume))
+            .context("Failed to call MountSnapshotOverlay")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        serde_json::from_str(&result.1).context("Failed to parse overlay mount")
+    }
+
+    /// Unmount a snapshot overlay, discarding its changes
+    pub fn unmount_snapshot_overlay(&self, mount_point: &str) -> Result<String> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("UnmountSnapshotOverlay", &(mount_point,))
+            .context("Failed to call UnmountSnapshotOverlay")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        Ok(result.1)
+    }
+
+    /// List active snapshot overlays
+    ///
+    /// The helper reaps overlays whose owning process has exited before answering.
+    pub fn list_snapshot_overlays(&self) -> Result<Vec<waypoint_common::OverlayMount>> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let json: String = proxy
+            .call("ListSnapshotOverlays", &())
+            .context("Failed to call ListSnapshotOverlays")?;
+
+        serde_json::from_str(&json).context("Failed to parse overlay list")
+    }
+
+    /// Estimate how much space deduplicating the snapshots could reclaim
+    pub fn estimate_dedup_savings(&self) -> Result<waypoint_common::DedupEstimate> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("EstimateDedupSavings", &())
+            .context("Failed to call EstimateDedupSavings")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        serde_json::from_str(&result.1).context("Failed to parse deduplication estimate")
+    }
+
+    /// Deduplicate all snapshots, returning the helper's message and bytes reclaimed
+    ///
+    /// `on_progress` is called from a listener thread with `(done, total, stage)` for
+    /// every `DedupProgress` signal, ending with "complete" or "failed".
+    pub fn deduplicate_snapshots<F>(&self, on_progress: F) -> Result<(String, u64)>
+    where
+        F: Fn(u64, u64, String) + Send + 'static,
+    {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        // Subscribe before starting so no update is missed
+        let signals = proxy
+            .receive_signal("DedupProgress")
+            .context("Failed to subscribe to DedupProgress")?;
+
+        std::thread::spawn(move || {
+            for msg in signals {
+                let Ok((done, total, stage)) = msg.body().deserialize::<(u64, u64, String)>()
+                else {
+                    continue;
+                };
+                let finished = matches!(stage.as_str(), "complete" | "failed");
+                on_progress(done, total, stage);
+                if finished {
+                    break;
+                }
+            }
+        });
+
+        let result: (bool, String, u64) = proxy
+            .call("DeduplicateSnapshots", &())
+            .context("Failed to call DeduplicateSnapshots")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        Ok((result.1, result.2))
+    }
+
+    /// Start a balance of chunks at most `usage` percent full
+    ///
+    /// Returns once the balance has started; poll `get_balance_status` for progress.
+    pub fn start_balance(&self, usage: u32) -> Result<String> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("StartBalance", &(usage,))
+            .context("Failed to call StartBalance")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        Ok(result.1)
+    }
+
+    /// Get the balance state of the snapshot filesystem
+    pub fn get_balance_status(&self) -> Result<waypoint_common::BalanceStatus> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("GetBalanceStatus", &())
+            .context("Failed to call GetBalanceStatus")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        serde_json::from_str(&result.1).context("Failed to parse balance status")
+    }
+
+    /// Cancel the running balance
+    pub fn cancel_balance(&self) -> Result<String> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call("CancelBalance", &())
+            .context("Failed to call CancelBalance")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
         }
 
         Ok(result.1)
@@ -818,7 +1869,7 @@ impl WaypointHelperClient {
             .context("Failed to call UpdateSnapshotMetadata")?;
 
         if !result.0 {
-            anyhow::bail!(result.1);
+            return Err(HelperError::decode(&result.1).into());
         }
 
         Ok(result.1)
@@ -891,6 +1942,26 @@ impl WaypointHelperClient {
         Ok(result)
     }
 
+    /// Backup a snapshot to a self-contained btrfs image file at the destination
+    pub fn backup_snapshot_image(
+        &self,
+        snapshot_path: String,
+        destination_mount: String,
+    ) -> Result<(bool, String, u64)> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String, u64) = proxy
+            .call("BackupSnapshotImage", &(snapshot_path, destination_mount))
+            .context("Failed to call BackupSnapshotImage")?;
+
+        Ok(result)
+    }
+
     /// List backups at a destination
     pub fn list_backups(&self, destination_mount: String) -> Result<(bool, String)> {
         let proxy = zbus::blocking::Proxy::new(
@@ -996,12 +2067,13 @@ impl WaypointHelperClient {
         Ok(stats)
     }
 
-    /// Restore a snapshot from backup
+    /// Restore a snapshot from backup, under `new_name` instead of the backup's name if given
     #[allow(dead_code)]
     pub fn restore_from_backup(
         &self,
         backup_path: String,
         snapshots_dir: String,
+        new_name: Option<String>,
     ) -> Result<(bool, String)> {
         let proxy = zbus::blocking::Proxy::new(
             &self.connection,
@@ -1011,7 +2083,10 @@ impl WaypointHelperClient {
         )?;
 
         let result: (bool, String) = proxy
-            .call("RestoreFromBackup", &(backup_path, snapshots_dir))
+            .call(
+                "RestoreFromBackup",
+                &(backup_path, snapshots_dir, new_name.unwrap_or_default()),
+            )
             .context("Failed to call RestoreFromBackup")?;
 
         Ok(result)
@@ -1039,4 +2114,32 @@ impl WaypointHelperClient {
 
         Ok(result)
     }
+
+    /// Compare a backup with the snapshot it was made from
+    pub fn compare_backup(
+        &self,
+        snapshot_path: String,
+        destination_mount: String,
+        snapshot_id: String,
+    ) -> Result<BackupComparison> {
+        let proxy = zbus::blocking::Proxy::new(
+            &self.connection,
+            DBUS_SERVICE_NAME,
+            DBUS_OBJECT_PATH,
+            DBUS_INTERFACE_NAME,
+        )?;
+
+        let result: (bool, String) = proxy
+            .call(
+                "CompareBackup",
+                &(snapshot_path, destination_mount, snapshot_id),
+            )
+            .context("Failed to call CompareBackup")?;
+
+        if !result.0 {
+            return Err(HelperError::decode(&result.1).into());
+        }
+
+        serde_json::from_str(&result.1).context("Failed to parse backup comparison")
+    }
 }
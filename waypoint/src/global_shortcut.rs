@@ -0,0 +1,174 @@
+//! System-wide quick snapshot shortcut via the XDG GlobalShortcuts portal
+//!
+//! Applications can't grab keys outside their own windows on Wayland, so the
+//! shortcut is registered with the desktop portal, which lets the user confirm
+//! or change the keys. Desktops without the portal (or an older version of
+//! it) simply don't get the shortcut.
+
+use anyhow::{Context, Result, bail};
+use std::collections::HashMap;
+use std::sync::mpsc;
+use zbus::blocking::{Connection, Proxy};
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+const PORTAL_SERVICE: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const GLOBAL_SHORTCUTS_INTERFACE: &str = "org.freedesktop.portal.GlobalShortcuts";
+const REQUEST_INTERFACE: &str = "org.freedesktop.portal.Request";
+
+/// Identifier of the quick snapshot shortcut within the portal session
+const SHORTCUT_ID: &str = "quick-snapshot";
+
+/// Keys suggested to the portal; the user can pick others in the desktop settings
+const PREFERRED_TRIGGER: &str = "CTRL+ALT+SHIFT+S";
+
+/// Events from the shortcut listener
+#[derive(Debug)]
+pub enum ShortcutEvent {
+    /// The shortcut was pressed
+    Activated,
+    /// The portal is missing or refused the shortcut
+    Unavailable,
+}
+
+/// Register the quick snapshot shortcut and listen for it in the background
+///
+/// The session stays open, and the shortcut registered, until Waypoint exits.
+pub fn start() -> mpsc::Receiver<ShortcutEvent> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        if let Err(e) = listen(&tx) {
+            log::warn!("Global snapshot shortcut unavailable: {e:#}");
+            let _ = tx.send(ShortcutEvent::Unavailable);
+        }
+    });
+    rx
+}
+
+/// Call a portal method that completes through a Request object
+///
+/// The request path is predictable from the handle token, so the Response
+/// signal is subscribed to before the call and can't be missed.
+fn portal_request<B>(
+    connection: &Connection,
+    portal: &Proxy<'_>,
+    method: &str,
+    token: &str,
+    body: &B,
+) -> Result<HashMap<String, OwnedValue>>
+where
+    B: serde::Serialize + zbus::zvariant::DynamicType,
+{
+    let sender = connection
+        .unique_name()
+        .context("No unique D-Bus name")?
+        .trim_start_matches(':')
+        .replace('.', "_");
+    let request_path = format!("{PORTAL_PATH}/request/{sender}/{token}");
+
+    let request = Proxy::new(
+        connection,
+        PORTAL_SERVICE,
+        request_path.as_str(),
+        REQUEST_INTERFACE,
+    )?;
+    let mut responses = request.receive_signal("Response")?;
+
+    let _: OwnedObjectPath = portal
+        .call(method, body)
+        .with_context(|| format!("{method} failed"))?;
+
+    let message = responses
+        .next()
+        .with_context(|| format!("No response to {method}"))?;
+    let (code, results): (u32, HashMap<String, OwnedValue>) = message.body().deserialize()?;
+    match code {
+        0 => Ok(results),
+        1 => bail!("{method} was cancelled"),
+        _ => bail!("{method} was refused by the portal"),
+    }
+}
+
+fn listen(tx: &mpsc::Sender<ShortcutEvent>) -> Result<()> {
+    let connection = Connection::session().context("Failed to connect to the session bus")?;
+    let portal = Proxy::new(
+        &connection,
+        PORTAL_SERVICE,
+        PORTAL_PATH,
+        GLOBAL_SHORTCUTS_INTERFACE,
+    )?;
+
+    let token = format!("waypoint_{}", std::process::id());
+
+    let options: HashMap<&str, Value> = HashMap::from([
+        ("handle_token", Value::from(format!("{token}_session"))),
+        ("session_handle_token", Value::from(token.as_str())),
+    ]);
+    let results = portal_request(
+        &connection,
+        &portal,
+        "CreateSession",
+        &format!("{token}_session"),
+        &(options,),
+    )
+    .context("The desktop portal has no global shortcut support")?;
+
+    // Documented as a string, but some portals send an object path
+    let session_handle = results
+        .get("session_handle")
+        .context("Portal returned no session")?;
+    let session_path = match &**session_handle {
+        Value::Str(path) => OwnedObjectPath::try_from(path.as_str())?,
+        Value::ObjectPath(path) => path.clone().into(),
+        other => bail!("Unexpected session handle {other:?}"),
+    };
+
+    // Subscribe before binding so an early press isn't lost
+    let activations = portal.receive_signal("Activated")?;
+
+    let shortcut_options: HashMap<&str, Value> = HashMap::from([
+        ("description", Value::from("Take a quick snapshot")),
+        ("preferred_trigger", Value::from(PREFERRED_TRIGGER)),
+    ]);
+    let bind_options: HashMap<&str, Value> =
+        HashMap::from([("handle_token", Value::from(format!("{token}_bind")))]);
+    portal_request(
+        &connection,
+        &portal,
+        "BindShortcuts",
+        &format!("{token}_bind"),
+        &(
+            &session_path,
+            vec![(SHORTCUT_ID, shortcut_options)],
+            "",
+            bind_options,
+        ),
+    )
+    .context("Failed to register the shortcut")?;
+
+    log::info!("Registered global quick snapshot shortcut");
+
+    for message in activations {
+        let (session, shortcut_id, _timestamp, _options): (
+            OwnedObjectPath,
+            String,
+            u64,
+            HashMap<String, OwnedValue>,
+        ) = match message.body().deserialize() {
+            Ok(body) => body,
+            Err(e) => {
+                log::debug!("Ignoring malformed Activated signal: {e}");
+                continue;
+            }
+        };
+
+        if session == session_path
+            && shortcut_id == SHORTCUT_ID
+            && tx.send(ShortcutEvent::Activated).is_err()
+        {
+            break;
+        }
+    }
+
+    Ok(())
+}
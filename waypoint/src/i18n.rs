@@ -0,0 +1,98 @@
+//! Translation of user-visible strings
+//!
+//! Strings are looked up with gettext in the `waypoint` text domain, through
+//! gettext-rs and the system's libintl. The language follows the usual
+//! `LANGUAGE`, `LC_ALL`, `LC_MESSAGES` and `LANG` environment variables;
+//! untranslated strings fall back to English.
+//!
+//! Wrap literals in `gettext("...")` (or `ngettext` for plurals) so
+//! `xgettext` can extract them into `po/waypoint.pot`. Values are inserted
+//! with `gettext_f` and named placeholders, since `format!` needs its format
+//! string at compile time.
+
+use gettextrs::LocaleCategory;
+
+/// Text domain of the catalog files (`waypoint.mo`)
+const GETTEXT_PACKAGE: &str = "waypoint";
+
+/// Directory holding `<lang>/LC_MESSAGES/waypoint.mo`, set at build time by packagers
+const LOCALE_DIR: &str = match option_env!("WAYPOINT_LOCALEDIR") {
+    Some(dir) => dir,
+    None => "/usr/share/locale",
+};
+
+/// Select the locale from the environment and bind the text domain
+///
+/// Must run before any widget is built.
+pub fn init() {
+    match gettextrs::setlocale(LocaleCategory::LcAll, "") {
+        Some(locale) => log::debug!("Locale: {}", String::from_utf8_lossy(&locale)),
+        None => log::warn!("Unsupported locale, falling back to English"),
+    }
+
+    let bound = gettextrs::bindtextdomain(GETTEXT_PACKAGE, LOCALE_DIR)
+        .and_then(|_| gettextrs::bind_textdomain_codeset(GETTEXT_PACKAGE, "UTF-8"))
+        .and_then(|_| gettextrs::textdomain(GETTEXT_PACKAGE));
+    if let Err(e) = bound {
+        log::warn!("Failed to bind translations in {LOCALE_DIR}: {e}");
+    }
+}
+
+/// Translate a string
+pub fn gettext(msgid: &str) -> String {
+    gettextrs::dgettext(GETTEXT_PACKAGE, msgid)
+}
+
+/// Mark a string for extraction without translating it
+///
+/// For `const` tables; pass the entry through `gettext` where it is shown.
+pub const fn gettext_noop(msgid: &str) -> &str {
+    msgid
+}
+
+/// Translate a string with singular and plural forms
+pub fn ngettext(msgid: &str, msgid_plural: &str, n: u64) -> String {
+    // Counts beyond u32 use the same plural form as the largest one
+    let n = u32::try_from(n).unwrap_or(u32::MAX);
+    gettextrs::dngettext(GETTEXT_PACKAGE, msgid, msgid_plural, n)
+}
+
+/// Translate a string and fill in `{name}` placeholders
+///
+/// Translators may reorder placeholders, e.g.
+/// `gettext_f("Delete {name}?", &[("name", &snapshot.name)])`.
+pub fn gettext_f(msgid: &str, args: &[(&str, &str)]) -> String {
+    fill_placeholders(gettext(msgid), args)
+}
+
+/// Plural form of `gettext_f`
+pub fn ngettext_f(msgid: &str, msgid_plural: &str, n: u64, args: &[(&str, &str)]) -> String {
+    fill_placeholders(ngettext(msgid, msgid_plural, n), args)
+}
+
+fn fill_placeholders(mut text: String, args: &[(&str, &str)]) -> String {
+    for (key, value) in args {
+        text = text.replace(&format!("{{{key}}}"), value);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_placeholders() {
+        assert_eq!(
+            fill_placeholders(
+                "{count} snapshots on {drive}".to_string(),
+                &[("drive", "USB"), ("count", "3")]
+            ),
+            "3 snapshots on USB"
+        );
+        assert_eq!(
+            fill_placeholders("{unknown} stays".to_string(), &[("name", "x")]),
+            "{unknown} stays"
+        );
+    }
+}
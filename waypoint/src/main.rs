@@ -1,8 +1,14 @@
+mod activity_log;
+mod app_log;
 mod backup_manager;
 mod btrfs;
 mod cache;
+mod create_queue;
 mod dbus_client;
+mod global_shortcut;
+mod i18n;
 mod mount_monitor;
+mod operations;
 mod packages;
 mod performance;
 mod signal_listener;
@@ -13,17 +19,27 @@ mod user_preferences;
 
 use gtk::prelude::*;
 use gtk::{Application, glib};
+use std::cell::Cell;
+use std::sync::mpsc;
 
 const APP_ID: &str = "tech.geektoshi.waypoint";
 
+thread_local! {
+    // Set while the first window waits for the helper's capabilities
+    static STARTING: Cell<bool> = const { Cell::new(false) };
+}
+
 fn main() -> glib::ExitCode {
-    // Initialize logging
-    // To enable performance profiling, set RUST_LOG=debug:
+    // Initialize logging at the level chosen in the log viewer (default info)
+    // RUST_LOG overrides it; to enable performance profiling, use debug:
     //   RUST_LOG=debug cargo run
     // Performance statistics will be logged after each snapshot list refresh
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    app_log::init();
     log::info!("Starting Waypoint v{}", env!("CARGO_PKG_VERSION"));
 
+    // Select the language before any string is translated
+    i18n::init();
+
     // Initialize GTK
     let app = Application::builder().application_id(APP_ID).build();
 
@@ -70,6 +86,33 @@ fn load_css() {
             background-color: #000000;
             border: 2px solid #000000;
         }
+
+        .theme-circle:focus-visible {
+            outline: 2px solid @accent_color;
+            outline-offset: 2px;
+        }
+
+        row.compact-row,
+        row.compact-row > box.header {
+            min-height: 34px;
+        }
+
+        row.compact-row > box.header {
+            padding-top: 2px;
+            padding-bottom: 2px;
+        }
+
+        row.category-pre-update {
+            box-shadow: inset 3px 0 @accent_color;
+        }
+
+        row.category-pre-config-change {
+            box-shadow: inset 3px 0 @warning_color;
+        }
+
+        row.category-milestone {
+            box-shadow: inset 3px 0 @success_color;
+        }
         "#,
     );
 
@@ -81,12 +124,44 @@ fn load_css() {
 }
 
 fn build_ui(app: &Application) {
+    // The application ID makes Waypoint single-instance: launching it again
+    // activates this process instead of starting a new one. Bring the existing
+    // window forward rather than building a second one with its own signal
+    // listener, which would duplicate refreshes and notifications.
+    if let Some(window) = app.active_window() {
+        log::info!("Waypoint is already running, presenting the existing window");
+        window.present();
+        return;
+    }
+    if STARTING.replace(true) {
+        return;
+    }
+
     // Initialize filesystem cache
     btrfs::init_cache();
 
-    // Start D-Bus signal listener for snapshot creation and backup progress events
-    let (snapshot_created_rx, backup_progress_rx) = signal_listener::start_signal_listener(app.clone());
+    // The window hides features the helper lacks, so learn them first without
+    // blocking the main loop. Holding the app keeps it running meanwhile.
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        dbus_client::negotiate_capabilities();
+        let _ = tx.send(());
+    });
+
+    let app = app.clone();
+    let hold = app.hold();
+    glib::spawn_future_local(async move {
+        while let Err(mpsc::TryRecvError::Empty) = rx.try_recv() {
+            glib::timeout_future(std::time::Duration::from_millis(50)).await;
+        }
 
-    let window = ui::MainWindow::new(app, snapshot_created_rx, backup_progress_rx);
-    window.present();
+        // Start D-Bus signal listener for snapshot creation and backup progress events
+        let (snapshot_created_rx, backup_progress_rx) =
+            signal_listener::start_signal_listener(app.clone());
+
+        let window = ui::MainWindow::new(&app, snapshot_created_rx, backup_progress_rx);
+        window.present();
+        STARTING.set(false);
+        drop(hold);
+    });
 }
@@ -0,0 +1,466 @@
+//! Long-running operations shown in the Operations panel
+//!
+//! Backups, verifications, comparisons, balances and deduplication all run in
+//! the background, and each reports progress its own way: D-Bus signals,
+//! polling or worker threads. They all feed the tracker here, so the panel in
+//! the header bar shows everything that is running in one place and users
+//! can keep working while it runs. The tracker belongs to the GTK main
+//! thread; background threads report through their usual channels.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::signal_listener::BackupProgressEvent;
+
+/// Finished operations kept for the panel; older ones are dropped
+const MAX_FINISHED: usize = 10;
+
+pub type OperationId = u64;
+
+/// What an operation does, for its icon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    CreateSnapshot,
+    Backup,
+    Verify,
+    Compare,
+    Balance,
+    Dedup,
+}
+
+impl OperationKind {
+    pub fn icon_name(self) -> &'static str {
+        match self {
+            OperationKind::CreateSnapshot => "camera-photo-symbolic",
+            OperationKind::Backup => "drive-harddisk-symbolic",
+            OperationKind::Verify => "emblem-ok-symbolic",
+            OperationKind::Compare => "view-dual-symbolic",
+            OperationKind::Balance => "view-refresh-symbolic",
+            OperationKind::Dedup => "edit-copy-symbolic",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationState {
+    /// Waiting for an earlier operation of the same kind to finish
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Clone)]
+pub struct Operation {
+    pub id: OperationId,
+    pub kind: OperationKind,
+    /// Identifies operations fed by signals, e.g. a backup by snapshot and destination
+    pub key: Option<String>,
+    pub title: String,
+    /// Latest progress message, or the outcome once finished
+    pub status: String,
+    /// Progress from 0.0 to 1.0, None when it can't be measured
+    pub fraction: Option<f64>,
+    pub state: OperationState,
+    /// Asks the operation to stop; it still reports how it ended
+    pub cancel: Option<Rc<dyn Fn()>>,
+}
+
+impl Operation {
+    /// Whether the operation hasn't finished yet, including while it is queued
+    pub fn is_running(&self) -> bool {
+        matches!(self.state, OperationState::Queued | OperationState::Running)
+    }
+}
+
+/// Running and recently finished operations, newest first
+#[derive(Default)]
+pub struct OperationTracker {
+    operations: Vec<Operation>,
+    next_id: OperationId,
+}
+
+impl OperationTracker {
+    pub fn start(
+        &mut self,
+        kind: OperationKind,
+        key: Option<String>,
+        title: String,
+    ) -> OperationId {
+        self.next_id += 1;
+        self.operations.insert(
+            0,
+            Operation {
+                id: self.next_id,
+                kind,
+                key,
+                title,
+                status: String::new(),
+                fraction: None,
+                state: OperationState::Running,
+                cancel: None,
+            },
+        );
+        self.next_id
+    }
+
+    /// Running operation with the given key
+    pub fn find_running(&self, key: &str) -> Option<OperationId> {
+        self.operations
+            .iter()
+            .find(|op| op.is_running() && op.key.as_deref() == Some(key))
+            .map(|op| op.id)
+    }
+
+    pub fn get(&self, id: OperationId) -> Option<&Operation> {
+        self.operations.iter().find(|op| op.id == id)
+    }
+
+    fn get_mut(&mut self, id: OperationId) -> Option<&mut Operation> {
+        self.operations.iter_mut().find(|op| op.id == id)
+    }
+
+    pub fn set_cancel(&mut self, id: OperationId, cancel: Rc<dyn Fn()>) {
+        if let Some(op) = self.get_mut(id) {
+            op.cancel = Some(cancel);
+        }
+    }
+
+    /// Mark a new operation as waiting for its turn
+    pub fn set_queued(&mut self, id: OperationId) {
+        if let Some(op) = self.get_mut(id).filter(|op| op.is_running()) {
+            op.state = OperationState::Queued;
+            op.status = "Queued".to_string();
+        }
+    }
+
+    /// Start a queued operation; does nothing once it has finished
+    pub fn begin(&mut self, id: OperationId) {
+        if let Some(op) = self
+            .get_mut(id)
+            .filter(|op| op.state == OperationState::Queued)
+        {
+            op.state = OperationState::Running;
+            op.status = String::new();
+        }
+    }
+
+    /// Record progress; ignored once the operation has finished
+    pub fn update(&mut self, id: OperationId, fraction: Option<f64>, status: String) {
+        if let Some(op) = self.get_mut(id).filter(|op| op.is_running()) {
+            op.fraction = fraction.map(|f| f.clamp(0.0, 1.0));
+            op.status = status;
+        }
+    }
+
+    /// Record how an operation ended; only the first outcome counts
+    pub fn finish(&mut self, id: OperationId, state: OperationState, status: String) {
+        if let Some(op) = self.get_mut(id).filter(|op| op.is_running()) {
+            op.state = state;
+            op.status = status;
+            op.cancel = None;
+            if state == OperationState::Succeeded {
+                op.fraction = Some(1.0);
+            }
+        }
+
+        let mut finished = 0;
+        self.operations.retain(|op| {
+            if op.is_running() {
+                return true;
+            }
+            finished += 1;
+            finished <= MAX_FINISHED
+        });
+    }
+
+    pub fn clear_finished(&mut self) {
+        self.operations.retain(Operation::is_running);
+    }
+
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    pub fn running_count(&self) -> usize {
+        self.operations.iter().filter(|op| op.is_running()).count()
+    }
+}
+
+thread_local! {
+    static TRACKER: RefCell<OperationTracker> = RefCell::default();
+    static LISTENERS: RefCell<Vec<Rc<dyn Fn()>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Change the tracker and tell the listeners
+///
+/// Listeners run after the tracker is released, so they can read it.
+fn modify<R>(f: impl FnOnce(&mut OperationTracker) -> R) -> R {
+    let result = TRACKER.with_borrow_mut(f);
+    let listeners = LISTENERS.with_borrow(|listeners| listeners.clone());
+    for listener in listeners {
+        listener();
+    }
+    result
+}
+
+/// Call `f` whenever an operation starts, progresses or finishes
+pub fn connect_changed(f: impl Fn() + 'static) {
+    LISTENERS.with_borrow_mut(|listeners| listeners.push(Rc::new(f)));
+}
+
+pub fn start(kind: OperationKind, title: impl Into<String>) -> OperationId {
+    modify(|tracker| tracker.start(kind, None, title.into()))
+}
+
+/// Start an operation that can be stopped from the panel
+pub fn start_cancellable(
+    kind: OperationKind,
+    title: impl Into<String>,
+    cancel: impl Fn() + 'static,
+) -> OperationId {
+    modify(|tracker| {
+        let id = tracker.start(kind, None, title.into());
+        tracker.set_cancel(id, Rc::new(cancel));
+        id
+    })
+}
+
+pub fn update(id: OperationId, fraction: Option<f64>, status: impl Into<String>) {
+    modify(|tracker| tracker.update(id, fraction, status.into()));
+}
+
+pub fn succeed(id: OperationId, status: impl Into<String>) {
+    modify(|tracker| tracker.finish(id, OperationState::Succeeded, status.into()));
+}
+
+pub fn fail(id: OperationId, error: impl Into<String>) {
+    modify(|tracker| tracker.finish(id, OperationState::Failed, error.into()));
+}
+
+pub fn cancelled(id: OperationId) {
+    modify(|tracker| tracker.finish(id, OperationState::Cancelled, "Cancelled".to_string()));
+}
+
+/// Start an operation that waits for its turn, e.g. in `create_queue`
+///
+/// `cancel` takes it out of the queue; `begin` marks it running once it starts.
+pub fn queue(
+    kind: OperationKind,
+    title: impl Into<String>,
+    cancel: impl Fn() + 'static,
+) -> OperationId {
+    modify(|tracker| {
+        let id = tracker.start(kind, None, title.into());
+        tracker.set_queued(id);
+        tracker.set_cancel(id, Rc::new(cancel));
+        id
+    })
+}
+
+/// Start a queued operation, replacing how it is stopped
+///
+/// Without `cancel` the running operation can't be stopped from the panel.
+pub fn begin(id: OperationId, cancel: Option<Rc<dyn Fn()>>) {
+    modify(|tracker| {
+        tracker.begin(id);
+        if let Some(op) = tracker.get_mut(id).filter(|op| op.is_running()) {
+            op.cancel = cancel;
+        }
+    });
+}
+
+/// Ask an operation to stop
+pub fn cancel(id: OperationId) {
+    let cancel = modify(|tracker| {
+        let cancel = tracker.get(id).and_then(|op| op.cancel.clone());
+        if cancel.is_some() {
+            let fraction = tracker.get(id).and_then(|op| op.fraction);
+            tracker.update(id, fraction, "Cancelling…".to_string());
+            if let Some(op) = tracker.get_mut(id) {
+                op.cancel = None;
+            }
+        }
+        cancel
+    });
+    if let Some(cancel) = cancel {
+        cancel();
+    }
+}
+
+pub fn clear_finished() {
+    modify(OperationTracker::clear_finished);
+}
+
+/// Current operations, newest first
+pub fn operations() -> Vec<Operation> {
+    TRACKER.with_borrow(|tracker| tracker.operations().to_vec())
+}
+
+pub fn running_count() -> usize {
+    TRACKER.with_borrow(OperationTracker::running_count)
+}
+
+/// Track a backup from its `BackupProgress` signals
+///
+/// Backups started by the automatic backup queue and by the backup dialog
+/// both report through the signal, so this covers all of them.
+pub fn backup_progress(event: &BackupProgressEvent, destination_label: &str) {
+    let key = format!("backup:{}:{}", event.snapshot_id, event.destination_uuid);
+    modify(|tracker| {
+        let id = match tracker.find_running(&key) {
+            Some(id) => id,
+            // A late "complete" for a backup that was never seen isn't worth showing
+            None if matches!(event.stage.as_str(), "complete" | "failed") => return,
+            None => tracker.start(
+                OperationKind::Backup,
+                Some(key),
+                format!("Back up {} to {destination_label}", event.snapshot_id),
+            ),
+        };
+
+        match event.stage.as_str() {
+            "complete" => tracker.finish(id, OperationState::Succeeded, "Backed up".to_string()),
+            "failed" => tracker.finish(id, OperationState::Failed, "Backup failed".to_string()),
+            stage => {
+                let fraction = (event.total_bytes > 0)
+                    .then(|| event.bytes_transferred as f64 / event.total_bytes as f64);
+                let status = match stage {
+                    "preparing" => "Preparing…".to_string(),
+                    "verifying" => "Verifying…".to_string(),
+                    _ if event.total_bytes > 0 => format!(
+                        "{} of {}",
+                        waypoint_common::format::format_bytes(event.bytes_transferred),
+                        waypoint_common::format::format_bytes(event.total_bytes)
+                    ),
+                    _ => "Transferring…".to_string(),
+                };
+                tracker.update(id, fraction, status);
+            }
+        }
+    });
+}
+
+/// Track a balance from its `BalanceProgress` signals
+///
+/// Balances keep running after Waypoint is closed and can be started from
+/// the command line, so they're picked up from the signal rather than where
+/// they are started. `cancel` asks the helper to stop the balance.
+pub fn balance_progress(balanced: u64, total: u64, stage: &str, cancel: impl Fn() + 'static) {
+    const KEY: &str = "balance";
+    modify(|tracker| {
+        let id = match tracker.find_running(KEY) {
+            Some(id) => id,
+            None if stage != "running" => return,
+            None => {
+                let id = tracker.start(
+                    OperationKind::Balance,
+                    Some(KEY.to_string()),
+                    "Balance filesystem".to_string(),
+                );
+                tracker.set_cancel(id, Rc::new(cancel));
+                id
+            }
+        };
+
+        match stage {
+            "complete" => tracker.finish(id, OperationState::Succeeded, "Completed".to_string()),
+            "cancelled" => tracker.finish(id, OperationState::Cancelled, "Cancelled".to_string()),
+            "failed" => tracker.finish(id, OperationState::Failed, "Balance failed".to_string()),
+            _ => {
+                let fraction = (total > 0).then(|| balanced as f64 / total as f64);
+                tracker.update(id, fraction, format!("{balanced} of about {total} chunks"));
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_operation_lifecycle() {
+        let mut tracker = OperationTracker::default();
+        let first = tracker.start(OperationKind::Verify, None, "Verify".to_string());
+        let second = tracker.start(
+            OperationKind::Backup,
+            Some("backup:a:b".to_string()),
+            "Back up".to_string(),
+        );
+        assert_eq!(tracker.operations()[0].id, second);
+        assert_eq!(tracker.find_running("backup:a:b"), Some(second));
+        assert_eq!(tracker.running_count(), 2);
+
+        tracker.update(first, Some(1.5), "Almost".to_string());
+        assert_eq!(tracker.get(first).unwrap().fraction, Some(1.0));
+
+        tracker.finish(second, OperationState::Failed, "No space".to_string());
+        assert_eq!(tracker.find_running("backup:a:b"), None);
+        assert_eq!(tracker.running_count(), 1);
+
+        // Updates and outcomes after the first outcome are ignored
+        tracker.update(second, Some(0.5), "Late".to_string());
+        tracker.finish(second, OperationState::Succeeded, "Late".to_string());
+        let op = tracker.get(second).unwrap();
+        assert_eq!(op.state, OperationState::Failed);
+        assert_eq!(op.status, "No space");
+
+        tracker.clear_finished();
+        assert_eq!(tracker.operations().len(), 1);
+    }
+
+    #[test]
+    fn test_queued_operation() {
+        let mut tracker = OperationTracker::default();
+        let id = tracker.start(OperationKind::CreateSnapshot, None, "Create".to_string());
+        tracker.set_queued(id);
+        let op = tracker.get(id).unwrap();
+        assert_eq!(op.state, OperationState::Queued);
+        assert_eq!(op.status, "Queued");
+        assert_eq!(tracker.running_count(), 1);
+
+        tracker.begin(id);
+        let op = tracker.get(id).unwrap();
+        assert_eq!(op.state, OperationState::Running);
+        assert!(op.status.is_empty());
+
+        // Cancelled while queued, a late start is ignored
+        let other = tracker.start(OperationKind::CreateSnapshot, None, "Create".to_string());
+        tracker.set_queued(other);
+        tracker.finish(other, OperationState::Cancelled, "Cancelled".to_string());
+        tracker.begin(other);
+        assert_eq!(tracker.get(other).unwrap().state, OperationState::Cancelled);
+    }
+
+    #[test]
+    fn test_finished_operations_are_limited() {
+        let mut tracker = OperationTracker::default();
+        let running = tracker.start(OperationKind::Balance, None, "Balance".to_string());
+        for _ in 0..MAX_FINISHED + 5 {
+            let id = tracker.start(OperationKind::Compare, None, "Compare".to_string());
+            tracker.finish(id, OperationState::Succeeded, String::new());
+        }
+        assert_eq!(tracker.operations().len(), MAX_FINISHED + 1);
+        assert!(tracker.get(running).is_some());
+    }
+
+    #[test]
+    fn test_cancel_runs_once() {
+        let calls = Rc::new(Cell::new(0));
+        let calls_clone = calls.clone();
+        let id = start_cancellable(OperationKind::Dedup, "Dedup", move || {
+            calls_clone.set(calls_clone.get() + 1);
+        });
+
+        cancel(id);
+        cancel(id);
+        assert_eq!(calls.get(), 1);
+        assert!(operations().iter().any(|op| op.id == id && op.is_running()));
+
+        cancelled(id);
+        assert_eq!(running_count(), 0);
+    }
+}
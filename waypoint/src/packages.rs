@@ -26,6 +26,16 @@ impl From<&Package> for waypoint_common::Package {
     }
 }
 
+/// Convert common Package to GUI Package
+impl From<&waypoint_common::Package> for Package {
+    fn from(p: &waypoint_common::Package) -> Self {
+        Package {
+            name: p.name.clone(),
+            version: p.version.clone(),
+        }
+    }
+}
+
 /// Get list of all installed packages using xbps-query
 #[allow(dead_code)]
 pub fn get_installed_packages() -> Result<Vec<Package>> {
@@ -57,6 +67,39 @@ pub fn get_installed_packages() -> Result<Vec<Package>> {
     Ok(packages)
 }
 
+/// Count packages with an update available in the local repository index
+///
+/// Runs a dry-run `xbps-install -un`, which works unprivileged and doesn't sync
+/// the repositories, so the count is only as fresh as the last sync.
+pub fn count_pending_updates() -> Result<usize> {
+    let output = Command::new("xbps-install")
+        .args(["-u", "-n"])
+        .output()
+        .context("Failed to execute xbps-install. Is XBPS installed?")?;
+
+    // xbps-install exits non-zero when everything is up to date, so only
+    // treat it as an error if it printed nothing useful
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() && stdout.trim().is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !stderr.trim().is_empty() {
+            anyhow::bail!("xbps-install failed: {}", stderr.trim());
+        }
+    }
+
+    Ok(count_update_lines(&stdout))
+}
+
+/// Count "update" transactions in `xbps-install -n` output
+///
+/// Each line has the form "pkgver action arch repository installedsize downloadsize".
+fn count_update_lines(output: &str) -> usize {
+    output
+        .lines()
+        .filter(|line| line.split_whitespace().nth(1) == Some("update"))
+        .count()
+}
+
 /// Split a package string like "firefox-120.0_1" into ("firefox", "120.0_1")
 #[allow(dead_code)]
 fn split_package_name_version(pkg: &str) -> Option<(&str, &str)> {
@@ -170,6 +213,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_count_update_lines() {
+        let output = "firefox-121.0_1 update x86_64 https://repo-default.voidlinux.org/current 250MB 60MB\n\
+                      libnew-1.0_1 install x86_64 https://repo-default.voidlinux.org/current 1MB 200KB\n\
+                      vim-9.1_1 update x86_64 https://repo-default.voidlinux.org/current 30MB 8MB\n";
+        assert_eq!(count_update_lines(output), 2);
+        assert_eq!(count_update_lines(""), 0);
+    }
+
     #[test]
     fn test_package_diff() {
         let old = vec![
@@ -12,7 +12,7 @@
 //! - `populate_ui` - Measures time to create and populate UI widgets
 //! - `get_snapshot_size` - Measures time to calculate snapshot size (including cache hits)
 //! - `du_command` - Measures time for the actual `du` command execution
-//! - `get_available_space` - Measures time to check available disk space
+//! - `get_filesystem_stats` - Measures time to check available disk space
 //! - `df_command` - Measures time for the actual `df` command execution
 //!
 //! # Viewing Statistics
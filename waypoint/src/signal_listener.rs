@@ -7,6 +7,8 @@ use gtk::glib;
 use waypoint_common::*;
 use zbus::{Connection, MatchRule};
 
+use crate::activity_log::{self, ActivityKind};
+use crate::operations;
 use crate::ui::notifications;
 
 #[derive(Clone, Debug)]
@@ -29,6 +31,10 @@ pub struct BackupProgressEvent {
 pub enum WaypointEvent {
     SnapshotCreated(SnapshotCreatedEvent),
     BackupProgress(BackupProgressEvent),
+    /// Quota auto-cleanup deleted these snapshots
+    QuotaCleanup(Vec<String>),
+    /// Chunks balanced so far, the approximate total and the stage
+    BalanceProgress(u64, u64, String),
 }
 
 /// Start listening for waypoint-helper D-Bus signals
@@ -71,11 +77,21 @@ pub fn start_signal_listener(
                     WaypointEvent::SnapshotCreated(evt) => {
                         println!("Main thread received SnapshotCreated: {evt:?}");
 
+                        activity_log::record(
+                            ActivityKind::SnapshotCreated,
+                            &evt.snapshot_name,
+                            true,
+                            Some(format!("Created by {}", evt.created_by)),
+                        );
+
                         // Only send notification if created by scheduler
                         if evt.created_by == "scheduler" {
                             notifications::notify_scheduled_snapshot(&app, &evt.snapshot_name);
                         }
 
+                        // Every new snapshot eats into free space
+                        notifications::check_low_space(&app);
+
                         // Forward to snapshot channel for backup processing
                         if let Err(e) = snapshot_sender_clone.send(evt) {
                             log::error!("Failed to forward snapshot created event: {e}");
@@ -89,6 +105,26 @@ pub fn start_signal_listener(
                             log::error!("Failed to forward backup progress event: {e}");
                         }
                     }
+                    WaypointEvent::QuotaCleanup(deleted) => {
+                        activity_log::record(
+                            ActivityKind::Cleanup,
+                            &format!("{} snapshot(s)", deleted.len()),
+                            true,
+                            Some(format!("Deleted to free quota space: {}", deleted.join(", "))),
+                        );
+                        notifications::notify_quota_cleanup(&app, &deleted);
+                    }
+                    WaypointEvent::BalanceProgress(balanced, total, stage) => {
+                        operations::balance_progress(balanced, total, &stage, || {
+                            std::thread::spawn(|| {
+                                let result = crate::dbus_client::WaypointHelperClient::new()
+                                    .and_then(|client| client.cancel_balance());
+                                if let Err(e) = result {
+                                    log::warn!("Failed to cancel balance: {e}");
+                                }
+                            });
+                        });
+                    }
                 }
             }
 
@@ -123,6 +159,25 @@ async fn listen_for_signals(sender: std::sync::mpsc::Sender<WaypointEvent>) -> R
 
     let _: () = proxy.call("AddMatch", &(rule.to_string(),)).await?;
 
+    let quota_rule = MatchRule::builder()
+        .msg_type(zbus::message::Type::Signal)
+        .interface(DBUS_INTERFACE_NAME)?
+        .member("QuotaCleanup")?
+        .build();
+    let _: () = proxy.call("AddMatch", &(quota_rule.to_string(),)).await?;
+
+    // Progress of long-running operations, for the Operations panel
+    for member in ["BackupProgress", "BalanceProgress"] {
+        let progress_rule = MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .interface(DBUS_INTERFACE_NAME)?
+            .member(member)?
+            .build();
+        let _: () = proxy
+            .call("AddMatch", &(progress_rule.to_string(),))
+            .await?;
+    }
+
     println!("Signal listener started for snapshot_created signals");
 
     // Create a message stream
@@ -179,6 +234,24 @@ async fn listen_for_signals(sender: std::sync::mpsc::Sender<WaypointEvent>) -> R
                                 }
                             }
                         }
+                        "BalanceProgress" => {
+                            if let Ok((balanced, total, stage)) =
+                                msg.body().deserialize::<(u64, u64, String)>()
+                            {
+                                let event = WaypointEvent::BalanceProgress(balanced, total, stage);
+                                if let Err(e) = sender.send(event) {
+                                    log::error!("Failed to send event to main thread: {e}");
+                                }
+                            }
+                        }
+                        "QuotaCleanup" => {
+                            if let Ok(deleted) = msg.body().deserialize::<Vec<String>>() {
+                                log::info!("Quota cleanup deleted {} snapshot(s)", deleted.len());
+                                if let Err(e) = sender.send(WaypointEvent::QuotaCleanup(deleted)) {
+                                    log::error!("Failed to send event to main thread: {e}");
+                                }
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -6,7 +6,10 @@ use std::fs::OpenOptions;
 use std::io::{Read, Write};
 use std::path::PathBuf;
 use std::rc::Rc;
-use waypoint_common::{SnapshotInfo, WaypointConfig};
+use waypoint_common::{
+    MetadataFormat, SnapshotCategory, SnapshotInfo, TransactionLink, TransactionPhase,
+    WaypointConfig,
+};
 
 use crate::packages::Package;
 
@@ -28,11 +31,23 @@ pub struct Snapshot {
     pub packages: Rc<Vec<Package>>,
     /// List of subvolumes included in this snapshot (wrapped in Rc for cheap cloning)
     pub subvolumes: Rc<Vec<PathBuf>>,
+    /// Prefix of the schedule whose retention applies, overriding the name prefix
+    pub retention_group: Option<String>,
+    /// When cleanup deletes the snapshot, regardless of schedule retention
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Package manager transaction the snapshot was taken before or after
+    pub transaction: Option<TransactionLink>,
+    /// Why the snapshot was taken
+    pub category: SnapshotCategory,
+    /// Pinned by the user, so cleanup never deletes it
+    pub is_favorite: bool,
 }
 
 /// Helper struct for serde serialization/deserialization
+///
+/// `P` is the package list, or just its length when loading summaries.
 #[derive(Debug, Serialize, Deserialize)]
-struct SnapshotSerde {
+struct SnapshotSerde<P = Vec<Package>> {
     id: String,
     name: String,
     timestamp: DateTime<Utc>,
@@ -42,9 +57,19 @@ struct SnapshotSerde {
     package_count: Option<usize>,
     size_bytes: Option<u64>,
     #[serde(default)]
-    packages: Vec<Package>,
+    packages: P,
     #[serde(default)]
     subvolumes: Vec<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    retention_group: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    transaction: Option<TransactionLink>,
+    #[serde(default, skip_serializing_if = "SnapshotCategory::is_manual")]
+    category: SnapshotCategory,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    is_favorite: bool,
 }
 
 impl Serialize for Snapshot {
@@ -63,6 +88,11 @@ impl Serialize for Snapshot {
             size_bytes: self.size_bytes,
             packages: (*self.packages).clone(),
             subvolumes: (*self.subvolumes).clone(),
+            retention_group: self.retention_group.clone(),
+            expires_at: self.expires_at,
+            transaction: self.transaction.clone(),
+            category: self.category,
+            is_favorite: self.is_favorite,
         };
         helper.serialize(serializer)
     }
@@ -73,19 +103,91 @@ impl<'de> Deserialize<'de> for Snapshot {
     where
         D: serde::Deserializer<'de>,
     {
-        let helper = SnapshotSerde::deserialize(deserializer)?;
-        Ok(Snapshot {
-            id: helper.id,
-            name: helper.name,
-            timestamp: helper.timestamp,
-            path: helper.path,
-            description: helper.description,
-            kernel_version: helper.kernel_version,
-            package_count: helper.package_count,
-            size_bytes: helper.size_bytes,
-            packages: Rc::new(helper.packages),
-            subvolumes: Rc::new(helper.subvolumes),
-        })
+        let (mut snapshot, packages) =
+            SnapshotSerde::<Vec<Package>>::deserialize(deserializer)?.split_packages();
+        snapshot.packages = Rc::new(packages);
+        Ok(snapshot)
+    }
+}
+
+impl<P> SnapshotSerde<P> {
+    /// Build the snapshot, handing back the package list separately
+    fn split_packages(self) -> (Snapshot, P) {
+        let snapshot = Snapshot {
+            id: self.id,
+            name: self.name,
+            timestamp: self.timestamp,
+            path: self.path,
+            description: self.description,
+            kernel_version: self.kernel_version,
+            package_count: self.package_count,
+            size_bytes: self.size_bytes,
+            packages: Rc::default(),
+            subvolumes: Rc::new(self.subvolumes),
+            retention_group: self.retention_group,
+            expires_at: self.expires_at,
+            transaction: self.transaction,
+            category: self.category,
+            is_favorite: self.is_favorite,
+        };
+        (snapshot, self.packages)
+    }
+}
+
+/// Length of a package list, counted while skipping over the packages
+#[derive(Debug, Default)]
+struct PackageCount(usize);
+
+impl<'de> Deserialize<'de> for PackageCount {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct CountVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for CountVisitor {
+            type Value = PackageCount;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a list of packages")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut count = 0;
+                while seq.next_element::<serde::de::IgnoredAny>()?.is_some() {
+                    count += 1;
+                }
+                Ok(PackageCount(count))
+            }
+        }
+
+        deserializer.deserialize_seq(CountVisitor)
+    }
+}
+
+/// Snapshot loaded without its package list, which only `package_count` describes
+struct SnapshotSummary(Snapshot);
+
+impl<'de> Deserialize<'de> for SnapshotSummary {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let (mut snapshot, PackageCount(count)) =
+            SnapshotSerde::<PackageCount>::deserialize(deserializer)?.split_packages();
+        if count > 0 {
+            snapshot.package_count = Some(count);
+        }
+        Ok(SnapshotSummary(snapshot))
+    }
+}
+
+impl From<SnapshotSummary> for Snapshot {
+    fn from(summary: SnapshotSummary) -> Self {
+        summary.0
     }
 }
 
@@ -129,6 +231,31 @@ impl Snapshot {
             self.timestamp.format("%b %d, %Y").to_string()
         }
     }
+
+    /// Countdown to the snapshot's expiry (e.g., "Expires in 3 days"), if it has one
+    pub fn format_expiry(&self) -> Option<String> {
+        let remaining = self.expires_at?.signed_duration_since(Utc::now());
+
+        Some(if remaining.num_seconds() <= 0 {
+            "Expired".to_string()
+        } else if remaining.num_hours() < 1 {
+            "Expires within the hour".to_string()
+        } else if remaining.num_hours() < 24 {
+            let hours = remaining.num_hours();
+            if hours == 1 {
+                "Expires in 1 hour".to_string()
+            } else {
+                format!("Expires in {hours} hours")
+            }
+        } else {
+            let days = remaining.num_days();
+            if days == 1 {
+                "Expires in 1 day".to_string()
+            } else {
+                format!("Expires in {days} days")
+            }
+        })
+    }
 }
 
 /// Convert GUI Snapshot to common SnapshotInfo (for use with backup filtering)
@@ -141,16 +268,66 @@ impl From<&Snapshot> for SnapshotInfo {
             package_count: s.package_count,
             packages: s.packages.as_ref().iter().map(|p| p.into()).collect(),
             subvolumes: s.subvolumes.as_ref().clone(),
+            retention_group: s.retention_group.clone(),
+            expires_at: s.expires_at,
+            transaction: s.transaction.clone(),
+            category: s.category,
         }
     }
 }
 
+/// Move each after-transaction snapshot next to its before-transaction snapshot
+///
+/// Expects snapshots oldest first. Snapshots taken while a long transaction
+/// ran would otherwise split the pair in the list. Pairs whose other half
+/// isn't in `snapshots` are left where they are.
+pub fn group_transaction_pairs(snapshots: Vec<&Snapshot>) -> Vec<&Snapshot> {
+    let pre_of = |snapshot: &Snapshot| -> Option<String> {
+        let link = snapshot.transaction.as_ref()?;
+        (link.phase == TransactionPhase::Post).then(|| link.paired_with.clone())?
+    };
+    let (mut posts, rest): (Vec<&Snapshot>, Vec<&Snapshot>) =
+        snapshots.iter().copied().partition(|snapshot| {
+            pre_of(snapshot).is_some_and(|pre| snapshots.iter().any(|s| s.name == pre))
+        });
+
+    let mut grouped = Vec::with_capacity(snapshots.len());
+    for snapshot in rest {
+        grouped.push(snapshot);
+        if let Some(index) = posts
+            .iter()
+            .position(|post| pre_of(post).as_deref() == Some(snapshot.name.as_str()))
+        {
+            grouped.push(posts.remove(index));
+        }
+    }
+    grouped.extend(posts);
+    grouped
+}
+
+/// Number of oldest snapshots whose deletion frees at least `needed` bytes
+///
+/// `sizes` are the snapshot sizes, oldest first. Returns `None` if deleting
+/// all of them still wouldn't free enough.
+pub fn count_to_free(sizes: &[u64], needed: u64) -> Option<usize> {
+    let mut freed = 0u64;
+    for (count, size) in sizes.iter().enumerate() {
+        if freed >= needed {
+            return Some(count);
+        }
+        freed = freed.saturating_add(*size);
+    }
+    (freed >= needed).then_some(sizes.len())
+}
+
 // Re-export format_bytes from waypoint_common
 pub use waypoint_common::format_bytes;
 
 /// Manage snapshot metadata persistence
 pub struct SnapshotManager {
     metadata_file: PathBuf,
+    /// JSON, or TOML for a metadata file ending in `.toml`
+    format: MetadataFormat,
 }
 
 impl SnapshotManager {
@@ -179,7 +356,11 @@ impl SnapshotManager {
             fs::create_dir_all(parent).context("Failed to create metadata directory")?;
         }
 
-        Ok(Self { metadata_file })
+        let format = MetadataFormat::from_path(&metadata_file);
+        Ok(Self {
+            metadata_file,
+            format,
+        })
     }
 
     /// Get path to snapshots metadata file
@@ -189,7 +370,7 @@ impl SnapshotManager {
 
     /// Load all snapshots from metadata file
     ///
-    /// Reads the snapshots metadata JSON file and performs automatic cleanup:
+    /// Reads the snapshots metadata file and performs automatic cleanup:
     /// - Removes phantom snapshots (metadata exists but directory doesn't)
     /// - Removes duplicate entries (keeps most recent)
     /// - Saves cleaned metadata back to disk if changes were made
@@ -199,28 +380,62 @@ impl SnapshotManager {
     ///
     /// # Errors
     /// - Failed to read metadata file
-    /// - Failed to parse JSON
+    /// - Failed to parse the metadata
     /// - Failed to save cleaned metadata
     ///
     /// # Note
     /// Returns empty vec if metadata file doesn't exist (not an error).
     pub fn load_snapshots(&self) -> Result<Vec<Snapshot>> {
+        let (snapshots, cleaned) = self.read_entries::<Snapshot>()?;
+
+        // If we cleaned anything, save the cleaned list
+        if cleaned {
+            let _ = self.save_snapshots(&snapshots);
+        }
+
+        Ok(snapshots)
+    }
+
+    /// Load all snapshots without their package lists
+    ///
+    /// Much faster than `load_snapshots` with thousands of packages per
+    /// snapshot, for views that only need `package_count`. Phantom and
+    /// duplicate entries are skipped the same way, but the cleaned list is not
+    /// saved, as it lacks the packages. `get_snapshot` loads a snapshot in full.
+    pub fn load_snapshot_summaries(&self) -> Result<Vec<Snapshot>> {
+        Ok(self.read_entries::<SnapshotSummary>()?.0)
+    }
+
+    /// Read the metadata file, dropping phantom and duplicate entries
+    ///
+    /// Returns the remaining snapshots and whether any entries were dropped.
+    fn read_entries<T>(&self) -> Result<(Vec<Snapshot>, bool)>
+    where
+        T: serde::de::DeserializeOwned + Into<Snapshot>,
+    {
         let path = self.metadata_path();
 
         if !path.exists() {
-            return Ok(Vec::new());
+            return Ok((Vec::new(), false));
         }
 
         let content = self
             .read_locked_file(path)
             .context("Failed to read snapshots metadata")?;
 
-        let mut snapshots: Vec<Snapshot> =
-            serde_json::from_str(&content).context("Failed to parse snapshots metadata")?;
+        let entries: Vec<T> = self
+            .format
+            .parse(&content)
+            .context("Failed to parse snapshots metadata")?;
+        let mut snapshots: Vec<Snapshot> = entries.into_iter().map(Into::into).collect();
 
-        // Filter out snapshots that don't exist on disk (phantom snapshots)
+        // Filter out snapshots that don't exist on disk (phantom snapshots).
+        // Snapshots in a root-only directory can't be seen and are kept.
         let initial_count = snapshots.len();
-        snapshots.retain(|s| s.path.exists());
+        for snapshot in &mut snapshots {
+            snapshot.path = crate::btrfs::locate_snapshot(&snapshot.path, &snapshot.name);
+        }
+        snapshots.retain(|s| waypoint_common::permissions::exists_or_hidden(&s.path));
         let after_phantom_cleanup = snapshots.len();
 
         // Remove duplicates by keeping only the last occurrence of each ID
@@ -237,7 +452,6 @@ impl SnapshotManager {
 
         let after_dedup = deduped.len();
 
-        // If we cleaned anything, save the cleaned list
         if after_phantom_cleanup < initial_count {
             log::info!(
                 "Cleaned up {} phantom snapshot(s) from metadata",
@@ -251,11 +465,7 @@ impl SnapshotManager {
             );
         }
 
-        if after_dedup < initial_count {
-            let _ = self.save_snapshots(&deduped);
-        }
-
-        Ok(deduped)
+        Ok((deduped, after_dedup < initial_count))
     }
 
     /// Save snapshots to disk
@@ -263,10 +473,14 @@ impl SnapshotManager {
     pub fn save_snapshots(&self, snapshots: &[Snapshot]) -> Result<()> {
         let path = self.metadata_path();
         let _lock = self.locked_file(path, true)?;
-        let content =
-            serde_json::to_string_pretty(snapshots).context("Failed to serialize snapshots")?;
+        let content = self
+            .format
+            .serialize(snapshots)
+            .context("Failed to serialize snapshots")?;
 
-        let tmp_path = path.with_extension("json.tmp");
+        let mut tmp_name = path.as_os_str().to_owned();
+        tmp_name.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_name);
 
         {
             let mut tmp_file = OpenOptions::new()
@@ -375,6 +589,17 @@ impl SnapshotManager {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_count_to_free() {
+        let sizes = [100, 300, 50];
+        assert_eq!(count_to_free(&sizes, 0), Some(0));
+        assert_eq!(count_to_free(&sizes, 100), Some(1));
+        assert_eq!(count_to_free(&sizes, 101), Some(2));
+        assert_eq!(count_to_free(&sizes, 450), Some(3));
+        assert_eq!(count_to_free(&sizes, 451), None);
+        assert_eq!(count_to_free(&[], 1), None);
+    }
+
     #[test]
     fn test_format_bytes() {
         assert_eq!(format_bytes(512), "512.00 B");
@@ -382,4 +607,112 @@ mod tests {
         assert_eq!(format_bytes(1024 * 1024), "1.00 MiB");
         assert_eq!(format_bytes(1024 * 1024 * 1024), "1.00 GiB");
     }
+
+    #[test]
+    fn test_toml_metadata_round_trip() {
+        let snapshot = Snapshot {
+            id: "before-upgrade".to_string(),
+            name: "before-upgrade".to_string(),
+            timestamp: Utc::now(),
+            path: PathBuf::from("/.snapshots/before-upgrade"),
+            description: Some("Before upgrade".to_string()),
+            kernel_version: Some("6.6.1_1".to_string()),
+            package_count: Some(1),
+            size_bytes: Some(4096),
+            packages: Rc::new(vec![Package {
+                name: "linux".to_string(),
+                version: "6.6.1_1".to_string(),
+            }]),
+            subvolumes: Rc::new(vec![PathBuf::from("/"), PathBuf::from("/home")]),
+            retention_group: Some("weekly".to_string()),
+            expires_at: None,
+            transaction: Some(TransactionLink {
+                id: "12".to_string(),
+                phase: TransactionPhase::Pre,
+                paired_with: None,
+            }),
+            category: SnapshotCategory::PreUpdate,
+            is_favorite: false,
+        };
+
+        let toml = MetadataFormat::Toml
+            .serialize(std::slice::from_ref(&snapshot))
+            .unwrap();
+        let parsed: Vec<Snapshot> = MetadataFormat::Toml.parse(&toml).unwrap();
+        assert_eq!(
+            serde_json::to_value(&parsed[0]).unwrap(),
+            serde_json::to_value(&snapshot).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_snapshot_summaries() {
+        let package = |name: &str| Package {
+            name: name.to_string(),
+            version: "1.0_1".to_string(),
+        };
+        let snapshot = Snapshot {
+            id: "daily".to_string(),
+            name: "daily".to_string(),
+            timestamp: Utc::now(),
+            path: PathBuf::from("/.snapshots/daily"),
+            description: None,
+            kernel_version: None,
+            package_count: None,
+            size_bytes: None,
+            packages: Rc::new(vec![package("bash"), package("linux")]),
+            subvolumes: Rc::new(vec![PathBuf::from("/")]),
+            retention_group: None,
+            expires_at: None,
+            transaction: None,
+            category: SnapshotCategory::Manual,
+            is_favorite: false,
+        };
+
+        for format in [MetadataFormat::Json, MetadataFormat::Toml] {
+            let content = format.serialize(std::slice::from_ref(&snapshot)).unwrap();
+            let parsed: Vec<SnapshotSummary> = format.parse(&content).unwrap();
+            let summary = Snapshot::from(parsed.into_iter().next().unwrap());
+            assert_eq!(summary.name, "daily");
+            assert!(summary.packages.is_empty());
+            assert_eq!(summary.package_count, Some(2));
+            assert_eq!(summary.subvolumes, snapshot.subvolumes);
+        }
+    }
+
+    #[test]
+    fn test_group_transaction_pairs() {
+        let snapshot =
+            |name: &str, transaction: Option<(TransactionPhase, Option<&str>)>| Snapshot {
+                id: name.to_string(),
+                name: name.to_string(),
+                timestamp: Utc::now(),
+                path: PathBuf::from("/.snapshots").join(name),
+                description: None,
+                kernel_version: None,
+                package_count: None,
+                size_bytes: None,
+                packages: Rc::new(Vec::new()),
+                subvolumes: Rc::new(Vec::new()),
+                retention_group: None,
+                expires_at: None,
+                transaction: transaction.map(|(phase, paired_with)| TransactionLink {
+                    id: "1".to_string(),
+                    phase,
+                    paired_with: paired_with.map(String::from),
+                }),
+                category: SnapshotCategory::Manual,
+                is_favorite: false,
+            };
+        let snapshots = [
+            snapshot("pre", Some((TransactionPhase::Pre, Some("post")))),
+            snapshot("hourly", None),
+            snapshot("post", Some((TransactionPhase::Post, Some("pre")))),
+            snapshot("orphan", Some((TransactionPhase::Post, Some("deleted")))),
+        ];
+
+        let grouped = group_transaction_pairs(snapshots.iter().collect());
+        let names: Vec<&str> = grouped.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["pre", "post", "hourly", "orphan"]);
+    }
 }
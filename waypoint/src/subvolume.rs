@@ -1,7 +1,100 @@
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::i18n::gettext;
+
+/// ID of the top-level subvolume of every Btrfs filesystem
+const TOP_LEVEL_SUBVOLUME_ID: u64 = 5;
+
+/// Names used for the subvolume holding snapshots (Waypoint, snapper, Timeshift)
+const SNAPSHOT_STORE_NAMES: &[&str] = &["@snapshots", ".snapshots", "snapshots", "timeshift-btrfs"];
+
+/// Names used for swap subvolumes
+const SWAP_NAMES: &[&str] = &["@swap", "swap", "@swapfile"];
+
+/// How a distribution names its subvolumes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubvolumeLayout {
+    /// `@`, `@home`, ... at the top level (Arch, Ubuntu, Debian, Mint)
+    AtPrefixed,
+    /// `@/home`, ... below `@`, root booted from a snapper snapshot (openSUSE)
+    Nested,
+    /// `root`, `home`, ... without prefix (Fedora)
+    Plain,
+    /// The root filesystem is the top-level subvolume itself
+    TopLevel,
+}
+
+impl SubvolumeLayout {
+    /// Detect the layout from the subvolume mounted at `/`
+    pub fn detect(subvolumes: &[SubvolumeInfo]) -> Option<Self> {
+        let root = subvolumes
+            .iter()
+            .find(|s| s.mount_point == Path::new("/"))?;
+
+        let layout = if root.id == TOP_LEVEL_SUBVOLUME_ID || root.subvol_path == "/" {
+            SubvolumeLayout::TopLevel
+        } else if root.subvol_path.starts_with("@/") {
+            SubvolumeLayout::Nested
+        } else if root.subvol_path.starts_with('@') {
+            SubvolumeLayout::AtPrefixed
+        } else {
+            SubvolumeLayout::Plain
+        };
+        Some(layout)
+    }
+
+    /// Human-readable description
+    pub fn description(self) -> String {
+        match self {
+            SubvolumeLayout::AtPrefixed => gettext("@ and @home style (Arch, Ubuntu, Debian)"),
+            SubvolumeLayout::Nested => gettext("Nested under @ (openSUSE)"),
+            SubvolumeLayout::Plain => gettext("root and home style (Fedora)"),
+            SubvolumeLayout::TopLevel => gettext("No subvolumes (root is the top level)"),
+        }
+    }
+}
+
+/// Manual corrections to subvolume detection
+///
+/// Stored in `~/.config/waypoint/subvolume-detection.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubvolumeOverrides {
+    /// Mount points offered for snapshots even though they look like
+    /// snapshot storage or swap
+    #[serde(default)]
+    pub always_include: Vec<PathBuf>,
+}
+
+fn overrides_path() -> PathBuf {
+    dirs::config_local_dir()
+        .map(|d| d.join("waypoint").join("subvolume-detection.json"))
+        .unwrap_or_else(|| PathBuf::from("/tmp/waypoint-subvolume-detection.json"))
+}
+
+/// Load detection overrides, falling back to none
+pub fn load_overrides() -> SubvolumeOverrides {
+    let Ok(content) = std::fs::read_to_string(overrides_path()) else {
+        return SubvolumeOverrides::default();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        log::error!("Failed to parse subvolume detection overrides: {e}");
+        SubvolumeOverrides::default()
+    })
+}
+
+/// Save detection overrides to disk
+pub fn save_overrides(overrides: &SubvolumeOverrides) -> Result<()> {
+    let path = overrides_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(overrides)?)?;
+    Ok(())
+}
+
 /// Information about a Btrfs subvolume
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SubvolumeInfo {
@@ -17,10 +110,10 @@ pub struct SubvolumeInfo {
 
 impl SubvolumeInfo {
     pub fn new(mount_point: PathBuf, subvol_path: String, id: u64) -> Self {
-        let display_name = if mount_point == Path::new("/") {
-            "Root filesystem (/)".to_string()
-        } else {
-            format!("{} ({})", mount_point.display(), mount_point.display())
+        let display_name = match mount_point.to_str() {
+            Some("/") => "Root filesystem (/)".to_string(),
+            Some("/home") => "Home (/home)".to_string(),
+            _ => format!("{} ({})", mount_point.display(), subvol_path),
         };
 
         Self {
@@ -45,7 +138,8 @@ pub fn detect_mounted_subvolumes() -> Result<Vec<SubvolumeInfo>> {
             continue;
         }
 
-        let mount_point = parts[1];
+        let mount_point = unescape_mount_field(parts[1]);
+        let mount_point = mount_point.as_str();
         let fs_type = parts[2];
         let options = parts[3];
 
@@ -60,7 +154,7 @@ pub fn detect_mounted_subvolumes() -> Result<Vec<SubvolumeInfo>> {
 
         for opt in options.split(',') {
             if let Some(path) = opt.strip_prefix("subvol=") {
-                subvol_path = Some(path.to_string());
+                subvol_path = Some(normalize_subvol_path(&unescape_mount_field(path)));
             } else if let Some(id_str) = opt.strip_prefix("subvolid=") {
                 if let Ok(id) = id_str.parse::<u64>() {
                     subvol_id = Some(id);
@@ -81,6 +175,13 @@ pub fn detect_mounted_subvolumes() -> Result<Vec<SubvolumeInfo>> {
             continue;
         };
 
+        // The top level is only a snapshot source when it is the root filesystem;
+        // elsewhere it's a maintenance mount such as the helper's /mnt/btrfs-root
+        if id == TOP_LEVEL_SUBVOLUME_ID && mount_point != "/" {
+            log::debug!("Skipping top-level subvolume mounted at {mount_point}");
+            continue;
+        }
+
         let subvol_info = SubvolumeInfo::new(PathBuf::from(mount_point), subvol_path, id);
         subvolumes.push(subvol_info);
     }
@@ -88,9 +189,62 @@ pub fn detect_mounted_subvolumes() -> Result<Vec<SubvolumeInfo>> {
     // Sort by mount point for consistent ordering
     subvolumes.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
 
+    // A subvolume mounted more than once (bind mounts, containers) is listed at
+    // its first mount point in sorted order, e.g. /home rather than /mnt/home
+    let mut seen_ids = std::collections::HashSet::new();
+    subvolumes.retain(|s| s.mount_point == Path::new("/") || seen_ids.insert(s.id));
+
     Ok(subvolumes)
 }
 
+/// Mounted subvolumes that can be snapshotted, applying the user's overrides
+pub fn detect_snapshot_candidates() -> Result<Vec<SubvolumeInfo>> {
+    let overrides = load_overrides();
+    Ok(detect_mounted_subvolumes()?
+        .into_iter()
+        .filter(|s| {
+            should_allow_snapshot(&s.subvol_path)
+                || overrides.always_include.contains(&s.mount_point)
+        })
+        .collect())
+}
+
+/// Decode the octal escapes (`\040` for a space etc.) used in /proc/mounts
+fn unescape_mount_field(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'\\' {
+            let byte = field
+                .get(i + 1..i + 4)
+                .and_then(|octal| u8::from_str_radix(octal, 8).ok());
+            if let Some(byte) = byte {
+                decoded.push(byte);
+                i += 4;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Subvolume path relative to the top level, without leading slash
+///
+/// `/@home` becomes `@home`; the top level itself is `/`.
+fn normalize_subvol_path(path: &str) -> String {
+    let trimmed = path.trim_matches('/');
+    if trimmed.is_empty() {
+        "/".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
 /// Get the subvolume ID for a given path
 fn get_subvolume_id(path: &Path) -> Result<u64> {
     let output = Command::new("btrfs")
@@ -125,11 +279,22 @@ fn get_subvolume_id(path: &Path) -> Result<u64> {
 /// Check if a subvolume should be allowed for snapshotting
 ///
 /// Returns false for subvolumes that should never be snapshotted:
-/// - @snapshots: The snapshot storage location itself
-/// - @swap: Swap space
+/// - Snapshot storage (`@snapshots`, `.snapshots`, ...) and anything inside it
+/// - Swap space (`@swap`, `swap`, ...)
+///
+/// openSUSE boots from a snapshot (`@/.snapshots/1/snapshot`), which is allowed.
 pub fn should_allow_snapshot(subvol_path: &str) -> bool {
-    // Filter out snapshots and swap subvolumes
-    subvol_path != "/@snapshots" && subvol_path != "/@swap"
+    let components: Vec<&str> = subvol_path.split('/').filter(|c| !c.is_empty()).collect();
+
+    if let [.., ".snapshots", number, "snapshot"] = components.as_slice()
+        && number.parse::<u64>().is_ok()
+    {
+        return true;
+    }
+
+    let in_snapshot_store = components.iter().any(|c| SNAPSHOT_STORE_NAMES.contains(c));
+    let is_swap = components.last().is_some_and(|c| SWAP_NAMES.contains(c));
+    !in_snapshot_store && !is_swap
 }
 
 #[cfg(test)]
@@ -143,6 +308,32 @@ mod tests {
 
         let home = SubvolumeInfo::new(PathBuf::from("/home"), "@home".to_string(), 257);
         assert!(home.display_name.contains("/home"));
+
+        let var = SubvolumeInfo::new(PathBuf::from("/var/log"), "@var_log".to_string(), 258);
+        assert_eq!(var.display_name, "/var/log (@var_log)");
+    }
+
+    #[test]
+    fn test_mount_fields() {
+        assert_eq!(unescape_mount_field("/mnt/my\\040data"), "/mnt/my data");
+        assert_eq!(unescape_mount_field("/plain"), "/plain");
+        assert_eq!(normalize_subvol_path("/@home"), "@home");
+        assert_eq!(normalize_subvol_path("/"), "/");
+    }
+
+    #[test]
+    fn test_detect_layout() {
+        let layout = |root: &str, id| {
+            SubvolumeLayout::detect(&[SubvolumeInfo::new(PathBuf::from("/"), root.to_string(), id)])
+        };
+        assert_eq!(layout("@", 256), Some(SubvolumeLayout::AtPrefixed));
+        assert_eq!(
+            layout("@/.snapshots/1/snapshot", 268),
+            Some(SubvolumeLayout::Nested)
+        );
+        assert_eq!(layout("root", 256), Some(SubvolumeLayout::Plain));
+        assert_eq!(layout("/", 5), Some(SubvolumeLayout::TopLevel));
+        assert_eq!(SubvolumeLayout::detect(&[]), None);
     }
 
     #[test]
@@ -152,8 +343,16 @@ mod tests {
         assert!(should_allow_snapshot("/@home"));
         assert!(should_allow_snapshot("/@var"));
 
+        // Other distribution layouts
+        assert!(should_allow_snapshot("home"));
+        assert!(should_allow_snapshot("@/home"));
+        assert!(should_allow_snapshot("@/.snapshots/1/snapshot"));
+
         // Should NOT allow snapshots and swap
         assert!(!should_allow_snapshot("/@snapshots"));
         assert!(!should_allow_snapshot("/@swap"));
+        assert!(!should_allow_snapshot("@/.snapshots"));
+        assert!(!should_allow_snapshot("@snapshots/backup-1/root"));
+        assert!(!should_allow_snapshot("swap"));
     }
 }
@@ -3,10 +3,12 @@ use gtk::prelude::*;
 use gtk::{Label, Orientation};
 use libadwaita as adw;
 
+use crate::i18n::{gettext, gettext_f};
+
 /// Show about dialog with app information
 pub fn show_about_dialog(window: &adw::ApplicationWindow) {
     let dialog = adw::Window::new();
-    dialog.set_title(Some("About Waypoint"));
+    dialog.set_title(Some(&gettext("About Waypoint")));
     dialog.set_default_size(400, 380);
     dialog.set_modal(true);
     dialog.set_transient_for(Some(window));
@@ -44,14 +46,18 @@ pub fn show_about_dialog(window: &adw::ApplicationWindow) {
     content.append(&name_label);
 
     // Version
-    let version_label = Label::new(Some(&format!("Version {}", env!("CARGO_PKG_VERSION"))));
+    let version_label = Label::new(Some(&gettext_f(
+        "Version {version}",
+        &[("version", env!("CARGO_PKG_VERSION"))],
+    )));
     version_label.add_css_class("dim-label");
     content.append(&version_label);
 
     // Description
-    let description = Label::new(Some(
+    let description = Label::new(Some(&gettext(
         "A GTK-based snapshot and rollback tool for Btrfs filesystems on Void Linux.",
-    ));
+    )));
+
     description.set_wrap(true);
     description.set_justify(gtk::Justification::Center);
     description.set_max_width_chars(40);
@@ -62,7 +68,7 @@ pub fn show_about_dialog(window: &adw::ApplicationWindow) {
     links_box.set_margin_top(12);
 
     // GitHub link
-    let github_btn = gtk::Button::with_label("View on GitHub");
+    let github_btn = gtk::Button::with_label(&gettext("View on GitHub"));
     github_btn.add_css_class("flat");
     github_btn.connect_clicked(|_| {
         let _ = std::process::Command::new("xdg-open")
@@ -72,7 +78,7 @@ pub fn show_about_dialog(window: &adw::ApplicationWindow) {
     links_box.append(&github_btn);
 
     // Report issue link
-    let issue_btn = gtk::Button::with_label("Report an issue");
+    let issue_btn = gtk::Button::with_label(&gettext("Report an issue"));
     issue_btn.add_css_class("flat");
     issue_btn.connect_clicked(|_| {
         let _ = std::process::Command::new("xdg-open")
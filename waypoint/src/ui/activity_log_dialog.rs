@@ -0,0 +1,186 @@
+//! Activity view listing what Waypoint has done recently
+
+use adw::prelude::*;
+use gtk::prelude::*;
+use gtk::{Label, ListBox, Orientation, glib};
+use libadwaita as adw;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::activity_log::{ActivityEntry, ActivityKind, ActivityLog};
+use crate::i18n::{gettext, gettext_noop, ngettext_f};
+
+/// Kind filter choices: (label, matching kind; None = any)
+const KIND_FILTERS: &[(&str, Option<ActivityKind>)] = &[
+    (gettext_noop("All activity"), None),
+    (gettext_noop("Created"), Some(ActivityKind::SnapshotCreated)),
+    (gettext_noop("Deleted"), Some(ActivityKind::SnapshotDeleted)),
+    (
+        gettext_noop("Restored"),
+        Some(ActivityKind::SnapshotRestored),
+    ),
+    (gettext_noop("Backups"), Some(ActivityKind::Backup)),
+    (gettext_noop("Cleanup"), Some(ActivityKind::Cleanup)),
+];
+
+/// Build a row for a single entry
+fn create_entry_row(entry: &ActivityEntry) -> adw::ActionRow {
+    let row = adw::ActionRow::new();
+    row.set_title(&glib::markup_escape_text(&format!(
+        "{}: {}",
+        entry.kind.label(),
+        entry.subject
+    )));
+
+    let mut subtitle = entry
+        .timestamp
+        .with_timezone(&chrono::Local)
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string();
+    if let Some(details) = &entry.details {
+        subtitle.push('\n');
+        subtitle.push_str(details);
+    }
+    row.set_subtitle(&glib::markup_escape_text(&subtitle));
+
+    let (icon_name, css_class, tooltip) = if entry.success {
+        ("emblem-ok-symbolic", "success", gettext("Succeeded"))
+    } else {
+        ("dialog-error-symbolic", "error", gettext("Failed"))
+    };
+    let icon = gtk::Image::from_icon_name(icon_name);
+    icon.add_css_class(css_class);
+    icon.set_tooltip_text(Some(&tooltip));
+    row.add_prefix(&icon);
+
+    row
+}
+
+/// Rebuild the list from the log (newest first)
+fn populate_list(list: &ListBox, entries: &[ActivityEntry], kind_idx: usize) {
+    while let Some(child) = list.first_child() {
+        list.remove(&child);
+    }
+
+    let kind = KIND_FILTERS.get(kind_idx).and_then(|(_, kind)| *kind);
+    let mut shown = 0;
+    for entry in entries
+        .iter()
+        .rev()
+        .filter(|e| kind.is_none_or(|kind| e.kind == kind))
+    {
+        list.append(&create_entry_row(entry));
+        shown += 1;
+    }
+
+    if shown == 0 {
+        let placeholder = adw::StatusPage::new();
+        placeholder.set_icon_name(Some("document-open-recent-symbolic"));
+        if entries.is_empty() {
+            placeholder.set_title(&gettext("No Activity Yet"));
+            placeholder.set_description(Some(&gettext(
+                "Snapshots created, deleted and restored, backups and automatic cleanups will appear here.",
+            )));
+        } else {
+            placeholder.set_title(&gettext("No Matching Activity"));
+            placeholder.set_description(Some(&gettext("Try another filter.")));
+        }
+        list.append(&placeholder);
+    }
+}
+
+/// Show the activity view
+pub fn show_activity_log_dialog(parent: &adw::ApplicationWindow) {
+    let dialog = adw::Window::new();
+    dialog.set_title(Some(&gettext("Activity")));
+    dialog.set_default_size(700, 600);
+    dialog.set_modal(true);
+    dialog.set_transient_for(Some(parent));
+
+    let content = gtk::Box::new(Orientation::Vertical, 0);
+
+    let header = adw::HeaderBar::new();
+    header.set_title_widget(Some(&adw::WindowTitle::new(&gettext("Activity"), "")));
+    let clear_button = gtk::Button::with_label(&gettext("Clear"));
+    clear_button.set_tooltip_text(Some(&gettext("Remove all entries")));
+    header.pack_end(&clear_button);
+    content.append(&header);
+
+    let filter_box = gtk::Box::new(Orientation::Horizontal, 12);
+    filter_box.set_margin_top(12);
+    filter_box.set_margin_start(12);
+    filter_box.set_margin_end(12);
+
+    let kind_labels: Vec<String> = KIND_FILTERS
+        .iter()
+        .map(|(label, _)| gettext(label))
+        .collect();
+    let kind_labels: Vec<&str> = kind_labels.iter().map(String::as_str).collect();
+
+    let kind_dropdown = gtk::DropDown::from_strings(&kind_labels);
+    kind_dropdown.set_tooltip_text(Some(&gettext("Filter by activity")));
+    filter_box.append(&kind_dropdown);
+
+    let count_label = Label::new(None);
+    count_label.set_hexpand(true);
+    count_label.set_halign(gtk::Align::End);
+    count_label.add_css_class("dim-label");
+    count_label.add_css_class("caption");
+    filter_box.append(&count_label);
+
+    content.append(&filter_box);
+
+    let list = ListBox::new();
+    list.set_selection_mode(gtk::SelectionMode::None);
+    list.add_css_class("boxed-list");
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_vexpand(true);
+    scrolled.set_margin_top(12);
+    scrolled.set_margin_bottom(12);
+    scrolled.set_margin_start(12);
+    scrolled.set_margin_end(12);
+    scrolled.set_child(Some(&list));
+    content.append(&scrolled);
+
+    dialog.set_content(Some(&content));
+
+    let activity_log = ActivityLog::new();
+    let entries = activity_log.load().unwrap_or_else(|e| {
+        log::warn!("Failed to load activity log: {e}");
+        Vec::new()
+    });
+    count_label.set_text(&ngettext_f(
+        "{count} entry",
+        "{count} entries",
+        entries.len() as u64,
+        &[("count", &entries.len().to_string())],
+    ));
+    clear_button.set_sensitive(!entries.is_empty());
+    populate_list(&list, &entries, 0);
+
+    let entries = Rc::new(RefCell::new(entries));
+    let list_clone = list.clone();
+    let entries_clone = entries.clone();
+    kind_dropdown.connect_selected_notify(move |dropdown| {
+        populate_list(&list_clone, &entries_clone.borrow(), dropdown.selected() as usize);
+    });
+
+    clear_button.connect_clicked(move |button| match activity_log.clear() {
+        Ok(()) => {
+            entries.borrow_mut().clear();
+            populate_list(&list, &[], kind_dropdown.selected() as usize);
+            count_label.set_text(&ngettext_f(
+                "{count} entry",
+                "{count} entries",
+                0,
+                &[("count", "0")],
+            ));
+
+            button.set_sensitive(false);
+        }
+        Err(e) => log::error!("Failed to clear activity log: {e}"),
+    });
+
+    dialog.present();
+}
@@ -3,19 +3,28 @@
 use adw::prelude::*;
 use chrono::Utc;
 use gtk::prelude::*;
-use gtk::{Label, Orientation};
+use gtk::{Label, Orientation, glib};
 use libadwaita as adw;
+use std::sync::mpsc;
+use waypoint_common::FilesystemStats;
+use waypoint_common::capabilities::{
+    FEATURE_BALANCE, FEATURE_DEDUP, FEATURE_METADATA_CHECK, FEATURE_SNAPSHOT_SHARING,
+};
 
+use super::dialogs;
 use crate::btrfs;
+use crate::dbus_client::WaypointHelperClient;
+use crate::i18n::{gettext, gettext_f, ngettext_f};
+use crate::operations::{self, OperationKind};
 use crate::snapshot::{Snapshot, format_bytes};
 
 /// Create empty state when no snapshots exist
 fn create_empty_state() -> adw::StatusPage {
     let status_page = adw::StatusPage::new();
-    status_page.set_title("No Snapshots Yet");
-    status_page.set_description(Some(
+    status_page.set_title(&gettext("No Snapshots Yet"));
+    status_page.set_description(Some(&gettext(
         "Create your first snapshot to see analytics and insights about your system backups.",
-    ));
+    )));
     status_page.set_icon_name(Some("folder-symbolic"));
     status_page.set_vexpand(true);
     status_page
@@ -94,9 +103,10 @@ pub fn show_analytics_dialog(
     parent: &adw::ApplicationWindow,
     snapshots: &[Snapshot],
     snapshot_manager: &std::rc::Rc<std::cell::RefCell<crate::snapshot::SnapshotManager>>,
+    on_metadata_changed: std::rc::Rc<dyn Fn()>,
 ) {
     let dialog = adw::Window::new();
-    dialog.set_title(Some("Analytics"));
+    dialog.set_title(Some(&gettext("Analytics")));
     dialog.set_default_size(700, 650);
     dialog.set_modal(true);
     dialog.set_transient_for(Some(parent));
@@ -105,7 +115,7 @@ pub fn show_analytics_dialog(
 
     // Header
     let header = adw::HeaderBar::new();
-    header.set_title_widget(Some(&adw::WindowTitle::new("Analytics", "")));
+    header.set_title_widget(Some(&adw::WindowTitle::new(&gettext("Analytics"), "")));
     content.append(&header);
 
     // Check for empty state
@@ -144,8 +154,11 @@ pub fn show_analytics_dialog(
     // Overview section
     main_box.append(&create_overview_section(&stats));
 
-    // Space usage section
-    main_box.append(&create_space_section(&stats));
+    // Space usage section, with the filesystem the snapshots live on
+    let filesystem = btrfs::get_filesystem_stats(&btrfs::snapshot_space_path())
+        .inspect_err(|e| log::warn!("Failed to get filesystem stats: {e}"))
+        .ok();
+    main_box.append(&create_space_section(&stats, filesystem.as_ref()));
 
     // Insights and recommendations
     main_box.append(&create_insights_section(&stats, snapshots, &snapshot_sizes));
@@ -157,6 +170,19 @@ pub fn show_analytics_dialog(
         stats.total_size,
     ));
 
+    // Manual maintenance actions
+    let has_metadata_check = crate::dbus_client::helper_supports(FEATURE_METADATA_CHECK);
+    let has_dedup = crate::dbus_client::helper_supports(FEATURE_DEDUP);
+    let has_balance = crate::dbus_client::helper_supports(FEATURE_BALANCE);
+    if has_metadata_check || has_dedup || has_balance {
+        main_box.append(&create_maintenance_section(
+            &dialog,
+            has_metadata_check.then_some(on_metadata_changed),
+            has_dedup,
+            has_balance,
+        ));
+    }
+
     clamp.set_child(Some(&main_box));
     scrolled.set_child(Some(&clamp));
     content.append(&scrolled);
@@ -241,29 +267,44 @@ fn calculate_statistics_with_sizes(
 /// Create overview section with basic stats
 fn create_overview_section(stats: &SnapshotStats) -> adw::PreferencesGroup {
     let group = adw::PreferencesGroup::new();
-    group.set_title("Overview");
+    group.set_title(&gettext("Overview"));
     group.set_margin_bottom(18);
 
     // Total snapshots
     let total_row = adw::ActionRow::new();
-    total_row.set_title("Total Snapshots");
+    total_row.set_title(&gettext("Total Snapshots"));
     total_row.add_suffix(&create_stat_label(&stats.total_count.to_string()));
     group.add(&total_row);
 
     // Oldest snapshot
     if let Some(days) = stats.oldest_age_days {
         let oldest_row = adw::ActionRow::new();
-        oldest_row.set_title("Oldest Snapshot");
+        oldest_row.set_title(&gettext("Oldest Snapshot"));
         let age_text = if days == 0 {
-            "Today".to_string()
-        } else if days == 1 {
-            "1 day ago".to_string()
+            gettext("Today")
         } else if days < 30 {
-            format!("{days} days ago")
+            ngettext_f(
+                "{count} day ago",
+                "{count} days ago",
+                days as u64,
+                &[("count", &days.to_string())],
+            )
         } else if days < 365 {
-            format!("{} months ago", days / 30)
+            let months = days / 30;
+            ngettext_f(
+                "{count} month ago",
+                "{count} months ago",
+                months as u64,
+                &[("count", &months.to_string())],
+            )
         } else {
-            format!("{} years ago", days / 365)
+            let years = days / 365;
+            ngettext_f(
+                "{count} year ago",
+                "{count} years ago",
+                years as u64,
+                &[("count", &years.to_string())],
+            )
         };
         oldest_row.add_suffix(&create_stat_label(&age_text));
         group.add(&oldest_row);
@@ -272,16 +313,23 @@ fn create_overview_section(stats: &SnapshotStats) -> adw::PreferencesGroup {
     // Newest snapshot
     if let Some(hours) = stats.newest_age_hours {
         let newest_row = adw::ActionRow::new();
-        newest_row.set_title("Newest Snapshot");
+        newest_row.set_title(&gettext("Newest Snapshot"));
         let age_text = if hours == 0 {
-            "Just now".to_string()
+            gettext("Just now")
         } else if hours < 24 {
-            format!("{} hour{} ago", hours, if hours == 1 { "" } else { "s" })
+            ngettext_f(
+                "{count} hour ago",
+                "{count} hours ago",
+                hours as u64,
+                &[("count", &hours.to_string())],
+            )
         } else {
-            format!(
-                "{} day{} ago",
-                hours / 24,
-                if hours / 24 == 1 { "" } else { "s" }
+            let days = hours / 24;
+            ngettext_f(
+                "{count} day ago",
+                "{count} days ago",
+                days as u64,
+                &[("count", &days.to_string())],
             )
         };
         newest_row.add_suffix(&create_stat_label(&age_text));
@@ -292,12 +340,15 @@ fn create_overview_section(stats: &SnapshotStats) -> adw::PreferencesGroup {
     if let Some(oldest_days) = stats.oldest_age_days {
         if oldest_days > 0 && stats.total_count > 1 {
             let freq_row = adw::ActionRow::new();
-            freq_row.set_title("Snapshot Frequency");
+            freq_row.set_title(&gettext("Snapshot Frequency"));
             let per_day = stats.total_count as f64 / oldest_days as f64;
             let freq_text = if per_day >= 1.0 {
-                format!("{per_day:.1} per day")
+                gettext_f("{count} per day", &[("count", &format!("{per_day:.1}"))])
             } else {
-                format!("1 per {:.0} days", 1.0 / per_day)
+                gettext_f(
+                    "1 per {days} days",
+                    &[("days", &format!("{:.0}", 1.0 / per_day))],
+                )
             };
             freq_row.add_suffix(&create_stat_label(&freq_text));
             group.add(&freq_row);
@@ -308,26 +359,129 @@ fn create_overview_section(stats: &SnapshotStats) -> adw::PreferencesGroup {
 }
 
 /// Create space usage section
-fn create_space_section(stats: &SnapshotStats) -> adw::PreferencesGroup {
+fn create_space_section(
+    stats: &SnapshotStats,
+    filesystem: Option<&FilesystemStats>,
+) -> adw::PreferencesGroup {
     let group = adw::PreferencesGroup::new();
-    group.set_title("Space Usage");
+    group.set_title(&gettext("Space Usage"));
     group.set_margin_bottom(18);
 
     // Total space used
     let total_row = adw::ActionRow::new();
-    total_row.set_title("Total Space Used");
+    total_row.set_title(&gettext("Total Space Used"));
     total_row.add_suffix(&create_stat_label(&format_bytes(stats.total_size)));
     group.add(&total_row);
 
     // Average snapshot size
     let avg_row = adw::ActionRow::new();
-    avg_row.set_title("Average Snapshot Size");
+    avg_row.set_title(&gettext("Average Snapshot Size"));
     avg_row.add_suffix(&create_stat_label(&format_bytes(stats.average_size)));
     group.add(&avg_row);
 
+    // Shared extents, which the sizes above count once per snapshot
+    if crate::dbus_client::helper_supports(FEATURE_SNAPSHOT_SHARING) {
+        group.add(&create_sharing_row());
+    }
+
+    let Some(filesystem) = filesystem else {
+        return group;
+    };
+
+    // Free space on the filesystem holding the snapshots
+    let free_row = adw::ActionRow::new();
+    free_row.set_title(&gettext("Filesystem Free Space"));
+    free_row.set_subtitle(&gettext_f(
+        "of {total}",
+        &[("total", &format_bytes(filesystem.total_bytes))],
+    ));
+    free_row.add_suffix(&create_stat_label(&format_bytes(
+        filesystem.available_bytes,
+    )));
+    group.add(&free_row);
+
+    if let Some(allocation) = &filesystem.btrfs {
+        let unallocated_row = adw::ActionRow::new();
+        unallocated_row.set_title(&gettext("Unallocated"));
+        unallocated_row.set_subtitle(&gettext(
+            "Space not yet assigned to data or metadata; new chunks need it",
+        ));
+        unallocated_row.add_suffix(&create_stat_label(&format_bytes(
+            allocation.unallocated_bytes,
+        )));
+        group.add(&unallocated_row);
+
+        let metadata_row = adw::ActionRow::new();
+        metadata_row.set_title(&gettext("Metadata"));
+        metadata_row.set_subtitle(&gettext_f(
+            "{used} used of {allocated} allocated",
+            &[
+                ("used", &format_bytes(allocation.metadata_used_bytes)),
+                ("allocated", &format_bytes(allocation.metadata_total_bytes)),
+            ],
+        ));
+        group.add(&metadata_row);
+    }
+
     group
 }
 
+/// Row comparing what snapshots reference with what they take up on disk
+///
+/// Loaded in the background since it walks every snapshot subvolume.
+fn create_sharing_row() -> adw::ActionRow {
+    let row = adw::ActionRow::new();
+    row.set_title(&gettext("Shared Data"));
+    row.set_subtitle(&gettext("Calculating…"));
+    row.set_subtitle_lines(4);
+
+    let value_label = create_stat_label("");
+    row.add_suffix(&value_label);
+
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let result = WaypointHelperClient::new().and_then(|client| client.get_snapshot_sharing());
+        let _ = tx.send(result);
+    });
+
+    let row_clone = row.clone();
+    glib::spawn_future_local(async move {
+        let result = loop {
+            match rx.try_recv() {
+                Ok(result) => break result,
+                Err(mpsc::TryRecvError::Empty) => {
+                    glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    break Err(anyhow::anyhow!("Sharing thread disconnected"));
+                }
+            }
+        };
+
+        match result {
+            Ok(sharing) if sharing.subvolumes > 0 => {
+                value_label.set_text(&format_bytes(sharing.shared_bytes()));
+                row_clone.set_subtitle(&gettext_f(
+                    "Snapshots reference {referenced} of data but only take up {exclusive} \
+                    thanks to shared extents. Deleting snapshots frees at most their own share, \
+                    which is why removing one often reclaims less than its size.",
+                    &[
+                        ("referenced", &format_bytes(sharing.referenced_sum)),
+                        ("exclusive", &format_bytes(sharing.exclusive)),
+                    ],
+                ));
+            }
+            Ok(_) => row_clone.set_subtitle(&gettext("No snapshots are tracked by quotas yet")),
+            Err(e) => {
+                log::warn!("Failed to get snapshot sharing: {e}");
+                row_clone.set_subtitle(&gettext("Enable quotas to see how much data is shared"));
+            }
+        }
+    });
+
+    row
+}
+
 /// Create largest snapshots section with visual size indicators (optimized)
 fn create_largest_snapshots_section(
     snapshots: &[Snapshot],
@@ -335,8 +489,10 @@ fn create_largest_snapshots_section(
     total_size: u64,
 ) -> adw::PreferencesGroup {
     let group = adw::PreferencesGroup::new();
-    group.set_title("Largest Snapshots");
-    group.set_description(Some("Top 5 snapshots consuming the most disk space"));
+    group.set_title(&gettext("Largest Snapshots"));
+    group.set_description(Some(&gettext(
+        "Top 5 snapshots consuming the most disk space",
+    )));
 
     // Build list with sizes from pre-calculated map
     let mut snapshots_with_sizes: Vec<(&Snapshot, u64)> = snapshots
@@ -364,10 +520,15 @@ fn create_largest_snapshots_section(
         row.set_title(&title_text);
 
         // Build subtitle
-        let subtitle = format!(
-            "{} • {} packages",
-            snapshot.format_timestamp(),
-            snapshot.package_count.unwrap_or(0)
+        let packages = snapshot.package_count.unwrap_or(0);
+        let subtitle = ngettext_f(
+            "{time} • {count} package",
+            "{time} • {count} packages",
+            packages as u64,
+            &[
+                ("time", &snapshot.format_timestamp()),
+                ("count", &packages.to_string()),
+            ],
         );
         row.set_subtitle(&subtitle);
 
@@ -428,7 +589,7 @@ fn create_insights_section(
     sizes: &std::collections::HashMap<String, u64>,
 ) -> adw::PreferencesGroup {
     let group = adw::PreferencesGroup::new();
-    group.set_title("Insights and Recommendations");
+    group.set_title(&gettext("Insights and Recommendations"));
     group.set_margin_bottom(18);
 
     let mut insights = Vec::new();
@@ -441,20 +602,26 @@ fn create_insights_section(
         if monthly_gb > 10.0 {
             // High growth - warning
             insights.push((
-                "High storage growth",
-                format!("Snapshots growing at {}/week (≈{}/month). Monitor disk space and consider adjusting retention policy.",
-                    format_bytes(growth as u64),
-                    format_bytes(monthly_growth as u64)),
-                "warning"
+                gettext("High storage growth"),
+                gettext_f(
+                    "Snapshots growing at {weekly}/week (≈{monthly}/month). Monitor disk space and consider adjusting retention policy.",
+                    &[
+                        ("weekly", &format_bytes(growth as u64)),
+                        ("monthly", &format_bytes(monthly_growth as u64)),
+                    ],
+                ),
+                "warning",
             ));
         } else if monthly_gb > 1.0 {
             // Moderate growth - informational
             insights.push((
-                "Steady growth",
-                format!(
-                    "Snapshots growing at {}/week (≈{}/month). Current growth rate is sustainable.",
-                    format_bytes(growth as u64),
-                    format_bytes(monthly_growth as u64)
+                gettext("Steady growth"),
+                gettext_f(
+                    "Snapshots growing at {weekly}/week (≈{monthly}/month). Current growth rate is sustainable.",
+                    &[
+                        ("weekly", &format_bytes(growth as u64)),
+                        ("monthly", &format_bytes(monthly_growth as u64)),
+                    ],
                 ),
                 "info",
             ));
@@ -462,9 +629,10 @@ fn create_insights_section(
     } else if stats.total_count > 1 {
         // No growth or negative growth
         insights.push((
-            "Stable storage usage",
-            "Snapshot sizes are consistent or decreasing. Your system footprint is well-managed."
-                .to_string(),
+            gettext("Stable storage usage"),
+            gettext(
+                "Snapshot sizes are consistent or decreasing. Your system footprint is well-managed.",
+            ),
             "success",
         ));
     }
@@ -472,26 +640,30 @@ fn create_insights_section(
     // Insight 2: Snapshot count management
     if stats.total_count > 50 {
         insights.push((
-            "Large snapshot count",
-            format!("You have {} snapshots. Consider adjusting retention policy to automatically clean up old snapshots.", stats.total_count),
-            "warning"
+            gettext("Large snapshot count"),
+            gettext_f(
+                "You have {count} snapshots. Consider adjusting retention policy to automatically clean up old snapshots.",
+                &[("count", &stats.total_count.to_string())],
+            ),
+            "warning",
         ));
     } else if stats.total_count > 20 && stats.total_count <= 50 {
         insights.push((
-            "Moderate snapshot count",
-            format!(
-                "{} snapshots stored. Your retention policy appears to be working well.",
-                stats.total_count
+            gettext("Moderate snapshot count"),
+            gettext_f(
+                "{count} snapshots stored. Your retention policy appears to be working well.",
+                &[("count", &stats.total_count.to_string())],
             ),
             "info",
         ));
     } else if stats.total_count <= 5 {
         insights.push((
-            "Few snapshots",
-            format!(
-                "Only {} snapshot{}. Consider enabling automated scheduling for regular backups.",
-                stats.total_count,
-                if stats.total_count == 1 { "" } else { "s" }
+            gettext("Few snapshots"),
+            ngettext_f(
+                "Only {count} snapshot. Consider enabling automated scheduling for regular backups.",
+                "Only {count} snapshots. Consider enabling automated scheduling for regular backups.",
+                stats.total_count as u64,
+                &[("count", &stats.total_count.to_string())],
             ),
             "info",
         ));
@@ -504,9 +676,12 @@ fn create_insights_section(
         let ratio = largest_size as f64 / stats.average_size as f64;
         if ratio > 3.0 {
             insights.push((
-                "Uneven snapshot sizes",
-                format!("Some snapshots are {}x larger than average. Check largest snapshots below to identify candidates for deletion.", ratio as u32),
-                "info"
+                gettext("Uneven snapshot sizes"),
+                gettext_f(
+                    "Some snapshots are {ratio}x larger than average. Check largest snapshots below to identify candidates for deletion.",
+                    &[("ratio", &(ratio as u32).to_string())],
+                ),
+                "info",
             ));
         }
     }
@@ -517,15 +692,20 @@ fn create_insights_section(
             let per_day = stats.total_count as f64 / oldest_days as f64;
             if per_day < 0.2 {
                 insights.push((
-                    "Infrequent snapshots",
-                    "Creating snapshots less than once per week. Enable automated scheduling for better system protection.".to_string(),
-                    "info"
+                    gettext("Infrequent snapshots"),
+                    gettext(
+                        "Creating snapshots less than once per week. Enable automated scheduling for better system protection.",
+                    ),
+                    "info",
                 ));
             } else if per_day > 3.0 {
                 insights.push((
-                    "Frequent snapshots",
-                    format!("Creating snapshots {per_day:.1}x per day. Ensure this frequency aligns with your backup strategy."),
-                    "info"
+                    gettext("Frequent snapshots"),
+                    gettext_f(
+                        "Creating snapshots {count}x per day. Ensure this frequency aligns with your backup strategy.",
+                        &[("count", &format!("{per_day:.1}"))],
+                    ),
+                    "info",
                 ));
             }
         }
@@ -534,8 +714,8 @@ fn create_insights_section(
     // Insight 5: Overall health status (only if no other insights)
     if insights.is_empty() {
         insights.push((
-            "Everything looks good",
-            "Your snapshot management is healthy. No issues detected.".to_string(),
+            gettext("Everything looks good"),
+            gettext("Your snapshot management is healthy. No issues detected."),
             "success",
         ));
     }
@@ -543,7 +723,7 @@ fn create_insights_section(
     // Add all insights to the group
     for (title, description, _level) in insights {
         let row = adw::ActionRow::new();
-        row.set_title(title);
+        row.set_title(&title);
         row.set_subtitle(&description);
         row.set_title_lines(2);
         row.set_subtitle_lines(3);
@@ -553,6 +733,516 @@ fn create_insights_section(
     group
 }
 
+/// Create the maintenance section with the metadata check, deduplication and balance actions
+///
+/// The metadata check is only offered with `on_metadata_changed`.
+fn create_maintenance_section(
+    dialog: &adw::Window,
+    on_metadata_changed: Option<std::rc::Rc<dyn Fn()>>,
+    dedup: bool,
+    balance: bool,
+) -> adw::PreferencesGroup {
+    let group = adw::PreferencesGroup::new();
+    group.set_title(&gettext("Maintenance"));
+    group.set_description(Some(&gettext(
+        "Manual actions that are never run automatically",
+    )));
+    group.set_margin_top(18);
+
+    if let Some(on_metadata_changed) = on_metadata_changed {
+        group.add(&create_metadata_check_row(dialog, on_metadata_changed));
+    }
+    if dedup {
+        group.add(&create_dedup_row(dialog));
+    }
+    if balance {
+        group.add(&create_balance_row(dialog));
+    }
+
+    group
+}
+
+/// Create the row for checking the snapshot metadata for conflicting entries
+fn create_metadata_check_row(
+    dialog: &adw::Window,
+    on_metadata_changed: std::rc::Rc<dyn Fn()>,
+) -> adw::ActionRow {
+    let row = adw::ActionRow::new();
+    row.set_title(&gettext("Check Snapshot Metadata"));
+    row.set_subtitle(&gettext(
+        "Find duplicate or mismatched entries left by interrupted operations",
+    ));
+    row.set_subtitle_lines(3);
+
+    let check_btn = gtk::Button::with_label(&gettext("Check…"));
+    check_btn.set_valign(gtk::Align::Center);
+    row.add_suffix(&check_btn);
+
+    let dialog = dialog.clone();
+    check_btn.connect_clicked(move |_| {
+        super::metadata_check_dialog::show_metadata_check_dialog(
+            &dialog,
+            on_metadata_changed.clone(),
+        );
+    });
+
+    row
+}
+
+/// Create the row for deduplicating snapshots
+fn create_dedup_row(dialog: &adw::Window) -> adw::ActionRow {
+    let row = adw::ActionRow::new();
+    row.set_title(&gettext("Deduplicate Snapshots"));
+    row.set_subtitle(&gettext(
+        "Share identical data between snapshots to reclaim space",
+    ));
+    row.set_subtitle_lines(3);
+
+    let progress = gtk::ProgressBar::new();
+    progress.set_valign(gtk::Align::Center);
+    progress.set_width_request(120);
+    progress.set_visible(false);
+    row.add_suffix(&progress);
+
+    let estimate_btn = gtk::Button::with_label(&gettext("Estimate"));
+    estimate_btn.set_valign(gtk::Align::Center);
+    estimate_btn.set_tooltip_text(Some(&gettext("Estimate how much space could be reclaimed")));
+    row.add_suffix(&estimate_btn);
+
+    let dedup_btn = gtk::Button::with_label(&gettext("Deduplicate…"));
+    dedup_btn.set_valign(gtk::Align::Center);
+    row.add_suffix(&dedup_btn);
+
+    let row_clone = row.clone();
+    let dedup_btn_clone = dedup_btn.clone();
+    estimate_btn.connect_clicked(move |btn| {
+        btn.set_sensitive(false);
+        dedup_btn_clone.set_sensitive(false);
+        row_clone.set_subtitle(&gettext("Estimating potential savings…"));
+
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = WaypointHelperClient::new().and_then(|client| client.estimate_dedup_savings());
+            let _ = tx.send(result);
+        });
+
+        let btn = btn.clone();
+        let row = row_clone.clone();
+        let dedup_btn = dedup_btn_clone.clone();
+        glib::spawn_future_local(async move {
+            let result = loop {
+                match rx.try_recv() {
+                    Ok(result) => break result,
+                    Err(mpsc::TryRecvError::Empty) => {
+                        glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                    }
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        break Err(anyhow::anyhow!("Estimate thread disconnected"));
+                    }
+                }
+            };
+
+            btn.set_sensitive(true);
+            dedup_btn.set_sensitive(true);
+            match result {
+                Ok(estimate) => row.set_subtitle(&gettext_f(
+                    "Up to {exclusive} of {total} across {count} snapshots is not yet shared and \
+                    could be reclaimed. Actual savings depend on how much of it is identical.",
+                    &[
+                        ("exclusive", &format_bytes(estimate.exclusive_bytes)),
+                        ("total", &format_bytes(estimate.total_bytes)),
+                        ("count", &estimate.snapshot_count.to_string()),
+                    ],
+                )),
+                Err(e) => {
+                    log::error!("Failed to estimate deduplication savings: {e}");
+                    row.set_subtitle(&gettext_f(
+                        "Could not estimate savings: {error}",
+                        &[("error", &e.to_string())],
+                    ));
+                }
+            }
+        });
+    });
+
+    let dialog = dialog.clone();
+    let row_for_dedup = row.clone();
+    dedup_btn.connect_clicked(move |btn| {
+        let confirm = adw::MessageDialog::builder()
+            .transient_for(&dialog)
+            .heading(gettext("Deduplicate Snapshots?"))
+            .body(gettext(
+                "Waypoint will scan every snapshot with duperemove and share identical data \
+                between them. This can take a long time and causes heavy disk activity.\n\n\
+                Snapshot contents are not changed.",
+            ))
+            .build();
+        confirm.add_response("cancel", &gettext("Cancel"));
+        confirm.add_response("dedup", &gettext("Deduplicate"));
+        confirm.set_response_appearance("dedup", adw::ResponseAppearance::Suggested);
+        confirm.set_default_response(Some("cancel"));
+        confirm.set_close_response("cancel");
+
+        let btn = btn.clone();
+        let estimate_btn = estimate_btn.clone();
+        let row = row_for_dedup.clone();
+        let progress = progress.clone();
+        let dialog = dialog.clone();
+        confirm.connect_response(None, move |_, response| {
+            if response != "dedup" {
+                return;
+            }
+            run_deduplication(&dialog, &row, &progress, &[&btn, &estimate_btn]);
+        });
+        confirm.present();
+    });
+
+    row
+}
+
+/// Run deduplication in the background, showing progress in the row
+fn run_deduplication(
+    dialog: &adw::Window,
+    row: &adw::ActionRow,
+    progress: &gtk::ProgressBar,
+    buttons: &[&gtk::Button],
+) {
+    for button in buttons {
+        button.set_sensitive(false);
+    }
+    progress.set_fraction(0.0);
+    progress.set_visible(true);
+    row.set_subtitle(&gettext("Starting deduplication…"));
+    let operation = operations::start(OperationKind::Dedup, gettext("Deduplicate snapshots"));
+
+    let (progress_tx, progress_rx) = mpsc::channel::<(u64, u64, String)>();
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let result = WaypointHelperClient::new().and_then(|client| {
+            client.deduplicate_snapshots(move |done, total, stage| {
+                let _ = progress_tx.send((done, total, stage));
+            })
+        });
+        let _ = tx.send(result);
+    });
+
+    let dialog = dialog.clone();
+    let row = row.clone();
+    let progress = progress.clone();
+    let buttons: Vec<gtk::Button> = buttons.iter().map(|b| (*b).clone()).collect();
+    glib::spawn_future_local(async move {
+        let result = loop {
+            if let Some((done, total, stage)) = progress_rx.try_iter().last() {
+                let action = match stage.as_str() {
+                    "scanning" => gettext("Scanning files"),
+                    "deduplicating" => gettext("Deduplicating extents"),
+                    _ => gettext("Finishing"),
+                };
+                if total > 0 {
+                    let status = gettext_f(
+                        "{action}… {done} of {total}",
+                        &[
+                            ("action", &action),
+                            ("done", &done.to_string()),
+                            ("total", &total.to_string()),
+                        ],
+                    );
+                    progress.set_fraction(done as f64 / total as f64);
+                    row.set_subtitle(&status);
+                    operations::update(operation, Some(done as f64 / total as f64), status);
+                } else {
+                    progress.pulse();
+                    row.set_subtitle(&format!("{action}…"));
+                    operations::update(operation, None, format!("{action}…"));
+                }
+            }
+
+            match rx.try_recv() {
+                Ok(result) => break result,
+                Err(mpsc::TryRecvError::Empty) => {
+                    glib::timeout_future(std::time::Duration::from_millis(200)).await;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    break Err(anyhow::anyhow!("Deduplication thread disconnected"));
+                }
+            }
+        };
+
+        progress.set_visible(false);
+        for button in &buttons {
+            button.set_sensitive(true);
+        }
+
+        match result {
+            Ok((_message, reclaimed)) => {
+                row.set_subtitle(&gettext_f(
+                    "Last run reclaimed {size}",
+                    &[("size", &format_bytes(reclaimed))],
+                ));
+                operations::succeed(
+                    operation,
+                    gettext_f("Reclaimed {size}", &[("size", &format_bytes(reclaimed))]),
+                );
+            }
+            Err(e) => {
+                log::error!("Deduplication failed: {e}");
+                operations::fail(operation, e.to_string());
+                row.set_subtitle(&gettext("Deduplication failed"));
+                dialogs::show_error_window(
+                    &dialog,
+                    &gettext("Deduplication Failed"),
+                    &e.to_string(),
+                );
+            }
+        }
+    });
+}
+
+/// Create the row for balancing the snapshot filesystem
+fn create_balance_row(dialog: &adw::Window) -> adw::ActionRow {
+    let row = adw::ActionRow::new();
+    row.set_title(&gettext("Balance Filesystem"));
+    row.set_subtitle(&gettext(
+        "Compact partly used chunks to return space to the unallocated pool. \
+        Fixes \"No space left\" errors while free space is still reported.",
+    ));
+    row.set_subtitle_lines(3);
+
+    let progress = gtk::ProgressBar::new();
+    progress.set_valign(gtk::Align::Center);
+    progress.set_width_request(120);
+    progress.set_visible(false);
+    row.add_suffix(&progress);
+
+    // Only chunks at most this full are rewritten; lower is faster
+    let usage_spin = gtk::SpinButton::with_range(1.0, 100.0, 5.0);
+    usage_spin.set_value(25.0);
+    usage_spin.set_valign(gtk::Align::Center);
+    usage_spin.set_tooltip_text(Some(&gettext(
+        "Only rewrite chunks at most this percent full",
+    )));
+    usage_spin.update_property(&[gtk::accessible::Property::Label(&gettext(
+        "Usage filter in percent",
+    ))]);
+    row.add_suffix(&usage_spin);
+
+    let cancel_btn = gtk::Button::with_label(&gettext("Cancel"));
+    cancel_btn.set_valign(gtk::Align::Center);
+    cancel_btn.set_visible(false);
+    row.add_suffix(&cancel_btn);
+
+    let balance_btn = gtk::Button::with_label(&gettext("Balance…"));
+    balance_btn.set_valign(gtk::Align::Center);
+    row.add_suffix(&balance_btn);
+
+    let widgets = BalanceWidgets {
+        row: row.clone(),
+        progress,
+        usage_spin,
+        cancel_btn: cancel_btn.clone(),
+        balance_btn: balance_btn.clone(),
+    };
+
+    // Pick up a balance that is already running, e.g. started from another window
+    watch_balance(dialog, &widgets);
+
+    let dialog_clone = dialog.clone();
+    let widgets_clone = widgets.clone();
+    balance_btn.connect_clicked(move |_| {
+        let usage = widgets_clone.usage_spin.value() as u32;
+        let confirm = adw::MessageDialog::builder()
+            .transient_for(&dialog_clone)
+            .heading(gettext("Balance Filesystem?"))
+            .body(gettext_f(
+                "Waypoint will rewrite data and metadata chunks that are at most {usage}% full. \
+                Balancing causes heavy disk activity and can take a long time on large \
+                filesystems; the system stays usable but may be slower.\n\n\
+                It runs in the background and can be cancelled at any time.",
+                &[("usage", &usage.to_string())],
+            ))
+            .build();
+        confirm.add_response("cancel", &gettext("Cancel"));
+        confirm.add_response("balance", &gettext("Balance"));
+        confirm.set_response_appearance("balance", adw::ResponseAppearance::Suggested);
+        confirm.set_default_response(Some("cancel"));
+        confirm.set_close_response("cancel");
+
+        let dialog = dialog_clone.clone();
+        let widgets = widgets_clone.clone();
+        confirm.connect_response(None, move |_, response| {
+            if response != "balance" {
+                return;
+            }
+            widgets.set_running(true);
+            widgets.row.set_subtitle(&gettext("Starting balance…"));
+
+            let (tx, rx) = mpsc::channel();
+            std::thread::spawn(move || {
+                let result = WaypointHelperClient::new().and_then(|client| client.start_balance(usage));
+                let _ = tx.send(result);
+            });
+
+            let dialog = dialog.clone();
+            let widgets = widgets.clone();
+            glib::spawn_future_local(async move {
+                let result = loop {
+                    match rx.try_recv() {
+                        Ok(result) => break result,
+                        Err(mpsc::TryRecvError::Empty) => {
+                            glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                        }
+                        Err(mpsc::TryRecvError::Disconnected) => {
+                            break Err(anyhow::anyhow!("Balance thread disconnected"));
+                        }
+                    }
+                };
+
+                match result {
+                    Ok(_) => watch_balance(&dialog, &widgets),
+                    Err(e) => {
+                        log::error!("Failed to start balance: {e}");
+                        widgets.set_running(false);
+                        widgets
+                            .row
+                            .set_subtitle(&gettext("Balance could not be started"));
+                        dialogs::show_error_window(
+                            &dialog,
+                            &gettext("Balance Failed"),
+                            &e.to_string(),
+                        );
+                    }
+                }
+            });
+        });
+        confirm.present();
+    });
+
+    cancel_btn.connect_clicked(move |btn| {
+        btn.set_sensitive(false);
+        row.set_subtitle(&gettext("Cancelling after the current chunk…"));
+        std::thread::spawn(|| {
+            if let Err(e) = WaypointHelperClient::new().and_then(|client| client.cancel_balance()) {
+                log::warn!("Failed to cancel balance: {e}");
+            }
+        });
+    });
+
+    widgets.row
+}
+
+/// Widgets of the balance row that change while a balance runs
+#[derive(Clone)]
+struct BalanceWidgets {
+    row: adw::ActionRow,
+    progress: gtk::ProgressBar,
+    usage_spin: gtk::SpinButton,
+    cancel_btn: gtk::Button,
+    balance_btn: gtk::Button,
+}
+
+impl BalanceWidgets {
+    fn set_running(&self, running: bool) {
+        self.progress.set_visible(running);
+        self.cancel_btn.set_visible(running);
+        if !running {
+            self.cancel_btn.set_sensitive(true);
+        }
+        self.balance_btn.set_sensitive(!running);
+        self.usage_spin.set_sensitive(!running);
+    }
+}
+
+/// Poll the balance status until no balance is running, updating the row
+///
+/// Stops quietly when the dialog is closed.
+fn watch_balance(dialog: &adw::Window, widgets: &BalanceWidgets) {
+    let dialog = dialog.clone();
+    let widgets = widgets.clone();
+    glib::spawn_future_local(async move {
+        let mut seen_running = false;
+        loop {
+            let (tx, rx) = mpsc::channel();
+            std::thread::spawn(move || {
+                let result = WaypointHelperClient::new().and_then(|client| client.get_balance_status());
+                let _ = tx.send(result);
+            });
+            let result = loop {
+                match rx.try_recv() {
+                    Ok(result) => break result,
+                    Err(mpsc::TryRecvError::Empty) => {
+                        glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                    }
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        break Err(anyhow::anyhow!("Balance status thread disconnected"));
+                    }
+                }
+            };
+
+            if !dialog.is_visible() {
+                return;
+            }
+
+            let status = match result {
+                Ok(status) => status,
+                Err(e) => {
+                    log::warn!("Failed to get balance status: {e}");
+                    widgets.set_running(false);
+                    return;
+                }
+            };
+
+            if !status.running {
+                widgets.set_running(false);
+                if seen_running {
+                    match status.last_result.as_deref() {
+                        Some("complete") => {
+                            widgets.row.set_subtitle(&gettext("Last balance completed"))
+                        }
+                        Some("cancelled") => widgets
+                            .row
+                            .set_subtitle(&gettext("Last balance was cancelled")),
+                        Some(failure) => {
+                            widgets.row.set_subtitle(&gettext("Last balance failed"));
+                            dialogs::show_error_window(
+                                &dialog,
+                                &gettext("Balance Failed"),
+                                failure,
+                            );
+                        }
+                        None => widgets.row.set_subtitle(&gettext("Balance finished")),
+                    }
+                }
+                return;
+            }
+
+            seen_running = true;
+            widgets.set_running(true);
+            if status.chunks_total > 0 {
+                widgets.progress.set_fraction(
+                    (status.chunks_balanced as f64 / status.chunks_total as f64).min(1.0),
+                );
+            } else {
+                widgets.progress.pulse();
+            }
+            let state = if status.paused {
+                gettext("Balance paused")
+            } else {
+                gettext("Balancing")
+            };
+            widgets.row.set_subtitle(&gettext_f(
+                "{state}… {done} of about {total} chunks",
+                &[
+                    ("state", &state),
+                    ("done", &status.chunks_balanced.to_string()),
+                    ("total", &status.chunks_total.to_string()),
+                ],
+            ));
+
+            glib::timeout_future(std::time::Duration::from_secs(2)).await;
+        }
+    });
+}
+
 /// Create a styled stat label
 fn create_stat_label(text: &str) -> Label {
     let label = Label::new(Some(text));
@@ -0,0 +1,295 @@
+//! Audit log viewer showing privileged operations recorded by the helper
+
+use adw::prelude::*;
+use gtk::prelude::*;
+use gtk::{Label, ListBox, Orientation, glib};
+use libadwaita as adw;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc;
+use waypoint_common::audit::AuditEvent;
+
+use crate::dbus_client::WaypointHelperClient;
+use crate::i18n::{gettext, gettext_f, gettext_noop, ngettext_f};
+
+/// Maximum number of events fetched from the helper
+const MAX_EVENTS: u32 = 1000;
+
+/// Operation filter choices: (label, matching operations; empty = any)
+const OPERATION_FILTERS: &[(&str, &[&str])] = &[
+    (gettext_noop("All operations"), &[]),
+    (gettext_noop("Create"), &["create_snapshot"]),
+    (gettext_noop("Delete"), &["delete_snapshot"]),
+    (
+        gettext_noop("Restore"),
+        &["restore_snapshot", "restore_to_subvolume"],
+    ),
+    (gettext_noop("Configuration"), &["modify_configuration"]),
+];
+
+/// Outcome filter choices
+const OUTCOME_FILTERS: &[&str] = &[
+    gettext_noop("All outcomes"),
+    gettext_noop("Success"),
+    gettext_noop("Failure"),
+    gettext_noop("Denied"),
+];
+
+/// Check whether an event passes the selected filters
+fn matches_filters(event: &AuditEvent, operation_idx: usize, outcome_idx: usize) -> bool {
+    let operation_match = OPERATION_FILTERS
+        .get(operation_idx)
+        .is_none_or(|(_, ops)| ops.is_empty() || ops.contains(&event.operation.as_str()));
+
+    let outcome_match = match outcome_idx {
+        1 => event.result == "success",
+        2 => event.result == "failure",
+        3 => event.result == "denied",
+        _ => true,
+    };
+
+    operation_match && outcome_match
+}
+
+/// Human-readable operation name
+fn operation_label(operation: &str) -> String {
+    match operation {
+        "create_snapshot" => gettext("Create snapshot"),
+        "delete_snapshot" => gettext("Delete snapshot"),
+        "restore_snapshot" => gettext("Restore snapshot"),
+        "restore_to_subvolume" => gettext("Restore to new subvolume"),
+        "modify_configuration" => gettext("Change configuration"),
+        "deduplicate_snapshots" => gettext("Deduplicate snapshots"),
+        "balance_filesystem" => gettext("Balance filesystem"),
+        "set_read_only" => gettext("Change read-only state"),
+        other => other.replace('_', " "),
+    }
+}
+
+/// Format an RFC 3339 timestamp in local time
+fn format_timestamp(timestamp: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .map(|dt| {
+            dt.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_else(|_| timestamp.to_string())
+}
+
+/// Build a row for a single audit event
+fn create_event_row(event: &AuditEvent) -> adw::ActionRow {
+    let row = adw::ActionRow::new();
+
+    // Denied events carry the Polkit action as operation and "authorization" as resource
+    let title = if event.result == "denied" {
+        gettext_f(
+            "Authorization denied: {operation}",
+            &[("operation", &event.operation)],
+        )
+    } else {
+        format!("{}: {}", operation_label(&event.operation), event.resource)
+    };
+    row.set_title(&glib::markup_escape_text(&title));
+
+    let mut subtitle = gettext_f(
+        "{time}  •  {user} (pid {pid})",
+        &[
+            ("time", &format_timestamp(&event.timestamp)),
+            ("user", event.user_display()),
+            ("pid", &event.process_id.to_string()),
+        ],
+    );
+    if let Some(details) = &event.details {
+        subtitle.push('\n');
+        subtitle.push_str(details);
+    }
+    row.set_subtitle(&glib::markup_escape_text(&subtitle));
+
+    let (icon_name, css_class, tooltip) = match event.result.as_str() {
+        "success" => ("emblem-ok-symbolic", "success", gettext("Succeeded")),
+        "denied" => (
+            "action-unavailable-symbolic",
+            "warning",
+            gettext("Authorization denied"),
+        ),
+        _ => ("dialog-error-symbolic", "error", gettext("Failed")),
+    };
+    let icon = gtk::Image::from_icon_name(icon_name);
+    icon.add_css_class(css_class);
+    icon.set_tooltip_text(Some(&tooltip));
+    row.add_prefix(&icon);
+
+    row
+}
+
+/// Rebuild the list from the loaded events and current filters (newest first)
+fn populate_list(list: &ListBox, events: &[AuditEvent], operation_idx: usize, outcome_idx: usize) {
+    while let Some(child) = list.first_child() {
+        list.remove(&child);
+    }
+
+    let mut shown = 0;
+    for event in events
+        .iter()
+        .rev()
+        .filter(|e| matches_filters(e, operation_idx, outcome_idx))
+    {
+        list.append(&create_event_row(event));
+        shown += 1;
+    }
+
+    if shown == 0 {
+        let placeholder = adw::StatusPage::new();
+        placeholder.set_icon_name(Some("document-open-recent-symbolic"));
+        if events.is_empty() {
+            placeholder.set_title(&gettext("No Audit Events"));
+            placeholder.set_description(Some(&gettext(
+                "Privileged operations such as creating, deleting and restoring snapshots will appear here.",
+            )));
+        } else {
+            placeholder.set_title(&gettext("No Matching Events"));
+            placeholder.set_description(Some(&gettext("Try adjusting the filters.")));
+        }
+        list.append(&placeholder);
+    }
+}
+
+/// Show the audit log viewer
+pub fn show_audit_log_dialog(parent: &adw::ApplicationWindow) {
+    let dialog = adw::Window::new();
+    dialog.set_title(Some(&gettext("Audit Log")));
+    dialog.set_default_size(700, 600);
+    dialog.set_modal(true);
+    dialog.set_transient_for(Some(parent));
+
+    let content = gtk::Box::new(Orientation::Vertical, 0);
+
+    // Header
+    let header = adw::HeaderBar::new();
+    header.set_title_widget(Some(&adw::WindowTitle::new(&gettext("Audit Log"), "")));
+    content.append(&header);
+
+    // Filters
+    let filter_box = gtk::Box::new(Orientation::Horizontal, 12);
+    filter_box.set_margin_top(12);
+    filter_box.set_margin_start(12);
+    filter_box.set_margin_end(12);
+
+    let operation_labels: Vec<String> = OPERATION_FILTERS
+        .iter()
+        .map(|(label, _)| gettext(label))
+        .collect();
+    let operation_labels: Vec<&str> = operation_labels.iter().map(String::as_str).collect();
+    let operation_dropdown = gtk::DropDown::from_strings(&operation_labels);
+    operation_dropdown.set_tooltip_text(Some(&gettext("Filter by operation")));
+    filter_box.append(&operation_dropdown);
+
+    let outcome_labels: Vec<String> = OUTCOME_FILTERS.iter().map(|label| gettext(label)).collect();
+    let outcome_labels: Vec<&str> = outcome_labels.iter().map(String::as_str).collect();
+    let outcome_dropdown = gtk::DropDown::from_strings(&outcome_labels);
+    outcome_dropdown.set_tooltip_text(Some(&gettext("Filter by outcome")));
+    filter_box.append(&outcome_dropdown);
+
+    let count_label = Label::new(None);
+    count_label.set_hexpand(true);
+    count_label.set_halign(gtk::Align::End);
+    count_label.add_css_class("dim-label");
+    count_label.add_css_class("caption");
+    filter_box.append(&count_label);
+
+    content.append(&filter_box);
+
+    // Event list
+    let list = ListBox::new();
+    list.set_selection_mode(gtk::SelectionMode::None);
+    list.add_css_class("boxed-list");
+
+    let loading = adw::StatusPage::new();
+    loading.set_title(&gettext("Loading Audit Log…"));
+    let spinner = gtk::Spinner::new();
+    spinner.set_spinning(true);
+    loading.set_child(Some(&spinner));
+    list.append(&loading);
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_vexpand(true);
+    scrolled.set_margin_top(12);
+    scrolled.set_margin_bottom(12);
+    scrolled.set_margin_start(12);
+    scrolled.set_margin_end(12);
+    scrolled.set_child(Some(&list));
+    content.append(&scrolled);
+
+    dialog.set_content(Some(&content));
+    dialog.present();
+
+    let events: Rc<RefCell<Vec<AuditEvent>>> = Rc::new(RefCell::new(Vec::new()));
+
+    // Re-filter when either dropdown changes
+    let refilter = {
+        let list = list.clone();
+        let events = events.clone();
+        let operation_dropdown = operation_dropdown.clone();
+        let outcome_dropdown = outcome_dropdown.clone();
+        Rc::new(move || {
+            populate_list(
+                &list,
+                &events.borrow(),
+                operation_dropdown.selected() as usize,
+                outcome_dropdown.selected() as usize,
+            );
+        })
+    };
+
+    let refilter_clone = refilter.clone();
+    operation_dropdown.connect_selected_notify(move |_| refilter_clone());
+    let refilter_clone = refilter.clone();
+    outcome_dropdown.connect_selected_notify(move |_| refilter_clone());
+
+    // Load events in background (may prompt for authentication)
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let result = WaypointHelperClient::new().and_then(|client| client.get_audit_log(MAX_EVENTS));
+        let _ = tx.send(result);
+    });
+
+    glib::spawn_future_local(async move {
+        let result = loop {
+            match rx.try_recv() {
+                Ok(result) => break result,
+                Err(mpsc::TryRecvError::Empty) => {
+                    glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    break Err(anyhow::anyhow!("Audit log thread disconnected"));
+                }
+            }
+        };
+
+        match result {
+            Ok(loaded) => {
+                count_label.set_text(&ngettext_f(
+                    "{count} event",
+                    "{count} events",
+                    loaded.len() as u64,
+                    &[("count", &loaded.len().to_string())],
+                ));
+
+                *events.borrow_mut() = loaded;
+                refilter();
+            }
+            Err(e) => {
+                log::error!("Failed to load audit log: {e}");
+                while let Some(child) = list.first_child() {
+                    list.remove(&child);
+                }
+                let error_page = adw::StatusPage::new();
+                error_page.set_icon_name(Some("dialog-error-symbolic"));
+                error_page.set_title(&gettext("Could Not Load Audit Log"));
+                error_page.set_description(Some(&e.to_string()));
+                list.append(&error_page);
+            }
+        }
+    });
+}
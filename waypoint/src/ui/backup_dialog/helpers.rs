@@ -2,3 +2,37 @@
 
 // Re-export formatting utilities from waypoint_common
 pub use waypoint_common::{format_bytes, format_elapsed_time};
+
+/// Name of the snapshot a listed backup holds
+///
+/// Image backups are `<snapshot>.img` files, other backups are directories
+/// named after the snapshot.
+pub fn backup_snapshot_id(backup_path: &str) -> &str {
+    let path = std::path::Path::new(backup_path);
+    if path.extension().is_some_and(|ext| ext == "img") {
+        path.file_stem()
+    } else {
+        path.file_name()
+    }
+    .and_then(|n| n.to_str())
+    .unwrap_or(backup_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_snapshot_id() {
+        assert_eq!(
+            backup_snapshot_id("/run/media/user/drive/waypoint-backups/pre-upgrade"),
+            "pre-upgrade"
+        );
+        assert_eq!(
+            backup_snapshot_id("/run/media/user/drive/waypoint-backups/weekly.img"),
+            "weekly"
+        );
+        assert_eq!(backup_snapshot_id("pre-upgrade"), "pre-upgrade");
+        assert_eq!(backup_snapshot_id(""), "");
+    }
+}
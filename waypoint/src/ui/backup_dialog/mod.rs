@@ -13,13 +13,15 @@ use libadwaita as adw;
 use super::dialogs;
 use crate::backup_manager::BackupManager;
 use crate::dbus_client::WaypointHelperClient;
+use crate::i18n::{gettext, gettext_f, ngettext_f};
 use std::cell::RefCell;
-use std::path::Path;
 use std::rc::Rc;
+use waypoint_common::capabilities::{FEATURE_COMPARE_BACKUP, FEATURE_IMAGE_BACKUP};
+use waypoint_common::{BackupComparison, HelperError};
 
 // Re-export and use types from submodules
 use types::{BackupDestination, DriveType, VerificationResults};
-use helpers::{format_bytes, format_elapsed_time};
+use helpers::{backup_snapshot_id, format_bytes, format_elapsed_time};
 
 /// Create the backups content page
 pub fn create_backups_content(
@@ -44,8 +46,8 @@ pub fn create_backups_content(
 
     // Destinations section
     let dest_group = adw::PreferencesGroup::new();
-    dest_group.set_title("Backup Destinations");
-    dest_group.set_description(Some("Available external drives for backups"));
+    dest_group.set_title(&gettext("Backup Destinations"));
+    dest_group.set_description(Some(&gettext("Available external drives for backups")));
     dest_group.set_margin_top(18);
 
     // Destinations list container
@@ -62,7 +64,7 @@ pub fn create_backups_content(
 
         if let Some(button) = btn {
             button.set_sensitive(false);
-            button.set_label("Scanning...");
+            button.set_label(&gettext("Scanning..."));
         }
 
         let btn_opt = btn.cloned();
@@ -87,12 +89,12 @@ pub fn create_backups_content(
                     Err(std::sync::mpsc::TryRecvError::Disconnected) => {
                         dialogs::show_error(
                             &parent_ref,
-                            "Scan Failed",
-                            "Scan thread disconnected unexpectedly",
+                            &gettext("Scan Failed"),
+                            &gettext("Scan thread disconnected unexpectedly"),
                         );
                         if let Some(btn) = btn_opt {
                             btn.set_sensitive(true);
-                            btn.set_label("Scan");
+                            btn.set_label(&gettext("Scan"));
                         }
                         return;
                     }
@@ -101,7 +103,7 @@ pub fn create_backups_content(
 
             if let Some(btn) = btn_opt.as_ref() {
                 btn.set_sensitive(true);
-                btn.set_label("Scan");
+                btn.set_label(&gettext("Scan"));
             }
 
             // Clear existing destinations
@@ -148,14 +150,14 @@ pub fn create_backups_content(
                     if merged_destinations.is_empty() {
                         // Enhanced empty state with actionable guidance
                         let empty_status = adw::StatusPage::new();
-                        empty_status.set_title("No Backup Drives Found");
-                        empty_status.set_description(Some(
+                        empty_status.set_title(&gettext("No Backup Drives Found"));
+                        empty_status.set_description(Some(&gettext(
                             "To get started with backups:\n\n\
                              1. Connect an external drive (USB, network, or internal)\n\
                              2. Format it with btrfs for incremental backups (recommended)\n\
                              3. Or use NTFS/exFAT for compatibility with other systems\n\
                              4. Click 'Scan' below to detect the drive"
-                        ));
+                        )));
                         empty_status.set_icon_name(Some("drive-harddisk-symbolic"));
 
                         dest_list.add(&empty_status);
@@ -200,8 +202,11 @@ pub fn create_backups_content(
                 Err(e) => {
                     dialogs::show_error(
                         &parent_ref,
-                        "Scan Failed",
-                        &format!("Failed to scan for destinations: {e}"),
+                        &gettext("Scan Failed"),
+                        &gettext_f(
+                            "Failed to scan for destinations: {error}",
+                            &[("error", &e.to_string())],
+                        ),
                     );
                 }
             }
@@ -263,12 +268,12 @@ pub fn create_backups_content(
 
     // Pending backups section
     let pending_group = adw::PreferencesGroup::new();
-    pending_group.set_title("Pending Backups");
+    pending_group.set_title(&gettext("Pending Backups"));
     pending_group.set_margin_top(18);
 
     // Status summary row
     let status_row = adw::ActionRow::new();
-    status_row.set_title("Queue Status");
+    status_row.set_title(&gettext("Queue Status"));
     update_backup_status_summary(&status_row, backup_manager.clone());
     pending_group.add(&status_row);
 
@@ -305,13 +310,15 @@ pub fn create_backups_content(
 
     // Settings section
     let settings_group = adw::PreferencesGroup::new();
-    settings_group.set_title("Backup Settings");
+    settings_group.set_title(&gettext("Backup Settings"));
     settings_group.set_margin_top(18);
 
     // Mount check interval setting
     let interval_row = adw::ActionRow::new();
-    interval_row.set_title("Mount Check Interval");
-    interval_row.set_subtitle("How often to check for newly mounted backup drives (in seconds)");
+    interval_row.set_title(&gettext("Mount Check Interval"));
+    interval_row.set_subtitle(&gettext(
+        "How often to check for newly mounted backup drives (in seconds)",
+    ));
 
     let current_interval = backup_manager
         .borrow()
@@ -357,7 +364,7 @@ fn scan_destinations() -> anyhow::Result<Vec<BackupDestination>> {
     let (success, result) = client.scan_backup_destinations()?;
 
     if !success {
-        return Err(anyhow::anyhow!(result));
+        return Err(HelperError::decode(&result).into());
     }
 
     // Parse JSON response
@@ -396,7 +403,9 @@ fn create_destination_row(
     parent: &adw::ApplicationWindow,
     backup_manager: Rc<RefCell<BackupManager>>,
 ) -> adw::ExpanderRow {
-    use waypoint_common::{BackupDestinationConfig, BackupFilter};
+    use waypoint_common::{
+        BackupDestinationConfig, BackupFilter, BackupFormat, FullDestinationAction,
+    };
 
     let row = adw::ExpanderRow::new();
 
@@ -416,9 +425,9 @@ fn create_destination_row(
 
     // Add drive type badge to title
     let type_badge = match dest.drive_type {
-        DriveType::Removable => "USB",
-        DriveType::Network => "Network",
-        DriveType::Internal => "Internal",
+        DriveType::Removable => gettext("USB"),
+        DriveType::Network => gettext("Network"),
+        DriveType::Internal => gettext("Internal"),
     };
 
     row.set_title(&display_name);
@@ -449,9 +458,15 @@ fn create_destination_row(
 
     // Status indicator (colored circle with text)
     if is_connected {
-        subtitle_parts.push("<span foreground=\"#26a269\">●</span> Connected".to_string());
+        subtitle_parts.push(format!(
+            "<span foreground=\"#26a269\">●</span> {}",
+            gettext("Connected")
+        ));
     } else {
-        subtitle_parts.push("<span foreground=\"#9a9996\">●</span> Disconnected".to_string());
+        subtitle_parts.push(format!(
+            "<span foreground=\"#9a9996\">●</span> {}",
+            gettext("Disconnected")
+        ));
     }
 
     // Drive type and filesystem
@@ -459,14 +474,22 @@ fn create_destination_row(
 
     // Backup count (if available)
     if let Some(count) = backup_count {
-        subtitle_parts.push(format!("{} backup{}", count, if count == 1 { "" } else { "s" }));
+        subtitle_parts.push(ngettext_f(
+            "{count} backup",
+            "{count} backups",
+            count as u64,
+            &[("count", &count.to_string())],
+        ));
     }
 
     // Pending count
     if let Some(ref uuid) = dest.uuid {
         let pending_count = backup_manager.borrow().get_pending_count(uuid);
         if pending_count > 0 {
-            subtitle_parts.push(format!("{pending_count} pending"));
+            subtitle_parts.push(gettext_f(
+                "{count} pending",
+                &[("count", &pending_count.to_string())],
+            ));
         }
     }
 
@@ -491,7 +514,7 @@ fn create_destination_row(
 
     // Get current configuration if UUID exists
     let uuid = dest.uuid.clone();
-    let (is_enabled, current_filter, on_snapshot_creation, on_drive_mount) =
+    let (is_enabled, current_filter, on_snapshot_creation, on_drive_mount, on_favorite, on_schedule, schedule_interval_hours) =
         if let Some(ref uuid) = uuid {
             let config = backup_manager.borrow().get_config().unwrap_or_default();
             if let Some(dest_config) = config.get_destination(uuid) {
@@ -500,12 +523,15 @@ fn create_destination_row(
                     dest_config.filter.clone(),
                     dest_config.on_snapshot_creation,
                     dest_config.on_drive_mount,
+                    dest_config.on_favorite,
+                    dest_config.on_schedule,
+                    dest_config.schedule_interval_hours,
                 )
             } else {
-                (false, BackupFilter::All, true, true)
+                (false, BackupFilter::All, true, true, false, false, 24)
             }
         } else {
-            (false, BackupFilter::All, true, true)
+            (false, BackupFilter::All, true, true, false, false, 24)
         };
 
     // Add enable switch
@@ -524,8 +550,10 @@ fn create_destination_row(
 
         // Backup filter selector
         let filter_row = adw::ActionRow::new();
-        filter_row.set_title("Backup Filter");
-        filter_row.set_subtitle("Choose which snapshots to backup to this destination");
+        filter_row.set_title(&gettext("Backup Filter"));
+        filter_row.set_subtitle(&gettext(
+            "Choose which snapshots to backup to this destination",
+        ));
 
         // Get all filter options and create labels
         let filter_options = BackupFilter::all_options();
@@ -554,8 +582,10 @@ fn create_destination_row(
 
         // Auto-backup on snapshot creation toggle
         let on_creation_row = adw::ActionRow::new();
-        on_creation_row.set_title("Backup on Snapshot Creation");
-        on_creation_row.set_subtitle("Automatically queue backups when new snapshots are created");
+        on_creation_row.set_title(&gettext("Backup on Snapshot Creation"));
+        on_creation_row.set_subtitle(&gettext(
+            "Automatically queue backups when new snapshots are created",
+        ));
 
         let on_creation_switch = gtk::Switch::new();
         on_creation_switch.set_active(on_snapshot_creation);
@@ -566,8 +596,10 @@ fn create_destination_row(
 
         // Auto-backup on drive mount toggle
         let on_mount_row = adw::ActionRow::new();
-        on_mount_row.set_title("Backup on Drive Mount");
-        on_mount_row.set_subtitle("Automatically process backups when this drive is connected");
+        on_mount_row.set_title(&gettext("Backup on Drive Mount"));
+        on_mount_row.set_subtitle(&gettext(
+            "Automatically process backups when this drive is connected",
+        ));
 
         let on_mount_switch = gtk::Switch::new();
         on_mount_switch.set_active(on_drive_mount);
@@ -576,13 +608,51 @@ fn create_destination_row(
 
         row.add_row(&on_mount_row);
 
+        // Auto-backup on favorite toggle
+        let on_favorite_row = adw::ActionRow::new();
+        on_favorite_row.set_title(&gettext("Backup Favorited Snapshots"));
+        on_favorite_row.set_subtitle(&gettext(
+            "Queue a backup when a snapshot is marked as favorite",
+        ));
+
+        let on_favorite_switch = gtk::Switch::new();
+        on_favorite_switch.set_active(on_favorite);
+        on_favorite_switch.set_valign(gtk::Align::Center);
+        on_favorite_row.add_suffix(&on_favorite_switch);
+
+        row.add_row(&on_favorite_row);
+
+        // Scheduled backup toggle and interval
+        let on_schedule_row = adw::ActionRow::new();
+        on_schedule_row.set_title(&gettext("Scheduled Backups"));
+        on_schedule_row.set_subtitle(&gettext(
+            "Back up matching snapshots at a regular interval while connected",
+        ));
+
+        let on_schedule_switch = gtk::Switch::new();
+        on_schedule_switch.set_active(on_schedule);
+        on_schedule_switch.set_valign(gtk::Align::Center);
+        on_schedule_row.add_suffix(&on_schedule_switch);
+
+        row.add_row(&on_schedule_row);
+
+        let interval_row = adw::SpinRow::with_range(1.0, 24.0 * 30.0, 1.0);
+        interval_row.set_title(&gettext("Backup Interval (hours)"));
+        interval_row.set_value(schedule_interval_hours.max(1) as f64);
+        interval_row.set_sensitive(on_schedule);
+        on_schedule_switch
+            .bind_property("active", &interval_row, "sensitive")
+            .build();
+
+        row.add_row(&interval_row);
+
         // Rename row
         let rename_row = adw::ActionRow::new();
-        rename_row.set_title("Drive Nickname");
-        rename_row.set_subtitle("Optional custom name for this drive");
+        rename_row.set_title(&gettext("Drive Nickname"));
+        rename_row.set_subtitle(&gettext("Optional custom name for this drive"));
 
         let nickname_entry = gtk::Entry::new();
-        nickname_entry.set_placeholder_text(Some("e.g., My Work Backup"));
+        nickname_entry.set_placeholder_text(Some(&gettext("e.g., My Work Backup")));
         nickname_entry.set_valign(gtk::Align::Center);
         nickname_entry.set_width_chars(20);
 
@@ -605,8 +675,10 @@ fn create_destination_row(
 
         // Retention policy row
         let retention_row = adw::ActionRow::new();
-        retention_row.set_title("Backup Retention");
-        retention_row.set_subtitle("How long to keep backups before automatic cleanup");
+        retention_row.set_title(&gettext("Backup Retention"));
+        retention_row.set_subtitle(&gettext(
+            "How long to keep backups before automatic cleanup",
+        ));
 
         // Load current retention setting
         let current_retention_days = if let Some(ref uuid_val) = uuid {
@@ -620,15 +692,16 @@ fn create_destination_row(
 
         // Create retention dropdown with presets
         let retention_options = [
-            "Keep Forever",
-            "1 Week (7 days)",
-            "2 Weeks (14 days)",
-            "1 Month (30 days)",
-            "2 Months (60 days)",
-            "3 Months (90 days)",
-            "6 Months (180 days)",
-            "1 Year (365 days)",
+            gettext("Keep Forever"),
+            gettext("1 Week (7 days)"),
+            gettext("2 Weeks (14 days)"),
+            gettext("1 Month (30 days)"),
+            gettext("2 Months (60 days)"),
+            gettext("3 Months (90 days)"),
+            gettext("6 Months (180 days)"),
+            gettext("1 Year (365 days)"),
         ];
+        let retention_options: Vec<&str> = retention_options.iter().map(String::as_str).collect();
         let retention_dropdown = gtk::DropDown::from_strings(&retention_options);
 
         // Set current selection based on retention_days
@@ -649,21 +722,105 @@ fn create_destination_row(
         retention_row.add_suffix(&retention_dropdown);
         row.add_row(&retention_row);
 
+        // What to do when a backup doesn't fit; deleting backups must be chosen explicitly
+        let when_full = uuid
+            .as_ref()
+            .and_then(|uuid_val| {
+                let config = backup_manager.borrow().get_config().ok()?;
+                config.get_destination(uuid_val).map(|d| d.when_full)
+            })
+            .unwrap_or_default();
+
+        let when_full_row = adw::ActionRow::new();
+        when_full_row.set_title(&gettext("When Drive Is Full"));
+        when_full_row.set_subtitle(&gettext(
+            "Deleting old backups keeps the latest one and those within the retention period",
+        ));
+
+        let when_full_options = [
+            gettext("Skip and Notify"),
+            gettext("Ask First"),
+            gettext("Delete Oldest Backups"),
+        ];
+        let when_full_options: Vec<&str> = when_full_options.iter().map(String::as_str).collect();
+        let when_full_dropdown = gtk::DropDown::from_strings(&when_full_options);
+        when_full_dropdown.set_selected(match when_full {
+            FullDestinationAction::Skip => 0,
+            FullDestinationAction::Ask => 1,
+            FullDestinationAction::Prune => 2,
+        });
+        when_full_dropdown.set_valign(gtk::Align::Center);
+
+        when_full_row.add_suffix(&when_full_dropdown);
+        row.add_row(&when_full_row);
+
+        // Verification after transfer, on by default for removable drives
+        let removable = dest.drive_type == DriveType::Removable;
+        let verify_after_backup = uuid
+            .as_ref()
+            .and_then(|uuid_val| {
+                let config = backup_manager.borrow().get_config().ok()?;
+                config
+                    .get_destination(uuid_val)
+                    .map(|d| d.verifies_after_backup(removable))
+            })
+            .unwrap_or(removable);
+
+        let verify_after_row = adw::ActionRow::new();
+        verify_after_row.set_title(&gettext("Verify After Backup"));
+        verify_after_row.set_subtitle(&gettext(
+            "Check each automatic backup against its snapshot once it's transferred",
+        ));
+
+        let verify_after_switch = gtk::Switch::new();
+        verify_after_switch.set_active(verify_after_backup);
+        verify_after_switch.set_valign(gtk::Align::Center);
+        verify_after_row.add_suffix(&verify_after_switch);
+
+        row.add_row(&verify_after_row);
+
+        // Image files instead of received subvolumes or copied directories
+        let image_backup = uuid
+            .as_ref()
+            .and_then(|uuid_val| {
+                let config = backup_manager.borrow().get_config().ok()?;
+                config.get_destination(uuid_val).map(|d| d.format)
+            })
+            .unwrap_or_default()
+            == BackupFormat::Image;
+
+        let image_row = adw::ActionRow::new();
+        image_row.set_title(&gettext("Image Backups"));
+        image_row.set_subtitle(
+            &gettext("Write each snapshot to a self-contained Btrfs image file, for archiving or burning to optical media"),
+        );
+
+        let image_switch = gtk::Switch::new();
+        image_switch.set_active(image_backup);
+        image_switch.set_valign(gtk::Align::Center);
+        image_row.add_suffix(&image_switch);
+
+        // Older helpers can't write images
+        if image_backup || crate::dbus_client::helper_supports(FEATURE_IMAGE_BACKUP) {
+            row.add_row(&image_row);
+        }
+
         // View backups button row
         let view_row = adw::ActionRow::new();
-        view_row.set_title("View Existing Backups");
+        view_row.set_title(&gettext("View Existing Backups"));
 
         if !is_connected {
-            view_row.set_subtitle("Drive must be connected to view backups");
+            view_row.set_subtitle(&gettext("Drive must be connected to view backups"));
         }
 
-        let view_button = Button::with_label("View");
+        let view_button = Button::with_label(&gettext("View"));
         view_button.set_valign(gtk::Align::Center);
         view_button.set_sensitive(is_connected); // Disable if not connected
         let dest_mount = dest.mount_point.clone();
         let parent_clone = parent.clone();
+        let backup_manager_view = backup_manager.clone();
         view_button.connect_clicked(move |_| {
-            show_backups_list_dialog(&parent_clone, &dest_mount);
+            show_backups_list_dialog(&parent_clone, &dest_mount, backup_manager_view.clone());
         });
         view_row.add_suffix(&view_button);
 
@@ -671,15 +828,15 @@ fn create_destination_row(
 
         // Verify backups button row
         let verify_row = adw::ActionRow::new();
-        verify_row.set_title("Verify Backup Integrity");
+        verify_row.set_title(&gettext("Verify Backup Integrity"));
 
         if !is_connected {
-            verify_row.set_subtitle("Drive must be connected to verify backups");
+            verify_row.set_subtitle(&gettext("Drive must be connected to verify backups"));
         } else {
-            verify_row.set_subtitle("Check if backups are intact and readable");
+            verify_row.set_subtitle(&gettext("Check if backups are intact and readable"));
         }
 
-        let verify_button = Button::with_label("Verify All");
+        let verify_button = Button::with_label(&gettext("Verify All"));
         verify_button.set_valign(gtk::Align::Center);
         verify_button.set_sensitive(is_connected); // Disable if not connected
 
@@ -687,7 +844,7 @@ fn create_destination_row(
         let parent_verify = parent.clone();
         verify_button.connect_clicked(move |btn| {
             btn.set_sensitive(false);
-            btn.set_label("Verifying...");
+            btn.set_label(&gettext("Verifying..."));
 
             let btn_clone = btn.clone();
             let mount_clone = dest_mount_verify.clone();
@@ -705,7 +862,7 @@ fn create_destination_row(
             gtk::glib::spawn_future_local(async move {
                 if let Ok(result) = receiver.recv().await {
                     btn_clone.set_sensitive(true);
-                    btn_clone.set_label("Verify All");
+                    btn_clone.set_label(&gettext("Verify All"));
                     show_verification_results_dialog(&parent_clone, result);
                 }
             });
@@ -716,10 +873,10 @@ fn create_destination_row(
 
         // Forget destination button row
         let forget_row = adw::ActionRow::new();
-        forget_row.set_title("Forget This Destination");
-        forget_row.set_subtitle("Remove this drive from backup destinations");
+        forget_row.set_title(&gettext("Forget This Destination"));
+        forget_row.set_subtitle(&gettext("Remove this drive from backup destinations"));
 
-        let forget_button = Button::with_label("Forget");
+        let forget_button = Button::with_label(&gettext("Forget"));
         forget_button.set_valign(gtk::Align::Center);
         forget_button.add_css_class("destructive-action");
 
@@ -733,9 +890,9 @@ fn create_destination_row(
 
                 dialogs::show_confirmation(
                     &parent_forget,
-                    "Forget Destination?",
-                    "This will remove this backup destination from the configuration. Existing backups on the drive will not be deleted.",
-                    "Forget",
+                    &gettext("Forget Destination?"),
+                    &gettext("This will remove this backup destination from the configuration. Existing backups on the drive will not be deleted."),
+                    &gettext("Forget"),
                     true,
                     move || {
                         if let Err(e) = bm_clone.borrow().remove_destination(&uuid_clone) {
@@ -764,8 +921,14 @@ fn create_destination_row(
                 let filter_dd = filter_combo.clone();
                 let on_creation_sw = on_creation_switch.clone();
                 let on_mount_sw = on_mount_switch.clone();
+                let on_favorite_sw = on_favorite_switch.clone();
+                let on_schedule_sw = on_schedule_switch.clone();
+                let interval_spin = interval_row.clone();
                 let nickname_ent = nickname_entry.clone();
                 let retention_dd = retention_dropdown.clone();
+                let when_full_dd = when_full_dropdown.clone();
+                let verify_after_sw = verify_after_switch.clone();
+                let image_sw = image_switch.clone();
                 let parent_window = parent.clone();
 
                 move || {
@@ -807,14 +970,31 @@ fn create_destination_row(
                         filter,
                         on_snapshot_creation: on_creation_sw.is_active(),
                         on_drive_mount: on_mount_sw.is_active(),
+                        on_favorite: on_favorite_sw.is_active(),
+                        on_schedule: on_schedule_sw.is_active(),
+                        schedule_interval_hours: interval_spin.value() as u32,
                         retention_days,
+                        verify_after_backup: Some(verify_after_sw.is_active()),
+                        format: if image_sw.is_active() {
+                            BackupFormat::Image
+                        } else {
+                            BackupFormat::Native
+                        },
+                        when_full: match when_full_dd.selected() {
+                            1 => FullDestinationAction::Ask,
+                            2 => FullDestinationAction::Prune,
+                            _ => FullDestinationAction::Skip,
+                        },
                     };
 
                     if let Err(e) = bm.borrow().add_destination(uuid.clone(), dest_config) {
                         log::error!("Failed to save backup configuration: {e}");
-                        dialogs::show_toast(&parent_window, "Failed to save backup settings");
+                        dialogs::show_toast(
+                            &parent_window,
+                            &gettext("Failed to save backup settings"),
+                        );
                     } else {
-                        dialogs::show_toast(&parent_window, "Backup settings saved");
+                        dialogs::show_toast(&parent_window, &gettext("Backup settings saved"));
                     }
                 }
             };
@@ -843,12 +1023,47 @@ fn create_destination_row(
                 save_clone();
             });
 
+            // Connect on_favorite switch
+            let save_clone = save_config.clone();
+            on_favorite_switch.connect_active_notify(move |_| {
+                save_clone();
+            });
+
+            // Connect on_schedule switch and interval
+            let save_clone = save_config.clone();
+            on_schedule_switch.connect_active_notify(move |_| {
+                save_clone();
+            });
+
+            let save_clone = save_config.clone();
+            interval_row.connect_value_notify(move |_| {
+                save_clone();
+            });
+
             // Connect retention dropdown
             let save_clone = save_config.clone();
             retention_dropdown.connect_selected_notify(move |_| {
                 save_clone();
             });
 
+            // Connect full drive dropdown
+            let save_clone = save_config.clone();
+            when_full_dropdown.connect_selected_notify(move |_| {
+                save_clone();
+            });
+
+            // Connect verify after backup switch
+            let save_clone = save_config.clone();
+            verify_after_switch.connect_active_notify(move |_| {
+                save_clone();
+            });
+
+            // Connect image backup switch
+            let save_clone = save_config.clone();
+            image_switch.connect_active_notify(move |_| {
+                save_clone();
+            });
+
             // Connect nickname entry (save on focus out or Enter key)
             nickname_entry.connect_activate(move |_| {
                 save_config();
@@ -860,9 +1075,13 @@ fn create_destination_row(
 }
 
 /// Show dialog listing backups at a destination
-fn show_backups_list_dialog(parent: &adw::ApplicationWindow, destination_mount: &str) {
+fn show_backups_list_dialog(
+    parent: &adw::ApplicationWindow,
+    destination_mount: &str,
+    backup_manager: Rc<RefCell<BackupManager>>,
+) {
     let dialog = adw::Window::new();
-    dialog.set_title(Some("Backups"));
+    dialog.set_title(Some(&gettext("Backups")));
     dialog.set_modal(true);
     dialog.set_transient_for(Some(parent));
     dialog.set_default_size(600, 500);
@@ -871,11 +1090,14 @@ fn show_backups_list_dialog(parent: &adw::ApplicationWindow, destination_mount:
 
     // Header
     let header = adw::HeaderBar::new();
-    header.set_title_widget(Some(&adw::WindowTitle::new("Backups", destination_mount)));
+    header.set_title_widget(Some(&adw::WindowTitle::new(
+        &gettext("Backups"),
+        destination_mount,
+    )));
     content.append(&header);
 
     // Loading state
-    let loading_label = Label::new(Some("Loading backups..."));
+    let loading_label = Label::new(Some(&gettext("Loading backups...")));
     loading_label.set_vexpand(true);
     loading_label.add_css_class("dim-label");
     content.append(&loading_label);
@@ -885,6 +1107,7 @@ fn show_backups_list_dialog(parent: &adw::ApplicationWindow, destination_mount:
 
     // Load backups in background
     let dest_mount = destination_mount.to_string();
+    let dest_mount_rows = dest_mount.clone();
     let dialog_clone = dialog.clone();
     let content_clone = content.clone();
     let parent_clone = parent.clone();
@@ -910,8 +1133,8 @@ fn show_backups_list_dialog(parent: &adw::ApplicationWindow, destination_mount:
                     dialog_clone.close();
                     dialogs::show_error(
                         &parent_clone,
-                        "Load Failed",
-                        "List backups thread disconnected unexpectedly",
+                        &gettext("Load Failed"),
+                        &gettext("List backups thread disconnected unexpectedly"),
                     );
                     return;
                 }
@@ -925,8 +1148,9 @@ fn show_backups_list_dialog(parent: &adw::ApplicationWindow, destination_mount:
             Ok(backups) => {
                 if backups.is_empty() {
                     let empty_status = adw::StatusPage::new();
-                    empty_status.set_title("No Backups");
-                    empty_status.set_description(Some("No backups found at this destination"));
+                    empty_status.set_title(&gettext("No Backups"));
+                    empty_status
+                        .set_description(Some(&gettext("No backups found at this destination")));
                     empty_status.set_icon_name(Some("folder-symbolic"));
                     empty_status.set_vexpand(true);
                     content_clone.append(&empty_status);
@@ -945,10 +1169,7 @@ fn show_backups_list_dialog(parent: &adw::ApplicationWindow, destination_mount:
                         let row = adw::ActionRow::new();
 
                         // Extract snapshot name from path
-                        let name = std::path::Path::new(backup_path)
-                            .file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or(backup_path);
+                        let name = backup_snapshot_id(backup_path);
 
                         row.set_title(name);
                         row.set_subtitle(backup_path);
@@ -964,13 +1185,14 @@ fn show_backups_list_dialog(parent: &adw::ApplicationWindow, destination_mount:
                         delete_btn.set_valign(gtk::Align::Center);
                         delete_btn.add_css_class("flat");
                         delete_btn.add_css_class("destructive-action");
-                        delete_btn.set_tooltip_text(Some("Delete this backup"));
+                        delete_btn.set_tooltip_text(Some(&gettext("Delete this backup")));
 
                         let backup_path_clone = backup_path.clone();
                         let parent_clone2 = parent_clone.clone();
                         let row_clone = row.clone();
                         let list_box_clone = list_box.clone();
                         let content_clone2 = content_clone.clone();
+                        let backup_manager_row = backup_manager.clone();
                         delete_btn.connect_clicked(move |_| {
                             show_delete_backup_confirmation(
                                 &parent_clone2,
@@ -978,9 +1200,30 @@ fn show_backups_list_dialog(parent: &adw::ApplicationWindow, destination_mount:
                                 &list_box_clone,
                                 &content_clone2,
                                 &backup_path_clone,
+                                backup_manager_row.clone(),
                             );
                         });
 
+                        if crate::dbus_client::helper_supports(FEATURE_COMPARE_BACKUP) {
+                            let compare_btn = Button::new();
+                            compare_btn.set_icon_name("view-dual-symbolic");
+                            compare_btn.set_valign(gtk::Align::Center);
+                            compare_btn.add_css_class("flat");
+                            compare_btn.set_tooltip_text(Some(&gettext("Compare with source")));
+
+                            let parent_compare = parent_clone.clone();
+                            let dest_mount_compare = dest_mount_rows.clone();
+                            let snapshot_id = name.to_string();
+                            compare_btn.connect_clicked(move |_| {
+                                compare_backup_with_source(
+                                    &parent_compare,
+                                    &dest_mount_compare,
+                                    &snapshot_id,
+                                );
+                            });
+                            row.add_suffix(&compare_btn);
+                        }
+
                         row.add_suffix(&delete_btn);
                         list_box.append(&row);
                     }
@@ -993,14 +1236,191 @@ fn show_backups_list_dialog(parent: &adw::ApplicationWindow, destination_mount:
                 dialog_clone.close();
                 dialogs::show_error(
                     &parent_clone,
-                    "Load Failed",
-                    &format!("Failed to list backups: {e}"),
+                    &gettext("Load Failed"),
+                    &gettext_f(
+                        "Failed to list backups: {error}",
+                        &[("error", &e.to_string())],
+                    ),
                 );
             }
         }
     });
 }
 
+/// Compare a backup with its source snapshot and show the differences
+fn compare_backup_with_source(
+    parent: &adw::ApplicationWindow,
+    destination_mount: &str,
+    snapshot_id: &str,
+) {
+    let snapshot_path = crate::btrfs::snapshot_path(snapshot_id);
+    if !waypoint_common::permissions::exists_or_hidden(&snapshot_path) {
+        dialogs::show_error(
+            parent,
+            &gettext("Nothing to Compare"),
+            &gettext_f(
+                "Snapshot '{name}' no longer exists on this system, so its backup can't be compared with it.",
+                &[("name", snapshot_id)],
+            ),
+        );
+        return;
+    }
+
+    let progress_dialog = adw::MessageDialog::new(
+        Some(parent),
+        Some(&gettext("Comparing Backup")),
+        Some(&gettext(
+            "Checking the backup against the snapshot. Large snapshots can take a while...",
+        )),
+    );
+    progress_dialog.present();
+
+    let destination_mount = destination_mount.to_string();
+    let snapshot_id = snapshot_id.to_string();
+    let snapshot_id_clone = snapshot_id.clone();
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let result = WaypointHelperClient::new().and_then(|client| {
+            client.compare_backup(
+                snapshot_path.to_string_lossy().to_string(),
+                destination_mount,
+                snapshot_id_clone,
+            )
+        });
+        let _ = tx.send(result);
+    });
+
+    let parent = parent.clone();
+    gtk::glib::spawn_future_local(async move {
+        let result = loop {
+            match rx.try_recv() {
+                Ok(result) => break result,
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    gtk::glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                    continue;
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    progress_dialog.close();
+                    dialogs::show_error(
+                        &parent,
+                        &gettext("Comparison Failed"),
+                        &gettext("Comparison thread disconnected unexpectedly"),
+                    );
+                    return;
+                }
+            }
+        };
+
+        progress_dialog.close();
+
+        match result {
+            Ok(comparison) => show_comparison_results_dialog(&parent, &snapshot_id, &comparison),
+            Err(e) => dialogs::show_error(&parent, &gettext("Comparison Failed"), &e.to_string()),
+        }
+    });
+}
+
+/// Show the differences found between a backup and its source snapshot
+fn show_comparison_results_dialog(
+    parent: &adw::ApplicationWindow,
+    snapshot_id: &str,
+    comparison: &BackupComparison,
+) {
+    let dialog = adw::Window::new();
+    dialog.set_title(Some(&gettext("Backup Comparison")));
+    dialog.set_modal(true);
+    dialog.set_transient_for(Some(parent));
+    dialog.set_default_size(600, 500);
+
+    let content = gtk::Box::new(Orientation::Vertical, 0);
+
+    let header = adw::HeaderBar::new();
+    header.set_title_widget(Some(&adw::WindowTitle::new(
+        &gettext("Backup Comparison"),
+        snapshot_id,
+    )));
+    content.append(&header);
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_vexpand(true);
+
+    let results_box = gtk::Box::new(Orientation::Vertical, 12);
+    results_box.set_margin_top(12);
+    results_box.set_margin_bottom(12);
+    results_box.set_margin_start(12);
+    results_box.set_margin_end(12);
+
+    let summary_group = adw::PreferencesGroup::new();
+    let summary_row = adw::ActionRow::new();
+    if comparison.matches() {
+        summary_row.set_title(&gettext("✓ The backup matches the snapshot"));
+        summary_row.add_css_class("success");
+    } else {
+        let total: usize = comparison
+            .subvolumes
+            .iter()
+            .map(|subvolume| subvolume.difference_count)
+            .sum();
+        summary_row.set_title(&ngettext_f(
+            "⚠ {count} difference found",
+            "⚠ {count} differences found",
+            total as u64,
+            &[("count", &total.to_string())],
+        ));
+        summary_row.set_subtitle(&gettext(
+            "The backup has drifted from the snapshot; back it up again to bring it up to date",
+        ));
+        summary_row.add_css_class("warning");
+    }
+    summary_group.add(&summary_row);
+    results_box.append(&summary_group);
+
+    for subvolume in &comparison.subvolumes {
+        let group = adw::PreferencesGroup::new();
+        group.set_title(&subvolume.subvolume);
+        group.set_description(Some(&if subvolume.method == "received-uuid" {
+            gettext("Checked by received UUID")
+        } else {
+            gettext("Checked file by file")
+        }));
+
+        if subvolume.differences.is_empty() {
+            let row = adw::ActionRow::new();
+            row.set_title(&gettext("✓ No differences"));
+            group.add(&row);
+        } else {
+            let expander = adw::ExpanderRow::new();
+            expander.set_title(&ngettext_f(
+                "{count} difference",
+                "{count} differences",
+                subvolume.difference_count as u64,
+                &[("count", &subvolume.difference_count.to_string())],
+            ));
+            if subvolume.difference_count > subvolume.differences.len() {
+                expander.set_subtitle(&gettext_f(
+                    "Showing the first {count}",
+                    &[("count", &subvolume.differences.len().to_string())],
+                ));
+            }
+            for difference in &subvolume.differences {
+                let row = adw::ActionRow::new();
+                row.set_title(&gtk::glib::markup_escape_text(difference));
+                row.set_title_lines(2);
+                expander.add_row(&row);
+            }
+            group.add(&expander);
+        }
+
+        results_box.append(&group);
+    }
+
+    scrolled.set_child(Some(&results_box));
+    content.append(&scrolled);
+
+    dialog.set_content(Some(&content));
+    dialog.present();
+}
+
 /// Show confirmation dialog before deleting a backup
 fn show_delete_backup_confirmation(
     parent: &adw::ApplicationWindow,
@@ -1008,6 +1428,7 @@ fn show_delete_backup_confirmation(
     list_box: &gtk::ListBox,
     content_box: &gtk::Box,
     backup_path: &str,
+    backup_manager: Rc<RefCell<BackupManager>>,
 ) {
     // Extract backup name for display
     let backup_name = std::path::Path::new(backup_path)
@@ -1017,15 +1438,15 @@ fn show_delete_backup_confirmation(
 
     let dialog = adw::MessageDialog::new(
         Some(parent),
-        Some("Delete Backup?"),
-        Some(&format!(
-            "Are you sure you want to delete the backup '{}'?\n\nThis action cannot be undone.",
-            backup_name
+        Some(&gettext("Delete Backup?")),
+        Some(&gettext_f(
+            "Are you sure you want to delete the backup '{name}'?\n\nThis action cannot be undone.",
+            &[("name", backup_name)],
         )),
     );
 
-    dialog.add_response("cancel", "Cancel");
-    dialog.add_response("delete", "Delete");
+    dialog.add_response("cancel", &gettext("Cancel"));
+    dialog.add_response("delete", &gettext("Delete"));
     dialog.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
     dialog.set_default_response(Some("cancel"));
     dialog.set_close_response("cancel");
@@ -1042,6 +1463,8 @@ fn show_delete_backup_confirmation(
         if response == "delete" {
             // Perform deletion
             let backup_path_clone = backup_path.clone();
+            let backup_path_record = backup_path.clone();
+            let backup_manager = backup_manager.clone();
             let parent_clone2 = parent_clone.clone();
             let row_clone2 = row_clone.clone();
             let list_box_clone2 = list_box_clone.clone();
@@ -1050,8 +1473,8 @@ fn show_delete_backup_confirmation(
             // Show progress
             let progress_dialog = adw::MessageDialog::new(
                 Some(&parent_clone),
-                Some("Deleting Backup"),
-                Some("Please wait while the backup is being deleted..."),
+                Some(&gettext("Deleting Backup")),
+                Some(&gettext("Please wait while the backup is being deleted...")),
             );
             progress_dialog.present();
 
@@ -1083,8 +1506,8 @@ fn show_delete_backup_confirmation(
                             progress_dialog.close();
                             dialogs::show_error(
                                 &parent_clone2,
-                                "Delete Failed",
-                                "Delete thread disconnected unexpectedly",
+                                &gettext("Delete Failed"),
+                                &gettext("Delete thread disconnected unexpectedly"),
                             );
                             return;
                         }
@@ -1096,6 +1519,11 @@ fn show_delete_backup_confirmation(
                 match result {
                     Ok((success, message)) => {
                         if success {
+                            // Other backups of the snapshot keep it marked as backed up
+                            if let Err(e) = backup_manager.borrow().remove_backup_record(&backup_path_record) {
+                                log::warn!("Failed to update backup history after deleting {backup_path_record}: {e}");
+                            }
+
                             // Remove the row from the list
                             list_box_clone2.remove(&row_clone2);
 
@@ -1113,28 +1541,35 @@ fn show_delete_backup_confirmation(
 
                                 // Show empty state
                                 let empty_status = adw::StatusPage::new();
-                                empty_status.set_title("No Backups");
-                                empty_status.set_description(Some("No backups found at this destination"));
+                                empty_status.set_title(&gettext("No Backups"));
+                                empty_status.set_description(Some(&gettext("No backups found at this destination")));
                                 empty_status.set_icon_name(Some("folder-symbolic"));
                                 empty_status.set_vexpand(true);
                                 content_box_clone2.append(&empty_status);
                             }
 
                             // Show a brief toast notification
-                            let toast = adw::Toast::new("Backup deleted successfully");
+                            let toast = adw::Toast::new(&gettext("Backup deleted successfully"));
                             toast.set_timeout(3);
 
                             // Find the ToastOverlay parent if available, otherwise just skip the toast
                             // (since we're in a simple dialog without ToastOverlay, we'll skip this for now)
                         } else {
-                            dialogs::show_error(&parent_clone2, "Delete Failed", &message);
+                            dialogs::show_error(
+                                &parent_clone2,
+                                &gettext("Delete Failed"),
+                                &HelperError::decode(&message).message,
+                            );
                         }
                     }
                     Err(e) => {
                         dialogs::show_error(
                             &parent_clone2,
-                            "Delete Failed",
-                            &format!("Failed to delete backup: {}", e),
+                            &gettext("Delete Failed"),
+                            &gettext_f(
+                                "Failed to delete backup: {error}",
+                                &[("error", &e.to_string())],
+                            ),
                         );
                     }
                 }
@@ -1151,7 +1586,7 @@ fn list_backups(destination_mount: &str) -> anyhow::Result<Vec<String>> {
     let (success, result) = client.list_backups(destination_mount.to_string())?;
 
     if !success {
-        return Err(anyhow::anyhow!(result));
+        return Err(HelperError::decode(&result).into());
     }
 
     // Parse JSON response
@@ -1166,7 +1601,7 @@ fn update_backup_status_summary(row: &adw::ActionRow, backup_manager: Rc<RefCell
     let config = match backup_manager.borrow().get_config() {
         Ok(c) => c,
         Err(_) => {
-            row.set_subtitle("Unable to load queue status");
+            row.set_subtitle(&gettext("Unable to load queue status"));
             return;
         }
     };
@@ -1174,7 +1609,7 @@ fn update_backup_status_summary(row: &adw::ActionRow, backup_manager: Rc<RefCell
     let pending_backups = &config.pending_backups;
 
     if pending_backups.is_empty() {
-        row.set_subtitle("No pending backups");
+        row.set_subtitle(&gettext("No pending backups"));
         return;
     }
 
@@ -1196,17 +1631,29 @@ fn update_backup_status_summary(row: &adw::ActionRow, backup_manager: Rc<RefCell
         // Show progress when backups are actively running
         let total = pending_count + in_progress_count + failed_count;
         let completed = total - pending_count - in_progress_count;
-        format!("Backing up: {} of {} snapshots", completed + in_progress_count, total)
+        gettext_f(
+            "Backing up: {done} of {total} snapshots",
+            &[
+                ("done", &(completed + in_progress_count).to_string()),
+                ("total", &total.to_string()),
+            ],
+        )
     } else {
         // Show status breakdown when idle
         let parts = vec![
             if pending_count > 0 {
-                Some(format!("{pending_count} pending"))
+                Some(gettext_f(
+                    "{count} pending",
+                    &[("count", &pending_count.to_string())],
+                ))
             } else {
                 None
             },
             if failed_count > 0 {
-                Some(format!("{failed_count} failed"))
+                Some(gettext_f(
+                    "{count} failed",
+                    &[("count", &failed_count.to_string())],
+                ))
             } else {
                 None
             },
@@ -1220,6 +1667,7 @@ fn update_backup_status_summary(row: &adw::ActionRow, backup_manager: Rc<RefCell
 /// Create pending backups list widget
 fn create_pending_backups_list(backup_manager: Rc<RefCell<BackupManager>>) -> gtk::Box {
     use waypoint_common::BackupStatus;
+    use waypoint_common::backup_config::MAX_BACKUP_ATTEMPTS;
 
     let container = gtk::Box::new(Orientation::Vertical, 6);
 
@@ -1228,7 +1676,10 @@ fn create_pending_backups_list(backup_manager: Rc<RefCell<BackupManager>>) -> gt
         Ok(c) => c,
         Err(e) => {
             log::error!("Failed to load backup config: {e}");
-            let error_label = Label::new(Some(&format!("Failed to load pending backups: {e}")));
+            let error_label = Label::new(Some(&gettext_f(
+                "Failed to load pending backups: {error}",
+                &[("error", &e.to_string())],
+            )));
             error_label.add_css_class("dim-label");
             container.append(&error_label);
             return container;
@@ -1272,7 +1723,7 @@ fn create_pending_backups_list(backup_manager: Rc<RefCell<BackupManager>>) -> gt
                 let elapsed_secs = (now - start_time).max(0);
                 format_elapsed_time(elapsed_secs)
             } else {
-                "Just started".to_string()
+                gettext("Just started")
             };
 
             // Get friendly name for destination
@@ -1281,11 +1732,14 @@ fn create_pending_backups_list(backup_manager: Rc<RefCell<BackupManager>>) -> gt
                 .map(|d| d.display_name().to_string())
                 .unwrap_or_else(|| pb.destination_uuid.clone());
 
-            row.set_subtitle(&format!("Backing up to {dest_name} • {elapsed}"));
+            row.set_subtitle(&gettext_f(
+                "Backing up to {destination} • {elapsed}",
+                &[("destination", &dest_name), ("elapsed", &elapsed)],
+            ));
 
             let status_icon = gtk::Image::from_icon_name("emblem-synchronizing-symbolic");
             status_icon.set_pixel_size(16);
-            status_icon.set_tooltip_text(Some("Backup in progress"));
+            status_icon.set_tooltip_text(Some(&gettext("Backup in progress")));
             row.add_prefix(&status_icon);
 
             let spinner = gtk::Spinner::new();
@@ -1306,17 +1760,17 @@ fn create_pending_backups_list(backup_manager: Rc<RefCell<BackupManager>>) -> gt
             if let Some(live_progress) = backup_manager.borrow().get_progress(&pb.snapshot_id, &pb.destination_uuid) {
                 // Map stage to progress percentage
                 let (fraction, text) = match live_progress.stage.as_str() {
-                    "preparing" => (0.10, "Preparing..."),
-                    "transferring" => (0.50, "Transferring..."),
-                    "complete" => (1.0, "Complete"),
-                    _ => (0.0, "In progress..."),
+                    "preparing" => (0.10, gettext("Preparing...")),
+                    "transferring" => (0.50, gettext("Transferring...")),
+                    "complete" => (1.0, gettext("Complete")),
+                    _ => (0.0, gettext("In progress...")),
                 };
 
                 progress_bar.set_fraction(fraction);
-                progress_bar.set_text(Some(text));
+                progress_bar.set_text(Some(&text));
             } else {
                 // Fallback to indeterminate progress
-                progress_bar.set_text(Some("Transferring..."));
+                progress_bar.set_text(Some(&gettext("Transferring...")));
                 progress_bar.pulse();
             }
 
@@ -1338,10 +1792,16 @@ fn create_pending_backups_list(backup_manager: Rc<RefCell<BackupManager>>) -> gt
                 .map(|d| d.display_name().to_string())
                 .unwrap_or_else(|| pb.destination_uuid.clone());
 
-            row.set_subtitle(&format!(
-                "Destination: {} • {} attempts",
-                dest_name, pb.retry_count
+            row.set_subtitle(&gettext_f(
+                "Destination: {destination} • Gave up after {count} attempts",
+                &[
+                    ("destination", &dest_name),
+                    ("count", &pb.retry_count.to_string()),
+                ],
             ));
+            if let Some(error) = &pb.last_error {
+                row.set_tooltip_text(Some(error));
+            }
 
             let status_icon = gtk::Image::from_icon_name("dialog-error-symbolic");
             status_icon.set_pixel_size(16);
@@ -1349,7 +1809,7 @@ fn create_pending_backups_list(backup_manager: Rc<RefCell<BackupManager>>) -> gt
             row.add_prefix(&status_icon);
 
             // Add retry button
-            let retry_btn = Button::with_label("Retry");
+            let retry_btn = Button::with_label(&gettext("Retry"));
             retry_btn.set_valign(gtk::Align::Center);
             retry_btn.add_css_class("flat");
 
@@ -1381,11 +1841,45 @@ fn create_pending_backups_list(backup_manager: Rc<RefCell<BackupManager>>) -> gt
                 .map(|d| d.display_name().to_string())
                 .unwrap_or_else(|| pb.destination_uuid.clone());
 
-            row.set_subtitle(&format!("Queued for {dest_name}"));
+            if pb.is_retrying() {
+                let retry_time = pb
+                    .next_retry_at
+                    .and_then(|at| chrono::DateTime::from_timestamp(at, 0))
+                    .map(|at| {
+                        at.with_timezone(&chrono::Local)
+                            .format("%H:%M")
+                            .to_string()
+                    })
+                    .unwrap_or_else(|| gettext("next connection"));
+                row.set_subtitle(&gettext_f(
+                    "Retrying on {destination} • Attempt {attempt} of {max} after {time}",
+                    &[
+                        ("destination", &dest_name),
+                        ("attempt", &(pb.retry_count + 1).to_string()),
+                        ("max", &MAX_BACKUP_ATTEMPTS.to_string()),
+                        ("time", &retry_time),
+                    ],
+                ));
+                if let Some(error) = &pb.last_error {
+                    row.set_tooltip_text(Some(error));
+                }
+            } else {
+                row.set_subtitle(&gettext_f(
+                    "Queued for {destination}",
+                    &[("destination", &dest_name)],
+                ));
+            }
 
-            let status_icon = gtk::Image::from_icon_name("document-save-symbolic");
+            let status_icon = if pb.is_retrying() {
+                let icon = gtk::Image::from_icon_name("dialog-warning-symbolic");
+                icon.add_css_class("warning");
+                icon
+            } else {
+                let icon = gtk::Image::from_icon_name("document-save-symbolic");
+                icon.add_css_class("dim-label");
+                icon
+            };
             status_icon.set_pixel_size(16);
-            status_icon.add_css_class("dim-label");
             row.add_prefix(&status_icon);
 
             container.append(&row);
@@ -1409,8 +1903,11 @@ fn create_backup_statistics(backup_manager: Rc<RefCell<BackupManager>>) -> Vec<a
 
     // Total backups count
     let total_row = adw::ActionRow::new();
-    total_row.set_title("Total Backups");
-    total_row.set_subtitle(&format!("{} completed", config.backup_history.len()));
+    total_row.set_title(&gettext("Total Backups"));
+    total_row.set_subtitle(&gettext_f(
+        "{count} completed",
+        &[("count", &config.backup_history.len().to_string())],
+    ));
     rows.push(total_row);
 
     // Unique snapshots backed up
@@ -1420,8 +1917,13 @@ fn create_backup_statistics(backup_manager: Rc<RefCell<BackupManager>>) -> Vec<a
         .map(|r| r.snapshot_id.as_str())
         .collect();
     let unique_row = adw::ActionRow::new();
-    unique_row.set_title("Snapshots with Backups");
-    unique_row.set_subtitle(&format!("{} snapshots", unique_snapshots.len()));
+    unique_row.set_title(&gettext("Snapshots with Backups"));
+    unique_row.set_subtitle(&ngettext_f(
+        "{count} snapshot",
+        "{count} snapshots",
+        unique_snapshots.len() as u64,
+        &[("count", &unique_snapshots.len().to_string())],
+    ));
     rows.push(unique_row);
 
     // Total storage used
@@ -1432,7 +1934,7 @@ fn create_backup_statistics(backup_manager: Rc<RefCell<BackupManager>>) -> Vec<a
         .sum();
     if total_bytes > 0 {
         let storage_row = adw::ActionRow::new();
-        storage_row.set_title("Total Backup Size");
+        storage_row.set_title(&gettext("Total Backup Size"));
         storage_row.set_subtitle(&format_bytes(total_bytes));
         rows.push(storage_row);
     }
@@ -1446,12 +1948,14 @@ fn create_backup_statistics(backup_manager: Rc<RefCell<BackupManager>>) -> Vec<a
     if !config.backup_history.is_empty() {
         let ratio = (incremental_count as f64 / config.backup_history.len() as f64) * 100.0;
         let ratio_row = adw::ActionRow::new();
-        ratio_row.set_title("Incremental Backups");
-        ratio_row.set_subtitle(&format!(
-            "{:.1}% ({} of {})",
-            ratio,
-            incremental_count,
-            config.backup_history.len()
+        ratio_row.set_title(&gettext("Incremental Backups"));
+        ratio_row.set_subtitle(&gettext_f(
+            "{ratio}% ({count} of {total})",
+            &[
+                ("ratio", &format!("{ratio:.1}")),
+                ("count", &incremental_count.to_string()),
+                ("total", &config.backup_history.len().to_string()),
+            ],
         ));
         rows.push(ratio_row);
     }
@@ -1471,8 +1975,8 @@ fn create_backup_history(backup_manager: Rc<RefCell<BackupManager>>) -> Vec<adw:
 
     if config.backup_history.is_empty() {
         let empty_row = adw::ActionRow::new();
-        empty_row.set_title("No backup history yet");
-        empty_row.set_subtitle("Backups will appear here after completion");
+        empty_row.set_title(&gettext("No backup history yet"));
+        empty_row.set_subtitle(&gettext("Backups will appear here after completion"));
         return vec![empty_row];
     }
 
@@ -1490,7 +1994,7 @@ fn create_backup_history(backup_manager: Rc<RefCell<BackupManager>>) -> Vec<adw:
         // Format timestamp
         let datetime = chrono::DateTime::from_timestamp(record.completed_at, 0)
             .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
-            .unwrap_or_else(|| "Unknown date".to_string());
+            .unwrap_or_else(|| gettext("Unknown date"));
 
         // Build subtitle
         let mut subtitle_parts = vec![datetime];
@@ -1500,9 +2004,9 @@ fn create_backup_history(backup_manager: Rc<RefCell<BackupManager>>) -> Vec<adw:
         }
 
         if record.is_incremental {
-            subtitle_parts.push("Incremental".to_string());
+            subtitle_parts.push(gettext("Incremental"));
         } else {
-            subtitle_parts.push("Full".to_string());
+            subtitle_parts.push(gettext("Full"));
         }
 
         row.set_subtitle(&subtitle_parts.join(" • "));
@@ -1532,7 +2036,7 @@ fn create_drive_health_section(mount_point: &str) -> gtk::Box {
 
     // Header
     let header_box = gtk::Box::new(Orientation::Horizontal, 8);
-    let header_label = Label::new(Some("Drive Health"));
+    let header_label = Label::new(Some(&gettext("Drive Health")));
     header_label.add_css_class("heading");
     header_box.append(&header_label);
     container.append(&header_box);
@@ -1558,16 +2062,16 @@ fn create_drive_health_section(mount_point: &str) -> gtk::Box {
 
             // Health indicator: green if <75%, yellow if 75-90%, red if >90%
             let (_health_class, health_text) = if used_pct < 75 {
-                ("success", "Healthy")
+                ("success", gettext("Healthy"))
             } else if used_pct < 90 {
-                ("warning", "Running Low")
+                ("warning", gettext("Running Low"))
             } else {
-                ("error", "Nearly Full")
+                ("error", gettext("Nearly Full"))
             };
 
             // Space usage bar
             let space_box = gtk::Box::new(Orientation::Vertical, 4);
-            let space_label = Label::new(Some("Storage"));
+            let space_label = Label::new(Some(&gettext("Storage")));
             space_label.set_xalign(0.0);
             space_label.add_css_class("dim-label");
             space_label.add_css_class("caption");
@@ -1583,12 +2087,14 @@ fn create_drive_health_section(mount_point: &str) -> gtk::Box {
                 progress_bar.add_css_class("warning");
             }
 
-            let space_text = Label::new(Some(&format!(
-                "{} / {} ({} free) • {}",
-                format_bytes(stats.used_bytes),
-                format_bytes(stats.total_bytes),
-                format_bytes(stats.available_bytes),
-                health_text
+            let space_text = Label::new(Some(&gettext_f(
+                "{used} / {total} ({free} free) • {health}",
+                &[
+                    ("used", &format_bytes(stats.used_bytes)),
+                    ("total", &format_bytes(stats.total_bytes)),
+                    ("free", &format_bytes(stats.available_bytes)),
+                    ("health", &health_text),
+                ],
             )));
             space_text.set_xalign(0.0);
             space_text.add_css_class("caption");
@@ -1605,7 +2111,7 @@ fn create_drive_health_section(mount_point: &str) -> gtk::Box {
             stats_grid.set_margin_top(8);
 
             // Backup count
-            let count_label = Label::new(Some("Backups Stored"));
+            let count_label = Label::new(Some(&gettext("Backups Stored")));
             count_label.set_xalign(0.0);
             count_label.add_css_class("dim-label");
             count_label.add_css_class("caption");
@@ -1617,7 +2123,7 @@ fn create_drive_health_section(mount_point: &str) -> gtk::Box {
 
             // Last backup time
             if let Some(last_time) = stats.last_backup_timestamp {
-                let last_label = Label::new(Some("Last Backup"));
+                let last_label = Label::new(Some(&gettext("Last Backup")));
                 last_label.set_xalign(0.0);
                 last_label.add_css_class("dim-label");
                 last_label.add_css_class("caption");
@@ -1628,9 +2134,12 @@ fn create_drive_health_section(mount_point: &str) -> gtk::Box {
                     .as_secs() as i64;
                 let age_secs = (now - last_time).max(0) as u64;
                 let age_text = if age_secs < 60 {
-                    "Just now".to_string()
+                    gettext("Just now")
                 } else {
-                    format!("{} ago", format_elapsed_time(age_secs as i64))
+                    gettext_f(
+                        "{elapsed} ago",
+                        &[("elapsed", &format_elapsed_time(age_secs as i64))],
+                    )
                 };
 
                 let last_value = Label::new(Some(&age_text));
@@ -1642,7 +2151,7 @@ fn create_drive_health_section(mount_point: &str) -> gtk::Box {
 
             // Oldest backup
             if let Some(oldest_time) = stats.oldest_backup_timestamp {
-                let oldest_label = Label::new(Some("Oldest Backup"));
+                let oldest_label = Label::new(Some(&gettext("Oldest Backup")));
                 oldest_label.set_xalign(0.0);
                 oldest_label.add_css_class("dim-label");
                 oldest_label.add_css_class("caption");
@@ -1654,7 +2163,7 @@ fn create_drive_health_section(mount_point: &str) -> gtk::Box {
                 let age_secs = (now - oldest_time).max(0) as u64;
                 let age_text = format_elapsed_time(age_secs as i64);
 
-                let oldest_value = Label::new(Some(&format!("{age_text} old")));
+                let oldest_value = Label::new(Some(&gettext_f("{age} old", &[("age", &age_text)])));
                 oldest_value.set_xalign(0.0);
                 oldest_value.add_css_class("caption");
                 stats_grid.attach(&oldest_label, 0, 2, 1, 1);
@@ -1664,14 +2173,17 @@ fn create_drive_health_section(mount_point: &str) -> gtk::Box {
             container.append(&stats_grid);
         }
         Ok(Err(e)) => {
-            let error_label = Label::new(Some(&format!("Failed to get drive stats: {e}")));
+            let error_label = Label::new(Some(&gettext_f(
+                "Failed to get drive stats: {error}",
+                &[("error", &e.to_string())],
+            )));
             error_label.set_xalign(0.0);
             error_label.add_css_class("dim-label");
             error_label.add_css_class("caption");
             container.append(&error_label);
         }
         Err(_) => {
-            let error_label = Label::new(Some("Failed to retrieve drive statistics"));
+            let error_label = Label::new(Some(&gettext("Failed to retrieve drive statistics")));
             error_label.set_xalign(0.0);
             error_label.add_css_class("dim-label");
             error_label.add_css_class("caption");
@@ -1685,11 +2197,6 @@ fn create_drive_health_section(mount_point: &str) -> gtk::Box {
 /// Format bytes into human-readable string (e.g., "1.5 GB")
 /// Verify all backups on a destination
 fn verify_all_backups(destination_mount: &str) -> VerificationResults {
-    use waypoint_common::WaypointConfig;
-
-    let config = WaypointConfig::new();
-    let snapshot_dir = config.snapshot_dir;
-
     let client = match WaypointHelperClient::new() {
         Ok(c) => c,
         Err(e) => {
@@ -1697,7 +2204,14 @@ fn verify_all_backups(destination_mount: &str) -> VerificationResults {
                 total: 0,
                 passed: 0,
                 failed: 1,
-                details: vec![("Error".to_string(), false, format!("Failed to connect to helper: {e}"))],
+                details: vec![(
+                    gettext("Error"),
+                    false,
+                    gettext_f(
+                        "Failed to connect to helper: {error}",
+                        &[("error", &e.to_string())],
+                    ),
+                )],
             };
         }
     };
@@ -1712,7 +2226,14 @@ fn verify_all_backups(destination_mount: &str) -> VerificationResults {
                         total: 0,
                         passed: 0,
                         failed: 1,
-                        details: vec![("Error".to_string(), false, format!("Failed to parse backups: {e}"))],
+                        details: vec![(
+                            gettext("Error"),
+                            false,
+                            gettext_f(
+                                "Failed to parse backups: {error}",
+                                &[("error", &e.to_string())],
+                            ),
+                        )],
                     };
                 }
             }
@@ -1722,7 +2243,7 @@ fn verify_all_backups(destination_mount: &str) -> VerificationResults {
                 total: 0,
                 passed: 0,
                 failed: 1,
-                details: vec![("Error".to_string(), false, err)],
+                details: vec![(gettext("Error"), false, HelperError::decode(&err).message)],
             };
         }
         Err(e) => {
@@ -1730,7 +2251,14 @@ fn verify_all_backups(destination_mount: &str) -> VerificationResults {
                 total: 0,
                 passed: 0,
                 failed: 1,
-                details: vec![("Error".to_string(), false, format!("Failed to list backups: {e}"))],
+                details: vec![(
+                    gettext("Error"),
+                    false,
+                    gettext_f(
+                        "Failed to list backups: {error}",
+                        &[("error", &e.to_string())],
+                    ),
+                )],
             };
         }
     };
@@ -1740,7 +2268,11 @@ fn verify_all_backups(destination_mount: &str) -> VerificationResults {
             total: 0,
             passed: 0,
             failed: 0,
-            details: vec![("Info".to_string(), true, "No backups found on this destination".to_string())],
+            details: vec![(
+                gettext("Info"),
+                true,
+                gettext("No backups found on this destination"),
+            )],
         };
     }
 
@@ -1753,13 +2285,9 @@ fn verify_all_backups(destination_mount: &str) -> VerificationResults {
 
     for backup_path in backups {
         // Extract snapshot ID from the backup path (e.g., "hourly-20251117-1100" from "/mnt/backup/waypoint-backups/hourly-20251117-1100")
-        let backup_id = Path::new(&backup_path)
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or(&backup_path)
-            .to_string();
+        let backup_id = backup_snapshot_id(&backup_path).to_string();
 
-        let snapshot_path = snapshot_dir.join(&backup_id);
+        let snapshot_path = crate::btrfs::snapshot_path(&backup_id);
 
         let result = client.verify_backup(
             snapshot_path.to_string_lossy().to_string(),
@@ -1782,17 +2310,28 @@ fn verify_all_backups(destination_mount: &str) -> VerificationResults {
                     }
                     Err(e) => {
                         results.failed += 1;
-                        results.details.push((backup_id, false, format!("Failed to parse result: {e}")));
+                        results.details.push((
+                            backup_id,
+                            false,
+                            gettext_f(
+                                "Failed to parse result: {error}",
+                                &[("error", &e.to_string())],
+                            ),
+                        ));
                     }
                 }
             }
             Ok((false, err)) => {
                 results.failed += 1;
-                results.details.push((backup_id, false, err));
+                results.details.push((backup_id, false, HelperError::decode(&err).message));
             }
             Err(e) => {
                 results.failed += 1;
-                results.details.push((backup_id, false, format!("Verification error: {e}")));
+                results.details.push((
+                    backup_id,
+                    false,
+                    gettext_f("Verification error: {error}", &[("error", &e.to_string())]),
+                ));
             }
         }
     }
@@ -1803,7 +2342,7 @@ fn verify_all_backups(destination_mount: &str) -> VerificationResults {
 /// Show verification results dialog
 fn show_verification_results_dialog(parent: &adw::ApplicationWindow, results: VerificationResults) {
     let dialog = adw::Window::new();
-    dialog.set_title(Some("Backup Verification Results"));
+    dialog.set_title(Some(&gettext("Backup Verification Results")));
     dialog.set_modal(true);
     dialog.set_transient_for(Some(parent));
     dialog.set_default_size(600, 500);
@@ -1812,26 +2351,40 @@ fn show_verification_results_dialog(parent: &adw::ApplicationWindow, results: Ve
 
     // Header
     let header = adw::HeaderBar::new();
-    header.set_title_widget(Some(&adw::WindowTitle::new("Verification Results", "")));
+    header.set_title_widget(Some(&adw::WindowTitle::new(
+        &gettext("Verification Results"),
+        "",
+    )));
     content.append(&header);
 
     // Summary section
     let summary_group = adw::PreferencesGroup::new();
-    summary_group.set_title("Summary");
+    summary_group.set_title(&gettext("Summary"));
     summary_group.set_margin_top(12);
     summary_group.set_margin_start(12);
     summary_group.set_margin_end(12);
 
     let summary_row = adw::ActionRow::new();
     if results.failed == 0 && results.passed > 0 {
-        summary_row.set_title(&format!("✓ All {} backup(s) verified successfully", results.passed));
+        summary_row.set_title(&ngettext_f(
+            "✓ {count} backup verified successfully",
+            "✓ All {count} backups verified successfully",
+            results.passed as u64,
+            &[("count", &results.passed.to_string())],
+        ));
         summary_row.add_css_class("success");
     } else if results.failed > 0 {
-        summary_row.set_title(&format!("⚠ {} passed, {} failed out of {} total",
-            results.passed, results.failed, results.total));
+        summary_row.set_title(&gettext_f(
+            "⚠ {passed} passed, {failed} failed out of {total} total",
+            &[
+                ("passed", &results.passed.to_string()),
+                ("failed", &results.failed.to_string()),
+                ("total", &results.total.to_string()),
+            ],
+        ));
         summary_row.add_css_class("warning");
     } else {
-        summary_row.set_title("No backups to verify");
+        summary_row.set_title(&gettext("No backups to verify"));
     }
 
     summary_group.add(&summary_row);
@@ -0,0 +1,135 @@
+//! Command transcript showing the exact commands the helper ran as root
+
+use adw::prelude::*;
+use gtk::prelude::*;
+use gtk::{Orientation, glib};
+use libadwaita as adw;
+use std::sync::mpsc;
+use waypoint_common::CommandRecord;
+
+use crate::dbus_client::WaypointHelperClient;
+use crate::i18n::gettext;
+
+/// One line per command: local start time, then the command line
+fn format_record(record: &CommandRecord) -> String {
+    format!(
+        "{}  $ {}",
+        record
+            .timestamp
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S"),
+        record.command
+    )
+}
+
+/// Load the transcript in the background and show it in `view`
+///
+/// Fetching may prompt for authentication.
+fn load_transcript(view: &gtk::TextView) {
+    view.buffer().set_text(&gettext("Loading…"));
+
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let result =
+            WaypointHelperClient::new().and_then(|client| client.get_command_transcript(0));
+        let _ = tx.send(result);
+    });
+
+    let view = view.clone();
+    glib::spawn_future_local(async move {
+        let result = loop {
+            match rx.try_recv() {
+                Ok(result) => break result,
+                Err(mpsc::TryRecvError::Empty) => {
+                    glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    break Err(anyhow::anyhow!("Command transcript thread disconnected"));
+                }
+            }
+        };
+
+        let text = match result {
+            Ok(records) if records.is_empty() => gettext(
+                "No commands yet. Commands appear here as soon as Waypoint runs them, \
+                 e.g. when you create or delete a snapshot.",
+            ),
+            Ok(records) => records
+                .iter()
+                .map(format_record)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Err(e) => {
+                log::error!("Failed to load command transcript: {e}");
+                format!("{}: {e}", gettext("Could not load the command transcript"))
+            }
+        };
+        view.buffer().set_text(&text);
+
+        let mark = view
+            .buffer()
+            .create_mark(None, &view.buffer().end_iter(), false);
+        view.scroll_mark_onscreen(&mark);
+        view.buffer().delete_mark(&mark);
+    });
+}
+
+/// Show the command transcript
+pub fn show_command_transcript_dialog(parent: &adw::ApplicationWindow) {
+    let dialog = adw::Window::new();
+    dialog.set_title(Some(&gettext("Command Transcript")));
+    dialog.set_default_size(900, 600);
+    dialog.set_transient_for(Some(parent));
+
+    let content = gtk::Box::new(Orientation::Vertical, 0);
+
+    let header = adw::HeaderBar::new();
+    header.set_title_widget(Some(&adw::WindowTitle::new(
+        &gettext("Command Transcript"),
+        &gettext("Most recent commands run as root, oldest first"),
+    )));
+
+    let copy_button = gtk::Button::from_icon_name("edit-copy-symbolic");
+    copy_button.set_tooltip_text(Some(&gettext("Copy to Clipboard")));
+    header.pack_end(&copy_button);
+
+    let refresh_button = gtk::Button::from_icon_name("view-refresh-symbolic");
+    refresh_button.set_tooltip_text(Some(&gettext("Refresh")));
+    header.pack_start(&refresh_button);
+    content.append(&header);
+
+    let view = gtk::TextView::new();
+    view.set_editable(false);
+    view.set_cursor_visible(false);
+    view.set_monospace(true);
+    view.set_wrap_mode(gtk::WrapMode::WordChar);
+    view.set_top_margin(6);
+    view.set_bottom_margin(6);
+    view.set_left_margin(6);
+    view.set_right_margin(6);
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_vexpand(true);
+    scrolled.set_margin_top(12);
+    scrolled.set_margin_bottom(12);
+    scrolled.set_margin_start(12);
+    scrolled.set_margin_end(12);
+    scrolled.add_css_class("card");
+    scrolled.set_child(Some(&view));
+    content.append(&scrolled);
+
+    dialog.set_content(Some(&content));
+
+    let view_clone = view.clone();
+    copy_button.connect_clicked(move |button| {
+        let buffer = view_clone.buffer();
+        let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+        button.clipboard().set_text(&text);
+    });
+
+    let view_clone = view.clone();
+    refresh_button.connect_clicked(move |_| load_transcript(&view_clone));
+
+    load_transcript(&view);
+    dialog.present();
+}
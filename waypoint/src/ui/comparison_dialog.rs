@@ -1,7 +1,10 @@
+use crate::i18n::{gettext, gettext_f};
 use crate::snapshot::SnapshotManager;
+use crate::user_preferences::UserPreferencesManager;
 use adw::prelude::*;
 use libadwaita as adw;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 
 use super::comparison_view::ComparisonView;
@@ -11,11 +14,21 @@ use super::dialogs;
 pub fn show_compare_dialog(
     window: &adw::ApplicationWindow,
     manager: &Rc<RefCell<SnapshotManager>>,
+    user_prefs_manager: &Rc<RefCell<UserPreferencesManager>>,
 ) {
-    let snapshots = match manager.borrow().load_snapshots() {
+    // Package lists are loaded for the two snapshots being compared only
+    let snapshots = match manager.borrow().load_snapshot_summaries() {
         Ok(s) => s,
         Err(e) => {
-            dialogs::show_error(window, "Error", &format!("Failed to load snapshots: {e}"));
+            dialogs::show_error(
+                window,
+                &gettext("Error"),
+                &gettext_f(
+                    "Failed to load snapshots: {error}",
+                    &[("error", &e.to_string())],
+                ),
+            );
+
             return;
         }
     };
@@ -23,21 +36,30 @@ pub fn show_compare_dialog(
     if snapshots.len() < 2 {
         dialogs::show_error(
             window,
-            "Not Enough Snapshots",
-            "You need at least 2 snapshots to compare.\n\nCreate more snapshots first.",
+            &gettext("Not Enough Snapshots"),
+            &gettext("You need at least 2 snapshots to compare.\n\nCreate more snapshots first."),
         );
         return;
     }
 
     // Create comparison dialog with navigation view
     let dialog = adw::Window::new();
-    dialog.set_title(Some("Compare Snapshots"));
+    dialog.set_title(Some(&gettext("Compare Snapshots")));
     dialog.set_default_size(850, 700);
     dialog.set_modal(true);
     dialog.set_transient_for(Some(window));
 
+    // Notes are shown next to the descriptions for context
+    let notes: HashMap<String, String> = user_prefs_manager
+        .borrow()
+        .load()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(id, prefs)| prefs.note.map(|note| (id, note)))
+        .collect();
+
     // Create the comparison view with snapshots
-    let comparison_view = ComparisonView::new(snapshots);
+    let comparison_view = ComparisonView::new(snapshots, notes);
 
     // Set the comparison view as dialog content
     dialog.set_content(Some(comparison_view.widget()));
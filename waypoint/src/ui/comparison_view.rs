@@ -1,5 +1,7 @@
 use adw::prelude::*;
 use crate::dbus_client::WaypointHelperClient;
+use crate::i18n::{gettext, gettext_f, gettext_noop, ngettext_f};
+use crate::operations::{self, OperationKind};
 use crate::packages::{diff_packages, PackageDiff};
 use crate::snapshot::Snapshot;
 use gtk::prelude::*;
@@ -7,14 +9,92 @@ use gtk::{Box, Button, ListBox, Orientation, ScrolledWindow};
 use libadwaita as adw;
 use serde::Deserialize;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::mpsc;
 
 /// File change representation (matches waypoint-helper output)
 #[derive(Debug, Clone, Deserialize)]
 struct FileChange {
-    change_type: String, // "Added", "Modified", "Deleted"
+    change_type: String, // "Added", "Modified", "Deleted", "Renamed"
     path: String,
+    /// Previous path of a renamed file
+    #[serde(default)]
+    old_path: Option<String>,
+}
+
+/// Maximum number of files listed per change type on a directory page
+const MAX_DIRECTORY_ROWS: usize = 500;
+
+/// The older of two compared snapshots, which changed files can be restored from
+#[derive(Debug, Clone)]
+struct RestoreSource {
+    snapshot: String,
+    /// Whether the older snapshot is the base, i.e. changes go from older to newer
+    is_base: bool,
+}
+
+impl RestoreSource {
+    fn new(base: &Snapshot, other: &Snapshot) -> Self {
+        let (older, is_base) = if base.timestamp <= other.timestamp {
+            (base, true)
+        } else {
+            (other, false)
+        };
+        Self {
+            snapshot: older.name.clone(),
+            is_base,
+        }
+    }
+
+    /// Whether the older snapshot contains the changed file
+    fn has_file(&self, change: &FileChange) -> bool {
+        match change.change_type.as_str() {
+            "Modified" | "Renamed" => true,
+            "Deleted" => self.is_base,
+            "Added" => !self.is_base,
+            _ => false,
+        }
+    }
+
+    /// Path of the changed file in the older snapshot
+    fn file_path<'a>(&self, change: &'a FileChange) -> &'a str {
+        match &change.old_path {
+            Some(old_path) if self.is_base => old_path,
+            _ => &change.path,
+        }
+    }
+}
+
+/// Change counts for one top-level directory
+#[derive(Debug, Clone, Default)]
+struct DirectoryChanges {
+    path: String,
+    added: usize,
+    modified: usize,
+    deleted: usize,
+    renamed: usize,
+}
+
+impl DirectoryChanges {
+    fn total(&self) -> usize {
+        self.added + self.modified + self.deleted + self.renamed
+    }
+
+    /// e.g. "12 added, 3 modified", leaving out zero counts
+    fn describe(&self) -> String {
+        [
+            (self.added, gettext_noop("{count} added")),
+            (self.modified, gettext_noop("{count} modified")),
+            (self.deleted, gettext_noop("{count} deleted")),
+            (self.renamed, gettext_noop("{count} renamed")),
+        ]
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| gettext_f(label, &[("count", &count.to_string())]))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
 }
 
 /// Comparison view with navigation between selection, package diff, and file diff
@@ -24,12 +104,12 @@ pub struct ComparisonView {
 }
 
 impl ComparisonView {
-    /// Create a new comparison view with loaded snapshots
-    pub fn new(snapshots: Vec<Snapshot>) -> Self {
+    /// Create a new comparison view with loaded snapshots and user notes keyed by snapshot ID
+    pub fn new(snapshots: Vec<Snapshot>, notes: HashMap<String, String>) -> Self {
         let nav_view = adw::NavigationView::new();
 
         // Create selection page
-        let selection_page = Self::create_selection_page(snapshots, nav_view.clone());
+        let selection_page = Self::create_selection_page(snapshots, notes, nav_view.clone());
         nav_view.add(&selection_page);
 
         Self { widget: nav_view }
@@ -38,9 +118,11 @@ impl ComparisonView {
     /// Create the selection page
     fn create_selection_page(
         snapshots: Vec<Snapshot>,
+        notes: HashMap<String, String>,
         nav_view: adw::NavigationView,
     ) -> adw::NavigationPage {
-        let page = adw::NavigationPage::new(&adw::ToolbarView::new(), "Compare Snapshots");
+        let page =
+            adw::NavigationPage::new(&adw::ToolbarView::new(), &gettext("Compare Snapshots"));
 
         let toolbar_view = page.child().and_downcast::<adw::ToolbarView>().unwrap();
 
@@ -61,10 +143,10 @@ impl ComparisonView {
 
         // Base snapshot dropdown
         let base_group = adw::PreferencesGroup::new();
-        base_group.set_title("Base Snapshot");
+        base_group.set_title(&gettext("Base Snapshot"));
 
         let base_row = adw::ComboRow::new();
-        base_row.set_title("Select base snapshot");
+        base_row.set_title(&gettext("Select base snapshot"));
 
         let base_model = gtk::StringList::new(&[]);
         for snapshot in &snapshots {
@@ -82,10 +164,10 @@ impl ComparisonView {
 
         // Compare snapshot dropdown
         let compare_group = adw::PreferencesGroup::new();
-        compare_group.set_title("Compare To");
+        compare_group.set_title(&gettext("Compare To"));
 
         let compare_row = adw::ComboRow::new();
-        compare_row.set_title("Select snapshot to compare");
+        compare_row.set_title(&gettext("Select snapshot to compare"));
 
         let compare_model = gtk::StringList::new(&[]);
         // Initially populate with all snapshots except the first (which is selected in base)
@@ -106,22 +188,38 @@ impl ComparisonView {
 
         content.append(&selection_box);
 
+        // Context section: descriptions and notes side by side (initially hidden)
+        let context_group = adw::PreferencesGroup::new();
+        context_group.set_title(&gettext("Context"));
+        context_group.set_visible(false);
+
+        let context_cards = Box::new(Orientation::Horizontal, 12);
+        context_cards.set_homogeneous(true);
+        context_group.add(&context_cards);
+
+        let elapsed_label = gtk::Label::new(None);
+        elapsed_label.add_css_class("dim-label");
+        elapsed_label.set_margin_top(6);
+        context_group.add(&elapsed_label);
+
+        content.append(&context_group);
+
         // Summary section (initially hidden)
         let summary_group = adw::PreferencesGroup::new();
-        summary_group.set_title("Summary");
+        summary_group.set_title(&gettext("Summary"));
         summary_group.set_visible(false);
 
         let summary_list = ListBox::new();
         summary_list.add_css_class("boxed-list");
 
         let packages_row = adw::ActionRow::new();
-        packages_row.set_title("Package Changes");
-        packages_row.set_subtitle("Select snapshots to compare");
+        packages_row.set_title(&gettext("Package Changes"));
+        packages_row.set_subtitle(&gettext("Select snapshots to compare"));
         summary_list.append(&packages_row);
 
         let files_row = adw::ActionRow::new();
-        files_row.set_title("File Changes");
-        files_row.set_subtitle("Not available");
+        files_row.set_title(&gettext("File Changes"));
+        files_row.set_subtitle(&gettext("Not available"));
         summary_list.append(&files_row);
 
         summary_group.add(&summary_list);
@@ -132,12 +230,12 @@ impl ComparisonView {
         button_box.set_halign(gtk::Align::Center);
         button_box.set_margin_top(12);
 
-        let view_packages_button = Button::with_label("View Packages");
+        let view_packages_button = Button::with_label(&gettext("View Packages"));
         view_packages_button.add_css_class("pill");
         view_packages_button.add_css_class("suggested-action");
         view_packages_button.set_visible(false);
 
-        let view_files_button = Button::with_label("View Files");
+        let view_files_button = Button::with_label(&gettext("View Files"));
         view_files_button.add_css_class("pill");
         view_files_button.set_visible(false);
 
@@ -149,6 +247,8 @@ impl ComparisonView {
         // Store snapshots for comparison
         let snapshots = Rc::new(snapshots);
         let current_diff: Rc<RefCell<Option<PackageDiff>>> = Rc::new(RefCell::new(None));
+        // Bumped on every selection change, so a slow package load can't show a stale diff
+        let package_request = Rc::new(std::cell::Cell::new(0u64));
 
         // Mapping from compare dropdown indices to actual snapshot indices
         let compare_mapping: Rc<RefCell<Vec<usize>>> = Rc::new(RefCell::new(
@@ -165,9 +265,11 @@ impl ComparisonView {
         let view_packages_clone = view_packages_button.clone();
         let view_files_clone = view_files_button.clone();
         let diff_for_base = current_diff.clone();
+        let request_for_base = package_request.clone();
         let mapping_for_compare = compare_mapping.clone();
 
         let files_row_clone = files_row.clone();
+        let context_group_clone = context_group.clone();
         let update_comparison = move || {
             let base_idx = base_row_clone.selected() as usize;
             let compare_dropdown_idx = compare_row_clone.selected() as usize;
@@ -181,6 +283,7 @@ impl ComparisonView {
             let compare_idx = match mapping.get(compare_dropdown_idx) {
                 Some(&idx) => idx,
                 None => {
+                    context_group_clone.set_visible(false);
                     summary_group_clone.set_visible(false);
                     view_packages_clone.set_visible(false);
                     view_files_clone.set_visible(false);
@@ -189,6 +292,7 @@ impl ComparisonView {
             };
 
             if base_idx == compare_idx {
+                context_group_clone.set_visible(false);
                 summary_group_clone.set_visible(false);
                 view_packages_clone.set_visible(false);
                 view_files_clone.set_visible(false);
@@ -198,31 +302,95 @@ impl ComparisonView {
             let snap1 = &snapshots_for_base[base_idx];
             let snap2 = &snapshots_for_compare[compare_idx];
 
-            // Compute package diff
-            let diff = diff_packages(&snap1.packages, &snap2.packages);
-
-            packages_row_clone.set_subtitle(&format!(
-                "{} added, {} removed, {} changed",
-                diff.added.len(),
-                diff.removed.len(),
-                diff.updated.len()
+            // Show what each snapshot was taken for and how far apart they are
+            while let Some(child) = context_cards.first_child() {
+                context_cards.remove(&child);
+            }
+            context_cards.append(&Self::create_context_card(
+                &gettext("Base"),
+                snap1,
+                notes.get(&snap1.id),
+            ));
+            context_cards.append(&Self::create_context_card(
+                &gettext("Compare To"),
+                snap2,
+                notes.get(&snap2.id),
             ));
+            elapsed_label.set_text(&format_elapsed(snap1.timestamp, snap2.timestamp));
+            context_group_clone.set_visible(true);
+
+            // The list only has package counts, so load both package lists in the background
+            *diff_for_base.borrow_mut() = None;
+            view_packages_clone.set_visible(false);
+            packages_row_clone.set_subtitle(&gettext("Loading packages..."));
+            let request = request_for_base.get() + 1;
+            request_for_base.set(request);
+
+            let names = (snap1.name.clone(), snap2.name.clone());
+            let (package_tx, package_rx) = mpsc::channel();
+            let requested = vec![names.0.clone(), names.1.clone()];
+            std::thread::spawn(move || {
+                let result = WaypointHelperClient::new()
+                    .and_then(|client| client.get_snapshot_packages(requested));
+                let _ = package_tx.send(result);
+            });
+
+            let packages_row_for_update = packages_row_clone.clone();
+            let view_packages_for_update = view_packages_clone.clone();
+            let diff_for_update = diff_for_base.clone();
+            let request_for_update = request_for_base.clone();
+            gtk::glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
+                let result = match package_rx.try_recv() {
+                    Ok(result) => result,
+                    Err(mpsc::TryRecvError::Empty) => return gtk::glib::ControlFlow::Continue,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        Err(anyhow::anyhow!("Package loading stopped unexpectedly"))
+                    }
+                };
+                if request_for_update.get() != request {
+                    return gtk::glib::ControlFlow::Break;
+                }
 
-            // Store diff for later use
-            *diff_for_base.borrow_mut() = Some(diff);
+                match result {
+                    Ok(packages) => {
+                        let no_packages = Vec::new();
+                        let diff = diff_packages(
+                            packages.get(&names.0).unwrap_or(&no_packages),
+                            packages.get(&names.1).unwrap_or(&no_packages),
+                        );
+                        packages_row_for_update.set_subtitle(&gettext_f(
+                            "{added} added, {removed} removed, {changed} changed",
+                            &[
+                                ("added", &diff.added.len().to_string()),
+                                ("removed", &diff.removed.len().to_string()),
+                                ("changed", &diff.updated.len().to_string()),
+                            ],
+                        ));
+
+                        // Store diff for later use
+                        *diff_for_update.borrow_mut() = Some(diff);
+                        view_packages_for_update.set_visible(true);
+                    }
+                    Err(e) => {
+                        log::warn!("Failed to load snapshot packages: {e}");
+                        packages_row_for_update.set_subtitle(&gettext("Failed to load packages"));
+                    }
+                }
+                gtk::glib::ControlFlow::Break
+            });
 
             // Compute file diff in background
-            files_row_clone.set_subtitle("Computing...");
+            files_row_clone.set_subtitle(&gettext("Computing..."));
             let snap1_name = snap1.name.clone();
             let snap2_name = snap2.name.clone();
 
             let (tx, rx) = std::sync::mpsc::channel();
             std::thread::spawn(move || {
-                let result = (|| -> anyhow::Result<usize> {
+                let result = (|| -> anyhow::Result<(usize, Vec<DirectoryChanges>)> {
                     let client = WaypointHelperClient::new()?;
                     let json = client.compare_snapshots(snap1_name, snap2_name)?;
                     let changes: Vec<FileChange> = serde_json::from_str(&json)?;
-                    Ok(changes.len())
+                    Ok((changes.len(), Self::summarize_changes(&changes)))
                 })();
                 let _ = tx.send(result);
             });
@@ -232,29 +400,50 @@ impl ComparisonView {
                 match rx.try_recv() {
                     Ok(result) => {
                         match result {
-                            Ok(count) => {
+                            Ok((count, summary)) => {
                                 if count == 0 {
-                                    files_row_for_update.set_subtitle("No changes");
+                                    files_row_for_update.set_subtitle(&gettext("No changes"));
                                 } else {
-                                    files_row_for_update.set_subtitle(&format!("{} files changed", count));
+                                    // e.g. "2431 files changed: 2019 in /usr/lib, 12 in /etc"
+                                    let top_dirs: Vec<String> = summary
+                                        .iter()
+                                        .take(3)
+                                        .map(|dir| {
+                                            gettext_f(
+                                                "{count} in {directory}",
+                                                &[
+                                                    ("count", &dir.total().to_string()),
+                                                    ("directory", &dir.path),
+                                                ],
+                                            )
+                                        })
+                                        .collect();
+                                    files_row_for_update.set_subtitle(&ngettext_f(
+                                        "{count} file changed: {directories}",
+                                        "{count} files changed: {directories}",
+                                        count as u64,
+                                        &[
+                                            ("count", &count.to_string()),
+                                            ("directories", &top_dirs.join(", ")),
+                                        ],
+                                    ));
                                 }
                             }
                             Err(_) => {
-                                files_row_for_update.set_subtitle("Failed to compute");
+                                files_row_for_update.set_subtitle(&gettext("Failed to compute"));
                             }
                         }
                         gtk::glib::ControlFlow::Break
                     }
                     Err(std::sync::mpsc::TryRecvError::Empty) => gtk::glib::ControlFlow::Continue,
                     Err(_) => {
-                        files_row_for_update.set_subtitle("Failed to compute");
+                        files_row_for_update.set_subtitle(&gettext("Failed to compute"));
                         gtk::glib::ControlFlow::Break
                     }
                 }
             });
 
             summary_group_clone.set_visible(true);
-            view_packages_clone.set_visible(true);
             view_files_clone.set_visible(true);
         };
 
@@ -344,7 +533,12 @@ impl ComparisonView {
                 let snap1 = &snapshots_for_files[base_idx];
                 let snap2 = &snapshots_for_files[compare_idx];
 
-                let file_page = Self::create_file_diff_page(&snap1.name, &snap2.name, nav_view_for_files.clone());
+                let file_page = Self::create_file_diff_page(
+                    &snap1.name,
+                    &snap2.name,
+                    RestoreSource::new(snap1, snap2),
+                    nav_view_for_files.clone(),
+                );
                 nav_view_for_files.push(&file_page);
             }
         });
@@ -356,13 +550,68 @@ impl ComparisonView {
         page
     }
 
+    /// Card with a snapshot's name, time, description and note
+    fn create_context_card(role: &str, snapshot: &Snapshot, note: Option<&String>) -> Box {
+        let card = Box::new(Orientation::Vertical, 6);
+        card.add_css_class("card");
+
+        let inner = Box::new(Orientation::Vertical, 6);
+        inner.set_margin_top(12);
+        inner.set_margin_bottom(12);
+        inner.set_margin_start(12);
+        inner.set_margin_end(12);
+
+        let new_label = |text: &str| {
+            let label = gtk::Label::new(Some(text));
+            label.set_halign(gtk::Align::Start);
+            label.set_xalign(0.0);
+            label.set_wrap(true);
+            label.set_wrap_mode(gtk::pango::WrapMode::WordChar);
+            label
+        };
+
+        let role_label = new_label(role);
+        role_label.add_css_class("caption");
+        role_label.add_css_class("dim-label");
+        inner.append(&role_label);
+
+        let name_label = new_label(&snapshot.name);
+        name_label.add_css_class("heading");
+        inner.append(&name_label);
+
+        let time_label = new_label(&snapshot.format_timestamp());
+        time_label.add_css_class("dim-label");
+        inner.append(&time_label);
+
+        let description_label = match snapshot.description.as_deref().map(str::trim) {
+            Some(description) if !description.is_empty() => new_label(description),
+            _ => {
+                let label = new_label(&gettext("No description"));
+                label.add_css_class("dim-label");
+                label
+            }
+        };
+        description_label.set_margin_top(6);
+        inner.append(&description_label);
+
+        if let Some(note) = note.map(|n| n.trim()).filter(|n| !n.is_empty()) {
+            let note_label = new_label(&gettext_f("Note: {note}", &[("note", note)]));
+            note_label.add_css_class("caption");
+            inner.append(&note_label);
+        }
+
+        card.append(&inner);
+        card
+    }
+
     /// Create package diff page
     fn create_package_diff_page(
         snap1_name: &str,
         snap2_name: &str,
         diff: &PackageDiff,
     ) -> adw::NavigationPage {
-        let page = adw::NavigationPage::new(&adw::ToolbarView::new(), "Package Differences");
+        let page =
+            adw::NavigationPage::new(&adw::ToolbarView::new(), &gettext("Package Differences"));
 
         let toolbar_view = page.child().and_downcast::<adw::ToolbarView>().unwrap();
 
@@ -370,7 +619,7 @@ impl ComparisonView {
 
         // Add export button
         let export_btn = gtk::Button::from_icon_name("document-save-symbolic");
-        export_btn.set_tooltip_text(Some("Export comparison to text file"));
+        export_btn.set_tooltip_text(Some(&gettext("Export comparison to text file")));
 
         let snap1_for_export = snap1_name.to_string();
         let snap2_for_export = snap2_name.to_string();
@@ -412,17 +661,20 @@ impl ComparisonView {
         if diff.added.is_empty() && diff.removed.is_empty() && diff.updated.is_empty() {
             let status_page = adw::StatusPage::new();
             status_page.set_icon_name(Some("emblem-ok-symbolic"));
-            status_page.set_title("Identical Package Sets");
-            status_page.set_description(Some(
+            status_page.set_title(&gettext("Identical Package Sets"));
+            status_page.set_description(Some(&gettext(
                 "Both snapshots have the exact same packages installed.\n\n\
                  This means no packages were added, removed, or updated between these snapshots."
-            ));
+            )));
             content.append(&status_page);
         } else {
             // Added packages
             if !diff.added.is_empty() {
                 let added_group = adw::PreferencesGroup::new();
-                added_group.set_title(&format!("Added Packages ({})", diff.added.len()));
+                added_group.set_title(&gettext_f(
+                    "Added Packages ({count})",
+                    &[("count", &diff.added.len().to_string())],
+                ));
 
                 let added_list = ListBox::new();
                 added_list.add_css_class("boxed-list");
@@ -442,7 +694,10 @@ impl ComparisonView {
             // Removed packages
             if !diff.removed.is_empty() {
                 let removed_group = adw::PreferencesGroup::new();
-                removed_group.set_title(&format!("Removed Packages ({})", diff.removed.len()));
+                removed_group.set_title(&gettext_f(
+                    "Removed Packages ({count})",
+                    &[("count", &diff.removed.len().to_string())],
+                ));
 
                 let removed_list = ListBox::new();
                 removed_list.add_css_class("boxed-list");
@@ -462,7 +717,10 @@ impl ComparisonView {
             // Updated packages
             if !diff.updated.is_empty() {
                 let changed_group = adw::PreferencesGroup::new();
-                changed_group.set_title(&format!("Updated Packages ({})", diff.updated.len()));
+                changed_group.set_title(&gettext_f(
+                    "Updated Packages ({count})",
+                    &[("count", &diff.updated.len().to_string())],
+                ));
 
                 let changed_list = ListBox::new();
                 changed_list.add_css_class("boxed-list");
@@ -491,9 +749,10 @@ impl ComparisonView {
     fn create_file_diff_page(
         snap1_name: &str,
         snap2_name: &str,
-        _nav_view: adw::NavigationView,
+        restore_source: RestoreSource,
+        nav_view: adw::NavigationView,
     ) -> adw::NavigationPage {
-        let page = adw::NavigationPage::new(&adw::ToolbarView::new(), "File Differences");
+        let page = adw::NavigationPage::new(&adw::ToolbarView::new(), &gettext("File Differences"));
 
         let toolbar_view = page.child().and_downcast::<adw::ToolbarView>().unwrap();
 
@@ -501,7 +760,7 @@ impl ComparisonView {
 
         // Add export button (will be enabled after loading completes)
         let export_btn = gtk::Button::from_icon_name("document-save-symbolic");
-        export_btn.set_tooltip_text(Some("Export file changes to text file"));
+        export_btn.set_tooltip_text(Some(&gettext("Export file changes to text file")));
         export_btn.set_sensitive(false); // Disabled during loading
         header.pack_end(&export_btn);
 
@@ -521,18 +780,28 @@ impl ComparisonView {
         spinner.set_size_request(48, 48);
         content.append(&spinner);
 
-        let loading_label = gtk::Label::new(Some("Comparing file changes..."));
+        let loading_label = gtk::Label::new(Some(&gettext("Comparing file changes...")));
         loading_label.add_css_class("title-3");
         loading_label.set_halign(gtk::Align::Center);
         loading_label.set_margin_top(12);
         content.append(&loading_label);
 
-        let hint_label = gtk::Label::new(Some("This may take a moment for large snapshots"));
+        let hint_label =
+            gtk::Label::new(Some(&gettext("This may take a moment for large snapshots")));
         hint_label.add_css_class("dim-label");
         hint_label.set_halign(gtk::Align::Center);
         hint_label.set_margin_top(6);
         content.append(&hint_label);
 
+        let cancel_btn = gtk::Button::with_label(&gettext("Cancel"));
+        cancel_btn.add_css_class("pill");
+        cancel_btn.set_halign(gtk::Align::Center);
+        cancel_btn.set_margin_top(12);
+        cancel_btn.set_visible(crate::dbus_client::helper_supports(
+            waypoint_common::capabilities::FEATURE_COMPARE_PROGRESS,
+        ));
+        content.append(&cancel_btn);
+
         let scrolled = ScrolledWindow::new();
         scrolled.set_child(Some(&content));
         toolbar_view.set_content(Some(&scrolled));
@@ -544,24 +813,103 @@ impl ComparisonView {
         let snap1_display = snap1_name.to_string();
         let snap2_display = snap2_name.to_string();
 
+        let (progress_tx, progress_rx) = mpsc::channel();
+
         std::thread::spawn(move || {
             let result = (|| -> anyhow::Result<Vec<FileChange>> {
                 let client = WaypointHelperClient::new()?;
-                let json = client.compare_snapshots(old_snapshot, new_snapshot)?;
+                let json = client.compare_snapshots_with_progress(
+                    old_snapshot,
+                    new_snapshot,
+                    move |progress| {
+                        let _ = progress_tx.send(progress);
+                    },
+                )?;
                 let changes: Vec<FileChange> = serde_json::from_str(&json)?;
                 Ok(changes)
             })();
             let _ = tx.send(result);
         });
 
+        // Ask the helper to stop; the comparison then ends with an error
+        let cancelled = Rc::new(std::cell::Cell::new(false));
+        let cancelled_for_btn = cancelled.clone();
+        let old_for_cancel = snap1_name.to_string();
+        let new_for_cancel = snap2_name.to_string();
+        let hint_for_cancel = hint_label.clone();
+        cancel_btn.connect_clicked(move |btn| {
+            btn.set_sensitive(false);
+            cancelled_for_btn.set(true);
+            hint_for_cancel.set_text(&gettext("Cancelling..."));
+            let old = old_for_cancel.clone();
+            let new = new_for_cancel.clone();
+            std::thread::spawn(move || {
+                let result = WaypointHelperClient::new()
+                    .and_then(|client| client.cancel_compare(&old, &new));
+                if let Err(e) = result {
+                    log::warn!("Failed to cancel comparison: {e}");
+                }
+            });
+        });
+
+        // The comparison keeps running, and stays visible in the Operations
+        // panel, when the user navigates away
+        let operation_title = gettext_f(
+            "Compare {base} and {other}",
+            &[("base", snap1_name), ("other", snap2_name)],
+        );
+        let operation = if cancel_btn.is_visible() {
+            let cancel_btn = cancel_btn.clone();
+            operations::start_cancellable(OperationKind::Compare, operation_title, move || {
+                cancel_btn.emit_clicked()
+            })
+        } else {
+            operations::start(OperationKind::Compare, operation_title)
+        };
+
         // Poll for results
         let page_clone = page.clone();
         let export_btn_clone = export_btn.clone();
+        let mut total_changes = None;
         gtk::glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
+            // Show activity while the helper scans the snapshots
+            if let Some(progress) = progress_rx.try_iter().last() {
+                let status = match progress.stage.as_str() {
+                    "scanning" => Some(gettext_f(
+                        "Scanned {count} files...",
+                        &[("count", &progress.entries_scanned.to_string())],
+                    )),
+                    "comparing" => Some(gettext_f(
+                        "Found {count} changes, preparing results...",
+                        &[("count", &progress.changes_found.to_string())],
+                    )),
+                    "complete" => {
+                        total_changes = Some(progress.changes_found);
+                        None
+                    }
+                    _ => None,
+                };
+                if let Some(status) = status {
+                    hint_label.set_text(&status);
+                    operations::update(operation, None, status);
+                }
+            }
+
             match rx.try_recv() {
                 Ok(result) => {
                     match result {
                         Ok(changes) => {
+                            let total = total_changes.unwrap_or(changes.len() as u64);
+                            operations::succeed(
+                                operation,
+                                ngettext_f(
+                                    "{count} change",
+                                    "{count} changes",
+                                    total,
+                                    &[("count", &total.to_string())],
+                                ),
+                            );
+
                             // Replace loading content with results
                             let new_toolbar_view = page_clone
                                 .child()
@@ -572,8 +920,24 @@ impl ComparisonView {
                                 &snap1_display,
                                 &snap2_display,
                                 changes.clone(),
+                                restore_source.clone(),
+                                nav_view.clone(),
                             );
 
+                            // Very large diffs are capped by the helper
+                            let shown = changes.len() as u64;
+                            if let Some(total) = total_changes.filter(|&t| t > shown) {
+                                let banner = adw::Banner::new(&gettext_f(
+                                    "Showing the first {shown} of {total} changes",
+                                    &[
+                                        ("shown", &changes.len().to_string()),
+                                        ("total", &total.to_string()),
+                                    ],
+                                ));
+                                banner.set_revealed(true);
+                                results_content.prepend(&banner);
+                            }
+
                             let scrolled = ScrolledWindow::new();
                             scrolled.set_child(Some(&results_content));
                             new_toolbar_view.set_content(Some(&scrolled));
@@ -595,14 +959,26 @@ impl ComparisonView {
 
                             let error_page = adw::StatusPage::new();
                             error_page.set_icon_name(Some("dialog-error-symbolic"));
-                            error_page.set_title("Comparison Failed");
+                            error_page.set_title(&gettext("Comparison Failed"));
+
+                            if cancelled.get() {
+                                operations::cancelled(operation);
+                            } else {
+                                operations::fail(operation, e.to_string());
+                            }
 
-                            let error_msg = if e.to_string().contains("timeout") {
-                                "The file comparison took too long (>25 seconds).\n\nThis happens with very large snapshots.\nTry using package comparison instead."
+                            let error_msg = if cancelled.get() {
+                                error_page.set_icon_name(Some("process-stop-symbolic"));
+                                error_page.set_title(&gettext("Comparison Cancelled"));
+                                gettext("The file comparison was cancelled.")
+                            } else if e.to_string().contains("timeout") {
+                                gettext(
+                                    "The file comparison took too long (>25 seconds).\n\nThis happens with very large snapshots.\nTry using package comparison instead.",
+                                )
                             } else {
-                                "Failed to compare file changes between snapshots."
+                                gettext("Failed to compare file changes between snapshots.")
                             };
-                            error_page.set_description(Some(error_msg));
+                            error_page.set_description(Some(&error_msg));
 
                             new_toolbar_view.set_content(Some(&error_page));
                         }
@@ -610,7 +986,10 @@ impl ComparisonView {
                     gtk::glib::ControlFlow::Break
                 }
                 Err(mpsc::TryRecvError::Empty) => gtk::glib::ControlFlow::Continue,
-                Err(_) => gtk::glib::ControlFlow::Break,
+                Err(_) => {
+                    operations::fail(operation, gettext("Comparison stopped unexpectedly"));
+                    gtk::glib::ControlFlow::Break
+                }
             }
         });
 
@@ -618,10 +997,16 @@ impl ComparisonView {
     }
 
     /// Create the results display for file diff
+    ///
+    /// Shows counts per change type and per top-level directory rather than
+    /// every file, since a system update easily changes thousands of files.
+    /// Activating a directory opens a page with just that directory's files.
     fn create_file_diff_results(
         snap1_name: &str,
         snap2_name: &str,
         changes: Vec<FileChange>,
+        restore_source: RestoreSource,
+        nav_view: adw::NavigationView,
     ) -> Box {
         let content = Box::new(Orientation::Vertical, 12);
         content.set_margin_top(12);
@@ -651,109 +1036,246 @@ impl ComparisonView {
         if changes.is_empty() {
             let status_page = adw::StatusPage::new();
             status_page.set_icon_name(Some("emblem-ok-symbolic"));
-            status_page.set_title("No File Changes");
-            status_page.set_description(Some("The snapshots have identical files"));
+            status_page.set_title(&gettext("No File Changes"));
+            status_page.set_description(Some(&gettext("The snapshots have identical files")));
             content.append(&status_page);
             return content;
         }
 
-        // Group changes by type
-        let mut added: Vec<&FileChange> = Vec::new();
-        let mut modified: Vec<&FileChange> = Vec::new();
-        let mut deleted: Vec<&FileChange> = Vec::new();
+        let summary = Self::summarize_changes(&changes);
+        let (added, modified, deleted, renamed) = summary.iter().fold((0, 0, 0, 0), |acc, dir| {
+            (
+                acc.0 + dir.added,
+                acc.1 + dir.modified,
+                acc.2 + dir.deleted,
+                acc.3 + dir.renamed,
+            )
+        });
 
-        for change in &changes {
-            match change.change_type.as_str() {
-                "Added" => added.push(change),
-                "Modified" => modified.push(change),
-                "Deleted" => deleted.push(change),
-                _ => {}
-            }
+        // Totals per change type
+        let totals_group = adw::PreferencesGroup::new();
+        totals_group.set_title(&gettext("Summary"));
+        for (title, count, icon_name) in [
+            (gettext("Added"), added, "list-add-symbolic"),
+            (gettext("Modified"), modified, "document-edit-symbolic"),
+            (gettext("Deleted"), deleted, "list-remove-symbolic"),
+            (gettext("Renamed"), renamed, "document-save-as-symbolic"),
+        ] {
+            let row = adw::ActionRow::new();
+            row.set_title(&title);
+            row.add_prefix(&gtk::Image::from_icon_name(icon_name));
+            let count_label = gtk::Label::new(Some(&count.to_string()));
+            count_label.add_css_class("dim-label");
+            row.add_suffix(&count_label);
+            totals_group.add(&row);
         }
+        content.append(&totals_group);
+
+        // Counts per top-level directory, largest first
+        let dirs_group = adw::PreferencesGroup::new();
+        dirs_group.set_title(&gettext("Changes by Directory"));
+        dirs_group.set_description(Some(&gettext_f(
+            "Select a directory to see its changed files and restore them from {snapshot}",
+            &[("snapshot", &restore_source.snapshot)],
+        )));
+
+        let changes = Rc::new(changes);
+        for dir in summary {
+            let row = adw::ActionRow::new();
+            row.set_title(&dir.path);
+            row.set_subtitle(&dir.describe());
+            row.set_activatable(true);
+
+            let count_label = gtk::Label::new(Some(&dir.total().to_string()));
+            count_label.add_css_class("dim-label");
+            row.add_suffix(&count_label);
+            row.add_suffix(&gtk::Image::from_icon_name("go-next-symbolic"));
+
+            let changes = changes.clone();
+            let restore_source = restore_source.clone();
+            let nav_view = nav_view.clone();
+            row.connect_activated(move |_| {
+                let page =
+                    Self::create_directory_changes_page(&dir.path, &changes, &restore_source);
+                nav_view.push(&page);
+            });
 
-        // Display each category with directory grouping
-        if !added.is_empty() {
-            Self::create_grouped_file_section(
-                &content,
-                "Added Files",
-                &added,
-                "list-add-symbolic",
-            );
+            dirs_group.add(&row);
         }
+        content.append(&dirs_group);
 
-        if !modified.is_empty() {
-            Self::create_grouped_file_section(
-                &content,
-                "Modified Files",
-                &modified,
-                "document-edit-symbolic",
-            );
-        }
+        content
+    }
 
-        if !deleted.is_empty() {
-            Self::create_grouped_file_section(
-                &content,
-                "Deleted Files",
-                &deleted,
-                "list-remove-symbolic",
-            );
+    /// Count changes per top-level directory, sorted by total (descending)
+    fn summarize_changes(changes: &[FileChange]) -> Vec<DirectoryChanges> {
+        use std::collections::HashMap;
+
+        let mut dirs: HashMap<String, DirectoryChanges> = HashMap::new();
+        for change in changes {
+            let path = Self::extract_top_level_dir(&change.path);
+            let entry = dirs.entry(path.clone()).or_insert_with(|| DirectoryChanges {
+                path,
+                ..Default::default()
+            });
+            match change.change_type.as_str() {
+                "Added" => entry.added += 1,
+                "Modified" => entry.modified += 1,
+                "Deleted" => entry.deleted += 1,
+                "Renamed" => entry.renamed += 1,
+                _ => {}
+            }
         }
 
-        content
+        let mut summary: Vec<DirectoryChanges> = dirs.into_values().collect();
+        summary.sort_by(|a, b| b.total().cmp(&a.total()).then_with(|| a.path.cmp(&b.path)));
+        summary
     }
 
-    /// Create a grouped file section (Added/Modified/Deleted)
-    fn create_grouped_file_section(
-        parent: &Box,
-        title: &str,
-        files: &[&FileChange],
-        icon_name: &str,
-    ) {
-        use std::collections::HashMap;
+    /// Create a page listing the changed files below one top-level directory
+    ///
+    /// Files that exist in the older snapshot can be selected and restored from it.
+    fn create_directory_changes_page(
+        dir: &str,
+        changes: &[FileChange],
+        restore_source: &RestoreSource,
+    ) -> adw::NavigationPage {
+        let page = adw::NavigationPage::new(&adw::ToolbarView::new(), dir);
+        let toolbar_view = page.child().and_downcast::<adw::ToolbarView>().unwrap();
 
-        // Group files by top-level directory
-        let mut groups: HashMap<String, Vec<&FileChange>> = HashMap::new();
+        let header = adw::HeaderBar::new();
+        let restore_btn = gtk::Button::with_label(&gettext("Restore Selected"));
+        restore_btn.add_css_class("suggested-action");
+        restore_btn.set_tooltip_text(Some(&gettext_f(
+            "Restore the selected files from {snapshot}",
+            &[("snapshot", &restore_source.snapshot)],
+        )));
+        restore_btn.set_sensitive(false);
+        header.pack_end(&restore_btn);
+        toolbar_view.add_top_bar(&header);
 
-        for file in files {
-            let dir = Self::extract_top_level_dir(&file.path);
-            groups.entry(dir).or_default().push(*file);
-        }
+        // Encoded paths of the selected files
+        let selected: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
 
-        // Convert to Vec and sort by file count (descending)
-        let mut sorted_groups: Vec<(String, Vec<&FileChange>)> = groups.into_iter().collect();
-        sorted_groups.sort_by(|a, b| b.1.len().cmp(&a.1.len()));
+        let content = Box::new(Orientation::Vertical, 12);
+        content.set_margin_top(12);
+        content.set_margin_bottom(12);
+        content.set_margin_start(12);
+        content.set_margin_end(12);
+
+        let files: Vec<&FileChange> = changes
+            .iter()
+            .filter(|change| Self::extract_top_level_dir(&change.path) == dir)
+            .collect();
+
+        for (change_type, title, icon_name) in [
+            ("Added", gettext("Added Files"), "list-add-symbolic"),
+            (
+                "Modified",
+                gettext("Modified Files"),
+                "document-edit-symbolic",
+            ),
+            ("Deleted", gettext("Deleted Files"), "list-remove-symbolic"),
+            (
+                "Renamed",
+                gettext("Renamed Files"),
+                "document-save-as-symbolic",
+            ),
+        ] {
+            let section: Vec<&FileChange> = files
+                .iter()
+                .copied()
+                .filter(|change| change.change_type == change_type)
+                .collect();
+            if section.is_empty() {
+                continue;
+            }
 
-        // Create section header
-        let section_group = adw::PreferencesGroup::new();
-        section_group.set_title(&format!("{} ({})", title, files.len()));
-        parent.append(&section_group);
+            let group = adw::PreferencesGroup::new();
+            group.set_title(&gettext_f(
+                "{title} ({count})",
+                &[("title", &title), ("count", &section.len().to_string())],
+            ));
 
-        // Display each directory group
-        for (dir, dir_files) in sorted_groups {
-            let expander = adw::ExpanderRow::new();
-            expander.set_title(&format!("{} ({} files)", dir, dir_files.len()));
+            let list = ListBox::new();
+            list.add_css_class("boxed-list");
+            list.set_selection_mode(gtk::SelectionMode::None);
 
-            // Show first 5 files
-            let display_count = std::cmp::min(5, dir_files.len());
-            for file in dir_files.iter().take(display_count) {
+            for file in section.iter().take(MAX_DIRECTORY_ROWS) {
                 let row = adw::ActionRow::new();
-                // Strip directory prefix for cleaner display
-                let short_name = Self::strip_directory_prefix(&file.path, &dir);
-                row.set_title(&short_name);
+                row.set_title(&Self::strip_directory_prefix(&file.path, dir));
+                if let Some(old_path) = &file.old_path {
+                    row.set_subtitle(&gettext_f("Renamed from {path}", &[("path", old_path)]));
+                }
+                super::file_diff_dialog::flag_non_utf8_path(&row, &file.path);
                 row.add_prefix(&gtk::Image::from_icon_name(icon_name));
-                expander.add_row(&row);
+
+                if restore_source.has_file(file) {
+                    let check = gtk::CheckButton::new();
+                    check.set_valign(gtk::Align::Center);
+                    row.add_prefix(&check);
+                    row.set_activatable_widget(Some(&check));
+
+                    let path = restore_source.file_path(file).to_string();
+                    let selected = selected.clone();
+                    let restore_btn = restore_btn.clone();
+                    check.connect_toggled(move |check| {
+                        let mut selected = selected.borrow_mut();
+                        if check.is_active() {
+                            selected.push(path.clone());
+                        } else {
+                            selected.retain(|p| p != &path);
+                        }
+                        restore_btn.set_sensitive(!selected.is_empty());
+                    });
+                }
+
+                list.append(&row);
             }
 
-            // Add "... and X other files" row if there are more
-            if dir_files.len() > 5 {
+            if section.len() > MAX_DIRECTORY_ROWS {
                 let more_row = adw::ActionRow::new();
-                more_row.set_title(&format!("... and {} other files", dir_files.len() - 5));
+                more_row.set_title(&gettext_f(
+                    "... and {count} other files (export the comparison for the full list)",
+                    &[("count", &(section.len() - MAX_DIRECTORY_ROWS).to_string())],
+                ));
                 more_row.add_css_class("dim-label");
-                expander.add_row(&more_row);
+                list.append(&more_row);
             }
 
-            section_group.add(&expander);
+            group.add(&list);
+            content.append(&group);
         }
+
+        let scrolled = ScrolledWindow::new();
+        scrolled.set_child(Some(&content));
+        toolbar_view.set_content(Some(&scrolled));
+
+        let snapshot = restore_source.snapshot.clone();
+        let page_clone = page.clone();
+        restore_btn.connect_clicked(move |_| {
+            Self::restore_selected_files(&page_clone, &snapshot, selected.borrow().clone());
+        });
+
+        page
+    }
+
+    /// Close the comparison and ask how to restore the files from the snapshot
+    fn restore_selected_files(page: &adw::NavigationPage, snapshot: &str, files: Vec<String>) {
+        // The comparison window is modal, so the restore dialogs go on the main window
+        let Some(dialog) = page.root().and_downcast::<gtk::Window>() else {
+            return;
+        };
+        let Some(main_window) = dialog
+            .transient_for()
+            .and_downcast::<adw::ApplicationWindow>()
+        else {
+            log::error!("Comparison window has no main window to restore from");
+            return;
+        };
+
+        dialog.close();
+        super::file_restore_dialog::confirm_restore_paths(&main_window, snapshot, files, false);
     }
 
     /// Extract top-level directory from a path
@@ -792,12 +1314,12 @@ impl ComparisonView {
 
         // Create file chooser dialog
         let dialog = gtk::FileDialog::new();
-        dialog.set_title("Export File Comparison");
+        dialog.set_title(&gettext("Export File Comparison"));
         dialog.set_initial_name(Some(&format!("file_changes_{snap1_name}_{snap2_name}.txt")));
 
         // Set default filter for text files
         let filter = gtk::FileFilter::new();
-        filter.set_name(Some("Text files"));
+        filter.set_name(Some(&gettext("Text files")));
         filter.add_pattern("*.txt");
         let filters = gio::ListStore::new::<gtk::FileFilter>();
         filters.append(&filter);
@@ -838,12 +1360,14 @@ impl ComparisonView {
         let mut added: Vec<&FileChange> = Vec::new();
         let mut modified: Vec<&FileChange> = Vec::new();
         let mut deleted: Vec<&FileChange> = Vec::new();
+        let mut renamed: Vec<&FileChange> = Vec::new();
 
         for change in changes {
             match change.change_type.as_str() {
                 "Added" => added.push(change),
                 "Modified" => modified.push(change),
                 "Deleted" => deleted.push(change),
+                "Renamed" => renamed.push(change),
                 _ => {}
             }
         }
@@ -858,6 +1382,7 @@ impl ComparisonView {
         writeln!(file, "  {} files added", added.len())?;
         writeln!(file, "  {} files modified", modified.len())?;
         writeln!(file, "  {} files deleted", deleted.len())?;
+        writeln!(file, "  {} files renamed", renamed.len())?;
         writeln!(file)?;
 
         if !added.is_empty() {
@@ -887,6 +1412,16 @@ impl ComparisonView {
             writeln!(file)?;
         }
 
+        if !renamed.is_empty() {
+            writeln!(file, "Renamed Files ({}):", renamed.len())?;
+            writeln!(file, "{}", "-".repeat(60))?;
+            for change in &renamed {
+                let old_path = change.old_path.as_deref().unwrap_or("?");
+                writeln!(file, "  > {old_path} -> {}", change.path)?;
+            }
+            writeln!(file)?;
+        }
+
         if changes.is_empty() {
             writeln!(file, "No file changes detected.")?;
             writeln!(file, "Both snapshots have identical files.")?;
@@ -901,12 +1436,12 @@ impl ComparisonView {
 
         // Create file chooser dialog
         let dialog = gtk::FileDialog::new();
-        dialog.set_title("Export Package Comparison");
+        dialog.set_title(&gettext("Export Package Comparison"));
         dialog.set_initial_name(Some(&format!("comparison_{snap1_name}_{snap2_name}.txt")));
 
         // Set default filter for text files
         let filter = gtk::FileFilter::new();
-        filter.set_name(Some("Text files"));
+        filter.set_name(Some(&gettext("Text files")));
         filter.add_pattern("*.txt");
         let filters = gio::ListStore::new::<gtk::FileFilter>();
         filters.append(&filter);
@@ -996,3 +1531,42 @@ impl ComparisonView {
         &self.widget
     }
 }
+
+/// Describe the time from `base` to `other`, e.g. "3 days, 4 hours later"
+fn format_elapsed(
+    base: chrono::DateTime<chrono::Utc>,
+    other: chrono::DateTime<chrono::Utc>,
+) -> String {
+    let seconds = other.signed_duration_since(base).num_seconds();
+    let mut remaining = seconds.unsigned_abs();
+
+    // The largest unit and the one after it are precise enough
+    let mut parts = Vec::new();
+    let mut started = false;
+    for unit_seconds in [86_400, 3_600, 60] {
+        let count = remaining / unit_seconds;
+        remaining %= unit_seconds;
+        if count > 0 {
+            let number = count.to_string();
+            let args = [("count", number.as_str())];
+            parts.push(match unit_seconds {
+                86_400 => ngettext_f("{count} day", "{count} days", count, &args),
+                3_600 => ngettext_f("{count} hour", "{count} hours", count, &args),
+                _ => ngettext_f("{count} minute", "{count} minutes", count, &args),
+            });
+        }
+        if started {
+            break;
+        }
+        started = count > 0;
+    }
+
+    let elapsed = parts.join(", ");
+    if parts.is_empty() {
+        gettext("Taken less than a minute apart")
+    } else if seconds < 0 {
+        gettext_f("Taken {elapsed} earlier", &[("elapsed", &elapsed)])
+    } else {
+        gettext_f("Taken {elapsed} later", &[("elapsed", &elapsed)])
+    }
+}
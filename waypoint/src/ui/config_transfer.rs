@@ -0,0 +1,299 @@
+//! Export and import of all Waypoint settings
+//!
+//! System-wide configuration goes through the privileged helper, while per-user
+//! settings (backup destinations, manual snapshot subvolumes) are read and written
+//! directly. Everything is packed into a single JSON `ConfigBundle`.
+
+use crate::backup_manager::BackupManager;
+use crate::dbus_client::WaypointHelperClient;
+use crate::i18n::{gettext, gettext_f, gettext_noop};
+use gtk::gio;
+use gtk::glib;
+use gtk::prelude::*;
+use libadwaita as adw;
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::mpsc;
+use waypoint_common::{BackupConfig, ConfigBundle};
+
+use super::{dialogs, preferences};
+
+/// Run a blocking operation in a background thread and deliver its result on the main thread
+fn run_in_background<T, F, C>(operation: F, on_done: C)
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+    C: FnOnce(T) + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(operation());
+    });
+
+    glib::spawn_future_local(async move {
+        loop {
+            match rx.try_recv() {
+                Ok(result) => {
+                    on_done(result);
+                    break;
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    log::error!("Configuration transfer thread disconnected");
+                    break;
+                }
+            }
+        }
+    });
+}
+
+/// File filter for settings bundles
+fn json_filters() -> gio::ListStore {
+    let filter = gtk::FileFilter::new();
+    filter.set_name(Some(&gettext("Waypoint settings (*.json)")));
+    filter.add_pattern("*.json");
+    let filters = gio::ListStore::new::<gtk::FileFilter>();
+    filters.append(&filter);
+    filters
+}
+
+/// Export all settings to a JSON file chosen by the user
+pub fn export_settings(
+    window: &adw::ApplicationWindow,
+    backup_manager: &Rc<RefCell<BackupManager>>,
+) {
+    let dialog = gtk::FileDialog::new();
+    dialog.set_title(&gettext("Export Settings"));
+    dialog.set_initial_name(Some(&format!(
+        "waypoint-settings-{}.json",
+        chrono::Local::now().format("%Y%m%d")
+    )));
+    dialog.set_filters(Some(&json_filters()));
+
+    let window_clone = window.clone();
+    let backup_config = backup_manager.borrow().get_config();
+
+    dialog.save(
+        Some(window),
+        None::<&gio::Cancellable>,
+        move |result| {
+            let Some(path) = result.ok().and_then(|file| file.path()) else {
+                return;
+            };
+
+            let backup_config = match backup_config {
+                Ok(config) => config,
+                Err(e) => {
+                    dialogs::show_error(
+                        &window_clone,
+                        &gettext("Export Failed"),
+                        &gettext_f(
+                            "Failed to read backup settings: {error}",
+                            &[("error", &e.to_string())],
+                        ),
+                    );
+                    return;
+                }
+            };
+
+            let subvolumes = preferences::load_config();
+
+            run_in_background(
+                move || write_bundle(&path, &backup_config, subvolumes).map(|_| path),
+                move |result| match result {
+                    Ok(path) => {
+                        log::info!("Exported settings to {}", path.display());
+                        dialogs::show_toast(&window_clone, &gettext("Settings exported"));
+                    }
+                    Err(e) => {
+                        log::error!("Failed to export settings: {e}");
+                        dialogs::show_error(
+                            &window_clone,
+                            &gettext("Export Failed"),
+                            &gettext_f(
+                                "Failed to export settings: {error}",
+                                &[("error", &e.to_string())],
+                            ),
+                        );
+                    }
+                },
+            );
+        },
+    );
+}
+
+/// Build the bundle from helper and user settings and write it to `path`
+fn write_bundle(
+    path: &std::path::Path,
+    backup_config: &BackupConfig,
+    subvolumes: Vec<PathBuf>,
+) -> anyhow::Result<()> {
+    let client = WaypointHelperClient::new()?;
+    let mut bundle = ConfigBundle::from_json(&client.export_config()?)?;
+
+    bundle.backup = Some(toml::to_string_pretty(backup_config)?);
+    bundle.subvolumes = Some(subvolumes);
+
+    std::fs::write(path, bundle.to_json()?)?;
+    Ok(())
+}
+
+/// Import settings from a JSON file chosen by the user
+pub fn import_settings(
+    window: &adw::ApplicationWindow,
+    backup_manager: &Rc<RefCell<BackupManager>>,
+) {
+    let dialog = gtk::FileDialog::new();
+    dialog.set_title(&gettext("Import Settings"));
+    dialog.set_filters(Some(&json_filters()));
+
+    let window_clone = window.clone();
+    let backup_manager = backup_manager.clone();
+
+    dialog.open(
+        Some(window),
+        None::<&gio::Cancellable>,
+        move |result| {
+            let Some(path) = result.ok().and_then(|file| file.path()) else {
+                return;
+            };
+
+            // Validate the whole bundle before asking for confirmation
+            let bundle = match std::fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|json| ConfigBundle::from_json(&json))
+            {
+                Ok(bundle) => bundle,
+                Err(e) => {
+                    dialogs::show_error(
+                        &window_clone,
+                        &gettext("Import Failed"),
+                        &gettext_f(
+                            "{path} is not a valid settings file:\n\n{error}",
+                            &[
+                                ("path", &path.to_string_lossy()),
+                                ("error", &format!("{e:#}")),
+                            ],
+                        ),
+                    );
+                    return;
+                }
+            };
+
+            let sections = describe_sections(&bundle);
+            if sections.is_empty() {
+                dialogs::show_error(
+                    &window_clone,
+                    &gettext("Import Failed"),
+                    &gettext("The settings file does not contain any settings."),
+                );
+                return;
+            }
+
+            let window_confirm = window_clone.clone();
+            let backup_manager = backup_manager.clone();
+            dialogs::show_confirmation(
+                &window_clone,
+                &gettext("Import Settings?"),
+                &gettext_f(
+                    "This will replace your current {sections}.\n\nSystem settings require administrator authentication.",
+                    &[("sections", &sections.join(", "))],
+                ),
+                &gettext("Import"),
+                true,
+                move || apply_bundle(&window_confirm, &backup_manager, bundle.clone()),
+            );
+        },
+    );
+}
+
+/// Human-readable names of the sections present in a bundle
+fn describe_sections(bundle: &ConfigBundle) -> Vec<String> {
+    [
+        (
+            bundle.schedules.is_some(),
+            gettext_noop("snapshot schedules"),
+        ),
+        (bundle.quota.is_some(), gettext_noop("quota settings")),
+        (bundle.exclude.is_some(), gettext_noop("exclusion patterns")),
+        (bundle.backup.is_some(), gettext_noop("backup destinations")),
+        (
+            bundle.subvolumes.is_some(),
+            gettext_noop("manual snapshot subvolumes"),
+        ),
+    ]
+    .into_iter()
+    .filter(|(present, _)| *present)
+    .map(|(_, name)| gettext(name))
+    .collect()
+}
+
+/// Apply a validated bundle: system settings via the helper first, then user settings
+fn apply_bundle(
+    window: &adw::ApplicationWindow,
+    backup_manager: &Rc<RefCell<BackupManager>>,
+    bundle: ConfigBundle,
+) {
+    let window_clone = window.clone();
+    let backup_manager = backup_manager.clone();
+    let bundle_for_helper = bundle.clone();
+
+    run_in_background(
+        move || -> anyhow::Result<()> {
+            if bundle_for_helper.has_system_config() {
+                let client = WaypointHelperClient::new()?;
+                let msg = client.import_config(bundle_for_helper.to_json()?)?;
+                log::info!("{msg}");
+            }
+            Ok(())
+        },
+        move |result| {
+            if let Err(e) = result {
+                dialogs::show_error(
+                    &window_clone,
+                    &gettext("Import Failed"),
+                    &gettext_f(
+                        "Failed to import system settings: {error}",
+                        &[("error", &e.to_string())],
+                    ),
+                );
+                return;
+            }
+
+            // Only touch user settings once the system import succeeded
+            if let Err(e) = apply_user_settings(&backup_manager, &bundle) {
+                dialogs::show_error(
+                    &window_clone,
+                    &gettext("Import Incomplete"),
+                    &gettext_f(
+                        "System settings were imported, but user settings failed: {error}",
+                        &[("error", &e.to_string())],
+                    ),
+                );
+                return;
+            }
+
+            dialogs::show_toast(&window_clone, &gettext("Settings imported"));
+        },
+    );
+}
+
+/// Write the per-user sections of a bundle
+fn apply_user_settings(
+    backup_manager: &Rc<RefCell<BackupManager>>,
+    bundle: &ConfigBundle,
+) -> anyhow::Result<()> {
+    if let Some(backup) = &bundle.backup {
+        let config: BackupConfig = toml::from_str(backup)?;
+        backup_manager.borrow().replace_config(config)?;
+    }
+
+    if let Some(subvolumes) = &bundle.subvolumes {
+        preferences::save_config(subvolumes)?;
+    }
+
+    Ok(())
+}
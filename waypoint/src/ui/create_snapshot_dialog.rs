@@ -1,7 +1,14 @@
 use adw::prelude::*;
+use gtk::glib;
 use gtk::prelude::*;
 use gtk::{Entry, Label, Orientation};
 use libadwaita as adw;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc;
+use waypoint_common::{SchedulesConfig, SnapshotCategory, WaypointConfig, validate_snapshot_name};
+
+use crate::i18n::{gettext, gettext_noop};
 
 /// Sanitize description text to prevent issues
 fn sanitize_description(desc: &str) -> String {
@@ -20,20 +27,153 @@ fn sanitize_description(desc: &str) -> String {
     sanitized
 }
 
+/// Values substituted into a description template
+struct TemplateValues {
+    date: String,
+    time: String,
+    kernel: Option<String>,
+    updates: Option<usize>,
+    hostname: Option<String>,
+}
+
+/// Replace `{date}`, `{time}`, `{kernel}`, `{updates}` and `{hostname}` in a template
+///
+/// Unknown tokens are left untouched and unavailable values become "unknown".
+fn expand_description_template(template: &str, values: &TemplateValues) -> String {
+    let unknown = || "unknown".to_string();
+    template
+        .replace("{date}", &values.date)
+        .replace("{time}", &values.time)
+        .replace("{kernel}", &values.kernel.clone().unwrap_or_else(unknown))
+        .replace(
+            "{updates}",
+            &values.updates.map(|n| n.to_string()).unwrap_or_else(unknown),
+        )
+        .replace("{hostname}", &values.hostname.clone().unwrap_or_else(unknown))
+}
+
+/// Expand the configured description template for the current time
+///
+/// Only runs the (slower) pending update query when the template uses `{updates}`.
+/// That query can take seconds, so call this off the main thread.
+pub fn default_description() -> String {
+    let template = super::preferences::load_manual_settings().description_template;
+
+    let updates = if template.contains("{updates}") {
+        crate::packages::count_pending_updates()
+            .map_err(|e| log::warn!("Could not count pending updates: {e}"))
+            .ok()
+    } else {
+        None
+    };
+
+    describe_now(&template, updates)
+}
+
+/// Expand a description template for the current time with a known update count
+fn describe_now(template: &str, updates: Option<usize>) -> String {
+    let now = chrono::Local::now();
+    let values = TemplateValues {
+        date: now.format("%Y-%m-%d").to_string(),
+        time: now.format("%H:%M").to_string(),
+        kernel: waypoint_common::kernel_version(),
+        updates,
+        hostname: std::fs::read_to_string("/etc/hostname")
+            .ok()
+            .map(|h| h.trim().to_string())
+            .filter(|h| !h.is_empty()),
+    };
+
+    sanitize_description(&expand_description_template(template, &values))
+}
+
+/// Default snapshot name for the current time
+fn default_name() -> String {
+    let timestamp = chrono::Utc::now();
+    format!("waypoint-{}", timestamp.format("%Y%m%d-%H%M%S"))
+}
+
+/// Generate the default snapshot name and description for the current time
+///
+/// Blocks like `default_description`, so call this off the main thread.
+pub fn default_name_and_description() -> (String, String) {
+    (default_name(), default_description())
+}
+
+/// Check a snapshot name typed into the dialog
+///
+/// Besides the usual name rules, names starting with a schedule prefix are
+/// refused, since that schedule's retention would treat them as its own.
+fn check_manual_name(name: &str, schedules: &SchedulesConfig) -> Result<(), String> {
+    validate_snapshot_name(name)?;
+    schedules.check_manual_name(name)
+}
+
+/// Lifetimes offered for temporary snapshots, in days
+const EXPIRY_PRESETS: &[(&str, i64)] = &[
+    (gettext_noop("1 day"), 1),
+    (gettext_noop("3 days"), 3),
+    (gettext_noop("1 week"), 7),
+    (gettext_noop("2 weeks"), 14),
+    (gettext_noop("1 month"), 30),
+    (gettext_noop("3 months"), 90),
+];
+
+/// Dropdown choosing when a snapshot expires, starting with "Never"
+pub fn expiry_dropdown() -> gtk::DropDown {
+    let labels: Vec<String> = std::iter::once(gettext("Never"))
+        .chain(EXPIRY_PRESETS.iter().map(|(label, _)| gettext(label)))
+        .collect();
+    let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+
+    gtk::DropDown::from_strings(&labels)
+}
+
+/// Lifetime selected in an `expiry_dropdown`, None for "Never"
+pub fn selected_expiry(dropdown: &gtk::DropDown) -> Option<chrono::Duration> {
+    let index = (dropdown.selected() as usize).checked_sub(1)?;
+    EXPIRY_PRESETS
+        .get(index)
+        .map(|(_, days)| chrono::Duration::days(*days))
+}
+
+/// Dropdown choosing why a snapshot is taken, starting with "Manual"
+pub fn category_dropdown() -> gtk::DropDown {
+    let labels: Vec<&str> = SnapshotCategory::ALL
+        .iter()
+        .map(|category| category.label())
+        .collect();
+    gtk::DropDown::from_strings(&labels)
+}
+
+/// Category selected in a `category_dropdown`
+pub fn selected_category(dropdown: &gtk::DropDown) -> SnapshotCategory {
+    SnapshotCategory::ALL
+        .get(dropdown.selected() as usize)
+        .copied()
+        .unwrap_or_default()
+}
+
 /// Show dialog to get custom description for snapshot (callback-based)
+///
+/// The callback gets the name, description, how long to keep temporary
+/// snapshots and the category.
 pub fn show_create_snapshot_dialog_async<F>(parent: &adw::ApplicationWindow, callback: F)
 where
-    F: Fn(Option<(String, String)>) + 'static,
+    F: Fn(Option<(String, String, Option<chrono::Duration>, SnapshotCategory)>) + 'static,
 {
-    let timestamp = chrono::Utc::now();
-    let default_name = format!("waypoint-{}", timestamp.format("%Y%m%d-%H%M%S"));
-    let default_desc = format!("System snapshot {}", timestamp.format("%Y-%m-%d %H:%M"));
+    let default_name = default_name();
+
+    // Counting pending updates for the default description can take seconds
+    let default_desc: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
 
     // Create dialog
     let dialog = adw::MessageDialog::new(
         Some(parent),
-        Some("Create Restore Point"),
-        Some("Give this snapshot a description to help identify it later."),
+        Some(&gettext("Create Restore Point")),
+        Some(&gettext(
+            "Give this snapshot a description to help identify it later.",
+        )),
     );
 
     // Create custom content
@@ -41,21 +181,96 @@ where
     content.set_margin_top(12);
     content.set_margin_bottom(12);
 
+    // Name entry, left blank for the generated name shown as placeholder
+    let name_label = Label::new(Some(&gettext("Name:")));
+    name_label.set_halign(gtk::Align::Start);
+    content.append(&name_label);
+
+    let name_entry = Entry::new();
+    name_entry.set_placeholder_text(Some(&default_name));
+    name_entry.set_activates_default(true);
+    content.append(&name_entry);
+
+    let name_error = Label::new(None);
+    name_error.set_wrap(true);
+    name_error.set_halign(gtk::Align::Start);
+    name_error.add_css_class("error");
+    name_error.add_css_class("caption");
+    name_error.set_visible(false);
+    content.append(&name_error);
+
     // Description entry
-    let desc_label = Label::new(Some("Description:"));
+    let desc_label = Label::new(Some(&gettext("Description:")));
     desc_label.set_halign(gtk::Align::Start);
     content.append(&desc_label);
 
+    // Left blank, the snapshot gets the default description shown as placeholder
     let desc_entry = Entry::new();
-    desc_entry.set_text(&default_desc);
-    desc_entry.set_placeholder_text(Some("e.g., Before Docker installation"));
+    desc_entry.set_placeholder_text(Some(&gettext("Preparing the default description…")));
+    {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(default_description());
+        });
+
+        let desc_entry = desc_entry.clone();
+        let default_desc = default_desc.clone();
+        glib::spawn_future_local(async move {
+            let description = loop {
+                match rx.try_recv() {
+                    Ok(description) => break description,
+                    Err(mpsc::TryRecvError::Empty) => {
+                        glib::timeout_future(std::time::Duration::from_millis(50)).await;
+                    }
+                    Err(mpsc::TryRecvError::Disconnected) => return,
+                }
+            };
+            desc_entry.set_placeholder_text(Some(&description));
+            *default_desc.borrow_mut() = Some(description);
+        });
+    }
+    desc_entry.set_tooltip_text(Some(&gettext(
+        "Leave blank to use the default description (configurable in Preferences)",
+    )));
     desc_entry.set_activates_default(true);
     content.append(&desc_entry);
 
-    // Info label
-    let info = Label::new(Some(
-        "The snapshot will be automatically named based on the current date and time.",
+    // Categories filter and color the list; milestones are never cleaned up
+    let category_box = gtk::Box::new(Orientation::Horizontal, 12);
+    let category_label = Label::new(Some(&gettext("Category:")));
+    category_label.set_halign(gtk::Align::Start);
+    category_label.set_hexpand(true);
+    category_box.append(&category_label);
+    let category = category_dropdown();
+    category.set_tooltip_text(Some(&gettext(
+        "Milestones are never deleted by retention or quota cleanup",
+    )));
+    category_box.append(&category);
+    category_box.set_visible(crate::dbus_client::helper_supports(
+        waypoint_common::capabilities::FEATURE_SNAPSHOT_CATEGORY,
+    ));
+    content.append(&category_box);
+
+    // Temporary snapshots are deleted by cleanup once they expire
+    let expiry_box = gtk::Box::new(Orientation::Horizontal, 12);
+    let expiry_label = Label::new(Some(&gettext("Delete after:")));
+    expiry_label.set_halign(gtk::Align::Start);
+    expiry_label.set_hexpand(true);
+    expiry_box.append(&expiry_label);
+    let expiry = expiry_dropdown();
+    expiry.set_tooltip_text(Some(&gettext(
+        "Cleanup deletes the snapshot once it expires, unless it's pinned",
+    )));
+    expiry_box.append(&expiry);
+    expiry_box.set_visible(crate::dbus_client::helper_supports(
+        waypoint_common::capabilities::FEATURE_SNAPSHOT_EXPIRY,
     ));
+    content.append(&expiry_box);
+
+    // Info label
+    let info = Label::new(Some(&gettext(
+        "Leave the name blank to name the snapshot after the current date and time.",
+    )));
     info.set_wrap(true);
     info.add_css_class("dim-label");
     info.set_halign(gtk::Align::Start);
@@ -64,18 +279,58 @@ where
 
     dialog.set_extra_child(Some(&content));
 
-    dialog.add_response("cancel", "Cancel");
-    dialog.add_response("create", "Create");
+    dialog.add_response("cancel", &gettext("Cancel"));
+    dialog.add_response("create", &gettext("Create"));
     dialog.set_response_appearance("create", adw::ResponseAppearance::Suggested);
     dialog.set_default_response(Some("create"));
     dialog.set_close_response("cancel");
 
+    // Refuse invalid names and names a schedule's retention would claim
+    let schedules = SchedulesConfig::load_or_default(&WaypointConfig::new().schedules_config);
+    let chosen_name = {
+        let name_entry = name_entry.clone();
+        let default_name = default_name.clone();
+        move || {
+            let name = name_entry.text().trim().to_string();
+            if name.is_empty() {
+                default_name.clone()
+            } else {
+                name
+            }
+        }
+    };
+    let update_name_error = {
+        let dialog = dialog.clone();
+        let chosen_name = chosen_name.clone();
+        move || {
+            let result = check_manual_name(&chosen_name(), &schedules);
+            name_error.set_label(result.as_ref().err().map_or("", String::as_str));
+            name_error.set_visible(result.is_err());
+            dialog.set_response_enabled("create", result.is_ok());
+        }
+    };
+    update_name_error();
+    name_entry.connect_changed(move |_| update_name_error());
+
     // Handle response
-    let default_name_clone = default_name.clone();
     dialog.connect_response(None, move |_, response| {
         if response == "create" {
-            let description = sanitize_description(&desc_entry.text());
-            callback(Some((default_name_clone.clone(), description)));
+            let mut description = sanitize_description(&desc_entry.text());
+            if description.is_empty() {
+                // Without the update count if it isn't known yet
+                description = default_desc.borrow().clone().unwrap_or_else(|| {
+                    describe_now(
+                        &super::preferences::load_manual_settings().description_template,
+                        None,
+                    )
+                });
+            }
+            callback(Some((
+                chosen_name(),
+                description,
+                selected_expiry(&expiry),
+                selected_category(&category),
+            )));
         } else {
             callback(None);
         }
@@ -87,7 +342,6 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use waypoint_common::validate_snapshot_name;
 
     #[test]
     fn test_valid_snapshot_names() {
@@ -119,6 +373,27 @@ mod tests {
         assert!(validate_snapshot_name(".hidden").is_err());
     }
 
+    #[test]
+    fn test_expand_description_template() {
+        let values = TemplateValues {
+            date: "2025-01-15".to_string(),
+            time: "14:30".to_string(),
+            kernel: Some("6.6.8_1".to_string()),
+            updates: Some(12),
+            hostname: None,
+        };
+
+        assert_eq!(
+            expand_description_template("Snapshot {date} {time} (kernel {kernel})", &values),
+            "Snapshot 2025-01-15 14:30 (kernel 6.6.8_1)"
+        );
+        assert_eq!(
+            expand_description_template("{updates} updates pending on {hostname}", &values),
+            "12 updates pending on unknown"
+        );
+        assert_eq!(expand_description_template("{other}", &values), "{other}");
+    }
+
     #[test]
     fn test_sanitize_description() {
         // Trim whitespace
@@ -1,3 +1,4 @@
+use crate::i18n::gettext;
 use adw::prelude::*;
 use gtk::prelude::*;
 use libadwaita as adw;
@@ -15,7 +16,7 @@ pub fn show_confirmation<F>(
 {
     let dialog = adw::MessageDialog::new(Some(window), Some(title), Some(message));
 
-    dialog.add_response("cancel", "Cancel");
+    dialog.add_response("cancel", &gettext("Cancel"));
     dialog.add_response("confirm", confirm_label);
 
     if destructive {
@@ -51,7 +52,7 @@ pub fn show_confirmation_with_cancel<F1, F2>(
 {
     let dialog = adw::MessageDialog::new(Some(window), Some(title), Some(message));
 
-    dialog.add_response("cancel", "Cancel");
+    dialog.add_response("cancel", &gettext("Cancel"));
     dialog.add_response("confirm", confirm_label);
 
     if destructive {
@@ -77,17 +78,16 @@ pub fn show_confirmation_with_cancel<F1, F2>(
 /// Show an error dialog
 pub fn show_error(window: &adw::ApplicationWindow, title: &str, message: &str) {
     let dialog = adw::MessageDialog::new(Some(window), Some(title), Some(message));
-    dialog.add_response("ok", "OK");
+    dialog.add_response("ok", &gettext("OK"));
     dialog.set_default_response(Some("ok"));
     dialog.set_close_response("ok");
     dialog.present();
 }
 
 /// Show an info dialog (ApplicationWindow version)
-#[allow(dead_code)]
 pub fn show_info(window: &adw::ApplicationWindow, title: &str, message: &str) {
     let dialog = adw::MessageDialog::new(Some(window), Some(title), Some(message));
-    dialog.add_response("ok", "OK");
+    dialog.add_response("ok", &gettext("OK"));
     dialog.set_default_response(Some("ok"));
     dialog.set_close_response("ok");
     dialog.present();
@@ -101,7 +101,7 @@ pub fn show_info_window(window: &adw::Window, title: &str, message: &str) {
         .heading(title)
         .body(message)
         .build();
-    dialog.add_response("ok", "OK");
+    dialog.add_response("ok", &gettext("OK"));
     dialog.set_default_response(Some("ok"));
     dialog.set_close_response("ok");
     dialog.present();
@@ -115,7 +115,7 @@ pub fn show_error_window(window: &adw::Window, title: &str, message: &str) {
         .heading(title)
         .body(message)
         .build();
-    dialog.add_response("ok", "OK");
+    dialog.add_response("ok", &gettext("OK"));
     dialog.set_default_response(Some("ok"));
     dialog.set_close_response("ok");
     dialog.present();
@@ -146,6 +146,28 @@ pub fn show_toast_with_timeout(
     println!("✓ {message}");
 }
 
+/// Show a toast with a button that stays until it is dismissed
+///
+/// Returns the toast so the caller can dismiss it once the operation it
+/// describes is over.
+pub fn show_toast_with_button<F>(
+    window: &adw::ApplicationWindow,
+    message: &str,
+    button_label: &str,
+    on_clicked: F,
+) -> Option<adw::Toast>
+where
+    F: Fn() + 'static,
+{
+    let toast_overlay = window.content()?.downcast::<adw::ToastOverlay>().ok()?;
+    let toast = adw::Toast::new(message);
+    toast.set_timeout(0);
+    toast.set_button_label(Some(button_label));
+    toast.connect_button_clicked(move |_| on_clicked());
+    toast_overlay.add_toast(toast.clone());
+    Some(toast)
+}
+
 /// Show a detailed error list dialog
 pub fn show_error_list(window: &adw::ApplicationWindow, title: &str, errors: &[String]) {
     use gtk::Orientation;
@@ -1,9 +1,13 @@
 //! User-friendly error messages with recovery suggestions
 //!
 //! This module transforms technical error messages into helpful, actionable
-//! messages that guide users toward solutions.
+//! messages that guide users toward solutions. Failures reported by the helper
+//! carry an `ErrorCode`, which is used instead of matching on the message text.
 
 use libadwaita as adw;
+use waypoint_common::{ErrorCode, HelperError};
+
+use crate::i18n::{gettext, gettext_f};
 
 /// Error context for providing better user guidance
 #[derive(Debug, Clone, Copy)]
@@ -33,6 +37,10 @@ pub fn show_error_with_context(
 ) {
     let (title, message, details) = format_error_message(context, error);
 
+    if HelperError::decode(error).code == ErrorCode::ReadOnlyFilesystem {
+        super::main_window_helpers::show_read_only_banner();
+    }
+
     // Build full message with details
     let full_message = if let Some(detail_text) = details {
         format!("{message}\n\n{detail_text}")
@@ -46,6 +54,29 @@ pub fn show_error_with_context(
 
 /// Format error message with helpful context and recovery suggestions
 fn format_error_message(context: ErrorContext, error: &str) -> (String, String, Option<String>) {
+    let error = &HelperError::decode(error);
+
+    if error.code == ErrorCode::RateLimited {
+        return (
+            gettext("Too Many Requests"),
+            gettext("This operation was attempted too often."),
+            Some(error.message.clone()),
+        );
+    }
+
+    if error.code == ErrorCode::ReadOnlyFilesystem {
+        return (
+            gettext("Filesystem Is Read-Only"),
+            gettext(
+                "The filesystem is mounted read-only, so snapshots can't be created, deleted or restored.",
+            ),
+            Some(gettext_f(
+                "Btrfs switches to read-only when it detects errors. Check the kernel log (`dmesg`) for the cause, repair the filesystem and remount it read-write, then try again.\n\nTechnical details: {error}",
+                &[("error", &error.to_string())],
+            )),
+        );
+    }
+
     match context {
         ErrorContext::SnapshotCreate => format_snapshot_create_error(error),
         ErrorContext::SnapshotDelete => format_snapshot_delete_error(error),
@@ -60,97 +91,141 @@ fn format_error_message(context: ErrorContext, error: &str) -> (String, String,
     }
 }
 
-fn format_snapshot_create_error(error: &str) -> (String, String, Option<String>) {
-    let title = "Failed to Create Snapshot".to_string();
+fn format_snapshot_create_error(error: &HelperError) -> (String, String, Option<String>) {
+    let title = gettext("Failed to Create Snapshot");
 
-    let (message, recovery) = if error.contains("not enough space")
-        || error.contains("No space left")
-    {
+    let (message, recovery) = if error.code == ErrorCode::MetadataFull {
+        (
+            gettext("Btrfs metadata space is full."),
+            Some(gettext_f(
+                "Run a balance from Analytics → Maintenance to free metadata space, then try again. Technical details: {error}",
+                &[("error", &error.to_string())],
+            )),
+        )
+    } else if matches!(
+        error.code,
+        ErrorCode::InsufficientSpace | ErrorCode::QuotaExceeded
+    ) {
         (
-            "Not enough disk space to create snapshot.".to_string(),
-            Some("Try deleting old snapshots or freeing up disk space before creating a new snapshot.".to_string())
+            gettext("Not enough disk space to create snapshot."),
+            Some(gettext(
+                "Try deleting old snapshots or freeing up disk space before creating a new snapshot.",
+            )),
         )
-    } else if error.contains("Authorization failed") || error.contains("not authorized") {
+    } else if error.code == ErrorCode::AuthFailed {
         (
-            "Permission denied.".to_string(),
-            Some("You need administrator privileges to create snapshots. Make sure you enter the correct password when prompted.".to_string())
+            gettext("Permission denied."),
+            Some(gettext(
+                "You need administrator privileges to create snapshots. Make sure you enter the correct password when prompted.",
+            )),
         )
-    } else if error.contains("not a btrfs") || error.contains("wrong fs type") {
+    } else if error.message.contains("not a btrfs") || error.message.contains("wrong fs type") {
         (
-            "Your root filesystem is not Btrfs.".to_string(),
-            Some("Waypoint requires a Btrfs filesystem to create snapshots. This system appears to be using a different filesystem type.".to_string())
+            gettext("Your root filesystem is not Btrfs."),
+            Some(gettext(
+                "Waypoint requires a Btrfs filesystem to create snapshots. This system appears to be using a different filesystem type.",
+            )),
         )
-    } else if error.contains("already exists") {
+    } else if error.code == ErrorCode::AlreadyExists {
         (
-            "A snapshot with this name already exists.".to_string(),
-            Some("Choose a different name for your snapshot.".to_string()),
+            gettext("A snapshot with this name already exists."),
+            Some(gettext("Choose a different name for your snapshot.")),
+        )
+    } else if error.code == ErrorCode::TimedOut {
+        (
+            gettext("Creating the snapshot took too long and was stopped."),
+            Some(gettext_f(
+                "Any partially created snapshot was removed. The filesystem may be busy; try again later. Technical details: {error}",
+                &[("error", &error.to_string())],
+            )),
         )
     } else {
         (
-            "An error occurred while creating the snapshot.".to_string(),
-            Some(format!("Technical details: {error}")),
+            gettext("An error occurred while creating the snapshot."),
+            Some(gettext_f(
+                "Technical details: {error}",
+                &[("error", &error.to_string())],
+            )),
         )
     };
 
     (title, message, recovery)
 }
 
-fn format_snapshot_delete_error(error: &str) -> (String, String, Option<String>) {
-    let title = "Failed to Delete Snapshot".to_string();
+fn format_snapshot_delete_error(error: &HelperError) -> (String, String, Option<String>) {
+    let title = gettext("Failed to Delete Snapshot");
 
-    let (message, recovery) = if error.contains("Authorization failed") {
+    let (message, recovery) = if error.code == ErrorCode::AuthFailed {
         (
-            "Permission denied.".to_string(),
-            Some("You need administrator privileges to delete snapshots.".to_string()),
+            gettext("Permission denied."),
+            Some(gettext(
+                "You need administrator privileges to delete snapshots.",
+            )),
         )
-    } else if error.contains("not found") || error.contains("does not exist") {
+    } else if error.code == ErrorCode::NotFound || error.message.contains("does not exist") {
         (
-            "Snapshot not found.".to_string(),
-            Some(
-                "The snapshot may have already been deleted. Try refreshing the list.".to_string(),
-            ),
+            gettext("Snapshot not found."),
+            Some(gettext(
+                "The snapshot may have already been deleted. Try refreshing the list.",
+            )),
         )
-    } else if error.contains("busy") || error.contains("in use") {
+    } else if error.code == ErrorCode::Busy || error.message.contains("in use") {
         (
-            "Snapshot is currently in use.".to_string(),
-            Some(
-                "Close any programs that might be accessing the snapshot and try again."
-                    .to_string(),
-            ),
+            gettext("Snapshot is currently in use."),
+            Some(gettext(
+                "Close any programs that might be accessing the snapshot and try again.",
+            )),
         )
     } else {
         (
-            "An error occurred while deleting the snapshot.".to_string(),
-            Some(format!("Technical details: {error}")),
+            gettext("An error occurred while deleting the snapshot."),
+            Some(gettext_f(
+                "Technical details: {error}",
+                &[("error", &error.to_string())],
+            )),
         )
     };
 
     (title, message, recovery)
 }
 
-fn format_snapshot_restore_error(error: &str) -> (String, String, Option<String>) {
-    let title = "Failed to Restore Snapshot".to_string();
+fn format_snapshot_restore_error(error: &HelperError) -> (String, String, Option<String>) {
+    let title = gettext("Failed to Restore Snapshot");
 
-    let (message, recovery) = if error.contains("Authorization failed") {
+    let (message, recovery) = if error.code == ErrorCode::AuthFailed {
         (
-            "Permission denied.".to_string(),
-            Some("You need administrator privileges to restore snapshots.".to_string()),
+            gettext("Permission denied."),
+            Some(gettext(
+                "You need administrator privileges to restore snapshots.",
+            )),
         )
-    } else if error.contains("not found") {
+    } else if error.code == ErrorCode::NotFound {
         (
-            "Snapshot not found.".to_string(),
-            Some("The snapshot may have been deleted. Check the snapshot list.".to_string()),
+            gettext("Snapshot not found."),
+            Some(gettext(
+                "The snapshot may have been deleted. Check the snapshot list.",
+            )),
         )
-    } else if error.contains("fstab") {
+    } else if error.code == ErrorCode::Busy {
         (
-            "Failed to update boot configuration.".to_string(),
-            Some("The system configuration file (/etc/fstab) could not be updated. Your system may require manual configuration.".to_string())
+            gettext("Snapshot is currently in use."),
+            Some(gettext(
+                "Another operation such as a backup is using this snapshot. Wait for it to finish and try again.",
+            )),
+        )
+    } else if error.message.contains("fstab") {
+        (
+            gettext("Failed to update boot configuration."),
+            Some(gettext(
+                "The system configuration file (/etc/fstab) could not be updated. Your system may require manual configuration.",
+            )),
         )
     } else {
         (
-            "An error occurred during snapshot restore.".to_string(),
-            Some(format!(
-                "Technical details: {error}\n\nNote: You must reboot for restore changes to take effect."
+            gettext("An error occurred during snapshot restore."),
+            Some(gettext_f(
+                "Technical details: {error}\n\nNote: You must reboot for restore changes to take effect.",
+                &[("error", &error.to_string())],
             )),
         )
     };
@@ -158,109 +233,127 @@ fn format_snapshot_restore_error(error: &str) -> (String, String, Option<String>
     (title, message, recovery)
 }
 
-fn format_snapshot_verify_error(error: &str) -> (String, String, Option<String>) {
-    let title = "Verification Failed".to_string();
+fn format_snapshot_verify_error(error: &HelperError) -> (String, String, Option<String>) {
+    let title = gettext("Verification Failed");
 
-    let (message, recovery) = if error.contains("not found") {
+    let (message, recovery) = if error.code == ErrorCode::NotFound {
         (
-            "Snapshot not found on disk.".to_string(),
-            Some("The snapshot directory may have been manually deleted. You can safely remove this entry from the list.".to_string())
+            gettext("Snapshot not found on disk."),
+            Some(gettext(
+                "The snapshot directory may have been manually deleted. You can safely remove this entry from the list.",
+            )),
         )
-    } else if error.contains("corrupt") {
+    } else if error.message.contains("corrupt") {
         (
-            "Snapshot appears to be corrupted.".to_string(),
-            Some("This snapshot should not be used for restore. Consider deleting it and creating a new one.".to_string())
+            gettext("Snapshot appears to be corrupted."),
+            Some(gettext(
+                "This snapshot should not be used for restore. Consider deleting it and creating a new one.",
+            )),
         )
     } else {
         (
-            "Unable to verify snapshot integrity.".to_string(),
-            Some(format!("Technical details: {error}")),
+            gettext("Unable to verify snapshot integrity."),
+            Some(gettext_f(
+                "Technical details: {error}",
+                &[("error", &error.to_string())],
+            )),
         )
     };
 
     (title, message, recovery)
 }
 
-fn format_snapshot_list_error(error: &str) -> (String, String, Option<String>) {
+fn format_snapshot_list_error(error: &HelperError) -> (String, String, Option<String>) {
     (
-        "Failed to Load Snapshots".to_string(),
-        "Unable to retrieve the snapshot list.".to_string(),
-        Some(format!(
-            "This could be a temporary issue. Try refreshing the list.\n\nTechnical details: {error}"
+        gettext("Failed to Load Snapshots"),
+        gettext("Unable to retrieve the snapshot list."),
+        Some(gettext_f(
+            "This could be a temporary issue. Try refreshing the list.\n\nTechnical details: {error}",
+            &[("error", &error.to_string())],
         )),
     )
 }
 
-fn format_disk_space_error(error: &str) -> (String, String, Option<String>) {
+fn format_disk_space_error(error: &HelperError) -> (String, String, Option<String>) {
     (
-        "Insufficient Disk Space".to_string(),
-        "Not enough free space to create a snapshot.".to_string(),
-        Some(format!(
-            "Delete old snapshots or free up disk space before proceeding.\n\nTechnical details: {error}"
+        gettext("Insufficient Disk Space"),
+        gettext("Not enough free space to create a snapshot."),
+        Some(gettext_f(
+            "Delete old snapshots or free up disk space before proceeding.\n\nTechnical details: {error}",
+            &[("error", &error.to_string())],
         )),
     )
 }
 
-fn format_filesystem_error(error: &str) -> (String, String, Option<String>) {
-    let (message, recovery) = if error.contains("not a btrfs") || error.contains("btrfs") {
+fn format_filesystem_error(error: &HelperError) -> (String, String, Option<String>) {
+    let (message, recovery) = if error.message.contains("btrfs") {
         (
-            "This system is not using Btrfs.".to_string(),
-            Some("Waypoint requires a Btrfs filesystem to function. Your root filesystem appears to be using a different type.".to_string())
+            gettext("This system is not using Btrfs."),
+            Some(gettext(
+                "Waypoint requires a Btrfs filesystem to function. Your root filesystem appears to be using a different type.",
+            )),
         )
     } else {
         (
-            "Filesystem check failed.".to_string(),
-            Some(format!(
-                "Unable to verify filesystem type.\n\nTechnical details: {error}"
+            gettext("Filesystem check failed."),
+            Some(gettext_f(
+                "Unable to verify filesystem type.\n\nTechnical details: {error}",
+                &[("error", &error.to_string())],
             )),
         )
     };
 
-    ("Filesystem Error".to_string(), message, recovery)
+    (gettext("Filesystem Error"), message, recovery)
 }
 
-fn format_dbus_error(error: &str) -> (String, String, Option<String>) {
+fn format_dbus_error(error: &HelperError) -> (String, String, Option<String>) {
     (
-        "Service Connection Error".to_string(),
-        "Unable to connect to the Waypoint system service.".to_string(),
-        Some(format!(
-            "The waypoint-helper service may not be running. Try restarting it or your system.\n\nTechnical details: {error}"
+        gettext("Service Connection Error"),
+        gettext("Unable to connect to the Waypoint system service."),
+        Some(gettext_f(
+            "The waypoint-helper service may not be running. Try restarting it or your system.\n\nTechnical details: {error}",
+            &[("error", &error.to_string())],
         )),
     )
 }
 
-fn format_authorization_error(error: &str) -> (String, String, Option<String>) {
+fn format_authorization_error(error: &HelperError) -> (String, String, Option<String>) {
     (
-        "Authorization Required".to_string(),
-        "This operation requires administrator privileges.".to_string(),
-        Some(format!(
-            "Enter your password when prompted to authorize this action.\n\nTechnical details: {error}"
+        gettext("Authorization Required"),
+        gettext("This operation requires administrator privileges."),
+        Some(gettext_f(
+            "Enter your password when prompted to authorize this action.\n\nTechnical details: {error}",
+            &[("error", &error.to_string())],
         )),
     )
 }
 
-fn format_configuration_error(error: &str) -> (String, String, Option<String>) {
-    let (message, recovery) = if error.contains("parse") || error.contains("JSON") {
+fn format_configuration_error(error: &HelperError) -> (String, String, Option<String>) {
+    let (message, recovery) = if error.message.contains("parse") || error.message.contains("JSON") {
         (
-            "Configuration file is invalid.".to_string(),
-            Some("The configuration file contains invalid data. It may need to be reset to defaults.".to_string())
+            gettext("Configuration file is invalid."),
+            Some(gettext(
+                "The configuration file contains invalid data. It may need to be reset to defaults.",
+            )),
         )
-    } else if error.contains("permission") || error.contains("denied") {
+    } else if error.message.contains("permission") || error.message.contains("denied") {
         (
-            "Cannot save configuration.".to_string(),
-            Some(
-                "Permission denied when writing configuration file. Check file permissions."
-                    .to_string(),
-            ),
+            gettext("Cannot save configuration."),
+            Some(gettext(
+                "Permission denied when writing configuration file. Check file permissions.",
+            )),
         )
     } else {
         (
-            "Configuration error occurred.".to_string(),
-            Some(format!("Technical details: {error}")),
+            gettext("Configuration error occurred."),
+            Some(gettext_f(
+                "Technical details: {error}",
+                &[("error", &error.to_string())],
+            )),
         )
     };
 
-    ("Configuration Error".to_string(), message, recovery)
+    (gettext("Configuration Error"), message, recovery)
 }
 
 #[cfg(test)]
@@ -310,6 +403,30 @@ mod tests {
         assert!(details.unwrap().contains("Choose a different name"));
     }
 
+    #[test]
+    fn test_snapshot_timeout_error() {
+        let (title, message, details) = format_error_message(
+            ErrorContext::SnapshotCreate,
+            "[timed-out] Failed to create snapshot: btrfs timed out after 120 seconds",
+        );
+
+        assert_eq!(title, "Failed to Create Snapshot");
+        assert!(message.contains("took too long"));
+        assert!(details.unwrap().contains("partially created snapshot was removed"));
+    }
+
+    #[test]
+    fn test_read_only_filesystem_error() {
+        let (title, message, details) = format_error_message(
+            ErrorContext::SnapshotDelete,
+            "Failed to delete snapshot: ERROR: cannot delete '/.snapshots/foo/root': Read-only file system",
+        );
+
+        assert_eq!(title, "Filesystem Is Read-Only");
+        assert!(message.contains("mounted read-only"));
+        assert!(details.unwrap().contains("dmesg"));
+    }
+
     #[test]
     fn test_generic_error_includes_details() {
         let (_, _, details) =
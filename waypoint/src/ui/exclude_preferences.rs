@@ -4,12 +4,15 @@ use adw::prelude::*;
 use gtk::Orientation;
 use gtk::prelude::*;
 use libadwaita as adw;
+use std::path::Path;
 use waypoint_common::{ExcludeConfig, ExcludePattern, PatternType};
 
+use crate::i18n::{gettext, gettext_f};
+
 /// Create the exclude patterns preferences page
 pub fn create_exclude_page(parent: &adw::ApplicationWindow) -> adw::PreferencesPage {
     let page = adw::PreferencesPage::new();
-    page.set_title("Exclusions");
+    page.set_title(&gettext("Exclusions"));
     page.set_icon_name(Some("edit-delete-symbolic"));
 
     // Load current config
@@ -17,17 +20,19 @@ pub fn create_exclude_page(parent: &adw::ApplicationWindow) -> adw::PreferencesP
 
     // Info group
     let info_group = adw::PreferencesGroup::new();
-    info_group.set_title("Exclude Patterns");
-    info_group.set_description(Some(
+    info_group.set_title(&gettext("Exclude Patterns"));
+    info_group.set_description(Some(&gettext(
         "Files and directories matching these patterns will be excluded from snapshots. \
          This saves disk space by skipping caches, temporary files, and other non-essential data.",
-    ));
+    )));
     page.add(&info_group);
 
     // System defaults group
     let defaults_group = adw::PreferencesGroup::new();
-    defaults_group.set_title("System Defaults");
-    defaults_group.set_description(Some("Built-in patterns (can be disabled but not deleted)"));
+    defaults_group.set_title(&gettext("System Defaults"));
+    defaults_group.set_description(Some(&gettext(
+        "Built-in patterns (can be disabled but not deleted)",
+    )));
 
     let system_patterns: Vec<_> = config
         .patterns
@@ -44,8 +49,8 @@ pub fn create_exclude_page(parent: &adw::ApplicationWindow) -> adw::PreferencesP
 
     // Custom patterns group
     let custom_group = adw::PreferencesGroup::new();
-    custom_group.set_title("Custom Patterns");
-    custom_group.set_description(Some("Your own exclusion patterns"));
+    custom_group.set_title(&gettext("Custom Patterns"));
+    custom_group.set_description(Some(&gettext("Your own exclusion patterns")));
 
     let custom_patterns: Vec<_> = config
         .patterns
@@ -56,7 +61,7 @@ pub fn create_exclude_page(parent: &adw::ApplicationWindow) -> adw::PreferencesP
 
     if custom_patterns.is_empty() {
         let empty_row = adw::ActionRow::new();
-        empty_row.set_title("No custom patterns defined");
+        empty_row.set_title(&gettext("No custom patterns defined"));
         empty_row.set_sensitive(false);
         custom_group.add(&empty_row);
     } else {
@@ -68,16 +73,18 @@ pub fn create_exclude_page(parent: &adw::ApplicationWindow) -> adw::PreferencesP
 
     page.add(&custom_group);
 
+    page.add(&create_test_group());
+
     // Actions group
     let actions_group = adw::PreferencesGroup::new();
-    actions_group.set_title("Actions");
+    actions_group.set_title(&gettext("Actions"));
 
     // Add pattern button
     let add_row = adw::ActionRow::new();
-    add_row.set_title("Add Custom Pattern");
-    add_row.set_subtitle("Create a new exclusion pattern");
+    add_row.set_title(&gettext("Add Custom Pattern"));
+    add_row.set_subtitle(&gettext("Create a new exclusion pattern"));
 
-    let add_button = gtk::Button::with_label("Add");
+    let add_button = gtk::Button::with_label(&gettext("Add"));
     add_button.set_valign(gtk::Align::Center);
     add_button.add_css_class("suggested-action");
 
@@ -109,14 +116,22 @@ fn create_pattern_row(
     let subtitle = format!(
         "{} - {}",
         match pattern.pattern_type {
-            PatternType::Exact => "Exact match",
-            PatternType::Prefix => "Prefix match",
-            PatternType::Glob => "Glob pattern",
+            PatternType::Exact => gettext("Exact match"),
+            PatternType::Prefix => gettext("Prefix match"),
+            PatternType::Glob => gettext("Glob pattern"),
         },
         pattern.description
     );
     row.set_subtitle(&subtitle);
 
+    // Patterns added before validation existed may never match anything
+    if let Err(e) = pattern.validate() {
+        let warning_icon = gtk::Image::from_icon_name("dialog-warning-symbolic");
+        warning_icon.set_tooltip_text(Some(&e));
+        warning_icon.add_css_class("warning");
+        row.add_prefix(&warning_icon);
+    }
+
     // Enable/disable switch
     let switch = gtk::Switch::new();
     switch.set_active(pattern.enabled);
@@ -140,11 +155,14 @@ fn create_pattern_row(
             log::error!("Failed to save exclude config: {e}");
             super::dialogs::show_error(
                 &parent_clone,
-                "Save Failed",
-                &format!("Failed to save exclusion pattern: {e}"),
+                &gettext("Save Failed"),
+                &gettext_f(
+                    "Failed to save exclusion pattern: {error}",
+                    &[("error", &e.to_string())],
+                ),
             );
         } else {
-            super::dialogs::show_toast(&parent_clone, "Exclusion pattern updated");
+            super::dialogs::show_toast(&parent_clone, &gettext("Exclusion pattern updated"));
         }
     });
 
@@ -170,12 +188,15 @@ fn create_pattern_row(
                 log::error!("Failed to save exclude config: {e}");
                 super::dialogs::show_error(
                     &parent_clone2,
-                    "Save Failed",
-                    &format!("Failed to delete exclusion pattern: {e}"),
+                    &gettext("Save Failed"),
+                    &gettext_f(
+                        "Failed to delete exclusion pattern: {error}",
+                        &[("error", &e.to_string())],
+                    ),
                 );
             } else {
                 log::info!("Deleted pattern: {pattern_str}");
-                super::dialogs::show_toast(&parent_clone2, "Exclusion pattern deleted");
+                super::dialogs::show_toast(&parent_clone2, &gettext("Exclusion pattern deleted"));
 
                 // Remove this row from the group
                 if let Some(ref group) = custom_group_clone {
@@ -184,7 +205,7 @@ fn create_pattern_row(
                     // Check if group is now empty and add empty state if needed
                     if !has_custom_rows(group) {
                         let empty_row = adw::ActionRow::new();
-                        empty_row.set_title("No custom patterns defined");
+                        empty_row.set_title(&gettext("No custom patterns defined"));
                         empty_row.set_sensitive(false);
                         group.add(&empty_row);
                     }
@@ -214,9 +235,58 @@ fn has_custom_rows(group: &adw::PreferencesGroup) -> bool {
     false
 }
 
+/// Create the group for testing a path against the enabled patterns
+///
+/// Uses the same matching as snapshot creation, so users can check a
+/// pattern before waiting for the next snapshot.
+fn create_test_group() -> adw::PreferencesGroup {
+    let group = adw::PreferencesGroup::new();
+    group.set_title(&gettext("Test a Path"));
+    group.set_description(Some(&gettext(
+        "Type a path to see whether the enabled patterns would exclude it",
+    )));
+
+    let path_row = adw::EntryRow::new();
+    path_row.set_title(&gettext("Path"));
+    group.add(&path_row);
+
+    let result_row = adw::ActionRow::new();
+    result_row.set_title(&gettext("Enter an absolute path"));
+    result_row.add_css_class("property");
+    group.add(&result_row);
+
+    path_row.connect_changed(move |entry| {
+        let text = entry.text();
+        let path = Path::new(text.as_str());
+        if !path.is_absolute() {
+            result_row.set_title(&gettext("Enter an absolute path"));
+            result_row.set_subtitle("");
+            return;
+        }
+
+        // Load on every change so toggled or added patterns are picked up
+        let config = ExcludeConfig::load().unwrap_or_default();
+        let matches = config.matching_patterns(path);
+        if matches.is_empty() {
+            result_row.set_title(&gettext("Not excluded"));
+            result_row.set_subtitle(&gettext("This path is kept in new snapshots"));
+        } else {
+            let patterns: Vec<&str> = matches.iter().map(|p| p.pattern.as_str()).collect();
+            result_row.set_title(&gettext("Excluded"));
+            result_row.set_subtitle(&gettext_f(
+                "Matched by {patterns}",
+                &[("patterns", &patterns.join(", "))],
+            ));
+        }
+    });
+
+    group
+}
+
 /// Show dialog to add a new pattern
 fn show_add_pattern_dialog(parent: &adw::ApplicationWindow, custom_group: &adw::PreferencesGroup) {
-    let dialog = adw::MessageDialog::new(Some(parent), Some("Add Exclusion Pattern"), None);
+    let dialog =
+        adw::MessageDialog::new(Some(parent), Some(&gettext("Add Exclusion Pattern")), None);
     dialog.set_modal(true);
 
     // Create form in a box
@@ -228,29 +298,60 @@ fn show_add_pattern_dialog(parent: &adw::ApplicationWindow, custom_group: &adw::
 
     // Pattern entry
     let pattern_row = adw::EntryRow::new();
-    pattern_row.set_title("Pattern");
+    pattern_row.set_title(&gettext("Pattern"));
     content.append(&pattern_row);
 
     // Pattern type dropdown
     let type_row = adw::ComboRow::new();
-    type_row.set_title("Pattern Type");
-    let type_model = gtk::StringList::new(&["Prefix Match", "Exact Match", "Glob Pattern"]);
+    type_row.set_title(&gettext("Pattern Type"));
+    let type_model = gtk::StringList::new(&[
+        &gettext("Prefix Match"),
+        &gettext("Exact Match"),
+        &gettext("Glob Pattern"),
+    ]);
     type_row.set_model(Some(&type_model));
     type_row.set_selected(0); // Default to prefix
     content.append(&type_row);
 
+    // Flag invalid patterns while typing
+    let update_validity = {
+        let pattern_row = pattern_row.clone();
+        let type_row = type_row.clone();
+        move || {
+            let text = pattern_row.text().to_string();
+            let pattern = ExcludePattern::new(
+                text.clone(),
+                selected_pattern_type(&type_row),
+                String::new(),
+            );
+            match pattern.validate() {
+                Err(e) if !text.is_empty() => {
+                    pattern_row.add_css_class("error");
+                    pattern_row.set_tooltip_text(Some(&e));
+                }
+                _ => {
+                    pattern_row.remove_css_class("error");
+                    pattern_row.set_tooltip_text(None);
+                }
+            }
+        }
+    };
+    let update = update_validity.clone();
+    pattern_row.connect_changed(move |_| update());
+    type_row.connect_selected_notify(move |_| update_validity());
+
     // Description entry
     let desc_row = adw::EntryRow::new();
-    desc_row.set_title("Description");
+    desc_row.set_title(&gettext("Description"));
     content.append(&desc_row);
 
     // Examples
-    let examples_label = gtk::Label::new(Some(
+    let examples_label = gtk::Label::new(Some(&gettext(
         "Examples:\n\
          • Prefix: /var/cache (excludes /var/cache/*)\n\
          • Exact: /swapfile (excludes only /swapfile)\n\
          • Glob: /home/*/.cache (excludes all user caches)",
-    ));
+    )));
     examples_label.set_xalign(0.0);
     examples_label.add_css_class("dim-label");
     examples_label.add_css_class("caption");
@@ -258,8 +359,8 @@ fn show_add_pattern_dialog(parent: &adw::ApplicationWindow, custom_group: &adw::
 
     dialog.set_extra_child(Some(&content));
 
-    dialog.add_response("cancel", "Cancel");
-    dialog.add_response("add", "Add");
+    dialog.add_response("cancel", &gettext("Cancel"));
+    dialog.add_response("add", &gettext("Add"));
     dialog.set_response_appearance("add", adw::ResponseAppearance::Suggested);
     dialog.set_default_response(Some("add"));
 
@@ -274,11 +375,7 @@ fn show_add_pattern_dialog(parent: &adw::ApplicationWindow, custom_group: &adw::
                 return;
             }
 
-            let pattern_type = match type_row.selected() {
-                0 => PatternType::Prefix,
-                1 => PatternType::Exact,
-                _ => PatternType::Glob,
-            };
+            let pattern_type = selected_pattern_type(&type_row);
 
             let new_pattern = ExcludePattern::new(
                 pattern_text.clone(),
@@ -290,6 +387,11 @@ fn show_add_pattern_dialog(parent: &adw::ApplicationWindow, custom_group: &adw::
                 },
             );
 
+            if let Err(e) = new_pattern.validate() {
+                super::dialogs::show_error(&parent_clone, &gettext("Invalid Pattern"), &e);
+                return;
+            }
+
             let mut config = ExcludeConfig::load().unwrap_or_default();
             config.add_pattern(new_pattern.clone());
 
@@ -297,12 +399,15 @@ fn show_add_pattern_dialog(parent: &adw::ApplicationWindow, custom_group: &adw::
                 log::error!("Failed to save exclude config: {e}");
                 super::dialogs::show_error(
                     &parent_clone,
-                    "Save Failed",
-                    &format!("Failed to add exclusion pattern: {e}"),
+                    &gettext("Save Failed"),
+                    &gettext_f(
+                        "Failed to add exclusion pattern: {error}",
+                        &[("error", &e.to_string())],
+                    ),
                 );
             } else {
                 log::info!("Added new exclude pattern: {pattern_text}");
-                super::dialogs::show_toast(&parent_clone, "Exclusion pattern added");
+                super::dialogs::show_toast(&parent_clone, &gettext("Exclusion pattern added"));
 
                 // Remove empty state if present
                 let mut child = custom_group_clone.first_child();
@@ -330,6 +435,15 @@ fn show_add_pattern_dialog(parent: &adw::ApplicationWindow, custom_group: &adw::
     dialog.present();
 }
 
+/// Pattern type chosen in the add dialog's dropdown
+fn selected_pattern_type(type_row: &adw::ComboRow) -> PatternType {
+    match type_row.selected() {
+        0 => PatternType::Prefix,
+        1 => PatternType::Exact,
+        _ => PatternType::Glob,
+    }
+}
+
 /// Save exclude config via D-Bus (requires root permissions)
 fn save_exclude_config(config: &ExcludeConfig) -> anyhow::Result<()> {
     use crate::dbus_client::WaypointHelperClient;
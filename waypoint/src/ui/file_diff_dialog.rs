@@ -10,12 +10,16 @@ use gtk::{Label, Orientation};
 use libadwaita as adw;
 
 use super::dialogs;
+use crate::i18n::{gettext, gettext_f, ngettext_f};
 
 /// File change representation (matches waypoint-helper output)
 #[derive(Debug, Clone, serde::Deserialize)]
 struct FileChange {
-    change_type: String, // "Added", "Modified", "Deleted"
+    change_type: String, // "Added", "Modified", "Deleted", "Renamed"
     path: String,
+    /// Previous path of a renamed file
+    #[serde(default)]
+    old_path: Option<String>,
 }
 
 /// Show dialog displaying file changes between two snapshots
@@ -26,7 +30,7 @@ pub fn show_file_diff_dialog(
 ) {
     // Create full window dialog
     let dialog = adw::Window::new();
-    dialog.set_title(Some("File Changes"));
+    dialog.set_title(Some(&gettext("File Changes")));
     dialog.set_default_size(800, 600);
     dialog.set_modal(true);
     dialog.set_transient_for(Some(parent));
@@ -35,7 +39,7 @@ pub fn show_file_diff_dialog(
 
     // Header
     let header = adw::HeaderBar::new();
-    header.set_title_widget(Some(&adw::WindowTitle::new("File Changes", "")));
+    header.set_title_widget(Some(&adw::WindowTitle::new(&gettext("File Changes"), "")));
     content.append(&header);
 
     // Main content - show loading state initially
@@ -52,12 +56,16 @@ pub fn show_file_diff_dialog(
     title.set_halign(gtk::Align::Start);
     title_box.append(&title);
 
-    let subtitle = Label::new(Some("Comparing file changes between snapshots..."));
+    let subtitle = Label::new(Some(&gettext(
+        "Comparing file changes between snapshots...",
+    )));
     subtitle.add_css_class("dim-label");
     subtitle.set_halign(gtk::Align::Start);
     title_box.append(&subtitle);
 
-    let warning = Label::new(Some("Large snapshots may take several minutes to compare."));
+    let warning = Label::new(Some(&gettext(
+        "Large snapshots may take several minutes to compare.",
+    )));
     warning.add_css_class("caption");
     warning.add_css_class("dim-label");
     warning.set_halign(gtk::Align::Start);
@@ -108,8 +116,21 @@ pub fn show_file_diff_dialog(
     let new_snapshot_owned = new_snapshot.to_string();
 
     let _dialog_for_close = dialog.clone();
+    let old_for_cancel = old_snapshot.to_string();
+    let new_for_cancel = new_snapshot.to_string();
     dialog.connect_close_request(move |_| {
         let _ = cancel_tx.send(());
+
+        // Stop the helper too, a large comparison can keep scanning for minutes
+        let old = old_for_cancel.clone();
+        let new = new_for_cancel.clone();
+        std::thread::spawn(move || {
+            use crate::dbus_client::WaypointHelperClient;
+
+            // Nothing to cancel if the comparison already finished
+            let _ = WaypointHelperClient::new().and_then(|client| client.cancel_compare(&old, &new));
+        });
+
         gtk::glib::Propagation::Proceed
     });
 
@@ -140,16 +161,21 @@ pub fn show_file_diff_dialog(
                             if error_msg.contains("timeout") || error_msg.contains("timed out") {
                                 dialogs::show_error(
                                     &parent_clone,
-                                    "Comparison Timeout",
-                                    "The file comparison took too long (>25 seconds).\n\n\
+                                    &gettext("Comparison Timeout"),
+                                    &gettext(
+                                        "The file comparison took too long (>25 seconds).\n\n\
                                      This happens with very large snapshots that have many file changes.\n\n\
                                      Try using \"Compare Packages\" instead, which works for snapshots of any size.",
+                                    ),
                                 );
                             } else {
                                 dialogs::show_error(
                                     &parent_clone,
-                                    "Comparison Failed",
-                                    &format!("Failed to compare snapshots: {e}"),
+                                    &gettext("Comparison Failed"),
+                                    &gettext_f(
+                                        "Failed to compare snapshots: {error}",
+                                        &[("error", &e.to_string())],
+                                    ),
                                 );
                             }
                             dialog_clone.close();
@@ -182,7 +208,7 @@ fn display_changes(
 
     // Header
     let header = adw::HeaderBar::new();
-    header.set_title_widget(Some(&adw::WindowTitle::new("File Changes", "")));
+    header.set_title_widget(Some(&adw::WindowTitle::new(&gettext("File Changes"), "")));
     content.append(&header);
 
     // Scrollable content
@@ -202,7 +228,12 @@ fn display_changes(
     title.set_halign(gtk::Align::Start);
     title_box.append(&title);
 
-    let subtitle = Label::new(Some(&format!("{} file(s) changed", changes.len())));
+    let subtitle = Label::new(Some(&ngettext_f(
+        "{count} file changed",
+        "{count} files changed",
+        changes.len() as u64,
+        &[("count", &changes.len().to_string())],
+    )));
     subtitle.add_css_class("dim-label");
     subtitle.set_halign(gtk::Align::Start);
     title_box.append(&subtitle);
@@ -212,33 +243,40 @@ fn display_changes(
         // No changes
         let status_page = adw::StatusPage::new();
         status_page.set_icon_name(Some("emblem-ok-symbolic"));
-        status_page.set_title("No Changes");
-        status_page.set_description(Some("The snapshots are identical"));
+        status_page.set_title(&gettext("No Changes"));
+        status_page.set_description(Some(&gettext("The snapshots are identical")));
         main_box.append(&status_page);
     } else {
         // Group changes by type
         let mut added: Vec<&FileChange> = Vec::new();
         let mut modified: Vec<&FileChange> = Vec::new();
         let mut deleted: Vec<&FileChange> = Vec::new();
+        let mut renamed: Vec<&FileChange> = Vec::new();
 
         for change in &changes {
             match change.change_type.as_str() {
                 "Added" => added.push(change),
                 "Modified" => modified.push(change),
                 "Deleted" => deleted.push(change),
+                "Renamed" => renamed.push(change),
                 _ => {} // Unknown type, skip
             }
         }
 
         // Display each category
         if !added.is_empty() {
-            let group = create_change_group("Added Files", &added, "list-add-symbolic", "success");
+            let group = create_change_group(
+                &gettext("Added Files"),
+                &added,
+                "list-add-symbolic",
+                "success",
+            );
             main_box.append(&group);
         }
 
         if !modified.is_empty() {
             let group = create_change_group(
-                "Modified Files",
+                &gettext("Modified Files"),
                 &modified,
                 "document-edit-symbolic",
                 "warning",
@@ -247,8 +285,22 @@ fn display_changes(
         }
 
         if !deleted.is_empty() {
-            let group =
-                create_change_group("Deleted Files", &deleted, "list-remove-symbolic", "error");
+            let group = create_change_group(
+                &gettext("Deleted Files"),
+                &deleted,
+                "list-remove-symbolic",
+                "error",
+            );
+            main_box.append(&group);
+        }
+
+        if !renamed.is_empty() {
+            let group = create_change_group(
+                &gettext("Renamed Files"),
+                &renamed,
+                "document-save-as-symbolic",
+                "accent",
+            );
             main_box.append(&group);
         }
     }
@@ -290,6 +342,10 @@ fn create_change_group(
     for change in changes {
         let row = adw::ActionRow::new();
         row.set_title(&change.path);
+        if let Some(old_path) = &change.old_path {
+            row.set_subtitle(&gettext_f("Renamed from {path}", &[("path", old_path)]));
+        }
+        flag_non_utf8_path(&row, &change.path);
 
         // Add icon based on change type
         let change_icon = gtk::Image::from_icon_name(icon_name);
@@ -303,3 +359,18 @@ fn create_change_group(
 
     group_box
 }
+
+/// Mark a row whose encoded path has a name that isn't valid UTF-8
+///
+/// Such names are shown with `\xNN` escapes; restoring still uses the original bytes.
+pub fn flag_non_utf8_path(row: &adw::ActionRow, encoded_path: &str) {
+    if !waypoint_common::path_encoding::is_non_utf8(encoded_path) {
+        return;
+    }
+    let icon = gtk::Image::from_icon_name("dialog-warning-symbolic");
+    icon.add_css_class("warning");
+    icon.set_tooltip_text(Some(&gettext(
+        "This name isn't valid UTF-8 and is shown with escapes; it is restored unchanged",
+    )));
+    row.add_suffix(&icon);
+}
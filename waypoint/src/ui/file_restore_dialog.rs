@@ -3,6 +3,7 @@
 #![allow(deprecated)] // Still using FileChooserDialog for folder selection
 
 use crate::dbus_client::WaypointHelperClient;
+use crate::i18n::{gettext, gettext_f, gettext_noop, ngettext_f};
 use adw::prelude::*;
 use gtk::prelude::*;
 use gtk::{FileChooserAction, FileChooserDialog, Orientation, ResponseType};
@@ -10,29 +11,48 @@ use libadwaita as adw;
 use std::cell::RefCell;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use waypoint_common::WaypointConfig;
+use waypoint_common::{ExcludeConfig, NestedSubvolumeMode};
 
 use super::dialogs;
 use super::error_helpers;
 
 /// Show custom file browser dialog for restoring files from a snapshot
 pub fn show_file_restore_dialog(parent: &adw::ApplicationWindow, snapshot_name: &str) {
-    let config = WaypointConfig::new();
-    let snapshot_path = config.snapshot_dir.join(snapshot_name).join("root");
-
-    // Verify snapshot exists
-    if !snapshot_path.exists() {
-        error_helpers::show_error_with_context(
-            parent,
-            error_helpers::ErrorContext::SnapshotRestore,
-            &format!("Snapshot directory not found: {}", snapshot_path.display()),
-        );
-        return;
+    let snapshot_path = crate::btrfs::snapshot_path(snapshot_name).join("root");
+
+    // Verify snapshot exists and can be listed
+    match snapshot_path.try_exists() {
+        Ok(true) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            error_helpers::show_error_with_context(
+                parent,
+                error_helpers::ErrorContext::SnapshotRestore,
+                &gettext_f(
+                    "Snapshot directory is only readable by root, so its files can't be listed: {path}",
+                    &[("path", &snapshot_path.to_string_lossy())],
+                ),
+            );
+            return;
+        }
+        _ => {
+            error_helpers::show_error_with_context(
+                parent,
+                error_helpers::ErrorContext::SnapshotRestore,
+                &gettext_f(
+                    "Snapshot directory not found: {path}",
+                    &[("path", &snapshot_path.to_string_lossy())],
+                ),
+            );
+            return;
+        }
     }
 
     // Create custom file browser window
     let dialog = adw::Window::new();
-    dialog.set_title(Some(&format!("Restore Files - {snapshot_name}")));
+    dialog.set_title(Some(&gettext_f(
+        "Restore Files - {name}",
+        &[("name", snapshot_name)],
+    )));
     dialog.set_modal(true);
     dialog.set_transient_for(Some(parent));
     dialog.set_default_size(900, 600);
@@ -42,10 +62,10 @@ pub fn show_file_restore_dialog(parent: &adw::ApplicationWindow, snapshot_name:
     // Header bar
     let header = adw::HeaderBar::new();
 
-    let cancel_button = gtk::Button::with_label("Cancel");
+    let cancel_button = gtk::Button::with_label(&gettext("Cancel"));
     header.pack_start(&cancel_button);
 
-    let restore_button = gtk::Button::with_label("Restore Selected");
+    let restore_button = gtk::Button::with_label(&gettext("Restore Selected"));
     restore_button.add_css_class("suggested-action");
     restore_button.set_sensitive(false); // Disabled until files are selected
     header.pack_end(&restore_button);
@@ -86,7 +106,7 @@ pub fn show_file_restore_dialog(parent: &adw::ApplicationWindow, snapshot_name:
     restore_button.connect_clicked(move |_| {
         let files = selected_files.borrow().clone();
         if files.is_empty() {
-            dialogs::show_toast(&parent_clone, "No files selected");
+            dialogs::show_toast(&parent_clone, &gettext("No files selected"));
             return;
         }
 
@@ -110,13 +130,37 @@ fn create_sidebar(_snapshot_root: &Path) -> gtk::Box {
 
     // Common directories
     let common_dirs = vec![
-        ("Home Directory", "user-home-symbolic", "home"),
-        ("Documents", "folder-documents-symbolic", "home/*/Documents"),
-        ("Downloads", "folder-download-symbolic", "home/*/Downloads"),
-        ("Pictures", "folder-pictures-symbolic", "home/*/Pictures"),
-        ("System Configuration", "preferences-system-symbolic", "etc"),
-        ("Applications", "applications-system-symbolic", "usr/share/applications"),
-        ("System Binaries", "utilities-terminal-symbolic", "usr/bin"),
+        (gettext("Home Directory"), "user-home-symbolic", "home"),
+        (
+            gettext("Documents"),
+            "folder-documents-symbolic",
+            "home/*/Documents",
+        ),
+        (
+            gettext("Downloads"),
+            "folder-download-symbolic",
+            "home/*/Downloads",
+        ),
+        (
+            gettext("Pictures"),
+            "folder-pictures-symbolic",
+            "home/*/Pictures",
+        ),
+        (
+            gettext("System Configuration"),
+            "preferences-system-symbolic",
+            "etc",
+        ),
+        (
+            gettext("Applications"),
+            "applications-system-symbolic",
+            "usr/share/applications",
+        ),
+        (
+            gettext("System Binaries"),
+            "utilities-terminal-symbolic",
+            "usr/bin",
+        ),
     ];
 
     for (label, icon, _path) in common_dirs {
@@ -130,7 +174,7 @@ fn create_sidebar(_snapshot_root: &Path) -> gtk::Box {
         let icon_widget = gtk::Image::from_icon_name(icon);
         row_box.append(&icon_widget);
 
-        let label_widget = gtk::Label::new(Some(label));
+        let label_widget = gtk::Label::new(Some(&label));
         label_widget.set_xalign(0.0);
         row_box.append(&label_widget);
 
@@ -154,7 +198,7 @@ fn create_file_list_area(
 
     // Search bar
     let search_entry = gtk::SearchEntry::new();
-    search_entry.set_placeholder_text(Some("Search files..."));
+    search_entry.set_placeholder_text(Some(&gettext("Search files...")));
     search_entry.set_margin_start(12);
     search_entry.set_margin_end(12);
     search_entry.set_margin_top(12);
@@ -184,7 +228,7 @@ fn create_file_list_area(
             row_box.append(&icon);
 
             let name = entry.file_name();
-            let label = gtk::Label::new(Some(&name.to_string_lossy()));
+            let label = gtk::Label::new(Some(&waypoint_common::encode_path(Path::new(&name))));
             label.set_xalign(0.0);
             label.set_hexpand(true);
             row_box.append(&label);
@@ -225,6 +269,48 @@ fn create_file_list_area(
     container
 }
 
+/// Maximum number of exclude patterns listed in the confirmation dialog
+const MAX_LISTED_EXCLUDES: usize = 8;
+
+/// Describe the exclude patterns that will be skipped when restoring folders
+fn exclude_summary() -> Option<String> {
+    let config = ExcludeConfig::load().unwrap_or_default();
+    let patterns = config.enabled_patterns();
+    if patterns.is_empty() {
+        return None;
+    }
+
+    let mut summary = gettext("Items matching these exclude patterns are skipped inside folders:");
+    for pattern in patterns.iter().take(MAX_LISTED_EXCLUDES) {
+        summary.push_str(&format!("\n• {}", pattern.pattern));
+    }
+    if patterns.len() > MAX_LISTED_EXCLUDES {
+        summary.push('\n');
+        summary.push_str(&gettext_f(
+            "…and {count} more (see Preferences → Exclusions)",
+            &[("count", &(patterns.len() - MAX_LISTED_EXCLUDES).to_string())],
+        ));
+    }
+
+    Some(summary)
+}
+
+/// Choices for nested subvolumes inside restored folders
+const NESTED_SUBVOLUME_OPTIONS: &[(&str, NestedSubvolumeMode)] = &[
+    (
+        gettext_noop("Skip nested subvolumes"),
+        NestedSubvolumeMode::Skip,
+    ),
+    (
+        gettext_noop("Copy nested subvolumes"),
+        NestedSubvolumeMode::Recurse,
+    ),
+    (
+        gettext_noop("Recreate nested subvolumes"),
+        NestedSubvolumeMode::Subvolume,
+    ),
+];
+
 /// Show confirmation dialog before restoring files
 fn show_restore_confirmation_dialog(
     parent: &adw::ApplicationWindow,
@@ -232,36 +318,102 @@ fn show_restore_confirmation_dialog(
     selected_files: Vec<PathBuf>,
     snapshot_root: &PathBuf,
 ) {
-    // Build file list message
+    // Build file list message; paths are encoded so names that aren't
+    // valid UTF-8 reach the helper unchanged
     let file_list: Vec<String> = selected_files
         .iter()
         .map(|p| {
             // Get path relative to snapshot root
-            p.strip_prefix(snapshot_root)
-                .ok()
-                .and_then(|rel| rel.to_str())
-                .map(|s| format!("/{s}"))
-                .unwrap_or_else(|| p.display().to_string())
+            let relative = p.strip_prefix(snapshot_root).unwrap_or(p);
+            waypoint_common::encode_path(&Path::new("/").join(relative))
         })
         .collect();
 
-    let heading = format!(
-        "Restore {} file(s) from snapshot '{}'?",
-        file_list.len(),
-        snapshot_name
+    // Folder contents are filtered through the exclude patterns by the helper
+    let has_folders = selected_files.iter().any(|p| p.is_dir());
+
+    confirm_restore_paths(parent, snapshot_name, file_list, has_folders);
+}
+
+/// Ask how to restore the given files and restore them from the snapshot
+///
+/// `file_list` holds absolute paths as they appear in the snapshot, encoded
+/// with `waypoint_common::encode_path`. Also used to restore files picked in
+/// a snapshot comparison.
+pub fn confirm_restore_paths(
+    parent: &adw::ApplicationWindow,
+    snapshot_name: &str,
+    file_list: Vec<String>,
+    has_folders: bool,
+) {
+    let heading = ngettext_f(
+        "Restore {count} file from snapshot '{name}'?",
+        "Restore {count} files from snapshot '{name}'?",
+        file_list.len() as u64,
+        &[
+            ("count", &file_list.len().to_string()),
+            ("name", snapshot_name),
+        ],
     );
-    let body = file_list.join("\n");
+    let mut body = file_list.join("\n");
+
+    if file_list
+        .iter()
+        .any(|p| waypoint_common::path_encoding::is_non_utf8(p))
+    {
+        body.push_str("\n\n");
+        body.push_str(&gettext(
+            "Names shown with \\xNN escapes aren't valid UTF-8. \
+             They are restored with their original bytes.",
+        ));
+    }
+
+    if let Some(summary) = has_folders.then(exclude_summary).flatten() {
+        body.push_str("\n\n");
+        body.push_str(&summary);
+    }
 
     let dialog = adw::MessageDialog::new(
         Some(parent),
-        Some("Restore Files from Snapshot"),
+        Some(&gettext("Restore Files from Snapshot")),
         Some(&heading),
     );
 
     dialog.set_body(&body);
-    dialog.add_response("cancel", "Cancel");
-    dialog.add_response("restore", "Restore to Original Location");
-    dialog.add_response("restore_custom", "Restore to Custom Location");
+
+    let verify_check = gtk::CheckButton::with_label(&gettext("Verify restored files (slower)"));
+    verify_check.set_tooltip_text(Some(&gettext(
+        "Compare checksums of each restored file against the snapshot",
+    )));
+    verify_check.set_visible(crate::dbus_client::helper_supports(
+        waypoint_common::capabilities::FEATURE_VERIFIED_FILE_RESTORE,
+    ));
+
+    // Nested subvolumes (e.g. /var/lib/docker) only matter when restoring folders
+    let labels: Vec<String> = NESTED_SUBVOLUME_OPTIONS
+        .iter()
+        .map(|(label, _)| gettext(label))
+        .collect();
+    let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+    let nested_dropdown = gtk::DropDown::from_strings(&labels);
+    nested_dropdown.set_tooltip_text(Some(&gettext(
+        "Subvolumes nested inside a folder are not part of its snapshot and may hold large amounts of data",
+    )));
+    nested_dropdown.set_visible(
+        has_folders
+            && crate::dbus_client::helper_supports(
+                waypoint_common::capabilities::FEATURE_NESTED_SUBVOLUME_RESTORE,
+            ),
+    );
+
+    let options_box = gtk::Box::new(Orientation::Vertical, 6);
+    options_box.append(&verify_check);
+    options_box.append(&nested_dropdown);
+    dialog.set_extra_child(Some(&options_box));
+
+    dialog.add_response("cancel", &gettext("Cancel"));
+    dialog.add_response("restore", &gettext("Restore to Original Location"));
+    dialog.add_response("restore_custom", &gettext("Restore to Custom Location"));
     dialog.set_response_appearance("restore", adw::ResponseAppearance::Suggested);
     dialog.set_default_response(Some("cancel"));
 
@@ -269,6 +421,11 @@ fn show_restore_confirmation_dialog(
     let parent_clone = parent.clone();
 
     dialog.connect_response(None, move |_, response| {
+        let verify = verify_check.is_active();
+        let nested = NESTED_SUBVOLUME_OPTIONS
+            .get(nested_dropdown.selected() as usize)
+            .map(|(_, mode)| *mode)
+            .unwrap_or_default();
         match response {
             "restore" => {
                 // Restore to original locations
@@ -278,6 +435,8 @@ fn show_restore_confirmation_dialog(
                     file_list.clone(),
                     "",
                     true,
+                    verify,
+                    nested,
                 );
             }
             "restore_custom" => {
@@ -286,6 +445,8 @@ fn show_restore_confirmation_dialog(
                     &parent_clone,
                     &snapshot_name_owned,
                     file_list.clone(),
+                    verify,
+                    nested,
                 );
             }
             _ => {} // Cancel - do nothing
@@ -300,14 +461,16 @@ fn show_custom_location_chooser(
     parent: &adw::ApplicationWindow,
     snapshot_name: &str,
     file_paths: Vec<String>,
+    verify: bool,
+    nested: NestedSubvolumeMode,
 ) {
     let dialog = FileChooserDialog::new(
-        Some("Choose Restore Location"),
+        Some(&gettext("Choose Restore Location")),
         Some(parent),
         FileChooserAction::SelectFolder,
         &[
-            ("Cancel", ResponseType::Cancel),
-            ("Restore Here", ResponseType::Accept),
+            (&gettext("Cancel"), ResponseType::Cancel),
+            (&gettext("Restore Here"), ResponseType::Accept),
         ],
     );
 
@@ -319,13 +482,15 @@ fn show_custom_location_chooser(
     dialog.connect_response(move |dialog, response| {
         if response == ResponseType::Accept {
             if let Some(folder) = dialog.file().and_then(|f| f.path()) {
-                let target_dir = folder.to_string_lossy().to_string();
+                let target_dir = waypoint_common::encode_path(&folder);
                 perform_file_restore(
                     &parent_clone,
                     &snapshot_name_owned,
                     file_paths.clone(),
                     &target_dir,
                     true,
+                    verify,
+                    nested,
                 );
             }
         }
@@ -342,6 +507,8 @@ fn perform_file_restore(
     file_paths: Vec<String>,
     target_directory: &str,
     overwrite: bool,
+    verify: bool,
+    nested: NestedSubvolumeMode,
 ) {
     let parent_clone = parent.clone();
     let snapshot_name_owned = snapshot_name.to_string();
@@ -359,10 +526,12 @@ fn perform_file_restore(
                 file_paths,
                 target_directory_owned,
                 overwrite,
+                verify,
+                nested,
             )?;
 
             if !success {
-                return Err(anyhow::anyhow!(message));
+                return Err(waypoint_common::HelperError::decode(&message).into());
             }
 
             Ok(message)
@@ -393,7 +562,10 @@ fn perform_file_restore(
                             error_helpers::show_error_with_context(
                                 &parent_clone,
                                 error_helpers::ErrorContext::SnapshotRestore,
-                                &format!("File restoration failed: {e}"),
+                                &gettext_f(
+                                    "File restoration failed: {error}",
+                                    &[("error", &e.to_string())],
+                                ),
                             );
                         }
                     }
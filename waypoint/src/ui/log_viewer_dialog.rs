@@ -0,0 +1,206 @@
+//! Log viewer showing Waypoint's recent log output
+
+use adw::prelude::*;
+use gtk::prelude::*;
+use gtk::{Orientation, gio, glib};
+use libadwaita as adw;
+use log::LevelFilter;
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::app_log;
+use crate::i18n::gettext;
+
+/// How often new log lines are picked up
+const TAIL_INTERVAL_MS: u64 = 500;
+
+fn level_label(level: LevelFilter) -> String {
+    match level {
+        LevelFilter::Off => gettext("Off"),
+        LevelFilter::Error => gettext("Errors"),
+        LevelFilter::Warn => gettext("Warnings"),
+        LevelFilter::Info => gettext("Info"),
+        LevelFilter::Debug => gettext("Debug"),
+        LevelFilter::Trace => gettext("Trace"),
+    }
+}
+
+/// Append lines and keep the view scrolled to the end if it was there
+fn append_lines(view: &gtk::TextView, scrolled: &gtk::ScrolledWindow, lines: &[String]) {
+    if lines.is_empty() {
+        return;
+    }
+
+    let adjustment = scrolled.vadjustment();
+    let at_bottom = adjustment.value() + adjustment.page_size() >= adjustment.upper() - 1.0;
+
+    let buffer = view.buffer();
+    let mut end = buffer.end_iter();
+    for line in lines {
+        buffer.insert(&mut end, line);
+        buffer.insert(&mut end, "\n");
+    }
+
+    if at_bottom {
+        let mark = buffer.create_mark(None, &buffer.end_iter(), false);
+        view.scroll_mark_onscreen(&mark);
+        buffer.delete_mark(&mark);
+    }
+}
+
+/// Save the shown log to a file chosen by the user
+fn save_log(parent: &adw::Window, text: String) {
+    let dialog = gtk::FileDialog::new();
+    dialog.set_title(&gettext("Save Log"));
+    dialog.set_initial_name(Some(&format!(
+        "waypoint-{}.log",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    )));
+
+    let error_parent = parent.clone();
+    dialog.save(Some(parent), None::<&gio::Cancellable>, move |result| {
+        let Ok(file) = result else {
+            return;
+        };
+        let Some(path) = file.path() else {
+            return;
+        };
+        match std::fs::write(&path, &text) {
+            Ok(()) => log::info!("Saved log to {}", path.display()),
+            Err(e) => {
+                log::error!("Failed to save log to {}: {e}", path.display());
+                super::dialogs::show_error_window(
+                    &error_parent,
+                    &gettext("Failed to Save Log"),
+                    &e.to_string(),
+                );
+            }
+        }
+    });
+}
+
+/// Show the log viewer
+pub fn show_log_viewer_dialog(parent: &adw::ApplicationWindow) {
+    let dialog = adw::Window::new();
+    dialog.set_title(Some(&gettext("Log")));
+    dialog.set_default_size(900, 600);
+    dialog.set_transient_for(Some(parent));
+
+    let content = gtk::Box::new(Orientation::Vertical, 0);
+
+    let header = adw::HeaderBar::new();
+    header.set_title_widget(Some(&adw::WindowTitle::new(&gettext("Log"), "")));
+
+    let save_button = gtk::Button::from_icon_name("document-save-symbolic");
+    save_button.set_tooltip_text(Some(&gettext("Save to File")));
+    header.pack_end(&save_button);
+
+    let copy_button = gtk::Button::from_icon_name("edit-copy-symbolic");
+    copy_button.set_tooltip_text(Some(&gettext("Copy to Clipboard")));
+    header.pack_end(&copy_button);
+
+    let clear_button = gtk::Button::with_label(&gettext("Clear"));
+    clear_button.set_tooltip_text(Some(&gettext("Clear the view; new lines keep appearing")));
+    header.pack_start(&clear_button);
+    content.append(&header);
+
+    // Level selection
+    let level_group = adw::PreferencesGroup::new();
+    level_group.set_margin_top(12);
+    level_group.set_margin_start(12);
+    level_group.set_margin_end(12);
+
+    let level_labels: Vec<String> = app_log::LEVELS.iter().map(|l| level_label(*l)).collect();
+    let level_refs: Vec<&str> = level_labels.iter().map(String::as_str).collect();
+    let level_row = adw::ComboRow::new();
+    level_row.set_title(&gettext("Log Level"));
+    level_row.set_model(Some(&gtk::StringList::new(&level_refs)));
+    if let Some(idx) = app_log::LEVELS.iter().position(|l| *l == app_log::level()) {
+        level_row.set_selected(idx as u32);
+    }
+    if app_log::overridden_by_env() {
+        level_row.set_subtitle(&gettext(
+            "Started from the RUST_LOG environment variable; changes last until Waypoint is closed",
+        ));
+    } else {
+        level_row.set_subtitle(&gettext(
+            "Choose Debug, reproduce the problem, then save the log to attach it to a bug report. \
+             Changes last until Waypoint is closed",
+        ));
+    }
+    level_row.connect_selected_notify(|row| {
+        if let Some(level) = app_log::LEVELS.get(row.selected() as usize)
+            && *level != app_log::level()
+        {
+            app_log::set_level(*level);
+        }
+    });
+    level_group.add(&level_row);
+    content.append(&level_group);
+
+    // Log text
+    let view = gtk::TextView::new();
+    view.set_editable(false);
+    view.set_cursor_visible(false);
+    view.set_monospace(true);
+    view.set_wrap_mode(gtk::WrapMode::WordChar);
+    view.set_top_margin(6);
+    view.set_bottom_margin(6);
+    view.set_left_margin(6);
+    view.set_right_margin(6);
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_vexpand(true);
+    scrolled.set_margin_top(12);
+    scrolled.set_margin_bottom(12);
+    scrolled.set_margin_start(12);
+    scrolled.set_margin_end(12);
+    scrolled.add_css_class("card");
+    scrolled.set_child(Some(&view));
+    content.append(&scrolled);
+
+    dialog.set_content(Some(&content));
+
+    let (lines, next_seq) = app_log::lines_since(0);
+    append_lines(&view, &scrolled, &lines);
+    let next_seq = Rc::new(Cell::new(next_seq));
+
+    let view_clone = view.clone();
+    copy_button.connect_clicked(move |button| {
+        let buffer = view_clone.buffer();
+        let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+        button.clipboard().set_text(&text);
+    });
+
+    let view_clone = view.clone();
+    let dialog_clone = dialog.clone();
+    save_button.connect_clicked(move |_| {
+        let buffer = view_clone.buffer();
+        let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+        save_log(&dialog_clone, text.to_string());
+    });
+
+    let view_clone = view.clone();
+    clear_button.connect_clicked(move |_| {
+        view_clone.buffer().set_text("");
+    });
+
+    // Tail the log while the window is open
+    let open = Rc::new(Cell::new(true));
+    let open_clone = open.clone();
+    dialog.connect_close_request(move |_| {
+        open_clone.set(false);
+        glib::Propagation::Proceed
+    });
+
+    glib::spawn_future_local(async move {
+        while open.get() {
+            glib::timeout_future(std::time::Duration::from_millis(TAIL_INTERVAL_MS)).await;
+            let (lines, seq) = app_log::lines_since(next_seq.get());
+            next_seq.set(seq);
+            append_lines(&view, &scrolled, &lines);
+        }
+    });
+
+    dialog.present();
+}
@@ -2,70 +2,147 @@
 
 use crate::btrfs;
 use crate::backup_manager::{BackupManager, BackupStatusType};
+use crate::dbus_client::WaypointHelperClient;
+use crate::i18n::{gettext, gettext_f, ngettext_f};
 use gtk::prelude::*;
 use gtk::{glib, Label};
 use libadwaita as adw;
 use std::cell::RefCell;
 use std::rc::Rc;
+use waypoint_common::{FilesystemStats, FullDestinationAction};
+use waypoint_common::capabilities::FEATURE_SNAPSHOT_DIR_PERMISSIONS;
 
-/// Update the disk space label with current usage
+thread_local! {
+    // Header title whose subtitle summarizes snapshots and free space
+    static HEADER_TITLE: RefCell<Option<adw::WindowTitle>> = const { RefCell::new(None) };
+    // Footer label and level bar showing the space left for snapshots
+    static DISK_SPACE: RefCell<Option<(Label, gtk::LevelBar)>> = const { RefCell::new(None) };
+    // Status banner, revealed when an operation finds the filesystem read-only
+    static STATUS_BANNER: RefCell<Option<adw::Banner>> = const { RefCell::new(None) };
+}
+
+/// Troubleshooting section for a filesystem that went read-only
+const READ_ONLY_HELP_URL: &str = "https://github.com/Letdown2491/waypoint-gtk/blob/main/docs/TROUBLESHOOTING.md#filesystem-is-read-only";
+
+/// Register the header title updated by `update_header_summary`
+pub fn set_header_title(title: &adw::WindowTitle) {
+    HEADER_TITLE.with(|cell| *cell.borrow_mut() = Some(title.clone()));
+}
+
+/// Register the footer widgets updated by `update_header_summary`
+pub fn set_disk_space_widgets(label: &Label, level_bar: &gtk::LevelBar) {
+    DISK_SPACE.with(|cell| *cell.borrow_mut() = Some((label.clone(), level_bar.clone())));
+}
+
+/// Show "N snapshots · X free" in the header subtitle
 ///
-/// Queries the available space for the root filesystem and updates the label and level bar
-/// with color-coded visuals based on remaining space percentage.
-#[allow(dead_code)] // Kept for potential future use
-pub fn update_disk_space_label(label: &Label, level_bar: &gtk::LevelBar) {
-    use std::path::PathBuf;
-
-    // Query disk space for root (where snapshots are stored)
-    let space_result = btrfs::get_available_space(&PathBuf::from("/"));
-
-    match space_result {
-        Ok(available_bytes) => {
-            // Get total filesystem size by reading from df
-            let total_result = std::process::Command::new("df")
-                .arg("-B1")
-                .arg("--output=size")
-                .arg("/")
-                .output();
-
-            let (available_gb, total_gb, percent_free) = match total_result {
-                Ok(output) if output.status.success() => {
-                    let stdout = String::from_utf8_lossy(&output.stdout);
-                    let lines: Vec<&str> = stdout.lines().collect();
-
-                    if lines.len() >= 2 {
-                        if let Ok(total_bytes) = lines[1].trim().parse::<u64>() {
-                            let available_gb = available_bytes as f64 / 1_073_741_824.0; // Convert to GB
-                            let total_gb = total_bytes as f64 / 1_073_741_824.0;
-                            let percent = (available_bytes as f64 / total_bytes as f64) * 100.0;
-                            (available_gb, total_gb, percent)
-                        } else {
-                            // Fallback: just show available
-                            let available_gb = available_bytes as f64 / 1_073_741_824.0;
-                            (available_gb, 0.0, 0.0)
-                        }
-                    } else {
-                        let available_gb = available_bytes as f64 / 1_073_741_824.0;
-                        (available_gb, 0.0, 0.0)
-                    }
-                }
-                _ => {
-                    // Fallback: just show available
-                    let available_gb = available_bytes as f64 / 1_073_741_824.0;
-                    (available_gb, 0.0, 0.0)
+/// The free space is queried in the background (it may go through the helper) and
+/// the subtitle turns red once the critical disk space threshold is reached. The
+/// footer disk space label is updated from the same query.
+pub fn update_header_summary(snapshot_count: usize) {
+    let Some(title) = HEADER_TITLE.with(|cell| cell.borrow().clone()) else {
+        return;
+    };
+    let disk_space = DISK_SPACE.with(|cell| cell.borrow().clone());
+
+    let count_text = ngettext_f(
+        "{count} snapshot",
+        "{count} snapshots",
+        snapshot_count as u64,
+        &[("count", &snapshot_count.to_string())],
+    );
+    title.set_subtitle(&count_text);
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(btrfs::get_filesystem_stats(&btrfs::snapshot_space_path()));
+    });
+
+    glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
+        let stats = match rx.try_recv() {
+            Ok(result) => result,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return glib::ControlFlow::Continue,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => return glib::ControlFlow::Break,
+        };
+
+        if let Some((label, level_bar)) = &disk_space {
+            update_disk_space_label(label, level_bar, &stats);
+        }
+
+        title.remove_css_class("error");
+        title.set_tooltip_text(None);
+
+        match stats {
+            Ok(stats) if stats.total_bytes > 0 => {
+                let free = crate::snapshot::format_bytes(stats.available_bytes);
+                title.set_subtitle(&gettext_f(
+                    "{snapshots} · {free} free",
+                    &[("snapshots", &count_text), ("free", &free)],
+                ));
+
+                let (_, critical_percent) =
+                    super::notifications::load_settings().space_thresholds();
+                let percent_used =
+                    100.0 - stats.available_bytes as f64 / stats.total_bytes as f64 * 100.0;
+                if percent_used >= critical_percent {
+                    title.add_css_class("error");
+                    title.set_tooltip_text(Some(&gettext(
+                        "Low disk space! Consider deleting old snapshots.",
+                    )));
                 }
+            }
+            Ok(_) => {}
+            Err(e) => log::debug!("Failed to query disk space for the header: {e}"),
+        }
+
+        glib::ControlFlow::Break
+    });
+}
+
+/// Update the disk space label with current usage
+///
+/// Takes the free space of the filesystem holding the snapshot directory and updates
+/// the label and level bar with color-coded visuals based on the percentage in use, using
+/// the warning and critical thresholds from the notification preferences.
+fn update_disk_space_label(
+    label: &Label,
+    level_bar: &gtk::LevelBar,
+    stats: &anyhow::Result<FilesystemStats>,
+) {
+    let (warning_percent, critical_percent) =
+        super::notifications::load_settings().space_thresholds();
+    level_bar.add_offset_value(gtk::LEVEL_BAR_OFFSET_HIGH, warning_percent / 100.0);
+    level_bar.add_offset_value(gtk::LEVEL_BAR_OFFSET_FULL, critical_percent / 100.0);
+
+    match stats {
+        Ok(stats) => {
+            let available_gb = stats.available_bytes as f64 / 1_073_741_824.0; // Convert to GB
+            let total_gb = stats.total_bytes as f64 / 1_073_741_824.0;
+            let percent_free = if stats.total_bytes > 0 {
+                (stats.available_bytes as f64 / stats.total_bytes as f64) * 100.0
+            } else {
+                0.0
             };
 
             // Format the label text
             let text = if total_gb > 0.0 {
-                format!(
-                    "{available_gb:.1} GB free of {total_gb:.1} GB ({percent_free:.0}% free)"
+                gettext_f(
+                    "{available} GB free of {total} GB ({percent}% free)",
+                    &[
+                        ("available", &format!("{available_gb:.1}")),
+                        ("total", &format!("{total_gb:.1}")),
+                        ("percent", &format!("{percent_free:.0}")),
+                    ],
                 )
             } else {
-                format!("{available_gb:.1} GB free")
+                gettext_f(
+                    "{available} GB free",
+                    &[("available", &format!("{available_gb:.1}"))],
+                )
             };
 
             label.set_text(&text);
+            level_bar.update_property(&[gtk::accessible::Property::ValueText(&text)]);
 
             // Update level bar to show percentage used (inverted from percent_free)
             if total_gb > 0.0 {
@@ -81,47 +158,56 @@ pub fn update_disk_space_label(label: &Label, level_bar: &gtk::LevelBar) {
 
             // Color-code based on percentage (if we have total)
             if total_gb > 0.0 {
-                if percent_free < 10.0 {
-                    // Critical: < 10% free - red
+                let percent_used = 100.0 - percent_free;
+                if percent_used >= critical_percent {
+                    // Critical - red
                     label.add_css_class("error");
-                    label.set_tooltip_text(Some(
+                    label.set_tooltip_text(Some(&gettext(
                         "Low disk space! Consider deleting old snapshots.",
-                    ));
-                } else if percent_free < 20.0 {
-                    // Warning: < 20% free - yellow
+                    )));
+                } else if percent_used >= warning_percent {
+                    // Warning - yellow
                     label.add_css_class("warning");
-                    label.set_tooltip_text(Some(
+                    label.set_tooltip_text(Some(&gettext(
                         "Disk space running low. Monitor snapshot usage.",
-                    ));
+                    )));
                 } else {
-                    // OK: >= 20% free - normal
-                    label.set_tooltip_text(Some("Available disk space for snapshots"));
+                    // OK - normal
+                    label.set_tooltip_text(Some(&gettext("Available disk space for snapshots")));
                 }
             } else {
-                label.set_tooltip_text(Some("Available disk space"));
+                label.set_tooltip_text(Some(&gettext("Available disk space")));
             }
         }
         Err(e) => {
-            label.set_text("Space: Unknown");
-            label.set_tooltip_text(Some(&format!("Failed to query disk space: {e}")));
+            label.set_text(&gettext("Space: Unknown"));
+            label.set_tooltip_text(Some(&gettext_f(
+                "Failed to query disk space: {error}",
+                &[("error", &e.to_string())],
+            )));
         }
     }
 }
 
-/// Create the status banner that shows if Btrfs is available
-pub fn create_status_banner() -> (adw::Banner, bool) {
+/// Create the status banner that shows if snapshots can be created
+///
+/// Also returns why snapshot creation is unavailable, if it is: the root
+/// filesystem isn't Btrfs. Whether it is read-only is checked afterwards with
+/// `check_read_only_in_background`.
+pub fn create_status_banner() -> (adw::Banner, Option<String>) {
     let banner = adw::Banner::new("");
+    STATUS_BANNER.with(|cell| *cell.borrow_mut() = Some(banner.clone()));
 
     // Check if running on Btrfs
-    let is_btrfs = match btrfs::is_btrfs(&std::path::PathBuf::from("/")) {
+    let unavailable = match btrfs::is_btrfs(&std::path::PathBuf::from("/")) {
         Ok(true) => {
             // Btrfs detected - don't show banner
             banner.set_revealed(false);
-            true
+            None
         }
         Ok(false) => {
-            banner.set_title("Btrfs is required to create system restore points");
-            banner.set_button_label(Some("Learn More"));
+            banner.set_title(&gettext("Btrfs is required to create system restore points"));
+            banner.set_button_label(Some(&gettext("Learn More")));
             banner.set_revealed(true);
 
             // Connect "Learn More" button to open documentation
@@ -132,16 +218,151 @@ pub fn create_status_banner() -> (adw::Banner, bool) {
                     .spawn();
             });
 
-            false
+            Some(gettext("Btrfs filesystem required"))
         }
         Err(e) => {
-            banner.set_title(&format!("Unable to detect filesystem type: {e}"));
+            banner.set_title(&gettext_f(
+                "Unable to detect filesystem type: {error}",
+                &[("error", &e.to_string())],
+            ));
             banner.set_revealed(true);
-            false
+            Some(gettext("Btrfs filesystem required"))
         }
     };
 
-    (banner, is_btrfs)
+    (banner, unavailable)
+}
+
+/// Create the banner warning that other users can read snapshots
+///
+/// Revealed when the helper reports a snapshot directory that isn't root-only.
+/// Its button asks the helper to restrict the directories to root.
+pub fn create_permissions_banner() -> adw::Banner {
+    let banner = adw::Banner::new("");
+    banner.set_button_label(Some(&gettext("Restrict Access")));
+    banner.set_revealed(false);
+
+    if !crate::dbus_client::helper_supports(FEATURE_SNAPSHOT_DIR_PERMISSIONS) {
+        return banner;
+    }
+
+    banner.connect_button_clicked(|banner| update_permissions_banner(banner, true));
+    update_permissions_banner(&banner, false);
+    banner
+}
+
+/// Check the snapshot directory permissions in the background, or fix them
+/// if `secure` is set, and reveal the banner while any isn't root-only
+fn update_permissions_banner(banner: &adw::Banner, secure: bool) {
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let result = WaypointHelperClient::new().and_then(|client| {
+            if secure {
+                client.secure_snapshot_dirs()
+            } else {
+                client.get_snapshot_dir_permissions()
+            }
+        });
+        let _ = tx.send(result);
+    });
+
+    let banner = banner.clone();
+    glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return glib::ControlFlow::Continue,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => return glib::ControlFlow::Break,
+        };
+
+        match result {
+            Ok(permissions) => {
+                let insecure: Vec<String> = permissions
+                    .iter()
+                    .filter(|permissions| !permissions.is_secure())
+                    .map(|permissions| permissions.path.display().to_string())
+                    .collect();
+                if !insecure.is_empty() {
+                    banner.set_title(&gettext_f(
+                        "Other users can open {dirs} and read files in your snapshots, such as old copies of private keys",
+                        &[("dirs", &insecure.join(", "))],
+                    ));
+                }
+                banner.set_revealed(!insecure.is_empty());
+            }
+            Err(e) if secure => {
+                log::error!("Failed to restrict snapshot directories: {e}");
+                banner.set_title(&gettext_f(
+                    "Could not restrict access to snapshots: {error}",
+                    &[("error", &e.to_string())],
+                ));
+            }
+            Err(e) => log::warn!("Failed to check snapshot directory permissions: {e}"),
+        }
+
+        glib::ControlFlow::Break
+    });
+}
+
+/// Whether the root or snapshot filesystem is mounted read-only
+///
+/// Runs `findmnt`, so call it off the main thread.
+pub fn filesystem_read_only() -> bool {
+    [std::path::PathBuf::from("/"), btrfs::snapshot_space_path()]
+        .iter()
+        .any(|path| btrfs::is_mounted_read_only(path).unwrap_or(false))
+}
+
+/// Check in the background whether the filesystem is read-only
+///
+/// If it is, reveals the status banner and calls `on_read_only`.
+pub fn check_read_only_in_background(on_read_only: impl FnOnce() + 'static) {
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(filesystem_read_only());
+    });
+
+    glib::spawn_future_local(async move {
+        let read_only = loop {
+            match rx.try_recv() {
+                Ok(read_only) => break read_only,
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => return,
+            }
+        };
+        if read_only {
+            show_read_only_banner();
+            on_read_only();
+        }
+    });
+}
+
+/// Reveal the status banner explaining that the filesystem went read-only
+///
+/// Called at startup and whenever the helper reports a read-only filesystem,
+/// since Btrfs can remount itself read-only while Waypoint is running.
+pub fn show_read_only_banner() {
+    STATUS_BANNER.with(|cell| {
+        let Some(banner) = cell.borrow().clone() else {
+            return;
+        };
+        if banner.is_revealed() {
+            return;
+        }
+
+        banner.set_title(&gettext(
+            "The filesystem is read-only, usually because Btrfs detected errors. Snapshot operations are disabled until it is repaired and remounted.",
+        ));
+        banner.set_button_label(Some(&gettext("Learn More")));
+        banner.add_css_class("error");
+        banner.connect_button_clicked(|_| {
+            let _ = std::process::Command::new("xdg-open")
+                .arg(READ_ONLY_HELP_URL)
+                .spawn();
+        });
+        banner.set_revealed(true);
+    });
 }
 
 /// Stop a progress pulse animation
@@ -171,27 +392,229 @@ pub fn update_backup_status_label(label: &Label, backup_manager: &Rc<RefCell<Bac
     match summary.status_type {
         BackupStatusType::NotConfigured => {
             label.add_css_class("dim-label");
-            label.set_tooltip_text(Some("Click to configure backup destinations"));
+            label.set_tooltip_text(Some(&gettext("Click to configure backup destinations")));
         }
         BackupStatusType::Healthy => {
             label.add_css_class("success");
-            label.set_tooltip_text(Some("All backup destinations are up to date"));
+            label.set_tooltip_text(Some(&gettext("All backup destinations are up to date")));
         }
         BackupStatusType::Active => {
             label.add_css_class("dim-label");
-            label.set_tooltip_text(Some("Backup in progress..."));
+            label.set_tooltip_text(Some(&gettext("Backup in progress...")));
         }
         BackupStatusType::Pending => {
             label.add_css_class("warning");
-            label.set_tooltip_text(Some("Click to view pending backups"));
+            label.set_tooltip_text(Some(&gettext("Click to view pending backups")));
         }
         BackupStatusType::Failed => {
             label.add_css_class("error");
-            label.set_tooltip_text(Some("Click to view failed backups"));
+            label.set_tooltip_text(Some(&gettext("Click to view failed backups")));
         }
         BackupStatusType::Disconnected => {
             label.add_css_class("warning");
-            label.set_tooltip_text(Some("Some backup destinations are not connected"));
+            label.set_tooltip_text(Some(&gettext("Some backup destinations are not connected")));
         }
     }
 }
+
+/// Process a destination's pending backups in the background if its drive is mounted
+///
+/// Used by the snapshot-creation, favorite and schedule triggers. Returns false when
+/// the destination isn't connected; the backups stay queued until it is.
+pub fn start_pending_backups_if_mounted(
+    app: Option<&gtk::Application>,
+    backup_manager: &Rc<RefCell<BackupManager>>,
+    dest_uuid: &str,
+) -> bool {
+    let Some(mount_point) = backup_manager.borrow().get_mounted_destination(dest_uuid) else {
+        log::info!(
+            "Destination {dest_uuid} is not currently mounted, backup will process when drive is connected"
+        );
+        return false;
+    };
+
+    let (dest_label, pending_count) = {
+        let bm = backup_manager.borrow();
+        let label = bm
+            .get_config()
+            .ok()
+            .and_then(|c| c.destinations.get(dest_uuid).map(|d| d.label.clone()))
+            .unwrap_or_else(|| mount_point.clone());
+        (label, bm.get_pending_count(dest_uuid))
+    };
+
+    if pending_count == 0 {
+        return false;
+    }
+
+    log::info!("Destination {dest_uuid} is mounted at {mount_point}, processing {pending_count} pending backup(s)");
+    if let Some(app) = app {
+        super::notifications::notify_backup_started(app, &dest_label, pending_count);
+    }
+
+    run_pending_backups(
+        app.cloned(),
+        backup_manager.borrow().clone(),
+        dest_uuid.to_string(),
+        mount_point,
+        dest_label,
+        None,
+    );
+
+    true
+}
+
+/// Process a destination's pending backups in a background thread
+///
+/// `when_full` overrides the destination's setting for a drive too full for a
+/// backup. Backups left queued for lack of space are reported once the run is
+/// done, if there is an application to report them with.
+fn run_pending_backups(
+    app: Option<gtk::Application>,
+    backup_manager: BackupManager,
+    dest_uuid: String,
+    mount_point: String,
+    dest_label: String,
+    when_full: Option<FullDestinationAction>,
+) {
+    let snapshot_dir = waypoint_common::WaypointConfig::new()
+        .snapshot_dir
+        .to_string_lossy()
+        .to_string();
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let backup_manager_bg = backup_manager.clone();
+    let dest_uuid_bg = dest_uuid.clone();
+    let mount_point_bg = mount_point.clone();
+    std::thread::spawn(move || {
+        let (uuid, mount) = (dest_uuid_bg.as_str(), mount_point_bg.as_str());
+        let result = match when_full {
+            Some(when_full) => backup_manager_bg.process_pending_backups_with(
+                uuid,
+                mount,
+                &snapshot_dir,
+                when_full,
+            ),
+            None => backup_manager_bg.process_pending_backups(uuid, mount, &snapshot_dir),
+        };
+        match result {
+            Ok((success, failed, errors)) => {
+                log::info!("Backup processing completed: {success} succeeded, {failed} failed");
+                if !errors.is_empty() {
+                    log::error!("Backup errors: {errors:?}");
+                }
+            }
+            Err(e) => {
+                log::error!("Failed to process backups for destination {uuid}: {e}");
+            }
+        }
+        let _ = tx.send(());
+    });
+
+    let Some(app) = app else {
+        return;
+    };
+    glib::spawn_future_local(async move {
+        loop {
+            match rx.try_recv() {
+                Ok(()) => break,
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => return,
+            }
+        }
+        report_space_shortage(&app, &backup_manager, &dest_uuid, &mount_point, &dest_label);
+    });
+}
+
+/// Tell the user about backups left queued because the destination is too full
+///
+/// Destinations set to ask get a dialog offering to delete old backups and try
+/// again, the others a notification.
+pub fn report_space_shortage(
+    app: &gtk::Application,
+    backup_manager: &BackupManager,
+    dest_uuid: &str,
+    mount_point: &str,
+    dest_label: &str,
+) {
+    let Some(reason) = backup_manager.take_space_shortage(dest_uuid) else {
+        return;
+    };
+
+    let when_full = backup_manager
+        .get_config()
+        .ok()
+        .and_then(|c| c.get_destination(dest_uuid).map(|d| d.when_full));
+    let window = app.active_window().and_downcast::<adw::ApplicationWindow>();
+    let (Some(FullDestinationAction::Ask), Some(window)) = (when_full, window) else {
+        super::notifications::notify_backup_destination_full(app, dest_label, &reason);
+        return;
+    };
+
+    let message = gettext_f(
+        "{reason}\n\nDelete the oldest backups on {drive} to make room? Backups within its retention period and its latest backup are kept.",
+        &[("reason", &reason), ("drive", dest_label)],
+    );
+    let app = app.clone();
+    let backup_manager = backup_manager.clone();
+    let dest_uuid = dest_uuid.to_string();
+    let mount_point = mount_point.to_string();
+    let dest_label = dest_label.to_string();
+    super::dialogs::show_confirmation(
+        &window,
+        &gettext("Backup Drive Full"),
+        &message,
+        &gettext("Delete Old Backups"),
+        true,
+        move || {
+            run_pending_backups(
+                Some(app.clone()),
+                backup_manager.clone(),
+                dest_uuid.clone(),
+                mount_point.clone(),
+                dest_label.clone(),
+                Some(FullDestinationAction::Prune),
+            );
+        },
+    );
+}
+
+/// Query the helper's capabilities in the background and hide unsupported features
+///
+/// Each widget is shown only if the helper supports the paired feature, so an older
+/// helper paired with a newer GUI never exposes operations it can't perform.
+pub fn apply_helper_capabilities(gated: Vec<(gtk::Widget, &'static str)>) {
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let caps = crate::dbus_client::WaypointHelperClient::new().map(|client| client.capabilities());
+        let _ = tx.send(caps);
+    });
+
+    glib::spawn_future_local(async move {
+        let caps = loop {
+            match rx.try_recv() {
+                Ok(caps) => break caps,
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => return,
+            }
+        };
+
+        match caps {
+            Ok(caps) => {
+                log::info!(
+                    "waypoint-helper interface version {} ({} optional features)",
+                    caps.interface_version,
+                    caps.features.len()
+                );
+                for (widget, feature) in gated {
+                    widget.set_visible(caps.supports(feature));
+                }
+            }
+            Err(e) => log::warn!("Could not query waypoint-helper capabilities: {e}"),
+        }
+    });
+}
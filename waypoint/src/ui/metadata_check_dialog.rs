@@ -0,0 +1,241 @@
+//! Snapshot metadata consistency check
+//!
+//! Lists metadata entries that contradict each other or their snapshot
+//! directory, as crashes during creation, renaming or deletion can leave them,
+//! and lets the user merge the entries or remove the extra ones. The helper
+//! both checks and changes the metadata.
+
+use adw::prelude::*;
+use gtk::prelude::*;
+use gtk::{Orientation, glib};
+use libadwaita as adw;
+use std::rc::Rc;
+use std::sync::mpsc;
+use waypoint_common::{ConflictKind, ConflictResolution, MetadataConflict};
+
+use super::dialogs;
+use crate::dbus_client::WaypointHelperClient;
+use crate::i18n::gettext;
+
+/// Show the metadata problems found and the actions resolving them
+///
+/// `on_changed` runs after every resolved problem, e.g. to refresh the
+/// snapshot list.
+pub fn show_metadata_check_dialog(parent: &impl IsA<gtk::Window>, on_changed: Rc<dyn Fn()>) {
+    let dialog = adw::Window::new();
+    dialog.set_transient_for(Some(parent));
+    dialog.set_modal(true);
+    dialog.set_title(Some(&gettext("Snapshot Metadata")));
+    dialog.set_default_size(560, 480);
+
+    let main_box = gtk::Box::new(Orientation::Vertical, 0);
+    main_box.append(&adw::HeaderBar::new());
+
+    let status = adw::StatusPage::new();
+    status.set_icon_name(Some("content-loading-symbolic"));
+    status.set_title(&gettext("Checking Metadata…"));
+    status.set_vexpand(true);
+    main_box.append(&status);
+
+    let group = adw::PreferencesGroup::new();
+    group.set_description(Some(&gettext(
+        "These entries were probably left behind by an interrupted operation. \
+        Merging keeps details only some of the entries have, removing keeps the \
+        entry matching the snapshot's directory.",
+    )));
+    group.set_margin_top(24);
+    group.set_margin_bottom(24);
+    group.set_margin_start(12);
+    group.set_margin_end(12);
+
+    let list = gtk::ListBox::new();
+    list.add_css_class("boxed-list");
+    list.set_selection_mode(gtk::SelectionMode::None);
+    group.add(&list);
+
+    let clamp = adw::Clamp::new();
+    clamp.set_maximum_size(600);
+    clamp.set_child(Some(&group));
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_vexpand(true);
+    scrolled.set_visible(false);
+    scrolled.set_child(Some(&clamp));
+    main_box.append(&scrolled);
+
+    dialog.set_content(Some(&main_box));
+
+    let view = ConflictView {
+        dialog: dialog.clone(),
+        list,
+        status,
+        scrolled,
+        on_changed,
+    };
+    view.reload();
+    dialog.present();
+}
+
+/// Widgets listing the conflicts, refilled after each resolution
+#[derive(Clone)]
+struct ConflictView {
+    dialog: adw::Window,
+    list: gtk::ListBox,
+    status: adw::StatusPage,
+    scrolled: gtk::ScrolledWindow,
+    on_changed: Rc<dyn Fn()>,
+}
+
+impl ConflictView {
+    /// Check the metadata again in the background and list what is found
+    fn reload(&self) {
+        self.list.set_sensitive(false);
+
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result =
+                WaypointHelperClient::new().and_then(|client| client.check_snapshot_metadata());
+            let _ = tx.send(result);
+        });
+
+        let view = self.clone();
+        glib::spawn_future_local(async move {
+            let result = loop {
+                match rx.try_recv() {
+                    Ok(result) => break result,
+                    Err(mpsc::TryRecvError::Empty) => {
+                        glib::timeout_future(std::time::Duration::from_millis(50)).await;
+                    }
+                    Err(mpsc::TryRecvError::Disconnected) => return,
+                }
+            };
+            view.show_conflicts(result);
+        });
+    }
+
+    fn show_conflicts(&self, result: anyhow::Result<Vec<MetadataConflict>>) {
+        while let Some(child) = self.list.first_child() {
+            self.list.remove(&child);
+        }
+        self.list.set_sensitive(true);
+
+        let conflicts = match result {
+            Ok(conflicts) => conflicts,
+            Err(e) => {
+                log::error!("Failed to check snapshot metadata: {e}");
+                self.status.set_icon_name(Some("dialog-error-symbolic"));
+                self.status.set_title(&gettext("Could Not Check Metadata"));
+                self.status.set_description(Some(&e.to_string()));
+                self.status.set_visible(true);
+                self.scrolled.set_visible(false);
+                return;
+            }
+        };
+
+        if conflicts.is_empty() {
+            self.status.set_icon_name(Some("emblem-ok-symbolic"));
+            self.status.set_title(&gettext("Metadata Is Consistent"));
+            self.status.set_description(Some(&gettext(
+                "Every snapshot has exactly one entry matching its directory.",
+            )));
+        }
+        self.status.set_visible(conflicts.is_empty());
+        self.scrolled.set_visible(!conflicts.is_empty());
+        for conflict in conflicts {
+            self.list.append(&self.create_row(conflict));
+        }
+    }
+
+    fn create_row(&self, conflict: MetadataConflict) -> adw::ActionRow {
+        let row = adw::ActionRow::new();
+        row.set_title(&glib::markup_escape_text(&conflict.name));
+        row.set_subtitle(&conflict.description());
+        row.set_subtitle_lines(2);
+
+        let icon = gtk::Image::from_icon_name("dialog-warning-symbolic");
+        icon.add_css_class("warning");
+        row.add_prefix(&icon);
+
+        let conflict = Rc::new(conflict);
+        if conflict.kind == ConflictKind::NameMismatch {
+            let rename_btn = gtk::Button::with_label(&gettext("Use Directory Name"));
+            rename_btn.set_valign(gtk::Align::Center);
+            rename_btn.set_tooltip_text(Some(&gettext(
+                "Rename the entry after its snapshot directory",
+            )));
+            self.connect_resolution(&rename_btn, &conflict, ConflictResolution::Merge);
+            row.add_suffix(&rename_btn);
+            return row;
+        }
+
+        let remove_btn = gtk::Button::with_label(&gettext("Remove Extra"));
+        remove_btn.set_valign(gtk::Align::Center);
+        remove_btn.set_tooltip_text(Some(&gettext("Keep one entry and drop the others")));
+        self.connect_resolution(&remove_btn, &conflict, ConflictResolution::RemoveExtra);
+        row.add_suffix(&remove_btn);
+
+        let merge_btn = gtk::Button::with_label(&gettext("Merge"));
+        merge_btn.set_valign(gtk::Align::Center);
+        merge_btn.add_css_class("suggested-action");
+        merge_btn.set_tooltip_text(Some(&gettext("Combine the entries into one")));
+        self.connect_resolution(&merge_btn, &conflict, ConflictResolution::Merge);
+        row.add_suffix(&merge_btn);
+
+        row
+    }
+
+    fn connect_resolution(
+        &self,
+        button: &gtk::Button,
+        conflict: &Rc<MetadataConflict>,
+        resolution: ConflictResolution,
+    ) {
+        let view = self.clone();
+        let conflict = conflict.clone();
+        button.connect_clicked(move |_| {
+            view.list.set_sensitive(false);
+
+            let (tx, rx) = mpsc::channel();
+            let conflict_for_thread = (*conflict).clone();
+            std::thread::spawn(move || {
+                let result = WaypointHelperClient::new().and_then(|client| {
+                    client.resolve_metadata_conflict(&conflict_for_thread, resolution)
+                });
+                let _ = tx.send(result);
+            });
+
+            let view = view.clone();
+            let conflict = conflict.clone();
+            glib::spawn_future_local(async move {
+                let result = loop {
+                    match rx.try_recv() {
+                        Ok(result) => break result,
+                        Err(mpsc::TryRecvError::Empty) => {
+                            glib::timeout_future(std::time::Duration::from_millis(50)).await;
+                        }
+                        Err(mpsc::TryRecvError::Disconnected) => return,
+                    }
+                };
+
+                match result {
+                    Ok(_) => {
+                        log::info!(
+                            "Resolved metadata conflict for '{}' ({resolution:?})",
+                            conflict.name
+                        );
+                        (view.on_changed)();
+                    }
+                    Err(e) => {
+                        log::error!("Failed to resolve metadata conflict: {e}");
+                        dialogs::show_error_window(
+                            &view.dialog,
+                            &gettext("Could Not Update Metadata"),
+                            &e.to_string(),
+                        );
+                    }
+                }
+                view.reload();
+            });
+        });
+    }
+}
@@ -1,16 +1,25 @@
 mod about_preferences;
+mod activity_log_dialog;
 mod analytics_dialog;
+mod audit_log_dialog;
 mod backup_dialog;
+mod command_transcript_dialog;
 mod comparison_dialog;
 mod comparison_view;
+mod config_transfer;
 mod create_snapshot_dialog;
 mod dialogs;
 mod error_helpers;
 mod exclude_preferences;
 mod file_diff_dialog;
 mod file_restore_dialog;
+mod log_viewer_dialog;
 mod main_window_helpers;
+mod metadata_check_dialog;
+mod notification_preferences;
 pub mod notifications;
+mod operations_panel;
+mod overlay_dialog;
 mod package_diff_dialog;
 pub mod preferences;
 mod preferences_window;
@@ -21,15 +30,30 @@ mod scheduler_dialog;
 mod shortcuts_window;
 mod snapshot_list;
 mod snapshot_row;
+mod subvolume_restore_dialog;
+mod timeline_view;
 mod toolbar;
 mod validation;
+mod verify_all_dialog;
 
+use crate::activity_log::{self, ActivityKind};
 use crate::backup_manager::BackupManager;
 use crate::btrfs;
+use crate::create_queue;
 use crate::dbus_client::WaypointHelperClient;
+use crate::i18n::{gettext, gettext_f, ngettext_f};
+use crate::operations::{self, OperationId, OperationKind};
 use crate::snapshot::{Snapshot, SnapshotManager};
-use crate::user_preferences::UserPreferencesManager;
-use waypoint_common::BackupConfig;
+use crate::user_preferences::{PreferenceChange, UserPreferencesManager};
+use waypoint_common::capabilities::{
+    FEATURE_AUDIT_LOG, FEATURE_BALANCE, FEATURE_CANCEL_AUTHORIZATION, FEATURE_CANCEL_CREATE,
+    FEATURE_COMMAND_TRANSCRIPT, FEATURE_CONFIG_TRANSFER, FEATURE_RESTORE_TO_SUBVOLUME,
+    FEATURE_EXCLUSIVE_SIZES, FEATURE_METADATA_CHECK, FEATURE_SNAPSHOT_OVERLAYS,
+    FEATURE_SNAPSHOT_PINS,
+};
+use waypoint_common::{
+    BackupConfig, BackupFormat, BackupTrigger, SchedulesConfig, SnapshotCategory, WaypointConfig,
+};
 use adw::prelude::*;
 use anyhow::Context;
 use gtk::glib;
@@ -51,6 +75,9 @@ use backup_dialog::types::{BackupDestination, DriveType};
 
 // Path validation moved to validation module
 
+/// How often to check whether a scheduled backup or a retry is due
+const SCHEDULED_BACKUP_CHECK_SECONDS: u32 = 15 * 60;
+
 pub struct MainWindow {
     window: adw::ApplicationWindow,
     snapshot_manager: Rc<RefCell<SnapshotManager>>,
@@ -81,17 +108,18 @@ impl MainWindow {
                 // Show error dialog to user
                 let dialog = adw::MessageDialog::new(
                     Some(&temp_window),
-                    Some("Failed to Initialize Waypoint"),
-                    Some(&format!(
-                        "Could not initialize the snapshot manager:\n\n{e}\n\n\
+                    Some(&gettext("Failed to Initialize Waypoint")),
+                    Some(&gettext_f(
+                        "Could not initialize the snapshot manager:\n\n{error}\n\n\
                         Please check that:\n\
                         • Btrfs filesystem is available\n\
-                        • /.snapshots directory exists and is mounted\n\
-                        • D-Bus service is running"
+                        • The snapshot directory exists and is mounted\n\
+                        • D-Bus service is running",
+                        &[("error", &e.to_string())],
                     )),
                 );
 
-                dialog.add_response("ok", "OK");
+                dialog.add_response("ok", &gettext("OK"));
                 dialog.set_default_response(Some("ok"));
 
                 let app_clone = app.clone();
@@ -135,7 +163,9 @@ impl MainWindow {
 
         // Create header bar
         let header = adw::HeaderBar::new();
-        header.set_title_widget(Some(&adw::WindowTitle::new("Waypoint", "")));
+        let window_title = adw::WindowTitle::new("Waypoint", "");
+        main_window_helpers::set_header_title(&window_title);
+        header.set_title_widget(Some(&window_title));
 
         // Add application icon to header bar
         let app_icon = if let Ok(icon_path) =
@@ -171,7 +201,7 @@ impl MainWindow {
         theme_list.set_selection_mode(gtk::SelectionMode::None);
         theme_list.add_css_class("boxed-list");
 
-        let theme_row = adw::ActionRow::builder().title("Switch theme").build();
+        let theme_row = adw::ActionRow::builder().title(gettext("Switch theme")).build();
 
         // Theme buttons
         let theme_buttons_box = gtk::Box::new(Orientation::Horizontal, 12);
@@ -179,33 +209,38 @@ impl MainWindow {
 
         let system_btn = gtk::Button::builder()
             .label("")
-            .tooltip_text("Match system theme")
+            .tooltip_text(gettext("Match system theme"))
             .width_request(16)
             .height_request(16)
             .build();
         system_btn.add_css_class("flat");
         system_btn.add_css_class("theme-circle");
         system_btn.add_css_class("theme-circle-system");
+        system_btn.update_property(&[gtk::accessible::Property::Label(&gettext(
+            "Match system theme",
+        ))]);
 
         let light_btn = gtk::Button::builder()
             .label("")
-            .tooltip_text("Light theme")
+            .tooltip_text(gettext("Light theme"))
             .width_request(16)
             .height_request(16)
             .build();
         light_btn.add_css_class("flat");
         light_btn.add_css_class("theme-circle");
         light_btn.add_css_class("theme-circle-light");
+        light_btn.update_property(&[gtk::accessible::Property::Label(&gettext("Light theme"))]);
 
         let dark_btn = gtk::Button::builder()
             .label("")
-            .tooltip_text("Dark theme")
+            .tooltip_text(gettext("Dark theme"))
             .width_request(16)
             .height_request(16)
             .build();
         dark_btn.add_css_class("flat");
         dark_btn.add_css_class("theme-circle");
         dark_btn.add_css_class("theme-circle-dark");
+        dark_btn.update_property(&[gtk::accessible::Property::Label(&gettext("Dark theme"))]);
 
         system_btn.set_hexpand(false);
         system_btn.set_vexpand(false);
@@ -223,6 +258,12 @@ impl MainWindow {
 
         theme_row.add_suffix(&theme_buttons_box);
         theme_list.append(&theme_row);
+
+        let compact_row = adw::SwitchRow::builder()
+            .title(gettext("Compact list"))
+            .active(snapshot_list::load_display_settings().compact)
+            .build();
+        theme_list.append(&compact_row);
         popover_box.append(&theme_list);
 
         // Menu items section
@@ -231,25 +272,69 @@ impl MainWindow {
         menu_list.add_css_class("boxed-list");
 
         let analytics_row = adw::ActionRow::builder()
-            .title("Analytics")
+            .title(gettext("Analytics"))
             .activatable(true)
             .build();
         menu_list.append(&analytics_row);
 
+        let verify_all_row = adw::ActionRow::builder()
+            .title(gettext("Verify All Snapshots"))
+            .activatable(true)
+            .build();
+        menu_list.append(&verify_all_row);
+
+        let activity_row = adw::ActionRow::builder()
+            .title(gettext("Activity"))
+            .activatable(true)
+            .build();
+        menu_list.append(&activity_row);
+
+        let audit_log_row = adw::ActionRow::builder()
+            .title(gettext("Audit Log"))
+            .activatable(true)
+            .build();
+        menu_list.append(&audit_log_row);
+
+        // Only shown in explain mode
+        let transcript_row = adw::ActionRow::builder()
+            .title(gettext("Command Transcript"))
+            .activatable(true)
+            .build();
+        transcript_row.set_visible(Self::show_command_transcript());
+        menu_list.append(&transcript_row);
+
+        let log_row = adw::ActionRow::builder()
+            .title(gettext("Log"))
+            .activatable(true)
+            .build();
+        menu_list.append(&log_row);
+
         let preferences_row = adw::ActionRow::builder()
-            .title("Preferences")
+            .title(gettext("Preferences"))
             .activatable(true)
             .build();
         menu_list.append(&preferences_row);
 
+        let export_row = adw::ActionRow::builder()
+            .title(gettext("Export Settings"))
+            .activatable(true)
+            .build();
+        menu_list.append(&export_row);
+
+        let import_row = adw::ActionRow::builder()
+            .title(gettext("Import Settings"))
+            .activatable(true)
+            .build();
+        menu_list.append(&import_row);
+
         let shortcuts_row = adw::ActionRow::builder()
-            .title("Keyboard Shortcuts")
+            .title(gettext("Keyboard Shortcuts"))
             .activatable(true)
             .build();
         menu_list.append(&shortcuts_row);
 
         let about_row = adw::ActionRow::builder()
-            .title("About Waypoint")
+            .title(gettext("About Waypoint"))
             .activatable(true)
             .build();
         menu_list.append(&about_row);
@@ -259,19 +344,35 @@ impl MainWindow {
         popover.set_child(Some(&popover_box));
         menu_button.set_popover(Some(&popover));
         header.pack_end(&menu_button);
+        header.pack_end(&operations_panel::create_operations_button());
 
-        // Status banner - also returns whether Btrfs is available
-        let (banner, is_btrfs) = main_window_helpers::create_status_banner();
+        // Status banner - also returns why snapshots can't be created, if they can't
+        let (banner, unavailable) = main_window_helpers::create_status_banner();
 
         // Toolbar with buttons
-        let (toolbar, create_btn, compare_btn, search_btn) = toolbar::create_toolbar();
+        let (toolbar, create_btn, quick_btn, compare_btn, timeline_btn, refresh_btn, search_btn) =
+            toolbar::create_toolbar();
 
-        // Disable create button if not on Btrfs
-        if !is_btrfs {
+        // Disable create buttons if not on Btrfs or the filesystem is read-only
+        if let Some(reason) = &unavailable {
             create_btn.set_sensitive(false);
-            create_btn.set_tooltip_text(Some("Btrfs filesystem required"));
+            create_btn.set_tooltip_text(Some(reason));
+            quick_btn.set_sensitive(false);
+            quick_btn.set_tooltip_text(Some(reason));
+        } else {
+            let create_btn = create_btn.clone();
+            let quick_btn = quick_btn.clone();
+            main_window_helpers::check_read_only_in_background(move || {
+                let reason = gettext("The filesystem is read-only");
+                create_btn.set_sensitive(false);
+                create_btn.set_tooltip_text(Some(&reason));
+                quick_btn.set_sensitive(false);
+                quick_btn.set_tooltip_text(Some(&reason));
+            });
         }
 
+        quick_btn.set_visible(preferences::load_manual_settings().quick_snapshot_enabled);
+
         // Search and filter UI (wrapped in Revealer for smooth animations)
         let search_revealer = gtk::Revealer::new();
         search_revealer.set_transition_type(gtk::RevealerTransitionType::SlideDown);
@@ -286,7 +387,7 @@ impl MainWindow {
 
         // Search entry
         let search_entry = SearchEntry::new();
-        search_entry.set_placeholder_text(Some("Search snapshots..."));
+        search_entry.set_placeholder_text(Some(&gettext("Search snapshots...")));
         search_entry.set_hexpand(true);
         search_box.append(&search_entry);
 
@@ -294,10 +395,10 @@ impl MainWindow {
         let filter_box = gtk::Box::new(Orientation::Horizontal, 6);
         filter_box.add_css_class("linked");
 
-        let all_btn = ToggleButton::with_label("All");
-        let week_btn = ToggleButton::with_label("Last 7 days");
-        let month_btn = ToggleButton::with_label("Last 30 days");
-        let quarter_btn = ToggleButton::with_label("Last 90 days");
+        let all_btn = ToggleButton::with_label(&gettext("All"));
+        let week_btn = ToggleButton::with_label(&gettext("Last 7 days"));
+        let month_btn = ToggleButton::with_label(&gettext("Last 30 days"));
+        let quarter_btn = ToggleButton::with_label(&gettext("Last 90 days"));
 
         all_btn.set_active(true); // Default to "All"
 
@@ -306,7 +407,52 @@ impl MainWindow {
         filter_box.append(&month_btn);
         filter_box.append(&quarter_btn);
 
-        search_box.append(&filter_box);
+        // Subvolume filter dropdown (first entry matches any subvolume)
+        let filter_subvolumes: Vec<PathBuf> = crate::subvolume::detect_snapshot_candidates()
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to detect subvolumes for filtering: {e}");
+                Vec::new()
+            })
+            .into_iter()
+            .map(|s| s.mount_point)
+            .collect();
+
+        let mut subvolume_labels = vec![gettext("All subvolumes")];
+        subvolume_labels.extend(filter_subvolumes.iter().map(|p| p.display().to_string()));
+        let subvolume_label_refs: Vec<&str> = subvolume_labels.iter().map(String::as_str).collect();
+
+        let subvolume_dropdown = gtk::DropDown::from_strings(&subvolume_label_refs);
+        subvolume_dropdown.set_tooltip_text(Some(&gettext(
+            "Only show snapshots that include this subvolume",
+        )));
+        subvolume_dropdown.set_sensitive(!filter_subvolumes.is_empty());
+
+        // Category filter dropdown (first entry matches any category)
+        let mut category_labels = vec![gettext("All categories")];
+        category_labels.extend(SnapshotCategory::ALL.iter().map(|c| c.label().to_string()));
+        let category_label_refs: Vec<&str> = category_labels.iter().map(String::as_str).collect();
+
+        let category_dropdown = gtk::DropDown::from_strings(&category_label_refs);
+        category_dropdown.set_tooltip_text(Some(&gettext("Only show snapshots in this category")));
+
+        // Backup filter dropdown (first entry matches any backup state)
+        let backup_labels = [gettext("Any backup state"), gettext("Not backed up")];
+        let backup_label_refs: Vec<&str> = backup_labels.iter().map(String::as_str).collect();
+        let backup_dropdown = gtk::DropDown::from_strings(&backup_label_refs);
+        backup_dropdown.set_tooltip_text(Some(&gettext(
+            "Only show snapshots without a backup on any destination",
+        )));
+
+        let filter_row = gtk::Box::new(Orientation::Horizontal, 12);
+        filter_row.append(&filter_box);
+        let filter_spacer = gtk::Box::new(Orientation::Horizontal, 0);
+        filter_spacer.set_hexpand(true);
+        filter_row.append(&filter_spacer);
+        filter_row.append(&category_dropdown);
+        filter_row.append(&backup_dropdown);
+        filter_row.append(&subvolume_dropdown);
+
+        search_box.append(&filter_row);
 
         // Match count label
         let match_label = Label::new(None);
@@ -322,6 +468,8 @@ impl MainWindow {
         let snapshot_list = ListBox::new();
         snapshot_list.set_selection_mode(gtk::SelectionMode::None);
         snapshot_list.add_css_class("boxed-list");
+        snapshot_list
+            .update_property(&[gtk::accessible::Property::Label(&gettext("Restore points"))]);
 
         let scrolled = ScrolledWindow::new();
         scrolled.set_vexpand(true);
@@ -333,13 +481,27 @@ impl MainWindow {
         scrolled.set_margin_start(12);
         scrolled.set_margin_end(12);
 
+        // Timeline view, shown instead of the list while the toolbar toggle is active
+        let timeline = timeline_view::TimelineView::new(&snapshot_manager);
+
+        let view_stack = gtk::Stack::new();
+        view_stack.set_transition_type(gtk::StackTransitionType::Crossfade);
+        view_stack.set_vexpand(true);
+        view_stack.add_named(&scrolled, Some("list"));
+        view_stack.add_named(timeline.widget(), Some("timeline"));
+
+        let view_stack_clone = view_stack.clone();
+        timeline_btn.connect_toggled(move |btn| {
+            view_stack_clone.set_visible_child_name(if btn.is_active() { "timeline" } else { "list" });
+        });
+
         // Backup status footer
         let backup_status_box = gtk::Box::new(Orientation::Vertical, 6);
         backup_status_box.set_halign(gtk::Align::Center);
         backup_status_box.set_margin_top(0);
         backup_status_box.set_margin_bottom(12);
 
-        let backup_status_label = Label::new(Some("Checking backup status..."));
+        let backup_status_label = Label::new(Some(&gettext("Checking backup status...")));
         backup_status_label.add_css_class("caption");
         backup_status_label.add_css_class("dim-label");
         backup_status_label.add_css_class("link"); // Make it look clickable
@@ -347,12 +509,29 @@ impl MainWindow {
 
         backup_status_box.append(&backup_status_label);
 
+        // Space left for snapshots, filled in with the header summary
+        let disk_space_box = gtk::Box::new(Orientation::Horizontal, 6);
+        disk_space_box.set_halign(gtk::Align::Center);
+        let disk_space_bar = gtk::LevelBar::new();
+        disk_space_bar.set_width_request(120);
+        disk_space_bar.set_valign(gtk::Align::Center);
+        disk_space_bar.update_property(&[gtk::accessible::Property::Label(&gettext(
+            "Disk space used by snapshots",
+        ))]);
+        let disk_space_label = Label::new(None);
+        disk_space_label.add_css_class("caption");
+        disk_space_box.append(&disk_space_bar);
+        disk_space_box.append(&disk_space_label);
+        main_window_helpers::set_disk_space_widgets(&disk_space_label, &disk_space_bar);
+        backup_status_box.append(&disk_space_box);
+
         // Main content box
         let content_box = gtk::Box::new(Orientation::Vertical, 0);
         content_box.append(&banner);
+        content_box.append(&main_window_helpers::create_permissions_banner());
         content_box.append(&toolbar);
         content_box.append(&search_revealer);
-        content_box.append(&scrolled);
+        content_box.append(&view_stack);
         content_box.append(&backup_status_box);
 
         // Use ToolbarView for proper GNOME layout
@@ -389,13 +568,10 @@ impl MainWindow {
         let search_btn_for_shortcut = search_btn.clone();
 
         let create_btn_for_shortcut = create_btn.clone();
+        let quick_btn_for_shortcut = quick_btn.clone();
         let win_for_prefs_shortcut = window.clone();
         let bm_for_prefs_shortcut = backup_manager.clone();
-        let list_for_refresh_shortcut = snapshot_list.clone();
-        let sm_for_refresh_shortcut = snapshot_manager.clone();
-        let up_for_refresh_shortcut = user_prefs_manager.clone();
-        let bm_for_refresh_shortcut = backup_manager.clone();
-        let compare_for_refresh_shortcut = compare_btn.clone();
+        let refresh_btn_for_shortcut = refresh_btn.clone();
 
         window_key_controller.connect_key_pressed(move |_, key, _code, modifier| {
             // Check for Ctrl+F (Cmd+F on macOS)
@@ -410,6 +586,18 @@ impl MainWindow {
                 return glib::Propagation::Stop;
             }
 
+            // Ctrl+Shift+N: Quick snapshot (only while the button is enabled in preferences)
+            let is_ctrl_shift_n = key.to_lower() == gtk::gdk::Key::n
+                && modifier.contains(gtk::gdk::ModifierType::CONTROL_MASK)
+                && modifier.contains(gtk::gdk::ModifierType::SHIFT_MASK);
+
+            if is_ctrl_shift_n {
+                if quick_btn_for_shortcut.is_visible() && quick_btn_for_shortcut.is_sensitive() {
+                    quick_btn_for_shortcut.emit_clicked();
+                }
+                return glib::Propagation::Stop;
+            }
+
             // Ctrl+N: Create new snapshot
             let is_ctrl_n =
                 key == gtk::gdk::Key::n && modifier.contains(gtk::gdk::ModifierType::CONTROL_MASK);
@@ -443,29 +631,9 @@ impl MainWindow {
                 key == gtk::gdk::Key::r && modifier.contains(gtk::gdk::ModifierType::CONTROL_MASK);
 
             if is_f5 || is_ctrl_r {
-                let sm_clone = sm_for_refresh_shortcut.clone();
-                let up_clone = up_for_refresh_shortcut.clone();
-                let bm_clone = bm_for_refresh_shortcut.clone();
-                let list_clone = list_for_refresh_shortcut.clone();
-                let compare_clone = compare_for_refresh_shortcut.clone();
-                let win_clone_for_refresh = win_for_prefs_shortcut.clone();
-
-                snapshot_list::refresh_snapshot_list_internal(
-                    &win_clone_for_refresh,
-                    &sm_clone,
-                    &up_clone,
-                    &bm_clone,
-                    &list_clone,
-                    &compare_clone,
-                    None,  // No search filter
-                    None,  // No date filter
-                    None,  // No match label
-                    move |_id, _action| {
-                        // Empty callback - action handlers are set up elsewhere
-                    },
-                    None,  // No create button for refresh
-                );
-
+                if refresh_btn_for_shortcut.is_sensitive() {
+                    refresh_btn_for_shortcut.emit_clicked();
+                }
                 return glib::Propagation::Stop;
             }
 
@@ -474,6 +642,9 @@ impl MainWindow {
         window.add_controller(window_key_controller);
 
         let date_filter = Rc::new(RefCell::new(DateFilter::All));
+        let subvolume_filter: Rc<RefCell<Option<PathBuf>>> = Rc::new(RefCell::new(None));
+        let category_filter: Rc<RefCell<Option<SnapshotCategory>>> = Rc::new(RefCell::new(None));
+        let backup_filter = Rc::new(RefCell::new(false));
 
         let main_window = Self {
             window: window.clone(),
@@ -491,6 +662,31 @@ impl MainWindow {
         // Load snapshots and update button states
         main_window.refresh_snapshot_list();
 
+        // Point out metadata entries an interrupted operation left conflicting
+        if crate::dbus_client::helper_supports(FEATURE_METADATA_CHECK) {
+            let refresh = Self::list_refresher(
+                &window,
+                &snapshot_manager,
+                &user_prefs_manager,
+                &backup_manager,
+                &snapshot_list,
+                &compare_btn,
+            );
+            Self::check_metadata_at_startup(&window, refresh);
+        }
+
+        // Hide menu entries for operations an older helper doesn't implement
+        main_window_helpers::apply_helper_capabilities(vec![
+            (audit_log_row.clone().upcast(), FEATURE_AUDIT_LOG),
+            (export_row.clone().upcast(), FEATURE_CONFIG_TRANSFER),
+            (import_row.clone().upcast(), FEATURE_CONFIG_TRANSFER),
+        ]);
+
+        // Clean up test overlays left mounted by a previous session that crashed
+        if crate::dbus_client::helper_supports(FEATURE_SNAPSHOT_OVERLAYS) {
+            overlay_dialog::reap_stale_overlays();
+        }
+
         // Connect search entry to filter snapshots
         let win_clone_search = window.clone();
         let sm_clone_search = snapshot_manager.clone();
@@ -500,6 +696,9 @@ impl MainWindow {
         let compare_btn_clone_search = compare_btn.clone();
         let match_label_clone = match_label.clone();
         let date_filter_clone = date_filter.clone();
+        let subvolume_filter_clone = subvolume_filter.clone();
+        let category_filter_clone = category_filter.clone();
+        let backup_filter_clone = backup_filter.clone();
 
         search_entry.connect_search_changed(move |entry| {
             let search_text = entry.text().to_string();
@@ -513,6 +712,9 @@ impl MainWindow {
                 &match_label_clone,
                 &search_text,
                 *date_filter_clone.borrow(),
+                subvolume_filter_clone.borrow().as_deref(),
+                *category_filter_clone.borrow(),
+                *backup_filter_clone.borrow(),
             );
         });
 
@@ -526,6 +728,9 @@ impl MainWindow {
         let match_label_clone_all = match_label.clone();
         let search_entry_clone_all = search_entry.clone();
         let date_filter_clone_all = date_filter.clone();
+        let subvolume_filter_clone_all = subvolume_filter.clone();
+        let category_filter_clone_all = category_filter.clone();
+        let backup_filter_clone_all = backup_filter.clone();
         let week_btn_clone = week_btn.clone();
         let month_btn_clone = month_btn.clone();
         let quarter_btn_clone = quarter_btn.clone();
@@ -547,6 +752,9 @@ impl MainWindow {
                     &match_label_clone_all,
                     &search_text,
                     DateFilter::All,
+                    subvolume_filter_clone_all.borrow().as_deref(),
+                    *category_filter_clone_all.borrow(),
+                    *backup_filter_clone_all.borrow(),
                 );
             }
         });
@@ -560,6 +768,9 @@ impl MainWindow {
         let match_label_clone_week = match_label.clone();
         let search_entry_clone_week = search_entry.clone();
         let date_filter_clone_week = date_filter.clone();
+        let subvolume_filter_clone_week = subvolume_filter.clone();
+        let category_filter_clone_week = category_filter.clone();
+        let backup_filter_clone_week = backup_filter.clone();
         let all_btn_clone = all_btn.clone();
         let month_btn_clone2 = month_btn.clone();
         let quarter_btn_clone2 = quarter_btn.clone();
@@ -581,6 +792,9 @@ impl MainWindow {
                     &match_label_clone_week,
                     &search_text,
                     DateFilter::Last7Days,
+                    subvolume_filter_clone_week.borrow().as_deref(),
+                    *category_filter_clone_week.borrow(),
+                    *backup_filter_clone_week.borrow(),
                 );
             }
         });
@@ -594,6 +808,9 @@ impl MainWindow {
         let match_label_clone_month = match_label.clone();
         let search_entry_clone_month = search_entry.clone();
         let date_filter_clone_month = date_filter.clone();
+        let subvolume_filter_clone_month = subvolume_filter.clone();
+        let category_filter_clone_month = category_filter.clone();
+        let backup_filter_clone_month = backup_filter.clone();
         let all_btn_clone2 = all_btn.clone();
         let week_btn_clone2 = week_btn.clone();
         let quarter_btn_clone3 = quarter_btn.clone();
@@ -615,6 +832,9 @@ impl MainWindow {
                     &match_label_clone_month,
                     &search_text,
                     DateFilter::Last30Days,
+                    subvolume_filter_clone_month.borrow().as_deref(),
+                    *category_filter_clone_month.borrow(),
+                    *backup_filter_clone_month.borrow(),
                 );
             }
         });
@@ -628,6 +848,9 @@ impl MainWindow {
         let match_label_clone_quarter = match_label.clone();
         let search_entry_clone_quarter = search_entry.clone();
         let date_filter_clone_quarter = date_filter.clone();
+        let subvolume_filter_clone_quarter = subvolume_filter.clone();
+        let category_filter_clone_quarter = category_filter.clone();
+        let backup_filter_clone_quarter = backup_filter.clone();
         let all_btn_clone3 = all_btn.clone();
         let week_btn_clone3 = week_btn.clone();
         let month_btn_clone3 = month_btn.clone();
@@ -649,10 +872,123 @@ impl MainWindow {
                     &match_label_clone_quarter,
                     &search_text,
                     DateFilter::Last90Days,
+                    subvolume_filter_clone_quarter.borrow().as_deref(),
+                    *category_filter_clone_quarter.borrow(),
+                    *backup_filter_clone_quarter.borrow(),
                 );
             }
         });
 
+        // Connect subvolume filter dropdown
+        let win_clone_subvol = window.clone();
+        let sm_clone_subvol = snapshot_manager.clone();
+        let up_clone_subvol = user_prefs_manager.clone();
+        let bm_clone_subvol = backup_manager.clone();
+        let list_clone_subvol = snapshot_list.clone();
+        let compare_btn_clone_subvol = compare_btn.clone();
+        let match_label_clone_subvol = match_label.clone();
+        let search_entry_clone_subvol = search_entry.clone();
+        let date_filter_clone_subvol = date_filter.clone();
+        let subvolume_filter_clone_subvol = subvolume_filter.clone();
+        let category_filter_clone_subvol = category_filter.clone();
+        let backup_filter_clone_subvol = backup_filter.clone();
+
+        subvolume_dropdown.connect_selected_notify(move |dropdown| {
+            // Index 0 is "All subvolumes", the rest map onto detected mount points
+            let selected = dropdown.selected() as usize;
+            *subvolume_filter_clone_subvol.borrow_mut() = selected
+                .checked_sub(1)
+                .and_then(|i| filter_subvolumes.get(i).cloned());
+
+            let search_text = search_entry_clone_subvol.text().to_string();
+            Self::refresh_with_filter(
+                &win_clone_subvol,
+                &sm_clone_subvol,
+                &up_clone_subvol,
+                &bm_clone_subvol,
+                &list_clone_subvol,
+                &compare_btn_clone_subvol,
+                &match_label_clone_subvol,
+                &search_text,
+                *date_filter_clone_subvol.borrow(),
+                subvolume_filter_clone_subvol.borrow().as_deref(),
+                *category_filter_clone_subvol.borrow(),
+                *backup_filter_clone_subvol.borrow(),
+            );
+        });
+
+        // Connect category filter dropdown
+        let win_clone_category = window.clone();
+        let sm_clone_category = snapshot_manager.clone();
+        let up_clone_category = user_prefs_manager.clone();
+        let bm_clone_category = backup_manager.clone();
+        let list_clone_category = snapshot_list.clone();
+        let compare_btn_clone_category = compare_btn.clone();
+        let match_label_clone_category = match_label.clone();
+        let search_entry_clone_category = search_entry.clone();
+        let date_filter_clone_category = date_filter.clone();
+        let subvolume_filter_clone_category = subvolume_filter.clone();
+        let category_filter_clone_category = category_filter.clone();
+        let backup_filter_clone_category = backup_filter.clone();
+
+        category_dropdown.connect_selected_notify(move |dropdown| {
+            // Index 0 is "All categories", the rest map onto SnapshotCategory::ALL
+            let selected = dropdown.selected() as usize;
+            *category_filter_clone_category.borrow_mut() = selected
+                .checked_sub(1)
+                .and_then(|i| SnapshotCategory::ALL.get(i).copied());
+
+            let search_text = search_entry_clone_category.text().to_string();
+            Self::refresh_with_filter(
+                &win_clone_category,
+                &sm_clone_category,
+                &up_clone_category,
+                &bm_clone_category,
+                &list_clone_category,
+                &compare_btn_clone_category,
+                &match_label_clone_category,
+                &search_text,
+                *date_filter_clone_category.borrow(),
+                subvolume_filter_clone_category.borrow().as_deref(),
+                *category_filter_clone_category.borrow(),
+                *backup_filter_clone_category.borrow(),
+            );
+        });
+
+        // Connect backup filter dropdown
+        let win_clone_backup = window.clone();
+        let sm_clone_backup = snapshot_manager.clone();
+        let up_clone_backup = user_prefs_manager.clone();
+        let bm_clone_backup = backup_manager.clone();
+        let list_clone_backup = snapshot_list.clone();
+        let compare_btn_clone_backup = compare_btn.clone();
+        let match_label_clone_backup = match_label.clone();
+        let search_entry_clone_backup = search_entry.clone();
+        let date_filter_clone_backup = date_filter.clone();
+        let subvolume_filter_clone_backup = subvolume_filter.clone();
+        let category_filter_clone_backup = category_filter.clone();
+        let backup_filter_clone_backup = backup_filter.clone();
+
+        backup_dropdown.connect_selected_notify(move |dropdown| {
+            *backup_filter_clone_backup.borrow_mut() = dropdown.selected() == 1;
+
+            let search_text = search_entry_clone_backup.text().to_string();
+            Self::refresh_with_filter(
+                &win_clone_backup,
+                &sm_clone_backup,
+                &up_clone_backup,
+                &bm_clone_backup,
+                &list_clone_backup,
+                &compare_btn_clone_backup,
+                &match_label_clone_backup,
+                &search_text,
+                *date_filter_clone_backup.borrow(),
+                subvolume_filter_clone_backup.borrow().as_deref(),
+                *category_filter_clone_backup.borrow(),
+                *backup_filter_clone_backup.borrow(),
+            );
+        });
+
         // Connect create button
         let sm_clone = snapshot_manager.clone();
         let up_clone = user_prefs_manager.clone();
@@ -669,15 +1005,77 @@ impl MainWindow {
                 bm_clone.clone(),
                 list_clone.clone(),
                 compare_btn_clone.clone(),
+                false,
+            );
+        });
+
+        // Connect quick snapshot button
+        let sm_clone_quick = snapshot_manager.clone();
+        let up_clone_quick = user_prefs_manager.clone();
+        let bm_clone_quick = backup_manager.clone();
+        let list_clone_quick = snapshot_list.clone();
+        let win_clone_quick = window.clone();
+        let compare_btn_clone_quick = compare_btn.clone();
+
+        quick_btn.connect_clicked(move |_| {
+            Self::on_create_snapshot(
+                &win_clone_quick,
+                sm_clone_quick.clone(),
+                up_clone_quick.clone(),
+                bm_clone_quick.clone(),
+                list_clone_quick.clone(),
+                compare_btn_clone_quick.clone(),
+                true,
             );
         });
 
+        // System-wide shortcut, which works even while the quick snapshot button is hidden
+        if preferences::load_manual_settings().global_shortcut_enabled {
+            let shortcut_events = crate::global_shortcut::start();
+            let quick_btn_for_global = quick_btn.clone();
+            let win_clone_global = window.clone();
+            glib::spawn_future_local(async move {
+                loop {
+                    match shortcut_events.try_recv() {
+                        Ok(crate::global_shortcut::ShortcutEvent::Activated) => {
+                            if quick_btn_for_global.is_sensitive() {
+                                quick_btn_for_global.emit_clicked();
+                            }
+                        }
+                        Ok(crate::global_shortcut::ShortcutEvent::Unavailable) => {
+                            dialogs::show_toast(
+                                &win_clone_global,
+                                &gettext("Global snapshot shortcut is not supported by this desktop"),
+                            );
+                            break;
+                        }
+                        Err(std::sync::mpsc::TryRecvError::Empty) => {
+                            glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                        }
+                        Err(std::sync::mpsc::TryRecvError::Disconnected) => break,
+                    }
+                }
+            });
+        }
+
+        // Pick up preference changes when returning from preferences
+        let quick_btn_for_prefs = quick_btn.clone();
+        let transcript_row_for_prefs = transcript_row.clone();
+        window.connect_is_active_notify(move |win| {
+            if win.is_active() {
+                quick_btn_for_prefs
+                    .set_visible(preferences::load_manual_settings().quick_snapshot_enabled);
+                transcript_row_for_prefs.set_visible(Self::show_command_transcript());
+            }
+        });
+
         // Connect compare button
         let sm_clone2 = snapshot_manager.clone();
+        let up_clone2 = user_prefs_manager.clone();
         let win_clone2 = window.clone();
 
         compare_btn.connect_clicked(move |_| {
-            Self::show_compare_dialog(&win_clone2, &sm_clone2);
+            Self::show_compare_dialog(&win_clone2, &sm_clone2, &up_clone2);
         });
 
         // Connect search button to toggle revealer
@@ -717,6 +1115,72 @@ impl MainWindow {
         });
         search_entry.add_controller(key_controller);
 
+        // Buttons on snapshot notifications; their target is the snapshot name
+        for (action_name, action) in [
+            (
+                notifications::ACTION_BROWSE_SNAPSHOT,
+                Some(SnapshotAction::Browse),
+            ),
+            (
+                notifications::ACTION_BACKUP_SNAPSHOT,
+                Some(SnapshotAction::Backup),
+            ),
+            (notifications::ACTION_SHOW_SNAPSHOT, None),
+        ] {
+            let notification_action =
+                gtk::gio::SimpleAction::new(action_name, Some(glib::VariantTy::STRING));
+            let win_clone = window.clone();
+            let sm_clone = snapshot_manager.clone();
+            let up_clone = user_prefs_manager.clone();
+            let bm_clone = backup_manager.clone();
+            let list_clone = snapshot_list.clone();
+            let compare_btn_clone = compare_btn.clone();
+            let revealer_clone = search_revealer.clone();
+            let search_btn_clone = search_btn.clone();
+            let search_entry_clone = search_entry.clone();
+
+            notification_action.connect_activate(move |_, parameter| {
+                let Some(snapshot_name) = parameter.and_then(|p| p.get::<String>()) else {
+                    return;
+                };
+                win_clone.present();
+
+                // Show the snapshot by searching for its name
+                let Some(action) = action else {
+                    if !revealer_clone.reveals_child() {
+                        search_btn_clone.emit_clicked();
+                    }
+                    search_entry_clone.set_text(&snapshot_name);
+                    return;
+                };
+
+                let snapshot_id = sm_clone
+                    .borrow()
+                    .load_snapshots()
+                    .ok()
+                    .and_then(|snapshots| snapshots.into_iter().find(|s| s.name == snapshot_name))
+                    .map(|s| s.id);
+                match snapshot_id {
+                    Some(id) => Self::handle_snapshot_action(
+                        &win_clone,
+                        &sm_clone,
+                        &up_clone,
+                        &bm_clone,
+                        &list_clone,
+                        &compare_btn_clone,
+                        &id,
+                        action,
+                    ),
+                    None => dialogs::show_error(
+                        &win_clone,
+                        "Snapshot Not Found",
+                        &format!("Snapshot '{snapshot_name}' no longer exists"),
+                    ),
+                }
+            });
+            app.add_action(&notification_action);
+        }
+
         // Connect theme buttons
         let style_manager = adw::StyleManager::default();
         system_btn.connect_clicked(move |_| {
@@ -733,13 +1197,99 @@ impl MainWindow {
             style_manager_dark.set_color_scheme(adw::ColorScheme::ForceDark);
         });
 
+        // Rebuild the list with the chosen row density
+        let win_clone_compact = window.clone();
+        let sm_clone_compact = snapshot_manager.clone();
+        let up_clone_compact = user_prefs_manager.clone();
+        let bm_clone_compact = backup_manager.clone();
+        let list_clone_compact = snapshot_list.clone();
+        let compare_clone_compact = compare_btn.clone();
+        compact_row.connect_active_notify(move |row| {
+            let settings = snapshot_list::ListDisplaySettings {
+                compact: row.is_active(),
+            };
+            if let Err(e) = snapshot_list::save_display_settings(&settings) {
+                log::error!("Failed to save list display settings: {e}");
+            }
+            Self::refresh_list_static(
+                &win_clone_compact,
+                &sm_clone_compact,
+                &up_clone_compact,
+                &bm_clone_compact,
+                &list_clone_compact,
+                &compare_clone_compact,
+            );
+        });
+
         // Connect hamburger menu items
         let win_clone_menu_analytics = window.clone();
         let sm_clone_menu_analytics = snapshot_manager.clone();
+        let up_clone_menu_analytics = user_prefs_manager.clone();
+        let bm_clone_menu_analytics = backup_manager.clone();
+        let list_clone_menu_analytics = snapshot_list.clone();
+        let compare_clone_menu_analytics = compare_btn.clone();
         let popover_clone_analytics = popover.clone();
         analytics_row.connect_activated(move |_| {
             popover_clone_analytics.popdown();
-            Self::show_analytics_dialog(&win_clone_menu_analytics, &sm_clone_menu_analytics);
+            Self::show_analytics_dialog(
+                &win_clone_menu_analytics,
+                &sm_clone_menu_analytics,
+                Self::list_refresher(
+                    &win_clone_menu_analytics,
+                    &sm_clone_menu_analytics,
+                    &up_clone_menu_analytics,
+                    &bm_clone_menu_analytics,
+                    &list_clone_menu_analytics,
+                    &compare_clone_menu_analytics,
+                ),
+            );
+        });
+
+        let win_clone_menu_verify = window.clone();
+        let sm_clone_menu_verify = snapshot_manager.clone();
+        let up_clone_menu_verify = user_prefs_manager.clone();
+        let bm_clone_menu_verify = backup_manager.clone();
+        let list_clone_menu_verify = snapshot_list.clone();
+        let compare_clone_menu_verify = compare_btn.clone();
+        let popover_clone_verify = popover.clone();
+        verify_all_row.connect_activated(move |_| {
+            popover_clone_verify.popdown();
+            Self::verify_all_snapshots(
+                &win_clone_menu_verify,
+                &sm_clone_menu_verify,
+                &up_clone_menu_verify,
+                &bm_clone_menu_verify,
+                &list_clone_menu_verify,
+                &compare_clone_menu_verify,
+            );
+        });
+
+        let win_clone_menu_activity = window.clone();
+        let popover_clone_activity = popover.clone();
+        activity_row.connect_activated(move |_| {
+            popover_clone_activity.popdown();
+            activity_log_dialog::show_activity_log_dialog(&win_clone_menu_activity);
+        });
+
+        let win_clone_menu_audit = window.clone();
+        let popover_clone_audit = popover.clone();
+        audit_log_row.connect_activated(move |_| {
+            popover_clone_audit.popdown();
+            audit_log_dialog::show_audit_log_dialog(&win_clone_menu_audit);
+        });
+
+        let win_clone_menu_transcript = window.clone();
+        let popover_clone_transcript = popover.clone();
+        transcript_row.connect_activated(move |_| {
+            popover_clone_transcript.popdown();
+            command_transcript_dialog::show_command_transcript_dialog(&win_clone_menu_transcript);
+        });
+
+        let win_clone_menu_log = window.clone();
+        let popover_clone_log = popover.clone();
+        log_row.connect_activated(move |_| {
+            popover_clone_log.popdown();
+            log_viewer_dialog::show_log_viewer_dialog(&win_clone_menu_log);
         });
 
         let win_clone_menu_prefs = window.clone();
@@ -750,6 +1300,22 @@ impl MainWindow {
             Self::show_preferences_dialog(&win_clone_menu_prefs, &bm_clone_menu_prefs);
         });
 
+        let win_clone_menu_export = window.clone();
+        let bm_clone_menu_export = backup_manager.clone();
+        let popover_clone_export = popover.clone();
+        export_row.connect_activated(move |_| {
+            popover_clone_export.popdown();
+            config_transfer::export_settings(&win_clone_menu_export, &bm_clone_menu_export);
+        });
+
+        let win_clone_menu_import = window.clone();
+        let bm_clone_menu_import = backup_manager.clone();
+        let popover_clone_import = popover.clone();
+        import_row.connect_activated(move |_| {
+            popover_clone_import.popdown();
+            config_transfer::import_settings(&win_clone_menu_import, &bm_clone_menu_import);
+        });
+
         let win_clone_menu_shortcuts = window.clone();
         let popover_clone_shortcuts = popover.clone();
         shortcuts_row.connect_activated(move |_| {
@@ -781,6 +1347,8 @@ impl MainWindow {
         gtk::glib::spawn_future_local(async move {
             loop {
                 if let Ok(event) = backup_progress_rx.try_recv() {
+                    let destination_label = bm_clone.borrow().destination_label(&event.destination_uuid);
+                    operations::backup_progress(&event, &destination_label);
                     bm_clone.borrow().update_progress(event);
                     // Update footer status in real-time
                     main_window_helpers::update_backup_status_label(&backup_status_label_for_progress, &bm_clone);
@@ -806,11 +1374,11 @@ impl MainWindow {
                     if let Ok(snapshots) = snapshot_manager_for_snapshots.borrow().load_snapshots() {
                         // Find the snapshot that was just created
                         if let Some(snapshot) = snapshots.iter().find(|s| s.name == event.snapshot_name) {
-                            let is_favorite = user_prefs_for_snapshots
-                                .borrow()
-                                .get(&event.snapshot_name)
-                                .map(|p| p.is_favorite)
-                                .unwrap_or(false);
+                            let eligibility = user_prefs_for_snapshots.borrow().backup_eligibility(
+                                &snapshot.id,
+                                &snapshot.name,
+                                &SchedulesConfig::load_or_default(&WaypointConfig::new().schedules_config),
+                            );
 
                             // Convert snapshots to SnapshotInfo for filtering
                             let snapshot_info: waypoint_common::SnapshotInfo = snapshot.into();
@@ -820,8 +1388,9 @@ impl MainWindow {
                             // Queue snapshot for automatic backup
                             match backup_manager_for_snapshots.borrow().queue_snapshot_backup(
                                 &snapshot_info,
-                                is_favorite,
+                                eligibility,
                                 &all_snapshots,
+                                BackupTrigger::SnapshotCreation,
                             ) {
                                 Ok(queued_destinations) => {
                                     if queued_destinations.is_empty() {
@@ -836,72 +1405,11 @@ impl MainWindow {
                                         // Check if any queued destinations are currently mounted
                                         // and trigger immediate backup processing
                                         for dest_uuid in queued_destinations {
-                                            if let Some(mount_point) = backup_manager_for_snapshots.borrow().get_mounted_destination(&dest_uuid) {
-                                                log::info!(
-                                                    "Destination {} is already mounted at {}, triggering immediate backup processing",
-                                                    dest_uuid,
-                                                    mount_point
-                                                );
-
-                                                // Get destination label and pending count for notifications
-                                                let (dest_label, pending_count) = {
-                                                    let bm = backup_manager_for_snapshots.borrow();
-                                                    let label = bm
-                                                        .get_config()
-                                                        .ok()
-                                                        .and_then(|c| c.destinations.get(&dest_uuid).map(|d| d.label.clone()))
-                                                        .unwrap_or_else(|| mount_point.clone());
-                                                    let count = bm.get_pending_count(&dest_uuid);
-                                                    (label, count)
-                                                };
-
-                                                // Send start notification
-                                                notifications::notify_backup_started(&app_for_snapshots, &dest_label, pending_count);
-
-                                                // Spawn background thread to process this destination's pending backups
-                                                let backup_manager_bg = backup_manager_for_snapshots.borrow().clone();
-                                                let dest_uuid_clone = dest_uuid.clone();
-                                                let mount_point_clone = mount_point.clone();
-
-                                                // Get snapshot directory from config
-                                                let snapshot_dir = waypoint_common::WaypointConfig::new()
-                                                    .snapshot_dir
-                                                    .to_string_lossy()
-                                                    .to_string();
-
-                                                std::thread::spawn(move || {
-                                                    log::info!("Background thread: Processing pending backups for destination {}", dest_uuid_clone);
-
-                                                    match backup_manager_bg.process_pending_backups(
-                                                        &dest_uuid_clone,
-                                                        &mount_point_clone,
-                                                        &snapshot_dir,
-                                                    ) {
-                                                        Ok((success, failed, errors)) => {
-                                                            log::info!(
-                                                                "Automatic backup processing completed: {} succeeded, {} failed",
-                                                                success,
-                                                                failed
-                                                            );
-                                                            if !errors.is_empty() {
-                                                                log::error!("Backup errors: {:?}", errors);
-                                                            }
-                                                        }
-                                                        Err(e) => {
-                                                            log::error!(
-                                                                "Failed to process automatic backups for destination {}: {}",
-                                                                dest_uuid_clone,
-                                                                e
-                                                            );
-                                                        }
-                                                    }
-                                                });
-                                            } else {
-                                                log::info!(
-                                                    "Destination {} is not currently mounted, backup will process when drive is connected",
-                                                    dest_uuid
-                                                );
-                                            }
+                                            main_window_helpers::start_pending_backups_if_mounted(
+                                                Some(&app_for_snapshots),
+                                                &backup_manager_for_snapshots,
+                                                &dest_uuid,
+                                            );
                                         }
                                     }
                                 }
@@ -929,6 +1437,25 @@ impl MainWindow {
             }
         });
 
+        // Manual refresh, also triggered by F5 / Ctrl+R
+        let window_manual_refresh = window.clone();
+        let manager_manual_refresh = snapshot_manager.clone();
+        let user_prefs_manual_refresh = user_prefs_manager.clone();
+        let backup_manager_manual_refresh = backup_manager.clone();
+        let list_manual_refresh = snapshot_list.clone();
+        let compare_manual_refresh = compare_btn.clone();
+        refresh_btn.connect_clicked(move |btn| {
+            Self::manual_refresh(
+                &window_manual_refresh,
+                &manager_manual_refresh,
+                &user_prefs_manual_refresh,
+                &backup_manager_manual_refresh,
+                &list_manual_refresh,
+                &compare_manual_refresh,
+                btn,
+            );
+        });
+
         // Set up periodic snapshot list refresh (every 30 seconds)
         // This ensures external snapshots (from scheduler) appear in the UI
         let window_refresh = window.clone();
@@ -949,6 +1476,57 @@ impl MainWindow {
             glib::ControlFlow::Continue
         });
 
+        // Check for due scheduled backups and retries (every 15 minutes)
+        let backup_manager_schedule = backup_manager.clone();
+        let snapshot_manager_schedule = snapshot_manager.clone();
+        let user_prefs_schedule = user_prefs_manager.clone();
+        let app_schedule = app.clone();
+        glib::timeout_add_seconds_local(SCHEDULED_BACKUP_CHECK_SECONDS, move || {
+            // Failed backups on connected drives are retried once their backoff elapses
+            let retry_destinations = backup_manager_schedule.borrow().due_retry_destinations();
+            for uuid in retry_destinations {
+                log::info!("Retrying failed backups for destination {uuid}");
+                main_window_helpers::start_pending_backups_if_mounted(
+                    Some(&app_schedule),
+                    &backup_manager_schedule,
+                    &uuid,
+                );
+            }
+
+            let due = backup_manager_schedule.borrow().due_scheduled_destinations();
+            if due.is_empty() {
+                return glib::ControlFlow::Continue;
+            }
+
+            let snapshots = match snapshot_manager_schedule.borrow().load_snapshots() {
+                Ok(snaps) => snaps,
+                Err(e) => {
+                    log::error!("Failed to load snapshots for scheduled backup: {e}");
+                    return glib::ControlFlow::Continue;
+                }
+            };
+
+            let schedules = SchedulesConfig::load_or_default(&WaypointConfig::new().schedules_config);
+            for (uuid, _mount_point) in due {
+                log::info!("Scheduled backup due for destination {uuid}");
+                if let Err(e) = backup_manager_schedule.borrow().queue_destination_snapshots(
+                    &uuid,
+                    &snapshots,
+                    |id, name| user_prefs_schedule.borrow().backup_eligibility(id, name, &schedules),
+                    BackupTrigger::Schedule,
+                ) {
+                    log::error!("Failed to queue scheduled backup for destination {uuid}: {e}");
+                    continue;
+                }
+                main_window_helpers::start_pending_backups_if_mounted(
+                    Some(&app_schedule),
+                    &backup_manager_schedule,
+                    &uuid,
+                );
+            }
+            glib::ControlFlow::Continue
+        });
+
         // Initialize mount monitoring for automatic backups
         use crate::mount_monitor::MountMonitor;
         let mount_monitor = MountMonitor::new();
@@ -960,6 +1538,7 @@ impl MainWindow {
             // Start monitoring for new drive mounts
             let backup_manager_monitor = backup_manager.clone();
             let snapshot_manager_monitor = snapshot_manager.clone();
+            let user_prefs_monitor = user_prefs_manager.clone();
             let window_monitor = window.clone();
             let app_monitor = app.clone();
 
@@ -989,7 +1568,13 @@ impl MainWindow {
                 };
 
                 // Queue snapshots that match this destination's filter (if backup_on_mount is enabled)
-                if let Err(e) = backup_manager_monitor.borrow().queue_destination_snapshots(&uuid, &snapshots) {
+                let schedules = SchedulesConfig::load_or_default(&WaypointConfig::new().schedules_config);
+                if let Err(e) = backup_manager_monitor.borrow().queue_destination_snapshots(
+                    &uuid,
+                    &snapshots,
+                    |id, name| user_prefs_monitor.borrow().backup_eligibility(id, name, &schedules),
+                    BackupTrigger::DriveMount,
+                ) {
                     log::error!("Failed to queue snapshots for destination {uuid}: {e}");
                 }
 
@@ -1016,6 +1601,8 @@ impl MainWindow {
 
                 // Clone backup manager for background thread work
                 let manager_for_thread = { backup_manager_monitor.borrow().clone() };
+                let manager_for_results = manager_for_thread.clone();
+                let started_at = chrono::Utc::now().timestamp();
                 let uuid_clone = uuid.clone();
                 let mount_clone = mount_point.clone();
                 let snapshot_dir_clone = snapshot_dir.clone();
@@ -1033,6 +1620,7 @@ impl MainWindow {
                 let window_ref = window_monitor.clone();
                 let app_ref = app_monitor.clone();
                 let dest_label_ref = dest_label.clone();
+                let mount_ref = mount_point.clone();
 
                 gtk::glib::spawn_future_local(async move {
                     let result = loop {
@@ -1088,8 +1676,18 @@ impl MainWindow {
 
                                 dialogs::show_toast(&window_ref, &message);
 
-                                // Show error details dialog only if there were failures
-                                if fail_count > 0 && !errors.is_empty() {
+                                let unverified =
+                                    manager_for_results.failed_verifications(&uuid, started_at);
+                                if !unverified.is_empty() {
+                                    notifications::notify_backup_verification_failed(
+                                        &app_ref,
+                                        &dest_label_ref,
+                                        &unverified,
+                                    );
+                                }
+
+                                // Show error details dialog if backups or their verification failed
+                                if !errors.is_empty() {
                                     dialogs::show_error_list(
                                         &window_ref,
                                         "Backup Errors",
@@ -1109,6 +1707,14 @@ impl MainWindow {
                             );
                         }
                     }
+
+                    main_window_helpers::report_space_shortage(
+                        &app_ref,
+                        &manager_for_results,
+                        &uuid,
+                        &mount_ref,
+                        &dest_label_ref,
+                    );
                 });
             });
         }
@@ -1132,9 +1738,12 @@ impl MainWindow {
             &self.backup_manager,
             &self.snapshot_list,
             &self.compare_btn,
-            None, // No search filter
-            None, // No date filter
-            None, // No match label
+            None,  // No search filter
+            None,  // No date filter
+            None,  // No subvolume filter
+            None,  // No category filter
+            false, // No backup filter
+            None,  // No match label
             move |id, action| {
                 Self::handle_snapshot_action(
                     &window,
@@ -1161,6 +1770,9 @@ impl MainWindow {
         match_label: &Label,
         search_text: &str,
         date_filter: DateFilter,
+        subvolume_filter: Option<&std::path::Path>,
+        category_filter: Option<SnapshotCategory>,
+        not_backed_up_only: bool,
     ) {
         let window_clone = window.clone();
         let manager_clone = manager.clone();
@@ -1178,6 +1790,9 @@ impl MainWindow {
             compare_btn,
             Some(search_text),
             Some(date_filter),
+            subvolume_filter,
+            category_filter,
+            not_backed_up_only,
             Some(match_label),
             move |id, action| {
                 Self::handle_snapshot_action(
@@ -1202,6 +1817,7 @@ impl MainWindow {
         backup_manager: Rc<RefCell<BackupManager>>,
         list: ListBox,
         compare_btn: Button,
+        quick: bool,
     ) {
         // Check if root is on Btrfs (can check without root)
         match btrfs::is_btrfs(&std::path::PathBuf::from("/")) {
@@ -1224,7 +1840,7 @@ impl MainWindow {
             _ => {}
         }
 
-        // Check available disk space in background (can check without root)
+        // Check read-only state and available disk space in background (can check without root)
         const MIN_SPACE_GB: u64 = 1; // Minimum 1 GB free space
         const MIN_SPACE_BYTES: u64 = MIN_SPACE_GB * 1024 * 1024 * 1024;
 
@@ -1234,16 +1850,19 @@ impl MainWindow {
         let user_prefs_clone = user_prefs_manager.clone();
         let compare_btn_clone = compare_btn.clone();
 
-        // Run disk space check in background
+        // Run disk space check in background, on the filesystem that holds the snapshots
+        let space_path = btrfs::snapshot_space_path();
+        let space_path_for_thread = space_path.clone();
         let (tx, rx) = mpsc::channel();
         std::thread::spawn(move || {
-            let result = btrfs::get_available_space(&std::path::PathBuf::from("/"));
-            let _ = tx.send(result);
+            let read_only = main_window_helpers::filesystem_read_only();
+            let result = btrfs::get_available_space(&space_path_for_thread);
+            let _ = tx.send((read_only, result));
         });
 
         // Poll for result and proceed based on available space
         glib::spawn_future_local(async move {
-            let space_result = loop {
+            let (read_only, space_result) = loop {
                 match rx.try_recv() {
                     Ok(result) => break result,
                     Err(mpsc::TryRecvError::Empty) => {
@@ -1252,23 +1871,54 @@ impl MainWindow {
                     }
                     Err(mpsc::TryRecvError::Disconnected) => {
                         log::error!("Disk space check thread disconnected");
-                        break Ok(MIN_SPACE_BYTES + 1); // Assume sufficient space
+                        break (false, Ok(MIN_SPACE_BYTES + 1)); // Assume sufficient space
                     }
                 }
             };
 
+            if read_only {
+                error_helpers::show_error_with_context(
+                    &window_clone,
+                    error_helpers::ErrorContext::FilesystemCheck,
+                    &waypoint_common::HelperError::new(
+                        waypoint_common::ErrorCode::ReadOnlyFilesystem,
+                        "The filesystem is mounted read-only",
+                    )
+                    .encode(),
+                );
+                return;
+            }
+
             // Check if we have enough space
             match space_result {
                 Ok(available) => {
                     if available < MIN_SPACE_BYTES {
                         let available_gb = available as f64 / (1024.0 * 1024.0 * 1024.0);
-                        error_helpers::show_error_with_context(
-                            &window_clone,
-                            error_helpers::ErrorContext::DiskSpace,
-                            &format!(
-                                "Only {available_gb:.2} GB available, need at least {MIN_SPACE_GB} GB"
-                            ),
+                        let message = format!(
+                            "Only {available_gb:.2} GB available on {}, need at least {MIN_SPACE_GB} GB",
+                            space_path.display()
                         );
+                        if preferences::load_manual_settings().make_room_automatically {
+                            Self::make_room_for_snapshot(
+                                &window_clone,
+                                manager_clone,
+                                user_prefs_clone,
+                                backup_manager,
+                                list_clone,
+                                compare_btn_clone,
+                                space_path,
+                                MIN_SPACE_BYTES - available,
+                                MIN_SPACE_BYTES,
+                                message,
+                                quick,
+                            );
+                        } else {
+                            error_helpers::show_error_with_context(
+                                &window_clone,
+                                error_helpers::ErrorContext::DiskSpace,
+                                &message,
+                            );
+                        }
                         return;
                     }
                 }
@@ -1278,70 +1928,474 @@ impl MainWindow {
                 }
             }
 
-            // Show custom description dialog
-            let window_clone2 = window_clone.clone();
-            let list_clone2 = list_clone.clone();
-            let manager_clone2 = manager_clone.clone();
-            let user_prefs_clone2 = user_prefs_clone.clone();
-            let backup_manager_clone2 = backup_manager.clone();
-            let compare_btn_clone2 = compare_btn_clone.clone();
-
-            create_snapshot_dialog::show_create_snapshot_dialog_async(
+            Self::ask_and_create_snapshot(
                 &window_clone,
-                move |result| {
-                    if let Some((snapshot_name, description)) = result {
-                        // User confirmed, create the snapshot
-                        Self::create_snapshot_with_description(
-                            &window_clone2,
-                            manager_clone2.clone(),
-                            user_prefs_clone2.clone(),
-                            backup_manager_clone2.clone(),
-                            list_clone2.clone(),
-                            compare_btn_clone2.clone(),
-                            snapshot_name,
-                            description,
-                        );
-                    }
-                    // If None, user cancelled - do nothing
-                },
+                manager_clone,
+                user_prefs_clone,
+                backup_manager,
+                list_clone,
+                compare_btn_clone,
+                quick,
             );
         });
     }
 
-    fn create_snapshot_with_description(
+    /// Create a snapshot once the pre-flight checks passed
+    ///
+    /// Quick snapshots are created right away; otherwise the description dialog is shown first.
+    fn ask_and_create_snapshot(
         window: &adw::ApplicationWindow,
         manager: Rc<RefCell<SnapshotManager>>,
         user_prefs_manager: Rc<RefCell<UserPreferencesManager>>,
         backup_manager: Rc<RefCell<BackupManager>>,
         list: ListBox,
         compare_btn: Button,
-        snapshot_name: String,
-        description: String,
+        quick: bool,
     ) {
+        // Quick snapshots skip the description dialog entirely
+        if quick {
+            // Counting pending updates for the description can take seconds
+            let (tx, rx) = mpsc::channel();
+            std::thread::spawn(move || {
+                let _ = tx.send(create_snapshot_dialog::default_name_and_description());
+            });
+
+            let window = window.clone();
+            glib::spawn_future_local(async move {
+                let (snapshot_name, description) = loop {
+                    match rx.try_recv() {
+                        Ok(defaults) => break defaults,
+                        Err(mpsc::TryRecvError::Empty) => {
+                            glib::timeout_future(std::time::Duration::from_millis(50)).await;
+                        }
+                        Err(mpsc::TryRecvError::Disconnected) => return,
+                    }
+                };
+                Self::create_snapshot_with_description(
+                    &window,
+                    manager,
+                    user_prefs_manager,
+                    backup_manager,
+                    list,
+                    compare_btn,
+                    snapshot_name,
+                    description,
+                    None,
+                    SnapshotCategory::Manual,
+                );
+            });
+            return;
+        }
+
+        // Show custom description dialog
         let window_clone = window.clone();
-        let list_clone = list.clone();
-        let manager_clone = manager.clone();
-        let user_prefs_clone = user_prefs_manager.clone();
-        let backup_manager_clone = backup_manager.clone();
-        let compare_btn_clone = compare_btn.clone();
-        let snapshot_name_clone = snapshot_name.clone();
-        let description_clone = description.clone();
+        create_snapshot_dialog::show_create_snapshot_dialog_async(window, move |result| {
+            if let Some((snapshot_name, description, expires_in, category)) = result {
+                // User confirmed, create the snapshot
+                Self::create_snapshot_with_description(
+                    &window_clone,
+                    manager.clone(),
+                    user_prefs_manager.clone(),
+                    backup_manager.clone(),
+                    list.clone(),
+                    compare_btn.clone(),
+                    snapshot_name,
+                    description,
+                    expires_in,
+                    category,
+                );
+            }
+            // If None, user cancelled - do nothing
+        });
+    }
 
-        // Show loading state
-        dialogs::show_toast(&window_clone, "Creating snapshot...");
+    /// Delete the oldest snapshots that aren't favorites to free `needed` bytes, then create
+    ///
+    /// Only offered when "Make Room Automatically" is on, and only after the
+    /// user confirms the list of snapshots to delete. Milestones are never
+    /// candidates either. Candidates are sized by their exclusive qgroup size,
+    /// the space deleting them actually frees; without it nothing is deleted.
+    /// Falls back to the low space error (`space_error`) if the sizes are
+    /// unknown or the candidates wouldn't free enough. Deletion stops once
+    /// `space_path` has `required` bytes available.
+    #[allow(clippy::too_many_arguments)]
+    fn make_room_for_snapshot(
+        window: &adw::ApplicationWindow,
+        manager: Rc<RefCell<SnapshotManager>>,
+        user_prefs_manager: Rc<RefCell<UserPreferencesManager>>,
+        backup_manager: Rc<RefCell<BackupManager>>,
+        list: ListBox,
+        compare_btn: Button,
+        space_path: std::path::PathBuf,
+        needed: u64,
+        required: u64,
+        space_error: String,
+        quick: bool,
+    ) {
+        // Never consider more than this many snapshots, however much space is missing
+        const MAX_SNAPSHOTS_TO_DELETE: usize = 5;
+
+        let mut snapshots = manager.borrow().load_snapshots().unwrap_or_default();
+        snapshots.sort_by_key(|s| s.timestamp);
+        let favorites = user_prefs_manager.borrow().load().unwrap_or_default();
+        // Directory names, which the helper deletes by
+        let candidates: Vec<String> = snapshots
+            .iter()
+            .filter(|s| !favorites.get(&s.id).is_some_and(|prefs| prefs.is_favorite))
+            .filter(|s| !s.category.is_protected())
+            .take(MAX_SNAPSHOTS_TO_DELETE)
+            .map(|s| {
+                s.path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or(&s.name)
+                    .to_string()
+            })
+            .collect();
+
+        if candidates.is_empty() {
+            error_helpers::show_error_with_context(
+                window,
+                error_helpers::ErrorContext::DiskSpace,
+                &space_error,
+            );
+            return;
+        }
 
-        // Create channel for thread communication
-        let (sender, receiver) = mpsc::channel();
+        // Sizes that include shared data would overstate what deleting frees
+        let sizes_unknown = format!(
+            "{space_error}. Snapshots can't be deleted automatically because the space they \
+             free is unknown; enable Btrfs quotas to make room automatically."
+        );
+        if !crate::dbus_client::helper_supports(FEATURE_EXCLUSIVE_SIZES) {
+            error_helpers::show_error_with_context(
+                window,
+                error_helpers::ErrorContext::DiskSpace,
+                &sizes_unknown,
+            );
+            return;
+        }
 
-        // Spawn blocking operation in thread
+        let (tx, rx) = mpsc::channel();
+        let names = candidates.clone();
         std::thread::spawn(move || {
-            // Load subvolume configuration
-            let subvolume_paths = preferences::load_config();
-            let subvolumes: Vec<String> = subvolume_paths
-                .iter()
-                .map(|p| p.to_string_lossy().to_string())
-                .collect();
-
+            let result = WaypointHelperClient::new()
+                .and_then(|client| client.get_snapshot_exclusive_sizes(names));
+            let _ = tx.send(result);
+        });
+
+        let window = window.clone();
+        glib::spawn_future_local(async move {
+            let sizes = loop {
+                match rx.try_recv() {
+                    Ok(result) => break result,
+                    Err(mpsc::TryRecvError::Empty) => {
+                        glib::timeout_future(std::time::Duration::from_millis(50)).await;
+                    }
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        break Err(anyhow::anyhow!("Snapshot size check thread disconnected"));
+                    }
+                }
+            };
+            let sizes = match sizes {
+                Ok(sizes) => sizes,
+                Err(e) => {
+                    log::warn!("Could not get snapshot sizes to make room: {e}");
+                    std::collections::HashMap::new()
+                }
+            };
+
+            let Some(candidate_sizes) = candidates
+                .iter()
+                .map(|name| sizes.get(name).copied())
+                .collect::<Option<Vec<u64>>>()
+            else {
+                error_helpers::show_error_with_context(
+                    &window,
+                    error_helpers::ErrorContext::DiskSpace,
+                    &sizes_unknown,
+                );
+                return;
+            };
+            let Some(count) = crate::snapshot::count_to_free(&candidate_sizes, needed) else {
+                error_helpers::show_error_with_context(
+                    &window,
+                    error_helpers::ErrorContext::DiskSpace,
+                    &format!(
+                        "{space_error}. Deleting the oldest snapshots that aren't favorites wouldn't free enough space."
+                    ),
+                );
+                return;
+            };
+            let to_delete: Vec<String> = candidates.into_iter().take(count).collect();
+
+            let listing = to_delete
+                .iter()
+                .zip(&candidate_sizes)
+                .map(|(name, size)| format!("• {name} ({})", waypoint_common::format_bytes(*size)))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let message = gettext_f(
+                "There isn't enough free space for a new snapshot. To make room, these snapshots will be deleted:\n\n{snapshots}\n\nThis cannot be undone.",
+                &[("snapshots", &listing)],
+            );
+
+            let window_clone = window.clone();
+            dialogs::show_confirmation(
+                &window,
+                &gettext("Delete Old Snapshots?"),
+                &message,
+                &gettext("Delete and Continue"),
+                true,
+                move || {
+                    Self::delete_to_make_room(
+                        &window_clone,
+                        manager.clone(),
+                        user_prefs_manager.clone(),
+                        backup_manager.clone(),
+                        list.clone(),
+                        compare_btn.clone(),
+                        to_delete.clone(),
+                        space_path.clone(),
+                        required,
+                        quick,
+                    );
+                },
+            );
+        });
+    }
+
+    /// Delete confirmed snapshots one by one, report them, then continue creating
+    ///
+    /// Free space is checked again after each deletion, and the remaining
+    /// snapshots are kept once `space_path` has `required` bytes available.
+    #[allow(clippy::too_many_arguments)]
+    fn delete_to_make_room(
+        window: &adw::ApplicationWindow,
+        manager: Rc<RefCell<SnapshotManager>>,
+        user_prefs_manager: Rc<RefCell<UserPreferencesManager>>,
+        backup_manager: Rc<RefCell<BackupManager>>,
+        list: ListBox,
+        compare_btn: Button,
+        to_delete: Vec<String>,
+        space_path: std::path::PathBuf,
+        required: u64,
+        quick: bool,
+    ) {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let client = match WaypointHelperClient::new() {
+                Ok(client) => client,
+                Err(e) => {
+                    let _ = tx.send((Vec::new(), Some(e.to_string())));
+                    return;
+                }
+            };
+            let mut deleted = Vec::new();
+            for name in to_delete {
+                if let Ok(available) = btrfs::get_available_space(&space_path)
+                    && available >= required
+                {
+                    log::info!("Enough space after deleting {} snapshot(s)", deleted.len());
+                    break;
+                }
+                match client.delete_snapshot(name.clone()) {
+                    Ok((true, _)) => deleted.push(name),
+                    Ok((false, message)) => {
+                        let _ = tx.send((deleted, Some(message)));
+                        return;
+                    }
+                    Err(e) => {
+                        let _ = tx.send((deleted, Some(e.to_string())));
+                        return;
+                    }
+                }
+            }
+            let _ = tx.send((deleted, None));
+        });
+
+        let window = window.clone();
+        glib::spawn_future_local(async move {
+            let (deleted, error) = loop {
+                match rx.try_recv() {
+                    Ok(result) => break result,
+                    Err(mpsc::TryRecvError::Empty) => {
+                        glib::timeout_future(std::time::Duration::from_millis(50)).await;
+                    }
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        break (
+                            Vec::new(),
+                            Some("Snapshot deletion thread disconnected".to_string()),
+                        );
+                    }
+                }
+            };
+
+            for name in &deleted {
+                activity_log::record(ActivityKind::SnapshotDeleted, name, true, None);
+            }
+            if !deleted.is_empty() {
+                dialogs::show_toast(
+                    &window,
+                    &gettext_f(
+                        "Deleted to make room: {snapshots}",
+                        &[("snapshots", &deleted.join(", "))],
+                    ),
+                );
+                Self::refresh_list_static(
+                    &window,
+                    &manager,
+                    &user_prefs_manager,
+                    &backup_manager,
+                    &list,
+                    &compare_btn,
+                );
+            }
+
+            if let Some(error) = error {
+                error_helpers::show_error_with_context(
+                    &window,
+                    error_helpers::ErrorContext::SnapshotDelete,
+                    &error,
+                );
+                return;
+            }
+
+            Self::ask_and_create_snapshot(
+                &window,
+                manager,
+                user_prefs_manager,
+                backup_manager,
+                list,
+                compare_btn,
+                quick,
+            );
+        });
+    }
+
+    /// Create a snapshot, or queue it while another one is being created
+    #[allow(clippy::too_many_arguments)]
+    fn create_snapshot_with_description(
+        window: &adw::ApplicationWindow,
+        manager: Rc<RefCell<SnapshotManager>>,
+        user_prefs_manager: Rc<RefCell<UserPreferencesManager>>,
+        backup_manager: Rc<RefCell<BackupManager>>,
+        list: ListBox,
+        compare_btn: Button,
+        snapshot_name: String,
+        description: String,
+        expires_in: Option<chrono::Duration>,
+        category: SnapshotCategory,
+    ) {
+        // Until it starts, cancelling just takes it out of the queue
+        let operation = Rc::new(std::cell::Cell::new(None));
+        let operation_for_cancel = operation.clone();
+        let id = operations::queue(
+            OperationKind::CreateSnapshot,
+            format!("Create snapshot {snapshot_name}"),
+            move || {
+                if let Some(id) = operation_for_cancel.get()
+                    && create_queue::remove(id)
+                {
+                    operations::cancelled(id);
+                }
+            },
+        );
+        operation.set(Some(id));
+
+        let window_clone = window.clone();
+        let queued = create_queue::run(id, move |turn| {
+            Self::run_snapshot_creation(
+                &window_clone,
+                manager,
+                user_prefs_manager,
+                backup_manager,
+                list,
+                compare_btn,
+                snapshot_name,
+                description,
+                expires_in,
+                category,
+                id,
+                turn,
+            );
+        });
+        if queued {
+            dialogs::show_toast(window, "Snapshot queued until the current one is created");
+        }
+    }
+
+    /// Create a snapshot once it is its turn in `create_queue`
+    #[allow(clippy::too_many_arguments)]
+    fn run_snapshot_creation(
+        window: &adw::ApplicationWindow,
+        manager: Rc<RefCell<SnapshotManager>>,
+        user_prefs_manager: Rc<RefCell<UserPreferencesManager>>,
+        backup_manager: Rc<RefCell<BackupManager>>,
+        list: ListBox,
+        compare_btn: Button,
+        snapshot_name: String,
+        description: String,
+        expires_in: Option<chrono::Duration>,
+        category: SnapshotCategory,
+        operation: OperationId,
+        turn: create_queue::Turn,
+    ) {
+        let window_clone = window.clone();
+        let list_clone = list.clone();
+        let manager_clone = manager.clone();
+        let user_prefs_clone = user_prefs_manager.clone();
+        let backup_manager_clone = backup_manager.clone();
+        let compare_btn_clone = compare_btn.clone();
+        let snapshot_name_clone = snapshot_name.clone();
+        let description_clone = description.clone();
+
+        let cancel_name = snapshot_name.clone();
+        let cancel_create = move || {
+            let cancel_name = cancel_name.clone();
+            std::thread::spawn(move || {
+                let result = WaypointHelperClient::new().and_then(|client| {
+                    // Creation only starts once the password prompt is answered
+                    if crate::dbus_client::helper_supports(FEATURE_CANCEL_AUTHORIZATION)
+                        && client.cancel_authorization().is_ok()
+                    {
+                        return Ok(());
+                    }
+                    client.cancel_create(&cancel_name)
+                });
+                if let Err(e) = result {
+                    log::warn!("Failed to cancel creation of {cancel_name}: {e}");
+                }
+            });
+        };
+
+        // Show loading state, with a way out if the helper supports cancelling
+        let progress_toast = if crate::dbus_client::helper_supports(FEATURE_CANCEL_CREATE) {
+            operations::begin(operation, Some(Rc::new(cancel_create.clone())));
+            dialogs::show_toast_with_button(
+                &window_clone,
+                "Creating snapshot...",
+                "Cancel",
+                cancel_create,
+            )
+        } else {
+            operations::begin(operation, None);
+            dialogs::show_toast(&window_clone, "Creating snapshot...");
+            None
+        };
+
+        // Create channel for thread communication
+        let (sender, receiver) = mpsc::channel();
+
+        // Spawn blocking operation in thread
+        std::thread::spawn(move || {
+            // Load subvolume configuration
+            let subvolume_paths = preferences::load_config();
+            let subvolumes: Vec<String> = subvolume_paths
+                .iter()
+                .map(|p| p.to_string_lossy().to_string())
+                .collect();
+
             // Connect to D-Bus helper
             let client = match WaypointHelperClient::new() {
                 Ok(c) => c,
@@ -1356,15 +2410,69 @@ impl MainWindow {
             };
 
             // Create snapshot (password prompt happens here)
-            let result = client.create_snapshot(snapshot_name_clone, description_clone, subvolumes);
+            let result =
+                client.create_snapshot(snapshot_name_clone.clone(), description_clone, subvolumes);
+
+            // Set the expiry and category before the metadata is read back below. They
+            // need their own authorization, so a refusal is shown once the snapshot exists.
+            let mut setting_errors = Vec::new();
+            if let (Ok((true, _)), Some(expires_in)) = (&result, expires_in) {
+                let expires_at = (chrono::Utc::now() + expires_in).to_rfc3339();
+                if let Err(e) = client.set_snapshot_expiry(&snapshot_name_clone, &expires_at) {
+                    log::warn!("Failed to set expiry of {snapshot_name_clone}: {e}");
+                    setting_errors.push(format!("The expiry date was not set: {e}"));
+                }
+            }
+            if let Ok((true, _)) = &result
+                && !category.is_manual()
+                && let Err(e) =
+                    client.set_snapshot_category(&snapshot_name_clone, category.as_str())
+            {
+                log::warn!("Failed to set category of {snapshot_name_clone}: {e}");
+                setting_errors.push(format!("The category was not set: {e}"));
+            }
 
             // Send result back to main thread
-            let _ = sender.send((Some((result, client)), None, subvolume_paths));
+            let _ = sender.send((Some((result, setting_errors)), None, subvolume_paths));
         });
 
-        // Receive results on main thread
-        glib::source::idle_add_local_once(move || {
-            if let Ok(msg) = receiver.recv() {
+        // Receive results on main thread without blocking it
+        glib::spawn_future_local(async move {
+            let received = loop {
+                match receiver.try_recv() {
+                    Ok(msg) => break Some(msg),
+                    Err(mpsc::TryRecvError::Empty) => {
+                        glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                    }
+                    Err(mpsc::TryRecvError::Disconnected) => break None,
+                }
+            };
+
+            if let Some(toast) = &progress_toast {
+                toast.dismiss();
+            }
+
+            match &received {
+                Some((Some((Ok((true, _)), _)), _, _)) => {
+                    operations::succeed(operation, gettext("Created"))
+                }
+                Some((Some((Ok((false, message)), _)), _, _)) => {
+                    let error = waypoint_common::HelperError::decode(message);
+                    if error.code == waypoint_common::ErrorCode::Cancelled {
+                        operations::cancelled(operation);
+                    } else {
+                        operations::fail(operation, error.message);
+                    }
+                }
+                Some((Some((Err(e), _)), _, _)) => operations::fail(operation, e.to_string()),
+                Some((None, Some((title, _)), _)) => operations::fail(operation, title.clone()),
+                _ => operations::fail(operation, gettext("Lost contact with the snapshot service")),
+            }
+
+            // The helper is done; let the next queued creation start
+            drop(turn);
+
+            if let Some(msg) = received {
                 let (result_opt, error_opt, subvolume_paths) = msg;
 
                 // Handle connection error
@@ -1374,19 +2482,13 @@ impl MainWindow {
                 }
 
                 // Handle snapshot result
-                if let Some((result, _client)) = result_opt {
+                if let Some((result, setting_errors)) = result_opt {
                     match result {
                         Ok((true, message)) => {
                             // Verify snapshot actually exists before saving metadata
-                            let snapshot_path = if PathBuf::from("/.snapshots").exists() {
-                                PathBuf::from(format!("/.snapshots/{snapshot_name}"))
-                            } else {
-                                PathBuf::from(format!(
-                                    "/mnt/btrfs-root/@snapshots/{snapshot_name}"
-                                ))
-                            };
+                            let snapshot_path = btrfs::snapshot_path(&snapshot_name);
 
-                            if !snapshot_path.exists() {
+                            if !waypoint_common::permissions::exists_or_hidden(&snapshot_path) {
                                 Self::show_error_dialog(
                                     &window_clone,
                                     "Snapshot Creation Failed",
@@ -1399,6 +2501,13 @@ impl MainWindow {
                             }
 
                             dialogs::show_toast(&window_clone, &message);
+                            if !setting_errors.is_empty() {
+                                Self::show_error_dialog(
+                                    &window_clone,
+                                    "Snapshot Settings Not Saved",
+                                    &setting_errors.join("\n\n"),
+                                );
+                            }
 
                             // Send desktop notification
                             if let Some(app) = window_clone.application() {
@@ -1407,6 +2516,7 @@ impl MainWindow {
 
                             // Calculate snapshot size and save metadata
                             Self::save_snapshot_metadata(
+                                &window_clone,
                                 &snapshot_name,
                                 &description,
                                 &subvolume_paths,
@@ -1418,23 +2528,24 @@ impl MainWindow {
                             if let Ok(snapshots) = manager_clone.borrow().load_snapshots() {
                                 // Find the snapshot we just created
                                 if let Some(snapshot) = snapshots.iter().find(|s| s.name == snapshot_name) {
-                                    let is_favorite = user_prefs_clone
-                                        .borrow()
-                                        .get(&snapshot_name)
-                                        .map(|p| p.is_favorite)
-                                        .unwrap_or(false);
+                                    let eligibility = user_prefs_clone.borrow().backup_eligibility(
+                                        &snapshot.id,
+                                        &snapshot.name,
+                                        &SchedulesConfig::load_or_default(&WaypointConfig::new().schedules_config),
+                                    );
 
                                     // Convert snapshots to SnapshotInfo for filtering
                                     let snapshot_info: waypoint_common::SnapshotInfo = snapshot.into();
                                     let all_snapshots: Vec<waypoint_common::SnapshotInfo> =
                                         snapshots.iter().map(|s| s.into()).collect();
 
-                                    log::info!("Attempting to queue snapshot {} for automatic backup (is_favorite: {})", snapshot_name, is_favorite);
+                                    log::info!("Attempting to queue snapshot {} for automatic backup ({:?})", snapshot_name, eligibility);
 
                                     match backup_manager_clone.borrow().queue_snapshot_backup(
                                         &snapshot_info,
-                                        is_favorite,
+                                        eligibility,
                                         &all_snapshots,
+                                        BackupTrigger::SnapshotCreation,
                                     ) {
                                         Ok(queued_destinations) => {
                                             if queued_destinations.is_empty() {
@@ -1448,74 +2559,11 @@ impl MainWindow {
                                                 // Check if any queued destinations are currently mounted
                                                 // and trigger immediate backup processing
                                                 for dest_uuid in queued_destinations {
-                                                    if let Some(mount_point) = backup_manager_clone.borrow().get_mounted_destination(&dest_uuid) {
-                                                        log::info!(
-                                                            "Destination {} is already mounted at {}, triggering immediate backup processing",
-                                                            dest_uuid,
-                                                            mount_point
-                                                        );
-
-                                                        // Get destination label and pending count for notifications
-                                                        let (dest_label, pending_count) = {
-                                                            let bm = backup_manager_clone.borrow();
-                                                            let label = bm
-                                                                .get_config()
-                                                                .ok()
-                                                                .and_then(|c| c.destinations.get(&dest_uuid).map(|d| d.label.clone()))
-                                                                .unwrap_or_else(|| mount_point.clone());
-                                                            let count = bm.get_pending_count(&dest_uuid);
-                                                            (label, count)
-                                                        };
-
-                                                        // Send start notification
-                                                        if let Some(app) = window_clone.application() {
-                                                            notifications::notify_backup_started(&app, &dest_label, pending_count);
-                                                        }
-
-                                                        // Spawn background thread to process this destination's pending backups
-                                                        let backup_manager_bg = backup_manager_clone.borrow().clone();
-                                                        let dest_uuid_clone = dest_uuid.clone();
-                                                        let mount_point_clone = mount_point.clone();
-
-                                                        // Get snapshot directory from config
-                                                        let snapshot_dir = waypoint_common::WaypointConfig::new()
-                                                            .snapshot_dir
-                                                            .to_string_lossy()
-                                                            .to_string();
-
-                                                        std::thread::spawn(move || {
-                                                            log::info!("Background thread: Processing pending backups for destination {}", dest_uuid_clone);
-
-                                                            match backup_manager_bg.process_pending_backups(
-                                                                &dest_uuid_clone,
-                                                                &mount_point_clone,
-                                                                &snapshot_dir,
-                                                            ) {
-                                                                Ok((success, failed, errors)) => {
-                                                                    log::info!(
-                                                                        "Immediate backup processing completed: {} succeeded, {} failed",
-                                                                        success,
-                                                                        failed
-                                                                    );
-                                                                    if !errors.is_empty() {
-                                                                        log::error!("Backup errors: {:?}", errors);
-                                                                    }
-                                                                }
-                                                                Err(e) => {
-                                                                    log::error!(
-                                                                        "Failed to process immediate backups for destination {}: {}",
-                                                                        dest_uuid_clone,
-                                                                        e
-                                                                    );
-                                                                }
-                                                            }
-                                                        });
-                                                    } else {
-                                                        log::info!(
-                                                            "Destination {} is not currently mounted, backup will process when drive is connected",
-                                                            dest_uuid
-                                                        );
-                                                    }
+                                                    main_window_helpers::start_pending_backups_if_mounted(
+                                                        window_clone.application().as_ref(),
+                                                        &backup_manager_clone,
+                                                        &dest_uuid,
+                                                    );
                                                 }
                                             }
                                         }
@@ -1547,7 +2595,45 @@ impl MainWindow {
                                 &compare_btn_clone,
                             );
                         }
+                        Ok((false, message))
+                            if waypoint_common::HelperError::decode(&message).code
+                                == waypoint_common::ErrorCode::Cancelled =>
+                        {
+                            dialogs::show_toast(&window_clone, "Snapshot creation cancelled");
+                        }
+                        Ok((false, message))
+                            if waypoint_common::HelperError::decode(&message).code
+                                == waypoint_common::ErrorCode::MetadataFull =>
+                        {
+                            let error = waypoint_common::HelperError::decode(&message);
+                            activity_log::record(
+                                ActivityKind::SnapshotCreated,
+                                &snapshot_name,
+                                false,
+                                Some(error.message.clone()),
+                            );
+                            Self::show_metadata_full_dialog(
+                                &window_clone,
+                                &manager_clone,
+                                &error.message,
+                                Self::list_refresher(
+                                    &window_clone,
+                                    &manager_clone,
+                                    &user_prefs_clone,
+                                    &backup_manager_clone,
+                                    &list_clone,
+                                    &compare_btn_clone,
+                                ),
+                            );
+                        }
                         Ok((false, message)) => {
+                            // Successes are recorded from the SnapshotCreated signal
+                            activity_log::record(
+                                ActivityKind::SnapshotCreated,
+                                &snapshot_name,
+                                false,
+                                Some(waypoint_common::HelperError::decode(&message).message),
+                            );
                             error_helpers::show_error_with_context(
                                 &window_clone,
                                 error_helpers::ErrorContext::SnapshotCreate,
@@ -1555,6 +2641,12 @@ impl MainWindow {
                             );
                         }
                         Err(e) => {
+                            activity_log::record(
+                                ActivityKind::SnapshotCreated,
+                                &snapshot_name,
+                                false,
+                                Some(e.to_string()),
+                            );
                             error_helpers::show_error_with_context(
                                 &window_clone,
                                 error_helpers::ErrorContext::SnapshotCreate,
@@ -1568,59 +2660,133 @@ impl MainWindow {
     }
 
     fn save_snapshot_metadata(
+        window: &adw::ApplicationWindow,
         snapshot_name: &str,
         description: &str,
         subvolume_paths: &[PathBuf],
         manager: &Rc<RefCell<SnapshotManager>>,
     ) {
-        // Construct snapshot path
-        // Use /.snapshots if mounted, otherwise fall back to /mnt/btrfs-root/@snapshots
-        let snapshot_path = if PathBuf::from("/.snapshots").exists() {
-            PathBuf::from(format!("/.snapshots/{snapshot_name}"))
-        } else {
-            PathBuf::from(format!("/mnt/btrfs-root/@snapshots/{snapshot_name}"))
+        let snapshot_path = btrfs::snapshot_path(snapshot_name);
+
+        // The helper has already written metadata for this snapshot, including the
+        // package list. Reuse it so the package count is accurate immediately.
+        let helper_entry = manager
+            .borrow()
+            .load_snapshots()
+            .ok()
+            .and_then(|snapshots| snapshots.into_iter().find(|s| s.name == snapshot_name));
+
+        let (id, timestamp, packages, kernel_version, expires_at, category) = match helper_entry {
+            Some(entry) => (
+                entry.id,
+                entry.timestamp,
+                entry.packages,
+                entry
+                    .kernel_version
+                    .or_else(waypoint_common::kernel_version),
+                entry.expires_at,
+                entry.category,
+            ),
+            None => {
+                log::debug!("No helper metadata found for {snapshot_name}, using local values");
+                (
+                    snapshot_name.to_string(),
+                    chrono::Utc::now(),
+                    Rc::new(Vec::new()),
+                    waypoint_common::kernel_version(),
+                    None,
+                    SnapshotCategory::Manual,
+                )
+            }
         };
 
+        // Derive the count from the list itself so the two can never disagree
+        let package_count = (!packages.is_empty()).then_some(packages.len());
+
         // Create snapshot metadata without size first (size calculation can be slow)
         let snapshot = Snapshot {
-            id: snapshot_name.to_string(),
+            id,
             name: snapshot_name.to_string(),
-            timestamp: chrono::Utc::now(),
+            timestamp,
             path: snapshot_path.clone(),
             description: Some(description.to_string()),
-            kernel_version: None, // Could add this later
-            package_count: None,  // Could add this later
-            size_bytes: None,     // Will be calculated in background
-            packages: Rc::new(Vec::new()),
+            kernel_version,
+            package_count,
+            size_bytes: None, // Will be calculated in background
+            packages,
             subvolumes: Rc::new(subvolume_paths.to_vec()),
+            retention_group: None,
+            expires_at,
+            transaction: None,
+            category,
+            is_favorite: false,
         };
 
+        // Keep the ID so the size update below replaces this same entry
+        let snapshot_id = snapshot.id.clone();
+
         // Save metadata immediately
         if let Err(e) = manager.borrow().add_snapshot(snapshot) {
             log::warn!("Failed to save snapshot metadata: {e}");
         }
 
         // Calculate snapshot size in background thread (non-blocking)
-        let snapshot_name_clone = snapshot_name.to_string();
         let manager_clone = manager.clone();
+        let window_clone = window.clone();
+        let snapshot_name = snapshot_name.to_string();
+        let name_for_size = snapshot_name.clone();
         let (tx, rx) = mpsc::channel();
 
         std::thread::spawn(move || {
-            let size_result = btrfs::get_snapshot_size(&snapshot_path);
-            let _ = tx.send((snapshot_name_clone, size_result));
+            // Goes through the helper first, which prefers the fast qgroup exclusive size
+            let size_result = btrfs::get_all_snapshot_sizes(std::slice::from_ref(&snapshot_path))
+                .remove(&snapshot_path)
+                .ok_or_else(|| anyhow::anyhow!("No size returned for {}", snapshot_path.display()));
+            // That size may be a `du` estimate, so the size warning asks for the exclusive size
+            let exclusive_size = crate::dbus_client::helper_supports(FEATURE_EXCLUSIVE_SIZES)
+                .then(|| {
+                    WaypointHelperClient::new()
+                        .and_then(|client| {
+                            client.get_snapshot_exclusive_sizes(vec![name_for_size.clone()])
+                        })
+                        .ok()
+                        .and_then(|sizes| sizes.get(&name_for_size).copied())
+                })
+                .flatten();
+            let _ = tx.send((snapshot_id, size_result, exclusive_size));
         });
 
         // Poll for result and update metadata when available
         glib::spawn_future_local(async move {
             loop {
                 match rx.try_recv() {
-                    Ok((name, size_result)) => {
+                    Ok((id, size_result, exclusive_size)) => {
+                        // Warn about unexpectedly large snapshots
+                        let quota_config = waypoint_common::QuotaConfig::load().unwrap_or_default();
+                        let exceeded = exclusive_size.and_then(|size| {
+                            quota_config
+                                .size_warning_bytes
+                                .filter(|_| quota_config.exceeds_size_warning(size))
+                                .map(|threshold| (size, threshold))
+                        });
+                        if let (Some(app), Some((size, threshold))) =
+                            (window_clone.application(), exceeded)
+                        {
+                            notifications::notify_snapshot_size_warning(
+                                &app,
+                                &snapshot_name,
+                                size,
+                                threshold,
+                            );
+                        }
+
                         match size_result {
                             Ok(size) => {
                                 log::debug!("Calculated snapshot size: {size} bytes");
+
                                 // Update snapshot with size
                                 if let Ok(Some(mut snapshot)) =
-                                    manager_clone.borrow().get_snapshot(&name)
+                                    manager_clone.borrow().get_snapshot(&id)
                                 {
                                     snapshot.size_bytes = Some(size);
                                     if let Err(e) = manager_clone.borrow().add_snapshot(snapshot) {
@@ -1647,6 +2813,33 @@ impl MainWindow {
         });
     }
 
+    /// Callback refreshing the snapshot list, for dialogs that change snapshots
+    fn list_refresher(
+        window: &adw::ApplicationWindow,
+        manager: &Rc<RefCell<SnapshotManager>>,
+        user_prefs_manager: &Rc<RefCell<UserPreferencesManager>>,
+        backup_manager: &Rc<RefCell<BackupManager>>,
+        list: &ListBox,
+        compare_btn: &Button,
+    ) -> Rc<dyn Fn()> {
+        let window = window.clone();
+        let manager = manager.clone();
+        let user_prefs_manager = user_prefs_manager.clone();
+        let backup_manager = backup_manager.clone();
+        let list = list.clone();
+        let compare_btn = compare_btn.clone();
+        Rc::new(move || {
+            Self::refresh_list_static(
+                &window,
+                &manager,
+                &user_prefs_manager,
+                &backup_manager,
+                &list,
+                &compare_btn,
+            );
+        })
+    }
+
     fn refresh_list_static(
         window: &adw::ApplicationWindow,
         manager: &Rc<RefCell<SnapshotManager>>,
@@ -1669,9 +2862,12 @@ impl MainWindow {
             backup_manager,
             list,
             compare_btn,
-            None, // No search filter
-            None, // No date filter
-            None, // No match label
+            None,  // No search filter
+            None,  // No date filter
+            None,  // No subvolume filter
+            None,  // No category filter
+            false, // No backup filter
+            None,  // No match label
             move |id, action| {
                 Self::handle_snapshot_action(
                     &window_clone,
@@ -1688,30 +2884,98 @@ impl MainWindow {
         );
     }
 
-    fn show_error_dialog(window: &adw::ApplicationWindow, title: &str, message: &str) {
-        dialogs::show_error(window, title, message);
-    }
-
-    fn handle_snapshot_action(
+    /// Refresh the list on request, showing a spinner until sizes are calculated
+    fn manual_refresh(
         window: &adw::ApplicationWindow,
         manager: &Rc<RefCell<SnapshotManager>>,
         user_prefs_manager: &Rc<RefCell<UserPreferencesManager>>,
         backup_manager: &Rc<RefCell<BackupManager>>,
         list: &ListBox,
         compare_btn: &Button,
-        snapshot_id: &str,
-        action: SnapshotAction,
+        refresh_btn: &Button,
     ) {
-        match action {
-            SnapshotAction::Browse => {
+        toolbar::set_refreshing(refresh_btn, true);
+
+        Self::refresh_list_static(
+            window,
+            manager,
+            user_prefs_manager,
+            backup_manager,
+            list,
+            compare_btn,
+        );
+
+        let refresh_btn = refresh_btn.clone();
+        glib::spawn_future_local(async move {
+            while snapshot_list::sizes_pending() {
+                glib::timeout_future(std::time::Duration::from_millis(100)).await;
+            }
+            toolbar::set_refreshing(&refresh_btn, false);
+        });
+    }
+
+    fn show_error_dialog(window: &adw::ApplicationWindow, title: &str, message: &str) {
+        dialogs::show_error(window, title, message);
+    }
+
+    /// Show a progress toast for an operation that may ask for a password
+    ///
+    /// Its Cancel button dismisses a pending authorization prompt, after which
+    /// the operation fails with a `Cancelled` error. Returns the toast so it can
+    /// be dismissed once the operation is over.
+    fn show_authorization_toast(
+        window: &adw::ApplicationWindow,
+        message: &str,
+    ) -> Option<adw::Toast> {
+        if !crate::dbus_client::helper_supports(FEATURE_CANCEL_AUTHORIZATION) {
+            dialogs::show_toast(window, message);
+            return None;
+        }
+        dialogs::show_toast_with_button(window, message, "Cancel", Self::cancel_authorization)
+    }
+
+    /// Dismiss this process's pending authorization prompts
+    fn cancel_authorization() {
+        std::thread::spawn(|| {
+            let result =
+                WaypointHelperClient::new().and_then(|client| client.cancel_authorization());
+            if let Err(e) = result {
+                log::warn!("Failed to cancel authorization: {e}");
+            }
+        });
+    }
+
+    /// Whether a helper failure message reports a cancelled operation
+    fn is_cancelled(message: &str) -> bool {
+        waypoint_common::HelperError::decode(message).code == waypoint_common::ErrorCode::Cancelled
+    }
+
+    fn handle_snapshot_action(
+        window: &adw::ApplicationWindow,
+        manager: &Rc<RefCell<SnapshotManager>>,
+        user_prefs_manager: &Rc<RefCell<UserPreferencesManager>>,
+        backup_manager: &Rc<RefCell<BackupManager>>,
+        list: &ListBox,
+        compare_btn: &Button,
+        snapshot_id: &str,
+        action: SnapshotAction,
+    ) {
+        match action {
+            SnapshotAction::Browse => {
                 Self::browse_snapshot(window, manager, snapshot_id);
             }
+            SnapshotAction::TestOverlay => {
+                Self::open_test_overlay(window, manager, snapshot_id);
+            }
             SnapshotAction::Verify => {
-                Self::verify_snapshot(window, manager, snapshot_id);
+                Self::verify_snapshot(window, manager, user_prefs_manager, snapshot_id);
             }
             SnapshotAction::Restore => {
                 Self::restore_snapshot(window, manager, list, snapshot_id);
             }
+            SnapshotAction::RestoreFiles => {
+                Self::restore_files(window, manager, snapshot_id);
+            }
             SnapshotAction::Delete => {
                 Self::delete_snapshot(
                     window,
@@ -1734,6 +2998,17 @@ impl MainWindow {
                     snapshot_id,
                 );
             }
+            SnapshotAction::ToggleBackupExclusion => {
+                Self::toggle_backup_exclusion(
+                    window,
+                    user_prefs_manager,
+                    manager,
+                    backup_manager,
+                    list,
+                    compare_btn,
+                    snapshot_id,
+                );
+            }
             SnapshotAction::EditNote => {
                 Self::edit_note(
                     window,
@@ -1748,6 +3023,42 @@ impl MainWindow {
             SnapshotAction::Backup => {
                 Self::backup_snapshot(window, manager, snapshot_id);
             }
+            SnapshotAction::ToggleReadOnly => {
+                Self::toggle_read_only(
+                    window,
+                    manager,
+                    user_prefs_manager,
+                    backup_manager,
+                    list,
+                    compare_btn,
+                    snapshot_id,
+                );
+            }
+            SnapshotAction::SetRetentionGroup => {
+                Self::choose_retention_group(
+                    window,
+                    manager,
+                    user_prefs_manager,
+                    backup_manager,
+                    list,
+                    compare_btn,
+                    snapshot_id,
+                );
+            }
+            SnapshotAction::SetExpiry => {
+                Self::choose_snapshot_expiry(
+                    window,
+                    manager,
+                    user_prefs_manager,
+                    backup_manager,
+                    list,
+                    compare_btn,
+                    snapshot_id,
+                );
+            }
+            SnapshotAction::UndoTransaction => {
+                Self::undo_transaction(window, manager, snapshot_id);
+            }
         }
     }
 
@@ -1757,7 +3068,7 @@ impl MainWindow {
         let (success, result) = client.scan_backup_destinations()?;
 
         if !success {
-            return Err(anyhow::anyhow!(result));
+            return Err(waypoint_common::HelperError::decode(&result).into());
         }
 
         // Parse JSON response
@@ -1776,6 +3087,11 @@ impl MainWindow {
             return Err(anyhow::anyhow!("Backup path does not exist"));
         }
 
+        // Image backups are single files, checked by the helper as they are written
+        if path.extension().is_some_and(|ext| ext == "img") && path.is_file() {
+            return Ok(());
+        }
+
         // Check if it's a directory
         if !path.is_dir() {
             return Err(anyhow::anyhow!("Backup path is not a directory"));
@@ -1833,21 +3149,42 @@ impl MainWindow {
 
 
     // Helper function to perform backup
-    fn perform_backup(snapshot_name: &str, destination_mount: &str) -> anyhow::Result<String> {
+    fn perform_backup(
+        snapshot_name: &str,
+        destination_mount: &str,
+        format: BackupFormat,
+    ) -> anyhow::Result<String> {
+        let result = Self::run_backup(snapshot_name, destination_mount, format);
+        let details = match &result {
+            Ok(_) => format!("Backed up to {destination_mount}"),
+            Err(e) => format!("Backup to {destination_mount} failed: {e}"),
+        };
+        activity_log::record(ActivityKind::Backup, snapshot_name, result.is_ok(), Some(details));
+        result
+    }
+
+    fn run_backup(
+        snapshot_name: &str,
+        destination_mount: &str,
+        format: BackupFormat,
+    ) -> anyhow::Result<String> {
         let client = WaypointHelperClient::new()?;
 
-        // Get snapshot path from config
-        let config = waypoint_common::WaypointConfig::new();
-        let snapshot_path = format!("{}/{}", config.snapshot_dir.display(), snapshot_name);
+        let snapshot_path = btrfs::snapshot_path(snapshot_name).to_string_lossy().to_string();
 
-        let (success, result, _size_bytes) = client.backup_snapshot(
-            snapshot_path,
-            destination_mount.to_string(),
-            String::new(), // No parent snapshot for now (full backup)
-        )?;
+        let (success, result, _size_bytes) = match format {
+            BackupFormat::Image => {
+                client.backup_snapshot_image(snapshot_path, destination_mount.to_string())?
+            }
+            BackupFormat::Native => client.backup_snapshot(
+                snapshot_path,
+                destination_mount.to_string(),
+                String::new(), // No parent snapshot for now (full backup)
+            )?,
+        };
 
         if !success {
-            return Err(anyhow::anyhow!(result));
+            return Err(waypoint_common::HelperError::decode(&result).into());
         }
 
         Ok(result)
@@ -2083,6 +3420,25 @@ impl MainWindow {
                                 row.set_title(&format!("{display_name}{type_badge}"));
                                 row.set_subtitle(&dest.mount_point);
 
+                                // Destinations can be set to receive image files instead
+                                let format = dest
+                                    .uuid
+                                    .as_ref()
+                                    .and_then(|uuid| saved_config.destinations.get(uuid))
+                                    .map(|d| d.format)
+                                    .unwrap_or_default();
+                                if format == BackupFormat::Image {
+                                    row.set_subtitle(&format!("{} (image file)", dest.mount_point));
+                                }
+
+                                // Same rule as automatic backups: unset means removable drives only
+                                let verify_contents = dest
+                                    .uuid
+                                    .as_ref()
+                                    .and_then(|uuid| saved_config.destinations.get(uuid))
+                                    .and_then(|d| d.verify_after_backup)
+                                    .unwrap_or(dest.drive_type == DriveType::Removable);
+
                                 // Add icon based on drive type
                                 let icon_name = match dest.drive_type {
                                     DriveType::Removable => "media-removable-symbolic",
@@ -2130,6 +3486,8 @@ impl MainWindow {
                                     let progress_group_ref3 = progress_group_ref2.clone();
                                     let pulse_handle_async = pulse_handle_row.clone();
                                     let progress_row_ref = progress_row_clone.clone();
+                                    let snapshot_name_verify = snapshot_name_ref2.clone();
+                                    let dest_mount_verify = dest_mount.clone();
 
                                     // Track start time for elapsed time display
                                     let start_time = std::time::Instant::now();
@@ -2137,7 +3495,11 @@ impl MainWindow {
                                     // Use thread + channel pattern
                                     let (tx, rx) = mpsc::channel();
                                     std::thread::spawn(move || {
-                                        let result = Self::perform_backup(&snapshot_name_clone, &dest_mount_clone);
+                                        let result = Self::perform_backup(
+                                            &snapshot_name_clone,
+                                            &dest_mount_clone,
+                                            format,
+                                        );
                                         let _ = tx.send(result);
                                     });
 
@@ -2179,7 +3541,20 @@ impl MainWindow {
                                                 let (verify_tx, verify_rx) = mpsc::channel();
                                                 let backup_path_clone = backup_path.clone();
                                                 std::thread::spawn(move || {
-                                                    let result = Self::verify_backup_exists(&backup_path_clone);
+                                                    let result = Self::verify_backup_exists(&backup_path_clone)
+                                                        .and_then(|()| {
+                                                            if !verify_contents {
+                                                                return Ok(());
+                                                            }
+                                                            let client = WaypointHelperClient::new()?;
+                                                            BackupManager::verify_completed_backup(
+                                                                &client,
+                                                                &btrfs::snapshot_path(&snapshot_name_verify),
+                                                                &dest_mount_verify,
+                                                                &snapshot_name_verify,
+                                                            )
+                                                            .map_err(anyhow::Error::msg)
+                                                        });
                                                     let _ = verify_tx.send(result);
                                                 });
 
@@ -2243,9 +3618,51 @@ impl MainWindow {
         });
     }
 
+    fn verify_all_snapshots(
+        window: &adw::ApplicationWindow,
+        manager: &Rc<RefCell<SnapshotManager>>,
+        user_prefs_manager: &Rc<RefCell<UserPreferencesManager>>,
+        backup_manager: &Rc<RefCell<BackupManager>>,
+        list: &ListBox,
+        compare_btn: &Button,
+    ) {
+        let snapshots = match manager.borrow().load_snapshots() {
+            Ok(snapshots) => snapshots,
+            Err(e) => {
+                dialogs::show_error(window, "Error", &format!("Failed to load snapshots: {e}"));
+                return;
+            }
+        };
+
+        if snapshots.is_empty() {
+            dialogs::show_toast(window, "No snapshots to verify");
+            return;
+        }
+
+        let snapshots = snapshots.into_iter().map(|s| (s.id, s.name)).collect();
+
+        let window_clone = window.clone();
+        let manager_clone = manager.clone();
+        let user_prefs_clone = user_prefs_manager.clone();
+        let backup_manager_clone = backup_manager.clone();
+        let list_clone = list.clone();
+        let compare_btn_clone = compare_btn.clone();
+        verify_all_dialog::show_verify_all_dialog(window, snapshots, user_prefs_manager, move || {
+            Self::refresh_list_static(
+                &window_clone,
+                &manager_clone,
+                &user_prefs_clone,
+                &backup_manager_clone,
+                &list_clone,
+                &compare_btn_clone,
+            );
+        });
+    }
+
     fn verify_snapshot(
         window: &adw::ApplicationWindow,
         manager: &Rc<RefCell<SnapshotManager>>,
+        user_prefs_manager: &Rc<RefCell<UserPreferencesManager>>,
         snapshot_id: &str,
     ) {
         // Get the snapshot to retrieve its actual name (directory name on disk)
@@ -2267,6 +3684,8 @@ impl MainWindow {
 
         let window_clone = window.clone();
         let snapshot_name = snapshot.name.clone();
+        let snapshot_id = snapshot.id.clone();
+        let user_prefs = user_prefs_manager.clone();
 
         // Run verification in background thread
         let (tx, rx) = mpsc::channel();
@@ -2298,6 +3717,16 @@ impl MainWindow {
                 }
             };
 
+            if let Ok(verification) = &result {
+                // Keeps or clears the failure badge on the next list refresh
+                if let Err(e) = user_prefs
+                    .borrow()
+                    .set_verification_failed(&snapshot_id, !verification.is_valid)
+                {
+                    log::warn!("Failed to record verification result: {e}");
+                }
+            }
+
             match result {
                 Ok(verification) => {
                     if verification.is_valid {
@@ -2345,6 +3774,454 @@ impl MainWindow {
         });
     }
 
+    /// Make a snapshot writable or read-only again after confirmation
+    fn toggle_read_only(
+        window: &adw::ApplicationWindow,
+        manager: &Rc<RefCell<SnapshotManager>>,
+        user_prefs_manager: &Rc<RefCell<UserPreferencesManager>>,
+        backup_manager: &Rc<RefCell<BackupManager>>,
+        list: &ListBox,
+        compare_btn: &Button,
+        snapshot_id: &str,
+    ) {
+        let snapshot_name = match manager.borrow().get_snapshot(snapshot_id) {
+            Ok(Some(s)) => s.name,
+            Ok(None) => {
+                Self::show_error_dialog(window, "Not Found", "Snapshot not found");
+                return;
+            }
+            Err(e) => {
+                Self::show_error_dialog(
+                    window,
+                    "Error",
+                    &format!("Failed to load snapshot: {e}"),
+                );
+                return;
+            }
+        };
+
+        // Query the current state first so the dialog offers the right change
+        let (tx, rx) = mpsc::channel();
+        let name_for_thread = snapshot_name.clone();
+        std::thread::spawn(move || {
+            let result = WaypointHelperClient::new()
+                .and_then(|client| client.get_snapshot_read_only(vec![name_for_thread.clone()]))
+                .and_then(|states| {
+                    states
+                        .get(&name_for_thread)
+                        .copied()
+                        .ok_or_else(|| anyhow::anyhow!("Snapshot not found"))
+                });
+            let _ = tx.send(result);
+        });
+
+        let window = window.clone();
+        let manager = manager.clone();
+        let user_prefs_manager = user_prefs_manager.clone();
+        let backup_manager = backup_manager.clone();
+        let list = list.clone();
+        let compare_btn = compare_btn.clone();
+        glib::spawn_future_local(async move {
+            let result = loop {
+                match rx.try_recv() {
+                    Ok(result) => break result,
+                    Err(mpsc::TryRecvError::Empty) => {
+                        glib::timeout_future(std::time::Duration::from_millis(50)).await;
+                    }
+                    Err(mpsc::TryRecvError::Disconnected) => return,
+                }
+            };
+
+            let read_only = match result {
+                Ok(read_only) => read_only,
+                Err(e) => {
+                    Self::show_error_dialog(
+                        &window,
+                        "Error",
+                        &format!("Failed to read snapshot state: {e}"),
+                    );
+                    return;
+                }
+            };
+
+            let (title, message, confirm_label) = if read_only {
+                (
+                    "Make Snapshot Writable?",
+                    format!(
+                        "Files in '{snapshot_name}' can then be changed, so it no longer records \
+                         the system exactly as it was. Writable snapshots can't be backed up until \
+                         they are made read-only again."
+                    ),
+                    "Make Writable",
+                )
+            } else {
+                (
+                    "Make Snapshot Read-Only?",
+                    format!("Files in '{snapshot_name}' can no longer be changed."),
+                    "Make Read-Only",
+                )
+            };
+
+            let window_clone = window.clone();
+            dialogs::show_confirmation(
+                &window,
+                title,
+                &message,
+                confirm_label,
+                read_only,
+                move || {
+                    Self::apply_read_only(
+                        &window_clone,
+                        &manager,
+                        &user_prefs_manager,
+                        &backup_manager,
+                        &list,
+                        &compare_btn,
+                        &snapshot_name,
+                        !read_only,
+                    );
+                },
+            );
+        });
+    }
+
+    /// Set the read-only state through the helper and refresh the list
+    fn apply_read_only(
+        window: &adw::ApplicationWindow,
+        manager: &Rc<RefCell<SnapshotManager>>,
+        user_prefs_manager: &Rc<RefCell<UserPreferencesManager>>,
+        backup_manager: &Rc<RefCell<BackupManager>>,
+        list: &ListBox,
+        compare_btn: &Button,
+        snapshot_name: &str,
+        read_only: bool,
+    ) {
+        let (tx, rx) = mpsc::channel();
+        let name_for_thread = snapshot_name.to_string();
+        std::thread::spawn(move || {
+            let result = WaypointHelperClient::new()
+                .and_then(|client| client.set_snapshot_read_only(&name_for_thread, read_only));
+            let _ = tx.send(result);
+        });
+
+        let window = window.clone();
+        let manager = manager.clone();
+        let user_prefs_manager = user_prefs_manager.clone();
+        let backup_manager = backup_manager.clone();
+        let list = list.clone();
+        let compare_btn = compare_btn.clone();
+        let snapshot_name = snapshot_name.to_string();
+        glib::spawn_future_local(async move {
+            let result = loop {
+                match rx.try_recv() {
+                    Ok(result) => break result,
+                    Err(mpsc::TryRecvError::Empty) => {
+                        glib::timeout_future(std::time::Duration::from_millis(50)).await;
+                    }
+                    Err(mpsc::TryRecvError::Disconnected) => return,
+                }
+            };
+
+            match result {
+                Ok(_) => {
+                    snapshot_list::set_cached_read_only(&snapshot_name, read_only);
+                    dialogs::show_toast(
+                        &window,
+                        if read_only {
+                            "Snapshot is now read-only"
+                        } else {
+                            "Snapshot is now writable"
+                        },
+                    );
+                    Self::refresh_list_static(
+                        &window,
+                        &manager,
+                        &user_prefs_manager,
+                        &backup_manager,
+                        &list,
+                        &compare_btn,
+                    );
+                }
+                Err(e) => {
+                    Self::show_error_dialog(
+                        &window,
+                        "Error",
+                        &format!("Failed to change read-only state: {e}"),
+                    );
+                }
+            }
+        });
+    }
+
+    /// Choose the schedule whose retention policy applies to a snapshot
+    ///
+    /// Snapshots follow their name prefix by default, so manual snapshots are
+    /// kept until deleted unless they are put under a schedule here.
+    fn choose_retention_group(
+        window: &adw::ApplicationWindow,
+        manager: &Rc<RefCell<SnapshotManager>>,
+        user_prefs_manager: &Rc<RefCell<UserPreferencesManager>>,
+        backup_manager: &Rc<RefCell<BackupManager>>,
+        list: &ListBox,
+        compare_btn: &Button,
+        snapshot_id: &str,
+    ) {
+        let snapshot = match manager.borrow().get_snapshot(snapshot_id) {
+            Ok(Some(s)) => s,
+            Ok(None) => {
+                dialogs::show_error(window, "Not Found", "Snapshot not found");
+                return;
+            }
+            Err(e) => {
+                dialogs::show_error(window, "Error", &format!("Failed to load snapshot: {e}"));
+                return;
+            }
+        };
+
+        let schedules = SchedulesConfig::load_or_default(&WaypointConfig::new().schedules_config);
+        let name_owner = schedules
+            .schedule_for_snapshot(&snapshot.name)
+            .map(|schedule| schedule.prefix.clone());
+
+        // The first entry clears the group; the rest are schedule prefixes
+        let mut groups = vec![String::new()];
+        let mut labels = vec![match &name_owner {
+            Some(prefix) => format!("By name ({prefix})"),
+            None => "By name (kept until deleted)".to_string(),
+        }];
+        for schedule in &schedules.schedules {
+            groups.push(schedule.prefix.clone());
+            labels.push(if schedule.enabled {
+                format!("{} ({})", schedule.prefix, schedule.schedule_type.as_str())
+            } else {
+                format!("{} ({}, disabled)", schedule.prefix, schedule.schedule_type.as_str())
+            });
+        }
+
+        let current = snapshot.retention_group.clone().unwrap_or_default();
+        let label_refs: Vec<&str> = labels.iter().map(String::as_str).collect();
+        let dropdown = gtk::DropDown::from_strings(&label_refs);
+        dropdown.set_selected(groups.iter().position(|g| *g == current).unwrap_or(0) as u32);
+
+        let dialog = adw::MessageDialog::new(
+            Some(window),
+            Some("Keep Under Schedule"),
+            Some(&format!(
+                "Choose whose retention policy decides when '{}' is deleted. Snapshots \
+                 follow the schedule their name starts with; snapshots without a schedule \
+                 prefix are kept until deleted by hand.",
+                snapshot.name
+            )),
+        );
+        dialog.set_extra_child(Some(&dropdown));
+        dialog.add_response("cancel", "Cancel");
+        dialog.add_response("apply", "Apply");
+        dialog.set_response_appearance("apply", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("apply"));
+        dialog.set_close_response("cancel");
+
+        let window = window.clone();
+        let manager = manager.clone();
+        let user_prefs_manager = user_prefs_manager.clone();
+        let backup_manager = backup_manager.clone();
+        let list = list.clone();
+        let compare_btn = compare_btn.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "apply" {
+                return;
+            }
+            let Some(group) = groups.get(dropdown.selected() as usize).cloned() else {
+                return;
+            };
+            if group == current {
+                return;
+            }
+
+            let (tx, rx) = mpsc::channel();
+            let name_for_thread = snapshot.name.clone();
+            let group_for_thread = group.clone();
+            std::thread::spawn(move || {
+                let result = WaypointHelperClient::new().and_then(|client| {
+                    client.set_retention_group(&name_for_thread, &group_for_thread)
+                });
+                let _ = tx.send(result);
+            });
+
+            let window = window.clone();
+            let manager = manager.clone();
+            let user_prefs_manager = user_prefs_manager.clone();
+            let backup_manager = backup_manager.clone();
+            let list = list.clone();
+            let compare_btn = compare_btn.clone();
+            glib::spawn_future_local(async move {
+                let result = loop {
+                    match rx.try_recv() {
+                        Ok(result) => break result,
+                        Err(mpsc::TryRecvError::Empty) => {
+                            glib::timeout_future(std::time::Duration::from_millis(50)).await;
+                        }
+                        Err(mpsc::TryRecvError::Disconnected) => return,
+                    }
+                };
+
+                match result {
+                    Ok(_) => {
+                        dialogs::show_toast(
+                            &window,
+                            &if group.is_empty() {
+                                "Snapshot retention now follows its name".to_string()
+                            } else {
+                                format!("Snapshot is now kept under '{group}'")
+                            },
+                        );
+                        Self::refresh_list_static(
+                            &window,
+                            &manager,
+                            &user_prefs_manager,
+                            &backup_manager,
+                            &list,
+                            &compare_btn,
+                        );
+                    }
+                    Err(e) => {
+                        Self::show_error_dialog(
+                            &window,
+                            "Error",
+                            &format!("Failed to change retention: {e}"),
+                        );
+                    }
+                }
+            });
+        });
+
+        dialog.present();
+    }
+
+    /// Let the user choose when a snapshot expires, or keep it indefinitely
+    fn choose_snapshot_expiry(
+        window: &adw::ApplicationWindow,
+        manager: &Rc<RefCell<SnapshotManager>>,
+        user_prefs_manager: &Rc<RefCell<UserPreferencesManager>>,
+        backup_manager: &Rc<RefCell<BackupManager>>,
+        list: &ListBox,
+        compare_btn: &Button,
+        snapshot_id: &str,
+    ) {
+        let snapshot = match manager.borrow().get_snapshot(snapshot_id) {
+            Ok(Some(s)) => s,
+            Ok(None) => {
+                dialogs::show_error(window, "Not Found", "Snapshot not found");
+                return;
+            }
+            Err(e) => {
+                dialogs::show_error(window, "Error", &format!("Failed to load snapshot: {e}"));
+                return;
+            }
+        };
+
+        let dropdown = create_snapshot_dialog::expiry_dropdown();
+        let body = match snapshot.expires_at {
+            Some(expires_at) => format!(
+                "'{}' currently expires on {}. Expired snapshots are deleted by the next \
+                 cleanup unless they're pinned.",
+                snapshot.name,
+                expires_at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+            ),
+            None => format!(
+                "Choose when '{}' is deleted. Expired snapshots are deleted by the next \
+                 cleanup unless they're pinned.",
+                snapshot.name
+            ),
+        };
+
+        let dialog = adw::MessageDialog::new(Some(window), Some("Set Expiry"), Some(&body));
+        dialog.set_extra_child(Some(&dropdown));
+        dialog.add_response("cancel", "Cancel");
+        dialog.add_response("apply", "Apply");
+        dialog.set_response_appearance("apply", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("apply"));
+        dialog.set_close_response("cancel");
+
+        let window = window.clone();
+        let manager = manager.clone();
+        let user_prefs_manager = user_prefs_manager.clone();
+        let backup_manager = backup_manager.clone();
+        let list = list.clone();
+        let compare_btn = compare_btn.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "apply" {
+                return;
+            }
+            let expires_in = create_snapshot_dialog::selected_expiry(&dropdown);
+            if expires_in.is_none() && snapshot.expires_at.is_none() {
+                return;
+            }
+
+            // An empty date clears the expiry
+            let expires_at = expires_in
+                .map(|duration| (chrono::Utc::now() + duration).to_rfc3339())
+                .unwrap_or_default();
+
+            let (tx, rx) = mpsc::channel();
+            let name_for_thread = snapshot.name.clone();
+            std::thread::spawn(move || {
+                let result = WaypointHelperClient::new()
+                    .and_then(|client| client.set_snapshot_expiry(&name_for_thread, &expires_at));
+                let _ = tx.send(result);
+            });
+
+            let window = window.clone();
+            let manager = manager.clone();
+            let user_prefs_manager = user_prefs_manager.clone();
+            let backup_manager = backup_manager.clone();
+            let list = list.clone();
+            let compare_btn = compare_btn.clone();
+            glib::spawn_future_local(async move {
+                let result = loop {
+                    match rx.try_recv() {
+                        Ok(result) => break result,
+                        Err(mpsc::TryRecvError::Empty) => {
+                            glib::timeout_future(std::time::Duration::from_millis(50)).await;
+                        }
+                        Err(mpsc::TryRecvError::Disconnected) => return,
+                    }
+                };
+
+                match result {
+                    Ok(_) => {
+                        dialogs::show_toast(
+                            &window,
+                            if expires_in.is_some() {
+                                "Snapshot expiry set"
+                            } else {
+                                "Snapshot no longer expires"
+                            },
+                        );
+                        Self::refresh_list_static(
+                            &window,
+                            &manager,
+                            &user_prefs_manager,
+                            &backup_manager,
+                            &list,
+                            &compare_btn,
+                        );
+                    }
+                    Err(e) => {
+                        Self::show_error_dialog(
+                            &window,
+                            "Error",
+                            &format!("Failed to set expiry: {e}"),
+                        );
+                    }
+                }
+            });
+        });
+
+        dialog.present();
+    }
+
     fn browse_snapshot(
         window: &adw::ApplicationWindow,
         manager: &Rc<RefCell<SnapshotManager>>,
@@ -2363,22 +4240,33 @@ impl MainWindow {
         };
 
         // Open snapshot directory in file manager using GTK's FileLauncher
-        use waypoint_common::WaypointConfig;
-
-        let config = WaypointConfig::new();
-        let snapshot_path = config.snapshot_dir.join(&snapshot.name);
+        let snapshot_path = snapshot.path.clone();
 
         // Check if path exists before trying to open
-        if !snapshot_path.exists() {
-            dialogs::show_error(
-                window,
-                "Snapshot Not Found",
-                &format!(
-                    "The snapshot directory does not exist:\n\n{}\n\nThe snapshot may have been deleted outside of Waypoint.",
-                    snapshot_path.display()
-                ),
-            );
-            return;
+        match snapshot_path.try_exists() {
+            Ok(true) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                dialogs::show_error(
+                    window,
+                    "Snapshot Not Readable",
+                    &format!(
+                        "The snapshot directory is only readable by root:\n\n{}\n\nOpen it as root to browse it.",
+                        snapshot_path.display()
+                    ),
+                );
+                return;
+            }
+            _ => {
+                dialogs::show_error(
+                    window,
+                    "Snapshot Not Found",
+                    &format!(
+                        "The snapshot directory does not exist:\n\n{}\n\nThe snapshot may have been deleted outside of Waypoint.",
+                        snapshot_path.display()
+                    ),
+                );
+                return;
+            }
         }
 
         // Use GTK's FileLauncher to open the directory
@@ -2397,8 +4285,22 @@ impl MainWindow {
         });
     }
 
+    fn open_test_overlay(
+        window: &adw::ApplicationWindow,
+        manager: &Rc<RefCell<SnapshotManager>>,
+        snapshot_id: &str,
+    ) {
+        match manager.borrow().get_snapshot(snapshot_id) {
+            Ok(Some(snapshot)) => overlay_dialog::mount_test_overlay(window, &snapshot),
+            Ok(None) => dialogs::show_error(window, "Not Found", "Snapshot not found"),
+            Err(e) => {
+                dialogs::show_error(window, "Error", &format!("Failed to load snapshot: {e}"))
+            }
+        }
+    }
+
     fn toggle_favorite(
-        _window: &adw::ApplicationWindow,
+        window: &adw::ApplicationWindow,
         user_prefs_manager: &Rc<RefCell<UserPreferencesManager>>,
         manager: &Rc<RefCell<SnapshotManager>>,
         backup_manager: &Rc<RefCell<BackupManager>>,
@@ -2407,9 +4309,22 @@ impl MainWindow {
         snapshot_id: &str,
     ) {
         // Toggle favorite state in user preferences
-        if let Err(e) = user_prefs_manager.borrow().toggle_favorite(snapshot_id) {
-            log::error!("Failed to toggle snapshot favorite state: {e}");
-            return;
+        let is_favorite = match user_prefs_manager.borrow().toggle_favorite(snapshot_id) {
+            Ok(is_favorite) => is_favorite,
+            Err(e) => {
+                log::error!("Failed to toggle snapshot favorite state: {e}");
+                return;
+            }
+        };
+
+        // Cleanup runs in the helper, which only sees pins stored in its metadata
+        if crate::dbus_client::helper_supports(FEATURE_SNAPSHOT_PINS) {
+            Self::record_pin(window, manager, snapshot_id, is_favorite);
+        }
+
+        // Newly favorited snapshots are backed up to destinations with the favorite trigger
+        if is_favorite {
+            Self::queue_favorite_backup(window, user_prefs_manager, manager, backup_manager, snapshot_id);
         }
 
         // Refresh the list to show updated star icon and potentially reorder
@@ -2432,6 +4347,9 @@ impl MainWindow {
                 None,
                 None,
                 None,
+                None,
+                false,
+                None,
                 move |id, action| {
                     // Re-create clones for the action handler
                     let window = window_clone.clone();
@@ -2441,18 +4359,148 @@ impl MainWindow {
                     let list = list_clone.clone();
                     let compare_btn = compare_btn_clone.clone();
 
-                    Self::handle_snapshot_action(
-                        &window,
-                        &manager,
-                        &user_prefs,
-                        &backup_manager,
-                        &list,
-                        &compare_btn,
-                        id,
-                        action,
-                    );
-                },
-                None,
+                    Self::handle_snapshot_action(
+                        &window,
+                        &manager,
+                        &user_prefs,
+                        &backup_manager,
+                        &list,
+                        &compare_btn,
+                        id,
+                        action,
+                    );
+                },
+                None,
+            );
+        }
+    }
+
+    /// Store a favorite's pin in the helper metadata in the background
+    fn record_pin(
+        window: &adw::ApplicationWindow,
+        manager: &Rc<RefCell<SnapshotManager>>,
+        snapshot_id: &str,
+        pinned: bool,
+    ) {
+        let name = match manager.borrow().get_snapshot(snapshot_id) {
+            Ok(Some(snapshot)) => snapshot.name,
+            _ => snapshot_id.to_string(),
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let name_for_thread = name.clone();
+        std::thread::spawn(move || {
+            let result = WaypointHelperClient::new()
+                .and_then(|client| client.set_snapshot_pinned(&name_for_thread, pinned));
+            let _ = tx.send(result);
+        });
+
+        let window = window.clone();
+        glib::spawn_future_local(async move {
+            let result = loop {
+                match rx.try_recv() {
+                    Ok(result) => break result,
+                    Err(mpsc::TryRecvError::Empty) => {
+                        glib::timeout_future(std::time::Duration::from_millis(50)).await;
+                    }
+                    Err(mpsc::TryRecvError::Disconnected) => return,
+                }
+            };
+
+            if let Err(e) = result {
+                log::warn!("Failed to record pin of {name}: {e}");
+                dialogs::show_toast(&window, "Could not protect the snapshot from cleanup");
+            }
+        });
+    }
+
+    fn toggle_backup_exclusion(
+        window: &adw::ApplicationWindow,
+        user_prefs_manager: &Rc<RefCell<UserPreferencesManager>>,
+        manager: &Rc<RefCell<SnapshotManager>>,
+        backup_manager: &Rc<RefCell<BackupManager>>,
+        list: &ListBox,
+        compare_btn: &Button,
+        snapshot_id: &str,
+    ) {
+        let excluded = match user_prefs_manager.borrow().toggle_exclude_from_backup(snapshot_id) {
+            Ok(excluded) => excluded,
+            Err(e) => {
+                log::error!("Failed to toggle backup exclusion: {e}");
+                dialogs::show_error(window, "Error", &format!("Failed to update snapshot: {e}"));
+                return;
+            }
+        };
+
+        if excluded {
+            // Drop queued backups so an excluded snapshot never reaches a backup drive
+            if let Err(e) = backup_manager.borrow().remove_pending_for_snapshot(snapshot_id) {
+                log::warn!("Failed to remove queued backups for {snapshot_id}: {e}");
+            }
+            dialogs::show_toast(window, "Excluded from automatic backups");
+        } else {
+            dialogs::show_toast(window, "Included in automatic backups");
+        }
+
+        Self::refresh_list_static(
+            window,
+            manager,
+            user_prefs_manager,
+            backup_manager,
+            list,
+            compare_btn,
+        );
+    }
+
+    /// Queue a newly favorited snapshot and start backing it up where possible
+    fn queue_favorite_backup(
+        window: &adw::ApplicationWindow,
+        user_prefs_manager: &Rc<RefCell<UserPreferencesManager>>,
+        manager: &Rc<RefCell<SnapshotManager>>,
+        backup_manager: &Rc<RefCell<BackupManager>>,
+        snapshot_id: &str,
+    ) {
+        let snapshots = match manager.borrow().load_snapshots() {
+            Ok(snaps) => snaps,
+            Err(e) => {
+                log::warn!("Failed to load snapshots for favorite backup: {e}");
+                return;
+            }
+        };
+        let Some(snapshot) = snapshots.iter().find(|s| s.id == snapshot_id) else {
+            return;
+        };
+
+        // Pending backups and backup records are keyed by the snapshot name
+        let snapshot_info: waypoint_common::SnapshotInfo = snapshot.into();
+        let all_snapshots: Vec<waypoint_common::SnapshotInfo> =
+            snapshots.iter().map(|s| s.into()).collect();
+
+        let eligibility = user_prefs_manager.borrow().backup_eligibility(
+            snapshot_id,
+            &snapshot.name,
+            &SchedulesConfig::load_or_default(&WaypointConfig::new().schedules_config),
+        );
+
+        let queued = match backup_manager.borrow().queue_snapshot_backup(
+            &snapshot_info,
+            eligibility,
+            &all_snapshots,
+            BackupTrigger::Favorite,
+        ) {
+            Ok(queued) => queued,
+            Err(e) => {
+                log::warn!("Failed to queue backup for favorite {}: {e}", snapshot.name);
+                return;
+            }
+        };
+
+        let app = window.application();
+        for dest_uuid in queued {
+            main_window_helpers::start_pending_backups_if_mounted(
+                app.as_ref(),
+                backup_manager,
+                &dest_uuid,
             );
         }
     }
@@ -2629,13 +4677,19 @@ impl MainWindow {
 
         // Bottom button area
         let button_box = gtk::Box::new(gtk::Orientation::Horizontal, 12);
-        button_box.set_halign(gtk::Align::End);
         button_box.set_margin_top(12);
 
+        // Undo the last note or favorite change, e.g. a note cleared by mistake
+        let undo_btn = gtk::Button::with_label(&gettext("Undo Last Change"));
+        undo_btn.set_halign(gtk::Align::Start);
+        undo_btn.set_hexpand(true);
+        Self::update_undo_button(&undo_btn, current_prefs.history.last());
+
         let cancel_btn = gtk::Button::with_label("Cancel");
         let save_btn = gtk::Button::with_label("Save");
         save_btn.add_css_class("suggested-action");
 
+        button_box.append(&undo_btn);
         button_box.append(&cancel_btn);
         button_box.append(&save_btn);
         content_box.append(&button_box);
@@ -2643,38 +4697,15 @@ impl MainWindow {
         toolbar_view.set_content(Some(&content_box));
         dialog.set_content(Some(&toolbar_view));
 
-        // Save function
-        let save_note = {
-            let dialog = dialog.clone();
+        // Refresh list to show updated note in subtitle and favorite state
+        let refresh_list = {
             let user_prefs_clone = user_prefs_manager.clone();
             let manager_clone = manager.clone();
             let backup_manager_clone = backup_manager.clone();
             let list_clone = list.clone();
             let compare_btn_clone = compare_btn.clone();
-            let snapshot_id = snapshot_id.to_string();
-            let text_view_clone = text_view.clone();
 
             move || {
-                // Get note text from buffer
-                let buffer = text_view_clone.buffer();
-                let note_text = buffer
-                    .text(&buffer.start_iter(), &buffer.end_iter(), false)
-                    .to_string();
-
-                // Update note (trim whitespace, use None if empty)
-                let note = if note_text.trim().is_empty() {
-                    None
-                } else {
-                    Some(note_text.trim().to_string())
-                };
-
-                // Save note to user preferences
-                if let Err(e) = user_prefs_clone.borrow().update_note(&snapshot_id, note) {
-                    log::error!("Failed to save snapshot note: {e}");
-                    return;
-                }
-
-                // Refresh list to show updated note in subtitle
                 let window_weak = list_clone.root().and_downcast::<adw::ApplicationWindow>();
                 if let Some(window) = window_weak {
                     let window_inner = window.clone();
@@ -2694,6 +4725,9 @@ impl MainWindow {
                         None,
                         None,
                         None,
+                        None,
+                        false,
+                        None,
                         move |id, action| {
                             Self::handle_snapshot_action(
                                 &window_inner,
@@ -2709,11 +4743,80 @@ impl MainWindow {
                         None,
                     );
                 }
+            }
+        };
+
+        // Save function
+        let save_note = {
+            let dialog = dialog.clone();
+            let user_prefs_clone = user_prefs_manager.clone();
+            let snapshot_id = snapshot_id.to_string();
+            let text_view_clone = text_view.clone();
+            let refresh_list = refresh_list.clone();
+
+            move || {
+                // Get note text from buffer
+                let buffer = text_view_clone.buffer();
+                let note_text = buffer
+                    .text(&buffer.start_iter(), &buffer.end_iter(), false)
+                    .to_string();
+
+                // Update note (trim whitespace, use None if empty)
+                let note = if note_text.trim().is_empty() {
+                    None
+                } else {
+                    Some(note_text.trim().to_string())
+                };
+
+                // Save note to user preferences
+                if let Err(e) = user_prefs_clone.borrow().update_note(&snapshot_id, note) {
+                    log::error!("Failed to save snapshot note: {e}");
+                    return;
+                }
 
+                refresh_list();
                 dialog.close();
             }
         };
 
+        // Handle undo button; the undone change is saved right away
+        let user_prefs_clone = user_prefs_manager.clone();
+        let snapshot_id_clone = snapshot_id.to_string();
+        let buffer_clone = buffer.clone();
+        let window_clone = window.clone();
+        let manager_clone = manager.clone();
+        undo_btn.connect_clicked(move |btn| {
+            let was_favorite = user_prefs_clone
+                .borrow()
+                .get(&snapshot_id_clone)
+                .is_ok_and(|prefs| prefs.is_favorite);
+            match user_prefs_clone
+                .borrow()
+                .undo_last_change(&snapshot_id_clone)
+            {
+                Ok(Some(prefs)) => {
+                    if prefs.is_favorite != was_favorite
+                        && crate::dbus_client::helper_supports(FEATURE_SNAPSHOT_PINS)
+                    {
+                        Self::record_pin(
+                            &window_clone,
+                            &manager_clone,
+                            &snapshot_id_clone,
+                            prefs.is_favorite,
+                        );
+                    }
+                    buffer_clone.set_text(prefs.note.as_deref().unwrap_or_default());
+                    Self::update_undo_button(btn, prefs.history.last());
+                }
+                Ok(None) => Self::update_undo_button(btn, None),
+                Err(e) => {
+                    log::error!("Failed to undo snapshot preference change: {e}");
+                    return;
+                }
+            }
+            refresh_list();
+        });
+
         // Handle cancel button
         let dialog_clone = dialog.clone();
         cancel_btn.connect_clicked(move |_| {
@@ -2754,6 +4857,25 @@ impl MainWindow {
         dialog.present();
     }
 
+    /// Whether the Command Transcript menu entry is shown (explain mode)
+    fn show_command_transcript() -> bool {
+        preferences::load_manual_settings().show_commands
+            && crate::dbus_client::helper_supports(FEATURE_COMMAND_TRANSCRIPT)
+    }
+
+    /// Describe the change the note dialog's undo button would revert
+    fn update_undo_button(button: &Button, last_change: Option<&PreferenceChange>) {
+        let tooltip = last_change.map(|change| match change {
+            PreferenceChange::Note { .. } => gettext("Restore the previous note"),
+            PreferenceChange::Favorite { previous: true } => gettext("Mark as favorite again"),
+            PreferenceChange::Favorite { previous: false } => {
+                gettext("Remove from favorites again")
+            }
+        });
+        button.set_sensitive(tooltip.is_some());
+        button.set_tooltip_text(tooltip.as_deref());
+    }
+
     fn delete_snapshot(
         window: &adw::ApplicationWindow,
         manager: &Rc<RefCell<SnapshotManager>>,
@@ -2791,11 +4913,14 @@ impl MainWindow {
         let list_clone = list.clone();
         let compare_btn_clone = compare_btn.clone();
 
-        // Check if snapshot has backups
-        let has_backups = backup_manager.borrow().is_snapshot_backed_up(&snapshot.id);
-        let message = if has_backups {
+        // Backup records are keyed by the snapshot's directory name
+        let backup_labels = backup_manager
+            .borrow()
+            .get_snapshot_backup_labels(&snapshot_basename);
+        let message = if !backup_labels.is_empty() {
             format!(
-                "Are you sure you want to delete '{snapshot_name}'?\n\nThis snapshot has backups on external drives. Deleting it here will NOT delete the backups.\n\nThis action cannot be undone."
+                "Are you sure you want to delete '{snapshot_name}'?\n\nThis snapshot has backups on external drives: {}. Deleting it here will NOT delete the backups.\n\nThis action cannot be undone.",
+                backup_labels.join(", ")
             )
         } else {
             format!(
@@ -2819,8 +4944,9 @@ impl MainWindow {
                 let name = snapshot_basename.clone();
                 let name_for_notification = snapshot_basename.clone();
 
-                // Show loading state
-                dialogs::show_toast(&window, "Deleting snapshot...");
+                // Show loading state, with a way to abandon the password prompt
+                let progress_toast =
+                    Self::show_authorization_toast(&window, "Deleting snapshot...");
 
                 // Create channel for thread communication
                 let (sender, receiver) = mpsc::channel();
@@ -2845,9 +4971,23 @@ impl MainWindow {
                     let _ = sender.send((Some(result), None));
                 });
 
-                // Receive results on main thread
-                glib::source::idle_add_local_once(move || {
-                    if let Ok(msg) = receiver.recv() {
+                // Receive results on main thread without blocking it, so the toast stays clickable
+                glib::spawn_future_local(async move {
+                    let received = loop {
+                        match receiver.try_recv() {
+                            Ok(msg) => break Some(msg),
+                            Err(mpsc::TryRecvError::Empty) => {
+                                glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                            }
+                            Err(mpsc::TryRecvError::Disconnected) => break None,
+                        }
+                    };
+
+                    if let Some(toast) = &progress_toast {
+                        toast.dismiss();
+                    }
+
+                    if let Some(msg) = received {
                         let (result_opt, error_opt) = msg;
 
                         // Handle connection error
@@ -2861,6 +5001,12 @@ impl MainWindow {
                             match result {
                                 Ok((true, message)) => {
                                     dialogs::show_toast(&window, &message);
+                                    activity_log::record(
+                                        ActivityKind::SnapshotDeleted,
+                                        &name_for_notification,
+                                        true,
+                                        None,
+                                    );
 
                                     // Send desktop notification
                                     if let Some(app) = window.application() {
@@ -2880,7 +5026,16 @@ impl MainWindow {
                                         &compare_btn,
                                     );
                                 }
+                                Ok((false, message)) if Self::is_cancelled(&message) => {
+                                    dialogs::show_toast(&window, "Deletion cancelled");
+                                }
                                 Ok((false, message)) => {
+                                    activity_log::record(
+                                        ActivityKind::SnapshotDeleted,
+                                        &name_for_notification,
+                                        false,
+                                        Some(waypoint_common::HelperError::decode(&message).message),
+                                    );
                                     error_helpers::show_error_with_context(
                                         &window,
                                         error_helpers::ErrorContext::SnapshotDelete,
@@ -2888,6 +5043,12 @@ impl MainWindow {
                                     );
                                 }
                                 Err(e) => {
+                                    activity_log::record(
+                                        ActivityKind::SnapshotDeleted,
+                                        &name_for_notification,
+                                        false,
+                                        Some(e.to_string()),
+                                    );
                                     error_helpers::show_error_with_context(
                                         &window,
                                         error_helpers::ErrorContext::SnapshotDelete,
@@ -2921,10 +5082,35 @@ impl MainWindow {
         };
 
         // Show restore choice dialog
-        Self::show_restore_choice_dialog(window, &snapshot.name);
+        Self::show_restore_choice_dialog(window, &snapshot.name, &snapshot.subvolumes);
+    }
+
+    /// Go straight to restoring individual files, as picked in the restore choice dialog
+    fn restore_files(
+        window: &adw::ApplicationWindow,
+        manager: &Rc<RefCell<SnapshotManager>>,
+        snapshot_id: &str,
+    ) {
+        let snapshot = match manager.borrow().get_snapshot(snapshot_id) {
+            Ok(Some(s)) => s,
+            Ok(None) => {
+                dialogs::show_error(window, "Not Found", "Snapshot not found");
+                return;
+            }
+            Err(e) => {
+                dialogs::show_error(window, "Error", &format!("Failed to load snapshot: {e}"));
+                return;
+            }
+        };
+
+        file_restore_dialog::show_file_restore_dialog(window, &snapshot.name);
     }
 
-    fn show_restore_choice_dialog(window: &adw::ApplicationWindow, snapshot_name: &str) {
+    fn show_restore_choice_dialog(
+        window: &adw::ApplicationWindow,
+        snapshot_name: &str,
+        subvolumes: &[PathBuf],
+    ) {
         let dialog = adw::Window::new();
         dialog.set_transient_for(Some(window));
         dialog.set_modal(true);
@@ -2947,7 +5133,7 @@ impl MainWindow {
         let group = adw::PreferencesGroup::new();
         group.set_title("How would you like to restore?");
         group.set_description(Some(
-            "Choose whether to restore the entire system or individual files",
+            "Choose whether to restore the entire system, individual files or a separate copy",
         ));
 
         // Full system restore option
@@ -2982,6 +5168,24 @@ impl MainWindow {
 
         group.add(&files_restore_row);
 
+        // Non-destructive restore to a new subvolume
+        let subvolume_restore_row = adw::ActionRow::new();
+        subvolume_restore_row.set_title("Restore to New Subvolume");
+        subvolume_restore_row
+            .set_subtitle("Create a writable copy to mount and inspect, without changing your system");
+        subvolume_restore_row.set_activatable(true);
+
+        let subvolume_icon = gtk::Image::from_icon_name("folder-new-symbolic");
+        subvolume_icon.set_pixel_size(24);
+        subvolume_restore_row.add_prefix(&subvolume_icon);
+
+        let subvolume_arrow = gtk::Image::from_icon_name("go-next-symbolic");
+        subvolume_restore_row.add_suffix(&subvolume_arrow);
+
+        subvolume_restore_row
+            .set_visible(crate::dbus_client::helper_supports(FEATURE_RESTORE_TO_SUBVOLUME));
+        group.add(&subvolume_restore_row);
+
         content_box.append(&group);
         main_box.append(&content_box);
 
@@ -3004,9 +5208,65 @@ impl MainWindow {
             file_restore_dialog::show_file_restore_dialog(&window_clone2, &snapshot_name_clone2);
         });
 
+        let window_clone3 = window.clone();
+        let snapshot_name_clone3 = snapshot_name.to_string();
+        let subvolumes = subvolumes.to_vec();
+        let dialog_clone3 = dialog.clone();
+        subvolume_restore_row.connect_activated(move |_| {
+            dialog_clone3.close();
+            subvolume_restore_dialog::show_restore_to_subvolume_dialog(
+                &window_clone3,
+                &snapshot_name_clone3,
+                &subvolumes,
+            );
+        });
+
         dialog.present();
     }
 
+    /// Roll back a package transaction by restoring the snapshot taken before it
+    ///
+    /// Goes straight to the restore preview, which lists what the transaction
+    /// changed, and its confirmation.
+    fn undo_transaction(
+        window: &adw::ApplicationWindow,
+        manager: &Rc<RefCell<SnapshotManager>>,
+        snapshot_id: &str,
+    ) {
+        let snapshots = match manager.borrow().load_snapshots() {
+            Ok(snapshots) => snapshots,
+            Err(e) => {
+                dialogs::show_error(window, "Error", &format!("Failed to load snapshot: {e}"));
+                return;
+            }
+        };
+        let Some(snapshot) = snapshots.iter().find(|s| s.id == snapshot_id) else {
+            dialogs::show_error(window, "Not Found", "Snapshot not found");
+            return;
+        };
+        let Some(pre_name) = snapshot
+            .transaction
+            .as_ref()
+            .and_then(|link| link.pre_snapshot(&snapshot.name))
+        else {
+            return;
+        };
+
+        if !snapshots.iter().any(|s| s.name == pre_name) {
+            dialogs::show_error(
+                window,
+                &gettext("Cannot Undo Transaction"),
+                &gettext_f(
+                    "The snapshot taken before this transaction, {name}, no longer exists.",
+                    &[("name", pre_name)],
+                ),
+            );
+            return;
+        }
+
+        Self::perform_full_restore(window, pre_name);
+    }
+
     fn perform_full_restore(window: &adw::ApplicationWindow, snapshot_basename: &str) {
         let window_clone = window.clone();
         let snapshot_id_owned = snapshot_basename.to_string();
@@ -3320,8 +5580,8 @@ impl MainWindow {
             let name = snapshot_name.clone();
             let name_for_notification = snapshot_name.clone();
 
-            // Show loading state
-            dialogs::show_toast(&window, "Restoring snapshot...");
+            // Show loading state, with a way to abandon the password prompt
+            let progress_toast = Self::show_authorization_toast(&window, "Restoring snapshot...");
 
             // Create channel for thread communication
             let (sender, receiver) = mpsc::channel();
@@ -3345,72 +5605,113 @@ impl MainWindow {
                 let _ = sender.send((Some(result), None));
             });
 
-            // Receive results on main thread
-            glib::source::idle_add_local_once(move || {
-                if let Ok(msg) = receiver.recv() {
-                        let (result_opt, error_opt) = msg;
-
-                        // Handle connection error
-                        if let Some((title, error)) = error_opt {
-                            dialogs::show_error(&window, &title, &error);
-                            return;
+            // Receive results on main thread without blocking it, so the toast stays clickable
+            glib::spawn_future_local(async move {
+                let received = loop {
+                    match receiver.try_recv() {
+                        Ok(msg) => break Some(msg),
+                        Err(mpsc::TryRecvError::Empty) => {
+                            glib::timeout_future(std::time::Duration::from_millis(100)).await;
                         }
+                        Err(mpsc::TryRecvError::Disconnected) => break None,
+                    }
+                };
 
-                        // Handle restore result
-                        if let Some(result) = result_opt {
-                            match result {
-                                Ok((true, message)) => {
-                                    // Send desktop notification
-                                    if let Some(app) = window.application() {
-                                        notifications::notify_snapshot_restored(&app, &name_for_notification);
-                                    }
+                if let Some(toast) = &progress_toast {
+                    toast.dismiss();
+                }
 
-                                    // Show success message with reboot instructions
-                                    let success_dialog = adw::MessageDialog::new(
-                                        Some(&window),
-                                        Some("Rollback Successful"),
-                                        Some(&format!(
-                                            "{message}\n\n\
-                                            You MUST reboot for the changes to take effect.\n\n\
-                                            After reboot, your system will be restored to the snapshot state.\n\n\
-                                            Reboot now?"
-                                        )),
-                                    );
+                if let Some(msg) = received {
+                    let (result_opt, error_opt) = msg;
 
-                                    success_dialog.add_response("later", "Reboot Later");
-                                    success_dialog.add_response("now", "Reboot Now");
-                                    success_dialog.set_response_appearance("now", adw::ResponseAppearance::Suggested);
-                                    success_dialog.set_default_response(Some("now"));
-                                    success_dialog.set_close_response("later");
-
-                                    success_dialog.connect_response(None, |_, response| {
-                                        if response == "now" {
-                                            // Attempt to reboot
-                                            let _ = std::process::Command::new("reboot")
-                                                .spawn();
-                                        }
-                                    });
+                    // Handle connection error
+                    if let Some((title, error)) = error_opt {
+                        dialogs::show_error(&window, &title, &error);
+                        return;
+                    }
 
-                                    success_dialog.present();
-                                }
-                                Ok((false, message)) => {
-                                    error_helpers::show_error_with_context(
-                                        &window,
-                                        error_helpers::ErrorContext::SnapshotRestore,
-                                        &message
-                                    );
-                                }
-                                Err(e) => {
-                                    error_helpers::show_error_with_context(
-                                        &window,
-                                        error_helpers::ErrorContext::SnapshotRestore,
-                                        &e.to_string()
+                    // Handle restore result
+                    if let Some(result) = result_opt {
+                        match result {
+                            Ok((true, message)) => {
+                                activity_log::record(
+                                    ActivityKind::SnapshotRestored,
+                                    &name_for_notification,
+                                    true,
+                                    None,
+                                );
+
+                                // Send desktop notification
+                                if let Some(app) = window.application() {
+                                    notifications::notify_snapshot_restored(
+                                        &app,
+                                        &name_for_notification,
                                     );
                                 }
+
+                                // Show success message with reboot instructions
+                                let success_dialog = adw::MessageDialog::new(
+                                    Some(&window),
+                                    Some("Rollback Successful"),
+                                    Some(&format!(
+                                        "{message}\n\n\
+                                        You MUST reboot for the changes to take effect.\n\n\
+                                        After reboot, your system will be restored to the snapshot state.\n\n\
+                                        Reboot now?"
+                                    )),
+                                );
+
+                                success_dialog.add_response("later", "Reboot Later");
+                                success_dialog.add_response("now", "Reboot Now");
+                                success_dialog.set_response_appearance(
+                                    "now",
+                                    adw::ResponseAppearance::Suggested,
+                                );
+                                success_dialog.set_default_response(Some("now"));
+                                success_dialog.set_close_response("later");
+
+                                success_dialog.connect_response(None, |_, response| {
+                                    if response == "now" {
+                                        // Attempt to reboot
+                                        let _ = std::process::Command::new("reboot").spawn();
+                                    }
+                                });
+
+                                success_dialog.present();
+                            }
+                            Ok((false, message)) if Self::is_cancelled(&message) => {
+                                dialogs::show_toast(&window, "Restore cancelled");
+                            }
+                            Ok((false, message)) => {
+                                activity_log::record(
+                                    ActivityKind::SnapshotRestored,
+                                    &name_for_notification,
+                                    false,
+                                    Some(waypoint_common::HelperError::decode(&message).message),
+                                );
+                                error_helpers::show_error_with_context(
+                                    &window,
+                                    error_helpers::ErrorContext::SnapshotRestore,
+                                    &message,
+                                );
+                            }
+                            Err(e) => {
+                                activity_log::record(
+                                    ActivityKind::SnapshotRestored,
+                                    &name_for_notification,
+                                    false,
+                                    Some(e.to_string()),
+                                );
+                                error_helpers::show_error_with_context(
+                                    &window,
+                                    error_helpers::ErrorContext::SnapshotRestore,
+                                    &e.to_string(),
+                                );
                             }
                         }
                     }
-                });
+                }
+            });
         });
 
         dialog.present();
@@ -3420,8 +5721,9 @@ impl MainWindow {
     fn show_compare_dialog(
         window: &adw::ApplicationWindow,
         manager: &Rc<RefCell<SnapshotManager>>,
+        user_prefs_manager: &Rc<RefCell<UserPreferencesManager>>,
     ) {
-        comparison_dialog::show_compare_dialog(window, manager);
+        comparison_dialog::show_compare_dialog(window, manager, user_prefs_manager);
     }
 
     /// Show preferences dialog
@@ -3432,10 +5734,60 @@ impl MainWindow {
         preferences_window::show_preferences_window(window, backup_manager.clone());
     }
 
+    /// Check the snapshot metadata in the background and offer to review problems
+    fn check_metadata_at_startup(window: &adw::ApplicationWindow, on_changed: Rc<dyn Fn()>) {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result =
+                WaypointHelperClient::new().and_then(|client| client.check_snapshot_metadata());
+            let _ = tx.send(result);
+        });
+
+        let window = window.clone();
+        glib::spawn_future_local(async move {
+            let result = loop {
+                match rx.try_recv() {
+                    Ok(result) => break result,
+                    Err(mpsc::TryRecvError::Empty) => {
+                        glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                    }
+                    Err(mpsc::TryRecvError::Disconnected) => return,
+                }
+            };
+
+            let count = match result {
+                Ok(conflicts) if !conflicts.is_empty() => conflicts.len(),
+                Ok(_) => return,
+                Err(e) => {
+                    log::warn!("Failed to check snapshot metadata: {e}");
+                    return;
+                }
+            };
+            let window_for_review = window.clone();
+            dialogs::show_toast_with_button(
+                &window,
+                &ngettext_f(
+                    "Found {count} problem in the snapshot metadata",
+                    "Found {count} problems in the snapshot metadata",
+                    count as u64,
+                    &[("count", &count.to_string())],
+                ),
+                &gettext("Review"),
+                move || {
+                    metadata_check_dialog::show_metadata_check_dialog(
+                        &window_for_review,
+                        on_changed.clone(),
+                    );
+                },
+            );
+        });
+    }
+
     /// Show analytics dialog
     fn show_analytics_dialog(
         window: &adw::ApplicationWindow,
         snapshot_manager: &std::rc::Rc<std::cell::RefCell<SnapshotManager>>,
+        on_metadata_changed: Rc<dyn Fn()>,
     ) {
         // Load snapshots
         let snapshots = match snapshot_manager.borrow().load_snapshots() {
@@ -3445,10 +5797,48 @@ impl MainWindow {
                 Vec::new()
             }
         };
-        analytics_dialog::show_analytics_dialog(window, &snapshots, snapshot_manager);
+        analytics_dialog::show_analytics_dialog(
+            window,
+            &snapshots,
+            snapshot_manager,
+            on_metadata_changed,
+        );
     }
 
 
+    /// Explain a snapshot refused for lack of metadata space and offer the balance action
+    fn show_metadata_full_dialog(
+        window: &adw::ApplicationWindow,
+        snapshot_manager: &Rc<RefCell<SnapshotManager>>,
+        details: &str,
+        on_metadata_changed: Rc<dyn Fn()>,
+    ) {
+        let dialog = adw::MessageDialog::new(
+            Some(window),
+            Some(&gettext("Filesystem Metadata Is Full")),
+            Some(&gettext_f(
+                "Btrfs has no room left for metadata, so no snapshot can be created even though \
+                free space is reported. A balance returns partly used space so metadata can \
+                grow again.\n\n{details}",
+                &[("details", details)],
+            )),
+        );
+        dialog.add_response("close", &gettext("Close"));
+        if crate::dbus_client::helper_supports(FEATURE_BALANCE) {
+            dialog.add_response("balance", &gettext("Open Maintenance"));
+            dialog.set_response_appearance("balance", adw::ResponseAppearance::Suggested);
+            dialog.set_default_response(Some("balance"));
+        }
+        dialog.set_close_response("close");
+
+        let window = window.clone();
+        let snapshot_manager = snapshot_manager.clone();
+        dialog.connect_response(Some("balance"), move |_, _| {
+            Self::show_analytics_dialog(&window, &snapshot_manager, on_metadata_changed.clone());
+        });
+        dialog.present();
+    }
+
     fn show_about_dialog(window: &adw::ApplicationWindow) {
         about_preferences::show_about_dialog(window);
     }
@@ -0,0 +1,209 @@
+//! Notification preferences UI
+
+use adw::prelude::*;
+use libadwaita as adw;
+
+use super::dialogs;
+use super::notifications::{self, NotificationSettings};
+use crate::i18n::{gettext, gettext_f, gettext_noop};
+
+/// A notification toggle: (title, subtitle, getter, setter)
+type Toggle = (
+    &'static str,
+    &'static str,
+    fn(&NotificationSettings) -> bool,
+    fn(&mut NotificationSettings, bool),
+);
+
+const SNAPSHOT_TOGGLES: &[Toggle] = &[
+    (
+        gettext_noop("Manual Snapshots"),
+        gettext_noop("When a restore point you created has been saved"),
+        |s| s.snapshot_created,
+        |s, v| s.snapshot_created = v,
+    ),
+    (
+        gettext_noop("Scheduled Snapshots"),
+        gettext_noop("Every snapshot created by the scheduler"),
+        |s| s.scheduled_snapshot,
+        |s, v| s.scheduled_snapshot = v,
+    ),
+    (
+        gettext_noop("Deleted Snapshots"),
+        gettext_noop("When a snapshot has been deleted"),
+        |s| s.snapshot_deleted,
+        |s, v| s.snapshot_deleted = v,
+    ),
+    (
+        gettext_noop("Retention Cleanup"),
+        gettext_noop("When the retention policy removes old snapshots"),
+        |s| s.retention_cleanup,
+        |s, v| s.retention_cleanup = v,
+    ),
+];
+
+const BACKUP_TOGGLES: &[Toggle] = &[
+    (
+        gettext_noop("Backup Started"),
+        gettext_noop("When pending backups start on a connected drive"),
+        |s| s.backup_started,
+        |s, v| s.backup_started = v,
+    ),
+    (
+        gettext_noop("Backup Completed"),
+        gettext_noop("When all pending backups finished successfully"),
+        |s| s.backup_completed,
+        |s, v| s.backup_completed = v,
+    ),
+    (
+        gettext_noop("Backup Failed"),
+        gettext_noop("When some or all backups to a drive failed"),
+        |s| s.backup_failed,
+        |s, v| s.backup_failed = v,
+    ),
+];
+
+const WARNING_TOGGLES: &[Toggle] = &[
+    (
+        gettext_noop("Low Disk Space"),
+        gettext_noop("When the snapshot filesystem reaches the critical threshold below"),
+        |s| s.low_space,
+        |s, v| s.low_space = v,
+    ),
+    (
+        gettext_noop("Large Snapshots"),
+        gettext_noop("When a new snapshot exceeds the size warning threshold"),
+        |s| s.size_warning,
+        |s, v| s.size_warning = v,
+    ),
+];
+
+/// Create the notification preferences page
+pub fn create_notification_page(parent: &adw::ApplicationWindow) -> adw::PreferencesPage {
+    let page = adw::PreferencesPage::new();
+    page.set_title(&gettext("Notifications"));
+    page.set_icon_name(Some("preferences-system-notifications-symbolic"));
+
+    let settings = notifications::load_settings();
+
+    for (title, description, toggles) in [
+        (
+            gettext("Snapshots"),
+            Some(gettext("Mute routine events such as hourly snapshots")),
+            SNAPSHOT_TOGGLES,
+        ),
+        (gettext("Backups"), None, BACKUP_TOGGLES),
+        (gettext("Warnings"), None, WARNING_TOGGLES),
+    ] {
+        let group = adw::PreferencesGroup::new();
+        group.set_title(&title);
+        group.set_description(description.as_deref());
+
+        for &(row_title, subtitle, get, set) in toggles {
+            let row = adw::SwitchRow::new();
+            row.set_title(&gettext(row_title));
+            row.set_subtitle(&gettext(subtitle));
+            row.set_active(get(&settings));
+
+            let parent_clone = parent.clone();
+            row.connect_active_notify(move |row| {
+                let mut settings = notifications::load_settings();
+                set(&mut settings, row.is_active());
+
+                if let Err(e) = notifications::save_settings(&settings) {
+                    log::error!("Failed to save notification settings: {e}");
+                    dialogs::show_error(
+                        &parent_clone,
+                        &gettext("Save Failed"),
+                        &gettext_f(
+                            "Failed to save notification preferences: {error}",
+                            &[("error", &e.to_string())],
+                        ),
+                    );
+                }
+            });
+
+            group.add(&row);
+        }
+
+        page.add(&group);
+    }
+
+    page.add(&create_disk_space_group(parent, &settings));
+
+    let restore_note = adw::PreferencesGroup::new();
+    restore_note.set_description(Some(&gettext(
+        "Notifications about restores are always shown because they require a reboot.",
+    )));
+    page.add(&restore_note);
+
+    page
+}
+
+/// A threshold of the disk space group: (title, subtitle, getter, setter)
+type Threshold = (
+    &'static str,
+    &'static str,
+    fn(&NotificationSettings) -> f64,
+    fn(&mut NotificationSettings, f64),
+);
+
+const SPACE_THRESHOLDS: &[Threshold] = &[
+    (
+        gettext_noop("Warning Threshold"),
+        gettext_noop("Percent used at which disk space is shown in yellow"),
+        |s| s.space_warning_percent,
+        |s, v| s.space_warning_percent = v,
+    ),
+    (
+        gettext_noop("Critical Threshold"),
+        gettext_noop("Percent used at which disk space is shown in red and you are notified"),
+        |s| s.space_critical_percent,
+        |s, v| s.space_critical_percent = v,
+    ),
+];
+
+/// Thresholds for coloring disk space and the low space notification
+///
+/// Tighten them on huge filesystems, where 10% free is still a lot of room,
+/// or relax them on small ones.
+fn create_disk_space_group(
+    parent: &adw::ApplicationWindow,
+    settings: &NotificationSettings,
+) -> adw::PreferencesGroup {
+    let group = adw::PreferencesGroup::new();
+    group.set_title(&gettext("Disk Space"));
+    group.set_description(Some(&gettext(
+        "When the snapshot filesystem counts as running low",
+    )));
+
+    for &(title, subtitle, get, set) in SPACE_THRESHOLDS {
+        let row = adw::SpinRow::with_range(1.0, 100.0, 1.0);
+        row.set_title(&gettext(title));
+        row.set_subtitle(&gettext(subtitle));
+        row.set_digits(0);
+        row.set_value(get(settings));
+
+        let parent_clone = parent.clone();
+        row.connect_value_notify(move |row| {
+            let mut settings = notifications::load_settings();
+            set(&mut settings, row.value());
+
+            if let Err(e) = notifications::save_settings(&settings) {
+                log::error!("Failed to save disk space thresholds: {e}");
+                dialogs::show_error(
+                    &parent_clone,
+                    &gettext("Save Failed"),
+                    &gettext_f(
+                        "Failed to save disk space thresholds: {error}",
+                        &[("error", &e.to_string())],
+                    ),
+                );
+            }
+        });
+
+        group.add(&row);
+    }
+
+    group
+}
@@ -1,5 +1,121 @@
 use gio::prelude::*;
 use gtk::Application;
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::path::PathBuf;
+
+use crate::i18n::{gettext, gettext_f};
+
+thread_local! {
+    // Whether a low space warning was already sent for the current low-space episode
+    static LOW_SPACE_WARNED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Which events produce desktop notifications
+///
+/// Restores always notify, since they need a reboot to take effect.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    /// Manually created snapshots
+    pub snapshot_created: bool,
+    /// Snapshots created by the scheduler
+    pub scheduled_snapshot: bool,
+    /// Deleted snapshots
+    pub snapshot_deleted: bool,
+    /// New snapshots larger than the size warning threshold
+    pub size_warning: bool,
+    /// Backups starting when a destination is connected
+    pub backup_started: bool,
+    /// Backups that completed without errors
+    pub backup_completed: bool,
+    /// Backups that failed completely or partially
+    pub backup_failed: bool,
+    /// Snapshots removed by the retention policy
+    pub retention_cleanup: bool,
+    /// The snapshot filesystem running low on free space
+    pub low_space: bool,
+    /// Percentage of the snapshot filesystem in use at which disk space is shown as a warning
+    pub space_warning_percent: f64,
+    /// Percentage in use at which disk space is shown as critical and a low space
+    /// notification is sent
+    pub space_critical_percent: f64,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            snapshot_created: true,
+            scheduled_snapshot: true,
+            snapshot_deleted: true,
+            size_warning: true,
+            backup_started: true,
+            backup_completed: true,
+            backup_failed: true,
+            retention_cleanup: true,
+            low_space: true,
+            space_warning_percent: 80.0,
+            space_critical_percent: 90.0,
+        }
+    }
+}
+
+impl NotificationSettings {
+    /// Warning and critical percentages used, kept in range and in order
+    pub fn space_thresholds(&self) -> (f64, f64) {
+        let critical = self.space_critical_percent.clamp(1.0, 100.0);
+        let warning = self.space_warning_percent.clamp(1.0, critical);
+        (warning, critical)
+    }
+}
+
+/// Path of the notification settings file
+fn settings_path() -> PathBuf {
+    dirs::config_local_dir()
+        .map(|d| d.join("waypoint").join("notifications.json"))
+        .unwrap_or_else(|| PathBuf::from("/tmp/waypoint-notifications.json"))
+}
+
+/// Load notification settings, falling back to defaults
+pub fn load_settings() -> NotificationSettings {
+    let config_path = settings_path();
+
+    if !config_path.exists() {
+        return NotificationSettings::default();
+    }
+
+    match std::fs::read_to_string(&config_path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::error!("Failed to parse notification settings: {e}");
+            NotificationSettings::default()
+        }),
+        Err(e) => {
+            log::error!("Failed to read notification settings: {e}");
+            NotificationSettings::default()
+        }
+    }
+}
+
+/// Save notification settings to disk
+pub fn save_settings(settings: &NotificationSettings) -> anyhow::Result<()> {
+    let config_path = settings_path();
+
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let content = serde_json::to_string_pretty(settings)?;
+    std::fs::write(&config_path, content)?;
+
+    Ok(())
+}
+
+/// App action opening a snapshot in the file manager; the target is the snapshot name
+pub const ACTION_BROWSE_SNAPSHOT: &str = "browse-snapshot";
+/// App action backing up a snapshot; the target is the snapshot name
+pub const ACTION_BACKUP_SNAPSHOT: &str = "backup-snapshot";
+/// App action showing a snapshot in the main window; the target is the snapshot name
+pub const ACTION_SHOW_SNAPSHOT: &str = "show-snapshot";
 
 /// Priority levels for notifications
 #[derive(Debug, Clone, Copy)]
@@ -35,6 +151,15 @@ pub fn send_notification(
     body: &str,
     priority: NotificationPriority,
 ) {
+    app.send_notification(None, &build_notification(title, body, priority));
+}
+
+/// Create a notification with the application icon
+fn build_notification(
+    title: &str,
+    body: &str,
+    priority: NotificationPriority,
+) -> gio::Notification {
     let notification = gio::Notification::new(title);
     notification.set_body(Some(body));
     notification.set_priority(priority.to_gio_priority());
@@ -43,21 +168,87 @@ pub fn send_notification(
     let icon = gio::ThemedIcon::new("waypoint");
     notification.set_icon(&icon);
 
+    notification
+}
+
+/// Send a notification about a new snapshot, with buttons acting on it
+///
+/// The buttons activate the `ACTION_*_SNAPSHOT` app actions; clicking the
+/// notification itself shows the snapshot.
+fn send_snapshot_notification(
+    app: &Application,
+    title: &str,
+    body: &str,
+    priority: NotificationPriority,
+    snapshot_name: &str,
+) {
+    let notification = build_notification(title, body, priority);
+    let target = snapshot_name.to_variant();
+    notification.add_button_with_target_value(
+        "Browse",
+        &format!("app.{ACTION_BROWSE_SNAPSHOT}"),
+        Some(&target),
+    );
+    notification.add_button_with_target_value(
+        "Back Up Now",
+        &format!("app.{ACTION_BACKUP_SNAPSHOT}"),
+        Some(&target),
+    );
+    notification.add_button_with_target_value(
+        "View Details",
+        &format!("app.{ACTION_SHOW_SNAPSHOT}"),
+        Some(&target),
+    );
+    notification
+        .set_default_action_and_target_value(&format!("app.{ACTION_SHOW_SNAPSHOT}"), Some(&target));
+
     app.send_notification(None, &notification);
 }
 
 /// Send a notification about successful snapshot creation
 pub fn notify_snapshot_created(app: &Application, snapshot_name: &str) {
-    send_notification(
+    if !load_settings().snapshot_created {
+        return;
+    }
+
+    send_snapshot_notification(
         app,
         "Snapshot Created",
         &format!("Successfully created snapshot '{snapshot_name}'"),
         NotificationPriority::Normal,
+        snapshot_name,
+    );
+}
+
+/// Send a notification when a new snapshot exceeds the size warning threshold
+pub fn notify_snapshot_size_warning(
+    app: &Application,
+    snapshot_name: &str,
+    size_bytes: u64,
+    threshold_bytes: u64,
+) {
+    if !load_settings().size_warning {
+        return;
+    }
+
+    send_notification(
+        app,
+        "Large Snapshot",
+        &format!(
+            "Snapshot '{snapshot_name}' uses {} of exclusive space, more than the {} warning threshold",
+            crate::snapshot::format_bytes(size_bytes),
+            crate::snapshot::format_bytes(threshold_bytes)
+        ),
+        NotificationPriority::High,
     );
 }
 
 /// Send a notification about successful snapshot deletion
 pub fn notify_snapshot_deleted(app: &Application, snapshot_name: &str) {
+    if !load_settings().snapshot_deleted {
+        return;
+    }
+
     send_notification(
         app,
         "Snapshot Deleted",
@@ -81,6 +272,10 @@ pub fn notify_snapshot_restored(app: &Application, snapshot_name: &str) {
 /// Send a notification about retention policy cleanup
 #[allow(dead_code)]
 pub fn notify_retention_cleanup(app: &Application, count: usize) {
+    if !load_settings().retention_cleanup {
+        return;
+    }
+
     send_notification(
         app,
         "Snapshots Cleaned Up",
@@ -93,15 +288,39 @@ pub fn notify_retention_cleanup(app: &Application, count: usize) {
     );
 }
 
+/// Send a notification listing the snapshots quota auto-cleanup deleted
+pub fn notify_quota_cleanup(app: &Application, snapshot_names: &[String]) {
+    if !load_settings().retention_cleanup || snapshot_names.is_empty() {
+        return;
+    }
+
+    send_notification(
+        app,
+        "Snapshots Removed to Free Quota Space",
+        &format!(
+            "Quota cleanup deleted {} old snapshot{}: {}",
+            snapshot_names.len(),
+            if snapshot_names.len() == 1 { "" } else { "s" },
+            snapshot_names.join(", ")
+        ),
+        NotificationPriority::Normal,
+    );
+}
+
 /// Send a notification about scheduled snapshot creation
 pub fn notify_scheduled_snapshot(app: &Application, snapshot_name: &str) {
-    send_notification(
+    if !load_settings().scheduled_snapshot {
+        return;
+    }
+
+    send_snapshot_notification(
         app,
         "Scheduled Snapshot Created",
         &format!(
             "Automated snapshot '{snapshot_name}' created successfully"
         ),
         NotificationPriority::Low,
+        snapshot_name,
     );
 }
 
@@ -111,6 +330,10 @@ pub fn notify_backup_started(
     destination_label: &str,
     pending_count: usize,
 ) {
+    if !load_settings().backup_started {
+        return;
+    }
+
     let message = if pending_count == 1 {
         format!("Starting backup of 1 snapshot to {destination_label}")
     } else {
@@ -131,6 +354,16 @@ pub fn notify_backup_completed(
     success_count: usize,
     failed_count: usize,
 ) {
+    let settings = load_settings();
+    let enabled = if failed_count == 0 {
+        settings.backup_completed
+    } else {
+        settings.backup_failed
+    };
+    if !enabled {
+        return;
+    }
+
     if failed_count == 0 {
         let message = if success_count == 1 {
             format!("Backed up 1 snapshot to {destination_label}")
@@ -168,3 +401,92 @@ pub fn notify_backup_completed(
         );
     }
 }
+
+/// Send a notification about backups that failed verification after transfer
+pub fn notify_backup_verification_failed(
+    app: &Application,
+    destination_label: &str,
+    snapshot_names: &[String],
+) {
+    if !load_settings().backup_failed {
+        return;
+    }
+
+    let message = if let [name] = snapshot_names {
+        format!("The backup of {name} on {destination_label} doesn't match the snapshot. Back it up again or check the drive.")
+    } else {
+        format!(
+            "{} backups on {destination_label} don't match their snapshots. Back them up again or check the drive.",
+            snapshot_names.len()
+        )
+    };
+    send_notification(
+        app,
+        "Backup Verification Failed",
+        &message,
+        NotificationPriority::High,
+    );
+}
+
+/// Send a notification about backups left queued because the destination is too full
+pub fn notify_backup_destination_full(app: &Application, destination_label: &str, reason: &str) {
+    if !load_settings().backup_failed {
+        return;
+    }
+
+    send_notification(
+        app,
+        &gettext("Backup Drive Full"),
+        &gettext_f(
+            "{reason}. Backups to {drive} stay queued until there is room.",
+            &[("reason", reason), ("drive", destination_label)],
+        ),
+        NotificationPriority::High,
+    );
+}
+
+/// Warn once when free space on the snapshot filesystem drops below the threshold
+///
+/// The warning re-arms after space recovers, so each low-space episode produces a
+/// single notification rather than one per snapshot.
+pub fn check_low_space(app: &Application) {
+    let space_path = crate::btrfs::snapshot_space_path();
+    let Ok(stats) = crate::btrfs::get_filesystem_stats(&space_path) else {
+        return;
+    };
+    let (total, available) = (stats.total_bytes, stats.available_bytes);
+
+    if total == 0 {
+        return;
+    }
+
+    let (_, critical_percent) = load_settings().space_thresholds();
+    let percent_free = available as f64 / total as f64 * 100.0;
+    if 100.0 - percent_free < critical_percent {
+        LOW_SPACE_WARNED.with(|warned| warned.set(false));
+        return;
+    }
+
+    if LOW_SPACE_WARNED.with(|warned| warned.replace(true)) {
+        return;
+    }
+
+    notify_low_space(app, available, percent_free);
+}
+
+/// Send a notification that the snapshot filesystem is running low on space
+pub fn notify_low_space(app: &Application, available_bytes: u64, percent_free: f64) {
+    if !load_settings().low_space {
+        return;
+    }
+
+    send_notification(
+        app,
+        "Low Disk Space",
+        &format!(
+            "Only {} ({percent_free:.0}%) free for snapshots. Delete old snapshots or adjust the retention policy.",
+            crate::snapshot::format_bytes(available_bytes)
+        ),
+        NotificationPriority::High,
+    );
+}
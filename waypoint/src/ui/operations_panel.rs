@@ -0,0 +1,234 @@
+//! Header bar button listing long-running operations
+//!
+//! Shows everything tracked in `crate::operations` with progress bars, cancel
+//! buttons and the outcome of recently finished operations. The button spins
+//! while anything is running, so users can tell at a glance.
+
+use gtk::prelude::*;
+use gtk::{Orientation, glib};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::i18n::{gettext, ngettext_f};
+use crate::operations::{self, Operation, OperationId, OperationState};
+
+/// Widgets of one operation row, updated in place as progress arrives
+struct OperationRow {
+    row: gtk::ListBoxRow,
+    status: gtk::Label,
+    progress: gtk::ProgressBar,
+    spinner: gtk::Spinner,
+    state_icon: gtk::Image,
+    cancel_btn: gtk::Button,
+}
+
+impl OperationRow {
+    fn new(op: &Operation) -> Self {
+        let content = gtk::Box::new(Orientation::Vertical, 6);
+        content.set_margin_top(8);
+        content.set_margin_bottom(8);
+        content.set_margin_start(12);
+        content.set_margin_end(12);
+
+        let top = gtk::Box::new(Orientation::Horizontal, 12);
+        top.append(&gtk::Image::from_icon_name(op.kind.icon_name()));
+
+        let labels = gtk::Box::new(Orientation::Vertical, 2);
+        labels.set_hexpand(true);
+        let title = gtk::Label::new(Some(&op.title));
+        title.set_xalign(0.0);
+        title.set_ellipsize(gtk::pango::EllipsizeMode::Middle);
+        labels.append(&title);
+        let status = gtk::Label::new(None);
+        status.set_xalign(0.0);
+        status.set_wrap(true);
+        status.add_css_class("dim-label");
+        status.add_css_class("caption");
+        labels.append(&status);
+        top.append(&labels);
+
+        let spinner = gtk::Spinner::new();
+        spinner.set_valign(gtk::Align::Center);
+        top.append(&spinner);
+
+        let state_icon = gtk::Image::new();
+        state_icon.set_valign(gtk::Align::Center);
+        top.append(&state_icon);
+
+        let cancel_btn = gtk::Button::from_icon_name("process-stop-symbolic");
+        cancel_btn.set_valign(gtk::Align::Center);
+        cancel_btn.add_css_class("flat");
+        cancel_btn.set_tooltip_text(Some(&gettext("Cancel")));
+        let id = op.id;
+        cancel_btn.connect_clicked(move |_| operations::cancel(id));
+        top.append(&cancel_btn);
+
+        content.append(&top);
+
+        let progress = gtk::ProgressBar::new();
+        content.append(&progress);
+
+        let row = gtk::ListBoxRow::new();
+        row.set_activatable(false);
+        row.set_child(Some(&content));
+
+        let widgets = Self {
+            row,
+            status,
+            progress,
+            spinner,
+            state_icon,
+            cancel_btn,
+        };
+        widgets.update(op);
+        widgets
+    }
+
+    fn update(&self, op: &Operation) {
+        self.status.set_text(&op.status);
+        self.status.set_visible(!op.status.is_empty());
+
+        let running = op.is_running();
+        match op.fraction.filter(|_| running) {
+            Some(fraction) => {
+                self.progress.set_fraction(fraction);
+                self.progress.set_visible(true);
+            }
+            None => self.progress.set_visible(false),
+        }
+        // Without a measurable fraction, the spinner shows it's still alive
+        let busy = op.state == OperationState::Running && op.fraction.is_none();
+        self.spinner.set_visible(busy);
+        self.spinner.set_spinning(busy);
+        self.cancel_btn.set_visible(op.cancel.is_some());
+
+        let (icon_name, css_class) = match op.state {
+            OperationState::Queued | OperationState::Running => ("", ""),
+            OperationState::Succeeded => ("emblem-ok-symbolic", "success"),
+            OperationState::Failed => ("dialog-error-symbolic", "error"),
+            OperationState::Cancelled => ("process-stop-symbolic", "dim-label"),
+        };
+        self.state_icon.set_visible(!running);
+        if !running {
+            self.state_icon.set_icon_name(Some(icon_name));
+            self.state_icon.add_css_class(css_class);
+        }
+    }
+}
+
+/// Create the header bar button with the operations popover
+pub fn create_operations_button() -> gtk::MenuButton {
+    let button_icon = gtk::Image::from_icon_name("emblem-synchronizing-symbolic");
+    let button_spinner = gtk::Spinner::new();
+    let button_content = gtk::Box::new(Orientation::Horizontal, 0);
+    button_content.append(&button_icon);
+    button_content.append(&button_spinner);
+
+    let button = gtk::MenuButton::new();
+    button.set_child(Some(&button_content));
+    button.update_property(&[gtk::accessible::Property::Label(&gettext("Operations"))]);
+
+    let popover_box = gtk::Box::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(12)
+        .margin_top(12)
+        .margin_bottom(12)
+        .margin_start(12)
+        .margin_end(12)
+        .width_request(360)
+        .build();
+
+    let header = gtk::Box::new(Orientation::Horizontal, 12);
+    let heading = gtk::Label::new(Some(&gettext("Operations")));
+    heading.add_css_class("heading");
+    heading.set_xalign(0.0);
+    heading.set_hexpand(true);
+    header.append(&heading);
+    let clear_btn = gtk::Button::with_label(&gettext("Clear Finished"));
+    clear_btn.add_css_class("flat");
+    clear_btn.connect_clicked(|_| operations::clear_finished());
+    header.append(&clear_btn);
+    popover_box.append(&header);
+
+    let list = gtk::ListBox::new();
+    list.set_selection_mode(gtk::SelectionMode::None);
+    list.add_css_class("boxed-list");
+    let placeholder = gtk::Label::new(Some(&gettext("Nothing is running")));
+    placeholder.add_css_class("dim-label");
+    placeholder.set_margin_top(24);
+    placeholder.set_margin_bottom(24);
+    list.set_placeholder(Some(&placeholder));
+
+    let scrolled = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .max_content_height(420)
+        .propagate_natural_height(true)
+        .child(&list)
+        .build();
+    popover_box.append(&scrolled);
+
+    let popover = gtk::Popover::new();
+    popover.set_child(Some(&popover_box));
+    button.set_popover(Some(&popover));
+
+    let rows: Rc<RefCell<HashMap<OperationId, OperationRow>>> = Rc::default();
+    let button_for_refresh = button.clone();
+    let refresh = move || {
+        let ops = operations::operations();
+        let mut rows = rows.borrow_mut();
+
+        rows.retain(|id, widgets| {
+            let keep = ops.iter().any(|op| op.id == *id);
+            if !keep {
+                list.remove(&widgets.row);
+            }
+            keep
+        });
+        // Operations are newest first; new ones go to the top
+        for op in ops.iter().rev() {
+            match rows.get(&op.id) {
+                Some(widgets) => widgets.update(op),
+                None => {
+                    let widgets = OperationRow::new(op);
+                    list.prepend(&widgets.row);
+                    rows.insert(op.id, widgets);
+                }
+            }
+        }
+
+        let running = operations::running_count();
+        button_icon.set_visible(running == 0);
+        button_spinner.set_visible(running > 0);
+        button_spinner.set_spinning(running > 0);
+        clear_btn.set_sensitive(ops.iter().any(|op| !op.is_running()));
+        button_for_refresh.set_tooltip_text(Some(&if running > 0 {
+            ngettext_f(
+                "{count} operation running",
+                "{count} operations running",
+                running as u64,
+                &[("count", &running.to_string())],
+            )
+        } else {
+            gettext("Operations")
+        }));
+    };
+    refresh();
+
+    // Listeners run inside tracker updates; redraw once control is back in the main loop
+    let refresh = Rc::new(refresh);
+    let pending = Rc::new(std::cell::Cell::new(false));
+    operations::connect_changed(move || {
+        if pending.replace(true) {
+            return;
+        }
+        let refresh = refresh.clone();
+        let pending = pending.clone();
+        glib::idle_add_local_once(move || {
+            pending.set(false);
+            refresh();
+        });
+    });
+
+    button
+}
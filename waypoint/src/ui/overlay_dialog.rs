@@ -0,0 +1,214 @@
+//! Ephemeral writable overlays of snapshots
+//!
+//! Lets power users run programs against an old system state without a rollback.
+//! The overlay lives as long as its window: closing the window unmounts it and
+//! discards every change. Overlays left behind by a crash are reaped by the helper.
+
+use adw::prelude::*;
+use gtk::prelude::*;
+use gtk::{Orientation, glib};
+use libadwaita as adw;
+use std::sync::mpsc;
+use waypoint_common::OverlayMount;
+
+use super::dialogs;
+use crate::dbus_client::WaypointHelperClient;
+use crate::i18n::{gettext, gettext_f};
+use crate::snapshot::Snapshot;
+
+/// Ask for confirmation, then mount a writable overlay of the snapshot
+pub fn mount_test_overlay(window: &adw::ApplicationWindow, snapshot: &Snapshot) {
+    // Prefer the root subvolume, which is what you'd run programs against
+    let subvolume = if snapshot.subvolumes.is_empty()
+        || snapshot.subvolumes.iter().any(|s| s.as_os_str() == "/")
+    {
+        "/".to_string()
+    } else {
+        snapshot.subvolumes[0].to_string_lossy().to_string()
+    };
+
+    let window_clone = window.clone();
+    let snapshot_name = snapshot.name.clone();
+    dialogs::show_confirmation(
+        window,
+        &gettext("Open Test Overlay?"),
+        &gettext_f(
+            "This mounts a temporary writable copy of the {subvolume} subvolume of '{name}'.\n\n\
+            Changes are kept in memory only and are discarded when the overlay is closed. \
+            The snapshot itself is never modified.",
+            &[("subvolume", &subvolume), ("name", &snapshot.name)],
+        ),
+        &gettext("Open Overlay"),
+        false,
+        move || mount_in_background(&window_clone, snapshot_name.clone(), subvolume.clone()),
+    );
+}
+
+fn mount_in_background(
+    window: &adw::ApplicationWindow,
+    snapshot_name: String,
+    subvolume: String,
+) {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let result = WaypointHelperClient::new()
+            .and_then(|client| client.mount_snapshot_overlay(&snapshot_name, &subvolume));
+        let _ = tx.send(result);
+    });
+
+    let window = window.clone();
+    glib::spawn_future_local(async move {
+        let result = loop {
+            match rx.try_recv() {
+                Ok(result) => break result,
+                Err(mpsc::TryRecvError::Empty) => {
+                    glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    break Err(anyhow::anyhow!("Overlay thread disconnected"));
+                }
+            }
+        };
+
+        match result {
+            Ok(overlay) => show_overlay_window(&window, overlay),
+            Err(e) => dialogs::show_error(
+                &window,
+                &gettext("Could Not Open Overlay"),
+                &gettext_f(
+                    "Failed to mount test overlay: {error}",
+                    &[("error", &e.to_string())],
+                ),
+            ),
+        }
+    });
+}
+
+/// Window that owns a mounted overlay; closing it unmounts the overlay
+fn show_overlay_window(parent: &adw::ApplicationWindow, overlay: OverlayMount) {
+    let dialog = adw::Window::new();
+    dialog.set_title(Some(&gettext("Test Overlay")));
+    dialog.set_default_size(520, -1);
+    dialog.set_transient_for(Some(parent));
+
+    let content = gtk::Box::new(Orientation::Vertical, 0);
+    content.append(&adw::HeaderBar::new());
+
+    let status = adw::StatusPage::new();
+    status.set_icon_name(Some("drive-harddisk-symbolic"));
+    status.set_title(&gettext("Ephemeral Overlay"));
+    status.set_description(Some(&gettext_f(
+        "A writable copy of '{name}' ({subvolume}) is mounted below.\n\
+        Everything written here is discarded when this window is closed.",
+        &[
+            ("name", &overlay.snapshot_name),
+            ("subvolume", &overlay.subvolume.to_string_lossy()),
+        ],
+    )));
+
+    let body = gtk::Box::new(Orientation::Vertical, 12);
+
+    let path_label = gtk::Label::new(Some(&overlay.mount_point.to_string_lossy()));
+    path_label.set_selectable(true);
+    path_label.add_css_class("monospace");
+    body.append(&path_label);
+
+    let button_box = gtk::Box::new(Orientation::Horizontal, 12);
+    button_box.set_halign(gtk::Align::Center);
+
+    let open_btn = gtk::Button::with_label(&gettext("Open Folder"));
+    open_btn.add_css_class("pill");
+    button_box.append(&open_btn);
+
+    let copy_btn = gtk::Button::with_label(&gettext("Copy Path"));
+    copy_btn.add_css_class("pill");
+    button_box.append(&copy_btn);
+
+    let discard_btn = gtk::Button::with_label(&gettext("Discard & Unmount"));
+    discard_btn.add_css_class("pill");
+    discard_btn.add_css_class("destructive-action");
+    button_box.append(&discard_btn);
+
+    body.append(&button_box);
+    status.set_child(Some(&body));
+    content.append(&status);
+    dialog.set_content(Some(&content));
+
+    let mount_point = overlay.mount_point.clone();
+    let dialog_clone = dialog.clone();
+    open_btn.connect_clicked(move |_| {
+        let file = gtk::gio::File::for_path(&mount_point);
+        let launcher = gtk::FileLauncher::new(Some(&file));
+        launcher.launch(Some(&dialog_clone), gtk::gio::Cancellable::NONE, |result| {
+            if let Err(e) = result {
+                log::warn!("Failed to open overlay folder: {e}");
+            }
+        });
+    });
+
+    let mount_point = overlay.mount_point.to_string_lossy().to_string();
+    copy_btn.connect_clicked(move |btn| {
+        btn.clipboard().set_text(&mount_point);
+    });
+
+    let dialog_clone = dialog.clone();
+    discard_btn.connect_clicked(move |_| dialog_clone.close());
+
+    // Closing the window is the only way out, so it always unmounts
+    let mount_point = overlay.mount_point.to_string_lossy().to_string();
+    let parent_clone = parent.clone();
+    dialog.connect_close_request(move |_| {
+        let mount_point = mount_point.clone();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = WaypointHelperClient::new()
+                .and_then(|client| client.unmount_snapshot_overlay(&mount_point));
+            let _ = tx.send(result);
+        });
+
+        let parent = parent_clone.clone();
+        glib::spawn_future_local(async move {
+            let result = loop {
+                match rx.try_recv() {
+                    Ok(result) => break result,
+                    Err(mpsc::TryRecvError::Empty) => {
+                        glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                    }
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        break Err(anyhow::anyhow!("Overlay thread disconnected"));
+                    }
+                }
+            };
+
+            match result {
+                Ok(_) => dialogs::show_toast(&parent, &gettext("Test overlay discarded")),
+                Err(e) => dialogs::show_error(
+                    &parent,
+                    &gettext("Could Not Unmount Overlay"),
+                    &gettext_f(
+                        "{error}\n\nThe overlay will be cleaned up automatically the next time Waypoint starts.",
+                        &[("error", &e.to_string())],
+                    ),
+                ),
+            }
+        });
+
+        glib::Propagation::Proceed
+    });
+
+    dialog.present();
+}
+
+/// Ask the helper to reap overlays left behind by a previous session
+pub fn reap_stale_overlays() {
+    std::thread::spawn(|| {
+        let result = WaypointHelperClient::new().and_then(|client| client.list_snapshot_overlays());
+        match result {
+            Ok(active) if !active.is_empty() => {
+                log::info!("{} snapshot overlay(s) still active", active.len());
+            }
+            Ok(_) => {}
+            Err(e) => log::debug!("Could not check for stale overlays: {e}"),
+        }
+    });
+}
@@ -4,6 +4,7 @@
 
 #![allow(dead_code)]
 
+use crate::i18n::{gettext, gettext_f, ngettext_f};
 use crate::packages::{Package, PackageDiff, diff_packages};
 use adw::prelude::*;
 use gtk::prelude::*;
@@ -40,7 +41,7 @@ pub fn show_package_diff_dialog(
     let diff_rc = Rc::new(diff);
 
     let dialog = adw::Window::new();
-    dialog.set_title(Some("Package Comparison"));
+    dialog.set_title(Some(&gettext("Package Comparison")));
     dialog.set_default_size(900, 750);
     dialog.set_modal(true);
     dialog.set_transient_for(Some(parent));
@@ -50,10 +51,13 @@ pub fn show_package_diff_dialog(
 
     // Header with export button
     let header = adw::HeaderBar::new();
-    header.set_title_widget(Some(&adw::WindowTitle::new("Package Comparison", "")));
+    header.set_title_widget(Some(&adw::WindowTitle::new(
+        &gettext("Package Comparison"),
+        "",
+    )));
 
     let export_btn = gtk::Button::from_icon_name("document-save-symbolic");
-    export_btn.set_tooltip_text(Some("Export to file"));
+    export_btn.set_tooltip_text(Some(&gettext("Export to file")));
     header.pack_end(&export_btn);
 
     content.append(&header);
@@ -98,22 +102,32 @@ pub fn show_package_diff_dialog(
     filter_box.set_halign(gtk::Align::Start);
 
     let total_changes = diff_rc.added.len() + diff_rc.removed.len() + diff_rc.updated.len();
-    let btn_all = gtk::ToggleButton::with_label(&format!("All ({total_changes})"));
+    let btn_all = gtk::ToggleButton::with_label(&gettext_f(
+        "All ({count})",
+        &[("count", &total_changes.to_string())],
+    ));
     btn_all.set_active(true);
     btn_all.add_css_class("pill");
     filter_box.append(&btn_all);
 
-    let btn_added = gtk::ToggleButton::with_label(&format!("Added ({})", diff_rc.added.len()));
+    let btn_added = gtk::ToggleButton::with_label(&gettext_f(
+        "Added ({count})",
+        &[("count", &diff_rc.added.len().to_string())],
+    ));
     btn_added.add_css_class("pill");
     filter_box.append(&btn_added);
 
-    let btn_removed =
-        gtk::ToggleButton::with_label(&format!("Removed ({})", diff_rc.removed.len()));
+    let btn_removed = gtk::ToggleButton::with_label(&gettext_f(
+        "Removed ({count})",
+        &[("count", &diff_rc.removed.len().to_string())],
+    ));
     btn_removed.add_css_class("pill");
     filter_box.append(&btn_removed);
 
-    let btn_updated =
-        gtk::ToggleButton::with_label(&format!("Updated ({})", diff_rc.updated.len()));
+    let btn_updated = gtk::ToggleButton::with_label(&gettext_f(
+        "Updated ({count})",
+        &[("count", &diff_rc.updated.len().to_string())],
+    ));
     btn_updated.add_css_class("pill");
     filter_box.append(&btn_updated);
 
@@ -121,7 +135,7 @@ pub fn show_package_diff_dialog(
 
     // Search entry - below filters
     let search_entry = gtk::SearchEntry::new();
-    search_entry.set_placeholder_text(Some("Search packages..."));
+    search_entry.set_placeholder_text(Some(&gettext("Search packages...")));
     search_entry.set_margin_top(6);
     controls_box.append(&search_entry);
 
@@ -196,7 +210,7 @@ pub fn show_package_diff_dialog(
                     diff_rc.added.iter().filter(|p| filter_pkg(p)).collect();
                 if !filtered_added.is_empty() {
                     let added_section = create_enhanced_section(
-                        "Packages Added",
+                        &gettext("Packages Added"),
                         &filtered_added
                             .iter()
                             .map(|p| PackageDisplayItem {
@@ -217,7 +231,7 @@ pub fn show_package_diff_dialog(
                     diff_rc.removed.iter().filter(|p| filter_pkg(p)).collect();
                 if !filtered_removed.is_empty() {
                     let removed_section = create_enhanced_section(
-                        "Packages Removed",
+                        &gettext("Packages Removed"),
                         &filtered_removed
                             .iter()
                             .map(|p| PackageDisplayItem {
@@ -241,7 +255,7 @@ pub fn show_package_diff_dialog(
                     .collect();
                 if !filtered_updated.is_empty() {
                     let updated_section = create_enhanced_section(
-                        "Packages Updated",
+                        &gettext("Packages Updated"),
                         &filtered_updated
                             .iter()
                             .map(|p| PackageDisplayItem {
@@ -263,12 +277,13 @@ pub fn show_package_diff_dialog(
                     && diff_rc.removed.is_empty()
                     && diff_rc.updated.is_empty()
                 {
-                    status.set_title("No Differences");
-                    status.set_description(Some("Both snapshots have identical packages"));
+                    status.set_title(&gettext("No Differences"));
+                    status
+                        .set_description(Some(&gettext("Both snapshots have identical packages")));
                     status.set_icon_name(Some("emblem-ok-symbolic"));
                 } else {
-                    status.set_title("No Matching Packages");
-                    status.set_description(Some("Try adjusting your search or filter"));
+                    status.set_title(&gettext("No Matching Packages"));
+                    status.set_description(Some(&gettext("Try adjusting your search or filter")));
                     status.set_icon_name(Some("edit-find-symbolic"));
                 }
                 list_box.append(&status);
@@ -278,15 +293,26 @@ pub fn show_package_diff_dialog(
             let total_items = diff_rc.added.len() + diff_rc.removed.len() + diff_rc.updated.len();
             if shown_items == total_items {
                 if total_items == 0 {
-                    status_label.set_text("No package differences");
+                    status_label.set_text(&gettext("No package differences"));
                 } else {
-                    status_label.set_text(&format!("Showing all {total_items} package changes"));
+                    status_label.set_text(&ngettext_f(
+                        "Showing {total} package change",
+                        "Showing all {total} package changes",
+                        total_items as u64,
+                        &[("total", &total_items.to_string())],
+                    ));
                 }
             } else if shown_items == 0 {
-                status_label.set_text("No packages match current filters");
+                status_label.set_text(&gettext("No packages match current filters"));
             } else {
-                status_label.set_text(&format!(
-                    "Showing {shown_items} of {total_items} package changes"
+                status_label.set_text(&ngettext_f(
+                    "Showing {shown} of {total} package change",
+                    "Showing {shown} of {total} package changes",
+                    total_items as u64,
+                    &[
+                        ("shown", &shown_items.to_string()),
+                        ("total", &total_items.to_string()),
+                    ],
                 ));
             }
         })
@@ -419,7 +445,7 @@ fn create_enhanced_section(title: &str, items: &[PackageDisplayItem]) -> gtk::Bo
         copy_btn.set_valign(gtk::Align::Center);
         copy_btn.add_css_class("flat");
         copy_btn.add_css_class("circular");
-        copy_btn.set_tooltip_text(Some("Copy package name"));
+        copy_btn.set_tooltip_text(Some(&gettext("Copy package name")));
 
         let pkg_name = item.name.clone();
         copy_btn.connect_clicked(move |_| {
@@ -496,15 +522,21 @@ fn export_comparison(parent: &adw::Window, snap1: &str, snap2: &str, diff: &Pack
         Ok(_) => {
             super::dialogs::show_info_window(
                 parent,
-                "Export Successful",
-                &format!("Comparison exported to:\n{}", save_path.display()),
+                &gettext("Export Successful"),
+                &gettext_f(
+                    "Comparison exported to:\n{path}",
+                    &[("path", &save_path.to_string_lossy())],
+                ),
             );
         }
         Err(e) => {
             super::dialogs::show_error_window(
                 parent,
-                "Export Failed",
-                &format!("Failed to export comparison: {e}"),
+                &gettext("Export Failed"),
+                &gettext_f(
+                    "Failed to export comparison: {error}",
+                    &[("error", &e.to_string())],
+                ),
             );
         }
     }
@@ -2,11 +2,16 @@ use adw::prelude::*;
 use gtk::prelude::*;
 use gtk::{CheckButton, Label};
 use libadwaita as adw;
+use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::path::PathBuf;
 use std::rc::Rc;
 
-use crate::subvolume::{SubvolumeInfo, detect_mounted_subvolumes, should_allow_snapshot};
+use crate::i18n::{gettext, gettext_f};
+use crate::subvolume::{
+    SubvolumeInfo, SubvolumeLayout, detect_mounted_subvolumes, load_overrides, save_overrides,
+    should_allow_snapshot,
+};
 
 // Global state for current subvolume selection (used across dialogs)
 thread_local! {
@@ -35,16 +40,16 @@ pub fn create_subvolumes_page(parent: &adw::ApplicationWindow) -> adw::Preferenc
 
     // Create preferences page
     let page = adw::PreferencesPage::new();
-    page.set_title("Manual Snapshots");
+    page.set_title(&gettext("Manual Snapshots"));
     page.set_icon_name(Some("drive-harddisk-symbolic"));
 
     // Create group for subvolume selection
     let group = adw::PreferencesGroup::new();
-    group.set_title("Manual Snapshots");
-    group.set_description(Some(
+    group.set_title(&gettext("Manual Snapshots"));
+    group.set_description(Some(&gettext(
         "Select which Btrfs subvolumes to include when manually creating snapshots. \
          Scheduled snapshots have separate settings configured in each schedule.",
-    ));
+    )));
 
     // Detect available subvolumes
     let subvolumes = match detect_mounted_subvolumes() {
@@ -55,17 +60,23 @@ pub fn create_subvolumes_page(parent: &adw::ApplicationWindow) -> adw::Preferenc
         }
     };
 
+    let detection_group = create_detection_group(parent, &subvolumes);
+
     if subvolumes.is_empty() {
-        let empty_label = Label::new(Some("No Btrfs subvolumes detected"));
+        let empty_label = Label::new(Some(&gettext("No Btrfs subvolumes detected")));
         empty_label.add_css_class("dim-label");
         group.add(&empty_label);
     } else {
+        let overrides = load_overrides();
+
         // Create checkbox for each subvolume
         let checkboxes: Vec<(SubvolumeInfo, CheckButton)> = subvolumes
             .into_iter()
             .filter_map(|subvol| {
                 // Filter out subvolumes that should never be snapshotted
-                if !should_allow_snapshot(&subvol.subvol_path) {
+                if !should_allow_snapshot(&subvol.subvol_path)
+                    && !overrides.always_include.contains(&subvol.mount_point)
+                {
                     return None;
                 }
 
@@ -103,28 +114,283 @@ pub fn create_subvolumes_page(parent: &adw::ApplicationWindow) -> adw::Preferenc
                     log::error!("Failed to save subvolume preferences: {e}");
                     super::dialogs::show_error(
                         &parent_clone,
-                        "Save Failed",
-                        &format!("Failed to save snapshot target preferences: {e}"),
+                        &gettext("Save Failed"),
+                        &gettext_f(
+                            "Failed to save snapshot target preferences: {error}",
+                            &[("error", &e.to_string())],
+                        ),
                     );
                 } else {
                     log::info!("Saved subvolume preferences: {enabled:?}");
-                    super::dialogs::show_toast(&parent_clone, "Manual snapshot settings updated");
+                    super::dialogs::show_toast(
+                        &parent_clone,
+                        &gettext("Manual snapshot settings updated"),
+                    );
                 }
             });
         }
     }
 
     page.add(&group);
+    page.add(&detection_group);
+
+    // Quick snapshot toggle
+    let quick_group = adw::PreferencesGroup::new();
+    quick_group.set_title(&gettext("Quick Snapshots"));
+
+    let quick_row = adw::SwitchRow::new();
+    quick_row.set_title(&gettext("Show Quick Snapshot Button"));
+    quick_row.set_subtitle(&gettext(
+        "Create a snapshot with an automatic name and description without asking (Ctrl+Shift+N)",
+    ));
+    quick_row.set_active(load_manual_settings().quick_snapshot_enabled);
+
+    let parent_clone = parent.clone();
+    quick_row.connect_active_notify(move |row| {
+        let mut settings = load_manual_settings();
+        settings.quick_snapshot_enabled = row.is_active();
+
+        if let Err(e) = save_manual_settings(&settings) {
+            log::error!("Failed to save manual snapshot settings: {e}");
+            super::dialogs::show_error(
+                &parent_clone,
+                &gettext("Save Failed"),
+                &gettext_f(
+                    "Failed to save quick snapshot preference: {error}",
+                    &[("error", &e.to_string())],
+                ),
+            );
+        }
+    });
+
+    quick_group.add(&quick_row);
+
+    let global_row = adw::SwitchRow::new();
+    global_row.set_title(&gettext("Global Shortcut"));
+    global_row.set_subtitle(&gettext(
+        "Take a quick snapshot from anywhere, even when Waypoint isn't focused. \
+        Your desktop asks which keys to use. Requires desktop portal support; \
+        takes effect the next time Waypoint starts.",
+    ));
+    global_row.set_active(load_manual_settings().global_shortcut_enabled);
+
+    let parent_clone = parent.clone();
+    global_row.connect_active_notify(move |row| {
+        let mut settings = load_manual_settings();
+        settings.global_shortcut_enabled = row.is_active();
+
+        if let Err(e) = save_manual_settings(&settings) {
+            log::error!("Failed to save manual snapshot settings: {e}");
+            super::dialogs::show_error(
+                &parent_clone,
+                &gettext("Save Failed"),
+                &gettext_f(
+                    "Failed to save global shortcut preference: {error}",
+                    &[("error", &e.to_string())],
+                ),
+            );
+        }
+    });
+
+    quick_group.add(&global_row);
+    page.add(&quick_group);
+
+    // Default description template
+    let description_group = adw::PreferencesGroup::new();
+    description_group.set_title(&gettext("Default Description"));
+    description_group.set_description(Some(&gettext(
+        "Used when a snapshot is created without a description. Available tokens: \
+        {date}, {time}, {kernel}, {updates} (pending package updates), {hostname}",
+    )));
+
+    let template_row = adw::EntryRow::new();
+    template_row.set_title(&gettext("Template"));
+    template_row.set_text(&load_manual_settings().description_template);
+    template_row.set_show_apply_button(true);
+
+    let parent_clone = parent.clone();
+    template_row.connect_apply(move |row| {
+        let mut settings = load_manual_settings();
+        let template = row.text().trim().to_string();
+        settings.description_template = if template.is_empty() {
+            default_description_template()
+        } else {
+            template
+        };
+        row.set_text(&settings.description_template);
+
+        if let Err(e) = save_manual_settings(&settings) {
+            log::error!("Failed to save manual snapshot settings: {e}");
+            super::dialogs::show_error(
+                &parent_clone,
+                &gettext("Save Failed"),
+                &gettext_f(
+                    "Failed to save description template: {error}",
+                    &[("error", &e.to_string())],
+                ),
+            );
+        } else {
+            super::dialogs::show_toast(&parent_clone, &gettext("Default description updated"));
+        }
+    });
+
+    description_group.add(&template_row);
+    page.add(&description_group);
+
+    // Low space handling
+    let space_group = adw::PreferencesGroup::new();
+    space_group.set_title(&gettext("Low Disk Space"));
+
+    let make_room_row = adw::SwitchRow::new();
+    make_room_row.set_title(&gettext("Make Room Automatically"));
+    make_room_row.set_subtitle(&gettext(
+        "When there isn't enough free space for a new snapshot, offer to delete the \
+        oldest snapshots that aren't favorites. You confirm every time before anything \
+        is deleted.",
+    ));
+    make_room_row.set_active(load_manual_settings().make_room_automatically);
+
+    let parent_clone = parent.clone();
+    make_room_row.connect_active_notify(move |row| {
+        let mut settings = load_manual_settings();
+        settings.make_room_automatically = row.is_active();
+
+        if let Err(e) = save_manual_settings(&settings) {
+            log::error!("Failed to save manual snapshot settings: {e}");
+            super::dialogs::show_error(
+                &parent_clone,
+                &gettext("Save Failed"),
+                &gettext_f(
+                    "Failed to save low disk space preference: {error}",
+                    &[("error", &e.to_string())],
+                ),
+            );
+        }
+    });
+
+    space_group.add(&make_room_row);
+    page.add(&space_group);
+
+    // Explain mode
+    let explain_group = adw::PreferencesGroup::new();
+    explain_group.set_title(&gettext("Explain Mode"));
+
+    let commands_row = adw::SwitchRow::new();
+    commands_row.set_title(&gettext("Show Commands"));
+    commands_row.set_subtitle(&gettext(
+        "Add Command Transcript to the main menu, listing the exact btrfs and other \
+        commands Waypoint runs as root when creating, deleting, restoring and backing up \
+        snapshots",
+    ));
+    commands_row.set_active(load_manual_settings().show_commands);
+
+    let parent_clone = parent.clone();
+    commands_row.connect_active_notify(move |row| {
+        let mut settings = load_manual_settings();
+        settings.show_commands = row.is_active();
+
+        if let Err(e) = save_manual_settings(&settings) {
+            log::error!("Failed to save manual snapshot settings: {e}");
+            super::dialogs::show_error(
+                &parent_clone,
+                &gettext("Save Failed"),
+                &gettext_f(
+                    "Failed to save explain mode preference: {error}",
+                    &[("error", &e.to_string())],
+                ),
+            );
+        }
+    });
+
+    explain_group.add(&commands_row);
+    page.add(&explain_group);
+
     page
 }
 
+/// Create the group showing the detected layout and hidden subvolumes
+///
+/// Subvolumes that look like snapshot storage or swap are hidden from every
+/// subvolume list; if detection gets a layout wrong, they can be offered anyway.
+fn create_detection_group(
+    parent: &adw::ApplicationWindow,
+    subvolumes: &[SubvolumeInfo],
+) -> adw::PreferencesGroup {
+    let group = adw::PreferencesGroup::new();
+    group.set_title(&gettext("Subvolume Detection"));
+
+    let layout_row = adw::ActionRow::new();
+    layout_row.set_title(&gettext("Detected Layout"));
+    layout_row.set_subtitle(
+        &SubvolumeLayout::detect(subvolumes)
+            .map(SubvolumeLayout::description)
+            .unwrap_or_else(|| gettext("Root filesystem is not on Btrfs")),
+    );
+    group.add(&layout_row);
+
+    let hidden: Vec<&SubvolumeInfo> = subvolumes
+        .iter()
+        .filter(|s| !should_allow_snapshot(&s.subvol_path))
+        .collect();
+    if hidden.is_empty() {
+        return group;
+    }
+
+    group.set_description(Some(&gettext(
+        "These subvolumes look like snapshot storage or swap and are hidden. \
+         Offer one for snapshots only if it was misdetected.",
+    )));
+
+    let overrides = load_overrides();
+    for subvol in hidden {
+        let row = adw::SwitchRow::new();
+        row.set_title(&subvol.mount_point.display().to_string());
+        row.set_subtitle(&gettext_f(
+            "Subvolume: {path}",
+            &[("path", &subvol.subvol_path)],
+        ));
+        row.set_active(overrides.always_include.contains(&subvol.mount_point));
+
+        let mount_point = subvol.mount_point.clone();
+        let parent_clone = parent.clone();
+        row.connect_active_notify(move |row| {
+            let mut overrides = load_overrides();
+            overrides.always_include.retain(|p| p != &mount_point);
+            if row.is_active() {
+                overrides.always_include.push(mount_point.clone());
+            }
+
+            if let Err(e) = save_overrides(&overrides) {
+                log::error!("Failed to save subvolume detection overrides: {e}");
+                super::dialogs::show_error(
+                    &parent_clone,
+                    &gettext("Save Failed"),
+                    &gettext_f(
+                        "Failed to save subvolume detection settings: {error}",
+                        &[("error", &e.to_string())],
+                    ),
+                );
+            } else {
+                super::dialogs::show_toast(
+                    &parent_clone,
+                    &gettext("Reopen Preferences to update the subvolume list"),
+                );
+            }
+        });
+
+        group.add(&row);
+    }
+
+    group
+}
+
 /// Create a row for a subvolume checkbox
 fn create_subvolume_row(subvol: &SubvolumeInfo, current_config: &[PathBuf]) -> adw::ActionRow {
     let row = adw::ActionRow::new();
     row.set_title(&subvol.display_name);
 
     // Show subvolume path as subtitle
-    let subtitle = format!("Subvolume: {}", subvol.subvol_path);
+    let subtitle = gettext_f("Subvolume: {path}", &[("path", &subvol.subvol_path)]);
     row.set_subtitle(&subtitle);
 
     // Add checkbox
@@ -139,7 +405,10 @@ fn create_subvolume_row(subvol: &SubvolumeInfo, current_config: &[PathBuf]) -> a
     if subvol.mount_point == PathBuf::from("/") {
         checkbox.set_active(true);
         checkbox.set_sensitive(false);
-        row.set_subtitle("Subvolume: @ (Required)");
+        row.set_subtitle(&gettext_f(
+            "Subvolume: {path} (Required)",
+            &[("path", &subvol.subvol_path)],
+        ));
     }
 
     row.add_suffix(&checkbox);
@@ -206,3 +475,88 @@ pub fn save_config(enabled_subvolumes: &[PathBuf]) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Settings that apply to manually created snapshots
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManualSnapshotSettings {
+    /// Show the quick snapshot button and enable its shortcut
+    #[serde(default = "default_true")]
+    pub quick_snapshot_enabled: bool,
+    /// Description used when none is entered (see `expand_description_template`)
+    #[serde(default = "default_description_template")]
+    pub description_template: String,
+    /// Register a system-wide quick snapshot shortcut with the desktop portal
+    #[serde(default)]
+    pub global_shortcut_enabled: bool,
+    /// Offer to delete the oldest non-favorite snapshots when there isn't enough
+    /// free space for a new one
+    #[serde(default)]
+    pub make_room_automatically: bool,
+    /// Show the transcript of commands the helper runs in the main menu
+    #[serde(default)]
+    pub show_commands: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Default description template for manual snapshots
+pub const DEFAULT_DESCRIPTION_TEMPLATE: &str = "System snapshot {date} {time} (kernel {kernel})";
+
+fn default_description_template() -> String {
+    DEFAULT_DESCRIPTION_TEMPLATE.to_string()
+}
+
+impl Default for ManualSnapshotSettings {
+    fn default() -> Self {
+        Self {
+            quick_snapshot_enabled: true,
+            description_template: default_description_template(),
+            global_shortcut_enabled: false,
+            make_room_automatically: false,
+            show_commands: false,
+        }
+    }
+}
+
+/// Path of the manual snapshot settings file
+fn manual_settings_path() -> PathBuf {
+    dirs::config_local_dir()
+        .map(|d| d.join("waypoint").join("manual-snapshots.json"))
+        .unwrap_or_else(|| PathBuf::from("/tmp/waypoint-manual-snapshots.json"))
+}
+
+/// Load manual snapshot settings, falling back to defaults
+pub fn load_manual_settings() -> ManualSnapshotSettings {
+    let config_path = manual_settings_path();
+
+    if !config_path.exists() {
+        return ManualSnapshotSettings::default();
+    }
+
+    match std::fs::read_to_string(&config_path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::error!("Failed to parse manual snapshot settings: {e}");
+            ManualSnapshotSettings::default()
+        }),
+        Err(e) => {
+            log::error!("Failed to read manual snapshot settings: {e}");
+            ManualSnapshotSettings::default()
+        }
+    }
+}
+
+/// Save manual snapshot settings to disk
+pub fn save_manual_settings(settings: &ManualSnapshotSettings) -> anyhow::Result<()> {
+    let config_path = manual_settings_path();
+
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let content = serde_json::to_string_pretty(settings)?;
+    std::fs::write(&config_path, content)?;
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 //! Modern sidebar-based preferences window
 
 use crate::backup_manager::BackupManager;
+use crate::i18n::gettext;
 use adw::prelude::*;
 use gtk::prelude::*;
 use gtk::{ListBox, Orientation, SelectionMode, Stack};
@@ -14,7 +15,7 @@ pub fn show_preferences_window(
     backup_manager: Rc<RefCell<BackupManager>>,
 ) {
     let window = adw::Window::new();
-    window.set_title(Some("Preferences"));
+    window.set_title(Some(&gettext("Preferences")));
     window.set_modal(true);
     window.set_transient_for(Some(parent));
     window.set_default_size(900, 600);
@@ -60,6 +61,9 @@ pub fn show_preferences_window(
     let backups_page = super::backup_dialog::create_backups_content(parent, backup_manager);
     stack.add_named(&backups_page, Some("backups"));
 
+    let notifications_page = create_notifications_content(parent);
+    stack.add_named(&notifications_page, Some("notifications"));
+
     main_box.append(&stack);
 
     // Wire up sidebar navigation with lazy loading for scheduling page
@@ -77,6 +81,7 @@ pub fn show_preferences_window(
                 2 => "exclusions",
                 3 => "quotas",
                 4 => "backups",
+                5 => "notifications",
                 _ => "scheduling",
             };
 
@@ -126,16 +131,23 @@ fn create_sidebar() -> ListBox {
     sidebar.add_css_class("navigation-sidebar");
 
     let items = [
-        ("Scheduled Snapshots", "preferences-system-time-symbolic"),
-        ("Manual Snapshots", "folder-symbolic"),
-        ("Exclusions", "edit-delete-symbolic"),
-        ("Quotas", "drive-harddisk-symbolic"),
-        ("Backups", "media-removable-symbolic"),
+        (
+            gettext("Scheduled Snapshots"),
+            "preferences-system-time-symbolic",
+        ),
+        (gettext("Manual Snapshots"), "folder-symbolic"),
+        (gettext("Exclusions"), "edit-delete-symbolic"),
+        (gettext("Quotas"), "drive-harddisk-symbolic"),
+        (gettext("Backups"), "media-removable-symbolic"),
+        (
+            gettext("Notifications"),
+            "preferences-system-notifications-symbolic",
+        ),
     ];
 
     for (title, icon_name) in items {
         let row = adw::ActionRow::new();
-        row.set_title(title);
+        row.set_title(&title);
 
         let icon = gtk::Image::from_icon_name(icon_name);
         icon.set_margin_start(6);
@@ -255,6 +267,41 @@ fn create_quotas_content(parent: &adw::ApplicationWindow) -> gtk::Box {
     container
 }
 
+/// Create notifications content page
+fn create_notifications_content(parent: &adw::ApplicationWindow) -> gtk::Box {
+    let container = gtk::Box::new(Orientation::Vertical, 0);
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_vexpand(true);
+    scrolled.set_hexpand(true);
+
+    let clamp = adw::Clamp::new();
+    clamp.set_maximum_size(800);
+    clamp.set_tightening_threshold(600);
+
+    let content_box = gtk::Box::new(Orientation::Vertical, 0);
+    content_box.set_margin_top(24);
+    content_box.set_margin_bottom(24);
+    content_box.set_margin_start(12);
+    content_box.set_margin_end(12);
+
+    let page_content = super::notification_preferences::create_notification_page(parent);
+
+    let mut child = page_content.first_child();
+    while let Some(widget) = child {
+        let next = widget.next_sibling();
+        widget.unparent();
+        content_box.append(&widget);
+        child = next;
+    }
+
+    clamp.set_child(Some(&content_box));
+    scrolled.set_child(Some(&clamp));
+    container.append(&scrolled);
+
+    container
+}
+
 /// Create scheduling content page
 fn create_scheduling_content(parent: &adw::ApplicationWindow) -> gtk::Box {
     let container = gtk::Box::new(Orientation::Vertical, 0);
@@ -1,18 +1,25 @@
 //! Quota configuration preferences UI
 
 use crate::dbus_client::WaypointHelperClient;
+use crate::i18n::{gettext, gettext_f, ngettext_f};
 use adw::prelude::*;
 use gtk::prelude::*;
 use gtk::{Orientation, SpinButton};
 use libadwaita as adw;
-use waypoint_common::{QuotaConfig, QuotaType};
+use std::cell::Cell;
+use std::rc::Rc;
+use waypoint_common::capabilities::{FEATURE_QUOTA_CLEANUP_PREVIEW, FEATURE_SNAPSHOT_COMPRESSION};
+use waypoint_common::compression::COMPRESSION_ALGORITHMS;
+use waypoint_common::{
+    QuotaCleanupReport, QuotaConfig, QuotaType, SnapshotCompression, format_bytes,
+};
 
 use super::dialogs;
 
 /// Create the quota preferences page
 pub fn create_quota_page(parent: &adw::ApplicationWindow) -> adw::PreferencesPage {
     let page = adw::PreferencesPage::new();
-    page.set_title("Quota");
+    page.set_title(&gettext("Quota"));
     page.set_icon_name(Some("drive-harddisk-symbolic"));
 
     // Load current config
@@ -20,24 +27,26 @@ pub fn create_quota_page(parent: &adw::ApplicationWindow) -> adw::PreferencesPag
 
     // Basic quota settings group
     let basic_group = adw::PreferencesGroup::new();
-    basic_group.set_title("Quota Settings");
-    basic_group.set_description(Some(
+    basic_group.set_title(&gettext("Quota Settings"));
+    basic_group.set_description(Some(&gettext(
         "Control snapshot disk space usage with btrfs quotas.",
-    ));
+    )));
 
     // Enable quotas switch
     let enable_row = adw::SwitchRow::new();
-    enable_row.set_title("Enable Quotas");
-    enable_row.set_subtitle("Track and limit snapshot disk usage");
+    enable_row.set_title(&gettext("Enable Quotas"));
+    enable_row.set_subtitle(&gettext("Track and limit snapshot disk usage"));
     enable_row.set_active(config.enabled);
     basic_group.add(&enable_row);
 
     // Quota type selection
     let type_row = adw::ComboRow::new();
-    type_row.set_title("Quota Type");
-    type_row.set_subtitle("Simple: faster, less overhead. Traditional: complete tracking");
+    type_row.set_title(&gettext("Quota Type"));
+    type_row.set_subtitle(&gettext(
+        "Simple: faster, less overhead. Traditional: complete tracking",
+    ));
 
-    let type_model = gtk::StringList::new(&["Simple", "Traditional"]);
+    let type_model = gtk::StringList::new(&[&gettext("Simple"), &gettext("Traditional")]);
     type_row.set_model(Some(&type_model));
     type_row.set_selected(match config.quota_type {
         QuotaType::Simple => 0,
@@ -48,7 +57,7 @@ pub fn create_quota_page(parent: &adw::ApplicationWindow) -> adw::PreferencesPag
 
     // Quota status row (between type and cleanup)
     let status_row = adw::ActionRow::new();
-    status_row.set_title("Quota Status");
+    status_row.set_title(&gettext("Quota Status"));
 
     // Try to get current usage
     let usage_text = if config.enabled {
@@ -61,15 +70,15 @@ pub fn create_quota_page(parent: &adw::ApplicationWindow) -> adw::PreferencesPag
                         let pct = usage.usage_percent().unwrap_or(0.0) * 100.0;
                         format!("{used} / {limit_str} ({pct:.1}%)")
                     } else {
-                        format!("{used} (no limit set)")
+                        gettext_f("{used} (no limit set)", &[("used", &used)])
                     }
                 }
-                Err(e) => format!("Error: {e}"),
+                Err(e) => gettext_f("Error: {error}", &[("error", &e.to_string())]),
             },
-            Err(_) => "Cannot connect to helper service".to_string(),
+            Err(_) => gettext("Cannot connect to helper service"),
         }
     } else {
-        "Quotas not enabled".to_string()
+        gettext("Quotas not enabled")
     };
 
     status_row.set_subtitle(&usage_text);
@@ -78,24 +87,47 @@ pub fn create_quota_page(parent: &adw::ApplicationWindow) -> adw::PreferencesPag
 
     // Auto-cleanup switch
     let cleanup_row = adw::SwitchRow::new();
-    cleanup_row.set_title("Automatic Cleanup");
-    cleanup_row.set_subtitle("Automatically delete old snapshots when quota limit is reached");
+    cleanup_row.set_title(&gettext("Automatic Cleanup"));
+    cleanup_row.set_subtitle(&gettext(
+        "Automatically delete old snapshots when quota limit is reached",
+    ));
     cleanup_row.set_active(config.auto_cleanup);
     cleanup_row.set_sensitive(config.enabled);
     basic_group.add(&cleanup_row);
 
+    // Preview of what auto-cleanup would delete at current usage
+    let preview_row = adw::ActionRow::new();
+    preview_row.set_title(&gettext("Preview Cleanup"));
+    preview_row.set_subtitle(&gettext(
+        "See which snapshots automatic cleanup would delete right now",
+    ));
+    let preview_button = gtk::Button::with_label(&gettext("Preview"));
+    preview_button.set_valign(gtk::Align::Center);
+    preview_row.add_suffix(&preview_button);
+    preview_row.set_sensitive(config.enabled && config.auto_cleanup);
+    preview_row.set_visible(crate::dbus_client::helper_supports(
+        FEATURE_QUOTA_CLEANUP_PREVIEW,
+    ));
+    basic_group.add(&preview_row);
+    {
+        let parent_clone = parent.clone();
+        preview_button.connect_clicked(move |button| {
+            show_cleanup_preview(&parent_clone, button);
+        });
+    }
+
     page.add(&basic_group);
 
     // Limits group
     let limits_group = adw::PreferencesGroup::new();
-    limits_group.set_title("Limits");
-    limits_group.set_description(Some("Set maximum disk space for snapshots"));
+    limits_group.set_title(&gettext("Limits"));
+    limits_group.set_description(Some(&gettext("Set maximum disk space for snapshots")));
     limits_group.set_margin_top(24);
 
     // Total limit row
     let limit_row = adw::ActionRow::new();
-    limit_row.set_title("Total Snapshot Limit");
-    limit_row.set_subtitle("Maximum space for all snapshots (0 = no limit)");
+    limit_row.set_title(&gettext("Total Snapshot Limit"));
+    limit_row.set_subtitle(&gettext("Maximum space for all snapshots (0 = no limit)"));
 
     // Create spin button for limit in GB
     let limit_spin = SpinButton::with_range(0.0, 10000.0, 1.0);
@@ -108,7 +140,7 @@ pub fn create_quota_page(parent: &adw::ApplicationWindow) -> adw::PreferencesPag
     limit_spin.set_valign(gtk::Align::Center);
     limit_spin.set_sensitive(config.enabled);
 
-    let limit_label = gtk::Label::new(Some("GB"));
+    let limit_label = gtk::Label::new(Some(&gettext("GB")));
     limit_label.set_valign(gtk::Align::Center);
     limit_label.add_css_class("dim-label");
 
@@ -121,8 +153,10 @@ pub fn create_quota_page(parent: &adw::ApplicationWindow) -> adw::PreferencesPag
 
     // Cleanup threshold row
     let threshold_row = adw::ActionRow::new();
-    threshold_row.set_title("Cleanup Threshold");
-    threshold_row.set_subtitle("Trigger cleanup when usage reaches this percentage");
+    threshold_row.set_title(&gettext("Cleanup Threshold"));
+    threshold_row.set_subtitle(&gettext(
+        "Trigger cleanup when usage reaches this percentage",
+    ));
 
     let threshold_spin = SpinButton::with_range(50.0, 99.0, 1.0);
     threshold_spin.set_value(config.cleanup_threshold * 100.0);
@@ -141,13 +175,110 @@ pub fn create_quota_page(parent: &adw::ApplicationWindow) -> adw::PreferencesPag
     threshold_row.add_suffix(&threshold_box);
     limits_group.add(&threshold_row);
 
+    // Snapshot size warning row
+    let size_warning_row = adw::ActionRow::new();
+    size_warning_row.set_title(&gettext("Snapshot Size Warning"));
+    size_warning_row.set_subtitle(&gettext(
+        "Flag snapshots whose exclusive size exceeds this amount (0 = no warning)",
+    ));
+
+    let size_warning_spin = SpinButton::with_range(0.0, 10000.0, 1.0);
+    let current_warning_gb = config
+        .size_warning_bytes
+        .map(|bytes| bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+        .unwrap_or(0.0);
+    size_warning_spin.set_value(current_warning_gb);
+    size_warning_spin.set_digits(0);
+    size_warning_spin.set_valign(gtk::Align::Center);
+    size_warning_spin.set_sensitive(config.enabled);
+
+    let size_warning_label = gtk::Label::new(Some(&gettext("GB")));
+    size_warning_label.set_valign(gtk::Align::Center);
+    size_warning_label.add_css_class("dim-label");
+
+    let size_warning_box = gtk::Box::new(Orientation::Horizontal, 6);
+    size_warning_box.append(&size_warning_spin);
+    size_warning_box.append(&size_warning_label);
+
+    size_warning_row.add_suffix(&size_warning_box);
+    limits_group.add(&size_warning_row);
+
     page.add(&limits_group);
 
+    // Compression group, managed by the helper rather than quota config
+    let compression_group = adw::PreferencesGroup::new();
+    compression_group.set_title(&gettext("Compression"));
+    compression_group.set_description(Some(&gettext(
+        "Compress data written into the snapshot directory, such as snapshots restored \
+         from backups. Snapshots of the system share data with it and keep the \
+         compression the system files were written with.",
+    )));
+    compression_group.set_margin_top(24);
+    compression_group.set_visible(crate::dbus_client::helper_supports(
+        FEATURE_SNAPSHOT_COMPRESSION,
+    ));
+
+    let compression_row = adw::ComboRow::new();
+    compression_row.set_title(&gettext("Snapshot Directory Compression"));
+    compression_row.set_subtitle(&gettext("Loading…"));
+    let compression_model = gtk::StringList::new(&[&gettext("Filesystem Default")]);
+    for algorithm in COMPRESSION_ALGORITHMS {
+        compression_model.append(algorithm);
+    }
+    compression_row.set_model(Some(&compression_model));
+    compression_row.set_sensitive(false);
+    compression_group.add(&compression_row);
+
+    let savings_row = adw::ActionRow::new();
+    savings_row.set_title(&gettext("Space Saved"));
+    savings_row.set_subtitle(&gettext("Loading…"));
+    compression_group.add(&savings_row);
+
+    page.add(&compression_group);
+
+    // Set while the combo row is updated from the helper, so it isn't saved back
+    let compression_loading = Rc::new(Cell::new(true));
+    if compression_group.is_visible() {
+        update_compression(
+            parent,
+            &compression_row,
+            &savings_row,
+            &compression_loading,
+            None,
+        );
+    }
+    {
+        let parent_clone = parent.clone();
+        let savings_row_clone = savings_row.clone();
+        let loading = compression_loading.clone();
+        compression_row.connect_selected_notify(move |row| {
+            if loading.get() {
+                return;
+            }
+            let algorithm = match row.selected() {
+                0 => String::new(),
+                i => COMPRESSION_ALGORITHMS
+                    .get(i as usize - 1)
+                    .map(|a| a.to_string())
+                    .unwrap_or_default(),
+            };
+            update_compression(
+                &parent_clone,
+                row,
+                &savings_row_clone,
+                &loading,
+                Some(algorithm),
+            );
+        });
+    }
+
     // Wire up sensitivity changes and auto-save
     let type_row_clone = type_row.clone();
     let cleanup_row_clone = cleanup_row.clone();
     let limit_spin_clone = limit_spin.clone();
+    let size_warning_spin_clone = size_warning_spin.clone();
     let status_row_clone = status_row.clone();
+    let preview_row_enable = preview_row.clone();
     let parent_clone_enable = parent.clone();
 
     // Store all widgets for auto-save in enable handler
@@ -161,7 +292,9 @@ pub fn create_quota_page(parent: &adw::ApplicationWindow) -> adw::PreferencesPag
         type_row_clone.set_sensitive(enabled);
         cleanup_row_clone.set_sensitive(enabled);
         limit_spin_clone.set_sensitive(enabled);
+        size_warning_spin_clone.set_sensitive(enabled);
         status_row_clone.set_visible(enabled);
+        preview_row_enable.set_sensitive(enabled && cleanup_row_clone.is_active());
 
         // Show confirmation dialog before enabling/disabling
         let parent = parent_clone_enable.clone();
@@ -186,9 +319,9 @@ pub fn create_quota_page(parent: &adw::ApplicationWindow) -> adw::PreferencesPag
             // Confirm enable
             super::dialogs::show_confirmation_with_cancel(
                 &parent,
-                "Enable Quotas?",
-                "This will enable btrfs quota tracking on your snapshot filesystem. This operation may take a moment.",
-                "Enable",
+                &gettext("Enable Quotas?"),
+                &gettext("This will enable btrfs quota tracking on your snapshot filesystem. This operation may take a moment."),
+                &gettext("Enable"),
                 false,
                 move || {
                     save_quota_config(
@@ -220,9 +353,9 @@ pub fn create_quota_page(parent: &adw::ApplicationWindow) -> adw::PreferencesPag
             // Confirm disable
             super::dialogs::show_confirmation_with_cancel(
                 &parent,
-                "Disable Quotas?",
-                "This will disable quota tracking. Usage information will no longer be available.",
-                "Disable",
+                &gettext("Disable Quotas?"),
+                &gettext("This will disable quota tracking. Usage information will no longer be available."),
+                &gettext("Disable"),
                 true,
                 move || {
                     save_quota_config(
@@ -245,9 +378,11 @@ pub fn create_quota_page(parent: &adw::ApplicationWindow) -> adw::PreferencesPag
     // Wire up threshold sensitivity based on auto-cleanup
     let threshold_spin_clone2 = threshold_spin.clone();
     let enable_row_clone2 = enable_row.clone();
+    let preview_row_clone = preview_row.clone();
     cleanup_row.connect_active_notify(move |switch| {
         if enable_row_clone2.is_active() {
             threshold_spin_clone2.set_sensitive(switch.is_active());
+            preview_row_clone.set_sensitive(switch.is_active());
         }
     });
 
@@ -335,9 +470,249 @@ pub fn create_quota_page(parent: &adw::ApplicationWindow) -> adw::PreferencesPag
         });
     }
 
+    // Auto-save for size warning changes (only the config file, quotas are unaffected)
+    {
+        let parent_clone = parent.clone();
+
+        size_warning_spin.connect_value_changed(move |spin| {
+            save_size_warning(&parent_clone, spin.value());
+        });
+    }
+
     page
 }
 
+/// Load compression of the snapshot directory, setting `algorithm` first when given
+///
+/// The rows show the helper's answer afterwards, so a failed change reverts the selection.
+fn update_compression(
+    parent: &adw::ApplicationWindow,
+    compression_row: &adw::ComboRow,
+    savings_row: &adw::ActionRow,
+    loading: &Rc<Cell<bool>>,
+    algorithm: Option<String>,
+) {
+    compression_row.set_sensitive(false);
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let changing = algorithm.is_some();
+    std::thread::spawn(move || {
+        let result = WaypointHelperClient::new().and_then(|client| match &algorithm {
+            Some(algorithm) => client.set_snapshot_compression(algorithm),
+            None => client.get_snapshot_compression(),
+        });
+        let _ = tx.send(result);
+    });
+
+    let parent = parent.clone();
+    let compression_row = compression_row.clone();
+    let savings_row = savings_row.clone();
+    let loading = loading.clone();
+    gtk::glib::spawn_future_local(async move {
+        let result = loop {
+            match rx.try_recv() {
+                Ok(result) => break result,
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    gtk::glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    break Err(anyhow::anyhow!("Compression thread disconnected"));
+                }
+            }
+        };
+
+        let compression = match result {
+            Ok(compression) => {
+                if changing {
+                    dialogs::show_toast(&parent, &gettext("Compression updated"));
+                }
+                compression
+            }
+            Err(e) if changing => {
+                dialogs::show_error(
+                    &parent,
+                    &gettext("Compression Failed"),
+                    &gettext_f(
+                        "Failed to change snapshot compression: {error}",
+                        &[("error", &e.to_string())],
+                    ),
+                );
+                // Show what is actually set rather than the rejected choice
+                update_compression(&parent, &compression_row, &savings_row, &loading, None);
+                return;
+            }
+            Err(e) => {
+                compression_row.set_subtitle(&gettext_f(
+                    "Unavailable: {error}",
+                    &[("error", &e.to_string())],
+                ));
+                savings_row.set_visible(false);
+                return;
+            }
+        };
+
+        loading.set(true);
+        let selected = compression
+            .property
+            .as_deref()
+            .and_then(|property| COMPRESSION_ALGORITHMS.iter().position(|a| *a == property))
+            .map_or(0, |i| i as u32 + 1);
+        compression_row.set_selected(selected);
+        loading.set(false);
+
+        let (subtitle, savings) = describe_compression(&compression);
+        compression_row.set_subtitle(&subtitle);
+        savings_row.set_subtitle(&savings);
+        compression_row.set_sensitive(true);
+    });
+}
+
+/// Subtitles for the compression and savings rows
+fn describe_compression(compression: &SnapshotCompression) -> (String, String) {
+    let subtitle = match (compression.effective(), compression.property.is_some()) {
+        (Some(algorithm), true) => gettext_f(
+            "New data is compressed with {algorithm}",
+            &[("algorithm", algorithm)],
+        ),
+        (Some(algorithm), false) => gettext_f(
+            "New data is compressed with {algorithm} (from mount options)",
+            &[("algorithm", algorithm)],
+        ),
+        (None, _) => gettext("New data is stored uncompressed"),
+    };
+
+    let savings = match compression.usage {
+        Some(usage) if usage.uncompressed_bytes > 0 => gettext_f(
+            "{saved} saved ({percent}%), {disk} on disk",
+            &[
+                ("saved", &format_bytes(usage.saved_bytes())),
+                ("percent", &format!("{:.0}", usage.saved_percent())),
+                ("disk", &format_bytes(usage.disk_bytes)),
+            ],
+        ),
+        Some(_) => gettext("No snapshot data yet"),
+        None => gettext("Install compsize to see how much space compression saves"),
+    };
+
+    (subtitle, savings)
+}
+
+/// Ask the helper what automatic cleanup would delete and show the answer
+fn show_cleanup_preview(parent: &adw::ApplicationWindow, button: &gtk::Button) {
+    button.set_sensitive(false);
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let result = WaypointHelperClient::new().and_then(|client| client.preview_quota_cleanup());
+        let _ = tx.send(result);
+    });
+
+    let parent = parent.clone();
+    let button = button.clone();
+    gtk::glib::spawn_future_local(async move {
+        let result = loop {
+            match rx.try_recv() {
+                Ok(result) => break result,
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    gtk::glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    break Err(anyhow::anyhow!("Preview thread disconnected unexpectedly"));
+                }
+            }
+        };
+        button.set_sensitive(true);
+
+        match result {
+            Ok(report) => {
+                dialogs::show_info(
+                    &parent,
+                    &gettext("Cleanup Preview"),
+                    &describe_cleanup_preview(&report),
+                );
+            }
+            Err(e) => {
+                dialogs::show_error(
+                    &parent,
+                    &gettext("Preview Failed"),
+                    &gettext_f(
+                        "Failed to preview quota cleanup: {error}",
+                        &[("error", &e.to_string())],
+                    ),
+                );
+            }
+        }
+    });
+}
+
+fn describe_cleanup_preview(report: &QuotaCleanupReport) -> String {
+    let mut message = if report.snapshots.is_empty() {
+        gettext(
+            "Nothing would be deleted. Usage is below the cleanup threshold, or only favorites are left.",
+        )
+    } else {
+        let listing = report
+            .snapshots
+            .iter()
+            .map(|name| format!("• {name}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        ngettext_f(
+            "The next snapshot creation would delete about {count} snapshot, oldest first:\n\n{snapshots}",
+            "The next snapshot creation would delete about {count} snapshots, oldest first:\n\n{snapshots}",
+            report.snapshots.len() as u64,
+            &[
+                ("count", &report.snapshots.len().to_string()),
+                ("snapshots", &listing),
+            ],
+        )
+    };
+
+    if !report.skipped_favorites.is_empty() {
+        message.push_str("\n\n");
+        message.push_str(&gettext_f(
+            "Favorites and milestones are never deleted: {snapshots}",
+            &[("snapshots", &report.skipped_favorites.join(", "))],
+        ));
+    }
+
+    message
+}
+
+/// Save the snapshot size warning threshold without touching quota state
+fn save_size_warning(parent: &adw::ApplicationWindow, warning_gb: f64) {
+    let mut config = QuotaConfig::load().unwrap_or_default();
+    config.size_warning_bytes = if warning_gb > 0.0 {
+        Some((warning_gb * 1024.0 * 1024.0 * 1024.0) as u64)
+    } else {
+        None
+    };
+
+    let result = toml::to_string_pretty(&config)
+        .map_err(anyhow::Error::from)
+        .and_then(|config_toml| {
+            let client = WaypointHelperClient::new()?;
+            client.save_quota_config(config_toml)
+        });
+
+    match result {
+        Ok(msg) => {
+            log::info!("{msg}");
+            dialogs::show_toast(parent, &gettext("Quota settings saved"));
+        }
+        Err(e) => {
+            dialogs::show_error(
+                parent,
+                &gettext("Save Failed"),
+                &gettext_f(
+                    "Failed to save quota settings: {error}",
+                    &[("error", &e.to_string())],
+                ),
+            );
+        }
+    }
+}
+
 /// Save quota configuration (called by auto-save handlers)
 fn save_quota_config(
     parent: &adw::ApplicationWindow,
@@ -369,19 +744,24 @@ fn save_quota_config(
         per_snapshot_limit_bytes: None, // Not configurable in UI yet
         cleanup_threshold,
         auto_cleanup,
+        // Saved separately by its own handler, keep the stored value
+        size_warning_bytes: QuotaConfig::load().ok().and_then(|c| c.size_warning_bytes),
     };
 
     // Apply quota settings via D-Bus
     if let Err(e) = apply_quota_settings(parent, &new_config) {
         dialogs::show_error(
             parent,
-            "Save Failed",
-            &format!("Failed to save quota settings: {e}"),
+            &gettext("Save Failed"),
+            &gettext_f(
+                "Failed to save quota settings: {error}",
+                &[("error", &e.to_string())],
+            ),
         );
         return;
     }
 
-    dialogs::show_toast(parent, "Quota settings saved");
+    dialogs::show_toast(parent, &gettext("Quota settings saved"));
 }
 
 /// Apply quota settings via D-Bus
@@ -1,9 +1,12 @@
 use gtk::prelude::*;
-use gtk::{Box, Button, DrawingArea, Label, Orientation, Switch};
+use gtk::{Box, Button, CheckButton, DrawingArea, Label, Orientation, Switch};
 use std::cell::RefCell;
+use std::path::PathBuf;
 use std::rc::Rc;
 use waypoint_common::{Schedule, ScheduleType};
 
+use crate::i18n::{gettext, gettext_f, ngettext_f};
+
 /// Data for rendering a sparkline (snapshot history)
 #[derive(Debug, Clone)]
 pub struct SparklineData {
@@ -48,12 +51,20 @@ pub struct ScheduleCard {
     last_run_label: Label,
     /// Retention summary label
     retention_label: Label,
+    /// One check button per subvolume, unticked while it is temporarily excluded
+    subvolumes_box: Box,
+    /// Warning naming the temporarily excluded subvolumes
+    excluded_label: Label,
+    /// Called with a subvolume and whether it is included now
+    subvolume_toggled: Rc<RefCell<Option<Rc<dyn Fn(PathBuf, bool)>>>>,
     /// Sparkline drawing area
     sparkline: DrawingArea,
     /// Sparkline data
     sparkline_data: Rc<RefCell<SparklineData>>,
     /// Edit button
     edit_button: Button,
+    /// Button creating a snapshot with this schedule's settings right away
+    run_now_button: Button,
 }
 
 impl ScheduleCard {
@@ -90,8 +101,16 @@ impl ScheduleCard {
         spacer.set_hexpand(true);
         header_box.append(&spacer);
 
+        // Run now button, to try the schedule without waiting for it
+        let run_now_button = Button::with_label(&gettext("Run Now"));
+        run_now_button.add_css_class("flat");
+        run_now_button.set_tooltip_text(Some(&gettext(
+            "Create a snapshot with this schedule's settings now; the schedule's timing is unchanged",
+        )));
+        header_box.append(&run_now_button);
+
         // Edit button (only visible when enabled)
-        let edit_button = Button::with_label("Edit");
+        let edit_button = Button::with_label(&gettext("Edit"));
         edit_button.add_css_class("flat");
         edit_button.set_visible(schedule.enabled);
         header_box.append(&edit_button);
@@ -105,13 +124,13 @@ impl ScheduleCard {
         info_box.set_visible(schedule.enabled);
 
         // Next run
-        let next_run_label = Label::new(Some("Next run: calculating..."));
+        let next_run_label = Label::new(Some(&gettext("Next run: calculating...")));
         next_run_label.add_css_class("body");
         next_run_label.set_halign(gtk::Align::Start);
         info_box.append(&next_run_label);
 
         // Last run
-        let last_run_label = Label::new(Some("Last success: never"));
+        let last_run_label = Label::new(Some(&gettext("Last success: never")));
         last_run_label.add_css_class("body");
         last_run_label.set_halign(gtk::Align::Start);
         info_box.append(&last_run_label);
@@ -128,16 +147,25 @@ impl ScheduleCard {
             &schedule.prefix
         };
 
-        let retention_text = format!(
-            "Retention: {} snapshots • Prefix: {}-",
-            schedule.keep_count, prefix_display
-        );
+        let retention_text = retention_text(schedule.keep_count, prefix_display);
         let retention_label = Label::new(Some(&retention_text));
         retention_label.add_css_class("body");
         retention_label.add_css_class("dim-label");
         retention_label.set_halign(gtk::Align::Start);
         info_box.append(&retention_label);
 
+        // Subvolumes, each of which can be left out for a while
+        let subvolumes_box = Box::new(Orientation::Horizontal, 6);
+        subvolumes_box.set_halign(gtk::Align::Start);
+        info_box.append(&subvolumes_box);
+
+        let excluded_label = Label::new(None);
+        excluded_label.add_css_class("caption");
+        excluded_label.add_css_class("warning");
+        excluded_label.set_halign(gtk::Align::Start);
+        excluded_label.set_wrap(true);
+        info_box.append(&excluded_label);
+
         widget.append(&info_box);
 
         // Sparkline section (only shown when enabled)
@@ -172,7 +200,7 @@ impl ScheduleCard {
 
         widget.append(&sparkline_box);
 
-        Self {
+        let card = Self {
             widget,
             schedule,
             enable_switch,
@@ -181,19 +209,76 @@ impl ScheduleCard {
             next_run_label,
             last_run_label,
             retention_label,
+            subvolumes_box,
+            excluded_label,
+            subvolume_toggled: Rc::new(RefCell::new(None)),
             sparkline,
             sparkline_data,
             edit_button,
+            run_now_button,
+        };
+        card.update_subvolumes();
+        card
+    }
+
+    /// Rebuild the subvolume check buttons and the exclusion warning
+    fn update_subvolumes(&self) {
+        while let Some(child) = self.subvolumes_box.first_child() {
+            self.subvolumes_box.remove(&child);
+        }
+
+        let title = Label::new(Some(&gettext("Subvolumes:")));
+        title.add_css_class("dim-label");
+        self.subvolumes_box.append(&title);
+
+        let mut excluded = Vec::new();
+        for subvolume in self.schedule.snapshot_subvolumes() {
+            let path = PathBuf::from(&subvolume);
+            let is_excluded = self.schedule.is_subvolume_excluded(&path);
+            if is_excluded {
+                excluded.push(subvolume.clone());
+            }
+
+            let check = CheckButton::with_label(&subvolume);
+            check.set_active(!is_excluded);
+            check.set_tooltip_text(Some(&if is_excluded {
+                gettext("Temporarily excluded; tick to include it in snapshots again")
+            } else {
+                gettext(
+                    "Untick to leave this subvolume out of snapshots for now, without editing the schedule",
+                )
+            }));
+
+            let toggled = self.subvolume_toggled.clone();
+            check.connect_toggled(move |check| {
+                let callback = toggled.borrow().clone();
+                if let Some(callback) = callback {
+                    callback(path.clone(), check.is_active());
+                }
+            });
+            self.subvolumes_box.append(&check);
+        }
+
+        self.excluded_label.set_visible(!excluded.is_empty());
+        if self.schedule.active_subvolumes().is_empty() {
+            self.excluded_label.set_text(&gettext(
+                "All subvolumes are temporarily excluded, so this schedule creates no snapshots",
+            ));
+        } else {
+            self.excluded_label.set_text(&gettext_f(
+                "Temporarily excluded from snapshots: {subvolumes}",
+                &[("subvolumes", &excluded.join(", "))],
+            ));
         }
     }
 
     /// Get the title for a schedule type
     fn get_schedule_title(schedule_type: &ScheduleType) -> String {
         match schedule_type {
-            ScheduleType::Hourly => "Hourly Snapshots".to_string(),
-            ScheduleType::Daily => "Daily Snapshots".to_string(),
-            ScheduleType::Weekly => "Weekly Snapshots".to_string(),
-            ScheduleType::Monthly => "Monthly Snapshots".to_string(),
+            ScheduleType::Hourly => gettext("Hourly Snapshots"),
+            ScheduleType::Daily => gettext("Daily Snapshots"),
+            ScheduleType::Weekly => gettext("Weekly Snapshots"),
+            ScheduleType::Monthly => gettext("Monthly Snapshots"),
         }
     }
 
@@ -262,15 +347,18 @@ impl ScheduleCard {
     /// Update the next run time display
     #[allow(dead_code)]
     pub fn set_next_run(&mut self, text: &str) {
-        self.next_run_label.set_text(&format!("Next run: {text}"));
+        self.next_run_label
+            .set_text(&gettext_f("Next run: {time}", &[("time", text)]));
     }
 
     /// Update the last run display
     #[allow(dead_code)]
     pub fn set_last_run(&mut self, text: &str, success: bool) {
         let icon = if success { "✓" } else { "✗" };
-        self.last_run_label
-            .set_text(&format!("Last success: {text} {icon}"));
+        self.last_run_label.set_text(&gettext_f(
+            "Last success: {time} {icon}",
+            &[("time", text), ("icon", icon)],
+        ));
     }
 
     /// Add sparkline data point
@@ -283,8 +371,8 @@ impl ScheduleCard {
     /// Update retention display
     #[allow(dead_code)]
     pub fn set_retention(&mut self, keep_count: u32, prefix: &str) {
-        let text = format!("Retention: {keep_count} snapshots • Prefix: {prefix}-");
-        self.retention_label.set_text(&text);
+        self.retention_label
+            .set_text(&retention_text(keep_count, prefix));
     }
 
     /// Get the enable switch widget
@@ -297,6 +385,16 @@ impl ScheduleCard {
         &self.edit_button
     }
 
+    /// Get the run now button widget
+    pub fn run_now_button(&self) -> &Button {
+        &self.run_now_button
+    }
+
+    /// Call `f` with a subvolume and whether it is included whenever its check button is toggled
+    pub fn connect_subvolume_toggled<F: Fn(PathBuf, bool) + 'static>(&self, f: F) {
+        *self.subvolume_toggled.borrow_mut() = Some(Rc::new(f));
+    }
+
     /// Get the schedule
     pub fn schedule(&self) -> &Schedule {
         &self.schedule
@@ -318,11 +416,9 @@ impl ScheduleCard {
             &schedule.prefix
         };
 
-        let retention_text = format!(
-            "Retention: {} snapshots • Prefix: {}-",
-            schedule.keep_count, prefix_display
-        );
+        let retention_text = retention_text(schedule.keep_count, prefix_display);
         self.retention_label.set_text(&retention_text);
+        self.update_subvolumes();
 
         // Show/hide elements based on enabled state
         self.info_box.set_visible(schedule.enabled);
@@ -335,3 +431,13 @@ impl ScheduleCard {
         &self.widget
     }
 }
+
+/// Summary of a schedule's retention and snapshot name prefix
+fn retention_text(keep_count: u32, prefix: &str) -> String {
+    ngettext_f(
+        "Retention: {count} snapshot • Prefix: {prefix}-",
+        "Retention: {count} snapshots • Prefix: {prefix}-",
+        u64::from(keep_count),
+        &[("count", &keep_count.to_string()), ("prefix", prefix)],
+    )
+}
@@ -3,9 +3,10 @@ use gtk::prelude::*;
 use gtk::{Box, CheckButton, Label, Orientation, SpinButton};
 use libadwaita as adw;
 use std::path::PathBuf;
-use waypoint_common::{Schedule, ScheduleType};
+use waypoint_common::{Schedule, ScheduleType, SnapshotCategory};
 
-use crate::subvolume::{detect_mounted_subvolumes, should_allow_snapshot};
+use crate::i18n::{gettext, gettext_f};
+use crate::subvolume::detect_snapshot_candidates;
 
 /// Create a modal dialog for editing a schedule
 pub fn create_schedule_edit_dialog(
@@ -13,9 +14,9 @@ pub fn create_schedule_edit_dialog(
     schedule: Schedule,
 ) -> adw::PreferencesWindow {
     let dialog = adw::PreferencesWindow::new();
-    let title = format!(
-        "Edit {} Schedule",
-        get_schedule_name(&schedule.schedule_type)
+    let title = gettext_f(
+        "Edit {schedule} Schedule",
+        &[("schedule", &get_schedule_name(&schedule.schedule_type))],
     );
     dialog.set_title(Some(&title));
     dialog.set_modal(true);
@@ -26,7 +27,7 @@ pub fn create_schedule_edit_dialog(
 
     // Schedule configuration group
     let config_group = adw::PreferencesGroup::new();
-    config_group.set_title("Schedule");
+    config_group.set_title(&gettext("Schedule"));
     page.add(&config_group);
 
     // Time setting (for all except hourly)
@@ -56,9 +57,13 @@ pub fn create_schedule_edit_dialog(
         None
     };
 
+    // Time of day window the schedule may fire in
+    let active_hours_row = create_active_hours_row(&schedule);
+    config_group.add(&active_hours_row);
+
     // Naming group
     let naming_group = adw::PreferencesGroup::new();
-    naming_group.set_title("Naming");
+    naming_group.set_title(&gettext("Naming"));
     page.add(&naming_group);
 
     let prefix_row = create_prefix_row(&schedule);
@@ -86,12 +91,15 @@ pub fn create_schedule_edit_dialog(
 
     naming_group.add(&preview_label);
 
+    let category_row = create_category_row(&schedule);
+    naming_group.add(&category_row);
+
     // Subvolumes group
     let subvolumes_group = adw::PreferencesGroup::new();
-    subvolumes_group.set_title("Subvolumes to Snapshot");
-    subvolumes_group.set_description(Some(
+    subvolumes_group.set_title(&gettext("Subvolumes to Snapshot"));
+    subvolumes_group.set_description(Some(&gettext(
         "Select which btrfs subvolumes to include in this schedule's snapshots",
-    ));
+    )));
     page.add(&subvolumes_group);
 
     let subvolume_checkboxes = create_subvolume_selection(&schedule);
@@ -99,12 +107,25 @@ pub fn create_schedule_edit_dialog(
         subvolumes_group.add(checkbox_row);
     }
 
+    // Backup group
+    let backup_group = adw::PreferencesGroup::new();
+    backup_group.set_title(&gettext("Backups"));
+    page.add(&backup_group);
+
+    let auto_backup_row = adw::SwitchRow::new();
+    auto_backup_row.set_title(&gettext("Back Up Automatically"));
+    auto_backup_row.set_subtitle(&gettext(
+        "Queue this schedule's snapshots for automatic backup. Favorites are always backed up.",
+    ));
+    auto_backup_row.set_active(schedule.auto_backup);
+    backup_group.add(&auto_backup_row);
+
     // Retention group with timeline-based retention
     let retention_group = adw::PreferencesGroup::new();
-    retention_group.set_title("Retention Policy");
-    retention_group.set_description(Some(
+    retention_group.set_title(&gettext("Retention Policy"));
+    retention_group.set_description(Some(&gettext(
         "Timeline-based retention keeps the most recent snapshot in each time period",
-    ));
+    )));
     page.add(&retention_group);
 
     // Add timeline retention expander
@@ -113,10 +134,10 @@ pub fn create_schedule_edit_dialog(
 
     // Legacy retention (for backward compatibility, hidden by default)
     let legacy_group = adw::PreferencesGroup::new();
-    legacy_group.set_title("Legacy Retention (Deprecated)");
-    legacy_group.set_description(Some(
+    legacy_group.set_title(&gettext("Legacy Retention (Deprecated)"));
+    legacy_group.set_description(Some(&gettext(
         "Simple count and age limits. Use timeline retention instead for better control.",
-    ));
+    )));
     legacy_group.set_visible(false);
     page.add(&legacy_group);
 
@@ -141,8 +162,11 @@ pub fn create_schedule_edit_dialog(
         if let Some(day_row) = day_of_month_row_opt {
             dialog.set_data("day_of_month_row", day_row);
         }
+        dialog.set_data("active_hours_row", active_hours_row.clone());
         dialog.set_data("prefix_row", prefix_row.clone());
         dialog.set_data("subvolume_checkboxes", subvolume_checkboxes);
+        dialog.set_data("auto_backup_row", auto_backup_row.clone());
+        dialog.set_data("category_row", category_row.clone());
         dialog.set_data("timeline_expander", timeline_expander.clone());
         dialog.set_data("keep_count_row", keep_count_row.clone());
         dialog.set_data("keep_days_row", keep_days_row.clone());
@@ -152,28 +176,36 @@ pub fn create_schedule_edit_dialog(
 }
 
 /// Get the display name for a schedule type
-fn get_schedule_name(schedule_type: &ScheduleType) -> &str {
+fn get_schedule_name(schedule_type: &ScheduleType) -> String {
     match schedule_type {
-        ScheduleType::Hourly => "Hourly",
-        ScheduleType::Daily => "Daily",
-        ScheduleType::Weekly => "Weekly",
-        ScheduleType::Monthly => "Monthly",
+        ScheduleType::Hourly => gettext("Hourly"),
+        ScheduleType::Daily => gettext("Daily"),
+        ScheduleType::Weekly => gettext("Weekly"),
+        ScheduleType::Monthly => gettext("Monthly"),
     }
 }
 
 /// Create time selection row
 fn create_time_row(schedule: &Schedule) -> adw::ActionRow {
     let row = adw::ActionRow::new();
-    row.set_title("Time");
-    row.set_subtitle("Time of day to create snapshot (24-hour format)");
+    row.set_title(&gettext("Time"));
+    row.set_subtitle(&gettext("Time of day to create snapshot (24-hour format)"));
+
+    let time = schedule.time.as_deref().unwrap_or("02:00");
+    add_time_spins(&row, time, 2.0);
 
-    let default_time = "02:00".to_string();
-    let time = schedule.time.as_ref().unwrap_or(&default_time);
+    row
+}
+
+/// Add hour and minute spin buttons showing `time` (HH:MM) to a row
+///
+/// `time_row_value` reads them back.
+fn add_time_spins(row: &adw::ActionRow, time: &str, default_hour: f64) {
     let time_parts: Vec<&str> = time.split(':').collect();
     let hour = time_parts
         .first()
         .and_then(|h| h.parse::<f64>().ok())
-        .unwrap_or(2.0);
+        .unwrap_or(default_hour);
     let minute = time_parts
         .get(1)
         .and_then(|m| m.parse::<f64>().ok())
@@ -204,6 +236,73 @@ fn create_time_row(schedule: &Schedule) -> adw::ActionRow {
         row.set_data("hour_spin", hour_spin);
         row.set_data("minute_spin", minute_spin);
     }
+}
+
+/// Time (HH:MM) set in a row built with `add_time_spins`
+fn time_row_value(row: &adw::ActionRow) -> Option<String> {
+    unsafe {
+        let hour_spin = row.data::<SpinButton>("hour_spin")?;
+        let minute_spin = row.data::<SpinButton>("minute_spin")?;
+        let hour = hour_spin.as_ref().value() as u32;
+        let minute = minute_spin.as_ref().value() as u32;
+        Some(format!("{hour:02}:{minute:02}"))
+    }
+}
+
+/// Create the expander restricting the schedule to a time of day window
+fn create_active_hours_row(schedule: &Schedule) -> adw::ExpanderRow {
+    let expander = adw::ExpanderRow::new();
+    expander.set_title(&gettext("Active Hours"));
+    expander.set_subtitle(&gettext(
+        "Only create snapshots within this time of day; may cross midnight",
+    ));
+    expander.set_show_enable_switch(true);
+    expander.set_enable_expansion(schedule.active_window().is_some());
+
+    let start_row = adw::ActionRow::new();
+    start_row.set_title(&gettext("From"));
+    add_time_spins(
+        &start_row,
+        schedule.active_start.as_deref().unwrap_or("08:00"),
+        8.0,
+    );
+    expander.add_row(&start_row);
+
+    let end_row = adw::ActionRow::new();
+    end_row.set_title(&gettext("Until"));
+    add_time_spins(
+        &end_row,
+        schedule.active_end.as_deref().unwrap_or("22:00"),
+        22.0,
+    );
+    expander.add_row(&end_row);
+
+    unsafe {
+        expander.set_data("start_row", start_row);
+        expander.set_data("end_row", end_row);
+    }
+
+    expander
+}
+
+/// Create the row choosing the category of the schedule's snapshots
+fn create_category_row(schedule: &Schedule) -> adw::ComboRow {
+    let row = adw::ComboRow::new();
+    row.set_title(&gettext("Category"));
+    row.set_subtitle(&gettext(
+        "Milestone snapshots are never deleted by retention",
+    ));
+
+    let labels: Vec<&str> = SnapshotCategory::ALL
+        .iter()
+        .map(|category| category.label())
+        .collect();
+    row.set_model(Some(&gtk::StringList::new(&labels)));
+    let selected = SnapshotCategory::ALL
+        .iter()
+        .position(|category| *category == schedule.category)
+        .unwrap_or(0);
+    row.set_selected(selected as u32);
 
     row
 }
@@ -211,17 +310,17 @@ fn create_time_row(schedule: &Schedule) -> adw::ActionRow {
 /// Create day of week selection row
 fn create_day_of_week_row(schedule: &Schedule) -> adw::ComboRow {
     let row = adw::ComboRow::new();
-    row.set_title("Day of Week");
-    row.set_subtitle("Which day to create weekly snapshots");
+    row.set_title(&gettext("Day of Week"));
+    row.set_subtitle(&gettext("Which day to create weekly snapshots"));
 
     let day_items = gtk::StringList::new(&[
-        "Sunday",
-        "Monday",
-        "Tuesday",
-        "Wednesday",
-        "Thursday",
-        "Friday",
-        "Saturday",
+        &gettext("Sunday"),
+        &gettext("Monday"),
+        &gettext("Tuesday"),
+        &gettext("Wednesday"),
+        &gettext("Thursday"),
+        &gettext("Friday"),
+        &gettext("Saturday"),
     ]);
     row.set_model(Some(&day_items));
     row.set_selected(schedule.day_of_week.unwrap_or(0) as u32);
@@ -232,8 +331,8 @@ fn create_day_of_week_row(schedule: &Schedule) -> adw::ComboRow {
 /// Create day of month selection row
 fn create_day_of_month_row(schedule: &Schedule) -> adw::ActionRow {
     let row = adw::ActionRow::new();
-    row.set_title("Day of Month");
-    row.set_subtitle("Which day of the month to create snapshots");
+    row.set_title(&gettext("Day of Month"));
+    row.set_subtitle(&gettext("Which day of the month to create snapshots"));
 
     let day_spin = SpinButton::with_range(1.0, 31.0, 1.0);
     day_spin.set_value(schedule.day_of_month.unwrap_or(1) as f64);
@@ -252,7 +351,7 @@ fn create_day_of_month_row(schedule: &Schedule) -> adw::ActionRow {
 /// Create prefix entry row
 fn create_prefix_row(schedule: &Schedule) -> adw::EntryRow {
     let row = adw::EntryRow::new();
-    row.set_title("Prefix");
+    row.set_title(&gettext("Prefix"));
     row.set_text(&schedule.prefix);
     row
 }
@@ -260,8 +359,10 @@ fn create_prefix_row(schedule: &Schedule) -> adw::EntryRow {
 /// Create keep count row
 fn create_keep_count_row(schedule: &Schedule) -> adw::ActionRow {
     let row = adw::ActionRow::new();
-    row.set_title("Keep Count");
-    row.set_subtitle("Maximum number of snapshots to keep (0 = unlimited)");
+    row.set_title(&gettext("Keep Count"));
+    row.set_subtitle(&gettext(
+        "Maximum number of snapshots to keep (0 = unlimited)",
+    ));
 
     let spin = SpinButton::with_range(0.0, 100.0, 1.0);
     spin.set_value(schedule.keep_count as f64);
@@ -280,8 +381,8 @@ fn create_keep_count_row(schedule: &Schedule) -> adw::ActionRow {
 /// Create keep days row
 fn create_keep_days_row(schedule: &Schedule) -> adw::ActionRow {
     let row = adw::ActionRow::new();
-    row.set_title("Keep Days");
-    row.set_subtitle("Maximum age of snapshots in days (0 = unlimited)");
+    row.set_title(&gettext("Keep Days"));
+    row.set_subtitle(&gettext("Maximum age of snapshots in days (0 = unlimited)"));
 
     let spin = SpinButton::with_range(0.0, 365.0, 1.0);
     spin.set_value(schedule.keep_days as f64);
@@ -302,8 +403,8 @@ fn create_timeline_retention_expander(schedule: &Schedule) -> adw::ExpanderRow {
     use waypoint_common::TimelineRetention;
 
     let expander = adw::ExpanderRow::new();
-    expander.set_title("Timeline Retention");
-    expander.set_subtitle("Keep most recent snapshot in each time period");
+    expander.set_title(&gettext("Timeline Retention"));
+    expander.set_subtitle(&gettext("Keep most recent snapshot in each time period"));
 
     // Get current timeline retention or create default
     let timeline = schedule.timeline_retention.as_ref()
@@ -317,41 +418,46 @@ fn create_timeline_retention_expander(schedule: &Schedule) -> adw::ExpanderRow {
 
     // Hourly retention row
     let hourly_row = create_timeline_bucket_row(
-        "Hourly",
-        "Keep last N hours (0 = disabled)",
-        timeline.hourly_limit
+        &gettext("Hourly"),
+        &gettext("Keep last N hours (0 = disabled)"),
+        timeline.hourly_limit,
+        timeline.hourly_for.as_deref(),
     );
     expander.add_row(&hourly_row);
 
     // Daily retention row
     let daily_row = create_timeline_bucket_row(
-        "Daily",
-        "Keep last N days (0 = disabled)",
-        timeline.daily_limit
+        &gettext("Daily"),
+        &gettext("Keep last N days (0 = disabled)"),
+        timeline.daily_limit,
+        timeline.daily_for.as_deref(),
     );
     expander.add_row(&daily_row);
 
     // Weekly retention row
     let weekly_row = create_timeline_bucket_row(
-        "Weekly",
-        "Keep last N weeks (0 = disabled)",
-        timeline.weekly_limit
+        &gettext("Weekly"),
+        &gettext("Keep last N weeks (0 = disabled)"),
+        timeline.weekly_limit,
+        timeline.weekly_for.as_deref(),
     );
     expander.add_row(&weekly_row);
 
     // Monthly retention row
     let monthly_row = create_timeline_bucket_row(
-        "Monthly",
-        "Keep last N months (0 = disabled)",
-        timeline.monthly_limit
+        &gettext("Monthly"),
+        &gettext("Keep last N months (0 = disabled)"),
+        timeline.monthly_limit,
+        timeline.monthly_for.as_deref(),
     );
     expander.add_row(&monthly_row);
 
     // Yearly retention row
     let yearly_row = create_timeline_bucket_row(
-        "Yearly",
-        "Keep last N years (0 = disabled)",
-        timeline.yearly_limit
+        &gettext("Yearly"),
+        &gettext("Keep last N years (0 = disabled)"),
+        timeline.yearly_limit,
+        timeline.yearly_for.as_deref(),
     );
     expander.add_row(&yearly_row);
 
@@ -368,31 +474,84 @@ fn create_timeline_retention_expander(schedule: &Schedule) -> adw::ExpanderRow {
 }
 
 /// Create a single timeline bucket row
-fn create_timeline_bucket_row(title: &str, subtitle: &str, initial_value: u32) -> adw::ActionRow {
+///
+/// Besides the count, the row takes an optional duration such as "2w" or
+/// "3mo", which replaces the count when set. The entry is checked as you type.
+fn create_timeline_bucket_row(
+    title: &str,
+    subtitle: &str,
+    initial_value: u32,
+    initial_duration: Option<&str>,
+) -> adw::ActionRow {
     let row = adw::ActionRow::new();
     row.set_title(title);
     row.set_subtitle(subtitle);
 
+    let duration_entry = gtk::Entry::new();
+    duration_entry.set_placeholder_text(Some(&gettext("or e.g. 2w")));
+    duration_entry.set_width_chars(8);
+    duration_entry.set_valign(gtk::Align::Center);
+    duration_entry.set_tooltip_text(Some(&gettext("Keep for a duration: h, d, w, mo or y")));
+    row.add_suffix(&duration_entry);
+
     let spin = SpinButton::with_range(0.0, 365.0, 1.0);
     spin.set_value(initial_value as f64);
     spin.set_width_chars(5);
     spin.set_valign(gtk::Align::Center);
     row.add_suffix(&spin);
 
+    let spin_clone = spin.clone();
+    duration_entry.connect_changed(move |entry| {
+        let text = entry.text();
+        let result = if text.trim().is_empty() {
+            None
+        } else {
+            Some(waypoint_common::parse_retention_duration(&text))
+        };
+
+        match &result {
+            Some(Err(e)) => {
+                entry.add_css_class("error");
+                entry.set_tooltip_text(Some(e));
+            }
+            _ => {
+                entry.remove_css_class("error");
+                entry.set_tooltip_text(Some(&gettext("Keep for a duration: h, d, w, mo or y")));
+            }
+        }
+        // A valid duration replaces the count
+        spin_clone.set_sensitive(!matches!(result, Some(Ok(_))));
+    });
+    duration_entry.set_text(initial_duration.unwrap_or_default());
+
     // Store for later retrieval
     unsafe {
         row.set_data("limit_spin", spin);
+        row.set_data("duration_entry", duration_entry);
     }
 
     row
 }
 
+/// Duration typed into a timeline bucket row, if it is valid
+fn bucket_row_duration(row: &adw::ActionRow) -> Option<String> {
+    let entry = unsafe { row.data::<gtk::Entry>("duration_entry")?.as_ref().clone() };
+    let text = entry.text().trim().to_string();
+    waypoint_common::parse_retention_duration(&text)
+        .ok()
+        .map(|_| text)
+}
+
 /// Create subvolume selection checkboxes
+///
+/// Rows come from the currently mounted subvolumes. Paths the schedule still
+/// lists but that are no longer mounted get an unchecked, insensitive row, so
+/// saving drops them instead of the helper rejecting the configuration.
 fn create_subvolume_selection(schedule: &Schedule) -> Vec<adw::ActionRow> {
     let mut rows = Vec::new();
 
-    // Detect mounted subvolumes
-    let subvolumes = match detect_mounted_subvolumes() {
+    // Detect mounted subvolumes that can be snapshotted
+    let subvolumes = match detect_snapshot_candidates() {
         Ok(subs) => subs,
         Err(e) => {
             log::warn!("Failed to detect subvolumes: {e}");
@@ -400,12 +559,36 @@ fn create_subvolume_selection(schedule: &Schedule) -> Vec<adw::ActionRow> {
         }
     };
 
-    for subvol in subvolumes {
-        // Filter out subvolumes that should never be snapshotted
-        if !should_allow_snapshot(&subvol.subvol_path) {
-            continue;
+    for missing in schedule
+        .subvolumes
+        .iter()
+        .filter(|path| !subvolumes.iter().any(|subvol| &subvol.mount_point == *path))
+    {
+        let row = adw::ActionRow::new();
+        row.set_title(&missing.display().to_string());
+        row.set_subtitle(&gettext(
+            "No longer a mounted subvolume; removed from this schedule when saved",
+        ));
+
+        let icon = gtk::Image::from_icon_name("dialog-warning-symbolic");
+        icon.add_css_class("warning");
+        row.add_prefix(&icon);
+
+        let checkbox = CheckButton::new();
+        checkbox.set_active(false);
+        checkbox.set_sensitive(false);
+        checkbox.set_valign(gtk::Align::Center);
+        row.add_suffix(&checkbox);
+
+        unsafe {
+            row.set_data("mount_point", missing.clone());
+            row.set_data("checkbox", checkbox);
         }
 
+        rows.push(row);
+    }
+
+    for subvol in subvolumes {
         let row = adw::ActionRow::new();
         row.set_title(&subvol.display_name);
 
@@ -419,9 +602,18 @@ fn create_subvolume_selection(schedule: &Schedule) -> Vec<adw::ActionRow> {
         if subvol.mount_point == PathBuf::from("/") {
             checkbox.set_active(true);
             checkbox.set_sensitive(false);
-            row.set_subtitle(&format!("{} (Required)", subvol.subvol_path));
+            row.set_subtitle(&gettext_f(
+                "{subvolume} (Required)",
+                &[("subvolume", &subvol.subvol_path)],
+            ));
         } else {
-            row.set_subtitle(&format!("Mount point: {} ({})", subvol.mount_point.display(), subvol.subvol_path));
+            row.set_subtitle(&gettext_f(
+                "Mount point: {mount_point} ({subvolume})",
+                &[
+                    ("mount_point", &subvol.mount_point.to_string_lossy()),
+                    ("subvolume", &subvol.subvol_path),
+                ],
+            ));
         }
 
         row.add_suffix(&checkbox);
@@ -442,7 +634,11 @@ fn create_subvolume_selection(schedule: &Schedule) -> Vec<adw::ActionRow> {
 fn update_preview_label(label: &Label, prefix: &str) {
     let now = chrono::Local::now();
     let timestamp = now.format("%Y-%m-%d_%H%M").to_string();
-    let preview = format!("Preview: {prefix}-{timestamp}");
+    let preview = gettext_f(
+        "Preview: {prefix}-{timestamp}",
+        &[("prefix", prefix), ("timestamp", &timestamp)],
+    );
+
     label.set_text(&preview);
 }
 
@@ -470,8 +666,26 @@ pub fn extract_schedule_from_dialog(dialog: &adw::PreferencesWindow) -> Option<S
             keep_days: 0,
             timeline_retention: None, // Will be populated if using timeline retention
             subvolumes: Vec::new(), // Will be populated from UI
+            excluded_subvolumes: Vec::new(), // Kept from the card's schedule
+            auto_backup: true,
+            category: SnapshotCategory::Manual,
+            active_start: None,
+            active_end: None,
         };
 
+        // Extract auto-backup setting
+        if let Some(auto_backup_row) = dialog.data::<adw::SwitchRow>("auto_backup_row") {
+            schedule.auto_backup = auto_backup_row.as_ref().is_active();
+        }
+
+        // Extract category
+        if let Some(category_row) = dialog.data::<adw::ComboRow>("category_row") {
+            schedule.category = SnapshotCategory::ALL
+                .get(category_row.as_ref().selected() as usize)
+                .copied()
+                .unwrap_or_default();
+        }
+
         // Extract prefix
         if let Some(prefix_row) = dialog.data::<adw::EntryRow>("prefix_row") {
             schedule.prefix = prefix_row.as_ref().text().to_string();
@@ -507,6 +721,7 @@ pub fn extract_schedule_from_dialog(dialog: &adw::PreferencesWindow) -> Option<S
                 if let Some(spin) = hourly_row.as_ref().data::<SpinButton>("limit_spin") {
                     timeline.hourly_limit = spin.as_ref().value() as u32;
                 }
+                timeline.hourly_for = bucket_row_duration(hourly_row.as_ref());
             }
 
             // Extract daily limit
@@ -514,6 +729,7 @@ pub fn extract_schedule_from_dialog(dialog: &adw::PreferencesWindow) -> Option<S
                 if let Some(spin) = daily_row.as_ref().data::<SpinButton>("limit_spin") {
                     timeline.daily_limit = spin.as_ref().value() as u32;
                 }
+                timeline.daily_for = bucket_row_duration(daily_row.as_ref());
             }
 
             // Extract weekly limit
@@ -521,6 +737,7 @@ pub fn extract_schedule_from_dialog(dialog: &adw::PreferencesWindow) -> Option<S
                 if let Some(spin) = weekly_row.as_ref().data::<SpinButton>("limit_spin") {
                     timeline.weekly_limit = spin.as_ref().value() as u32;
                 }
+                timeline.weekly_for = bucket_row_duration(weekly_row.as_ref());
             }
 
             // Extract monthly limit
@@ -528,6 +745,7 @@ pub fn extract_schedule_from_dialog(dialog: &adw::PreferencesWindow) -> Option<S
                 if let Some(spin) = monthly_row.as_ref().data::<SpinButton>("limit_spin") {
                     timeline.monthly_limit = spin.as_ref().value() as u32;
                 }
+                timeline.monthly_for = bucket_row_duration(monthly_row.as_ref());
             }
 
             // Extract yearly limit
@@ -535,6 +753,7 @@ pub fn extract_schedule_from_dialog(dialog: &adw::PreferencesWindow) -> Option<S
                 if let Some(spin) = yearly_row.as_ref().data::<SpinButton>("limit_spin") {
                     timeline.yearly_limit = spin.as_ref().value() as u32;
                 }
+                timeline.yearly_for = bucket_row_duration(yearly_row.as_ref());
             }
 
             schedule.timeline_retention = Some(timeline);
@@ -564,11 +783,18 @@ pub fn extract_schedule_from_dialog(dialog: &adw::PreferencesWindow) -> Option<S
 
         // Extract time (for non-hourly)
         if let Some(time_row) = dialog.data::<adw::ActionRow>("time_row") {
-            if let Some(hour_spin) = time_row.as_ref().data::<SpinButton>("hour_spin") {
-                if let Some(minute_spin) = time_row.as_ref().data::<SpinButton>("minute_spin") {
-                    let hour = hour_spin.as_ref().value() as u32;
-                    let minute = minute_spin.as_ref().value() as u32;
-                    schedule.time = Some(format!("{hour:02}:{minute:02}"));
+            schedule.time = time_row_value(time_row.as_ref());
+        }
+
+        // Extract active hours
+        if let Some(expander) = dialog.data::<adw::ExpanderRow>("active_hours_row") {
+            let expander = expander.as_ref();
+            if expander.enables_expansion() {
+                if let Some(start_row) = expander.data::<adw::ActionRow>("start_row") {
+                    schedule.active_start = time_row_value(start_row.as_ref());
+                }
+                if let Some(end_row) = expander.data::<adw::ActionRow>("end_row") {
+                    schedule.active_end = time_row_value(end_row.as_ref());
                 }
             }
         }
@@ -2,6 +2,7 @@ use super::dialogs;
 use super::schedule_card::ScheduleCard;
 use super::schedule_edit_dialog;
 use crate::dbus_client::WaypointHelperClient;
+use crate::i18n::{gettext, gettext_f, ngettext_f};
 use adw::prelude::*;
 use gtk::prelude::*;
 use gtk::{Box, Label, Orientation};
@@ -20,18 +21,31 @@ fn create_scheduler_content_with_options(parent: &adw::ApplicationWindow, lazy_l
 
     // InfoBar for restart prompt (initially hidden)
     let info_bar = adw::Banner::new("");
-    info_bar.set_title("Schedules updated. Restart the service to apply changes.");
-    info_bar.set_button_label(Some("Restart Service"));
+    info_bar.set_title(&gettext(
+        "Schedules updated. Restart the service to apply changes.",
+    ));
+    info_bar.set_button_label(Some(&gettext("Restart Service")));
     info_bar.set_revealed(false);
     content_box.append(&info_bar);
 
     // Service status group
     let status_group = adw::PreferencesGroup::new();
-    status_group.set_title("Service Status");
+    status_group.set_title(&gettext("Service Status"));
     content_box.append(&status_group);
 
+    let validate_button = gtk::Button::with_label(&gettext("Validate"));
+    validate_button.add_css_class("flat");
+    validate_button.set_tooltip_text(Some(&gettext(
+        "Check the schedules and show when each one runs next",
+    )));
+    let parent_for_validate = parent.clone();
+    validate_button.connect_clicked(move |button| {
+        validate_schedules(&parent_for_validate, button);
+    });
+    status_group.set_header_suffix(Some(&validate_button));
+
     let status_row = adw::ActionRow::new();
-    status_row.set_title("Scheduler Service");
+    status_row.set_title(&gettext("Scheduler Service"));
 
     // Create box to hold icon and text
     let status_box = gtk::Box::new(gtk::Orientation::Horizontal, 6);
@@ -41,10 +55,10 @@ fn create_scheduler_content_with_options(parent: &adw::ApplicationWindow, lazy_l
     status_icon.set_valign(gtk::Align::Center);
     status_box.append(&status_icon);
 
-    let status_label = Label::new(Some(if lazy_load {
-        "Not loaded"
+    let status_label = Label::new(Some(&if lazy_load {
+        gettext("Not loaded")
     } else {
-        "Checking..."
+        gettext("Checking...")
     }));
     status_label.add_css_class("dim-label");
     status_label.set_valign(gtk::Align::Center);
@@ -55,11 +69,11 @@ fn create_scheduler_content_with_options(parent: &adw::ApplicationWindow, lazy_l
 
     // Last snapshot row
     let last_snapshot_row = adw::ActionRow::new();
-    last_snapshot_row.set_title("Last Automatic Snapshot");
-    let last_snapshot_label = Label::new(Some(if lazy_load {
-        "Not loaded"
+    last_snapshot_row.set_title(&gettext("Last Automatic Snapshot"));
+    let last_snapshot_label = Label::new(Some(&if lazy_load {
+        gettext("Not loaded")
     } else {
-        "Checking..."
+        gettext("Checking...")
     }));
     last_snapshot_label.add_css_class("dim-label");
     last_snapshot_label.set_valign(gtk::Align::Center);
@@ -71,10 +85,10 @@ fn create_scheduler_content_with_options(parent: &adw::ApplicationWindow, lazy_l
 
     // Schedules section (using PreferencesGroup like Service Status)
     let schedules_group = adw::PreferencesGroup::new();
-    schedules_group.set_title("Snapshot Schedules");
-    schedules_group.set_description(Some(
+    schedules_group.set_title(&gettext("Snapshot Schedules"));
+    schedules_group.set_description(Some(&gettext(
         "Enable multiple schedules to run concurrently with independent retention policies",
-    ));
+    )));
     content_box.append(&schedules_group);
 
     // Container for schedule cards
@@ -133,6 +147,16 @@ fn create_scheduler_content_with_options(parent: &adw::ApplicationWindow, lazy_l
                     let enabled = card_for_close.borrow().schedule().enabled;
                     edited_schedule.enabled = enabled;
 
+                    // Keep temporary exclusions of subvolumes the schedule still has
+                    edited_schedule.excluded_subvolumes = card_for_close
+                        .borrow()
+                        .schedule()
+                        .excluded_subvolumes
+                        .iter()
+                        .filter(|p| edited_schedule.subvolumes.contains(p))
+                        .cloned()
+                        .collect();
+
                     // Update the card
                     card_for_close.borrow_mut().set_schedule(edited_schedule);
 
@@ -146,6 +170,18 @@ fn create_scheduler_content_with_options(parent: &adw::ApplicationWindow, lazy_l
             dialog.present();
         });
 
+        // Wire up run now button
+        let card_clone = card.clone();
+        let schedule_cards_for_run = schedule_cards.clone();
+        let parent_for_run = parent.clone();
+
+        card.borrow()
+            .run_now_button()
+            .connect_clicked(move |button| {
+                let schedule = card_clone.borrow().schedule().clone();
+                run_schedule_now(&parent_for_run, button, schedule, &schedule_cards_for_run);
+            });
+
         // Wire up enable switch
         let card_clone = card.clone();
         let schedule_cards_clone = schedule_cards.clone();
@@ -174,6 +210,20 @@ fn create_scheduler_content_with_options(parent: &adw::ApplicationWindow, lazy_l
                 gtk::glib::Propagation::Proceed
             });
 
+        // Wire up subvolume toggles; the scheduler rereads exclusions at every run,
+        // so no restart is needed
+        let card_clone = card.clone();
+        let schedule_cards_clone = schedule_cards.clone();
+        let parent_for_subvolumes = parent.clone();
+
+        card.borrow()
+            .connect_subvolume_toggled(move |subvolume, included| {
+                let mut schedule = card_clone.borrow().schedule().clone();
+                schedule.set_subvolume_excluded(&subvolume, !included);
+                card_clone.borrow_mut().set_schedule(schedule);
+                save_all_schedules_from_cards(&parent_for_subvolumes, &schedule_cards_clone);
+            });
+
         schedule_cards.borrow_mut().push(card);
     }
 
@@ -226,17 +276,16 @@ fn calculate_next_run(schedule: &Schedule) -> String {
     use chrono::{Datelike, Duration, Local, Timelike};
 
     if !schedule.enabled {
-        return "Schedule disabled".to_string();
+        return gettext("Schedule disabled");
     }
 
     let now = Local::now();
 
     match schedule.schedule_type {
-        ScheduleType::Hourly => {
-            let next = now + Duration::hours(1);
-            let next = next.with_minute(0).unwrap().with_second(0).unwrap();
-            format_relative_time(&next.with_timezone(&chrono::Utc))
-        }
+        ScheduleType::Hourly => match schedule.next_hourly_run(now) {
+            Some(next) => format_relative_time(&next.with_timezone(&chrono::Utc)),
+            None => gettext("Outside active hours"),
+        },
         ScheduleType::Daily | ScheduleType::Weekly | ScheduleType::Monthly => {
             if let Some(ref time_str) = schedule.time {
                 let parts: Vec<&str> = time_str.split(':').collect();
@@ -326,7 +375,7 @@ fn calculate_next_run(schedule: &Schedule) -> String {
                     }
                 }
             }
-            "Configuration error".to_string()
+            gettext("Configuration error")
         }
     }
 }
@@ -337,20 +386,37 @@ fn format_relative_time(time: &chrono::DateTime<chrono::Utc>) -> String {
     let duration = time.signed_duration_since(now);
 
     if duration.num_days() > 1 {
-        format!("in {} days", duration.num_days())
+        let days = duration.num_days();
+        ngettext_f(
+            "in {count} day",
+            "in {count} days",
+            days as u64,
+            &[("count", &days.to_string())],
+        )
     } else if duration.num_days() == 1 {
         let local_time = time.with_timezone(&chrono::Local);
-        format!("tomorrow at {}", local_time.format("%H:%M"))
+        gettext_f(
+            "tomorrow at {time}",
+            &[("time", &local_time.format("%H:%M").to_string())],
+        )
     } else if duration.num_hours() > 0 {
-        if duration.num_hours() == 1 && duration.num_minutes() % 60 < 30 {
-            format!("in {} hour", duration.num_hours())
-        } else {
-            format!("in {} hours", duration.num_hours())
-        }
+        let hours = duration.num_hours();
+        ngettext_f(
+            "in {count} hour",
+            "in {count} hours",
+            hours as u64,
+            &[("count", &hours.to_string())],
+        )
     } else if duration.num_minutes() > 0 {
-        format!("in {} minutes", duration.num_minutes())
+        let minutes = duration.num_minutes();
+        ngettext_f(
+            "in {count} minute",
+            "in {count} minutes",
+            minutes as u64,
+            &[("count", &minutes.to_string())],
+        )
     } else {
-        "very soon".to_string()
+        gettext("very soon")
     }
 }
 
@@ -454,7 +520,7 @@ fn update_schedule_cards_data(schedule_cards: &Rc<RefCell<Vec<Rc<RefCell<Schedul
     // Fetch snapshots in background thread
     std::thread::spawn(move || {
         let snapshots = match WaypointHelperClient::new() {
-            Ok(client) => match client.list_snapshots() {
+            Ok(client) => match client.list_snapshot_summaries() {
                 Ok(snapshots) => snapshots,
                 Err(e) => {
                     log::error!("Failed to list snapshots: {e}");
@@ -499,17 +565,35 @@ fn update_schedule_cards_data(schedule_cards: &Rc<RefCell<Vec<Rc<RefCell<Schedul
                     if let Some(last_time) = find_last_snapshot(&snapshots, &schedule) {
                         let duration = chrono::Utc::now().signed_duration_since(last_time);
                         let time_str = if duration.num_days() > 0 {
-                            format!("{} days ago", duration.num_days())
+                            let days = duration.num_days();
+                            ngettext_f(
+                                "{count} day ago",
+                                "{count} days ago",
+                                days as u64,
+                                &[("count", &days.to_string())],
+                            )
                         } else if duration.num_hours() > 0 {
-                            format!("{} hours ago", duration.num_hours())
+                            let hours = duration.num_hours();
+                            ngettext_f(
+                                "{count} hour ago",
+                                "{count} hours ago",
+                                hours as u64,
+                                &[("count", &hours.to_string())],
+                            )
                         } else if duration.num_minutes() > 0 {
-                            format!("{} minutes ago", duration.num_minutes())
+                            let minutes = duration.num_minutes();
+                            ngettext_f(
+                                "{count} minute ago",
+                                "{count} minutes ago",
+                                minutes as u64,
+                                &[("count", &minutes.to_string())],
+                            )
                         } else {
-                            "just now".to_string()
+                            gettext("just now")
                         };
                         card.set_last_run(&time_str, true);
                     } else {
-                        card.set_last_run("never", false);
+                        card.set_last_run(&gettext("never"), false);
                     }
 
                     // Build and populate sparkline data
@@ -546,13 +630,13 @@ pub fn load_scheduler_status(content_box: &Box) {
             if let Some(status_icon) = content_box.data::<gtk::Image>("status_icon") {
                 let status_label_ref = status_label.as_ref();
                 let status_icon_ref = status_icon.as_ref();
-                status_label_ref.set_text("Checking...");
+                status_label_ref.set_text(&gettext("Checking..."));
                 update_service_status(status_label_ref, status_icon_ref);
             }
         }
         if let Some(last_snapshot_label) = content_box.data::<Label>("last_snapshot_label") {
             let last_snapshot_label_ref = last_snapshot_label.as_ref();
-            last_snapshot_label_ref.set_text("Checking...");
+            last_snapshot_label_ref.set_text(&gettext("Checking..."));
             update_last_snapshot(last_snapshot_label_ref);
         }
 
@@ -584,13 +668,7 @@ pub fn load_scheduler_status(content_box: &Box) {
 fn load_schedules_config() -> SchedulesConfig {
     use waypoint_common::WaypointConfig;
 
-    let config = WaypointConfig::new();
-
-    if config.schedules_config.exists() {
-        SchedulesConfig::load_from_file(&config.schedules_config).unwrap_or_default()
-    } else {
-        SchedulesConfig::default()
-    }
+    SchedulesConfig::load_or_default(&WaypointConfig::new().schedules_config)
 }
 
 /// Save all schedules configuration from cards
@@ -619,8 +697,11 @@ fn save_all_schedules_from_cards(
         Err(e) => {
             dialogs::show_error(
                 parent,
-                "Configuration Error",
-                &format!("Failed to serialize configuration: {e}"),
+                &gettext("Configuration Error"),
+                &gettext_f(
+                    "Failed to serialize configuration: {error}",
+                    &[("error", &e.to_string())],
+                ),
             );
             return;
         }
@@ -635,7 +716,7 @@ fn save_all_schedules_from_cards(
             let client = WaypointHelperClient::new()?;
             let (success, message) = client.save_schedules_config(config_content)?;
             if !success {
-                return Err(anyhow::anyhow!(message));
+                return Err(waypoint_common::HelperError::decode(&message).into());
             }
 
             // Note: Service restart is now separate (via InfoBar button)
@@ -656,8 +737,152 @@ fn save_all_schedules_from_cards(
                 Err(e) => {
                     dialogs::show_error(
                         &parent_clone,
-                        "Save Failed",
-                        &format!("Failed to save scheduler configuration: {e}"),
+                        &gettext("Save Failed"),
+                        &gettext_f(
+                            "Failed to save scheduler configuration: {error}",
+                            &[("error", &e.to_string())],
+                        ),
+                    );
+                }
+            }
+            gtk::glib::ControlFlow::Break
+        } else {
+            gtk::glib::ControlFlow::Continue
+        }
+    });
+}
+
+/// Create a snapshot with a schedule's settings right away
+///
+/// Uses the same name, description and subvolumes as the scheduler would,
+/// leaving out temporarily excluded ones, so
+/// users can check a schedule works without waiting for it to fire.
+fn run_schedule_now(
+    parent: &adw::ApplicationWindow,
+    button: &gtk::Button,
+    schedule: Schedule,
+    schedule_cards: &Rc<RefCell<Vec<Rc<RefCell<ScheduleCard>>>>>,
+) {
+    let snapshot_name = match schedule.snapshot_name(chrono::Local::now()) {
+        Ok(name) => name,
+        Err(e) => {
+            dialogs::show_error(parent, &gettext("Run Failed"), &e);
+            return;
+        }
+    };
+    let subvolumes = schedule.active_subvolumes();
+    if subvolumes.is_empty() {
+        dialogs::show_error(
+            parent,
+            &gettext("Run Failed"),
+            &gettext("All subvolumes of this schedule are temporarily excluded"),
+        );
+        return;
+    }
+
+    button.set_sensitive(false);
+    log::info!(
+        "Running schedule '{}' now: {snapshot_name}",
+        schedule.prefix
+    );
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let name_for_thread = snapshot_name.clone();
+
+    std::thread::spawn(move || {
+        let result = (|| -> anyhow::Result<()> {
+            let client = WaypointHelperClient::new()?;
+            let (success, message) = client.create_snapshot(
+                name_for_thread,
+                schedule.description.clone(),
+                subvolumes,
+            )?;
+            if !success {
+                return Err(waypoint_common::HelperError::decode(&message).into());
+            }
+            Ok(())
+        })();
+
+        let _ = tx.send(result);
+    });
+
+    let parent_clone = parent.clone();
+    let button_clone = button.clone();
+    let schedule_cards_clone = schedule_cards.clone();
+
+    gtk::glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
+        if let Ok(result) = rx.try_recv() {
+            button_clone.set_sensitive(true);
+            match result {
+                Ok(_) => {
+                    dialogs::show_toast(
+                        &parent_clone,
+                        &gettext_f("Snapshot '{name}' created", &[("name", &snapshot_name)]),
+                    );
+                    update_schedule_cards_data(&schedule_cards_clone);
+                }
+                Err(e) => {
+                    dialogs::show_error(
+                        &parent_clone,
+                        &gettext("Run Failed"),
+                        &gettext_f(
+                            "Failed to create snapshot '{name}': {error}",
+                            &[("name", &snapshot_name), ("error", &e.to_string())],
+                        ),
+                    );
+                }
+            }
+            gtk::glib::ControlFlow::Break
+        } else {
+            gtk::glib::ControlFlow::Continue
+        }
+    });
+}
+
+/// Check the saved schedules with the scheduler's own validation and show the report
+fn validate_schedules(parent: &adw::ApplicationWindow, button: &gtk::Button) {
+    button.set_sensitive(false);
+
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    std::thread::spawn(move || {
+        let result = std::process::Command::new("waypoint-scheduler")
+            .arg("--validate")
+            .output()
+            .map(|output| {
+                (
+                    output.status.success(),
+                    String::from_utf8_lossy(&output.stdout).trim().to_string(),
+                )
+            });
+        let _ = tx.send(result);
+    });
+
+    let parent_clone = parent.clone();
+    let button_clone = button.clone();
+
+    gtk::glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
+        if let Ok(result) = rx.try_recv() {
+            button_clone.set_sensitive(true);
+            match result {
+                Ok((true, report)) => {
+                    dialogs::show_info(&parent_clone, &gettext("Schedules Are Valid"), &report);
+                }
+                Ok((false, report)) => {
+                    dialogs::show_error(
+                        &parent_clone,
+                        &gettext("Schedule Problems Found"),
+                        &report,
+                    );
+                }
+                Err(e) => {
+                    dialogs::show_error(
+                        &parent_clone,
+                        &gettext("Validation Failed"),
+                        &gettext_f(
+                            "Failed to run waypoint-scheduler: {error}",
+                            &[("error", &e.to_string())],
+                        ),
                     );
                 }
             }
@@ -678,7 +903,7 @@ fn restart_scheduler_service(parent: &adw::ApplicationWindow) {
             let client = WaypointHelperClient::new()?;
             let (success, message) = client.restart_scheduler()?;
             if !success {
-                return Err(anyhow::anyhow!(message));
+                return Err(waypoint_common::HelperError::decode(&message).into());
             }
             Ok(())
         })();
@@ -691,13 +916,16 @@ fn restart_scheduler_service(parent: &adw::ApplicationWindow) {
         if let Ok(result) = rx.try_recv() {
             match result {
                 Ok(_) => {
-                    dialogs::show_toast(&parent_clone, "Scheduler service restarted");
+                    dialogs::show_toast(&parent_clone, &gettext("Scheduler service restarted"));
                 }
                 Err(e) => {
                     dialogs::show_error(
                         &parent_clone,
-                        "Restart Failed",
-                        &format!("Failed to restart scheduler service: {e}"),
+                        &gettext("Restart Failed"),
+                        &gettext_f(
+                            "Failed to restart scheduler service: {error}",
+                            &[("error", &e.to_string())],
+                        ),
                     );
                 }
             }
@@ -777,7 +1005,7 @@ fn update_last_snapshot(last_snapshot_label: &Label) {
 
     std::thread::spawn(move || {
         let text = match WaypointHelperClient::new() {
-            Ok(client) => match client.list_snapshots() {
+            Ok(client) => match client.list_snapshot_summaries() {
                 Ok(snapshots) => {
                     // Filter for automatic snapshots (those with schedule prefixes)
                     let auto_snapshots: Vec<_> = snapshots
@@ -795,21 +1023,39 @@ fn update_last_snapshot(last_snapshot_label: &Label) {
                         let duration = now.signed_duration_since(latest.timestamp);
 
                         if duration.num_days() > 0 {
-                            format!("{} days ago", duration.num_days())
+                            let days = duration.num_days();
+                            ngettext_f(
+                                "{count} day ago",
+                                "{count} days ago",
+                                days as u64,
+                                &[("count", &days.to_string())],
+                            )
                         } else if duration.num_hours() > 0 {
-                            format!("{} hours ago", duration.num_hours())
+                            let hours = duration.num_hours();
+                            ngettext_f(
+                                "{count} hour ago",
+                                "{count} hours ago",
+                                hours as u64,
+                                &[("count", &hours.to_string())],
+                            )
                         } else if duration.num_minutes() > 0 {
-                            format!("{} minutes ago", duration.num_minutes())
+                            let minutes = duration.num_minutes();
+                            ngettext_f(
+                                "{count} minute ago",
+                                "{count} minutes ago",
+                                minutes as u64,
+                                &[("count", &minutes.to_string())],
+                            )
                         } else {
-                            "Just now".to_string()
+                            gettext("Just now")
                         }
                     } else {
-                        "No automatic snapshots yet".to_string()
+                        gettext("No automatic snapshots yet")
                     }
                 }
-                Err(e) => format!("Error: {e}"),
+                Err(e) => gettext_f("Error: {error}", &[("error", &e.to_string())]),
             },
-            Err(_) => "Cannot connect to helper service".to_string(),
+            Err(_) => gettext("Cannot connect to helper service"),
         };
 
         let _ = tx.send(text);
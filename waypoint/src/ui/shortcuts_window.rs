@@ -4,10 +4,12 @@ use adw::prelude::*;
 use gtk::prelude::*;
 use libadwaita as adw;
 
+use crate::i18n::gettext;
+
 /// Show the keyboard shortcuts window
 pub fn show_shortcuts_window(window: &adw::ApplicationWindow) {
     let dialog = adw::Window::builder()
-        .title("Keyboard Shortcuts")
+        .title(gettext("Keyboard Shortcuts"))
         .modal(true)
         .transient_for(window)
         .default_width(500)
@@ -32,25 +34,70 @@ pub fn show_shortcuts_window(window: &adw::ApplicationWindow) {
 
     // General shortcuts group
     let general_group = adw::PreferencesGroup::builder()
-        .title("General")
+        .title(gettext("General"))
         .build();
 
-    add_shortcut_row(&general_group, "Open search", "Ctrl+F");
-    add_shortcut_row(&general_group, "Create new restore point", "Ctrl+N");
-    add_shortcut_row(&general_group, "Refresh snapshot list", "Ctrl+R or F5");
-    add_shortcut_row(&general_group, "Open preferences", "Ctrl+,");
-    add_shortcut_row(&general_group, "Show keyboard shortcuts", "Ctrl+?");
-    add_shortcut_row(&general_group, "Close search bar", "Escape");
+    add_shortcut_row(&general_group, &gettext("Open search"), "Ctrl+F");
+    add_shortcut_row(
+        &general_group,
+        &gettext("Create new restore point"),
+        "Ctrl+N",
+    );
+    add_shortcut_row(
+        &general_group,
+        &gettext("Quick snapshot (no description)"),
+        "Ctrl+Shift+N",
+    );
+    add_shortcut_row(
+        &general_group,
+        &gettext("Refresh snapshot list"),
+        "Ctrl+R or F5",
+    );
+    add_shortcut_row(&general_group, &gettext("Open preferences"), "Ctrl+,");
+    add_shortcut_row(
+        &general_group,
+        &gettext("Show keyboard shortcuts"),
+        "Ctrl+?",
+    );
+    add_shortcut_row(&general_group, &gettext("Close search bar"), "Escape");
 
     content_box.append(&general_group);
 
+    // Snapshot list shortcuts group
+    let list_group = adw::PreferencesGroup::builder()
+        .title(gettext("Restore Points"))
+        .build();
+
+    add_shortcut_row(
+        &list_group,
+        &gettext("Move between restore points"),
+        "Up or Down",
+    );
+    add_shortcut_row(
+        &list_group,
+        &gettext("Move between row buttons"),
+        "Tab or Shift+Tab",
+    );
+    add_shortcut_row(
+        &list_group,
+        &gettext("Open actions for the focused restore point"),
+        "Enter or Space",
+    );
+    add_shortcut_row(
+        &list_group,
+        &gettext("Delete the focused restore point"),
+        "Delete",
+    );
+
+    content_box.append(&list_group);
+
     // Editing shortcuts group
     let editing_group = adw::PreferencesGroup::builder()
-        .title("Note Editing")
+        .title(gettext("Note Editing"))
         .build();
 
-    add_shortcut_row(&editing_group, "Save note changes", "Ctrl+Enter");
-    add_shortcut_row(&editing_group, "Cancel note editing", "Escape");
+    add_shortcut_row(&editing_group, &gettext("Save note changes"), "Ctrl+Enter");
+    add_shortcut_row(&editing_group, &gettext("Cancel note editing"), "Escape");
 
     content_box.append(&editing_group);
 
@@ -2,18 +2,120 @@
 //!
 //! This module handles the display and filtering of snapshots in the main list view.
 
+use gtk::glib;
 use gtk::prelude::*;
 use gtk::{Button, Label, ListBox};
 use libadwaita as adw;
 use libadwaita::prelude::PreferencesRowExt;
+use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::sync::mpsc;
 
-use super::snapshot_row::{BackupStatus, SnapshotAction, SnapshotRow};
+use super::snapshot_row::{BackupStatus, ExclusiveSizes, SnapshotAction, SnapshotRow};
 use crate::backup_manager::BackupManager;
+use crate::btrfs;
+use crate::i18n::{gettext, ngettext_f};
 use crate::performance;
-use crate::snapshot::SnapshotManager;
-use crate::user_preferences::UserPreferencesManager;
+use crate::snapshot::{Snapshot, SnapshotManager};
+use crate::user_preferences::{SnapshotPreferences, UserPreferencesManager};
+use waypoint_common::{QuotaConfig, SnapshotCategory};
+use waypoint_common::capabilities::{FEATURE_EXCLUSIVE_SIZES, FEATURE_READ_ONLY_TOGGLE};
+
+/// Callback invoked with the calculated size (or `None` on failure)
+type SizeCallback = Box<dyn FnOnce(Option<u64>)>;
+
+thread_local! {
+    // Rows waiting for a background size calculation, keyed by snapshot path.
+    // A path present in this map has a calculation in flight.
+    static PENDING_SIZE_ROWS: RefCell<HashMap<PathBuf, Vec<SizeCallback>>> =
+        RefCell::new(HashMap::new());
+
+    // Read-only state of snapshots by name, filled in the background
+    static READ_ONLY_STATES: RefCell<HashMap<String, bool>> = RefCell::new(HashMap::new());
+
+    // Exclusive qgroup sizes of snapshots by name, filled in the background
+    static EXCLUSIVE_SIZES: RefCell<HashMap<String, u64>> = RefCell::new(HashMap::new());
+}
+
+/// Whether any row is still waiting for its size to be calculated
+pub fn sizes_pending() -> bool {
+    PENDING_SIZE_ROWS.with(|pending| !pending.borrow().is_empty())
+}
+
+/// Whether the snapshot is known to be writable
+fn is_known_writable(name: &str) -> bool {
+    READ_ONLY_STATES.with(|states| states.borrow().get(name) == Some(&false))
+}
+
+/// Cached exclusive sizes of a snapshot and its predecessor
+fn exclusive_sizes(name: &str, previous: Option<&str>) -> ExclusiveSizes {
+    EXCLUSIVE_SIZES.with(|sizes| {
+        let sizes = sizes.borrow();
+        ExclusiveSizes {
+            current: sizes.get(name).copied(),
+            previous: previous.and_then(|previous| sizes.get(previous).copied()),
+        }
+    })
+}
+
+/// Update the cached read-only state after changing it
+pub fn set_cached_read_only(name: &str, read_only: bool) {
+    READ_ONLY_STATES.with(|states| {
+        states.borrow_mut().insert(name.to_string(), read_only);
+    });
+}
+
+/// How the snapshot list is laid out
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ListDisplaySettings {
+    /// Single-line rows with details in tooltips, to fit more snapshots on screen
+    pub compact: bool,
+}
+
+/// Path of the list display settings file
+fn display_settings_path() -> PathBuf {
+    dirs::config_local_dir()
+        .map(|d| d.join("waypoint").join("list-display.json"))
+        .unwrap_or_else(|| PathBuf::from("/tmp/waypoint-list-display.json"))
+}
+
+/// Load list display settings, falling back to defaults
+pub fn load_display_settings() -> ListDisplaySettings {
+    let config_path = display_settings_path();
+
+    if !config_path.exists() {
+        return ListDisplaySettings::default();
+    }
+
+    match std::fs::read_to_string(&config_path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::error!("Failed to parse list display settings: {e}");
+            ListDisplaySettings::default()
+        }),
+        Err(e) => {
+            log::error!("Failed to read list display settings: {e}");
+            ListDisplaySettings::default()
+        }
+    }
+}
+
+/// Save list display settings to disk
+pub fn save_display_settings(settings: &ListDisplaySettings) -> anyhow::Result<()> {
+    let config_path = display_settings_path();
+
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let content = serde_json::to_string_pretty(settings)?;
+    std::fs::write(&config_path, content)?;
+
+    Ok(())
+}
 
 /// Date filter options for snapshot list
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -29,6 +131,9 @@ pub enum DateFilter {
 }
 
 /// Compute the backup status for a snapshot
+///
+/// Backup records and queue entries are keyed by the snapshot's name, which is
+/// also its directory name.
 fn compute_backup_status(
     snapshot_id: &str,
     backup_manager: &Rc<RefCell<BackupManager>>,
@@ -38,13 +143,13 @@ fn compute_backup_status(
     let bm = backup_manager.borrow();
     let config = match bm.get_config() {
         Ok(c) => c,
-        Err(_) => return BackupStatus::NotBackedUp,
+        Err(_) => return BackupStatus::NoDestinations,
     };
 
     // Count enabled destinations
     let enabled_count = config.enabled_destinations().count();
     if enabled_count == 0 {
-        return BackupStatus::NotBackedUp;
+        return BackupStatus::NoDestinations;
     }
 
     // Use helper method to get backup destinations
@@ -72,7 +177,7 @@ fn compute_backup_status(
     } else if backed_up_count == 0 {
         BackupStatus::NotBackedUp
     } else if backed_up_count >= enabled_count {
-        BackupStatus::FullyBackedUp
+        BackupStatus::FullyBackedUp(backed_up_count)
     } else {
         BackupStatus::PartiallyBackedUp(backed_up_count, enabled_count)
     }
@@ -90,6 +195,9 @@ fn compute_backup_status(
 /// * `compare_btn` - Compare button to enable/disable based on snapshot count
 /// * `search_text` - Optional text filter to search snapshot names and descriptions
 /// * `date_filter` - Optional date range filter
+/// * `subvolume_filter` - Optional mount point that matching snapshots must include
+/// * `category_filter` - Optional category that matching snapshots must have
+/// * `not_backed_up_only` - Only show snapshots without a backup on any destination
 /// * `match_label` - Optional label to show "X of Y snapshots" count
 /// * `action_handler` - Callback to handle snapshot actions (delete, restore, browse, etc.)
 ///
@@ -98,6 +206,9 @@ fn compute_backup_status(
 /// - Loads snapshots from the manager
 /// - Applies text filter (case-insensitive search in name/description)
 /// - Applies date filter (age-based filtering)
+/// - Applies subvolume filter (snapshots without recorded subvolumes count as root-only)
+/// - Applies category filter
+/// - Applies backup filter
 /// - Updates match count label if provided
 /// - Enables/disables compare button (requires ≥2 snapshots)
 /// - Shows placeholder if no snapshots match
@@ -111,6 +222,9 @@ pub fn refresh_snapshot_list_internal(
     compare_btn: &Button,
     search_text: Option<&str>,
     date_filter: Option<DateFilter>,
+    subvolume_filter: Option<&Path>,
+    category_filter: Option<SnapshotCategory>,
+    not_backed_up_only: bool,
     match_label: Option<&Label>,
     action_handler: impl Fn(&str, SnapshotAction) + 'static + Clone,
     create_btn: Option<&Button>,
@@ -124,9 +238,9 @@ pub fn refresh_snapshot_list_internal(
     }
     drop(_clear_timer);
 
-    // Load all snapshots
+    // Load all snapshots; the list only shows package counts, so skip the package lists
     let _load_timer = performance::tracker().start("load_snapshots");
-    let all_snapshots = match manager.borrow().load_snapshots() {
+    let all_snapshots = match manager.borrow().load_snapshot_summaries() {
         Ok(s) => s,
         Err(e) => {
             log::error!("Failed to load snapshots: {e}");
@@ -135,6 +249,8 @@ pub fn refresh_snapshot_list_internal(
     };
     drop(_load_timer);
 
+    super::main_window_helpers::update_header_summary(all_snapshots.len());
+
     // Apply filters if provided
     let _filter_timer = performance::tracker().start("filter_snapshots");
     let filtered_snapshots: Vec<_> =
@@ -163,7 +279,23 @@ pub fn refresh_snapshot_list_internal(
                         DateFilter::Last90Days => age_days <= 90,
                     };
 
-                    text_match && date_match
+                    // Subvolume filter (older snapshots without a subvolume list only contain root)
+                    let subvolume_match = subvolume_filter.is_none_or(|subvol| {
+                        if snapshot.subvolumes.is_empty() {
+                            subvol == Path::new("/")
+                        } else {
+                            snapshot.subvolumes.iter().any(|s| s == subvol)
+                        }
+                    });
+
+                    let category_match = category_filter.is_none_or(|c| snapshot.category == c);
+
+                    let backup_match = !not_backed_up_only
+                        || !backup_manager
+                            .borrow()
+                            .is_snapshot_backed_up(&snapshot.name);
+
+                    text_match && date_match && subvolume_match && category_match && backup_match
                 })
                 .collect()
         } else {
@@ -175,26 +307,38 @@ pub fn refresh_snapshot_list_internal(
     // Update match count label if provided
     if let Some(label) = match_label {
         let is_filtered = search_text.map(|s| !s.is_empty()).unwrap_or(false)
-            || date_filter.map(|f| f != DateFilter::All).unwrap_or(false);
+            || date_filter.map(|f| f != DateFilter::All).unwrap_or(false)
+            || subvolume_filter.is_some()
+            || category_filter.is_some()
+            || not_backed_up_only;
 
         if is_filtered {
-            label.set_text(&format!(
-                "Showing {} of {} snapshots",
-                filtered_snapshots.len(),
-                all_snapshots.len()
+            label.set_text(&ngettext_f(
+                "Showing {shown} of {total} snapshot",
+                "Showing {shown} of {total} snapshots",
+                all_snapshots.len() as u64,
+                &[
+                    ("shown", &filtered_snapshots.len().to_string()),
+                    ("total", &all_snapshots.len().to_string()),
+                ],
             ));
         } else {
-            label.set_text(&format!("{} snapshots", all_snapshots.len()));
+            label.set_text(&ngettext_f(
+                "{count} snapshot",
+                "{count} snapshots",
+                all_snapshots.len() as u64,
+                &[("count", &all_snapshots.len().to_string())],
+            ));
         }
     }
 
     // Update compare button state
     if filtered_snapshots.len() < 2 {
         compare_btn.set_sensitive(false);
-        compare_btn.set_tooltip_text(Some("At least 2 snapshots needed to compare"));
+        compare_btn.set_tooltip_text(Some(&gettext("At least 2 snapshots needed to compare")));
     } else {
         compare_btn.set_sensitive(true);
-        compare_btn.set_tooltip_text(Some("Compare packages between snapshots"));
+        compare_btn.set_tooltip_text(Some(&gettext("Compare packages between snapshots")));
     }
 
     // Display snapshots or placeholder
@@ -203,15 +347,16 @@ pub fn refresh_snapshot_list_internal(
         let placeholder = adw::StatusPage::new();
 
         if all_snapshots.is_empty() {
-            placeholder.set_title("No Restore Points Yet");
-            placeholder.set_description(Some(
+            placeholder.set_title(&gettext("No Restore Points Yet"));
+            placeholder.set_description(Some(&gettext(
                 "Restore points let you roll back your system to a previous state",
-            ));
+            )));
             placeholder.set_icon_name(Some("waypoint"));
 
             // Add prominent "Create Restore Point" button if create_btn is provided
             if let Some(main_create_btn) = create_btn {
-                let create_button = gtk::Button::with_label("Create Your First Restore Point");
+                let create_button =
+                    gtk::Button::with_label(&gettext("Create Your First Restore Point"));
                 create_button.add_css_class("pill");
                 create_button.add_css_class("suggested-action");
 
@@ -224,8 +369,10 @@ pub fn refresh_snapshot_list_internal(
                 placeholder.set_child(Some(&create_button));
             }
         } else {
-            placeholder.set_title("No Matching Snapshots");
-            placeholder.set_description(Some("No snapshots match your search criteria.\n\nTry adjusting your search or filter settings."));
+            placeholder.set_title(&gettext("No Matching Snapshots"));
+            placeholder.set_description(Some(&gettext(
+                "No snapshots match your search criteria.\n\nTry adjusting your search or filter settings.",
+            )));
 
             // Create custom icon with specific size
             let icon = gtk::Image::from_icon_name("edit-find-symbolic");
@@ -242,6 +389,21 @@ pub fn refresh_snapshot_list_internal(
         // Load user preferences
         let user_prefs = user_prefs_manager.borrow().load().unwrap_or_default();
 
+        // Exclusive size above which rows get a warning icon (requires quotas)
+        let quota_config = QuotaConfig::load().unwrap_or_default();
+        let size_warning = quota_config
+            .size_warning_bytes
+            .filter(|&bytes| quota_config.enabled && bytes > 0)
+            .filter(|_| crate::dbus_client::helper_supports(FEATURE_EXCLUSIVE_SIZES));
+
+        let compact = load_display_settings().compact;
+
+        // Snapshot paths whose size calculation should be started after the list is built
+        let mut sizes_to_calculate = Vec::new();
+
+        // Sizes are compared with the previous snapshot, filtered out or not
+        let previous = previous_snapshots(&all_snapshots);
+
         // Separate pinned and non-pinned snapshots based on user preferences
         let (pinned, regular): (Vec<_>, Vec<_>) = filtered_snapshots.into_iter().partition(|s| {
             user_prefs
@@ -250,11 +412,15 @@ pub fn refresh_snapshot_list_internal(
                 .unwrap_or(false)
         });
 
+        // Keep the snapshots from before and after a package transaction together
+        let pinned = crate::snapshot::group_transaction_pairs(pinned);
+        let regular = crate::snapshot::group_transaction_pairs(regular);
+
         // Add pinned snapshots section if any exist
         if !pinned.is_empty() {
             // Add section header for pinned snapshots
             let pinned_header = adw::ActionRow::new();
-            pinned_header.set_title("Pinned Restore Points");
+            pinned_header.set_title(&gettext("Pinned Restore Points"));
             pinned_header.add_css_class("header-row");
             pinned_header.set_activatable(false);
             list.append(&pinned_header);
@@ -262,24 +428,25 @@ pub fn refresh_snapshot_list_internal(
             // Add pinned snapshots (most recent first)
             for snapshot in pinned.iter().rev() {
                 let prefs = user_prefs.get(&snapshot.id).cloned().unwrap_or_default();
-                let backup_status = compute_backup_status(&snapshot.id, backup_manager);
-                let handler_clone = action_handler.clone();
-                let row = SnapshotRow::new_with_context(
+                let backup_status = compute_backup_status(&snapshot.name, backup_manager);
+                append_snapshot_row(
+                    list,
                     snapshot,
-                    &prefs,
-                    move |id, action| {
-                        handler_clone(&id, action);
-                    },
+                    previous.get(&snapshot.name).cloned(),
+                    prefs,
+                    backup_status,
                     max_size,
-                    &backup_status,
+                    size_warning,
+                    compact,
+                    action_handler.clone(),
+                    &mut sizes_to_calculate,
                 );
-                list.append(&row);
             }
 
             // Add section header for regular snapshots if any exist
             if !regular.is_empty() {
                 let regular_header = adw::ActionRow::new();
-                regular_header.set_title("All Restore Points");
+                regular_header.set_title(&gettext("All Restore Points"));
                 regular_header.add_css_class("header-row");
                 regular_header.set_activatable(false);
                 regular_header.set_margin_top(12);
@@ -293,18 +460,35 @@ pub fn refresh_snapshot_list_internal(
         // for expensive fields (packages, subvolumes), so cloning snapshots is cheap.
         for snapshot in regular.iter().rev() {
             let prefs = user_prefs.get(&snapshot.id).cloned().unwrap_or_default();
-            let backup_status = compute_backup_status(&snapshot.id, backup_manager);
-            let handler_clone = action_handler.clone();
-            let row = SnapshotRow::new_with_context(
+            let backup_status = compute_backup_status(&snapshot.name, backup_manager);
+            append_snapshot_row(
+                list,
                 snapshot,
-                &prefs,
-                move |id, action| {
-                    handler_clone(&id, action);
-                },
+                previous.get(&snapshot.name).cloned(),
+                prefs,
+                backup_status,
                 max_size,
-                &backup_status,
+                size_warning,
+                compact,
+                action_handler.clone(),
+                &mut sizes_to_calculate,
             );
-            list.append(&row);
+        }
+
+        calculate_missing_sizes(sizes_to_calculate);
+
+        if let Some(threshold) = size_warning {
+            let previous_names = previous
+                .iter()
+                .map(|(name, before)| (name.clone(), before.name.clone()))
+                .collect();
+            let names = all_snapshots.iter().map(|s| s.name.clone()).collect();
+            load_exclusive_sizes(list, names, previous_names, threshold);
+        }
+
+        if crate::dbus_client::helper_supports(FEATURE_READ_ONLY_TOGGLE) {
+            let names: Vec<String> = all_snapshots.iter().map(|s| s.name.clone()).collect();
+            load_read_only_states(list, names);
         }
     }
     drop(_ui_timer);
@@ -312,3 +496,342 @@ pub fn refresh_snapshot_list_internal(
     // Log performance statistics at debug level
     performance::log_stats();
 }
+
+/// Each snapshot's predecessor in time, keyed by name
+///
+/// Only snapshots of the same set of subvolumes are compared, so a root-only
+/// snapshot taken after one that also covered /home doesn't show a shrink.
+fn previous_snapshots(snapshots: &[Snapshot]) -> HashMap<String, Snapshot> {
+    let mut by_time: Vec<&Snapshot> = snapshots.iter().collect();
+    by_time.sort_by_key(|s| s.timestamp);
+
+    let mut latest: HashMap<Vec<&Path>, &Snapshot> = HashMap::new();
+    let mut previous = HashMap::new();
+    for snapshot in by_time {
+        let mut subvolumes: Vec<&Path> = snapshot.subvolumes.iter().map(PathBuf::as_path).collect();
+        subvolumes.sort();
+        subvolumes.dedup();
+        if let Some(before) = latest.insert(subvolumes, snapshot) {
+            previous.insert(snapshot.name.clone(), before.clone());
+        }
+    }
+    previous
+}
+
+/// Size of a snapshot from its metadata or the size cache
+fn known_size(snapshot: &Snapshot) -> Option<u64> {
+    snapshot
+        .size_bytes
+        .or_else(|| btrfs::cached_snapshot_size(&snapshot.path))
+}
+
+/// Append a row for a snapshot, registering it for a background size update if needed
+///
+/// Snapshots without a stored size first try the in-memory size cache. If that misses,
+/// the row shows a spinner and is replaced with a fully populated row once the size
+/// has been calculated.
+#[allow(clippy::too_many_arguments)]
+fn append_snapshot_row(
+    list: &ListBox,
+    snapshot: &Snapshot,
+    previous: Option<Snapshot>,
+    prefs: SnapshotPreferences,
+    backup_status: BackupStatus,
+    max_size: Option<u64>,
+    size_warning: Option<u64>,
+    compact: bool,
+    action_handler: impl Fn(&str, SnapshotAction) + 'static + Clone,
+    sizes_to_calculate: &mut Vec<PathBuf>,
+) {
+    let mut snapshot = snapshot.clone();
+    snapshot.size_bytes = known_size(&snapshot);
+    let size_pending = snapshot.size_bytes.is_none();
+
+    let exclusive = exclusive_sizes(
+        &snapshot.name,
+        previous.as_ref().map(|before| before.name.as_str()),
+    );
+
+    let handler_clone = action_handler.clone();
+    let row = SnapshotRow::new_with_context(
+        &snapshot,
+        &prefs,
+        move |id, action| {
+            handler_clone(&id, action);
+        },
+        max_size,
+        &backup_status,
+        size_pending,
+        size_warning,
+        exclusive,
+        previous.as_ref().and_then(known_size),
+        compact,
+    );
+    if is_known_writable(&snapshot.name) {
+        SnapshotRow::mark_writable(&row);
+    }
+    list.append(&row);
+
+    if !size_pending {
+        return;
+    }
+
+    let path = snapshot.path.clone();
+    let on_size: SizeCallback = Box::new(move |size| {
+        // The list may have been refreshed while the size was being calculated
+        let Some(list) = row.parent().and_downcast::<ListBox>() else {
+            return;
+        };
+
+        snapshot.size_bytes = size;
+        let new_row = SnapshotRow::new_with_context(
+            &snapshot,
+            &prefs,
+            move |id, action| {
+                action_handler(&id, action);
+            },
+            max_size,
+            &backup_status,
+            false,
+            size_warning,
+            // Exclusive sizes may have arrived in the meantime
+            exclusive_sizes(
+                &snapshot.name,
+                previous.as_ref().map(|before| before.name.as_str()),
+            ),
+            // Sizes calculated in the same batch are already cached
+            previous.as_ref().and_then(known_size),
+            compact,
+        );
+        if is_known_writable(&snapshot.name) {
+            SnapshotRow::mark_writable(&new_row);
+        }
+        list.insert(&new_row, row.index());
+        list.remove(&row);
+    });
+
+    let newly_pending = PENDING_SIZE_ROWS.with(|pending| {
+        let mut pending = pending.borrow_mut();
+        let newly_pending = !pending.contains_key(&path);
+        pending.entry(path.clone()).or_default().push(on_size);
+        newly_pending
+    });
+
+    if newly_pending {
+        sizes_to_calculate.push(path);
+    }
+}
+
+/// Calculate sizes for the given snapshot paths in a background thread
+///
+/// Uses the privileged helper (which prefers fast qgroup sizes when quotas are
+/// enabled) and updates every row registered for each path when results arrive.
+fn calculate_missing_sizes(paths: Vec<PathBuf>) {
+    if paths.is_empty() {
+        return;
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let paths_for_thread = paths.clone();
+    std::thread::spawn(move || {
+        let sizes = btrfs::get_all_snapshot_sizes(&paths_for_thread);
+        let _ = tx.send(sizes);
+    });
+
+    glib::spawn_future_local(async move {
+        let sizes = loop {
+            match rx.try_recv() {
+                Ok(sizes) => break sizes,
+                Err(mpsc::TryRecvError::Empty) => {
+                    glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    log::error!("Size calculation thread disconnected");
+                    break HashMap::new();
+                }
+            }
+        };
+
+        for path in paths {
+            let callbacks = PENDING_SIZE_ROWS
+                .with(|pending| pending.borrow_mut().remove(&path))
+                .unwrap_or_default();
+            let size = sizes.get(&path).copied();
+            if size.is_none() {
+                log::warn!("Could not calculate size of {}", path.display());
+            }
+            for callback in callbacks {
+                callback(size);
+            }
+        }
+    });
+}
+
+/// Query the exclusive sizes of snapshots not seen before and flag large ones
+///
+/// `previous_names` maps snapshots to their predecessor, whose size the
+/// growth warning needs. Sizes are cached for the session like read-only
+/// states, so only new snapshots cost a helper call.
+fn load_exclusive_sizes(
+    list: &ListBox,
+    names: Vec<String>,
+    previous_names: HashMap<String, String>,
+    threshold: u64,
+) {
+    let unknown: std::collections::HashSet<String> = EXCLUSIVE_SIZES.with(|sizes| {
+        let sizes = sizes.borrow();
+        names
+            .into_iter()
+            .filter(|name| !sizes.contains_key(name))
+            .collect()
+    });
+    if unknown.is_empty() {
+        return;
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let names: Vec<String> = unknown.iter().cloned().collect();
+    std::thread::spawn(move || {
+        let result = crate::dbus_client::WaypointHelperClient::new()
+            .and_then(|client| client.get_snapshot_exclusive_sizes(names));
+        let _ = tx.send(result);
+    });
+
+    let list = list.clone();
+    glib::spawn_future_local(async move {
+        let result = loop {
+            match rx.try_recv() {
+                Ok(result) => break result,
+                Err(mpsc::TryRecvError::Empty) => {
+                    glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => return,
+            }
+        };
+
+        let sizes = match result {
+            Ok(sizes) => sizes,
+            Err(e) => {
+                log::warn!("Failed to query exclusive sizes of snapshots: {e}");
+                return;
+            }
+        };
+        EXCLUSIVE_SIZES.with(|cache| cache.borrow_mut().extend(sizes));
+
+        // Rows built before these sizes were known haven't been checked yet
+        let mut child = list.first_child();
+        while let Some(widget) = child {
+            child = widget.next_sibling();
+            let Some(row) = widget.downcast_ref::<adw::ActionRow>() else {
+                continue;
+            };
+            let name = row.title().to_string();
+            let previous = previous_names.get(&name).map(String::as_str);
+            if !unknown.contains(&name) && previous.is_none_or(|p| !unknown.contains(p)) {
+                continue;
+            }
+            if let Some(note) = exclusive_sizes(&name, previous).warning(threshold) {
+                SnapshotRow::mark_size_warning(row, &note);
+            }
+        }
+    });
+}
+
+/// Query the read-only state of snapshots not seen before and flag writable rows
+///
+/// States are cached for the session, so only new snapshots cost a helper call.
+fn load_read_only_states(list: &ListBox, names: Vec<String>) {
+    let unknown: Vec<String> = READ_ONLY_STATES.with(|states| {
+        let states = states.borrow();
+        names
+            .into_iter()
+            .filter(|name| !states.contains_key(name))
+            .collect()
+    });
+    if unknown.is_empty() {
+        return;
+    }
+
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let result = crate::dbus_client::WaypointHelperClient::new()
+            .and_then(|client| client.get_snapshot_read_only(unknown));
+        let _ = tx.send(result);
+    });
+
+    let list = list.clone();
+    glib::spawn_future_local(async move {
+        let result = loop {
+            match rx.try_recv() {
+                Ok(result) => break result,
+                Err(mpsc::TryRecvError::Empty) => {
+                    glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => return,
+            }
+        };
+
+        let states = match result {
+            Ok(states) => states,
+            Err(e) => {
+                log::warn!("Failed to query read-only state of snapshots: {e}");
+                return;
+            }
+        };
+
+        let mut child = list.first_child();
+        while let Some(widget) = child {
+            child = widget.next_sibling();
+            if let Some(row) = widget.downcast_ref::<adw::ActionRow>() {
+                if states.get(row.title().as_str()) == Some(&false) {
+                    SnapshotRow::mark_writable(row);
+                }
+            }
+        }
+
+        READ_ONLY_STATES.with(|cache| cache.borrow_mut().extend(states));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn snapshot(name: &str, minutes: i64, subvolumes: &[&str]) -> Snapshot {
+        Snapshot {
+            id: name.to_string(),
+            name: name.to_string(),
+            timestamp: Utc::now() + Duration::minutes(minutes),
+            path: PathBuf::from(format!("/.snapshots/{name}")),
+            description: None,
+            kernel_version: None,
+            package_count: None,
+            size_bytes: None,
+            packages: Rc::new(Vec::new()),
+            subvolumes: Rc::new(subvolumes.iter().map(PathBuf::from).collect()),
+            retention_group: None,
+            expires_at: None,
+            transaction: None,
+            category: SnapshotCategory::Manual,
+            is_favorite: false,
+        }
+    }
+
+    #[test]
+    fn test_previous_snapshots_match_subvolume_sets() {
+        let snapshots = vec![
+            snapshot("root-1", 0, &["/"]),
+            snapshot("both-1", 1, &["/", "/home"]),
+            snapshot("root-2", 2, &["/"]),
+            snapshot("both-2", 3, &["/home", "/"]),
+        ];
+
+        let previous = previous_snapshots(&snapshots);
+        let previous_of = |name: &str| previous.get(name).map(|s| s.name.clone());
+        assert_eq!(previous_of("root-2").as_deref(), Some("root-1"));
+        assert_eq!(previous_of("both-2").as_deref(), Some("both-1"));
+        assert_eq!(previous_of("root-1"), None);
+        assert_eq!(previous_of("both-1"), None);
+    }
+}
@@ -1,31 +1,48 @@
+use crate::i18n::{gettext, gettext_f, ngettext_f};
 use crate::snapshot::{Snapshot, format_bytes};
 use crate::user_preferences::SnapshotPreferences;
 use adw::prelude::*;
 use gtk::prelude::*;
 use gtk::{Box, Button, Orientation};
 use libadwaita as adw;
+use waypoint_common::{SnapshotCategory, TransactionPhase};
+use waypoint_common::capabilities::{
+    FEATURE_READ_ONLY_TOGGLE, FEATURE_RETENTION_GROUP, FEATURE_SNAPSHOT_EXPIRY,
+    FEATURE_SNAPSHOT_OVERLAYS,
+};
+use waypoint_common::format::format_size_delta;
 
 pub struct SnapshotRow {
     row: adw::ActionRow,
 }
 
+#[derive(Clone, Copy)]
 pub enum SnapshotAction {
     Browse,
+    TestOverlay,
     Verify,
     Restore,
+    RestoreFiles,
     Delete,
     ToggleFavorite,
+    ToggleBackupExclusion,
     EditNote,
     Backup,
+    ToggleReadOnly,
+    SetRetentionGroup,
+    SetExpiry,
+    UndoTransaction,
 }
 
 /// Backup status for a snapshot
 #[derive(Debug, Clone, PartialEq)]
 pub enum BackupStatus {
+    /// No backup destination is configured
+    NoDestinations,
     /// Not backed up to any destination
     NotBackedUp,
     /// Backed up to all enabled destinations
-    FullyBackedUp,
+    FullyBackedUp(usize), // backed_up_count
     /// Backed up to some but not all enabled destinations
     PartiallyBackedUp(usize, usize), // (backed_up_count, total_count)
     /// Has pending backups
@@ -34,6 +51,54 @@ pub enum BackupStatus {
     Failed,
 }
 
+/// Exclusive qgroup sizes of a snapshot and the one before it
+///
+/// The size warning uses these rather than `Snapshot::size_bytes`, which may
+/// be a `du` estimate that counts data shared with other snapshots.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExclusiveSizes {
+    pub current: Option<u64>,
+    pub previous: Option<u64>,
+}
+
+impl ExclusiveSizes {
+    /// Tooltip of the size warning, if the sizes exceed `threshold`
+    ///
+    /// Growth since the previous snapshot is checked first; a snapshot that
+    /// grew that much is always over the threshold itself.
+    pub fn warning(&self, threshold: u64) -> Option<String> {
+        let delta = self
+            .current
+            .zip(self.previous)
+            .map(|(size, previous)| size as i64 - previous as i64);
+        if let Some(delta) = delta.filter(|&delta| delta > threshold as i64) {
+            return Some(gettext_f(
+                "Exclusive size grew by {delta} since the previous snapshot, more than the {threshold} size warning threshold",
+                &[
+                    ("delta", &format_bytes(delta.unsigned_abs())),
+                    ("threshold", &format_bytes(threshold)),
+                ],
+            ));
+        }
+        self.current.filter(|&size| size > threshold).map(|_| {
+            gettext_f(
+                "Exclusive size is larger than the {threshold} size warning threshold",
+                &[("threshold", &format_bytes(threshold))],
+            )
+        })
+    }
+}
+
+/// Badge icon of a category, None for manual snapshots
+fn category_icon(category: SnapshotCategory) -> Option<&'static str> {
+    match category {
+        SnapshotCategory::Manual => None,
+        SnapshotCategory::PreUpdate => Some("software-update-available-symbolic"),
+        SnapshotCategory::PreConfigChange => Some("preferences-system-symbolic"),
+        SnapshotCategory::Milestone => Some("bookmark-new-symbolic"),
+    }
+}
+
 impl SnapshotRow {
     #[allow(dead_code)]
     pub fn new<F>(snapshot: &Snapshot, on_action: F) -> adw::ActionRow
@@ -45,16 +110,33 @@ impl SnapshotRow {
             &SnapshotPreferences::default(),
             on_action,
             None,
-            &BackupStatus::NotBackedUp,
+            &BackupStatus::NoDestinations,
+            false,
+            None,
+            ExclusiveSizes::default(),
+            None,
+            false,
         )
     }
 
+    /// Create a row with list context
+    ///
+    /// `previous_size` is the size of the snapshot taken before this one, used
+    /// to show how much the system changed between the two. `size_warning` is
+    /// compared with the `exclusive` sizes. A `compact` row fits on one line,
+    /// with the details moved to its tooltip.
+    #[allow(clippy::too_many_arguments)]
     pub fn new_with_context<F>(
         snapshot: &Snapshot,
         preferences: &SnapshotPreferences,
         on_action: F,
         _max_size: Option<u64>,
         backup_status: &BackupStatus,
+        size_pending: bool,
+        size_warning: Option<u64>,
+        exclusive: ExclusiveSizes,
+        previous_size: Option<u64>,
+        compact: bool,
     ) -> adw::ActionRow
     where
         F: Fn(String, SnapshotAction) + 'static,
@@ -65,56 +147,170 @@ impl SnapshotRow {
         // Create prefix box for waypoint icon + backup status
         let prefix_box = Box::new(Orientation::Horizontal, 4);
 
+        // Status badges are only visual; screen readers get them via the row description
+        let mut status_notes: Vec<String> = Vec::new();
+
         // Add waypoint icon as prefix
-        let icon = gtk::Image::from_icon_name("waypoint");
-        icon.set_pixel_size(16);
+        let icon = gtk::Image::builder()
+            .icon_name("waypoint")
+            .pixel_size(16)
+            .accessible_role(gtk::AccessibleRole::Presentation)
+            .build();
         prefix_box.append(&icon);
 
         // Add backup status indicator
         match backup_status {
-            BackupStatus::FullyBackedUp => {
+            BackupStatus::FullyBackedUp(count) => {
                 let backup_icon = gtk::Image::from_icon_name("emblem-ok-symbolic");
                 backup_icon.set_pixel_size(12);
-                backup_icon.set_tooltip_text(Some("Backed up to all destinations"));
+                let note = ngettext_f(
+                    "Backed up to {count} destination",
+                    "Backed up to {count} destinations",
+                    *count as u64,
+                    &[("count", &count.to_string())],
+                );
+                backup_icon.set_tooltip_text(Some(&note));
                 backup_icon.add_css_class("success");
+                status_notes.push(note);
                 prefix_box.append(&backup_icon);
             }
             BackupStatus::PartiallyBackedUp(count, total) => {
                 let backup_icon = gtk::Image::from_icon_name("emblem-important-symbolic");
                 backup_icon.set_pixel_size(12);
-                backup_icon.set_tooltip_text(Some(&format!(
-                    "Backed up to {count} of {total} destinations"
-                )));
+                let note = format!("Backed up to {count} of {total} destinations");
+                backup_icon.set_tooltip_text(Some(&note));
                 backup_icon.add_css_class("warning");
+                status_notes.push(note);
                 prefix_box.append(&backup_icon);
             }
             BackupStatus::Pending => {
                 let backup_icon = gtk::Image::from_icon_name("document-save-symbolic");
                 backup_icon.set_pixel_size(12);
-                backup_icon.set_tooltip_text(Some("Backup pending"));
+                backup_icon.set_tooltip_text(Some(&gettext("Backup pending")));
                 backup_icon.add_css_class("dim-label");
+                status_notes.push("Backup pending".to_string());
                 prefix_box.append(&backup_icon);
             }
             BackupStatus::Failed => {
                 let backup_icon = gtk::Image::from_icon_name("dialog-error-symbolic");
                 backup_icon.set_pixel_size(12);
-                backup_icon.set_tooltip_text(Some("Backup failed"));
+                backup_icon.set_tooltip_text(Some(&gettext("Backup failed")));
                 backup_icon.add_css_class("error");
+                status_notes.push("Backup failed".to_string());
                 prefix_box.append(&backup_icon);
             }
             BackupStatus::NotBackedUp => {
-                // No icon for not backed up state
+                let backup_icon = gtk::Image::from_icon_name("action-unavailable-symbolic");
+                backup_icon.set_pixel_size(12);
+                backup_icon.set_tooltip_text(Some(&gettext("Not backed up")));
+                backup_icon.add_css_class("dim-label");
+                status_notes.push("Not backed up".to_string());
+                prefix_box.append(&backup_icon);
+            }
+            BackupStatus::NoDestinations => {
+                // Backups aren't set up, so there is nothing to point out
             }
         }
 
+        // Show a spinner while the size is being calculated in the background
+        if snapshot.size_bytes.is_none() && size_pending {
+            let spinner = gtk::Spinner::new();
+            spinner.set_spinning(true);
+            spinner.set_tooltip_text(Some(&gettext("Calculating size")));
+            prefix_box.append(&spinner);
+        }
+
+        // Growth since the previous snapshot, shown next to the size
+        let size_delta = snapshot
+            .size_bytes
+            .zip(previous_size)
+            .map(|(size, previous)| size as i64 - previous as i64);
+
+        // Flag snapshots whose exclusive size, or its growth, exceeds the threshold
+        if let Some(note) = size_warning.and_then(|threshold| exclusive.warning(threshold)) {
+            prefix_box.append(&size_warning_icon(&note));
+            status_notes.push(note);
+        }
+
+        // Flag snapshots that failed their last verification until they pass again
+        if preferences.verification_failed {
+            let verify_icon = gtk::Image::from_icon_name("dialog-error-symbolic");
+            verify_icon.set_pixel_size(12);
+            verify_icon.set_tooltip_text(Some(&gettext(
+                "Failed verification, verify again to clear",
+            )));
+            verify_icon.add_css_class("error");
+            status_notes.push("Failed verification".to_string());
+            prefix_box.append(&verify_icon);
+        }
+
+        // Temporary snapshots show when cleanup will remove them
+        let expiry = snapshot.format_expiry();
+        if let Some(expiry) = &expiry {
+            let expiry_icon = gtk::Image::from_icon_name("alarm-symbolic");
+            expiry_icon.set_pixel_size(12);
+            expiry_icon.set_tooltip_text(Some(expiry));
+            expiry_icon.add_css_class("dim-label");
+            status_notes.push(expiry.clone());
+            prefix_box.append(&expiry_icon);
+        }
+
+        // Package manager transactions, with the snapshot from the other side
+        let transaction = snapshot.transaction.as_ref().map(|link| {
+            let id = format!("#{}", link.id);
+            let summary = match link.phase {
+                TransactionPhase::Pre => gettext_f("Before transaction {id}", &[("id", &id)]),
+                TransactionPhase::Post => gettext_f("After transaction {id}", &[("id", &id)]),
+            };
+            let details = match &link.paired_with {
+                Some(pair) => gettext_f(
+                    "{summary}, paired with {pair}",
+                    &[("summary", &summary), ("pair", pair)],
+                ),
+                None => summary.clone(),
+            };
+            (summary, details)
+        });
+        if let Some((_, details)) = &transaction {
+            let transaction_icon = gtk::Image::from_icon_name("system-software-install-symbolic");
+            transaction_icon.set_pixel_size(12);
+            transaction_icon.set_tooltip_text(Some(details));
+            transaction_icon.add_css_class("dim-label");
+            status_notes.push(details.clone());
+            prefix_box.append(&transaction_icon);
+        }
+
+        // Categorized snapshots get a badge and a colored edge (see load_css)
+        let category = (!snapshot.category.is_manual()).then(|| snapshot.category.label());
+        if let (Some(label), Some(icon_name)) = (category, category_icon(snapshot.category)) {
+            let category_icon = gtk::Image::from_icon_name(icon_name);
+            category_icon.set_pixel_size(12);
+            category_icon.set_tooltip_text(Some(label));
+            category_icon.add_css_class("dim-label");
+            status_notes.push(label.to_string());
+            prefix_box.append(&category_icon);
+            row.add_css_class(&format!("category-{}", snapshot.category.as_str()));
+        }
+
         row.add_prefix(&prefix_box);
 
         // Build subtitle with metadata - cleaner format with relative time
         let mut subtitle_parts = vec![snapshot.format_relative_time()];
+        subtitle_parts.extend(category.map(String::from));
+        subtitle_parts.extend(expiry);
+        subtitle_parts.extend(transaction.map(|(summary, _)| summary));
 
         // Add size if available
         if let Some(size) = snapshot.size_bytes {
             subtitle_parts.push(format_bytes(size));
+            if let Some(delta) = size_delta {
+                subtitle_parts.push(gettext_f(
+                    "{delta} since previous",
+                    &[("delta", &format_size_delta(delta))],
+                ));
+            }
+        } else if size_pending {
+            subtitle_parts.push("Calculating size…".to_string());
         }
 
         if let Some(count) = snapshot.package_count {
@@ -128,6 +324,14 @@ impl SnapshotRow {
             }
         }
 
+        // With several snapshot directories, say which filesystem the snapshot is on
+        if let Some(location) = crate::btrfs::snapshot_location(&snapshot.path) {
+            subtitle_parts.push(gettext_f(
+                "in {location}",
+                &[("location", &location.display().to_string())],
+            ));
+        }
+
         // Build subtitle text with optional note
         let subtitle = if let Some(note) = &preferences.note {
             // Truncate note if too long (show first 60 chars + ellipsis)
@@ -141,7 +345,32 @@ impl SnapshotRow {
             subtitle_parts.join("  •  ")
         };
 
-        row.set_subtitle(&subtitle);
+        if compact {
+            row.set_title_lines(1);
+            row.set_tooltip_text(Some(&subtitle));
+            row.add_css_class("compact-row");
+
+            // Keep the age visible, it's what people scan the list by
+            let age_label = gtk::Label::new(Some(&snapshot.format_relative_time()));
+            age_label.add_css_class("dim-label");
+            age_label.add_css_class("caption");
+            age_label.set_valign(gtk::Align::Center);
+            row.add_suffix(&age_label);
+
+            // Screen readers still get the details the subtitle would show
+            status_notes.push(subtitle);
+        } else {
+            row.set_subtitle(&subtitle);
+        }
+
+        if preferences.is_favorite {
+            status_notes.insert(0, "Pinned".to_string());
+        }
+        if !status_notes.is_empty() {
+            row.update_property(&[gtk::accessible::Property::Description(
+                &status_notes.join(". "),
+            )]);
+        }
 
         // Add action buttons - primary action + menu
         let button_box = Box::new(Orientation::Horizontal, 6);
@@ -154,52 +383,129 @@ impl SnapshotRow {
                 "non-starred-symbolic"
             })
             .tooltip_text(if preferences.is_favorite {
-                "Unpin Restore Point"
+                gettext("Unpin Restore Point")
             } else {
-                "Pin Restore Point"
+                gettext("Pin Restore Point")
             })
             .valign(gtk::Align::Center)
             .build();
         star_btn.add_css_class("flat");
+        set_accessible_label(
+            &star_btn,
+            &if preferences.is_favorite {
+                gettext_f("Unpin restore point {name}", &[("name", &snapshot.name)])
+            } else {
+                gettext_f("Pin restore point {name}", &[("name", &snapshot.name)])
+            },
+        );
 
         // Primary action: Restore button
         let restore_btn = Button::builder()
             .icon_name("view-refresh-symbolic")
-            .tooltip_text("Restore System to This Point")
+            .tooltip_text(gettext("Restore System to This Point"))
             .valign(gtk::Align::Center)
             .build();
         restore_btn.add_css_class("flat");
+        set_accessible_label(
+            &restore_btn,
+            &gettext_f("Restore system to {name}", &[("name", &snapshot.name)]),
+        );
 
         // Menu button for secondary actions
         let menu_btn = gtk::MenuButton::new();
         menu_btn.set_icon_name("view-more-symbolic");
-        menu_btn.set_tooltip_text(Some("More Actions"));
+        menu_btn.set_tooltip_text(Some(&gettext("More Actions")));
         menu_btn.set_valign(gtk::Align::Center);
         menu_btn.add_css_class("flat");
+        set_accessible_label(&menu_btn, &format!("More actions for {}", snapshot.name));
 
         // Create popover menu
         let menu = gtk::gio::Menu::new();
 
         // Browse action
         let browse_action_name = format!("snapshot.browse-{}", snapshot.id.replace('/', "-"));
-        menu.append(Some("Browse Files"), Some(&browse_action_name));
+        menu.append(Some(&gettext("Browse Files")), Some(&browse_action_name));
+
+        // Restore files action, skipping the restore type choice
+        let restore_files_action_name =
+            format!("snapshot.restore-files-{}", snapshot.id.replace('/', "-"));
+        menu.append(
+            Some(&gettext("Restore Files…")),
+            Some(&restore_files_action_name),
+        );
+
+        // Test overlay action (only if the helper supports overlays)
+        if crate::dbus_client::helper_supports(FEATURE_SNAPSHOT_OVERLAYS) {
+            let overlay_action_name = format!("snapshot.overlay-{}", snapshot.id.replace('/', "-"));
+            menu.append(Some(&gettext("Open Test Overlay")), Some(&overlay_action_name));
+        }
 
         // Verify action
         let verify_action_name = format!("snapshot.verify-{}", snapshot.id.replace('/', "-"));
-        menu.append(Some("Verify Integrity"), Some(&verify_action_name));
+        menu.append(Some(&gettext("Verify Integrity")), Some(&verify_action_name));
 
         // Backup action
         let backup_action_name = format!("snapshot.backup-{}", snapshot.id.replace('/', "-"));
-        menu.append(Some("Backup to External Drive"), Some(&backup_action_name));
+        menu.append(Some(&gettext("Backup to External Drive")), Some(&backup_action_name));
+
+        // Backup exclusion action
+        let exclude_action_name = format!("snapshot.exclude-backup-{}", snapshot.id.replace('/', "-"));
+        menu.append(
+            Some(&if preferences.exclude_from_backup {
+                gettext("Include in Automatic Backups")
+            } else {
+                gettext("Exclude from Automatic Backups")
+            }),
+            Some(&exclude_action_name),
+        );
 
         // Edit Note action
         let edit_note_action_name = format!("snapshot.edit-note-{}", snapshot.id.replace('/', "-"));
-        menu.append(Some("Edit Note"), Some(&edit_note_action_name));
+        menu.append(Some(&gettext("Edit Note")), Some(&edit_note_action_name));
+
+        // Read-only toggle (only if the helper supports it)
+        if crate::dbus_client::helper_supports(FEATURE_READ_ONLY_TOGGLE) {
+            let read_only_action_name =
+                format!("snapshot.read-only-{}", snapshot.id.replace('/', "-"));
+            menu.append(
+                Some(&gettext("Change Read-Only State…")),
+                Some(&read_only_action_name),
+            );
+        }
+
+        // Retention group (only if the helper supports it)
+        if crate::dbus_client::helper_supports(FEATURE_RETENTION_GROUP) {
+            let retention_action_name =
+                format!("snapshot.retention-{}", snapshot.id.replace('/', "-"));
+            menu.append(
+                Some(&gettext("Keep Under Schedule…")),
+                Some(&retention_action_name),
+            );
+        }
+
+        // Expiry date (only if the helper supports it)
+        if crate::dbus_client::helper_supports(FEATURE_SNAPSHOT_EXPIRY) {
+            let expiry_action_name = format!("snapshot.expiry-{}", snapshot.id.replace('/', "-"));
+            menu.append(Some(&gettext("Set Expiry…")), Some(&expiry_action_name));
+        }
+
+        // Undo a package transaction by restoring the snapshot taken before it
+        let undo_target = snapshot
+            .transaction
+            .as_ref()
+            .and_then(|link| link.pre_snapshot(&snapshot.name));
+        if undo_target.is_some() {
+            let undo_action_name = format!(
+                "snapshot.undo-transaction-{}",
+                snapshot.id.replace('/', "-")
+            );
+            menu.append(Some(&gettext("Undo Transaction…")), Some(&undo_action_name));
+        }
 
         // Delete action in a separate section (creates visual separator)
         let delete_section = gtk::gio::Menu::new();
         let delete_action_name = format!("snapshot.delete-{}", snapshot.id.replace('/', "-"));
-        delete_section.append(Some("Delete Restore Point"), Some(&delete_action_name));
+        delete_section.append(Some(&gettext("Delete Restore Point")), Some(&delete_action_name));
         menu.append_section(None, &delete_section);
 
         let popover = gtk::PopoverMenu::from_model(Some(&menu));
@@ -236,6 +542,28 @@ impl SnapshotRow {
         });
         action_group.add_action(&browse_action);
 
+        // Restore files action
+        let restore_files_action = gtk::gio::SimpleAction::new(
+            &format!("restore-files-{}", snapshot.id.replace('/', "-")),
+            None,
+        );
+        let restore_files_id = snapshot.id.clone();
+        let restore_files_cb = callback.clone();
+        restore_files_action.connect_activate(move |_, _| {
+            restore_files_cb(restore_files_id.clone(), SnapshotAction::RestoreFiles);
+        });
+        action_group.add_action(&restore_files_action);
+
+        // Test overlay action
+        let overlay_action =
+            gtk::gio::SimpleAction::new(&format!("overlay-{}", snapshot.id.replace('/', "-")), None);
+        let overlay_id = snapshot.id.clone();
+        let overlay_cb = callback.clone();
+        overlay_action.connect_activate(move |_, _| {
+            overlay_cb(overlay_id.clone(), SnapshotAction::TestOverlay);
+        });
+        action_group.add_action(&overlay_action);
+
         // Verify action
         let verify_action =
             gtk::gio::SimpleAction::new(&format!("verify-{}", snapshot.id.replace('/', "-")), None);
@@ -256,6 +584,18 @@ impl SnapshotRow {
         });
         action_group.add_action(&backup_action);
 
+        // Backup exclusion action
+        let exclude_action = gtk::gio::SimpleAction::new(
+            &format!("exclude-backup-{}", snapshot.id.replace('/', "-")),
+            None,
+        );
+        let exclude_id = snapshot.id.clone();
+        let exclude_cb = callback.clone();
+        exclude_action.connect_activate(move |_, _| {
+            exclude_cb(exclude_id.clone(), SnapshotAction::ToggleBackupExclusion);
+        });
+        action_group.add_action(&exclude_action);
+
         // Edit Note action
         let edit_note_action = gtk::gio::SimpleAction::new(
             &format!("edit-note-{}", snapshot.id.replace('/', "-")),
@@ -268,6 +608,52 @@ impl SnapshotRow {
         });
         action_group.add_action(&edit_note_action);
 
+        // Read-only toggle action
+        let read_only_action = gtk::gio::SimpleAction::new(
+            &format!("read-only-{}", snapshot.id.replace('/', "-")),
+            None,
+        );
+        let read_only_id = snapshot.id.clone();
+        let read_only_cb = callback.clone();
+        read_only_action.connect_activate(move |_, _| {
+            read_only_cb(read_only_id.clone(), SnapshotAction::ToggleReadOnly);
+        });
+        action_group.add_action(&read_only_action);
+
+        // Retention group action
+        let retention_action = gtk::gio::SimpleAction::new(
+            &format!("retention-{}", snapshot.id.replace('/', "-")),
+            None,
+        );
+        let retention_id = snapshot.id.clone();
+        let retention_cb = callback.clone();
+        retention_action.connect_activate(move |_, _| {
+            retention_cb(retention_id.clone(), SnapshotAction::SetRetentionGroup);
+        });
+        action_group.add_action(&retention_action);
+
+        // Expiry action
+        let expiry_action =
+            gtk::gio::SimpleAction::new(&format!("expiry-{}", snapshot.id.replace('/', "-")), None);
+        let expiry_id = snapshot.id.clone();
+        let expiry_cb = callback.clone();
+        expiry_action.connect_activate(move |_, _| {
+            expiry_cb(expiry_id.clone(), SnapshotAction::SetExpiry);
+        });
+        action_group.add_action(&expiry_action);
+
+        // Undo transaction action
+        let undo_action = gtk::gio::SimpleAction::new(
+            &format!("undo-transaction-{}", snapshot.id.replace('/', "-")),
+            None,
+        );
+        let undo_id = snapshot.id.clone();
+        let undo_cb = callback.clone();
+        undo_action.connect_activate(move |_, _| {
+            undo_cb(undo_id.clone(), SnapshotAction::UndoTransaction);
+        });
+        action_group.add_action(&undo_action);
+
         // Delete action
         let delete_action =
             gtk::gio::SimpleAction::new(&format!("delete-{}", snapshot.id.replace('/', "-")), None);
@@ -286,12 +672,64 @@ impl SnapshotRow {
         button_box.append(&menu_btn);
 
         row.add_suffix(&button_box);
-        row.set_activatable(false);
+
+        // Enter or Space on the focused row opens the actions menu
+        row.set_activatable_widget(Some(&menu_btn));
+
+        // Delete key on the focused row asks to delete the restore point
+        let key_controller = gtk::EventControllerKey::new();
+        let delete_id = snapshot_id.clone();
+        let delete_cb = callback.clone();
+        key_controller.connect_key_pressed(move |_, key, _, modifiers| {
+            if key == gtk::gdk::Key::Delete && modifiers.is_empty() {
+                delete_cb(delete_id.clone(), SnapshotAction::Delete);
+                return gtk::glib::Propagation::Stop;
+            }
+            gtk::glib::Propagation::Proceed
+        });
+        row.add_controller(key_controller);
 
         row
     }
 }
 
+impl SnapshotRow {
+    /// Flag a row whose snapshot is writable
+    ///
+    /// The read-only state is queried after the list is built, so the badge
+    /// is added to existing rows.
+    pub fn mark_writable(row: &adw::ActionRow) {
+        let icon = gtk::Image::from_icon_name("document-edit-symbolic");
+        icon.set_pixel_size(12);
+        icon.set_tooltip_text(Some(&gettext("Writable — backups need read-only snapshots")));
+        icon.add_css_class("warning");
+        set_accessible_label(&icon, "Writable snapshot");
+        row.add_prefix(&icon);
+    }
+
+    /// Flag a row whose exclusive size exceeds the size warning threshold
+    ///
+    /// Exclusive sizes are queried after the list is built, so the warning
+    /// is added to existing rows.
+    pub fn mark_size_warning(row: &adw::ActionRow, note: &str) {
+        row.add_prefix(&size_warning_icon(note));
+    }
+}
+
+/// Warning icon for a snapshot over the size warning threshold
+fn size_warning_icon(note: &str) -> gtk::Image {
+    let icon = gtk::Image::from_icon_name("dialog-warning-symbolic");
+    icon.set_pixel_size(12);
+    icon.set_tooltip_text(Some(note));
+    icon.add_css_class("warning");
+    icon
+}
+
+/// Give an icon-only widget a label for screen readers
+fn set_accessible_label(widget: &impl IsA<gtk::Accessible>, label: &str) {
+    widget.update_property(&[gtk::accessible::Property::Label(label)]);
+}
+
 impl std::ops::Deref for SnapshotRow {
     type Target = adw::ActionRow;
 
@@ -0,0 +1,190 @@
+//! Non-destructive restore of a snapshot to a new subvolume
+//!
+//! Creates a writable copy of one subvolume of a snapshot at a path of the
+//! user's choosing. The running system and the default subvolume are left
+//! untouched, so no reboot is needed and nothing is rolled back.
+
+use adw::prelude::*;
+use gtk::prelude::*;
+use gtk::{Orientation, glib};
+use libadwaita as adw;
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+use super::dialogs;
+use crate::dbus_client::WaypointHelperClient;
+use crate::i18n::{gettext, gettext_f};
+
+/// Default target for a restored subvolume, next to the root of the system
+fn default_target(snapshot_name: &str, subvolume: &str) -> String {
+    if subvolume == "/" {
+        format!("/waypoint-restored-{snapshot_name}")
+    } else {
+        format!(
+            "/waypoint-restored-{snapshot_name}-{}",
+            subvolume.trim_start_matches('/').replace('/', "_")
+        )
+    }
+}
+
+/// Ask for a subvolume and target path, then restore to a new subvolume
+pub fn show_restore_to_subvolume_dialog(
+    window: &adw::ApplicationWindow,
+    snapshot_name: &str,
+    subvolumes: &[PathBuf],
+) {
+    let subvolumes: Vec<String> = if subvolumes.is_empty() {
+        vec!["/".to_string()]
+    } else {
+        subvolumes.iter().map(|s| s.to_string_lossy().to_string()).collect()
+    };
+
+    let dialog = adw::Window::new();
+    dialog.set_transient_for(Some(window));
+    dialog.set_modal(true);
+    dialog.set_title(Some(&gettext("Restore to New Subvolume")));
+    dialog.set_default_size(500, -1);
+
+    let main_box = gtk::Box::new(Orientation::Vertical, 0);
+
+    let header = adw::HeaderBar::new();
+    header.set_show_end_title_buttons(false);
+
+    let cancel_btn = gtk::Button::with_label(&gettext("Cancel"));
+    header.pack_start(&cancel_btn);
+
+    let restore_btn = gtk::Button::with_label(&gettext("Restore"));
+    restore_btn.add_css_class("suggested-action");
+    header.pack_end(&restore_btn);
+
+    main_box.append(&header);
+
+    let content_box = gtk::Box::new(Orientation::Vertical, 0);
+    content_box.set_margin_top(24);
+    content_box.set_margin_bottom(24);
+    content_box.set_margin_start(24);
+    content_box.set_margin_end(24);
+
+    let group = adw::PreferencesGroup::new();
+    group.set_description(Some(&gettext(
+        "Creates a writable copy of the snapshot that you can mount and inspect. \
+        Your running system is not changed and no reboot is needed.",
+    )));
+
+    let subvol_strs: Vec<&str> = subvolumes.iter().map(String::as_str).collect();
+    let subvol_row = adw::ComboRow::new();
+    subvol_row.set_title(&gettext("Subvolume"));
+    subvol_row.set_model(Some(&gtk::StringList::new(&subvol_strs)));
+    subvol_row.set_sensitive(subvolumes.len() > 1);
+    group.add(&subvol_row);
+
+    let target_row = adw::EntryRow::new();
+    target_row.set_title(&gettext("Target Path"));
+    target_row.set_text(&default_target(snapshot_name, &subvolumes[0]));
+    group.add(&target_row);
+
+    // Keep the suggested path in sync with the subvolume until the user edits it
+    let target_clone = target_row.clone();
+    let subvolumes_clone = subvolumes.clone();
+    let name_clone = snapshot_name.to_string();
+    subvol_row.connect_selected_notify(move |row| {
+        let is_default = subvolumes_clone
+            .iter()
+            .any(|s| target_clone.text() == default_target(&name_clone, s));
+        if let Some(subvolume) = subvolumes_clone.get(row.selected() as usize) {
+            if is_default {
+                target_clone.set_text(&default_target(&name_clone, subvolume));
+            }
+        }
+    });
+
+    content_box.append(&group);
+
+    let hint = gtk::Label::new(Some(&gettext(
+        "The target must be on the same Btrfs filesystem as the snapshot and must not exist yet.",
+    )));
+    hint.add_css_class("dim-label");
+    hint.add_css_class("caption");
+    hint.set_wrap(true);
+    hint.set_xalign(0.0);
+    hint.set_margin_top(12);
+    content_box.append(&hint);
+
+    main_box.append(&content_box);
+    dialog.set_content(Some(&main_box));
+
+    let dialog_clone = dialog.clone();
+    cancel_btn.connect_clicked(move |_| dialog_clone.close());
+
+    let window_clone = window.clone();
+    let dialog_clone = dialog.clone();
+    let snapshot_name = snapshot_name.to_string();
+    restore_btn.connect_clicked(move |_| {
+        let target = target_row.text().trim().to_string();
+        if target.is_empty() {
+            return;
+        }
+
+        let subvolume = subvolumes
+            .get(subvol_row.selected() as usize)
+            .cloned()
+            .unwrap_or_else(|| "/".to_string());
+
+        dialog_clone.close();
+        restore_in_background(&window_clone, snapshot_name.clone(), subvolume, target);
+    });
+
+    dialog.present();
+}
+
+fn restore_in_background(
+    window: &adw::ApplicationWindow,
+    snapshot_name: String,
+    subvolume: String,
+    target: String,
+) {
+    dialogs::show_toast(window, &gettext("Restoring to new subvolume..."));
+
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let result = WaypointHelperClient::new()
+            .and_then(|client| client.restore_to_subvolume(&snapshot_name, &subvolume, &target));
+        let _ = tx.send(result);
+    });
+
+    let window = window.clone();
+    glib::spawn_future_local(async move {
+        let result = loop {
+            match rx.try_recv() {
+                Ok(result) => break result,
+                Err(mpsc::TryRecvError::Empty) => {
+                    glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    break Err(anyhow::anyhow!("Restore thread disconnected"));
+                }
+            }
+        };
+
+        match result {
+            Ok(path) => dialogs::show_info(
+                &window,
+                &gettext("Restored to New Subvolume"),
+                &gettext_f(
+                    "The snapshot was restored to:\n\n{path}\n\n\
+                    Your running system was not changed. Delete the copy with \
+                    'btrfs subvolume delete {path}' when you no longer need it.",
+                    &[("path", &path)],
+                ),
+            ),
+            Err(e) => dialogs::show_error(
+                &window,
+                &gettext("Restore Failed"),
+                &gettext_f(
+                    "Failed to restore to new subvolume: {error}",
+                    &[("error", &e.to_string())],
+                ),
+            ),
+        }
+    });
+}
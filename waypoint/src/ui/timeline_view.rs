@@ -0,0 +1,425 @@
+//! Timeline view of snapshots
+//!
+//! Plots every snapshot on a date axis, one column per day, colored by the
+//! schedule (or other origin) that created it. Gaps and thinning columns make
+//! retention visible at a glance; clicking a day lists that day's snapshots.
+
+use adw::prelude::*;
+use gtk::prelude::*;
+use gtk::{Label, ListBox, Orientation, glib};
+use libadwaita as adw;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use waypoint_common::{ScheduleType, SchedulesConfig, WaypointConfig};
+
+use crate::i18n::{gettext, ngettext_f};
+use crate::snapshot::{Snapshot, SnapshotManager};
+
+/// Width of one day column in pixels
+const DAY_WIDTH: f64 = 14.0;
+
+/// Dot radius in pixels
+const DOT_RADIUS: f64 = 4.0;
+
+/// Horizontal padding before the first and after the last day
+const SIDE_PADDING: f64 = 12.0;
+
+/// Space reserved below the plot for month labels
+const AXIS_HEIGHT: f64 = 24.0;
+
+/// Height of the plot area
+const PLOT_HEIGHT: i32 = 160;
+
+/// Where a snapshot came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotOrigin {
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    /// Safety snapshot taken automatically before a rollback
+    PreRollback,
+    /// Created by hand (or by a schedule that no longer exists)
+    Manual,
+}
+
+impl SnapshotOrigin {
+    const ALL: [SnapshotOrigin; 6] = [
+        SnapshotOrigin::Hourly,
+        SnapshotOrigin::Daily,
+        SnapshotOrigin::Weekly,
+        SnapshotOrigin::Monthly,
+        SnapshotOrigin::PreRollback,
+        SnapshotOrigin::Manual,
+    ];
+
+    /// Classify a snapshot by name using the configured schedule prefixes
+    pub fn classify(snapshot_name: &str, schedules: &SchedulesConfig) -> Self {
+        if snapshot_name.starts_with("waypoint-pre-rollback-") {
+            return SnapshotOrigin::PreRollback;
+        }
+
+        match schedules
+            .schedule_for_snapshot(snapshot_name)
+            .map(|s| s.schedule_type)
+        {
+            Some(ScheduleType::Hourly) => SnapshotOrigin::Hourly,
+            Some(ScheduleType::Daily) => SnapshotOrigin::Daily,
+            Some(ScheduleType::Weekly) => SnapshotOrigin::Weekly,
+            Some(ScheduleType::Monthly) => SnapshotOrigin::Monthly,
+            None => SnapshotOrigin::Manual,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            SnapshotOrigin::Hourly => gettext("Hourly"),
+            SnapshotOrigin::Daily => gettext("Daily"),
+            SnapshotOrigin::Weekly => gettext("Weekly"),
+            SnapshotOrigin::Monthly => gettext("Monthly"),
+            SnapshotOrigin::PreRollback => gettext("Before rollback"),
+            SnapshotOrigin::Manual => gettext("Manual"),
+        }
+    }
+
+    /// Color from the GNOME palette
+    fn hex_color(&self) -> &'static str {
+        match self {
+            SnapshotOrigin::Hourly => "#62a0ea",
+            SnapshotOrigin::Daily => "#33d17a",
+            SnapshotOrigin::Weekly => "#f6d32d",
+            SnapshotOrigin::Monthly => "#ff7800",
+            SnapshotOrigin::PreRollback => "#e01b24",
+            SnapshotOrigin::Manual => "#9141ac",
+        }
+    }
+
+    fn rgb(&self) -> (f64, f64, f64) {
+        let hex = self.hex_color().trim_start_matches('#');
+        let channel = |i: usize| {
+            u8::from_str_radix(&hex[i..i + 2], 16).unwrap_or(0) as f64 / 255.0
+        };
+        (channel(0), channel(2), channel(4))
+    }
+}
+
+/// A snapshot as plotted on the timeline
+#[derive(Debug, Clone)]
+struct TimelineEntry {
+    name: String,
+    time: String,
+    description: Option<String>,
+    origin: SnapshotOrigin,
+}
+
+/// Snapshots grouped by local calendar day
+#[derive(Default)]
+struct TimelineData {
+    days: BTreeMap<NaiveDate, Vec<TimelineEntry>>,
+    first_day: Option<NaiveDate>,
+    last_day: Option<NaiveDate>,
+    selected: Option<NaiveDate>,
+}
+
+impl TimelineData {
+    fn day_count(&self) -> i64 {
+        match (self.first_day, self.last_day) {
+            (Some(first), Some(last)) => (last - first).num_days() + 1,
+            _ => 0,
+        }
+    }
+
+    fn day_at(&self, x: f64) -> Option<NaiveDate> {
+        let first = self.first_day?;
+        let index = ((x - SIDE_PADDING) / DAY_WIDTH).floor();
+        if index < 0.0 || index as i64 >= self.day_count() {
+            return None;
+        }
+        Some(first + Duration::days(index as i64))
+    }
+}
+
+/// Timeline of snapshots with a per-day detail list
+#[derive(Clone)]
+pub struct TimelineView {
+    widget: gtk::Box,
+    area: gtk::DrawingArea,
+    scrolled: gtk::ScrolledWindow,
+    day_title: Label,
+    day_list: ListBox,
+    data: Rc<RefCell<TimelineData>>,
+}
+
+impl TimelineView {
+    /// Build the timeline; it reloads snapshots from `manager` every time it is shown
+    pub fn new(manager: &Rc<RefCell<SnapshotManager>>) -> Self {
+        let widget = gtk::Box::new(Orientation::Vertical, 12);
+        widget.set_margin_top(6);
+        widget.set_margin_bottom(12);
+        widget.set_margin_start(12);
+        widget.set_margin_end(12);
+
+        let area = gtk::DrawingArea::new();
+        area.set_content_height(PLOT_HEIGHT);
+
+        let scrolled = gtk::ScrolledWindow::new();
+        scrolled.set_policy(gtk::PolicyType::Automatic, gtk::PolicyType::Never);
+        scrolled.set_child(Some(&area));
+        scrolled.add_css_class("card");
+        widget.append(&scrolled);
+
+        // Legend
+        let legend = gtk::Box::new(Orientation::Horizontal, 12);
+        legend.set_halign(gtk::Align::Center);
+        for origin in SnapshotOrigin::ALL {
+            let label = Label::new(None);
+            label.set_markup(&format!(
+                "<span foreground=\"{}\">●</span> {}",
+                origin.hex_color(),
+                glib::markup_escape_text(&origin.label())
+            ));
+            label.add_css_class("caption");
+            legend.append(&label);
+        }
+        widget.append(&legend);
+
+        // Snapshots of the selected day
+        let day_title = Label::new(Some(&gettext("Click a day to see its snapshots")));
+        day_title.set_halign(gtk::Align::Start);
+        day_title.add_css_class("heading");
+        widget.append(&day_title);
+
+        let day_list = ListBox::new();
+        day_list.set_selection_mode(gtk::SelectionMode::None);
+        day_list.add_css_class("boxed-list");
+
+        let list_scrolled = gtk::ScrolledWindow::new();
+        list_scrolled.set_vexpand(true);
+        list_scrolled.set_child(Some(&day_list));
+        widget.append(&list_scrolled);
+
+        let view = Self {
+            widget,
+            area,
+            scrolled,
+            day_title,
+            day_list,
+            data: Rc::new(RefCell::new(TimelineData::default())),
+        };
+
+        let data = view.data.clone();
+        view.area.set_draw_func(move |area, cr, width, height| {
+            draw_timeline(area, cr, width, height, &data.borrow());
+        });
+
+        let click = gtk::GestureClick::new();
+        let view_clone = view.clone();
+        click.connect_released(move |_, _, x, _| {
+            let day = view_clone.data.borrow().day_at(x);
+            if let Some(day) = day {
+                view_clone.select_day(day);
+            }
+        });
+        view.area.add_controller(click);
+
+        // Reload whenever the view becomes visible so it never shows stale data
+        let view_clone = view.clone();
+        let manager = manager.clone();
+        view.widget.connect_map(move |_| {
+            match manager.borrow().load_snapshot_summaries() {
+                Ok(snapshots) => view_clone.set_snapshots(&snapshots),
+                Err(e) => log::error!("Failed to load snapshots for timeline: {e}"),
+            }
+        });
+
+        view
+    }
+
+    pub fn widget(&self) -> &gtk::Box {
+        &self.widget
+    }
+
+    /// Replace the plotted snapshots
+    pub fn set_snapshots(&self, snapshots: &[Snapshot]) {
+        let schedules = SchedulesConfig::load_or_default(&WaypointConfig::new().schedules_config);
+
+        let mut data = self.data.borrow_mut();
+        data.days.clear();
+        for snapshot in snapshots {
+            let local = snapshot.timestamp.with_timezone(&Local);
+            data.days
+                .entry(local.date_naive())
+                .or_default()
+                .push(TimelineEntry {
+                    name: snapshot.name.clone(),
+                    time: local.format("%H:%M").to_string(),
+                    description: snapshot.description.clone(),
+                    origin: SnapshotOrigin::classify(&snapshot.name, &schedules),
+                });
+        }
+        for entries in data.days.values_mut() {
+            entries.sort_by(|a, b| a.time.cmp(&b.time));
+        }
+
+        let today = Local::now().date_naive();
+        data.first_day = data.days.keys().next().copied().map(|d| d.min(today));
+        data.last_day = data.first_day.map(|_| today);
+
+        let width = SIDE_PADDING * 2.0 + data.day_count() as f64 * DAY_WIDTH;
+        let selected = data.selected.filter(|d| data.days.contains_key(d));
+        drop(data);
+
+        self.area.set_content_width(width.ceil() as i32);
+        self.area.queue_draw();
+
+        match selected {
+            Some(day) => self.select_day(day),
+            None => {
+                self.data.borrow_mut().selected = None;
+                self.show_day(None);
+                // Start at the most recent end of the axis
+                let adjustment = self.scrolled.hadjustment();
+                glib::idle_add_local_once(move || {
+                    adjustment.set_value(adjustment.upper());
+                });
+            }
+        }
+    }
+
+    fn select_day(&self, day: NaiveDate) {
+        self.data.borrow_mut().selected = Some(day);
+        self.area.queue_draw();
+        self.show_day(Some(day));
+    }
+
+    fn show_day(&self, day: Option<NaiveDate>) {
+        while let Some(child) = self.day_list.first_child() {
+            self.day_list.remove(&child);
+        }
+
+        let Some(day) = day else {
+            self.day_title
+                .set_text(&gettext("Click a day to see its snapshots"));
+            return;
+        };
+
+        let data = self.data.borrow();
+        let entries = data.days.get(&day).map(Vec::as_slice).unwrap_or_default();
+        self.day_title.set_text(&ngettext_f(
+            "{day} — {count} snapshot",
+            "{day} — {count} snapshots",
+            entries.len() as u64,
+            &[
+                ("day", &day.format("%A, %B %-d, %Y").to_string()),
+                ("count", &entries.len().to_string()),
+            ],
+        ));
+
+        if entries.is_empty() {
+            let placeholder = adw::StatusPage::new();
+            placeholder.set_icon_name(Some("x-office-calendar-symbolic"));
+            placeholder.set_title(&gettext("No Snapshots"));
+            placeholder.set_description(Some(&gettext(
+                "No restore points were created on this day.",
+            )));
+            self.day_list.append(&placeholder);
+            return;
+        }
+
+        for entry in entries.iter().rev() {
+            let row = adw::ActionRow::new();
+            row.set_title(&glib::markup_escape_text(&entry.name));
+            let mut subtitle = format!("{}  •  {}", entry.time, entry.origin.label());
+            if let Some(description) = entry.description.as_deref().filter(|d| !d.is_empty()) {
+                subtitle.push_str("  •  ");
+                subtitle.push_str(description);
+            }
+            row.set_subtitle(&glib::markup_escape_text(&subtitle));
+
+            let dot = Label::new(None);
+            dot.set_markup(&format!(
+                "<span foreground=\"{}\">●</span>",
+                entry.origin.hex_color()
+            ));
+            row.add_prefix(&dot);
+            self.day_list.append(&row);
+        }
+    }
+}
+
+/// Draw the day columns, month labels and one dot per snapshot
+fn draw_timeline(
+    area: &gtk::DrawingArea,
+    cr: &gtk::cairo::Context,
+    _width: i32,
+    height: i32,
+    data: &TimelineData,
+) {
+    let fg = area.color();
+    let (red, green, blue) = (fg.red() as f64, fg.green() as f64, fg.blue() as f64);
+
+    let Some(first_day) = data.first_day else {
+        cr.set_source_rgba(red, green, blue, 0.6);
+        cr.move_to(SIDE_PADDING, height as f64 / 2.0);
+        let _ = cr.show_text(&gettext("No snapshots yet"));
+
+        return;
+    };
+
+    let baseline = height as f64 - AXIS_HEIGHT;
+    let max_dots = ((baseline - DOT_RADIUS) / (DOT_RADIUS * 2.0 + 2.0)).floor().max(1.0) as usize;
+
+    // Axis
+    cr.set_source_rgba(red, green, blue, 0.3);
+    cr.set_line_width(1.0);
+    cr.move_to(SIDE_PADDING, baseline + 0.5);
+    cr.line_to(SIDE_PADDING + data.day_count() as f64 * DAY_WIDTH, baseline + 0.5);
+    let _ = cr.stroke();
+
+    cr.set_font_size(10.0);
+    for index in 0..data.day_count() {
+        let day = first_day + Duration::days(index);
+        let x = SIDE_PADDING + index as f64 * DAY_WIDTH;
+
+        if data.selected == Some(day) {
+            cr.set_source_rgba(red, green, blue, 0.12);
+            cr.rectangle(x, 0.0, DAY_WIDTH, baseline);
+            let _ = cr.fill();
+        }
+
+        // Month boundaries get a tick and a label
+        if day.day() == 1 || index == 0 {
+            cr.set_source_rgba(red, green, blue, 0.3);
+            cr.move_to(x + 0.5, baseline);
+            cr.line_to(x + 0.5, baseline + 6.0);
+            let _ = cr.stroke();
+
+            cr.set_source_rgba(red, green, blue, 0.7);
+            cr.move_to(x + 2.0, baseline + 18.0);
+            let _ = cr.show_text(&day.format("%b %Y").to_string());
+        }
+
+        let Some(entries) = data.days.get(&day) else {
+            continue;
+        };
+
+        let center_x = x + DAY_WIDTH / 2.0;
+        for (stack_index, entry) in entries.iter().take(max_dots).enumerate() {
+            let center_y = baseline - DOT_RADIUS - 2.0 - stack_index as f64 * (DOT_RADIUS * 2.0 + 2.0);
+            let (r, g, b) = entry.origin.rgb();
+            cr.set_source_rgb(r, g, b);
+            cr.arc(center_x, center_y, DOT_RADIUS, 0.0, std::f64::consts::TAU);
+            let _ = cr.fill();
+        }
+
+        // Columns that don't fit get a marker on top
+        if entries.len() > max_dots {
+            cr.set_source_rgba(red, green, blue, 0.8);
+            cr.move_to(center_x - 3.0, 9.0);
+            let _ = cr.show_text("+");
+        }
+    }
+}
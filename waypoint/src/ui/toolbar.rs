@@ -2,30 +2,46 @@
 //!
 //! This module provides the toolbar UI component with all action buttons.
 
+use crate::i18n::gettext;
 use gtk::prelude::*;
-use gtk::{Button, Label, Orientation};
+use gtk::{Button, Label, Orientation, ToggleButton};
 
 /// Create the main toolbar with action buttons
 ///
 /// Creates a horizontal toolbar containing primary action buttons:
 /// - Create Restore Point (suggested action, pill-styled)
+/// - Quick Snapshot (creates a snapshot without asking for a description)
 /// - Compare Snapshots
+/// - Timeline (toggles between the list and timeline views)
+/// - Refresh (reloads the snapshot list)
 /// - Search (toggles search bar)
 ///
 /// # Returns
 /// A tuple containing:
 /// - `gtk::Box` - The toolbar container
 /// - `Button` - Create restore point button
+/// - `Button` - Quick snapshot button
 /// - `Button` - Compare snapshots button
+/// - `ToggleButton` - Timeline view toggle
+/// - `Button` - Refresh button (see `set_refreshing`)
 /// - `Button` - Search toggle button
 ///
 /// # Example
 /// ```no_run
-/// let (toolbar, create_btn, compare_btn, search_btn) = toolbar::create_toolbar();
+/// let (toolbar, create_btn, quick_btn, compare_btn, timeline_btn, refresh_btn, search_btn) =
+///     toolbar::create_toolbar();
 /// // Connect button handlers...
 /// container.append(&toolbar);
 /// ```
-pub fn create_toolbar() -> (gtk::Box, Button, Button, Button) {
+pub fn create_toolbar() -> (
+    gtk::Box,
+    Button,
+    Button,
+    Button,
+    ToggleButton,
+    Button,
+    Button,
+) {
     // Use Clamp for toolbar as well (GNOME HIG)
     let toolbar = gtk::Box::new(Orientation::Horizontal, 12);
     toolbar.set_margin_top(18);
@@ -36,7 +52,7 @@ pub fn create_toolbar() -> (gtk::Box, Button, Button, Button) {
     // Create button with icon
     let create_btn_content = gtk::Box::new(Orientation::Horizontal, 6);
     let create_icon = gtk::Image::from_icon_name("document-save-symbolic");
-    let create_label = Label::new(Some("Create Restore Point"));
+    let create_label = Label::new(Some(&gettext("Create Restore Point")));
     create_btn_content.append(&create_icon);
     create_btn_content.append(&create_label);
 
@@ -47,6 +63,16 @@ pub fn create_toolbar() -> (gtk::Box, Button, Button, Button) {
 
     toolbar.append(&create_btn);
 
+    // Quick snapshot button (skips the description dialog)
+    let quick_btn = Button::builder()
+        .icon_name("camera-photo-symbolic")
+        .tooltip_text(gettext("Quick Snapshot (Ctrl+Shift+N)"))
+        .build();
+    quick_btn.add_css_class("flat");
+    quick_btn.add_css_class("circular");
+
+    toolbar.append(&quick_btn);
+
     // Spacer - separates primary action from secondary actions
     let spacer = gtk::Box::new(Orientation::Horizontal, 0);
     spacer.set_hexpand(true);
@@ -56,25 +82,66 @@ pub fn create_toolbar() -> (gtk::Box, Button, Button, Button) {
     // Compare button with icon
     let compare_btn_content = gtk::Box::new(Orientation::Horizontal, 6);
     let compare_icon = gtk::Image::from_icon_name("view-dual-symbolic");
-    let compare_label = Label::new(Some("Compare"));
+    let compare_label = Label::new(Some(&gettext("Compare")));
     compare_btn_content.append(&compare_icon);
     compare_btn_content.append(&compare_label);
 
     let compare_btn = Button::new();
     compare_btn.set_child(Some(&compare_btn_content));
     compare_btn.add_css_class("flat");
-    compare_btn.set_tooltip_text(Some("Compare packages between snapshots"));
+    compare_btn.set_tooltip_text(Some(&gettext("Compare packages between snapshots")));
 
     toolbar.append(&compare_btn);
 
+    // Timeline toggle (switches the main view between list and timeline)
+    let timeline_btn = ToggleButton::builder()
+        .icon_name("x-office-calendar-symbolic")
+        .tooltip_text(gettext("Timeline View"))
+        .build();
+    timeline_btn.add_css_class("flat");
+
+    toolbar.append(&timeline_btn);
+
+    // Refresh button (shows a spinner while refreshing)
+    let refresh_btn = Button::new();
+    refresh_btn.add_css_class("flat");
+    set_refreshing(&refresh_btn, false);
+
+    toolbar.append(&refresh_btn);
+
     // Search button
     let search_btn = Button::builder()
         .icon_name("system-search-symbolic")
-        .tooltip_text("Search (Ctrl+F)")
+        .tooltip_text(gettext("Search (Ctrl+F)"))
         .build();
     search_btn.add_css_class("flat");
 
     toolbar.append(&search_btn);
 
-    (toolbar, create_btn, compare_btn, search_btn)
+    (
+        toolbar,
+        create_btn,
+        quick_btn,
+        compare_btn,
+        timeline_btn,
+        refresh_btn,
+        search_btn,
+    )
+}
+
+/// Show whether a refresh is in progress on the refresh button
+///
+/// While refreshing, the icon is replaced with a spinner and the button is
+/// insensitive so refreshes don't pile up.
+pub fn set_refreshing(refresh_btn: &Button, refreshing: bool) {
+    if refreshing {
+        let spinner = gtk::Spinner::new();
+        spinner.start();
+        refresh_btn.set_child(Some(&spinner));
+        refresh_btn.set_tooltip_text(Some(&gettext("Refreshing…")));
+    } else {
+        refresh_btn.set_icon_name("view-refresh-symbolic");
+        refresh_btn.set_tooltip_text(Some(&gettext("Refresh (F5)")));
+    }
+    refresh_btn.set_sensitive(!refreshing);
 }
@@ -1,5 +1,3 @@
-use std::path::PathBuf;
-
 /// Validate that a path is safe to open with xdg-open
 ///
 /// # Arguments
@@ -20,10 +18,7 @@ pub fn validate_path_for_open(path: &std::path::Path) -> Result<(), String> {
     };
 
     // Define allowed base directories
-    let allowed_dirs = [
-        PathBuf::from("/.snapshots"),
-        PathBuf::from("/mnt/btrfs-root/@snapshots"),
-    ];
+    let allowed_dirs = crate::btrfs::snapshot_dirs();
 
     // Check if the canonical path starts with any allowed directory
     for allowed_dir in &allowed_dirs {
@@ -51,6 +46,7 @@ mod tests {
     use super::*;
     use std::fs;
     use std::os::unix::fs as unix_fs;
+    use waypoint_common::WaypointConfig;
 
     #[test]
     fn test_validate_nonexistent_path() {
@@ -190,14 +186,11 @@ mod tests {
 
     #[test]
     fn test_allowed_directories_list() {
-        // Verify our allowed directories are as expected
-        let allowed_dirs = [
-            PathBuf::from("/.snapshots"),
-            PathBuf::from("/mnt/btrfs-root/@snapshots"),
-        ];
-
-        assert_eq!(allowed_dirs.len(), 2);
-        assert_eq!(allowed_dirs[0], PathBuf::from("/.snapshots"));
-        assert_eq!(allowed_dirs[1], PathBuf::from("/mnt/btrfs-root/@snapshots"));
+        // The allowed directories follow the configuration
+        let config = WaypointConfig::new();
+        let allowed_dirs = crate::btrfs::snapshot_dirs();
+
+        assert!(allowed_dirs.contains(&config.snapshot_dir));
+        assert!(allowed_dirs.contains(&config.fallback_snapshot_dir()));
     }
 }
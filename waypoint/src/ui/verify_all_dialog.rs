@@ -0,0 +1,306 @@
+//! Bulk verification of every snapshot
+//!
+//! Verifies all snapshots with a small pool of worker threads, shows progress
+//! while it runs and a summary with per-snapshot details when it's done.
+//! Failing snapshots are remembered in the user preferences so the list can
+//! flag them until they verify successfully again.
+
+use adw::prelude::*;
+use gtk::prelude::*;
+use gtk::{Orientation, glib};
+use libadwaita as adw;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
+
+use crate::dbus_client::{VerificationResult, WaypointHelperClient};
+use crate::i18n::{gettext, gettext_f, ngettext_f};
+use crate::operations::{self, OperationKind};
+use crate::user_preferences::UserPreferencesManager;
+
+/// Snapshots verified at the same time; each one reads a whole snapshot
+const VERIFY_CONCURRENCY: usize = 2;
+
+/// Outcome of verifying one snapshot
+struct VerifyOutcome {
+    snapshot_id: String,
+    snapshot_name: String,
+    result: Result<VerificationResult, String>,
+}
+
+impl VerifyOutcome {
+    fn failed(&self) -> bool {
+        !matches!(&self.result, Ok(v) if v.is_valid)
+    }
+
+    fn has_warnings(&self) -> bool {
+        matches!(&self.result, Ok(v) if v.is_valid && !v.warnings.is_empty())
+    }
+}
+
+/// Verify every snapshot and show a summary
+///
+/// `snapshots` holds `(id, name)` pairs. `on_finished` runs once all results
+/// have been recorded, e.g. to refresh the snapshot list.
+pub fn show_verify_all_dialog<F>(
+    window: &adw::ApplicationWindow,
+    snapshots: Vec<(String, String)>,
+    user_prefs: &Rc<RefCell<UserPreferencesManager>>,
+    on_finished: F,
+) where
+    F: Fn() + 'static,
+{
+    let total = snapshots.len();
+
+    let dialog = adw::Window::new();
+    dialog.set_transient_for(Some(window));
+    dialog.set_modal(true);
+    dialog.set_title(Some(&gettext("Verify All Snapshots")));
+    dialog.set_default_size(560, 520);
+
+    let main_box = gtk::Box::new(Orientation::Vertical, 0);
+    main_box.append(&adw::HeaderBar::new());
+
+    let content = gtk::Box::new(Orientation::Vertical, 12);
+    content.set_margin_top(24);
+    content.set_margin_bottom(24);
+    content.set_margin_start(24);
+    content.set_margin_end(24);
+
+    let status_label = gtk::Label::new(Some(&ngettext_f(
+        "Verifying {count} snapshot…",
+        "Verifying {count} snapshots…",
+        total as u64,
+        &[("count", &total.to_string())],
+    )));
+    status_label.add_css_class("title-4");
+    status_label.set_xalign(0.0);
+    status_label.set_wrap(true);
+    content.append(&status_label);
+
+    let progress = gtk::ProgressBar::new();
+    progress.set_show_text(true);
+    progress.set_text(Some(&gettext_f(
+        "{done} of {total}",
+        &[("done", "0"), ("total", &total.to_string())],
+    )));
+    content.append(&progress);
+
+    let scrolled = gtk::ScrolledWindow::new();
+    scrolled.set_vexpand(true);
+    scrolled.set_visible(false);
+
+    let details = gtk::ListBox::new();
+    details.set_selection_mode(gtk::SelectionMode::None);
+    details.add_css_class("boxed-list");
+    details.set_valign(gtk::Align::Start);
+    scrolled.set_child(Some(&details));
+    content.append(&scrolled);
+
+    main_box.append(&content);
+    dialog.set_content(Some(&main_box));
+
+    // Cancelling from the Operations panel stops workers from picking up
+    // more snapshots; closing the dialog leaves them running in the background
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_clone = stop.clone();
+    let operation = operations::start_cancellable(
+        OperationKind::Verify,
+        ngettext_f(
+            "Verify {count} snapshot",
+            "Verify {count} snapshots",
+            total as u64,
+            &[("count", &total.to_string())],
+        ),
+        move || stop_clone.store(true, Ordering::SeqCst),
+    );
+
+    let queue = Arc::new(Mutex::new(VecDeque::from(snapshots)));
+    let (tx, rx) = mpsc::channel::<VerifyOutcome>();
+
+    for _ in 0..VERIFY_CONCURRENCY.min(total) {
+        let queue = queue.clone();
+        let stop = stop.clone();
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            let client = WaypointHelperClient::new().map_err(|e| e.to_string());
+            loop {
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Some((snapshot_id, snapshot_name)) =
+                    queue.lock().ok().and_then(|mut q| q.pop_front())
+                else {
+                    break;
+                };
+
+                let result = match &client {
+                    Ok(client) => client
+                        .verify_snapshot(snapshot_name.clone())
+                        .map_err(|e| e.to_string()),
+                    Err(e) => Err(e.clone()),
+                };
+
+                let outcome = VerifyOutcome {
+                    snapshot_id,
+                    snapshot_name,
+                    result,
+                };
+                if tx.send(outcome).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    drop(tx);
+
+    let user_prefs = user_prefs.clone();
+    glib::spawn_future_local(async move {
+        let mut outcomes = Vec::with_capacity(total);
+        loop {
+            match rx.try_recv() {
+                Ok(outcome) => {
+                    // Only a completed verification changes the recorded state
+                    if let Ok(verification) = &outcome.result {
+                        if let Err(e) = user_prefs
+                            .borrow()
+                            .set_verification_failed(&outcome.snapshot_id, !verification.is_valid)
+                        {
+                            log::warn!(
+                                "Failed to record verification result for {}: {e}",
+                                outcome.snapshot_name
+                            );
+                        }
+                    }
+
+                    outcomes.push(outcome);
+                    let fraction = outcomes.len() as f64 / total.max(1) as f64;
+                    let text = gettext_f(
+                        "{done} of {total}",
+                        &[
+                            ("done", &outcomes.len().to_string()),
+                            ("total", &total.to_string()),
+                        ],
+                    );
+                    progress.set_fraction(fraction);
+                    progress.set_text(Some(&text));
+                    operations::update(operation, Some(fraction), text);
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    glib::timeout_future(std::time::Duration::from_millis(100)).await;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => break,
+            }
+        }
+
+        on_finished();
+
+        let failed = outcomes.iter().filter(|o| o.failed()).count();
+        let with_warnings = outcomes.iter().filter(|o| o.has_warnings()).count();
+        let valid = outcomes.len() - failed - with_warnings;
+
+        let mut summary = gettext_f(
+            "{valid} valid, {with_warnings} with warnings, {failed} failed",
+            &[
+                ("valid", &valid.to_string()),
+                ("with_warnings", &with_warnings.to_string()),
+                ("failed", &failed.to_string()),
+            ],
+        );
+        if outcomes.len() < total {
+            summary.push_str(&gettext_f(
+                " ({count} not verified)",
+                &[("count", &(total - outcomes.len()).to_string())],
+            ));
+        }
+        if stop.load(Ordering::SeqCst) {
+            operations::cancelled(operation);
+        } else if failed > 0 {
+            operations::fail(operation, summary.clone());
+        } else {
+            operations::succeed(operation, summary.clone());
+        }
+        status_label.set_text(&summary);
+        if failed > 0 {
+            status_label.add_css_class("error");
+        }
+        progress.set_visible(false);
+
+        // Failures first, then warnings, then the rest by name
+        outcomes.sort_by(|a, b| {
+            (!a.failed(), !a.has_warnings(), &a.snapshot_name)
+                .cmp(&(!b.failed(), !b.has_warnings(), &b.snapshot_name))
+        });
+        for outcome in &outcomes {
+            details.append(&create_outcome_row(outcome));
+        }
+        scrolled.set_visible(!outcomes.is_empty());
+    });
+
+    dialog.present();
+}
+
+fn create_outcome_row(outcome: &VerifyOutcome) -> adw::ExpanderRow {
+    let row = adw::ExpanderRow::new();
+    row.set_title(&outcome.snapshot_name);
+
+    let (icon_name, css_class, subtitle, lines): (&str, &str, String, Vec<String>) =
+        match &outcome.result {
+            Ok(v) if !v.is_valid => (
+                "dialog-error-symbolic",
+                "error",
+                ngettext_f(
+                    "{count} error",
+                    "{count} errors",
+                    v.errors.len() as u64,
+                    &[("count", &v.errors.len().to_string())],
+                ),
+                v.errors.iter().chain(&v.warnings).cloned().collect(),
+            ),
+            Ok(v) if !v.warnings.is_empty() => (
+                "dialog-warning-symbolic",
+                "warning",
+                ngettext_f(
+                    "Valid, {count} warning",
+                    "Valid, {count} warnings",
+                    v.warnings.len() as u64,
+                    &[("count", &v.warnings.len().to_string())],
+                ),
+                v.warnings.clone(),
+            ),
+            Ok(_) => (
+                "emblem-ok-symbolic",
+                "success",
+                gettext("Valid"),
+                Vec::new(),
+            ),
+            Err(e) => (
+                "dialog-error-symbolic",
+                "error",
+                gettext("Could not be verified"),
+                vec![e.clone()],
+            ),
+        };
+
+    let icon = gtk::Image::from_icon_name(icon_name);
+    icon.add_css_class(css_class);
+    row.add_prefix(&icon);
+    row.set_subtitle(&subtitle);
+
+    row.set_enable_expansion(!lines.is_empty());
+    for line in lines {
+        let label = gtk::Label::new(Some(&line));
+        label.set_wrap(true);
+        label.set_xalign(0.0);
+        label.set_selectable(true);
+        label.set_margin_top(6);
+        label.set_margin_bottom(6);
+        label.set_margin_start(12);
+        label.set_margin_end(12);
+        row.add_row(&label);
+    }
+
+    row
+}
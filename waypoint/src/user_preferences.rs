@@ -11,6 +11,7 @@ use std::fs;
 use std::fs::OpenOptions;
 use std::io::{Read, Write};
 use std::path::PathBuf;
+use waypoint_common::{BackupEligibility, SchedulesConfig};
 
 /// User preferences for a specific snapshot
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -22,6 +23,65 @@ pub struct SnapshotPreferences {
     /// User's personal note for this snapshot
     #[serde(default)]
     pub note: Option<String>,
+
+    /// Never back this snapshot up automatically
+    #[serde(default)]
+    pub exclude_from_backup: bool,
+
+    /// The last verification of this snapshot found errors
+    #[serde(default)]
+    pub verification_failed: bool,
+
+    /// Recent favorite and note changes, oldest first, for undoing them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<PreferenceChange>,
+}
+
+/// Number of changes kept per snapshot for undo
+const MAX_HISTORY: usize = 5;
+
+/// A change to a snapshot's preferences, holding the value it replaced
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum PreferenceChange {
+    Favorite { previous: bool },
+    Note { previous: Option<String> },
+}
+
+impl SnapshotPreferences {
+    /// Toggle the favorite flag, remembering the change for undo
+    fn toggle_favorite(&mut self) {
+        self.record(PreferenceChange::Favorite {
+            previous: self.is_favorite,
+        });
+        self.is_favorite = !self.is_favorite;
+    }
+
+    /// Replace the note, remembering the old one for undo if it changed
+    fn set_note(&mut self, note: Option<String>) {
+        if self.note == note {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.note, note);
+        self.record(PreferenceChange::Note { previous });
+    }
+
+    fn record(&mut self, change: PreferenceChange) {
+        self.history.push(change);
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+    }
+
+    /// Revert the most recent change, returning it
+    pub fn undo(&mut self) -> Option<PreferenceChange> {
+        let change = self.history.pop()?;
+        match &change {
+            PreferenceChange::Favorite { previous } => self.is_favorite = *previous,
+            PreferenceChange::Note { previous } => self.note = previous.clone(),
+        }
+        Some(change)
+    }
 }
 
 /// Manager for user-specific snapshot preferences
@@ -113,8 +173,14 @@ impl UserPreferencesManager {
     pub fn update(&self, snapshot_id: &str, preferences: SnapshotPreferences) -> Result<()> {
         let mut all_prefs = self.load()?;
 
-        // If preferences are default (not favorite, no note), remove the entry to keep file clean
-        if !preferences.is_favorite && preferences.note.is_none() {
+        // If preferences are default (not favorite, no note, not excluded, not failed,
+        // nothing to undo), remove the entry to keep file clean
+        if !preferences.is_favorite
+            && preferences.note.is_none()
+            && !preferences.exclude_from_backup
+            && !preferences.verification_failed
+            && preferences.history.is_empty()
+        {
             all_prefs.remove(snapshot_id);
         } else {
             all_prefs.insert(snapshot_id.to_string(), preferences);
@@ -126,19 +192,73 @@ impl UserPreferencesManager {
     /// Toggle favorite status for a snapshot
     pub fn toggle_favorite(&self, snapshot_id: &str) -> Result<bool> {
         let mut prefs = self.get(snapshot_id)?;
-        prefs.is_favorite = !prefs.is_favorite;
+        prefs.toggle_favorite();
         let new_state = prefs.is_favorite;
         self.update(snapshot_id, prefs)?;
         Ok(new_state)
     }
 
+    /// Toggle whether a snapshot is excluded from automatic backups
+    pub fn toggle_exclude_from_backup(&self, snapshot_id: &str) -> Result<bool> {
+        let mut prefs = self.get(snapshot_id)?;
+        prefs.exclude_from_backup = !prefs.exclude_from_backup;
+        let new_state = prefs.exclude_from_backup;
+        self.update(snapshot_id, prefs)?;
+        Ok(new_state)
+    }
+
+    /// Automatic backup eligibility of a snapshot
+    ///
+    /// Combines this user's favorite and exclusion flags with the auto-backup
+    /// setting of the schedule that created the snapshot. Preferences are
+    /// stored by snapshot id, while schedules are matched by snapshot name.
+    pub fn backup_eligibility(
+        &self,
+        snapshot_id: &str,
+        snapshot_name: &str,
+        schedules: &SchedulesConfig,
+    ) -> BackupEligibility {
+        let prefs = self.get(snapshot_id).unwrap_or_default();
+        BackupEligibility {
+            is_favorite: prefs.is_favorite,
+            excluded: prefs.exclude_from_backup,
+            schedule_allows_backup: schedules
+                .schedule_for_snapshot(snapshot_name)
+                .is_none_or(|schedule| schedule.auto_backup),
+        }
+    }
+
+    /// Record the outcome of verifying a snapshot
+    ///
+    /// A failure is kept until the snapshot verifies successfully again.
+    pub fn set_verification_failed(&self, snapshot_id: &str, failed: bool) -> Result<()> {
+        let mut prefs = self.get(snapshot_id)?;
+        if prefs.verification_failed == failed {
+            return Ok(());
+        }
+        prefs.verification_failed = failed;
+        self.update(snapshot_id, prefs)
+    }
+
     /// Update note for a snapshot
     pub fn update_note(&self, snapshot_id: &str, note: Option<String>) -> Result<()> {
         let mut prefs = self.get(snapshot_id)?;
-        prefs.note = note;
+        prefs.set_note(note);
         self.update(snapshot_id, prefs)
     }
 
+    /// Undo the last favorite or note change of a snapshot
+    ///
+    /// Returns the preferences after the undo, or `None` if there was nothing to undo.
+    pub fn undo_last_change(&self, snapshot_id: &str) -> Result<Option<SnapshotPreferences>> {
+        let mut prefs = self.get(snapshot_id)?;
+        if prefs.undo().is_none() {
+            return Ok(None);
+        }
+        self.update(snapshot_id, prefs.clone())?;
+        Ok(Some(prefs))
+    }
+
     fn locked_file(&self, write: bool) -> Result<std::fs::File> {
         let file = OpenOptions::new()
             .read(true)
@@ -157,3 +277,77 @@ impl UserPreferencesManager {
         Ok(file)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_restores_previous_values() {
+        let mut prefs = SnapshotPreferences::default();
+        prefs.set_note(Some("Before upgrade".to_string()));
+        prefs.toggle_favorite();
+        prefs.set_note(None);
+        // Saving an unchanged note is not a change
+        prefs.set_note(None);
+        assert_eq!(prefs.history.len(), 3);
+
+        assert_eq!(
+            prefs.undo(),
+            Some(PreferenceChange::Note {
+                previous: Some("Before upgrade".to_string())
+            })
+        );
+        assert_eq!(prefs.note.as_deref(), Some("Before upgrade"));
+        prefs.undo();
+        assert!(!prefs.is_favorite);
+        prefs.undo();
+        assert!(prefs.note.is_none());
+        assert!(prefs.undo().is_none());
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let mut prefs = SnapshotPreferences::default();
+        for _ in 0..MAX_HISTORY + 3 {
+            prefs.toggle_favorite();
+        }
+        assert_eq!(prefs.history.len(), MAX_HISTORY);
+
+        // Entries without history stay compact
+        let json = serde_json::to_string(&SnapshotPreferences::default()).unwrap();
+        assert!(!json.contains("history"));
+    }
+
+    #[test]
+    fn test_backup_eligibility_uses_id_and_name() {
+        let manager = UserPreferencesManager {
+            preferences_file: std::env::temp_dir().join(format!(
+                "waypoint-test-eligibility-{}.json",
+                std::process::id()
+            )),
+        };
+        let mut prefs = SnapshotPreferences::default();
+        prefs.exclude_from_backup = true;
+        manager.update("snapshot-id", prefs).unwrap();
+
+        let mut schedule = waypoint_common::Schedule::default_hourly();
+        schedule.auto_backup = false;
+        let schedules = SchedulesConfig {
+            schedules: vec![schedule],
+        };
+
+        // Preferences are found by id, the schedule by name
+        let eligibility =
+            manager.backup_eligibility("snapshot-id", "hourly-20250101-1200", &schedules);
+        assert!(eligibility.excluded);
+        assert!(!eligibility.schedule_allows_backup);
+
+        let eligibility =
+            manager.backup_eligibility("hourly-20250101-1200", "snapshot-id", &schedules);
+        assert!(!eligibility.excluded);
+        assert!(eligibility.schedule_allows_backup);
+
+        let _ = fs::remove_file(&manager.preferences_file);
+    }
+}